@@ -63,6 +63,10 @@ pub fn output_body_strategy(headers: &HeaderMap, strategy: BodyStrategy) -> Opti
 }
 
 pub fn convert_response_value(response: &mut ApiResponse, strategy: BodyStrategy) -> Result<()> {
+	// Files are streamed from their bucket as they are sent
+	if let PublicValue::File(_) = response.body {
+		return Ok(());
+	}
 	match strategy {
 		BodyStrategy::Auto | BodyStrategy::Json => {
 			response.body = PublicValue::Bytes(PublicBytes::from(
@@ -1,12 +1,19 @@
 use core::fmt;
 use std::sync::Arc;
+use std::sync::atomic::Ordering;
 
 use anyhow::{Result, bail, ensure};
+use futures::StreamExt;
 use reblessive::tree::Stk;
+use uuid::Uuid;
 
-use super::store::{ListOptions, ObjectKey, ObjectMeta, ObjectStore};
+use super::manager::{BucketsManager, UsageDelta};
+use super::store::{
+	ByteStream, Conditional, GetOptions, ListOptions, ObjectKey, ObjectMeta, ObjectRange,
+	ObjectStore, PreconditionFailure, PutOptions,
+};
 use crate::catalog::providers::BucketProvider;
-use crate::catalog::{BucketDefinition, Permission};
+use crate::catalog::{BucketDefinition, DatabaseId, NamespaceId, Permission};
 use crate::ctx::{Context, FrozenContext};
 use crate::dbs::Options;
 use crate::doc::CursorDoc;
@@ -22,6 +29,55 @@ fn accept_payload(value: Value) -> Result<bytes::Bytes> {
 	value.cast_to::<Bytes>().map(|x| x.0).map_err(err::Error::from).map_err(anyhow::Error::new)
 }
 
+/// A streamed write which has been checked by a [`BucketController`].
+///
+/// The usage of the file has already been reserved, and is released again
+/// if the write fails.
+pub(crate) struct StreamWrite {
+	ns: NamespaceId,
+	db: DatabaseId,
+	bucket: Arc<BucketDefinition>,
+	store: Arc<dyn ObjectStore>,
+	key: ObjectKey,
+	size: Option<u64>,
+	delta: Option<UsageDelta>,
+}
+
+impl StreamWrite {
+	/// Writes the stream to the store, failing if the stream yields a
+	/// different number of bytes than the size which was checked.
+	pub(crate) async fn write(self, buckets: &BucketsManager, data: ByteStream) -> Result<()> {
+		let data = match self.size {
+			Some(size) => exact_size(data, size),
+			None => data,
+		};
+
+		if let Err(e) = self.store.put_stream(&self.key, data).await {
+			buckets.release_usage(self.ns, self.db, &self.bucket, &*self.store, self.delta).await;
+			bail!(err::Error::ObjectStoreFailure(self.bucket.name.clone(), e));
+		}
+
+		Ok(())
+	}
+}
+
+/// Wraps a stream so that it fails unless it yields exactly `size` bytes.
+fn exact_size(data: ByteStream, size: u64) -> ByteStream {
+	Box::pin(futures::stream::try_unfold((data, size), |(mut data, remaining)| async move {
+		match data.next().await {
+			Some(chunk) => {
+				let chunk = chunk?;
+				let Some(remaining) = remaining.checked_sub(chunk.len() as u64) else {
+					return Err("The data is larger than its announced size".to_string());
+				};
+				Ok(Some((chunk, (data, remaining))))
+			}
+			None if remaining > 0 => Err("The data is smaller than its announced size".to_string()),
+			None => Ok(None),
+		}
+	}))
+}
+
 /// Allows you to control a specific bucket in the context of the current user
 pub(crate) struct BucketController<'a> {
 	stk: &'a mut Stk,
@@ -29,6 +85,8 @@ pub(crate) struct BucketController<'a> {
 	opt: &'a Options,
 	doc: Option<&'a CursorDoc>,
 
+	ns: NamespaceId,
+	db: DatabaseId,
 	bucket: Arc<BucketDefinition>,
 	store: Arc<dyn ObjectStore>,
 }
@@ -65,11 +123,21 @@ impl<'a> BucketController<'a> {
 			opt,
 			doc,
 
+			ns,
+			db,
 			bucket,
 			store,
 		})
 	}
 
+	/// Fetches the buckets manager which tracks in-progress multipart uploads
+	fn buckets(&self) -> Result<&'a BucketsManager> {
+		match self.ctx.get_buckets() {
+			Some(buckets) => Ok(buckets),
+			None => bail!(err::Error::BucketUnavailable(self.bucket.name.clone())),
+		}
+	}
+
 	/// Checks if the bucket allows writes, and if not, return an
 	/// `Error::ReadonlyBucket`
 	fn require_writeable(&self) -> Result<()> {
@@ -77,6 +145,86 @@ impl<'a> BucketController<'a> {
 		Ok(())
 	}
 
+	/// Checks that writing `size` bytes to `key` would not exceed the quota
+	/// of the bucket, without reserving the usage of the file.
+	async fn check_quota(&self, key: &ObjectKey, size: u64) -> Result<()> {
+		self.buckets()?.check_quota(self.ns, self.db, &self.bucket, &*self.store, key, size).await
+	}
+
+	/// Reserves the usage of writing `size` bytes to `key`, failing if this
+	/// would exceed the quota of the bucket.
+	async fn reserve_quota(
+		&self,
+		key: &ObjectKey,
+		size: u64,
+		overwrite: bool,
+	) -> Result<Option<UsageDelta>> {
+		self.buckets()?
+			.reserve_quota(self.ns, self.db, &self.bucket, &*self.store, key, size, overwrite)
+			.await
+	}
+
+	/// Reserves the usage of copying `key` to `target`, failing if this would
+	/// exceed the quota of the bucket. Returns the reserved change in usage,
+	/// together with the size of the file being copied.
+	async fn reserve_copy_quota(
+		&self,
+		key: &ObjectKey,
+		target: &ObjectKey,
+		overwrite: bool,
+	) -> Result<(Option<UsageDelta>, u64)> {
+		if !self.bucket.has_quota() {
+			return Ok((None, 0));
+		}
+		// A missing source file is reported by the copy itself
+		let Some(meta) = self
+			.store
+			.head(key)
+			.await
+			.map_err(|e| err::Error::ObjectStoreFailure(self.bucket.name.clone(), e))?
+		else {
+			return Ok((None, 0));
+		};
+		Ok((self.reserve_quota(target, meta.size, overwrite).await?, meta.size))
+	}
+
+	/// Returns the change in usage which results from deleting `key`.
+	async fn release_quota(&self, key: &ObjectKey) -> Result<Option<UsageDelta>> {
+		self.buckets()?.release_quota(&self.bucket, &*self.store, key).await
+	}
+
+	/// Applies a change in usage once a delete has succeeded.
+	async fn apply_usage(&self, delta: Option<UsageDelta>) -> Result<()> {
+		self.buckets()?.apply_usage(self.ns, self.db, &self.bucket, &*self.store, delta).await
+	}
+
+	/// Settles the usage reserved for a write which does not overwrite an
+	/// existing file, once it is known whether the file was written.
+	async fn settle_usage(
+		&self,
+		reserved: Option<UsageDelta>,
+		size: u64,
+		written: bool,
+	) -> Result<()> {
+		self.buckets()?
+			.settle_usage(self.ns, self.db, &self.bucket, &*self.store, reserved, size, written)
+			.await
+	}
+
+	/// Returns the result of a write, releasing the reserved usage if the
+	/// write has failed.
+	async fn written<T>(&self, reserved: Option<UsageDelta>, res: Result<T, String>) -> Result<T> {
+		match res {
+			Ok(v) => Ok(v),
+			Err(e) => {
+				self.buckets()?
+					.release_usage(self.ns, self.db, &self.bucket, &*self.store, reserved)
+					.await;
+				bail!(err::Error::ObjectStoreFailure(self.bucket.name.clone(), e))
+			}
+		}
+	}
+
 	/// Attempt to put a file
 	/// `Bytes` and `String` values are supported, and will be converted into
 	/// `Bytes` Create or update permissions will be used, based on if the
@@ -85,11 +233,33 @@ impl<'a> BucketController<'a> {
 		let payload = accept_payload(value)?;
 		self.require_writeable()?;
 		self.check_permission(BucketOperation::Put, Some(key), None).await?;
+		let delta = self.reserve_quota(key, payload.len() as u64, true).await?;
 
-		self.store
-			.put(key, payload)
-			.await
-			.map_err(|e| err::Error::ObjectStoreFailure(self.bucket.name.clone(), e))?;
+		let res = self.store.put(key, payload).await;
+		self.written(delta, res).await
+	}
+
+	/// Stores data at the specified key with the given content type and metadata.
+	///
+	/// Returns an error if the preconditions on the existing object are not satisfied.
+	///
+	/// `Bytes` and `String` values are supported and will be converted into bytes.
+	pub(crate) async fn put_opts(
+		&mut self,
+		key: &ObjectKey,
+		value: Value,
+		opts: PutOptions,
+	) -> Result<()> {
+		let payload = accept_payload(value)?;
+		self.require_writeable()?;
+		self.check_permission(BucketOperation::Put, Some(key), None).await?;
+		let delta = self.reserve_quota(key, payload.len() as u64, true).await?;
+
+		let res = self.store.put_opts(key, payload, opts).await;
+		if let Conditional::Skipped(_) = self.written(delta, res).await? {
+			self.settle_usage(delta, 0, false).await?;
+			bail!(err::Error::ObjectPreconditionFailed(self.bucket.name.clone(), key.to_string()));
+		}
 
 		Ok(())
 	}
@@ -104,13 +274,12 @@ impl<'a> BucketController<'a> {
 		let payload = accept_payload(value)?;
 		self.require_writeable()?;
 		self.check_permission(BucketOperation::Put, Some(key), None).await?;
+		let size = payload.len() as u64;
+		let delta = self.reserve_quota(key, size, false).await?;
 
-		self.store
-			.put_if_not_exists(key, payload)
-			.await
-			.map_err(|e| err::Error::ObjectStoreFailure(self.bucket.name.clone(), e))?;
-
-		Ok(())
+		let res = self.store.put_if_not_exists(key, payload).await;
+		let written = self.written(delta, res).await?;
+		self.settle_usage(delta, size, written).await
 	}
 
 	/// Retrieves metadata for an object without fetching its contents.
@@ -145,19 +314,212 @@ impl<'a> BucketController<'a> {
 		Ok(Some(Bytes(bytes)))
 	}
 
+	/// Retrieves data from the specified key, subject to the given range and preconditions.
+	///
+	/// The range is clamped to the size of the object. Returns `None` if the
+	/// object does not exist, or if it matches the `if_none_match` precondition.
+	pub(crate) async fn get_opts(
+		&mut self,
+		key: &ObjectKey,
+		opts: GetOptions,
+	) -> Result<Option<Bytes>> {
+		self.check_permission(BucketOperation::Get, Some(key), None).await?;
+
+		let res = self
+			.store
+			.get_opts(key, opts)
+			.await
+			.map_err(|e| err::Error::ObjectStoreFailure(self.bucket.name.clone(), e))?;
+
+		match res {
+			Conditional::Applied(bytes) => Ok(bytes.map(Bytes)),
+			Conditional::Skipped(PreconditionFailure::NotModified) => Ok(None),
+			Conditional::Skipped(PreconditionFailure::Failed) => {
+				bail!(err::Error::ObjectPreconditionFailed(
+					self.bucket.name.clone(),
+					key.to_string()
+				))
+			}
+		}
+	}
+
+	/// Retrieves the metadata of an object together with a stream of its
+	/// contents, without loading the whole object into memory.
+	///
+	/// If a range is specified, only the bytes within the range are streamed.
+	/// Returns `None` if the object does not exist.
+	pub(crate) async fn get_stream(
+		&mut self,
+		key: &ObjectKey,
+		range: Option<ObjectRange>,
+	) -> Result<Option<(ObjectMeta, ByteStream)>> {
+		self.check_permission(BucketOperation::Get, Some(key), None).await?;
+
+		let fail = |e| err::Error::ObjectStoreFailure(self.bucket.name.clone(), e);
+		let Some(meta) = self.store.head(key).await.map_err(fail)? else {
+			return Ok(None);
+		};
+		let Some(stream) = self.store.get_stream(key, range).await.map_err(fail)? else {
+			return Ok(None);
+		};
+
+		Ok(Some((meta, stream)))
+	}
+
+	/// Checks that a stream of `size` bytes may be written to the specified
+	/// key, returning a [`StreamWrite`] which performs the write.
+	///
+	/// The write itself does not need the transaction of this controller, so
+	/// that no transaction is held open while the data is being received.
+	/// Buckets with a quota require the size of the data to be known upfront,
+	/// and the usage of the file is reserved before the returned write is
+	/// performed.
+	pub(crate) async fn put_stream(
+		&mut self,
+		key: &ObjectKey,
+		size: Option<u64>,
+	) -> Result<StreamWrite> {
+		self.require_writeable()?;
+		self.check_permission(BucketOperation::Put, Some(key), None).await?;
+
+		let delta = match size {
+			Some(size) => self.reserve_quota(key, size, true).await?,
+			None if self.bucket.has_quota() => {
+				bail!(err::Error::BucketUnknownSize(self.bucket.name.clone(), key.to_string()))
+			}
+			None => None,
+		};
+
+		Ok(StreamWrite {
+			ns: self.ns,
+			db: self.db,
+			bucket: self.bucket.clone(),
+			store: self.store.clone(),
+			key: key.clone(),
+			size,
+			delta,
+		})
+	}
+
+	/// Starts a multipart upload to the specified key, returning the id of the upload.
+	///
+	/// The object only becomes visible once the upload has been completed.
+	pub(crate) async fn create_upload(&mut self, key: &ObjectKey) -> Result<Uuid> {
+		self.require_writeable()?;
+		self.check_permission(BucketOperation::Put, Some(key), None).await?;
+
+		let upload = self
+			.store
+			.put_multipart(key)
+			.await
+			.map_err(|e| err::Error::ObjectStoreFailure(self.bucket.name.clone(), e))?;
+
+		let id = self
+			.buckets()?
+			.create_upload(self.ns, self.db, &self.bucket.name, key.clone(), upload)
+			.await;
+
+		Ok(id)
+	}
+
+	/// Appends a chunk of data to an in-progress multipart upload.
+	///
+	/// `Bytes` and `String` values are supported and will be converted into bytes.
+	pub(crate) async fn put_upload_part(
+		&mut self,
+		key: &ObjectKey,
+		id: Uuid,
+		value: Value,
+	) -> Result<()> {
+		let payload = accept_payload(value)?;
+		self.require_writeable()?;
+		self.check_permission(BucketOperation::Put, Some(key), None).await?;
+
+		let pending = self.buckets()?.get_upload(self.ns, self.db, &self.bucket.name, key, id)?;
+
+		let mut upload = pending.upload.lock().await;
+		// Fail early if the completed file would exceed the quota
+		let size = pending.uploaded.load(Ordering::Acquire) + payload.len() as u64;
+		self.check_quota(key, size).await?;
+
+		let len = payload.len() as u64;
+		upload
+			.put_part(payload)
+			.await
+			.map_err(|e| err::Error::ObjectStoreFailure(self.bucket.name.clone(), e))?;
+		pending.uploaded.fetch_add(len, Ordering::AcqRel);
+
+		Ok(())
+	}
+
+	/// Completes an in-progress multipart upload, making the object visible.
+	pub(crate) async fn complete_upload(&mut self, key: &ObjectKey, id: Uuid) -> Result<()> {
+		self.require_writeable()?;
+		self.check_permission(BucketOperation::Put, Some(key), None).await?;
+
+		let buckets = self.buckets()?;
+		let pending = buckets.get_upload(self.ns, self.db, &self.bucket.name, key, id)?;
+		// The upload can not be resumed once completion has been attempted
+		buckets.remove_upload(id);
+
+		let mut upload = pending.upload.lock().await;
+		let size = pending.uploaded.load(Ordering::Acquire);
+		let delta = match self.reserve_quota(key, size, true).await {
+			Ok(delta) => delta,
+			Err(e) => {
+				// Discard the uploaded data, as the upload can not be resumed
+				if let Err(e) = upload.abort().await {
+					warn!("Failed to abort multipart upload {id}: {e}");
+				}
+				return Err(e);
+			}
+		};
+
+		let res = upload.complete().await;
+		if res.is_err() {
+			// Discard the uploaded data, as the upload can not be resumed
+			if let Err(e) = upload.abort().await {
+				warn!("Failed to abort multipart upload {id}: {e}");
+			}
+		}
+
+		self.written(delta, res).await
+	}
+
+	/// Aborts an in-progress multipart upload, discarding any uploaded data.
+	pub(crate) async fn abort_upload(&mut self, key: &ObjectKey, id: Uuid) -> Result<()> {
+		self.require_writeable()?;
+		self.check_permission(BucketOperation::Put, Some(key), None).await?;
+
+		let buckets = self.buckets()?;
+		let pending = buckets.get_upload(self.ns, self.db, &self.bucket.name, key, id)?;
+		buckets.remove_upload(id);
+
+		pending
+			.upload
+			.lock()
+			.await
+			.abort()
+			.await
+			.map_err(|e| err::Error::ObjectStoreFailure(self.bucket.name.clone(), e))?;
+
+		Ok(())
+	}
+
 	/// Deletes an object from the bucket.
 	///
 	/// This operation is idempotent - deleting a non-existent object is not an error.
 	pub(crate) async fn delete(&mut self, key: &ObjectKey) -> Result<()> {
 		self.require_writeable()?;
 		self.check_permission(BucketOperation::Delete, Some(key), None).await?;
+		let delta = self.release_quota(key).await?;
 
 		self.store
 			.delete(key)
 			.await
 			.map_err(|e| err::Error::ObjectStoreFailure(self.bucket.name.clone(), e))?;
 
-		Ok(())
+		self.apply_usage(delta).await
 	}
 
 	/// Copies an object to a new location within the bucket.
@@ -166,13 +528,10 @@ impl<'a> BucketController<'a> {
 	pub(crate) async fn copy(&mut self, key: &ObjectKey, target: ObjectKey) -> Result<()> {
 		self.require_writeable()?;
 		self.check_permission(BucketOperation::Copy, Some(key), Some(&target)).await?;
+		let (delta, _) = self.reserve_copy_quota(key, &target, true).await?;
 
-		self.store
-			.copy(key, &target)
-			.await
-			.map_err(|e| err::Error::ObjectStoreFailure(self.bucket.name.clone(), e))?;
-
-		Ok(())
+		let res = self.store.copy(key, &target).await;
+		self.written(delta, res).await
 	}
 
 	/// Copies an object to a new location only if the target does not already exist.
@@ -185,13 +544,11 @@ impl<'a> BucketController<'a> {
 	) -> Result<()> {
 		self.require_writeable()?;
 		self.check_permission(BucketOperation::Copy, Some(key), Some(&target)).await?;
+		let (delta, size) = self.reserve_copy_quota(key, &target, false).await?;
 
-		self.store
-			.copy_if_not_exists(key, &target)
-			.await
-			.map_err(|e| err::Error::ObjectStoreFailure(self.bucket.name.clone(), e))?;
-
-		Ok(())
+		let res = self.store.copy_if_not_exists(key, &target).await;
+		let written = self.written(delta, res).await?;
+		self.settle_usage(delta, size, written).await
 	}
 
 	/// Moves an object to a new location within the bucket.
@@ -201,13 +558,19 @@ impl<'a> BucketController<'a> {
 	pub(crate) async fn rename(&mut self, key: &ObjectKey, target: ObjectKey) -> Result<()> {
 		self.require_writeable()?;
 		self.check_permission(BucketOperation::Rename, Some(key), Some(&target)).await?;
+		// Any file which is overwritten by the rename is released
+		let delta = if *key == target {
+			None
+		} else {
+			self.release_quota(&target).await?
+		};
 
 		self.store
 			.rename(key, &target)
 			.await
 			.map_err(|e| err::Error::ObjectStoreFailure(self.bucket.name.clone(), e))?;
 
-		Ok(())
+		self.apply_usage(delta).await
 	}
 
 	/// Moves an object to a new location only if the target does not already exist.
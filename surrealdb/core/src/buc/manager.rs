@@ -1,34 +1,64 @@
 use std::sync::Arc;
+use std::sync::atomic::AtomicU64;
+use std::time::Duration;
 
-use anyhow::{Result, bail};
+use anyhow::{Result, bail, ensure};
+use chrono::Utc;
 use dashmap::DashMap;
 use dashmap::mapref::entry::Entry;
+use rand::{Rng, thread_rng};
+use revision::revisioned;
+use tokio::sync::Mutex;
+use uuid::Uuid;
+use web_time::Instant;
 
 use crate::buc::BucketStoreProvider;
 use crate::buc::store::prefixed::PrefixedStore;
-use crate::buc::store::{ObjectKey, ObjectStore};
+use crate::buc::store::{ListOptions, MultipartUpload, ObjectKey, ObjectStore};
 use crate::catalog::providers::BucketProvider;
-use crate::catalog::{DatabaseId, NamespaceId};
-use crate::cnf::{GLOBAL_BUCKET, GLOBAL_BUCKET_ENFORCED};
+use crate::catalog::{BucketDefinition, DatabaseId, NamespaceId};
+use crate::cnf::{
+	BUCKET_LIST_BATCH_SIZE, BUCKET_UPLOAD_TIMEOUT_SECS, GLOBAL_BUCKET, GLOBAL_BUCKET_ENFORCED,
+};
 use crate::err::Error;
-use crate::kvs::Transaction;
+use crate::expr::statements::info::InfoStructure;
+use crate::kvs::LockType::Optimistic;
+use crate::kvs::TransactionType::{self, Read, Write};
+use crate::kvs::sequences::Sequences;
+use crate::kvs::{Transaction, TransactionFactory, impl_kv_value_revisioned};
+use crate::val::Value;
 
 /// Type alias for the concurrent map of bucket connections.
 type BucketConnections = Arc<DashMap<BucketConnectionKey, Arc<dyn ObjectStore>>>;
 
+/// Type alias for the concurrent map of in-progress multipart uploads.
+type PendingUploads = Arc<DashMap<Uuid, Arc<PendingUpload>>>;
+
+/// The number of times a change in usage is attempted when it conflicts
+/// with a concurrent change to the usage of the same bucket.
+const USAGE_ATTEMPTS: u32 = 16;
+
 /// Manages bucket storage connections with caching.
 ///
 /// The `BucketsManager` is responsible for:
 /// - Creating and caching connections to bucket storage backends
 /// - Managing global bucket connections with automatic namespacing
 /// - Enforcing global bucket policies when configured
+/// - Tracking the usage of buckets which have a size or file count quota
 ///
 /// Connections are cached by namespace, database, and bucket name to avoid
 /// redundant connection establishment.
+///
+/// The usage of a bucket with a quota is stored in the datastore, and is
+/// changed in a transaction of its own, so that the quota is enforced across
+/// a cluster. The usage is reserved before a file is written, and released
+/// again if the write fails or does not take place.
 #[derive(Clone)]
 pub(crate) struct BucketsManager {
 	buckets: BucketConnections,
+	uploads: PendingUploads,
 	provider: Arc<dyn BucketStoreProvider>,
+	transactions: Option<(TransactionFactory, Sequences)>,
 }
 
 impl BucketsManager {
@@ -39,15 +69,34 @@ impl BucketsManager {
 	pub(crate) fn new(provider: Arc<dyn BucketStoreProvider>) -> Self {
 		Self {
 			buckets: Default::default(),
+			uploads: Default::default(),
 			provider,
+			transactions: None,
 		}
 	}
 
+	/// Sets the factory used to create the transactions in which the usage
+	/// of buckets with a quota is changed.
+	pub(crate) fn with_transactions(mut self, tf: TransactionFactory, sqs: Sequences) -> Self {
+		self.transactions = Some((tf, sqs));
+		self
+	}
+
+	/// Creates a new transaction in which the usage of a bucket is read or changed.
+	async fn transaction(&self, write: TransactionType, bu: &str) -> Result<Transaction> {
+		let Some((tf, sqs)) = &self.transactions else {
+			bail!(Error::BucketUnavailable(bu.to_string()));
+		};
+		tf.transaction(write, Optimistic, sqs.clone()).await
+	}
+
 	/// Clears all cached bucket connections.
 	///
 	/// This is typically called during datastore restart to ensure fresh connections.
+	/// Any in-progress multipart uploads are discarded without being completed.
 	pub(crate) fn clear(&self) {
 		self.buckets.clear();
+		self.uploads.clear();
 	}
 
 	/// Connects to a bucket storage backend.
@@ -160,6 +209,528 @@ impl BucketsManager {
 		self.buckets.insert(key, store);
 		Ok(())
 	}
+
+	/// Fetches the current usage of a bucket.
+	///
+	/// The usage of buckets with a quota is stored in the datastore, once it
+	/// has first been computed by a write to the bucket. The usage of buckets
+	/// without a quota, or which have not yet been written to, is computed by
+	/// listing the files in the bucket.
+	///
+	/// The usage is read in a transaction of its own, as it is changed
+	/// independently of the transaction in which the files were written.
+	pub(crate) async fn usage(
+		&self,
+		ns: NamespaceId,
+		db: DatabaseId,
+		bucket: &BucketDefinition,
+		store: &dyn ObjectStore,
+	) -> Result<BucketUsage> {
+		if !bucket.has_quota() {
+			return self.stored_usage(None, ns, db, bucket, store).await;
+		}
+		let tx = self.transaction(Read, &bucket.name).await?;
+		let usage = self.stored_usage(Some(&tx), ns, db, bucket, store).await;
+		tx.cancel().await?;
+		usage
+	}
+
+	/// Fetches the usage of a bucket from the datastore, computing it from
+	/// the files in the bucket if it is not stored.
+	async fn stored_usage(
+		&self,
+		tx: Option<&Transaction>,
+		ns: NamespaceId,
+		db: DatabaseId,
+		bucket: &BucketDefinition,
+		store: &dyn ObjectStore,
+	) -> Result<BucketUsage> {
+		if let Some(tx) = tx
+			&& let Some(usage) =
+				tx.get(&crate::key::database::bq::new(ns, db, &bucket.name), None).await?
+		{
+			return Ok(usage);
+		}
+		BucketUsage::compute(store)
+			.await
+			.map_err(|e| Error::ObjectStoreFailure(bucket.name.clone(), e).into())
+	}
+
+	/// Returns the change in usage which results from writing a file of
+	/// `size` bytes to `key`, or `None` if the bucket has no quota.
+	///
+	/// When `overwrite` is false, and the file already exists, the write will
+	/// not take place, so no change in usage is returned.
+	async fn write_delta(
+		&self,
+		bucket: &BucketDefinition,
+		store: &dyn ObjectStore,
+		key: &ObjectKey,
+		size: u64,
+		overwrite: bool,
+	) -> Result<Option<UsageDelta>> {
+		// Buckets without a quota are not tracked
+		if !bucket.has_quota() {
+			return Ok(None);
+		}
+		// Fetch the size of the file being replaced
+		let existing = store
+			.head(key)
+			.await
+			.map_err(|e| Error::ObjectStoreFailure(bucket.name.clone(), e))?
+			.map(|meta| meta.size);
+		Ok(match existing {
+			Some(_) if !overwrite => None,
+			Some(existing) => Some(UsageDelta {
+				size: size as i64 - existing as i64,
+				count: 0,
+			}),
+			None => Some(UsageDelta::file(size)),
+		})
+	}
+
+	/// Checks that writing a file of `size` bytes to `key` would not exceed
+	/// the quota of the bucket, without reserving the usage of the file.
+	///
+	/// This is used to fail early while data is still being received, as the
+	/// usage is only reserved once the size of the whole file is known.
+	pub(crate) async fn check_quota(
+		&self,
+		ns: NamespaceId,
+		db: DatabaseId,
+		bucket: &BucketDefinition,
+		store: &dyn ObjectStore,
+		key: &ObjectKey,
+		size: u64,
+	) -> Result<()> {
+		let Some(delta) = self.write_delta(bucket, store, key, size, true).await? else {
+			return Ok(());
+		};
+		self.usage(ns, db, bucket, store).await?.check(bucket, key, delta)
+	}
+
+	/// Reserves the usage of a file of `size` bytes which is about to be
+	/// written to `key`, failing if the quota of the bucket would be exceeded.
+	///
+	/// The quota is checked and the usage changed in a single transaction,
+	/// so concurrent writes, on any node, can not together exceed the quota.
+	/// The returned change in usage must be released with
+	/// [`release_usage`](Self::release_usage) if the write then fails, or if
+	/// it does not take place. Writes which do not increase the usage of the
+	/// bucket are always allowed, so that files can still be replaced after
+	/// a quota has been lowered.
+	///
+	/// When `overwrite` is false, and the file already exists, nothing is
+	/// reserved, and the write must be settled with
+	/// [`settle_usage`](Self::settle_usage) once it is known to have happened.
+	#[allow(clippy::too_many_arguments)]
+	pub(crate) async fn reserve_quota(
+		&self,
+		ns: NamespaceId,
+		db: DatabaseId,
+		bucket: &BucketDefinition,
+		store: &dyn ObjectStore,
+		key: &ObjectKey,
+		size: u64,
+		overwrite: bool,
+	) -> Result<Option<UsageDelta>> {
+		let Some(delta) = self.write_delta(bucket, store, key, size, overwrite).await? else {
+			return Ok(None);
+		};
+		self.change_usage(ns, db, bucket, store, |usage| {
+			usage.check(bucket, key, delta).map(|_| delta)
+		})
+		.await?;
+		Ok(Some(delta))
+	}
+
+	/// Returns the change in usage which results from deleting the file at `key`.
+	pub(crate) async fn release_quota(
+		&self,
+		bucket: &BucketDefinition,
+		store: &dyn ObjectStore,
+		key: &ObjectKey,
+	) -> Result<Option<UsageDelta>> {
+		// Buckets without a quota are not tracked
+		if !bucket.has_quota() {
+			return Ok(None);
+		}
+		let existing =
+			store.head(key).await.map_err(|e| Error::ObjectStoreFailure(bucket.name.clone(), e))?;
+		Ok(existing.map(|meta| UsageDelta {
+			size: -(meta.size as i64),
+			count: -1,
+		}))
+	}
+
+	/// Applies a change in usage to a bucket, once a delete has succeeded.
+	pub(crate) async fn apply_usage(
+		&self,
+		ns: NamespaceId,
+		db: DatabaseId,
+		bucket: &BucketDefinition,
+		store: &dyn ObjectStore,
+		delta: Option<UsageDelta>,
+	) -> Result<()> {
+		match delta {
+			Some(delta) => self.change_usage(ns, db, bucket, store, |_| Ok(delta)).await,
+			None => Ok(()),
+		}
+	}
+
+	/// Releases the usage which was reserved for a write which has failed, or
+	/// which did not take place.
+	///
+	/// A failure to release the usage is logged rather than returned, so that
+	/// the error of the write itself is reported.
+	pub(crate) async fn release_usage(
+		&self,
+		ns: NamespaceId,
+		db: DatabaseId,
+		bucket: &BucketDefinition,
+		store: &dyn ObjectStore,
+		delta: Option<UsageDelta>,
+	) {
+		if let Some(delta) = delta
+			&& let Err(e) = self.change_usage(ns, db, bucket, store, |_| Ok(delta.negate())).await
+		{
+			warn!("Failed to release the usage of bucket {}: {e}", bucket.name);
+		}
+	}
+
+	/// Settles the usage reserved for a write which does not overwrite an
+	/// existing file, once it is known whether the file of `size` bytes was
+	/// written.
+	#[allow(clippy::too_many_arguments)]
+	pub(crate) async fn settle_usage(
+		&self,
+		ns: NamespaceId,
+		db: DatabaseId,
+		bucket: &BucketDefinition,
+		store: &dyn ObjectStore,
+		reserved: Option<UsageDelta>,
+		size: u64,
+		written: bool,
+	) -> Result<()> {
+		match (reserved, written) {
+			// The file already existed, so the reserved usage is released
+			(Some(_), false) => {
+				self.release_usage(ns, db, bucket, store, reserved).await;
+				Ok(())
+			}
+			// The file was deleted after the quota was checked
+			(None, true) if bucket.has_quota() => {
+				self.apply_usage(ns, db, bucket, store, Some(UsageDelta::file(size))).await
+			}
+			_ => Ok(()),
+		}
+	}
+
+	/// Changes the stored usage of a bucket in a transaction of its own,
+	/// retrying when the transaction conflicts with a concurrent change.
+	///
+	/// The change is computed by `change` from the current usage of the
+	/// bucket, which is computed from the backend if it is not yet stored.
+	async fn change_usage<F>(
+		&self,
+		ns: NamespaceId,
+		db: DatabaseId,
+		bucket: &BucketDefinition,
+		store: &dyn ObjectStore,
+		change: F,
+	) -> Result<()>
+	where
+		F: Fn(BucketUsage) -> Result<UsageDelta>,
+	{
+		let key = crate::key::database::bq::new(ns, db, &bucket.name);
+		let mut attempt = 0;
+		loop {
+			attempt += 1;
+			let tx = self.transaction(Write, &bucket.name).await?;
+			let res = async {
+				let mut usage = self.stored_usage(Some(&tx), ns, db, bucket, store).await?;
+				usage.apply(change(usage)?);
+				tx.set(&key, &usage, None).await
+			}
+			.await;
+			let res = match res {
+				Ok(()) => tx.commit().await,
+				Err(e) => {
+					tx.cancel().await?;
+					return Err(e);
+				}
+			};
+			match res {
+				Ok(()) => return Ok(()),
+				// Retry with a jittered backoff if a concurrent change conflicted
+				Err(e) if attempt < USAGE_ATTEMPTS && is_retryable(&e) => {
+					let sleep_ms = thread_rng().gen_range(1..=(1u64 << attempt.min(8)));
+					tokio::time::sleep(Duration::from_millis(sleep_ms)).await;
+				}
+				Err(e) => return Err(e),
+			}
+		}
+	}
+
+	/// Deletes any files which have expired according to the lifecycle rules
+	/// of a bucket, returning the number of files which were deleted.
+	///
+	/// The usage of the bucket is reduced by the deleted files after each
+	/// batch, including when a delete fails part way through a batch.
+	pub(crate) async fn expire_files(
+		&self,
+		ns: NamespaceId,
+		db: DatabaseId,
+		bucket: &BucketDefinition,
+		store: &dyn ObjectStore,
+	) -> Result<usize> {
+		let now = Utc::now();
+		let mut deleted = 0;
+		for rule in &bucket.lifecycle {
+			// Ignore any rules with an unrepresentable expiry
+			let Some(cutoff) = chrono::Duration::from_std(rule.expiry)
+				.ok()
+				.and_then(|x| now.checked_sub_signed(x))
+			else {
+				continue;
+			};
+			let mut opts = ListOptions {
+				prefix: Some(ObjectKey::new(rule.prefix.clone())),
+				limit: Some(*BUCKET_LIST_BATCH_SIZE),
+				..Default::default()
+			};
+			loop {
+				let batch = store
+					.list(&opts)
+					.await
+					.map_err(|e| Error::ObjectStoreFailure(bucket.name.clone(), e))?;
+				let mut released = UsageDelta::default();
+				let res = async {
+					for meta in batch.iter().filter(|meta| meta.updated < cutoff) {
+						store
+							.delete(&meta.key)
+							.await
+							.map_err(|e| Error::ObjectStoreFailure(bucket.name.clone(), e))?;
+						deleted += 1;
+						released.size -= meta.size as i64;
+						released.count -= 1;
+					}
+					Ok::<_, Error>(())
+				}
+				.await;
+				// Release the usage of the deleted files
+				if released.count < 0 && bucket.has_quota() {
+					self.apply_usage(ns, db, bucket, store, Some(released)).await?;
+				}
+				res?;
+				match batch.last() {
+					Some(last) if batch.len() >= *BUCKET_LIST_BATCH_SIZE => {
+						opts.start = Some(last.key.clone());
+					}
+					_ => break,
+				}
+			}
+		}
+		Ok(deleted)
+	}
+
+	/// Registers a new multipart upload, returning its unique identifier.
+	///
+	/// Any uploads which have not been completed within the configured upload
+	/// timeout are aborted before the new upload is registered.
+	pub(crate) async fn create_upload(
+		&self,
+		ns: NamespaceId,
+		db: DatabaseId,
+		bu: &str,
+		key: ObjectKey,
+		upload: Box<dyn MultipartUpload>,
+	) -> Uuid {
+		// Abort any expired uploads
+		self.expire_uploads().await;
+		// Register the new upload
+		let id = Uuid::now_v7();
+		let upload = PendingUpload {
+			bucket: BucketConnectionKey::new(ns, db, bu),
+			key,
+			created: Instant::now(),
+			uploaded: AtomicU64::new(0),
+			upload: Mutex::new(upload),
+		};
+		self.uploads.insert(id, Arc::new(upload));
+		id
+	}
+
+	/// Fetches an in-progress multipart upload for the specified file.
+	///
+	/// Returns `UploadNotFound` if the upload does not exist, or if it was
+	/// created for a different file.
+	pub(crate) fn get_upload(
+		&self,
+		ns: NamespaceId,
+		db: DatabaseId,
+		bu: &str,
+		key: &ObjectKey,
+		id: Uuid,
+	) -> Result<Arc<PendingUpload>> {
+		let Some(upload) = self.uploads.get(&id).map(|x| x.value().clone()) else {
+			bail!(Error::UploadNotFound(id.to_string()));
+		};
+		// Ensure the upload belongs to the specified file
+		ensure!(
+			upload.bucket == BucketConnectionKey::new(ns, db, bu) && &upload.key == key,
+			Error::UploadNotFound(id.to_string())
+		);
+		Ok(upload)
+	}
+
+	/// Removes a multipart upload once it has been completed or aborted.
+	pub(crate) fn remove_upload(&self, id: Uuid) {
+		self.uploads.remove(&id);
+	}
+
+	/// Aborts and removes any multipart uploads which have expired.
+	///
+	/// Uploads are held in memory on the node on which they were created, so
+	/// this is called by the lifecycle task on every node, as well as whenever
+	/// a new upload is created.
+	pub(crate) async fn expire_uploads(&self) {
+		let timeout = Duration::from_secs(*BUCKET_UPLOAD_TIMEOUT_SECS);
+		// Collect the expired uploads, so no map locks are held while aborting
+		let expired: Vec<_> = self
+			.uploads
+			.iter()
+			.filter(|x| x.value().created.elapsed() > timeout)
+			.map(|x| *x.key())
+			.collect();
+		// Abort each of the expired uploads
+		for id in expired {
+			if let Some((_, upload)) = self.uploads.remove(&id)
+				&& let Err(e) = upload.upload.lock().await.abort().await
+			{
+				warn!("Failed to abort expired multipart upload {id}: {e}");
+			}
+		}
+	}
+}
+
+/// Checks if an error is caused by a transaction conflict, which can be retried.
+fn is_retryable(e: &anyhow::Error) -> bool {
+	matches!(e.downcast_ref(), Some(Error::Kvs(e)) if e.is_retryable())
+}
+
+/// The number and total size of the files stored in a bucket.
+#[revisioned(revision = 1)]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub(crate) struct BucketUsage {
+	/// The total size of all files in bytes
+	pub(crate) size: u64,
+	/// The number of files
+	pub(crate) count: u64,
+}
+
+impl_kv_value_revisioned!(BucketUsage);
+
+impl BucketUsage {
+	/// Computes the usage of a store by listing all of its files.
+	async fn compute(store: &dyn ObjectStore) -> Result<Self, String> {
+		let mut usage = Self::default();
+		let mut opts = ListOptions {
+			limit: Some(*BUCKET_LIST_BATCH_SIZE),
+			..Default::default()
+		};
+		loop {
+			let batch = store.list(&opts).await?;
+			for meta in &batch {
+				usage.size += meta.size;
+				usage.count += 1;
+			}
+			match batch.last() {
+				Some(last) if batch.len() >= *BUCKET_LIST_BATCH_SIZE => {
+					opts.start = Some(last.key.clone());
+				}
+				_ => return Ok(usage),
+			}
+		}
+	}
+
+	/// Applies a change in usage, saturating at zero.
+	fn apply(&mut self, delta: UsageDelta) {
+		self.size = self.size.saturating_add_signed(delta.size);
+		self.count = self.count.saturating_add_signed(delta.count);
+	}
+
+	/// Checks that a change in usage would not exceed the quota of a bucket.
+	fn check(
+		mut self,
+		bucket: &BucketDefinition,
+		key: &ObjectKey,
+		delta: UsageDelta,
+	) -> Result<()> {
+		self.apply(delta);
+		if let Some(max_size) = bucket.max_size {
+			ensure!(
+				delta.size <= 0 || self.size <= max_size,
+				Error::BucketQuotaExceeded(bucket.name.clone(), key.to_string(), "size")
+			);
+		}
+		if let Some(max_count) = bucket.max_count {
+			ensure!(
+				delta.count <= 0 || self.count <= max_count,
+				Error::BucketQuotaExceeded(bucket.name.clone(), key.to_string(), "count")
+			);
+		}
+		Ok(())
+	}
+}
+
+impl InfoStructure for BucketUsage {
+	fn structure(self) -> Value {
+		Value::from(map! {
+			"size".to_string() => self.size.into(),
+			"count".to_string() => self.count.into(),
+		})
+	}
+}
+
+/// A change in the usage of a bucket, resulting from a write or delete.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub(crate) struct UsageDelta {
+	size: i64,
+	count: i64,
+}
+
+impl UsageDelta {
+	/// Returns the change in usage which results from adding a new file.
+	fn file(size: u64) -> Self {
+		Self {
+			size: size as i64,
+			count: 1,
+		}
+	}
+
+	/// Returns the change in usage which reverses this change.
+	fn negate(self) -> Self {
+		Self {
+			size: -self.size,
+			count: -self.count,
+		}
+	}
+}
+
+/// An in-progress multipart upload to a bucket.
+///
+/// Uploads are held in memory on the node on which they were created, and
+/// are identified by a unique id which is returned to the caller. All parts
+/// of an upload must therefore be sent to the same node, as the upload is not
+/// found on any other node, and is lost if its node restarts.
+pub(crate) struct PendingUpload {
+	bucket: BucketConnectionKey,
+	key: ObjectKey,
+	created: Instant,
+	/// The number of bytes uploaded so far
+	pub(crate) uploaded: AtomicU64,
+	pub(crate) upload: Mutex<Box<dyn MultipartUpload>>,
 }
 
 /// Key for caching bucket connections.
@@ -2,7 +2,7 @@
 //!
 //! This module provides abstractions for object storage backends (buckets) that can be used
 //! to store and retrieve binary data (files). It supports multiple storage backends including
-//! in-memory storage and local filesystem storage.
+//! in-memory storage, local filesystem storage, and S3-compatible storage.
 //!
 //! The module is organized into:
 //! - `BucketController` - Controls bucket operations with permission checking
@@ -21,6 +21,8 @@ use crate::buc::store::ObjectStore;
 #[cfg(not(target_arch = "wasm32"))]
 use crate::buc::store::file::FileStore;
 use crate::buc::store::memory::MemoryStore;
+#[cfg(all(feature = "bucket-s3", not(target_arch = "wasm32")))]
+use crate::buc::store::s3::S3Store;
 use crate::err::Error;
 
 pub(crate) mod manager;
@@ -45,16 +47,17 @@ pub trait BucketStoreProviderRequirements: Send + Sync + 'static {}
 /// Trait for creating connections to bucket storage backends.
 ///
 /// Implementors of this trait can parse storage URLs and create appropriate
-/// [`ObjectStore`] instances. The community edition supports `memory://` and
-/// `file://` backends, while enterprise editions may support additional backends
-/// like S3, GCS, or Azure Blob Storage.
+/// [`ObjectStore`] instances. The community edition supports `memory://`,
+/// `file://`, and `s3://` backends, while enterprise editions may support
+/// additional backends like GCS or Azure Blob Storage.
 #[cfg_attr(target_family = "wasm", async_trait::async_trait(?Send))]
 #[cfg_attr(not(target_family = "wasm"), async_trait::async_trait)]
 pub trait BucketStoreProvider: BucketStoreProviderRequirements {
 	/// Connect to a bucket storage backend.
 	///
 	/// # Arguments
-	/// * `url` - The storage backend URL (e.g., `memory://`, `file:///path/to/dir`)
+	/// * `url` - The storage backend URL (e.g., `memory://`, `file:///path/to/dir`,
+	///   `s3://bucket/prefix`)
 	/// * `global` - Whether this is a global bucket connection
 	/// * `readonly` - Whether the bucket should be opened in read-only mode
 	///
@@ -76,9 +79,13 @@ impl BucketStoreProvider for CommunityComposer {
 	async fn connect(
 		&self,
 		url: &str,
-		_global: bool,
+		global: bool,
 		_readonly: bool,
 	) -> Result<Arc<dyn ObjectStore>> {
+		// Only the S3 backend treats global buckets differently
+		#[cfg(not(all(feature = "bucket-s3", not(target_arch = "wasm32"))))]
+		let _ = global;
+
 		if MemoryStore::parse_url(url) {
 			return Ok(Arc::new(MemoryStore::new()));
 		}
@@ -88,6 +95,11 @@ impl BucketStoreProvider for CommunityComposer {
 			return Ok(Arc::new(FileStore::new(opts)));
 		}
 
+		#[cfg(all(feature = "bucket-s3", not(target_arch = "wasm32")))]
+		if let Some(opts) = S3Store::parse_url(url, global)? {
+			return Ok(Arc::new(S3Store::new(opts)?));
+		}
+
 		bail!(Error::UnsupportedBackend)
 	}
 }
@@ -1,18 +1,72 @@
+use std::collections::BTreeMap;
+use std::fs::Metadata;
 use std::future::Future;
+use std::hash::{DefaultHasher, Hash, Hasher};
+use std::io::ErrorKind;
 use std::path::{Path as OsPath, PathBuf};
 use std::pin::Pin;
+use std::sync::LazyLock;
 
 use bytes::Bytes;
 use path_clean::PathClean;
+use serde::{Deserialize, Serialize};
 use tokio::fs::File;
-use tokio::io::AsyncWriteExt;
+use tokio::io::{AsyncReadExt, AsyncSeekExt, AsyncWriteExt};
+use tokio::sync::{Mutex, MutexGuard};
 use url::Url;
+use uuid::Uuid;
 use web_time::SystemTime;
 
-use super::{ListOptions, ObjectKey, ObjectMeta, ObjectStore};
+use super::{
+	ByteStream, Conditional, ListOptions, MultipartUpload, ObjectKey, ObjectMeta, ObjectRange,
+	ObjectStore, PreconditionFailure, PutOptions,
+};
 use crate::cnf::BUCKET_FOLDER_ALLOWLIST;
 use crate::err::Error;
 
+/// The file extension used for in-progress multipart uploads
+const UPLOAD_EXTENSION: &str = "surreal-upload";
+
+/// The file extension used for the metadata stored alongside a file
+const META_EXTENSION: &str = "surreal-meta";
+
+/// The size of the chunks in which files are streamed from disk
+const STREAM_CHUNK_SIZE: u64 = 64 * 1024;
+
+/// The number of locks across which writes to files are striped
+const WRITE_LOCK_STRIPES: usize = 64;
+
+/// The locks which serialise writes to the same file within this process.
+///
+/// Every write holds the lock for the path of the file while it replaces the
+/// file, so that conditional writes can check the current file and replace it
+/// without any other write taking place in between. Paths are hashed onto a
+/// fixed number of locks, so that no lock needs to be created for each file.
+static WRITE_LOCKS: LazyLock<[Mutex<()>; WRITE_LOCK_STRIPES]> =
+	LazyLock::new(|| std::array::from_fn(|_| Mutex::new(())));
+
+/// Acquires the write locks for the specified paths.
+///
+/// The locks are always acquired in the same order, so that writes which
+/// involve several files, such as copies and renames, can not deadlock.
+async fn lock_paths(paths: &[&OsPath]) -> Vec<MutexGuard<'static, ()>> {
+	let mut stripes: Vec<usize> = paths
+		.iter()
+		.map(|path| {
+			let mut hasher = DefaultHasher::new();
+			path.hash(&mut hasher);
+			hasher.finish() as usize % WRITE_LOCK_STRIPES
+		})
+		.collect();
+	stripes.sort_unstable();
+	stripes.dedup();
+	let mut guards = Vec::with_capacity(stripes.len());
+	for stripe in stripes {
+		guards.push(WRITE_LOCKS[stripe].lock().await);
+	}
+	guards
+}
+
 /// Options for configuring the FileStore
 #[derive(Clone, Debug)]
 pub struct FileStoreOptions {
@@ -171,6 +225,14 @@ impl FileStore {
 			relative_path_str.to_string()
 		};
 
+		// The files used internally by the store can not be accessed directly
+		if relative_path.split(['/', '\\']).any(|x| is_internal_path(OsPath::new(x))) {
+			return Err(format!(
+				"Keys ending with .{UPLOAD_EXTENSION} or .{META_EXTENSION} are reserved: {}",
+				path.as_str()
+			));
+		}
+
 		// Combine the canonical root with the relative path
 		let full_path = canonical_root.join(&relative_path).clean();
 
@@ -194,6 +256,202 @@ impl FileStore {
 		}
 		Ok(())
 	}
+
+	/// Write data to a file, creating or truncating it
+	async fn write_file(path: &OsPath, data: &[u8]) -> Result<(), String> {
+		let mut file =
+			File::create(path).await.map_err(|e| format!("Failed to create file: {}", e))?;
+
+		file.write_all(data).await.map_err(|e| format!("Failed to write to file: {}", e))?;
+
+		file.flush().await.map_err(|e| format!("Failed to flush file: {}", e))?;
+
+		Ok(())
+	}
+
+	/// Get the path of a hidden temporary file next to a file, into which its
+	/// new contents are written before the file is replaced.
+	fn temporary_path(path: &OsPath) -> Result<PathBuf, String> {
+		let name = path
+			.file_name()
+			.map(|x| x.to_string_lossy().into_owned())
+			.ok_or_else(|| format!("Invalid file path: {}", path.display()))?;
+		Ok(path.with_file_name(format!(".{name}.{}.{UPLOAD_EXTENSION}", Uuid::new_v4().simple())))
+	}
+
+	/// Remove a temporary file which is no longer needed
+	async fn discard(path: &OsPath) {
+		if let Err(e) = tokio::fs::remove_file(path).await
+			&& e.kind() != ErrorKind::NotFound
+		{
+			warn!("Failed to remove the temporary file at {}: {}", path.display(), e);
+		}
+	}
+
+	/// Get the filesystem metadata for a file, or `None` if it does not exist
+	async fn file_metadata(path: &OsPath) -> Result<Option<Metadata>, String> {
+		match tokio::fs::metadata(path).await {
+			Ok(metadata) => Ok(Some(metadata)),
+			Err(e) if e.kind() == ErrorKind::NotFound => Ok(None),
+			Err(e) => Err(format!("Failed to get metadata: {}", e)),
+		}
+	}
+}
+
+/// The object metadata which is stored in a hidden file alongside each file
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct FileMeta {
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	content_type: Option<String>,
+	#[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+	metadata: BTreeMap<String, String>,
+}
+
+impl FileMeta {
+	/// Get the path of the metadata file for a file
+	fn path(path: &OsPath) -> Option<PathBuf> {
+		let name = path.file_name()?.to_string_lossy();
+		Some(path.with_file_name(format!(".{name}.{META_EXTENSION}")))
+	}
+
+	/// Read the metadata stored alongside a file
+	async fn read(path: &OsPath) -> Result<Self, String> {
+		let Some(meta_path) = Self::path(path) else {
+			return Ok(Self::default());
+		};
+		let data = match tokio::fs::read(&meta_path).await {
+			Ok(data) => data,
+			Err(e) if e.kind() == ErrorKind::NotFound => return Ok(Self::default()),
+			Err(e) => return Err(format!("Failed to read file metadata: {}", e)),
+		};
+		match serde_json::from_slice(&data) {
+			Ok(meta) => Ok(meta),
+			Err(e) => {
+				// A corrupt metadata file should not prevent access to the file itself
+				warn!("Ignoring invalid file metadata at {}: {}", meta_path.display(), e);
+				Ok(Self::default())
+			}
+		}
+	}
+
+	/// Write the metadata alongside a file, removing any stale metadata if empty
+	async fn write(&self, path: &OsPath) -> Result<(), String> {
+		let Some(meta_path) = Self::path(path) else {
+			return Ok(());
+		};
+		if self.content_type.is_none() && self.metadata.is_empty() {
+			return Self::remove(path).await;
+		}
+		let data = serde_json::to_vec(self)
+			.map_err(|e| format!("Failed to serialize file metadata: {}", e))?;
+		FileStore::write_file(&meta_path, &data).await
+	}
+
+	/// Remove the metadata stored alongside a file, if any
+	async fn remove(path: &OsPath) -> Result<(), String> {
+		let Some(meta_path) = Self::path(path) else {
+			return Ok(());
+		};
+		match tokio::fs::remove_file(&meta_path).await {
+			Ok(()) => Ok(()),
+			Err(e) if e.kind() == ErrorKind::NotFound => Ok(()),
+			Err(e) => Err(format!("Failed to delete file metadata: {}", e)),
+		}
+	}
+
+	/// Copy or move the metadata of a file to another file
+	async fn transfer(source: &OsPath, target: &OsPath, remove_source: bool) -> Result<(), String> {
+		let meta = Self::read(source).await?;
+		meta.write(target).await?;
+		if remove_source {
+			Self::remove(source).await?;
+		}
+		Ok(())
+	}
+
+	/// Build the object metadata for a file
+	fn into_object_meta(self, key: ObjectKey, metadata: &Metadata) -> ObjectMeta {
+		let modified = metadata.modified().unwrap_or_else(|_| SystemTime::now());
+		ObjectMeta {
+			size: metadata.len(),
+			updated: modified.into(),
+			key,
+			content_type: self.content_type,
+			etag: Some(file_etag(metadata)),
+			metadata: self.metadata,
+		}
+	}
+}
+
+/// Compute the ETag of a file from its modification time and size
+fn file_etag(metadata: &Metadata) -> String {
+	let modified = metadata
+		.modified()
+		.ok()
+		.and_then(|x| x.duration_since(SystemTime::UNIX_EPOCH).ok())
+		.map(|x| x.as_nanos())
+		.unwrap_or(0);
+	format!("{:x}-{:x}", modified, metadata.len())
+}
+
+/// A chunked upload into a [`FileStore`].
+///
+/// Parts are written to a temporary file alongside the target file, which
+/// is atomically moved into place once the upload is completed.
+struct FileUpload {
+	file: Option<File>,
+	temp: PathBuf,
+	target: PathBuf,
+}
+
+impl MultipartUpload for FileUpload {
+	fn put_part(
+		&mut self,
+		data: Bytes,
+	) -> Pin<Box<dyn Future<Output = Result<(), String>> + Send + '_>> {
+		Box::pin(async move {
+			let Some(file) = self.file.as_mut() else {
+				return Err("The upload has already been completed or aborted".to_string());
+			};
+			file.write_all(&data).await.map_err(|e| format!("Failed to write to file: {}", e))
+		})
+	}
+
+	fn complete(&mut self) -> Pin<Box<dyn Future<Output = Result<(), String>> + Send + '_>> {
+		Box::pin(async move {
+			let Some(mut file) = self.file.take() else {
+				return Err("The upload has already been completed or aborted".to_string());
+			};
+			file.flush().await.map_err(|e| format!("Failed to flush file: {}", e))?;
+			drop(file);
+
+			let _lock = lock_paths(&[&self.target]).await;
+			tokio::fs::rename(&self.temp, &self.target)
+				.await
+				.map_err(|e| format!("Failed to rename file: {}", e))?;
+
+			// Any metadata of a previous file no longer applies
+			FileMeta::remove(&self.target).await
+		})
+	}
+
+	fn abort(&mut self) -> Pin<Box<dyn Future<Output = Result<(), String>> + Send + '_>> {
+		Box::pin(async move {
+			if self.file.take().is_some() {
+				tokio::fs::remove_file(&self.temp)
+					.await
+					.map_err(|e| format!("Failed to delete file: {}", e))?;
+			}
+			Ok(())
+		})
+	}
+}
+
+/// Check if a path is an in-progress multipart upload, or file metadata
+fn is_internal_path(path: &OsPath) -> bool {
+	path.extension().is_some_and(|ext| {
+		ext.eq_ignore_ascii_case(UPLOAD_EXTENSION) || ext.eq_ignore_ascii_case(META_EXTENSION)
+	})
 }
 
 /// Check if a path is allowed according to the allowlist
@@ -239,43 +497,86 @@ impl ObjectStore for FileStore {
 		Box::pin(async move {
 			let os_path = self.to_os_path(key).await?;
 			Self::ensure_parent_dirs(&os_path).await?;
+			let _lock = lock_paths(&[&os_path]).await;
+			Self::write_file(&os_path, &data).await?;
+			FileMeta::remove(&os_path).await
+		})
+	}
+
+	fn put_if_not_exists<'a>(
+		&'a self,
+		key: &'a ObjectKey,
+		data: Bytes,
+	) -> Pin<Box<dyn Future<Output = Result<bool, String>> + Send + 'a>> {
+		Box::pin(async move {
+			let os_path = self.to_os_path(key).await?;
+			Self::ensure_parent_dirs(&os_path).await?;
+			let _lock = lock_paths(&[&os_path]).await;
 
-			let mut file = File::create(&os_path)
+			// Only create the file if it does not already exist
+			let mut file = match tokio::fs::OpenOptions::new()
+				.write(true)
+				.create_new(true)
+				.open(&os_path)
 				.await
-				.map_err(|e| format!("Failed to create file: {}", e))?;
+			{
+				Ok(file) => file,
+				Err(e) if e.kind() == ErrorKind::AlreadyExists => return Ok(false),
+				Err(e) => return Err(format!("Failed to create file: {}", e)),
+			};
 
 			file.write_all(&data).await.map_err(|e| format!("Failed to write to file: {}", e))?;
-
 			file.flush().await.map_err(|e| format!("Failed to flush file: {}", e))?;
 
-			Ok(())
+			FileMeta::remove(&os_path).await?;
+			Ok(true)
 		})
 	}
 
-	fn put_if_not_exists<'a>(
+	fn put_opts<'a>(
 		&'a self,
 		key: &'a ObjectKey,
 		data: Bytes,
-	) -> Pin<Box<dyn Future<Output = Result<(), String>> + Send + 'a>> {
+		opts: PutOptions,
+	) -> Pin<Box<dyn Future<Output = Result<Conditional<()>, String>> + Send + 'a>> {
 		Box::pin(async move {
 			let os_path = self.to_os_path(key).await?;
-
-			// Check if the file already exists
-			if Self::path_exists(&os_path).await? {
-				return Ok(());
-			}
-
 			Self::ensure_parent_dirs(&os_path).await?;
 
-			let mut file = File::create(&os_path)
-				.await
-				.map_err(|e| format!("Failed to create file: {}", e))?;
+			// Write the data to a temporary file first, so that the file can
+			// be replaced as soon as the preconditions have been checked
+			let temp = Self::temporary_path(&os_path)?;
+			if let Err(e) = Self::write_file(&temp, &data).await {
+				Self::discard(&temp).await;
+				return Err(e);
+			}
 
-			file.write_all(&data).await.map_err(|e| format!("Failed to write to file: {}", e))?;
+			// Check the preconditions against the current file, and replace it,
+			// without any other write to the file taking place in between
+			let _lock = lock_paths(&[&os_path]).await;
+			let etag = match Self::file_metadata(&os_path).await {
+				Ok(metadata) => metadata.map(|x| file_etag(&x)),
+				Err(e) => {
+					Self::discard(&temp).await;
+					return Err(e);
+				}
+			};
+			if opts.precondition.check(etag.as_deref()).is_err() {
+				Self::discard(&temp).await;
+				return Ok(Conditional::Skipped(PreconditionFailure::Failed));
+			}
+			if let Err(e) = tokio::fs::rename(&temp, &os_path).await {
+				Self::discard(&temp).await;
+				return Err(format!("Failed to rename file: {}", e));
+			}
 
-			file.flush().await.map_err(|e| format!("Failed to flush file: {}", e))?;
+			let meta = FileMeta {
+				content_type: opts.content_type,
+				metadata: opts.metadata,
+			};
+			meta.write(&os_path).await?;
 
-			Ok(())
+			Ok(Conditional::Applied(()))
 		})
 	}
 
@@ -299,10 +600,11 @@ impl ObjectStore for FileStore {
 		})
 	}
 
-	fn head<'a>(
+	fn get_range<'a>(
 		&'a self,
 		key: &'a ObjectKey,
-	) -> Pin<Box<dyn Future<Output = Result<Option<ObjectMeta>, String>> + Send + 'a>> {
+		range: ObjectRange,
+	) -> Pin<Box<dyn Future<Output = Result<Option<Bytes>, String>> + Send + 'a>> {
 		Box::pin(async move {
 			let os_path = self.to_os_path(key).await?;
 
@@ -311,20 +613,117 @@ impl ObjectStore for FileStore {
 				return Ok(None);
 			}
 
-			let metadata = tokio::fs::metadata(&os_path)
+			let mut file =
+				File::open(&os_path).await.map_err(|e| format!("Failed to open file: {}", e))?;
+
+			let metadata =
+				file.metadata().await.map_err(|e| format!("Failed to get metadata: {}", e))?;
+
+			let range = range.clamp(metadata.len());
+
+			file.seek(std::io::SeekFrom::Start(range.start))
 				.await
-				.map_err(|e| format!("Failed to get metadata: {}", e))?;
+				.map_err(|e| format!("Failed to seek file: {}", e))?;
+
+			// Read up to the end of the range, without allocating the whole
+			// range upfront, as the file may be truncated concurrently
+			let mut data = Vec::new();
+			file.take(range.end - range.start)
+				.read_to_end(&mut data)
+				.await
+				.map_err(|e| format!("Failed to read file: {}", e))?;
+
+			Ok(Some(Bytes::from(data)))
+		})
+	}
 
-			let size = metadata.len();
+	fn get_stream<'a>(
+		&'a self,
+		key: &'a ObjectKey,
+		range: Option<ObjectRange>,
+	) -> Pin<Box<dyn Future<Output = Result<Option<ByteStream>, String>> + Send + 'a>> {
+		Box::pin(async move {
+			let os_path = self.to_os_path(key).await?;
+
+			// Check if the file exists
+			if !Self::path_exists(&os_path).await? {
+				return Ok(None);
+			}
 
-			// Get modified time if available
-			let updated = metadata.modified().unwrap_or_else(|_| SystemTime::now()).into();
+			let mut file =
+				File::open(&os_path).await.map_err(|e| format!("Failed to open file: {}", e))?;
 
-			Ok(Some(ObjectMeta {
-				size,
-				updated,
-				key: key.to_owned(),
-			}))
+			let metadata =
+				file.metadata().await.map_err(|e| format!("Failed to get metadata: {}", e))?;
+
+			let range = range.unwrap_or_default().clamp(metadata.len());
+
+			file.seek(std::io::SeekFrom::Start(range.start))
+				.await
+				.map_err(|e| format!("Failed to seek file: {}", e))?;
+
+			// Read the file in chunks, until the end of the range
+			let remaining = range.end - range.start;
+			let stream = futures::stream::try_unfold(
+				(file, remaining),
+				|(mut file, remaining)| async move {
+					if remaining == 0 {
+						return Ok(None);
+					}
+					let mut buf = vec![0u8; remaining.min(STREAM_CHUNK_SIZE) as usize];
+					let read = file
+						.read(&mut buf)
+						.await
+						.map_err(|e| format!("Failed to read file: {}", e))?;
+					if read == 0 {
+						return Ok(None);
+					}
+					buf.truncate(read);
+					Ok(Some((Bytes::from(buf), (file, remaining - read as u64))))
+				},
+			);
+
+			Ok(Some(Box::pin(stream) as ByteStream))
+		})
+	}
+
+	fn put_multipart<'a>(
+		&'a self,
+		key: &'a ObjectKey,
+	) -> Pin<Box<dyn Future<Output = Result<Box<dyn MultipartUpload>, String>> + Send + 'a>> {
+		Box::pin(async move {
+			let target = self.to_os_path(key).await?;
+			Self::ensure_parent_dirs(&target).await?;
+
+			// Write the upload to a hidden temporary file next to the target
+			let temp = Self::temporary_path(&target)?;
+
+			let file =
+				File::create(&temp).await.map_err(|e| format!("Failed to create file: {}", e))?;
+
+			Ok(Box::new(FileUpload {
+				file: Some(file),
+				temp,
+				target,
+			}) as Box<dyn MultipartUpload>)
+		})
+	}
+
+	fn head<'a>(
+		&'a self,
+		key: &'a ObjectKey,
+	) -> Pin<Box<dyn Future<Output = Result<Option<ObjectMeta>, String>> + Send + 'a>> {
+		Box::pin(async move {
+			let os_path = self.to_os_path(key).await?;
+
+			// Check if the file exists
+			let Some(metadata) = Self::file_metadata(&os_path).await? else {
+				return Ok(None);
+			};
+
+			let meta = FileMeta::read(&os_path).await?;
+
+			Ok(Some(meta.into_object_meta(key.to_owned(), &metadata)))
 		})
 	}
 
@@ -334,6 +733,7 @@ impl ObjectStore for FileStore {
 	) -> Pin<Box<dyn Future<Output = Result<(), String>> + Send + 'a>> {
 		Box::pin(async move {
 			let os_path = self.to_os_path(key).await?;
+			let _lock = lock_paths(&[&os_path]).await;
 
 			// Check if the file exists
 			if !Self::path_exists(&os_path).await? {
@@ -344,7 +744,7 @@ impl ObjectStore for FileStore {
 				.await
 				.map_err(|e| format!("Failed to delete file: {}", e))?;
 
-			Ok(())
+			FileMeta::remove(&os_path).await
 		})
 	}
 
@@ -366,6 +766,7 @@ impl ObjectStore for FileStore {
 		Box::pin(async move {
 			let source_key = self.to_os_path(key).await?;
 			let target_key = self.to_os_path(target).await?;
+			let _lock = lock_paths(&[&source_key, &target_key]).await;
 
 			// Check if the source file exists
 			if !Self::path_exists(&source_key).await? {
@@ -378,7 +779,7 @@ impl ObjectStore for FileStore {
 				.await
 				.map_err(|e| format!("Failed to copy file: {}", e))?;
 
-			Ok(())
+			FileMeta::transfer(&source_key, &target_key, false).await
 		})
 	}
 
@@ -386,20 +787,21 @@ impl ObjectStore for FileStore {
 		&'a self,
 		key: &'a ObjectKey,
 		target: &'a ObjectKey,
-	) -> Pin<Box<dyn Future<Output = Result<(), String>> + Send + 'a>> {
+	) -> Pin<Box<dyn Future<Output = Result<bool, String>> + Send + 'a>> {
 		Box::pin(async move {
 			let source_key = self.to_os_path(key).await?;
 			let target_key = self.to_os_path(target).await?;
+			let _lock = lock_paths(&[&source_key, &target_key]).await;
 
 			// Check if target already exists
 			if Self::path_exists(&target_key).await? {
-				return Ok(());
+				return Ok(false);
 			}
 
 			// Check if the source file exists
 			if !Self::path_exists(&source_key).await? {
 				// Silently ignore operations on non-existent source files
-				return Ok(());
+				return Ok(false);
 			}
 
 			Self::ensure_parent_dirs(&target_key).await?;
@@ -408,7 +810,8 @@ impl ObjectStore for FileStore {
 				.await
 				.map_err(|e| format!("Failed to copy file: {}", e))?;
 
-			Ok(())
+			FileMeta::transfer(&source_key, &target_key, false).await?;
+			Ok(true)
 		})
 	}
 
@@ -420,6 +823,7 @@ impl ObjectStore for FileStore {
 		Box::pin(async move {
 			let source_key = self.to_os_path(key).await?;
 			let target_key = self.to_os_path(target).await?;
+			let _lock = lock_paths(&[&source_key, &target_key]).await;
 
 			// Check if the source file exists
 			if !Self::path_exists(&source_key).await? {
@@ -432,7 +836,7 @@ impl ObjectStore for FileStore {
 				.await
 				.map_err(|e| format!("Failed to rename file: {}", e))?;
 
-			Ok(())
+			FileMeta::transfer(&source_key, &target_key, true).await
 		})
 	}
 
@@ -444,6 +848,7 @@ impl ObjectStore for FileStore {
 		Box::pin(async move {
 			let source_key = self.to_os_path(key).await?;
 			let target_key = self.to_os_path(target).await?;
+			let _lock = lock_paths(&[&source_key, &target_key]).await;
 
 			// Check if target already exists
 			if Self::path_exists(&target_key).await? {
@@ -461,7 +866,7 @@ impl ObjectStore for FileStore {
 				.await
 				.map_err(|e| format!("Failed to rename file: {}", e))?;
 
-			Ok(())
+			FileMeta::transfer(&source_key, &target_key, true).await
 		})
 	}
 
@@ -494,13 +899,8 @@ impl ObjectStore for FileStore {
 					return Ok(Vec::new());
 				}
 
-				let size = metadata.len();
-				let updated = metadata.modified().unwrap_or_else(|_| SystemTime::now()).into();
-				return Ok(vec![ObjectMeta {
-					key: base_key,
-					size,
-					updated,
-				}]);
+				let meta = FileMeta::read(&os_path).await?;
+				return Ok(vec![meta.into_object_meta(base_key, &metadata)]);
 			}
 
 			// If it's a directory, read its contents
@@ -528,6 +928,11 @@ impl ObjectStore for FileStore {
 					continue;
 				}
 
+				// Skip any in-progress multipart uploads and file metadata
+				if is_internal_path(&path) {
+					continue;
+				}
+
 				// Convert the path to a relative Key
 				let rel_path = path
 					.strip_prefix(&os_path)
@@ -535,17 +940,18 @@ impl ObjectStore for FileStore {
 				let rel_str = rel_path.to_string_lossy();
 				let entry_key = base_key.join(&ObjectKey::new(rel_str.into_owned()));
 
-				all_entries.push((entry_key, metadata));
+				all_entries.push((entry_key, path, metadata));
 			}
 
 			// Sort entries by key to ensure consistent ordering
-			all_entries.sort_by(|(key_a, _), (key_b, _)| key_a.to_string().cmp(&key_b.to_string()));
+			all_entries
+				.sort_by(|(key_a, _, _), (key_b, _, _)| key_a.to_string().cmp(&key_b.to_string()));
 
 			// Filter by start key if provided
 			let filtered_entries = if let Some(ref start_key) = opts.start {
 				all_entries
 					.into_iter()
-					.filter(|(key, _)| key.to_string() > start_key.to_string())
+					.filter(|(key, _, _)| key.to_string() > start_key.to_string())
 					.collect()
 			} else {
 				all_entries
@@ -559,18 +965,11 @@ impl ObjectStore for FileStore {
 			};
 
 			// Convert to ObjectMeta
-			let objects = limited_entries
-				.into_iter()
-				.map(|(entry_key, metadata)| {
-					let size = metadata.len();
-					let updated = metadata.modified().unwrap_or_else(|_| SystemTime::now()).into();
-					ObjectMeta {
-						key: entry_key,
-						size,
-						updated,
-					}
-				})
-				.collect();
+			let mut objects = Vec::with_capacity(limited_entries.len());
+			for (entry_key, path, metadata) in limited_entries {
+				let meta = FileMeta::read(&path).await?;
+				objects.push(meta.into_object_meta(entry_key, &metadata));
+			}
 
 			Ok(objects)
 		})
@@ -3,14 +3,21 @@
 //! This module provides a simple in-memory implementation of the [`ObjectStore`] trait,
 //! useful for testing and development environments where persistence is not required.
 
+use std::collections::BTreeMap;
 use std::future::Future;
 use std::pin::Pin;
+use std::sync::Arc;
 
-use bytes::Bytes;
+use bytes::{Bytes, BytesMut};
 use dashmap::DashMap;
+use dashmap::mapref::entry::Entry as MapEntry;
+use md5::{Digest, Md5};
 use url::Url;
 
-use super::{ListOptions, ObjectKey, ObjectMeta, ObjectStore};
+use super::{
+	ByteStream, Conditional, GetOptions, ListOptions, MultipartUpload, ObjectKey, ObjectMeta,
+	ObjectRange, ObjectStore, PreconditionFailure, PutOptions,
+};
 use crate::val::Datetime;
 
 /// Internal storage entry containing data and metadata.
@@ -18,13 +25,35 @@ use crate::val::Datetime;
 pub struct Entry {
 	bytes: Bytes,
 	updated: Datetime,
+	etag: String,
+	content_type: Option<String>,
+	metadata: BTreeMap<String, String>,
+}
+
+impl Entry {
+	/// Builds the object metadata for this entry.
+	fn to_meta(&self, key: &ObjectKey) -> ObjectMeta {
+		ObjectMeta {
+			size: self.bytes.len() as u64,
+			updated: self.updated.0,
+			key: key.to_owned(),
+			content_type: self.content_type.clone(),
+			etag: Some(self.etag.clone()),
+			metadata: self.metadata.clone(),
+		}
+	}
 }
 
 impl From<Bytes> for Entry {
 	fn from(bytes: Bytes) -> Self {
+		// The ETag is the MD5 digest of the contents, as with S3
+		let etag = format!("{:x}", Md5::digest(&bytes));
 		Self {
 			bytes,
 			updated: Datetime::now(),
+			etag,
+			content_type: None,
+			metadata: BTreeMap::new(),
 		}
 	}
 }
@@ -43,7 +72,7 @@ impl From<Bytes> for Entry {
 /// - Caching layers
 #[derive(Clone, Debug, Default)]
 pub struct MemoryStore {
-	store: DashMap<ObjectKey, Entry>,
+	store: Arc<DashMap<ObjectKey, Entry>>,
 }
 
 impl MemoryStore {
@@ -68,6 +97,43 @@ impl MemoryStore {
 	}
 }
 
+/// A chunked upload into a [`MemoryStore`].
+///
+/// Parts are buffered until the upload is completed, at which point the
+/// object is inserted into the store in a single operation.
+struct MemoryUpload {
+	store: Arc<DashMap<ObjectKey, Entry>>,
+	key: ObjectKey,
+	data: BytesMut,
+}
+
+impl MultipartUpload for MemoryUpload {
+	fn put_part(
+		&mut self,
+		data: Bytes,
+	) -> Pin<Box<dyn Future<Output = Result<(), String>> + Send + '_>> {
+		Box::pin(async move {
+			self.data.extend_from_slice(&data);
+			Ok(())
+		})
+	}
+
+	fn complete(&mut self) -> Pin<Box<dyn Future<Output = Result<(), String>> + Send + '_>> {
+		Box::pin(async move {
+			let data = std::mem::take(&mut self.data).freeze();
+			self.store.insert(self.key.clone(), data.into());
+			Ok(())
+		})
+	}
+
+	fn abort(&mut self) -> Pin<Box<dyn Future<Output = Result<(), String>> + Send + '_>> {
+		Box::pin(async move {
+			self.data.clear();
+			Ok(())
+		})
+	}
+}
+
 impl ObjectStore for MemoryStore {
 	fn put<'a>(
 		&'a self,
@@ -84,11 +150,68 @@ impl ObjectStore for MemoryStore {
 		&'a self,
 		key: &'a ObjectKey,
 		data: Bytes,
-	) -> Pin<Box<dyn Future<Output = Result<(), String>> + Send + 'a>> {
+	) -> Pin<Box<dyn Future<Output = Result<bool, String>> + Send + 'a>> {
 		Box::pin(async move {
-			self.store.entry(key.clone()).or_insert_with(|| data.into());
+			match self.store.entry(key.clone()) {
+				MapEntry::Occupied(_) => Ok(false),
+				MapEntry::Vacant(entry) => {
+					entry.insert(data.into());
+					Ok(true)
+				}
+			}
+		})
+	}
 
-			Ok(())
+	fn put_opts<'a>(
+		&'a self,
+		key: &'a ObjectKey,
+		data: Bytes,
+		opts: PutOptions,
+	) -> Pin<Box<dyn Future<Output = Result<Conditional<()>, String>> + Send + 'a>> {
+		Box::pin(async move {
+			let entry = Entry {
+				content_type: opts.content_type,
+				metadata: opts.metadata,
+				..Entry::from(data)
+			};
+			// The map entry is locked while the precondition is checked, and any
+			// unsatisfied precondition on a write is reported as a failure
+			match self.store.entry(key.clone()) {
+				MapEntry::Occupied(mut existing) => {
+					if opts.precondition.check(Some(&existing.get().etag)).is_err() {
+						return Ok(Conditional::Skipped(PreconditionFailure::Failed));
+					}
+					existing.insert(entry);
+				}
+				MapEntry::Vacant(vacant) => {
+					if opts.precondition.check(None).is_err() {
+						return Ok(Conditional::Skipped(PreconditionFailure::Failed));
+					}
+					vacant.insert(entry);
+				}
+			}
+			Ok(Conditional::Applied(()))
+		})
+	}
+
+	fn get_opts<'a>(
+		&'a self,
+		key: &'a ObjectKey,
+		opts: GetOptions,
+	) -> Pin<Box<dyn Future<Output = Result<Conditional<Option<Bytes>>, String>> + Send + 'a>> {
+		Box::pin(async move {
+			let entry = self.store.get(key);
+			if let Err(e) = opts.precondition.check(entry.as_ref().map(|v| v.etag.as_str())) {
+				return Ok(Conditional::Skipped(e));
+			}
+			let data = entry.map(|v| match opts.range {
+				Some(range) => {
+					let range = range.clamp(v.bytes.len() as u64);
+					v.bytes.slice(range.start as usize..range.end as usize)
+				}
+				None => v.bytes.clone(),
+			});
+			Ok(Conditional::Applied(data))
 		})
 	}
 
@@ -102,16 +225,55 @@ impl ObjectStore for MemoryStore {
 		})
 	}
 
+	fn get_range<'a>(
+		&'a self,
+		key: &'a ObjectKey,
+		range: ObjectRange,
+	) -> Pin<Box<dyn Future<Output = Result<Option<Bytes>, String>> + Send + 'a>> {
+		Box::pin(async move {
+			let data = self.store.get(key).map(|v| {
+				let range = range.clamp(v.bytes.len() as u64);
+				v.bytes.slice(range.start as usize..range.end as usize)
+			});
+			Ok(data)
+		})
+	}
+
+	fn get_stream<'a>(
+		&'a self,
+		key: &'a ObjectKey,
+		range: Option<ObjectRange>,
+	) -> Pin<Box<dyn Future<Output = Result<Option<ByteStream>, String>> + Send + 'a>> {
+		Box::pin(async move {
+			// The data is already in memory, so the stream is a single chunk
+			let data = match range {
+				Some(range) => self.get_range(key, range).await?,
+				None => self.get(key).await?,
+			};
+			Ok(data.map(|v| Box::pin(futures::stream::once(async move { Ok(v) })) as ByteStream))
+		})
+	}
+
+	fn put_multipart<'a>(
+		&'a self,
+		key: &'a ObjectKey,
+	) -> Pin<Box<dyn Future<Output = Result<Box<dyn MultipartUpload>, String>> + Send + 'a>> {
+		Box::pin(async move {
+			let upload = MemoryUpload {
+				store: self.store.clone(),
+				key: key.clone(),
+				data: BytesMut::new(),
+			};
+			Ok(Box::new(upload) as Box<dyn MultipartUpload>)
+		})
+	}
+
 	fn head<'a>(
 		&'a self,
 		key: &'a ObjectKey,
 	) -> Pin<Box<dyn Future<Output = Result<Option<ObjectMeta>, String>> + Send + 'a>> {
 		Box::pin(async move {
-			let data = self.store.get(key).map(|v| ObjectMeta {
-				size: v.bytes.len() as u64,
-				updated: v.updated.0,
-				key: key.to_owned(),
-			});
+			let data = self.store.get(key).map(|v| v.to_meta(key));
 
 			Ok(data)
 		})
@@ -162,22 +324,27 @@ impl ObjectStore for MemoryStore {
 		&'a self,
 		key: &'a ObjectKey,
 		target: &'a ObjectKey,
-	) -> Pin<Box<dyn Future<Output = Result<(), String>> + Send + 'a>> {
+	) -> Pin<Box<dyn Future<Output = Result<bool, String>> + Send + 'a>> {
 		Box::pin(async move {
-			if !self.store.contains_key(target) {
-				// This is intentionally somewhat verbosely written to ensure the lock is being
-				// properly handled.
-				let entry = {
-					let Some(entry) = self.store.get(key) else {
-						return Ok(());
-					};
-					entry.clone()
-				};
-
-				self.store.insert(target.clone(), entry);
+			if self.store.contains_key(target) {
+				return Ok(false);
 			}
+			// This is intentionally somewhat verbosely written to ensure the lock is being
+			// properly handled.
+			let entry = {
+				let Some(entry) = self.store.get(key) else {
+					return Ok(false);
+				};
+				entry.clone()
+			};
 
-			Ok(())
+			Ok(match self.store.entry(target.clone()) {
+				MapEntry::Occupied(_) => false,
+				MapEntry::Vacant(vacant) => {
+					vacant.insert(entry);
+					true
+				}
+			})
 		})
 	}
 
@@ -265,12 +432,7 @@ impl ObjectStore for MemoryStore {
 
 			// Convert to ObjectMeta
 			for x in limited_keys {
-				let entry = x.value();
-				objects.push(ObjectMeta {
-					key: x.key().clone(),
-					size: entry.bytes.len() as u64,
-					updated: entry.updated.0,
-				});
+				objects.push(x.value().to_meta(x.key()));
 			}
 
 			Ok(objects)
@@ -5,13 +5,18 @@
 //! - [`ObjectKey`] - Normalized path representation for object keys
 //! - [`ObjectMeta`] - Metadata about stored objects
 //! - [`ListOptions`] - Options for listing objects in a bucket
+//! - [`ObjectRange`] - A byte range for partial reads of an object
+//! - [`MultipartUpload`] - A chunked upload of an object
+//! - [`PutOptions`] / [`GetOptions`] - Metadata and preconditions for writes and reads
 
+use std::collections::BTreeMap;
 use std::future::Future;
 use std::pin::Pin;
 use std::sync::Arc;
 
 use bytes::Bytes;
 use chrono::{DateTime, Utc};
+use futures::{Stream, StreamExt, TryStreamExt};
 
 use crate::err::Error;
 use crate::val::{Datetime, File, Object, Value};
@@ -21,13 +26,16 @@ pub(crate) mod file;
 pub(crate) mod memory;
 pub(crate) mod path;
 pub(crate) mod prefixed;
+#[cfg(all(feature = "bucket-s3", not(target_arch = "wasm32")))]
+pub(crate) mod s3;
 
 // Expose type for external composers
 pub use path::ObjectKey;
 
 /// Metadata for a stored object.
 ///
-/// Contains information about an object's size, last modification time, and key.
+/// Contains information about an object's size, last modification time, and key,
+/// along with its content type, ETag, and any user-supplied metadata.
 pub struct ObjectMeta {
 	/// Size of the object in bytes
 	pub size: u64,
@@ -35,12 +43,19 @@ pub struct ObjectMeta {
 	pub updated: DateTime<Utc>,
 	/// The object's key (path)
 	pub key: ObjectKey,
+	/// The MIME type of the object, if known
+	pub content_type: Option<String>,
+	/// An opaque identifier which changes whenever the object contents change
+	pub etag: Option<String>,
+	/// User-supplied metadata stored alongside the object
+	pub metadata: BTreeMap<String, String>,
 }
 
 impl ObjectMeta {
 	/// Converts the metadata into a SurrealDB `Value` for query results.
 	///
-	/// The returned value is an object with `updated`, `size`, and `file` fields.
+	/// The returned value is an object with `updated`, `size`, `file`,
+	/// `content_type`, `etag`, and `metadata` fields.
 	pub(crate) fn into_value(self, bucket: String) -> Value {
 		Value::from(map! {
 			"updated" => Value::from(Datetime(self.updated)),
@@ -48,7 +63,167 @@ impl ObjectMeta {
 			"file" => Value::File(File {
 				bucket,
 				key: self.key.to_string(),
-			})
+			}),
+			"content_type" => self.content_type.map(Value::from).unwrap_or(Value::None),
+			"etag" => self.etag.map(Value::from).unwrap_or(Value::None),
+			"metadata" => Value::from(Object::from(self.metadata))
+		})
+	}
+}
+
+/// Preconditions on the ETag of an object, which must be satisfied for an
+/// operation to be performed.
+///
+/// An ETag of `*` matches any existing object.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Precondition {
+	/// Only perform the operation if the object's ETag matches
+	pub if_match: Option<String>,
+	/// Only perform the operation if the object's ETag does not match
+	pub if_none_match: Option<String>,
+}
+
+/// The reason why a [`Precondition`] was not satisfied.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PreconditionFailure {
+	/// The `if_none_match` condition matched the current object
+	NotModified,
+	/// The `if_match` condition did not match the current object
+	Failed,
+}
+
+impl Precondition {
+	/// Returns `true` if no preconditions have been specified.
+	pub fn is_empty(&self) -> bool {
+		self.if_match.is_none() && self.if_none_match.is_none()
+	}
+
+	/// Checks the preconditions against the ETag of the current object, or
+	/// `None` if the object does not exist.
+	pub fn check(&self, etag: Option<&str>) -> Result<(), PreconditionFailure> {
+		if let Some(expected) = &self.if_match {
+			match etag {
+				Some(etag) if etag_matches(expected, etag) => (),
+				_ => return Err(PreconditionFailure::Failed),
+			}
+		}
+		if let Some(expected) = &self.if_none_match
+			&& let Some(etag) = etag
+			&& etag_matches(expected, etag)
+		{
+			return Err(PreconditionFailure::NotModified);
+		}
+		Ok(())
+	}
+
+	/// Parses the `if_match` and `if_none_match` options from an object.
+	fn take_from(obj: &mut Object) -> Result<Self, Error> {
+		let mut precondition = Precondition::default();
+
+		if let Some(etag) = obj.remove("if_match") {
+			precondition.if_match = Some(etag.coerce_to::<String>()?);
+		}
+
+		if let Some(etag) = obj.remove("if_none_match") {
+			precondition.if_none_match = Some(etag.coerce_to::<String>()?);
+		}
+
+		Ok(precondition)
+	}
+}
+
+/// Fails if any options remain which are not supported by a function, so
+/// that misspelt options, such as a misspelt precondition, are not ignored.
+fn reject_unknown_options(obj: &Object, name: &str) -> Result<(), Error> {
+	match obj.keys().next() {
+		Some(key) => Err(Error::InvalidFunctionArguments {
+			name: name.to_string(),
+			message: format!("The option `{key}` is not supported"),
+		}),
+		None => Ok(()),
+	}
+}
+
+/// Compares two ETags, ignoring any surrounding quotes.
+fn etag_matches(expected: &str, etag: &str) -> bool {
+	expected == "*" || expected.trim_matches('"') == etag.trim_matches('"')
+}
+
+/// The outcome of an operation which is subject to a [`Precondition`].
+#[derive(Debug)]
+pub enum Conditional<T> {
+	/// The preconditions were satisfied, and the operation was performed
+	Applied(T),
+	/// The preconditions were not satisfied, and the operation was skipped
+	Skipped(PreconditionFailure),
+}
+
+/// Options for storing an object.
+#[derive(Clone, Debug, Default)]
+pub struct PutOptions {
+	/// The MIME type of the object
+	pub content_type: Option<String>,
+	/// User-supplied metadata to store alongside the object
+	pub metadata: BTreeMap<String, String>,
+	/// Preconditions on the existing object at the key
+	pub precondition: Precondition,
+}
+
+impl TryFrom<Object> for PutOptions {
+	type Error = Error;
+	fn try_from(mut obj: Object) -> Result<Self, Self::Error> {
+		let mut opts = PutOptions {
+			precondition: Precondition::take_from(&mut obj)?,
+			..Default::default()
+		};
+
+		if let Some(content_type) = obj.remove("content_type") {
+			opts.content_type = Some(content_type.coerce_to::<String>()?);
+		}
+
+		if let Some(metadata) = obj.remove("metadata") {
+			for (k, v) in metadata.coerce_to::<Object>()? {
+				opts.metadata.insert(k, v.coerce_to::<String>()?);
+			}
+		}
+
+		reject_unknown_options(&obj, "file::put")?;
+
+		Ok(opts)
+	}
+}
+
+/// Options for retrieving an object.
+#[derive(Clone, Debug, Default)]
+pub struct GetOptions {
+	/// Only retrieve the bytes within this range
+	pub range: Option<ObjectRange>,
+	/// Preconditions on the object at the key
+	pub precondition: Precondition,
+}
+
+impl TryFrom<Object> for GetOptions {
+	type Error = Error;
+	fn try_from(mut obj: Object) -> Result<Self, Self::Error> {
+		let precondition = Precondition::take_from(&mut obj)?;
+		let mut range = Object::default();
+		for key in ["offset", "length"] {
+			if let Some(value) = obj.remove(key) {
+				range.insert(key.to_string(), value);
+			}
+		}
+
+		reject_unknown_options(&obj, "file::get")?;
+
+		let range = if range.is_empty() {
+			None
+		} else {
+			Some(ObjectRange::try_from(range)?)
+		};
+
+		Ok(GetOptions {
+			range,
+			precondition,
 		})
 	}
 }
@@ -88,6 +263,79 @@ impl TryFrom<Object> for ListOptions {
 	}
 }
 
+/// A range of bytes within a stored object.
+///
+/// Ranges are clamped to the size of the object, so a range which starts
+/// beyond the end of an object yields no data.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct ObjectRange {
+	/// The byte offset at which to start reading
+	pub offset: u64,
+	/// The maximum number of bytes to read, or until the end of the object if `None`
+	pub length: Option<u64>,
+}
+
+impl ObjectRange {
+	/// Returns the byte range covered within an object of the given size.
+	pub fn clamp(&self, size: u64) -> std::ops::Range<u64> {
+		let start = self.offset.min(size);
+		let end = match self.length {
+			Some(length) => start.saturating_add(length).min(size),
+			None => size,
+		};
+		start..end
+	}
+}
+
+impl TryFrom<Object> for ObjectRange {
+	type Error = Error;
+	fn try_from(mut obj: Object) -> Result<Self, Self::Error> {
+		let mut range = ObjectRange::default();
+
+		if let Some(offset) = obj.remove("offset") {
+			let offset = offset.coerce_to::<i64>()?;
+			range.offset = u64::try_from(offset).map_err(|_| Error::InvalidFunctionArguments {
+				name: "file::get".to_string(),
+				message: "The range offset must not be negative".to_string(),
+			})?;
+		}
+
+		if let Some(length) = obj.remove("length") {
+			let length = length.coerce_to::<i64>()?;
+			range.length =
+				Some(u64::try_from(length).map_err(|_| Error::InvalidFunctionArguments {
+					name: "file::get".to_string(),
+					message: "The range length must not be negative".to_string(),
+				})?);
+		}
+
+		Ok(range)
+	}
+}
+
+/// A stream of bytes read from, or written to, an object store.
+pub type ByteStream = Pin<Box<dyn Stream<Item = Result<Bytes, String>> + Send + 'static>>;
+
+/// A chunked upload of a single object.
+///
+/// Parts are appended in the order in which they are written, and the object
+/// only becomes visible in the store once the upload has been completed. An
+/// upload which is aborted, or dropped before completion, leaves any existing
+/// object at the same key untouched.
+pub trait MultipartUpload: Send + 'static {
+	/// Appends a chunk of data to the upload.
+	fn put_part(
+		&mut self,
+		data: Bytes,
+	) -> Pin<Box<dyn Future<Output = Result<(), String>> + Send + '_>>;
+
+	/// Completes the upload, making the object visible in the store.
+	fn complete(&mut self) -> Pin<Box<dyn Future<Output = Result<(), String>> + Send + '_>>;
+
+	/// Aborts the upload, discarding any data which has been written.
+	fn abort(&mut self) -> Pin<Box<dyn Future<Output = Result<(), String>> + Send + '_>>;
+}
+
 /// Trait for object storage backends.
 ///
 /// This trait defines the core operations that all object storage implementations
@@ -104,11 +352,13 @@ pub trait ObjectStore: Send + Sync + 'static {
 	) -> Pin<Box<dyn Future<Output = Result<(), String>> + Send + 'a>>;
 
 	/// Stores data at the specified key only if the key does not already exist.
+	///
+	/// Returns whether the data was written.
 	fn put_if_not_exists<'a>(
 		&'a self,
 		key: &'a ObjectKey,
 		data: Bytes,
-	) -> Pin<Box<dyn Future<Output = Result<(), String>> + Send + 'a>>;
+	) -> Pin<Box<dyn Future<Output = Result<bool, String>> + Send + 'a>>;
 
 	/// Retrieves data from the specified key.
 	///
@@ -118,6 +368,107 @@ pub trait ObjectStore: Send + Sync + 'static {
 		key: &'a ObjectKey,
 	) -> Pin<Box<dyn Future<Output = Result<Option<Bytes>, String>> + Send + 'a>>;
 
+	/// Stores data at the specified key with the given content type and
+	/// metadata, if the preconditions on any existing object are satisfied.
+	fn put_opts<'a>(
+		&'a self,
+		key: &'a ObjectKey,
+		data: Bytes,
+		opts: PutOptions,
+	) -> Pin<Box<dyn Future<Output = Result<Conditional<()>, String>> + Send + 'a>>;
+
+	/// Retrieves data from the specified key, if the preconditions on the
+	/// object are satisfied.
+	///
+	/// Returns `Ok(Conditional::Applied(None))` if the key does not exist and
+	/// no `if_match` precondition was specified.
+	#[allow(clippy::type_complexity)]
+	fn get_opts<'a>(
+		&'a self,
+		key: &'a ObjectKey,
+		opts: GetOptions,
+	) -> Pin<Box<dyn Future<Output = Result<Conditional<Option<Bytes>>, String>> + Send + 'a>> {
+		Box::pin(async move {
+			// Stores without native support check the ETag before reading
+			if !opts.precondition.is_empty() {
+				let meta = self.head(key).await?;
+				if let Err(e) =
+					opts.precondition.check(meta.as_ref().and_then(|m| m.etag.as_deref()))
+				{
+					return Ok(Conditional::Skipped(e));
+				}
+			}
+			let data = match opts.range {
+				Some(range) => self.get_range(key, range).await?,
+				None => self.get(key).await?,
+			};
+			Ok(Conditional::Applied(data))
+		})
+	}
+
+	/// Retrieves a range of bytes from the specified key.
+	///
+	/// Returns `Ok(None)` if the key does not exist.
+	fn get_range<'a>(
+		&'a self,
+		key: &'a ObjectKey,
+		range: ObjectRange,
+	) -> Pin<Box<dyn Future<Output = Result<Option<Bytes>, String>> + Send + 'a>> {
+		Box::pin(async move {
+			let Some(stream) = self.get_stream(key, Some(range)).await? else {
+				return Ok(None);
+			};
+			let chunks: Vec<Bytes> = stream.try_collect().await?;
+			Ok(Some(chunks.concat().into()))
+		})
+	}
+
+	/// Retrieves data from the specified key as a stream of chunks, without
+	/// loading the whole object into memory.
+	///
+	/// If a range is specified, only the bytes within the range are returned.
+	/// Returns `Ok(None)` if the key does not exist.
+	fn get_stream<'a>(
+		&'a self,
+		key: &'a ObjectKey,
+		range: Option<ObjectRange>,
+	) -> Pin<Box<dyn Future<Output = Result<Option<ByteStream>, String>> + Send + 'a>>;
+
+	/// Stores a stream of data at the specified key, overwriting any existing data.
+	///
+	/// The data is written through a [`MultipartUpload`], so the object only
+	/// becomes visible once the stream has been fully consumed.
+	fn put_stream<'a>(
+		&'a self,
+		key: &'a ObjectKey,
+		mut data: ByteStream,
+	) -> Pin<Box<dyn Future<Output = Result<(), String>> + Send + 'a>> {
+		Box::pin(async move {
+			let mut upload = self.put_multipart(key).await?;
+			while let Some(chunk) = data.next().await {
+				let res = match chunk {
+					Ok(chunk) => upload.put_part(chunk).await,
+					Err(e) => Err(e),
+				};
+				if let Err(e) = res {
+					// Attempt to clean up the partial upload
+					if let Err(abort) = upload.abort().await {
+						warn!("Failed to abort multipart upload: {abort}");
+					}
+					return Err(e);
+				}
+			}
+			upload.complete().await
+		})
+	}
+
+	/// Starts a chunked upload to the specified key.
+	#[allow(clippy::type_complexity)]
+	fn put_multipart<'a>(
+		&'a self,
+		key: &'a ObjectKey,
+	) -> Pin<Box<dyn Future<Output = Result<Box<dyn MultipartUpload>, String>> + Send + 'a>>;
+
 	/// Retrieves metadata for the specified key without fetching the data.
 	///
 	/// Returns `Ok(None)` if the key does not exist.
@@ -148,11 +499,14 @@ pub trait ObjectStore: Send + Sync + 'static {
 	) -> Pin<Box<dyn Future<Output = Result<(), String>> + Send + 'a>>;
 
 	/// Copies data from one key to another only if the target does not exist.
+	///
+	/// Returns whether the data was copied, which is not the case when the
+	/// target exists or when the source does not exist.
 	fn copy_if_not_exists<'a>(
 		&'a self,
 		key: &'a ObjectKey,
 		target: &'a ObjectKey,
-	) -> Pin<Box<dyn Future<Output = Result<(), String>> + Send + 'a>>;
+	) -> Pin<Box<dyn Future<Output = Result<bool, String>> + Send + 'a>>;
 
 	/// Moves data from one key to another, overwriting the target if it exists.
 	fn rename<'a>(
@@ -190,10 +544,27 @@ impl ObjectStore for Arc<dyn ObjectStore> {
 		&'a self,
 		key: &'a ObjectKey,
 		data: Bytes,
-	) -> Pin<Box<dyn Future<Output = Result<(), String>> + Send + 'a>> {
+	) -> Pin<Box<dyn Future<Output = Result<bool, String>> + Send + 'a>> {
 		(**self).put_if_not_exists(key, data)
 	}
 
+	fn put_opts<'a>(
+		&'a self,
+		key: &'a ObjectKey,
+		data: Bytes,
+		opts: PutOptions,
+	) -> Pin<Box<dyn Future<Output = Result<Conditional<()>, String>> + Send + 'a>> {
+		(**self).put_opts(key, data, opts)
+	}
+
+	fn get_opts<'a>(
+		&'a self,
+		key: &'a ObjectKey,
+		opts: GetOptions,
+	) -> Pin<Box<dyn Future<Output = Result<Conditional<Option<Bytes>>, String>> + Send + 'a>> {
+		(**self).get_opts(key, opts)
+	}
+
 	fn get<'a>(
 		&'a self,
 		key: &'a ObjectKey,
@@ -201,6 +572,37 @@ impl ObjectStore for Arc<dyn ObjectStore> {
 		(**self).get(key)
 	}
 
+	fn get_range<'a>(
+		&'a self,
+		key: &'a ObjectKey,
+		range: ObjectRange,
+	) -> Pin<Box<dyn Future<Output = Result<Option<Bytes>, String>> + Send + 'a>> {
+		(**self).get_range(key, range)
+	}
+
+	fn get_stream<'a>(
+		&'a self,
+		key: &'a ObjectKey,
+		range: Option<ObjectRange>,
+	) -> Pin<Box<dyn Future<Output = Result<Option<ByteStream>, String>> + Send + 'a>> {
+		(**self).get_stream(key, range)
+	}
+
+	fn put_stream<'a>(
+		&'a self,
+		key: &'a ObjectKey,
+		data: ByteStream,
+	) -> Pin<Box<dyn Future<Output = Result<(), String>> + Send + 'a>> {
+		(**self).put_stream(key, data)
+	}
+
+	fn put_multipart<'a>(
+		&'a self,
+		key: &'a ObjectKey,
+	) -> Pin<Box<dyn Future<Output = Result<Box<dyn MultipartUpload>, String>> + Send + 'a>> {
+		(**self).put_multipart(key)
+	}
+
 	fn head<'a>(
 		&'a self,
 		key: &'a ObjectKey,
@@ -234,7 +636,7 @@ impl ObjectStore for Arc<dyn ObjectStore> {
 		&'a self,
 		key: &'a ObjectKey,
 		target: &'a ObjectKey,
-	) -> Pin<Box<dyn Future<Output = Result<(), String>> + Send + 'a>> {
+	) -> Pin<Box<dyn Future<Output = Result<bool, String>> + Send + 'a>> {
 		(**self).copy_if_not_exists(key, target)
 	}
 
@@ -261,3 +663,68 @@ impl ObjectStore for Arc<dyn ObjectStore> {
 		(**self).list(opts)
 	}
 }
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn precondition(if_match: Option<&str>, if_none_match: Option<&str>) -> Precondition {
+		Precondition {
+			if_match: if_match.map(String::from),
+			if_none_match: if_none_match.map(String::from),
+		}
+	}
+
+	#[test]
+	fn precondition_if_match() {
+		let p = precondition(Some("abc"), None);
+		assert_eq!(p.check(Some("abc")), Ok(()));
+		assert_eq!(p.check(Some("\"abc\"")), Ok(()));
+		assert_eq!(p.check(Some("def")), Err(PreconditionFailure::Failed));
+		assert_eq!(p.check(None), Err(PreconditionFailure::Failed));
+
+		let p = precondition(Some("*"), None);
+		assert_eq!(p.check(Some("def")), Ok(()));
+		assert_eq!(p.check(None), Err(PreconditionFailure::Failed));
+	}
+
+	#[test]
+	fn precondition_if_none_match() {
+		let p = precondition(None, Some("abc"));
+		assert_eq!(p.check(Some("abc")), Err(PreconditionFailure::NotModified));
+		assert_eq!(p.check(Some("def")), Ok(()));
+		assert_eq!(p.check(None), Ok(()));
+
+		let p = precondition(None, Some("*"));
+		assert_eq!(p.check(Some("def")), Err(PreconditionFailure::NotModified));
+		assert_eq!(p.check(None), Ok(()));
+	}
+
+	#[test]
+	fn get_options_without_range() {
+		let obj = Object::from(map! {
+			"if_match".to_string() => Value::from("abc"),
+		});
+		let opts = GetOptions::try_from(obj).unwrap();
+		assert_eq!(opts.range, None);
+		assert_eq!(opts.precondition, precondition(Some("abc"), None));
+	}
+
+	#[test]
+	fn options_reject_unknown_keys() {
+		let obj = Object::from(map! {
+			"if_match".to_string() => Value::from("abc"),
+			"if_matches".to_string() => Value::from("abc"),
+		});
+		assert!(PutOptions::try_from(obj.clone()).is_err());
+		assert!(GetOptions::try_from(obj).is_err());
+
+		let obj = Object::from(map! {
+			"content_type".to_string() => Value::from("text/plain"),
+			"if_none_match".to_string() => Value::from("*"),
+		});
+		let opts = PutOptions::try_from(obj).unwrap();
+		assert_eq!(opts.content_type.as_deref(), Some("text/plain"));
+		assert_eq!(opts.precondition, precondition(None, Some("*")));
+	}
+}
@@ -8,7 +8,10 @@ use std::pin::Pin;
 
 use bytes::Bytes;
 
-use super::{ListOptions, ObjectKey, ObjectMeta, ObjectStore};
+use super::{
+	ByteStream, Conditional, GetOptions, ListOptions, MultipartUpload, ObjectKey, ObjectMeta,
+	ObjectRange, ObjectStore, PutOptions,
+};
 
 /// A wrapper that adds a prefix to all keys in an underlying [`ObjectStore`].
 ///
@@ -57,12 +60,33 @@ impl<T: ObjectStore> ObjectStore for PrefixedStore<T> {
 		&'a self,
 		key: &'a ObjectKey,
 		data: Bytes,
-	) -> Pin<Box<dyn Future<Output = Result<(), String>> + Send + 'a>> {
+	) -> Pin<Box<dyn Future<Output = Result<bool, String>> + Send + 'a>> {
 		let full_key = self.prefix.join(key);
 
 		Box::pin(async move { self.store.put_if_not_exists(&full_key, data).await })
 	}
 
+	fn put_opts<'a>(
+		&'a self,
+		key: &'a ObjectKey,
+		data: Bytes,
+		opts: PutOptions,
+	) -> Pin<Box<dyn Future<Output = Result<Conditional<()>, String>> + Send + 'a>> {
+		let full_key = self.prefix.join(key);
+
+		Box::pin(async move { self.store.put_opts(&full_key, data, opts).await })
+	}
+
+	fn get_opts<'a>(
+		&'a self,
+		key: &'a ObjectKey,
+		opts: GetOptions,
+	) -> Pin<Box<dyn Future<Output = Result<Conditional<Option<Bytes>>, String>> + Send + 'a>> {
+		let full_key = self.prefix.join(key);
+
+		Box::pin(async move { self.store.get_opts(&full_key, opts).await })
+	}
+
 	fn get<'a>(
 		&'a self,
 		key: &'a ObjectKey,
@@ -72,6 +96,45 @@ impl<T: ObjectStore> ObjectStore for PrefixedStore<T> {
 		Box::pin(async move { self.store.get(&full_key).await })
 	}
 
+	fn get_range<'a>(
+		&'a self,
+		key: &'a ObjectKey,
+		range: ObjectRange,
+	) -> Pin<Box<dyn Future<Output = Result<Option<Bytes>, String>> + Send + 'a>> {
+		let full_key = self.prefix.join(key);
+
+		Box::pin(async move { self.store.get_range(&full_key, range).await })
+	}
+
+	fn get_stream<'a>(
+		&'a self,
+		key: &'a ObjectKey,
+		range: Option<ObjectRange>,
+	) -> Pin<Box<dyn Future<Output = Result<Option<ByteStream>, String>> + Send + 'a>> {
+		let full_key = self.prefix.join(key);
+
+		Box::pin(async move { self.store.get_stream(&full_key, range).await })
+	}
+
+	fn put_stream<'a>(
+		&'a self,
+		key: &'a ObjectKey,
+		data: ByteStream,
+	) -> Pin<Box<dyn Future<Output = Result<(), String>> + Send + 'a>> {
+		let full_key = self.prefix.join(key);
+
+		Box::pin(async move { self.store.put_stream(&full_key, data).await })
+	}
+
+	fn put_multipart<'a>(
+		&'a self,
+		key: &'a ObjectKey,
+	) -> Pin<Box<dyn Future<Output = Result<Box<dyn MultipartUpload>, String>> + Send + 'a>> {
+		let full_key = self.prefix.join(key);
+
+		Box::pin(async move { self.store.put_multipart(&full_key).await })
+	}
+
 	fn head<'a>(
 		&'a self,
 		key: &'a ObjectKey,
@@ -119,7 +182,7 @@ impl<T: ObjectStore> ObjectStore for PrefixedStore<T> {
 		&'a self,
 		key: &'a ObjectKey,
 		target: &'a ObjectKey,
-	) -> Pin<Box<dyn Future<Output = Result<(), String>> + Send + 'a>> {
+	) -> Pin<Box<dyn Future<Output = Result<bool, String>> + Send + 'a>> {
 		let full_key = self.prefix.join(key);
 		let full_target = self.prefix.join(target);
 
@@ -0,0 +1,788 @@
+//! S3-compatible object store implementation.
+//!
+//! This module provides an [`ObjectStore`] implementation which talks the S3 REST
+//! protocol, allowing buckets to be backed by Amazon S3, MinIO, Cloudflare R2, or
+//! any other S3-compatible storage service.
+
+use std::collections::BTreeMap;
+use std::future::Future;
+use std::pin::Pin;
+
+use bytes::Bytes;
+use futures::{StreamExt, TryStreamExt};
+use object_store::aws::{
+	AmazonS3, AmazonS3Builder, AmazonS3ConfigKey, S3ConditionalPut, S3CopyIfNotExists,
+};
+use object_store::path::Path;
+use object_store::{
+	Attribute, Attributes, GetOptions, ObjectStore as _, ObjectStoreExt, PutMode, PutOptions,
+	UpdateVersion, WriteMultipart,
+};
+use url::Url;
+
+use super::{
+	ByteStream, Conditional, GetOptions as ObjectGetOptions, ListOptions, MultipartUpload,
+	ObjectKey, ObjectMeta, ObjectRange, ObjectStore, PreconditionFailure,
+	PutOptions as ObjectPutOptions,
+};
+use crate::cnf::BUCKET_S3_ENV_ALLOWLIST;
+use crate::err::Error;
+
+/// The query options which can be specified on an `s3://` backend url.
+///
+/// Any option which is not specified falls back to the standard `AWS_*`
+/// environment variables of the server process, but only for the global
+/// bucket, and for S3 buckets listed in `SURREAL_BUCKET_S3_ENV_ALLOWLIST`.
+const ALLOWED_OPTIONS: &[&str] = &[
+	"endpoint",
+	"region",
+	"access_key_id",
+	"secret_access_key",
+	"session_token",
+	"path_style",
+	"allow_http",
+	"unsigned_payload",
+	"skip_signature",
+	"checksum_algorithm",
+	"conditional_put",
+	"copy_if_not_exists",
+];
+
+/// The maximum number of parts which are uploaded concurrently
+const MAX_CONCURRENT_PARTS: usize = 8;
+
+/// Options for configuring the S3Store
+///
+/// This type intentionally does not implement `Debug`, as it may contain
+/// credentials which must never end up in logs or error messages.
+#[derive(Clone)]
+pub struct S3StoreOptions {
+	builder: AmazonS3Builder,
+	root: ObjectKey,
+}
+
+/// A store implementation that uses an S3-compatible storage service
+///
+/// # URL Format
+/// - `s3://bucket`
+/// - `s3://bucket/prefix?endpoint=http://localhost:9000&region=us-east-1&path_style=true`
+///
+/// The path component of the URL is used as a root prefix for all keys, so
+/// that multiple SurrealDB buckets can share a single S3 bucket. Supported
+/// query options are:
+/// - `endpoint` - a custom endpoint for S3-compatible services
+/// - `region` - the region of the S3 bucket
+/// - `access_key_id`, `secret_access_key`, `session_token` - static credentials
+/// - `path_style` - whether to use path-style instead of virtual-hosted-style requests
+/// - `allow_http` - whether to allow plain HTTP endpoints
+/// - `unsigned_payload`, `skip_signature`, `checksum_algorithm` - request signing options
+/// - `conditional_put`, `copy_if_not_exists` - how conditional requests are performed
+#[derive(Clone)]
+pub struct S3Store {
+	client: AmazonS3,
+	root: ObjectKey,
+}
+
+impl S3Store {
+	/// Create a new S3Store with the given options
+	pub fn new(options: S3StoreOptions) -> Result<Self, Error> {
+		let client = options
+			.builder
+			.build()
+			.map_err(|e| Error::InvalidBucketUrl(format!("Failed to configure S3 client: {e}")))?;
+		Ok(S3Store {
+			client,
+			root: options.root,
+		})
+	}
+
+	/// Parse a URL into S3StoreOptions
+	///
+	/// The `AWS_*` environment configuration of the server process, including
+	/// its credentials, is only used for the global bucket, which is configured
+	/// by the operator, and for S3 buckets which the operator has explicitly
+	/// listed in `SURREAL_BUCKET_S3_ENV_ALLOWLIST`. Any other bucket needs to
+	/// specify its own credentials in the url.
+	pub fn parse_url(url_str: &str, global: bool) -> Result<Option<S3StoreOptions>, Error> {
+		let Ok(url) = Url::parse(url_str) else {
+			return Ok(None);
+		};
+
+		if url.scheme() != "s3" {
+			return Ok(None);
+		}
+
+		let Some(bucket) = url.host_str().filter(|x| !x.is_empty()) else {
+			return Err(Error::InvalidBucketUrl(
+				"Expected to find a bucket name in the S3 url".to_string(),
+			));
+		};
+
+		// Load any defaults from the environment if permitted, and default
+		// to the conditional request modes supported by native S3 and MinIO
+		let builder = if uses_env(bucket, global) {
+			AmazonS3Builder::from_env()
+		} else {
+			AmazonS3Builder::new()
+		};
+		let mut builder = builder
+			.with_bucket_name(bucket)
+			.with_conditional_put(S3ConditionalPut::ETagMatch)
+			.with_copy_if_not_exists(S3CopyIfNotExists::Multipart);
+
+		for (key, value) in url.query_pairs() {
+			if !ALLOWED_OPTIONS.contains(&key.as_ref()) {
+				return Err(Error::InvalidBucketUrl(format!(
+					"Unsupported query option `{key}` for S3 bucket backend"
+				)));
+			}
+			// Path-style addressing is the inverse of virtual-hosted-style requests
+			if key == "path_style" {
+				let path_style = parse_bool(&key, &value)?;
+				builder = builder.with_virtual_hosted_style_request(!path_style);
+				continue;
+			}
+			let config = key.parse::<AmazonS3ConfigKey>().map_err(|_| {
+				Error::InvalidBucketUrl(format!(
+					"Unsupported query option `{key}` for S3 bucket backend"
+				))
+			})?;
+			builder = builder.with_config(config, value);
+		}
+
+		Ok(Some(S3StoreOptions {
+			builder,
+			root: ObjectKey::new(url.path()),
+		}))
+	}
+
+	/// Returns the custom endpoint specified on an `s3://` backend url, which
+	/// needs to be checked against the network capabilities before the
+	/// bucket is defined.
+	pub(crate) fn endpoint(url_str: &str) -> Result<Option<Url>, Error> {
+		let Ok(url) = Url::parse(url_str) else {
+			return Ok(None);
+		};
+		if url.scheme() != "s3" {
+			return Ok(None);
+		}
+		match url.query_pairs().find(|(k, _)| k == "endpoint") {
+			Some((_, endpoint)) => Url::parse(&endpoint)
+				.map(Some)
+				.map_err(|_| Error::InvalidBucketUrl(format!("Invalid S3 endpoint `{endpoint}`"))),
+			None => Ok(None),
+		}
+	}
+
+	/// Convert an object key into a path within the S3 bucket
+	fn to_path(&self, key: &ObjectKey) -> Path {
+		Path::from(self.root.join(key).as_str())
+	}
+
+	/// Convert a path within the S3 bucket back into an object key
+	fn to_key(&self, path: &Path) -> ObjectKey {
+		let key = ObjectKey::new(path.as_ref());
+		key.strip_prefix(&self.root).unwrap_or(key)
+	}
+
+	/// Convert S3 object metadata into object metadata for this store
+	fn to_meta(&self, meta: object_store::ObjectMeta) -> ObjectMeta {
+		ObjectMeta {
+			size: meta.size,
+			updated: meta.last_modified,
+			key: self.to_key(&meta.location),
+			content_type: None,
+			etag: meta.e_tag,
+			metadata: BTreeMap::new(),
+		}
+	}
+}
+
+/// Whether an S3 bucket may use the `AWS_*` environment configuration
+fn uses_env(bucket: &str, global: bool) -> bool {
+	global || BUCKET_S3_ENV_ALLOWLIST.iter().any(|allowed| allowed == bucket)
+}
+
+/// Convert the content type and user metadata into S3 object attributes
+fn to_attributes(opts: &ObjectPutOptions) -> Attributes {
+	let mut attributes = Attributes::new();
+	if let Some(content_type) = &opts.content_type {
+		attributes.insert(Attribute::ContentType, content_type.clone().into());
+	}
+	for (k, v) in &opts.metadata {
+		attributes.insert(Attribute::Metadata(k.clone().into()), v.clone().into());
+	}
+	attributes
+}
+
+/// Apply the content type and user metadata from S3 object attributes
+fn apply_attributes(meta: &mut ObjectMeta, attributes: &Attributes) {
+	for (attribute, value) in attributes.iter() {
+		match attribute {
+			Attribute::ContentType => meta.content_type = Some(value.to_string()),
+			Attribute::Metadata(k) => {
+				meta.metadata.insert(k.to_string(), value.to_string());
+			}
+			_ => (),
+		}
+	}
+}
+
+/// A chunked upload into an [`S3Store`].
+///
+/// Data is buffered into parts of the minimum size accepted by S3, which are
+/// uploaded concurrently in the background using an S3 multipart upload.
+struct S3Upload {
+	writer: Option<WriteMultipart>,
+}
+
+impl MultipartUpload for S3Upload {
+	fn put_part(
+		&mut self,
+		data: Bytes,
+	) -> Pin<Box<dyn Future<Output = Result<(), String>> + Send + '_>> {
+		Box::pin(async move {
+			let Some(writer) = self.writer.as_mut() else {
+				return Err("The upload has already been completed or aborted".to_string());
+			};
+			// Apply backpressure if too many parts are in flight
+			writer
+				.wait_for_capacity(MAX_CONCURRENT_PARTS)
+				.await
+				.map_err(|e| format!("Failed to upload part: {}", e))?;
+			writer.put(data);
+			Ok(())
+		})
+	}
+
+	fn complete(&mut self) -> Pin<Box<dyn Future<Output = Result<(), String>> + Send + '_>> {
+		Box::pin(async move {
+			let Some(writer) = self.writer.take() else {
+				return Err("The upload has already been completed or aborted".to_string());
+			};
+			writer.finish().await.map_err(|e| format!("Failed to complete upload: {}", e))?;
+			Ok(())
+		})
+	}
+
+	fn abort(&mut self) -> Pin<Box<dyn Future<Output = Result<(), String>> + Send + '_>> {
+		Box::pin(async move {
+			if let Some(writer) = self.writer.take() {
+				writer.abort().await.map_err(|e| format!("Failed to abort upload: {}", e))?;
+			}
+			Ok(())
+		})
+	}
+}
+
+/// Parse a boolean query option, treating an empty value as `true`
+fn parse_bool(key: &str, value: &str) -> Result<bool, Error> {
+	if value.is_empty() {
+		return Ok(true);
+	}
+	value.parse().map_err(|_| {
+		Error::InvalidBucketUrl(format!("Expected to find a bool for query option `{key}`"))
+	})
+}
+
+/// Check if an error signifies that a conditional request was rejected
+fn is_conflict(e: &object_store::Error) -> bool {
+	matches!(
+		e,
+		object_store::Error::AlreadyExists { .. } | object_store::Error::Precondition { .. }
+	)
+}
+
+/// Check if an error signifies that an object does not exist
+fn is_not_found(e: &object_store::Error) -> bool {
+	matches!(e, object_store::Error::NotFound { .. })
+}
+
+impl ObjectStore for S3Store {
+	fn put<'a>(
+		&'a self,
+		key: &'a ObjectKey,
+		data: Bytes,
+	) -> Pin<Box<dyn Future<Output = Result<(), String>> + Send + 'a>> {
+		Box::pin(async move {
+			self.client
+				.put(&self.to_path(key), data.into())
+				.await
+				.map_err(|e| format!("Failed to put object: {}", e))?;
+
+			Ok(())
+		})
+	}
+
+	fn put_if_not_exists<'a>(
+		&'a self,
+		key: &'a ObjectKey,
+		data: Bytes,
+	) -> Pin<Box<dyn Future<Output = Result<bool, String>> + Send + 'a>> {
+		Box::pin(async move {
+			// Send the request with an `If-None-Match: *` precondition
+			let opts = PutOptions::from(PutMode::Create);
+
+			match self.client.put_opts(&self.to_path(key), data.into(), opts).await {
+				Ok(_) => Ok(true),
+				// The object already exists, so this is a no-op
+				Err(e) if is_conflict(&e) => Ok(false),
+				Err(e) => Err(format!("Failed to put object: {}", e)),
+			}
+		})
+	}
+
+	fn put_opts<'a>(
+		&'a self,
+		key: &'a ObjectKey,
+		data: Bytes,
+		opts: ObjectPutOptions,
+	) -> Pin<Box<dyn Future<Output = Result<Conditional<()>, String>> + Send + 'a>> {
+		Box::pin(async move {
+			let path = self.to_path(key);
+			let precondition = &opts.precondition;
+			// Use the native S3 preconditions where possible
+			let mode = match (&precondition.if_match, &precondition.if_none_match) {
+				(None, None) => PutMode::Overwrite,
+				(None, Some(etag)) if etag == "*" => PutMode::Create,
+				(Some(etag), None) if etag != "*" => PutMode::Update(UpdateVersion {
+					e_tag: Some(etag.clone()),
+					version: None,
+				}),
+				_ => {
+					// Otherwise check the preconditions before the write
+					let etag = match self.client.head(&path).await {
+						Ok(meta) => meta.e_tag,
+						Err(e) if is_not_found(&e) => None,
+						Err(e) => return Err(format!("Failed to get metadata: {}", e)),
+					};
+					if precondition.check(etag.as_deref()).is_err() {
+						return Ok(Conditional::Skipped(PreconditionFailure::Failed));
+					}
+					PutMode::Overwrite
+				}
+			};
+
+			let put = PutOptions {
+				mode,
+				attributes: to_attributes(&opts),
+				..Default::default()
+			};
+
+			match self.client.put_opts(&path, data.into(), put).await {
+				Ok(_) => Ok(Conditional::Applied(())),
+				Err(e) if is_conflict(&e) || is_not_found(&e) => {
+					Ok(Conditional::Skipped(PreconditionFailure::Failed))
+				}
+				Err(e) => Err(format!("Failed to put object: {}", e)),
+			}
+		})
+	}
+
+	fn get_opts<'a>(
+		&'a self,
+		key: &'a ObjectKey,
+		opts: ObjectGetOptions,
+	) -> Pin<Box<dyn Future<Output = Result<Conditional<Option<Bytes>>, String>> + Send + 'a>> {
+		Box::pin(async move {
+			let precondition = opts.precondition;
+			let mut get = GetOptions {
+				if_match: precondition.if_match.clone(),
+				if_none_match: precondition.if_none_match.clone(),
+				..Default::default()
+			};
+
+			if let Some(range) = opts.range {
+				// Resolve the range against the size of the object, as S3
+				// rejects ranges which start beyond the end of the object
+				let Some(meta) = self.head(key).await? else {
+					return Ok(match precondition.check(None) {
+						Ok(()) => Conditional::Applied(None),
+						Err(e) => Conditional::Skipped(e),
+					});
+				};
+				let range = range.clamp(meta.size);
+				if range.is_empty() {
+					return Ok(match precondition.check(meta.etag.as_deref()) {
+						Ok(()) => Conditional::Applied(Some(Bytes::new())),
+						Err(e) => Conditional::Skipped(e),
+					});
+				}
+				get = get.with_range(Some(range));
+			}
+
+			let res = match self.client.get_opts(&self.to_path(key), get).await {
+				Ok(res) => res,
+				Err(object_store::Error::NotModified {
+					..
+				}) => return Ok(Conditional::Skipped(PreconditionFailure::NotModified)),
+				Err(object_store::Error::Precondition {
+					..
+				}) => return Ok(Conditional::Skipped(PreconditionFailure::Failed)),
+				Err(e) if is_not_found(&e) => {
+					return Ok(match precondition.check(None) {
+						Ok(()) => Conditional::Applied(None),
+						Err(e) => Conditional::Skipped(e),
+					});
+				}
+				Err(e) => return Err(format!("Failed to get object: {}", e)),
+			};
+
+			let data = res.bytes().await.map_err(|e| format!("Failed to read object: {}", e))?;
+
+			Ok(Conditional::Applied(Some(data)))
+		})
+	}
+
+	fn get<'a>(
+		&'a self,
+		key: &'a ObjectKey,
+	) -> Pin<Box<dyn Future<Output = Result<Option<Bytes>, String>> + Send + 'a>> {
+		Box::pin(async move {
+			let res = match self.client.get(&self.to_path(key)).await {
+				Ok(res) => res,
+				Err(e) if is_not_found(&e) => return Ok(None),
+				Err(e) => return Err(format!("Failed to get object: {}", e)),
+			};
+
+			let data = res.bytes().await.map_err(|e| format!("Failed to read object: {}", e))?;
+
+			Ok(Some(data))
+		})
+	}
+
+	fn get_stream<'a>(
+		&'a self,
+		key: &'a ObjectKey,
+		range: Option<ObjectRange>,
+	) -> Pin<Box<dyn Future<Output = Result<Option<ByteStream>, String>> + Send + 'a>> {
+		Box::pin(async move {
+			let mut opts = GetOptions::new();
+
+			if let Some(range) = range {
+				// Resolve the range against the size of the object, as S3
+				// rejects ranges which start beyond the end of the object
+				let Some(meta) = self.head(key).await? else {
+					return Ok(None);
+				};
+				let range = range.clamp(meta.size);
+				if range.is_empty() {
+					return Ok(Some(Box::pin(futures::stream::empty()) as ByteStream));
+				}
+				opts = opts.with_range(Some(range));
+			}
+
+			let res = match self.client.get_opts(&self.to_path(key), opts).await {
+				Ok(res) => res,
+				Err(e) if is_not_found(&e) => return Ok(None),
+				Err(e) => return Err(format!("Failed to get object: {}", e)),
+			};
+
+			let stream = res.into_stream().map_err(|e| format!("Failed to read object: {}", e));
+
+			Ok(Some(Box::pin(stream) as ByteStream))
+		})
+	}
+
+	fn put_multipart<'a>(
+		&'a self,
+		key: &'a ObjectKey,
+	) -> Pin<Box<dyn Future<Output = Result<Box<dyn MultipartUpload>, String>> + Send + 'a>> {
+		Box::pin(async move {
+			let upload = self
+				.client
+				.put_multipart(&self.to_path(key))
+				.await
+				.map_err(|e| format!("Failed to start upload: {}", e))?;
+
+			Ok(Box::new(S3Upload {
+				writer: Some(WriteMultipart::new(upload)),
+			}) as Box<dyn MultipartUpload>)
+		})
+	}
+
+	fn head<'a>(
+		&'a self,
+		key: &'a ObjectKey,
+	) -> Pin<Box<dyn Future<Output = Result<Option<ObjectMeta>, String>> + Send + 'a>> {
+		Box::pin(async move {
+			// A HEAD request through `get_opts` also returns the object attributes
+			let opts = GetOptions {
+				head: true,
+				..Default::default()
+			};
+			match self.client.get_opts(&self.to_path(key), opts).await {
+				Ok(res) => {
+					let mut meta = self.to_meta(res.meta.clone());
+					apply_attributes(&mut meta, &res.attributes);
+					Ok(Some(meta))
+				}
+				Err(e) if is_not_found(&e) => Ok(None),
+				Err(e) => Err(format!("Failed to get metadata: {}", e)),
+			}
+		})
+	}
+
+	fn delete<'a>(
+		&'a self,
+		key: &'a ObjectKey,
+	) -> Pin<Box<dyn Future<Output = Result<(), String>> + Send + 'a>> {
+		Box::pin(async move {
+			match self.client.delete(&self.to_path(key)).await {
+				Ok(_) => Ok(()),
+				Err(e) if is_not_found(&e) => Ok(()),
+				Err(e) => Err(format!("Failed to delete object: {}", e)),
+			}
+		})
+	}
+
+	fn exists<'a>(
+		&'a self,
+		key: &'a ObjectKey,
+	) -> Pin<Box<dyn Future<Output = Result<bool, String>> + Send + 'a>> {
+		Box::pin(async move {
+			match self.client.head(&self.to_path(key)).await {
+				Ok(_) => Ok(true),
+				Err(e) if is_not_found(&e) => Ok(false),
+				Err(e) => Err(format!("Failed to check if object exists: {}", e)),
+			}
+		})
+	}
+
+	fn copy<'a>(
+		&'a self,
+		key: &'a ObjectKey,
+		target: &'a ObjectKey,
+	) -> Pin<Box<dyn Future<Output = Result<(), String>> + Send + 'a>> {
+		Box::pin(async move {
+			match self.client.copy(&self.to_path(key), &self.to_path(target)).await {
+				Ok(_) => Ok(()),
+				Err(e) if is_not_found(&e) => {
+					Err(format!("Source key does not exist: {}", key.as_str()))
+				}
+				Err(e) => Err(format!("Failed to copy object: {}", e)),
+			}
+		})
+	}
+
+	fn copy_if_not_exists<'a>(
+		&'a self,
+		key: &'a ObjectKey,
+		target: &'a ObjectKey,
+	) -> Pin<Box<dyn Future<Output = Result<bool, String>> + Send + 'a>> {
+		Box::pin(async move {
+			match self.client.copy_if_not_exists(&self.to_path(key), &self.to_path(target)).await {
+				Ok(_) => Ok(true),
+				// The target already exists, so this is a no-op
+				Err(e) if is_conflict(&e) => Ok(false),
+				// Silently ignore operations on non-existent source objects
+				Err(e) if is_not_found(&e) => Ok(false),
+				Err(e) => Err(format!("Failed to copy object: {}", e)),
+			}
+		})
+	}
+
+	fn rename<'a>(
+		&'a self,
+		key: &'a ObjectKey,
+		target: &'a ObjectKey,
+	) -> Pin<Box<dyn Future<Output = Result<(), String>> + Send + 'a>> {
+		Box::pin(async move {
+			match self.client.rename(&self.to_path(key), &self.to_path(target)).await {
+				Ok(_) => Ok(()),
+				Err(e) if is_not_found(&e) => {
+					Err(format!("Source key does not exist: {}", key.as_str()))
+				}
+				Err(e) => Err(format!("Failed to rename object: {}", e)),
+			}
+		})
+	}
+
+	fn rename_if_not_exists<'a>(
+		&'a self,
+		key: &'a ObjectKey,
+		target: &'a ObjectKey,
+	) -> Pin<Box<dyn Future<Output = Result<(), String>> + Send + 'a>> {
+		Box::pin(async move {
+			// S3 has no native rename, so this performs a conditional copy
+			// followed by a deletion of the source object
+			match self.client.rename_if_not_exists(&self.to_path(key), &self.to_path(target)).await
+			{
+				Ok(_) => Ok(()),
+				// The target already exists, so this is a no-op
+				Err(e) if is_conflict(&e) => Ok(()),
+				Err(e) if is_not_found(&e) => {
+					Err(format!("Source key does not exist: {}", key.as_str()))
+				}
+				Err(e) => Err(format!("Failed to rename object: {}", e)),
+			}
+		})
+	}
+
+	fn list<'a>(
+		&'a self,
+		opts: &'a ListOptions,
+	) -> Pin<Box<dyn Future<Output = Result<Vec<ObjectMeta>, String>> + Send + 'a>> {
+		Box::pin(async move {
+			// Combine the store's root with the request prefix
+			let prefix = self.to_path(&opts.prefix.clone().unwrap_or_default());
+
+			// S3 returns keys in lexicographical order, so the start key
+			// can be passed through as the listing offset
+			let stream = match opts.start {
+				Some(ref start) => {
+					self.client.list_with_offset(Some(&prefix), &self.to_path(start))
+				}
+				None => self.client.list(Some(&prefix)),
+			};
+
+			// Apply limit if specified
+			let stream = match opts.limit {
+				Some(limit) => stream.take(limit).boxed(),
+				None => stream,
+			};
+
+			let objects = stream
+				.map_ok(|meta| self.to_meta(meta))
+				.try_collect()
+				.await
+				.map_err(|e| format!("Failed to list objects: {}", e))?;
+
+			Ok(objects)
+		})
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn parse_url_ignores_other_schemes() {
+		assert!(S3Store::parse_url("memory", false).unwrap().is_none());
+		assert!(S3Store::parse_url("file:///tmp/bucket", false).unwrap().is_none());
+	}
+
+	#[test]
+	fn parse_url_requires_bucket_name() {
+		let res = S3Store::parse_url("s3:///prefix", false);
+		assert!(matches!(res, Err(Error::InvalidBucketUrl(_))));
+	}
+
+	#[test]
+	fn parse_url_rejects_unknown_options() {
+		let res = S3Store::parse_url("s3://bucket?metadata_endpoint=http://localhost", false);
+		assert!(matches!(res, Err(Error::InvalidBucketUrl(_))));
+		let res = S3Store::parse_url("s3://bucket?path_style=maybe", false);
+		assert!(matches!(res, Err(Error::InvalidBucketUrl(_))));
+	}
+
+	#[test]
+	fn keys_are_mapped_under_root_prefix() {
+		let opts = S3Store::parse_url(
+			"s3://bucket/some/prefix?endpoint=http://localhost:9000&region=us-east-1&path_style&allow_http=true&access_key_id=minio&secret_access_key=minio123",
+			false,
+		)
+		.unwrap()
+		.unwrap();
+		let store = S3Store::new(opts).unwrap();
+		let key = ObjectKey::new("/folder/file.txt");
+		let path = store.to_path(&key);
+		assert_eq!(path.as_ref(), "some/prefix/folder/file.txt");
+		assert_eq!(store.to_key(&path), key);
+	}
+
+	#[test]
+	fn keys_are_mapped_without_root_prefix() {
+		let opts = S3Store::parse_url("s3://bucket?region=us-east-1", false).unwrap().unwrap();
+		let store = S3Store::new(opts).unwrap();
+		let key = ObjectKey::new("/file.txt");
+		let path = store.to_path(&key);
+		assert_eq!(path.as_ref(), "file.txt");
+		assert_eq!(store.to_key(&path), key);
+	}
+
+	#[test]
+	fn env_is_only_used_when_permitted() {
+		assert!(uses_env("bucket", true));
+		assert!(!uses_env("bucket", false));
+	}
+
+	#[test]
+	fn endpoint_is_extracted() {
+		let url = "s3://bucket?endpoint=http://localhost:9000&region=us-east-1";
+		let endpoint = S3Store::endpoint(url).unwrap().unwrap();
+		assert_eq!(endpoint.host_str(), Some("localhost"));
+		assert_eq!(endpoint.port(), Some(9000));
+		assert!(S3Store::endpoint("s3://bucket").unwrap().is_none());
+		assert!(S3Store::endpoint("file:///tmp/bucket").unwrap().is_none());
+		assert!(S3Store::endpoint("s3://bucket?endpoint=localhost").is_err());
+	}
+
+	/// Connects to the MinIO server started by the `start-minio` task, which
+	/// can be overridden with the `SURREAL_TEST_S3_URL` environment variable.
+	fn minio() -> S3Store {
+		let url = std::env::var("SURREAL_TEST_S3_URL").unwrap_or_else(|_| {
+			"s3://surrealdb/test?endpoint=http://127.0.0.1:9000&region=us-east-1&path_style&allow_http&access_key_id=minioadmin&secret_access_key=minioadmin".to_string()
+		});
+		let opts = S3Store::parse_url(&url, false).unwrap().unwrap();
+		S3Store::new(opts).unwrap()
+	}
+
+	#[tokio::test]
+	#[ignore = "requires a MinIO server, see the ci-bucket-s3 task"]
+	async fn minio_roundtrip() {
+		let store = minio();
+		let prefix = ObjectKey::new(format!("/{}", uuid::Uuid::new_v4()));
+		let key = prefix.join(&ObjectKey::new("/file.txt"));
+		// Put and get an object
+		store.put(&key, Bytes::from_static(b"hello world")).await.unwrap();
+		assert_eq!(store.get(&key).await.unwrap().unwrap(), Bytes::from_static(b"hello world"));
+		assert!(store.exists(&key).await.unwrap());
+		// Read a range of an object
+		let range = ObjectRange {
+			offset: 6,
+			length: Some(100),
+		};
+		let data = store.get_range(&key, range).await.unwrap().unwrap();
+		assert_eq!(data, Bytes::from_static(b"world"));
+		// Creating an existing object is a no-op
+		store.put_if_not_exists(&key, Bytes::from_static(b"other")).await.unwrap();
+		assert_eq!(store.get(&key).await.unwrap().unwrap(), Bytes::from_static(b"hello world"));
+		// Conditional writes use the ETag of the object
+		let etag = store.head(&key).await.unwrap().unwrap().etag.unwrap();
+		let mut opts = ObjectPutOptions::default();
+		opts.precondition.if_match = Some("\"outdated\"".to_string());
+		let res = store.put_opts(&key, Bytes::from_static(b"a"), opts).await.unwrap();
+		assert!(matches!(res, Conditional::Skipped(PreconditionFailure::Failed)));
+		let mut opts = ObjectPutOptions {
+			content_type: Some("text/plain".to_string()),
+			..Default::default()
+		};
+		opts.precondition.if_match = Some(etag);
+		opts.metadata.insert("author".to_string(), "tobie".to_string());
+		let res = store.put_opts(&key, Bytes::from_static(b"updated"), opts).await.unwrap();
+		assert!(matches!(res, Conditional::Applied(())));
+		let res = store.get_opts(&key, ObjectGetOptions::default()).await.unwrap();
+		assert!(matches!(res, Conditional::Applied(Some(ref data)) if data.as_ref() == b"updated"));
+		// Upload an object in multiple parts
+		let large = prefix.join(&ObjectKey::new("/large.bin"));
+		let mut upload = store.put_multipart(&large).await.unwrap();
+		upload.put_part(Bytes::from(vec![1u8; 6 * 1024 * 1024])).await.unwrap();
+		upload.put_part(Bytes::from(vec![2u8; 1024])).await.unwrap();
+		upload.complete().await.unwrap();
+		let meta = store.head(&large).await.unwrap().unwrap();
+		assert_eq!(meta.size, 6 * 1024 * 1024 + 1024);
+		// List the objects under the prefix
+		let opts = ListOptions {
+			prefix: Some(prefix.clone()),
+			..Default::default()
+		};
+		let keys: Vec<_> = store.list(&opts).await.unwrap().into_iter().map(|m| m.key).collect();
+		assert_eq!(keys, vec![key.clone(), large.clone()]);
+		// Delete the objects
+		store.delete(&key).await.unwrap();
+		store.delete(&large).await.unwrap();
+		assert!(store.get(&key).await.unwrap().is_none());
+	}
+}
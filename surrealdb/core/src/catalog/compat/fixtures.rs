@@ -673,6 +673,9 @@ pub fn bucket_basic() -> BucketDefinition {
 		backend: None,
 		comment: None,
 		permissions: Permission::Full,
+		max_size: None,
+		max_count: None,
+		lifecycle: Vec::new(),
 	}
 }
 
@@ -685,6 +688,9 @@ pub fn bucket_readonly() -> BucketDefinition {
 		backend: Some("s3://bucket/archives".to_string()),
 		comment: Some("Read-only archive storage".to_string()),
 		permissions: Permission::None,
+		max_size: None,
+		max_count: None,
+		lifecycle: Vec::new(),
 	}
 }
 
@@ -972,6 +978,8 @@ pub fn index_basic() -> IndexDefinition {
 		index: Index::Idx,
 		comment: None,
 		prepare_remove: false,
+		cond: None,
+		include: vec![],
 	}
 }
 
@@ -985,6 +993,8 @@ pub fn index_unique() -> IndexDefinition {
 		index: Index::Uniq,
 		comment: Some("Unique email constraint".to_string()),
 		prepare_remove: false,
+		cond: None,
+		include: vec![],
 	}
 }
 
@@ -1006,9 +1016,13 @@ pub fn index_hnsw() -> IndexDefinition {
 			extend_candidates: false,
 			keep_pruned_connections: true,
 			use_hashed_vector: false,
+			quantization: None,
+			multi_vector: false,
 		}),
 		comment: Some("Vector similarity search index".to_string()),
 		prepare_remove: false,
+		cond: None,
+		include: vec![],
 	}
 }
 
@@ -1029,6 +1043,8 @@ pub fn index_fulltext() -> IndexDefinition {
 		}),
 		comment: Some("Full-text search on articles".to_string()),
 		prepare_remove: false,
+		cond: None,
+		include: vec![],
 	}
 }
 
@@ -1044,6 +1060,8 @@ pub fn index_count() -> IndexDefinition {
 		))))),
 		comment: None,
 		prepare_remove: true,
+		cond: None,
+		include: vec![],
 	}
 }
 
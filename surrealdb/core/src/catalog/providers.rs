@@ -158,6 +158,13 @@ pub(crate) trait DatabaseProvider: NamespaceProvider {
 		db: DatabaseId,
 	) -> Result<Arc<[catalog::SequenceDefinition]>>;
 
+	/// Retrieve all changefeed consumer definitions for a specific database.
+	async fn all_db_consumers(
+		&self,
+		ns: NamespaceId,
+		db: DatabaseId,
+	) -> Result<Arc<[catalog::ConsumerDefinition]>>;
+
 	/// Retrieve all function definitions for a specific database.
 	async fn all_db_functions(
 		&self,
@@ -217,6 +224,14 @@ pub(crate) trait DatabaseProvider: NamespaceProvider {
 		sq: &str,
 	) -> Result<Arc<catalog::SequenceDefinition>>;
 
+	/// Retrieve a specific changefeed consumer definition from a database.
+	async fn get_db_consumer(
+		&self,
+		ns: NamespaceId,
+		db: DatabaseId,
+		cs: &str,
+	) -> Result<Arc<catalog::ConsumerDefinition>>;
+
 	/// Retrieve a specific function definition from a database.
 	async fn get_db_function(
 		&self,
@@ -1,3 +1,5 @@
+use std::time;
+
 use revision::revisioned;
 use serde::{Deserialize, Serialize};
 use surrealdb_types::{SqlFormat, ToSql};
@@ -7,14 +9,14 @@ use crate::expr::statements::info::InfoStructure;
 use crate::kvs::impl_kv_value_revisioned;
 use crate::sql;
 use crate::sql::statements::define::{DefineBucketStatement, DefineKind};
-use crate::val::Value;
+use crate::val::{Duration, Value};
 
 #[revisioned(revision = 1)]
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
 #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct BucketId(pub u32);
 
-#[revisioned(revision = 1)]
+#[revisioned(revision = 2)]
 #[derive(Clone, Debug, Default, Eq, PartialEq, Hash)]
 pub struct BucketDefinition {
 	pub(crate) id: Option<BucketId>,
@@ -23,10 +25,43 @@ pub struct BucketDefinition {
 	pub(crate) permissions: Permission,
 	pub(crate) readonly: bool,
 	pub(crate) comment: Option<String>,
+	/// The maximum total size in bytes of all files in the bucket
+	#[revision(start = 2)]
+	pub(crate) max_size: Option<u64>,
+	/// The maximum number of files in the bucket
+	#[revision(start = 2)]
+	pub(crate) max_count: Option<u64>,
+	/// Rules for expiring files in the bucket
+	#[revision(start = 2)]
+	pub(crate) lifecycle: Vec<BucketLifecycleRule>,
 }
 impl_kv_value_revisioned!(BucketDefinition);
 
+/// A rule which deletes files under a prefix once they reach a certain age.
+#[revisioned(revision = 1)]
+#[derive(Clone, Debug, Default, Eq, PartialEq, Hash)]
+pub struct BucketLifecycleRule {
+	/// The key prefix of the files to which this rule applies
+	pub(crate) prefix: String,
+	/// The age after which a file is deleted
+	pub(crate) expiry: time::Duration,
+}
+
+impl InfoStructure for BucketLifecycleRule {
+	fn structure(self) -> Value {
+		Value::from(map! {
+			"prefix".to_string() => self.prefix.into(),
+			"expiry".to_string() => Duration(self.expiry).into(),
+		})
+	}
+}
+
 impl BucketDefinition {
+	/// Returns `true` if the bucket limits the size or number of stored files.
+	pub(crate) fn has_quota(&self) -> bool {
+		self.max_size.is_some() || self.max_count.is_some()
+	}
+
 	pub fn to_sql_definition(&self) -> DefineBucketStatement {
 		DefineBucketStatement {
 			kind: DefineKind::Default,
@@ -34,6 +69,9 @@ impl BucketDefinition {
 			backend: self.backend.clone().map(|v| sql::Expr::Literal(sql::Literal::String(v))),
 			permissions: self.permissions.clone().into(),
 			readonly: self.readonly,
+			max_size: self.max_size,
+			max_count: self.max_count,
+			lifecycle: self.lifecycle.iter().cloned().map(Into::into).collect(),
 			comment: self
 				.comment
 				.clone()
@@ -50,6 +88,9 @@ impl InfoStructure for BucketDefinition {
 			"permissions".to_string() => self.permissions.structure(),
 			"backend".to_string(), if let Some(backend) = self.backend => Value::String(backend),
 			"readonly".to_string() => self.readonly.into(),
+			"max_size".to_string(), if let Some(max_size) = self.max_size => max_size.into(),
+			"max_count".to_string(), if let Some(max_count) = self.max_count => max_count.into(),
+			"lifecycle".to_string() => self.lifecycle.into_iter().map(InfoStructure::structure).collect::<Vec<_>>().into(),
 			"comment".to_string(), if let Some(comment) = self.comment => comment.into(),
 		})
 	}
@@ -0,0 +1,102 @@
+use revision::revisioned;
+use surrealdb_types::{SqlFormat, ToSql};
+
+use crate::expr::statements::info::InfoStructure;
+use crate::kvs::impl_kv_value_revisioned;
+use crate::sql::statements::define::{
+	ConsumerSink as SqlConsumerSink, DefineConsumerStatement, DefineKind,
+};
+use crate::sql::{self};
+use crate::types::PublicFile;
+use crate::val::{File, TableName, Value};
+
+/// An external target to which the changes read by a consumer are delivered.
+#[revisioned(revision = 1)]
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub enum ConsumerSink {
+	/// The changes are sent as a JSON array in the body of a POST request.
+	Webhook(String),
+	/// The changes are appended as JSON Lines segments under a bucket path.
+	File(File),
+}
+
+impl ConsumerSink {
+	fn to_sql(&self) -> SqlConsumerSink {
+		match self {
+			Self::Webhook(url) => {
+				SqlConsumerSink::Webhook(sql::Expr::Literal(sql::Literal::String(url.clone())))
+			}
+			Self::File(file) => SqlConsumerSink::File(sql::Expr::Literal(sql::Literal::File(
+				PublicFile::from(file.clone()),
+			))),
+		}
+	}
+
+	fn structure(&self) -> Value {
+		match self {
+			Self::Webhook(url) => Value::from(map! {
+				"webhook".to_string() => url.clone().into(),
+			}),
+			Self::File(file) => Value::from(map! {
+				"file".to_string() => Value::File(file.clone()),
+			}),
+		}
+	}
+}
+
+/// A named changefeed consumer, which tracks the position up to which the
+/// changes of a table have been acknowledged.
+#[revisioned(revision = 2)]
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+#[non_exhaustive]
+pub struct ConsumerDefinition {
+	pub(crate) name: String,
+	pub(crate) target_table: TableName,
+	pub(crate) comment: Option<String>,
+	/// The versionstamp of the last acknowledged change, if any.
+	pub(crate) position: Option<u64>,
+	/// The external target to which changes are delivered, if any.
+	#[revision(start = 2)]
+	pub(crate) sink: Option<ConsumerSink>,
+}
+
+impl_kv_value_revisioned!(ConsumerDefinition);
+
+impl ConsumerDefinition {
+	/// The versionstamp of the first change which has not been acknowledged.
+	pub(crate) fn next_versionstamp(&self) -> u64 {
+		self.position.map(|v| v.saturating_add(1)).unwrap_or(0)
+	}
+
+	pub fn to_sql_definition(&self) -> DefineConsumerStatement {
+		DefineConsumerStatement {
+			kind: DefineKind::Default,
+			name: sql::Expr::Idiom(sql::Idiom::field(self.name.clone())),
+			target_table: sql::Expr::Table(self.target_table.clone().into_string()),
+			comment: self
+				.comment
+				.clone()
+				.map(|v| sql::Expr::Literal(sql::Literal::String(v)))
+				.unwrap_or(sql::Expr::Literal(sql::Literal::None)),
+			sink: self.sink.as_ref().map(ConsumerSink::to_sql),
+		}
+	}
+}
+
+impl InfoStructure for ConsumerDefinition {
+	fn structure(self) -> Value {
+		Value::from(map! {
+			"name".to_string() => self.name.into(),
+			"what".to_string() => self.target_table.into(),
+			"position".to_string() => self.position.map(Value::from).unwrap_or(Value::None),
+			"sink".to_string(), if let Some(v) = self.sink => v.structure(),
+			"comment".to_string(), if let Some(v) = self.comment => v.into(),
+		})
+	}
+}
+
+impl ToSql for ConsumerDefinition {
+	fn fmt_sql(&self, f: &mut String, fmt: SqlFormat) {
+		self.to_sql_definition().fmt_sql(f, fmt)
+	}
+}
@@ -8,7 +8,7 @@ use surrealdb_types::{SqlFormat, ToSql, write_sql};
 
 use crate::err::Error;
 use crate::expr::statements::info::InfoStructure;
-use crate::expr::{Cond, Idiom};
+use crate::expr::{BinaryOperator, Cond, Expr, Idiom};
 use crate::kvs::impl_kv_value_revisioned;
 use crate::sql;
 use crate::sql::statements::define::DefineKind;
@@ -50,7 +50,7 @@ impl From<u32> for IndexId {
 	}
 }
 
-#[revisioned(revision = 1)]
+#[revisioned(revision = 3)]
 #[derive(Clone, Debug, Eq, PartialEq, Hash)]
 #[non_exhaustive]
 pub struct IndexDefinition {
@@ -64,6 +64,15 @@ pub struct IndexDefinition {
 	/// Indexes marked for removal are excluded from query planning and document
 	/// indexing, and any in-progress index builds are cancelled.
 	pub(crate) prepare_remove: bool,
+	/// The optional predicate of a partial index. Only records matching this
+	/// condition are stored in the index.
+	#[revision(start = 2)]
+	pub(crate) cond: Option<Cond>,
+	/// The extra fields stored alongside each entry of a covering index, so
+	/// that queries only reading these fields and the indexed columns can be
+	/// answered without fetching the records.
+	#[revision(start = 3)]
+	pub(crate) include: Vec<Idiom>,
 }
 
 impl_kv_value_revisioned!(IndexDefinition);
@@ -76,6 +85,8 @@ impl IndexDefinition {
 			what: sql::Expr::Table(self.table_name.clone().into_string()),
 			cols: self.cols.iter().cloned().map(|x| sql::Expr::Idiom(x.into())).collect(),
 			index: self.index.to_sql_definition(),
+			include: self.include.iter().cloned().map(|x| sql::Expr::Idiom(x.into())).collect(),
+			cond: self.cond.clone().map(Into::into),
 			comment: self
 				.comment
 				.clone()
@@ -85,6 +96,26 @@ impl IndexDefinition {
 		}
 	}
 
+	/// Returns whether this index can serve a query with the given condition.
+	///
+	/// A partial index only contains the records matching its own condition,
+	/// so it can only be used when every conjunct of the index condition is
+	/// also a conjunct of the query condition. Non-partial indexes always
+	/// qualify.
+	pub(crate) fn is_implied_by(&self, cond: Option<&Cond>) -> bool {
+		let Some(ix_cond) = &self.cond else {
+			return true;
+		};
+		let Some(cond) = cond else {
+			return false;
+		};
+		let mut query = Vec::new();
+		collect_conjuncts(&cond.0, &mut query);
+		let mut required = Vec::new();
+		collect_conjuncts(&ix_cond.0, &mut required);
+		required.iter().all(|r| query.contains(r))
+	}
+
 	/// Checks if this index has been marked for removal and returns an error if so.
 	///
 	/// This method is used during index building to detect when an index has been
@@ -105,6 +136,21 @@ impl IndexDefinition {
 	}
 }
 
+/// Flattens a tree of `AND` expressions into its individual conjuncts.
+fn collect_conjuncts<'a>(expr: &'a Expr, out: &mut Vec<&'a Expr>) {
+	if let Expr::Binary {
+		left,
+		op: BinaryOperator::And,
+		right,
+	} = expr
+	{
+		collect_conjuncts(left, out);
+		collect_conjuncts(right, out);
+	} else {
+		out.push(expr);
+	}
+}
+
 impl InfoStructure for IndexDefinition {
 	fn structure(self) -> Value {
 		Value::from(map! {
@@ -112,6 +158,8 @@ impl InfoStructure for IndexDefinition {
 			"table".to_string() => self.table_name.into_string().into(),
 			"cols".to_string() => Value::Array(Array(self.cols.into_iter().map(|x| x.structure()).collect())),
 			"index".to_string() => self.index.structure(),
+			"include".to_string(), if !self.include.is_empty() => Value::Array(Array(self.include.into_iter().map(|x| x.structure()).collect())),
+			"cond".to_string(), if let Some(v) = self.cond => v.0.structure(),
 			"comment".to_string(), if let Some(v) = self.comment => v.into(),
 			"prepare_remove".to_string(), if self.prepare_remove => self.prepare_remove.into()
 		})
@@ -124,7 +172,7 @@ impl ToSql for IndexDefinition {
 	}
 }
 
-#[revisioned(revision = 1)]
+#[revisioned(revision = 4)]
 #[derive(Clone, Debug, Default, Eq, PartialEq, Hash)]
 pub(crate) enum Index {
 	/// (Basic) non unique
@@ -138,6 +186,15 @@ pub(crate) enum Index {
 	FullText(FullTextParams),
 	/// Count index
 	Count(Option<Cond>),
+	/// Spatial index over geometries
+	#[revision(start = 2)]
+	Spatial,
+	/// Trigram index over strings
+	#[revision(start = 3)]
+	Trigram,
+	/// Inverted-file index for distance-based metrics, stored in the key-value store
+	#[revision(start = 4)]
+	Ivf(IvfParams),
 }
 
 impl Index {
@@ -148,6 +205,9 @@ impl Index {
 			Self::Hnsw(params) => sql::index::Index::Hnsw(params.clone().into()),
 			Self::FullText(params) => sql::index::Index::FullText(params.clone().into()),
 			Self::Count(cond) => sql::index::Index::Count(cond.clone().map(Into::into)),
+			Self::Spatial => sql::index::Index::Spatial,
+			Self::Trigram => sql::index::Index::Trigram,
+			Self::Ivf(params) => sql::index::Index::Ivf(params.clone().into()),
 		}
 	}
 
@@ -346,8 +406,33 @@ impl Display for VectorType {
 	}
 }
 
+/// Compressed representation of the vectors traversed by an HNSW index.
+///
+/// The full-precision vectors are still stored, and are used to re-rank the
+/// candidates found on the compressed vectors.
+#[revisioned(revision = 1)]
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub enum Quantization {
+	/// 8-bit scalar quantization.
+	Scalar,
+	/// 1-bit binary quantization.
+	Binary,
+	/// Product quantization with the given number of sub-vectors.
+	Product(u16),
+}
+
+impl Display for Quantization {
+	fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+		match self {
+			Self::Scalar => f.write_str("SCALAR"),
+			Self::Binary => f.write_str("BINARY"),
+			Self::Product(m) => write!(f, "PRODUCT {m}"),
+		}
+	}
+}
+
 /// HNSW index parameters.
-#[revisioned(revision = 2)]
+#[revisioned(revision = 4)]
 #[derive(Clone, Debug, Eq, PartialEq, Hash)]
 pub(crate) struct HnswParams {
 	/// The dimension of the index.
@@ -371,4 +456,26 @@ pub(crate) struct HnswParams {
 	/// Whether to use vector hash for vector retrieval.
 	#[revision(start = 2)]
 	pub use_hashed_vector: bool,
+	/// The quantization of the traversed vectors, if any.
+	#[revision(start = 3)]
+	pub quantization: Option<Quantization>,
+	/// Whether each record holds an array of vectors rather than one vector.
+	#[revision(start = 4)]
+	pub multi_vector: bool,
+}
+
+/// IVF index parameters.
+#[revisioned(revision = 1)]
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub(crate) struct IvfParams {
+	/// The dimension of the index.
+	pub dimension: u16,
+	/// The distance metric to use.
+	pub distance: Distance,
+	/// The vector type to use.
+	pub vector_type: VectorType,
+	/// The number of lists the vectors are partitioned into.
+	pub lists: u16,
+	/// The number of nearest lists scanned by a search.
+	pub probes: u16,
 }
@@ -7,6 +7,7 @@ mod api;
 pub(crate) mod base;
 mod bucket;
 mod config;
+mod consumer;
 mod event;
 mod field;
 mod function;
@@ -23,6 +24,7 @@ pub use analyzer::*;
 pub use api::*;
 pub use bucket::*;
 pub use config::*;
+pub use consumer::*;
 pub use event::*;
 pub use field::*;
 pub use function::*;
@@ -150,7 +150,13 @@ use crate::val::{Datetime, TableName, Value};
 	backend: Some("backend".to_string()),
 	comment: Some("comment".to_string()),
 	permissions: Permission::Full,
-}, 32)]
+	max_size: Some(1024),
+	max_count: None,
+	lifecycle: vec![BucketLifecycleRule {
+		prefix: "tmp/".to_string(),
+		expiry: Duration::from_secs(3600),
+	}],
+}, 48)]
 #[case::config(ConfigDefinition::GraphQL(GraphQLConfig {
 	tables: GraphQLTablesConfig::default(),
 	functions: GraphQLFunctionsConfig::default(),
@@ -207,7 +213,9 @@ use crate::val::{Datetime, TableName, Value};
 	index: Index::Idx,
 	comment: Some("comment".to_string()),
 	prepare_remove: false,
-}, 34)]
+	cond: None,
+	include: vec![],
+}, 36)]
 #[case::model(MlModelDefinition {
 	name: "model".to_string(),
 	hash: "hash".to_string(),
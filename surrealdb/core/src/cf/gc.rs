@@ -11,7 +11,9 @@ use crate::kvs::tasklease::LeaseHandler;
 use crate::kvs::{BoxTimeStamp, BoxTimeStampImpl, KVKey, Transaction};
 
 // gc_all_at deletes all change feed entries that become stale at the given
-// current time.
+// current time. Entries are never retained beyond the expiry of the feed, even
+// when they have not yet been acknowledged by all of the changefeed consumers
+// on a database, so that a stalled consumer can not grow the feed unbounded.
 #[instrument(level = "trace", target = "surrealdb::core::cfs", skip_all)]
 pub async fn gc_all_at(lh: &LeaseHandler, tx: &Transaction) -> Result<()> {
 	// Fetch all namespaces
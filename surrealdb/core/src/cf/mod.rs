@@ -1,6 +1,7 @@
 pub(crate) mod gc;
 pub(crate) mod mutations;
 pub(crate) mod reader;
+pub(crate) mod sink;
 pub(crate) mod writer;
 
 pub use self::gc::*;
@@ -77,6 +77,15 @@ pub static DATASTORE_CACHE_SIZE: LazyLock<usize> =
 pub static SURREALISM_CACHE_SIZE: LazyLock<usize> =
 	lazy_env_parse!("SURREAL_SURREALISM_CACHE_SIZE", usize, 100);
 
+/// Specifies the number of distinct statements for which statistics are kept
+/// (default: 1,000)
+pub static STATEMENT_STATS_CAPACITY: LazyLock<usize> =
+	lazy_env_parse!("SURREAL_STATEMENT_STATS_CAPACITY", usize, 1_000);
+
+/// Specifies the number of recent slow queries which are kept (default: 100)
+pub static SLOW_QUERY_LOG_CAPACITY: LazyLock<usize> =
+	lazy_env_parse!("SURREAL_SLOW_QUERY_LOG_CAPACITY", usize, 100);
+
 /// The maximum number of keys that should be scanned at once in general queries
 /// (default: 500)
 pub static NORMAL_FETCH_SIZE: LazyLock<u32> =
@@ -87,6 +96,21 @@ pub static NORMAL_FETCH_SIZE: LazyLock<u32> =
 pub static EXPORT_BATCH_SIZE: LazyLock<u32> =
 	lazy_env_parse!("SURREAL_EXPORT_BATCH_SIZE", u32, 1000);
 
+/// The maximum number of record batches which are imported concurrently from
+/// a binary export (default: 8)
+pub static IMPORT_CONCURRENCY: LazyLock<usize> =
+	lazy_env_parse!("SURREAL_IMPORT_CONCURRENCY", usize, 8);
+
+/// The maximum size of a single frame within a binary export which is
+/// accepted when importing (default: 256 MiB)
+pub static IMPORT_MAX_FRAME_SIZE: LazyLock<usize> =
+	lazy_env_parse!(bytes, "SURREAL_IMPORT_MAX_FRAME_SIZE", usize, 256 << 20);
+
+/// The maximum number of change sets which are delivered to a changefeed sink
+/// in a single batch (default: 100)
+pub static CHANGEFEED_SINK_BATCH_SIZE: LazyLock<u32> =
+	lazy_env_parse!("SURREAL_CHANGEFEED_SINK_BATCH_SIZE", u32, 100);
+
 /// The maximum number of keys that should be scanned at once for count queries
 /// (default: 50,000)
 pub static COUNT_BATCH_SIZE: LazyLock<u32> =
@@ -182,6 +206,16 @@ pub static BUCKET_FOLDER_ALLOWLIST: LazyLock<Vec<PathBuf>> = LazyLock::new(|| {
 		.unwrap_or_default()
 });
 
+/// Specifies a list of S3 bucket names which bucket backends may access using
+/// the `AWS_*` environment configuration of the server process (default: empty)
+pub static BUCKET_S3_ENV_ALLOWLIST: LazyLock<Vec<String>> =
+	LazyLock::new(|| match std::env::var("SURREAL_BUCKET_S3_ENV_ALLOWLIST") {
+		Ok(input) => {
+			input.split(',').map(str::trim).filter(|s| !s.is_empty()).map(String::from).collect()
+		}
+		Err(_) => Vec::new(),
+	});
+
 /// Specify the name of a global bucket for file data (default: None)
 pub static GLOBAL_BUCKET: LazyLock<Option<String>> =
 	lazy_env_parse!("SURREAL_GLOBAL_BUCKET", Option<String>);
@@ -190,6 +224,15 @@ pub static GLOBAL_BUCKET: LazyLock<Option<String>> =
 pub static GLOBAL_BUCKET_ENFORCED: LazyLock<bool> =
 	lazy_env_parse!("SURREAL_GLOBAL_BUCKET_ENFORCED", bool, false);
 
+/// The time after which an unfinished multipart bucket upload is discarded (default: 3600 seconds)
+pub static BUCKET_UPLOAD_TIMEOUT_SECS: LazyLock<u64> =
+	lazy_env_parse!("SURREAL_BUCKET_UPLOAD_TIMEOUT_SECS", u64, 3600);
+
+/// The number of files listed at a time when computing bucket usage or expiring files (default:
+/// 1000)
+pub static BUCKET_LIST_BATCH_SIZE: LazyLock<usize> =
+	lazy_env_parse!("SURREAL_BUCKET_LIST_BATCH_SIZE", usize, 1000);
+
 /// Specify the USER-AGENT string used by HTTP requests
 pub static SURREALDB_USER_AGENT: LazyLock<String> =
 	LazyLock::new(|| std::env::var("SURREAL_USER_AGENT").unwrap_or("SurrealDB".to_string()));
@@ -197,3 +240,7 @@ pub static SURREALDB_USER_AGENT: LazyLock<String> =
 /// The maximum size of the HNSW vector cache (default: 256 MiB)
 pub static HNSW_CACHE_SIZE: LazyLock<u64> =
 	lazy_env_parse!("SURREAL_HNSW_CACHE_SIZE", u64, 256 * 1024 * 1024);
+
+/// The maximum size of the cache of quantized codes of each HNSW index (default: 64 MiB)
+pub static HNSW_CODE_CACHE_SIZE: LazyLock<u64> =
+	lazy_env_parse!("SURREAL_HNSW_CODE_CACHE_SIZE", u64, 64 * 1024 * 1024);
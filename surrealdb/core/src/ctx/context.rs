@@ -13,7 +13,7 @@ use async_channel::Sender;
 use surrealism_runtime::controller::Runtime;
 #[cfg(feature = "surrealism")]
 use surrealism_runtime::package::SurrealismPackage;
-#[cfg(feature = "http")]
+#[cfg(any(feature = "http", feature = "bucket-s3"))]
 use url::Url;
 use web_time::Instant;
 
@@ -28,7 +28,7 @@ use crate::ctx::canceller::Canceller;
 use crate::ctx::reason::Reason;
 #[cfg(feature = "surrealism")]
 use crate::dbs::capabilities::ExperimentalTarget;
-#[cfg(feature = "http")]
+#[cfg(any(feature = "http", feature = "bucket-s3"))]
 use crate::dbs::capabilities::NetTarget;
 use crate::dbs::{Capabilities, NewPlannerStrategy, Options, Session, Variables};
 use crate::err::Error;
@@ -39,8 +39,10 @@ use crate::idx::trees::store::IndexStores;
 use crate::kvs::Transaction;
 use crate::kvs::cache::ds::DatastoreCache;
 use crate::kvs::index::IndexBuilder;
+use crate::kvs::running::RunningQueries;
 use crate::kvs::sequences::Sequences;
 use crate::kvs::slowlog::SlowLog;
+use crate::kvs::stats::QueryStats;
 use crate::mem::ALLOC;
 use crate::sql::expression::convert_public_value_to_internal;
 #[cfg(feature = "surrealism")]
@@ -59,6 +61,10 @@ pub struct Context {
 	// that exceed a given duration threshold. This configuration is propagated
 	// from the datastore into the context for the lifetime of a request.
 	slow_log: Option<SlowLog>,
+	// The statement statistics and recent slow queries of the datastore.
+	query_stats: Option<QueryStats>,
+	// The statements which are currently running on the datastore.
+	running_queries: Option<RunningQueries>,
 	// Whether or not this context is cancelled.
 	cancelled: Arc<AtomicBool>,
 	// A collection of read only values stored in this context.
@@ -138,6 +144,8 @@ impl Context {
 			parent: None,
 			deadline: None,
 			slow_log: None,
+			query_stats: None,
+			running_queries: None,
 			cancelled: Arc::new(AtomicBool::new(false)),
 			notifications: None,
 			query_planner: None,
@@ -169,6 +177,8 @@ impl Context {
 			values: HashMap::default(),
 			deadline: parent.deadline,
 			slow_log: parent.slow_log.clone(),
+			query_stats: parent.query_stats.clone(),
+			running_queries: parent.running_queries.clone(),
 			cancelled: Arc::new(AtomicBool::new(false)),
 			notifications: parent.notifications.clone(),
 			query_planner: parent.query_planner.clone(),
@@ -203,6 +213,8 @@ impl Context {
 			values: HashMap::default(),
 			deadline: parent.deadline,
 			slow_log: parent.slow_log.clone(),
+			query_stats: parent.query_stats.clone(),
+			running_queries: parent.running_queries.clone(),
 			cancelled: Arc::new(AtomicBool::new(false)),
 			notifications: parent.notifications.clone(),
 			query_planner: parent.query_planner.clone(),
@@ -244,6 +256,8 @@ impl Context {
 			values: from.collect_values(HashMap::default()),
 			deadline: from.deadline,
 			slow_log: from.slow_log.clone(),
+			query_stats: from.query_stats.clone(),
+			running_queries: from.running_queries.clone(),
 			cancelled: Arc::new(AtomicBool::new(false)),
 			notifications: from.notifications.clone(),
 			query_planner: from.query_planner.clone(),
@@ -278,6 +292,8 @@ impl Context {
 			values: HashMap::default(),
 			deadline: None,
 			slow_log: from.slow_log.clone(),
+			query_stats: from.query_stats.clone(),
+			running_queries: from.running_queries.clone(),
 			cancelled: Arc::new(AtomicBool::new(false)),
 			notifications: from.notifications.clone(),
 			query_planner: from.query_planner.clone(),
@@ -309,6 +325,8 @@ impl Context {
 	pub(crate) fn from_ds(
 		time_out: Option<Duration>,
 		slow_log: Option<SlowLog>,
+		query_stats: QueryStats,
+		running_queries: RunningQueries,
 		capabilities: Arc<Capabilities>,
 		index_stores: IndexStores,
 		index_builder: IndexBuilder,
@@ -324,6 +342,8 @@ impl Context {
 			parent: None,
 			deadline: None,
 			slow_log,
+			query_stats: Some(query_stats),
+			running_queries: Some(running_queries),
 			cancelled: Arc::new(AtomicBool::new(false)),
 			notifications: None,
 			query_planner: None,
@@ -456,7 +476,7 @@ impl Context {
 
 	/// Add cancellation to the context. The value that is returned will cancel
 	/// the context and it's children once called.
-	pub(crate) fn add_cancel(&mut self) -> Canceller {
+	pub(crate) fn add_cancel(&self) -> Canceller {
 		let cancelled = self.cancelled.clone();
 		Canceller::new(cancelled)
 	}
@@ -533,6 +553,18 @@ impl Context {
 		self.slow_log.as_ref()
 	}
 
+	/// Returns the statement statistics of the datastore, if any, attached to
+	/// this context.
+	pub(crate) fn query_stats(&self) -> Option<&QueryStats> {
+		self.query_stats.as_ref()
+	}
+
+	/// Returns the registry of running statements of the datastore, if any,
+	/// attached to this context.
+	pub(crate) fn running_queries(&self) -> Option<&RunningQueries> {
+		self.running_queries.as_ref()
+	}
+
 	pub(crate) fn notifications(&self) -> Option<Sender<PublicNotification>> {
 		self.notifications.clone()
 	}
@@ -884,7 +916,7 @@ impl Context {
 	/// # Errors
 	/// - `NetTargetNotAllowed`: Returned if any of the resolved targets are not allowed.
 	/// - `InvalidUrl`: Returned if the URL does not have a valid host.
-	#[cfg(feature = "http")]
+	#[cfg(any(feature = "http", feature = "bucket-s3"))]
 	pub(crate) async fn check_allowed_net(&self, url: &Url) -> Result<()> {
 		let match_any_deny_net = |t| {
 			if self.capabilities.matches_any_deny_net(t) {
@@ -1115,7 +1147,7 @@ mod tests {
 	#[tokio::test]
 	async fn test_context_is_done_detects_cancellation() {
 		// Test that is_done detects cancellation
-		let mut ctx = Context::background();
+		let ctx = Context::background();
 		let canceller = ctx.add_cancel();
 		canceller.cancel();
 		let ctx = ctx.freeze();
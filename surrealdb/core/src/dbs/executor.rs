@@ -8,6 +8,7 @@ use reblessive::TreeStack;
 use surrealdb_types::{Error as TypesError, QueryError, ToSql};
 #[cfg(not(target_family = "wasm"))]
 use tokio::spawn;
+use tokio_util::sync::CancellationToken;
 use tracing::instrument;
 #[cfg(target_family = "wasm")]
 use wasm_bindgen_futures::spawn_local as spawn;
@@ -27,11 +28,13 @@ use crate::expr::plan::LogicalPlan;
 use crate::expr::statements::{OptionStatement, UseStatement};
 use crate::expr::{Base, ControlFlow, Expr, FlowResult, TopLevelExpr};
 use crate::iam::{Action, ResourceKind};
+use crate::kvs::running::RunningQuery;
 use crate::kvs::slowlog::SlowLogVisit;
+use crate::kvs::stats::normalize;
 use crate::kvs::{Datastore, LockType, Transaction, TransactionType};
 use crate::rpc::types_error_from_anyhow;
 use crate::types::PublicNotification;
-use crate::val::{Array, Value, convert_value_to_public_value};
+use crate::val::{Array, Datetime, Uuid, Value, convert_value_to_public_value};
 use crate::{err, expr, sql};
 
 const TARGET: &str = "surrealdb::core::dbs";
@@ -45,6 +48,8 @@ pub struct Executor {
 	/// Cached session info to avoid re-extracting from context on every query.
 	/// Session values don't change between statements in the same executor batch.
 	cached_session: Option<Arc<crate::exec::context::SessionInfo>>,
+	/// Cancels the streaming execution of this query when it is killed.
+	cancellation: CancellationToken,
 }
 
 impl Executor {
@@ -70,6 +75,7 @@ impl Executor {
 			opt,
 			ctx,
 			cached_session: None,
+			cancellation: CancellationToken::new(),
 		}
 	}
 
@@ -98,7 +104,7 @@ impl Executor {
 	/// implement `Display` and `VisitExpression`.
 	fn check_slow_log<S: SlowLogVisit + ToSql>(&self, start: &Instant, stm: &S) {
 		if let Some(slow_log) = self.ctx.slow_log() {
-			slow_log.check_log(&self.ctx, start, stm);
+			slow_log.check_log(&self.ctx, self.opt.ns.as_deref(), start, stm);
 		}
 	}
 
@@ -192,8 +198,6 @@ impl Executor {
 		plan: Arc<dyn crate::exec::ExecOperator>,
 		txn: Arc<Transaction>,
 	) -> FlowResult<Value> {
-		use tokio_util::sync::CancellationToken;
-
 		use crate::catalog::providers::{DatabaseProvider, NamespaceProvider};
 		use crate::exec::context::{
 			DatabaseContext, ExecutionContext, NamespaceContext, RootContext,
@@ -208,7 +212,7 @@ impl Executor {
 			}
 		}
 
-		let cancellation = CancellationToken::new();
+		let cancellation = self.cancellation.child_token();
 
 		// If a query timeout is configured, spawn a task that cancels the
 		// token when the timeout expires. This lets operators that check
@@ -329,13 +333,56 @@ impl Executor {
 	}
 
 	/// Executes a statement which needs a transaction with the supplied
-	/// transaction.
+	/// transaction, registering it as running and recording it in the
+	/// statement statistics.
 	#[instrument(level = "debug", name = "executor", target = "surrealdb::core::dbs", skip_all)]
 	async fn execute_plan_in_transaction(
 		&mut self,
 		txn: Arc<Transaction>,
 		start: &Instant,
 		plan: TopLevelExpr,
+	) -> FlowResult<Value> {
+		// Statements run during an import are not tracked
+		if self.opt.import {
+			return self.compute_plan_in_transaction(txn, start, plan).await;
+		}
+		let statement = normalize(&plan);
+		let ns = self.opt.ns.clone();
+		// Register the statement until it has finished running
+		let session = self.get_session_info().and_then(|s| s.id).map(Uuid::from);
+		let _running = self.ctx.running_queries().map(|running| {
+			running.register(RunningQuery {
+				session,
+				auth: self.opt.auth.clone(),
+				namespace: ns.as_deref().map(str::to_owned),
+				database: self.opt.db.as_deref().map(str::to_owned),
+				statement: statement.clone(),
+				canceller: self.ctx.add_cancel(),
+				cancellation: self.cancellation.clone(),
+				started: Datetime::now(),
+				start: *start,
+			})
+		});
+		let res = self.compute_plan_in_transaction(txn, start, plan).await;
+		if let Some(stats) = self.ctx.query_stats() {
+			let (rows, error) = match &res {
+				Ok(Value::Array(a)) | Err(ControlFlow::Return(Value::Array(a))) => {
+					(a.len() as u64, false)
+				}
+				Ok(_) | Err(ControlFlow::Return(_)) => (0, false),
+				Err(_) => (0, true),
+			};
+			stats.record(ns.as_deref(), statement, start.elapsed(), rows, error);
+		}
+		res
+	}
+
+	/// Computes a statement with the supplied transaction.
+	async fn compute_plan_in_transaction(
+		&mut self,
+		txn: Arc<Transaction>,
+		start: &Instant,
+		plan: TopLevelExpr,
 	) -> FlowResult<Value> {
 		/// Helper method to get mutable access to the context
 		macro_rules! ctx_mut {
@@ -523,6 +570,10 @@ impl Executor {
 				ctx_mut!().set_transaction(txn);
 				s.compute(&self.ctx, &self.opt, None).await.map_err(ControlFlow::Err)
 			}
+			TopLevelExpr::Acknowledge(s) => {
+				ctx_mut!().set_transaction(txn);
+				s.compute(&self.ctx, &self.opt, None).await.map_err(ControlFlow::Err)
+			}
 			TopLevelExpr::Access(s) => {
 				ctx_mut!().set_transaction(txn);
 				self.stack.enter(|stk| s.compute(stk, &self.ctx, &self.opt, None)).finish().await
@@ -1451,6 +1502,56 @@ mod tests {
 		}
 	}
 
+	#[tokio::test]
+	async fn kill_running_query_from_another_session() {
+		use std::sync::Arc;
+
+		use crate::types::{PublicValue, PublicVariables};
+
+		let ds = Arc::new(Datastore::new("memory").await.unwrap());
+		let sess = Session::owner().with_ns("NS").with_db("DB");
+		// Start a long running query in the background
+		let query = tokio::spawn({
+			let ds = ds.clone();
+			let sess = sess.clone();
+			async move { ds.execute("SLEEP 1m; RETURN 1;", &sess, None).await.unwrap() }
+		});
+		// Find the running statement from another session
+		let other = Session::owner();
+		let id = loop {
+			let res = ds
+				.execute(
+					"(INFO FOR QUERIES).find(|$q| $q.statement == 'SLEEP 1m').id",
+					&other,
+					None,
+				)
+				.await
+				.unwrap();
+			if let Ok(PublicValue::Uuid(id)) = &res[0].result {
+				break *id;
+			}
+			tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+		};
+		// Kill the running statement
+		let mut vars = PublicVariables::new();
+		vars.insert("id", id);
+		let res = ds.execute("KILL QUERY $id", &other, Some(vars.clone())).await.unwrap();
+		assert!(res[0].result.is_ok(), "Failed to kill the running query: {:?}", res);
+		// The statement and the rest of its query are cancelled
+		let res = tokio::time::timeout(std::time::Duration::from_secs(10), query)
+			.await
+			.expect("the killed query should finish promptly")
+			.unwrap();
+		assert_eq!(res.len(), 2);
+		for res in res {
+			let err = res.result.unwrap_err().to_string();
+			assert!(err.contains("cancelled"), "Expected the query to be cancelled: {}", err);
+		}
+		// The statement is no longer running
+		let res = ds.execute("KILL QUERY $id", &other, Some(vars)).await.unwrap();
+		assert!(res[0].result.is_err());
+	}
+
 	#[tokio::test]
 	async fn import_stream_suppresses_results_but_persists_data() {
 		use bytes::Bytes;
@@ -1549,4 +1650,58 @@ mod tests {
 			"Error should explain that import mode is locked, got: {err}"
 		);
 	}
+
+	#[tokio::test]
+	async fn import_stream_accepts_binary_export() {
+		use bytes::Bytes;
+
+		use crate::kvs::export::{Config, ExportFormat};
+
+		let ds = Datastore::new("memory").await.unwrap();
+		let sess = Session::owner().with_ns("NS").with_db("DB");
+
+		ds.execute(
+			"DEFINE NAMESPACE NS; USE NS NS; DEFINE DATABASE DB; USE DB DB;
+			DEFINE INDEX name ON person FIELDS name;
+			INSERT INTO person [{ id: 1, name: 'a' }, { id: 2, name: 'b' }];
+			RELATE person:1->knows->person:2;",
+			&sess,
+			None,
+		)
+		.await
+		.unwrap();
+
+		// Export the database in the binary format
+		let (snd, rcv) = async_channel::unbounded();
+		let cfg = Config {
+			format: ExportFormat::Binary,
+			..Default::default()
+		};
+		ds.export_with_config(&sess, snd, cfg).await.unwrap().await.unwrap();
+		let mut bytes = Vec::new();
+		while let Ok(v) = rcv.try_recv() {
+			bytes.extend(v);
+		}
+
+		// Import the export into another database
+		let sess = Session::owner().with_ns("NS").with_db("OTHER");
+		ds.execute("USE NS NS; DEFINE DATABASE OTHER", &sess, None).await.unwrap();
+		let body = futures::stream::iter(bytes.chunks(7).map(|c| Ok(Bytes::copy_from_slice(c))));
+		let results = ds.import_stream(&sess, body).await.unwrap();
+		assert!(results.is_empty(), "Expected no errors, got {results:?}");
+
+		let verify = ds
+			.execute(
+				"SELECT VALUE name FROM person WITH INDEX name ORDER BY name;
+				RETURN person:1->knows->person;",
+				&sess,
+				None,
+			)
+			.await
+			.unwrap();
+		let names = verify[0].result.as_ref().unwrap().as_array().unwrap();
+		assert_eq!(names.len(), 2, "Expected 2 imported records, got {}", names.len());
+		let edges = verify[1].result.as_ref().unwrap().as_array().unwrap();
+		assert_eq!(edges.len(), 1, "Expected the graph edge to be imported");
+	}
 }
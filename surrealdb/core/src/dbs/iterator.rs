@@ -684,7 +684,7 @@ impl Iterator {
 		// Log the statement
 		trace!(target: TARGET, statement = %stm.to_sql(), "Iterating statement");
 		// Enable context override
-		let mut cancel_ctx = Context::new(ctx);
+		let cancel_ctx = Context::new(ctx);
 		self.canceller = cancel_ctx.add_cancel();
 		let mut cancel_ctx = cancel_ctx.freeze();
 		// Process the query LIMIT clause
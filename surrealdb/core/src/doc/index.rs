@@ -15,14 +15,17 @@
 //! Range scans and lookups benefit because a single probe/range can be used for
 //! numeric predicates without fanning out per numeric variant.
 
-use anyhow::Result;
+use anyhow::{Result, bail};
 use reblessive::tree::Stk;
+use surrealdb_types::ToSql;
 
 use crate::catalog::{DatabaseDefinition, Index, IndexDefinition, TableDefinition};
 use crate::ctx::FrozenContext;
 use crate::dbs::{Force, Options};
 use crate::doc::{CursorDoc, Document};
+use crate::err::Error;
 use crate::expr::FlowResultExt as _;
+use crate::expr::statements::define::follows_record_link;
 use crate::idx::index::IndexOperation;
 use crate::kvs::index::ConsumeResult;
 use crate::val::{RecordId, Value};
@@ -144,6 +147,7 @@ impl Document {
 	/// Eg. IF the index is composed of the columns `name` and `instrument`
 	/// Given this doc: { "id": 1, "instrument":"piano", "name":"Tobie" }
 	/// It will return: ["Tobie", "piano"]
+	/// The values of the fields included in a covering index follow the values of the columns.
 	pub(crate) async fn build_opt_values(
 		stk: &mut Stk,
 		ctx: &FrozenContext,
@@ -154,8 +158,25 @@ impl Document {
 		if doc.doc.as_ref().is_nullish() {
 			return Ok(None);
 		}
-		let mut o = Vec::with_capacity(ix.cols.len());
-		for i in ix.cols.iter() {
+		// Records which don't match the condition of a partial index are not indexed
+		if let Some(cond) = &ix.cond
+			&& !stk
+				.run(|stk| cond.0.compute(stk, ctx, opt, Some(doc)))
+				.await
+				.catch_return()?
+				.is_truthy()
+		{
+			return Ok(None);
+		}
+		let mut o = Vec::with_capacity(ix.cols.len() + ix.include.len());
+		for i in ix.cols.iter().chain(ix.include.iter()) {
+			// Expression columns must not depend on the content of linked records
+			if i.is_expression() && follows_record_link(i, doc.doc.as_ref()) {
+				bail!(Error::IxExpressionRecordLink {
+					expr: i.to_sql(),
+					index: ix.name.clone(),
+				});
+			}
 			let v = i.compute(stk, ctx, opt, Some(doc)).await.catch_return()?;
 			o.push(v);
 		}
@@ -59,6 +59,10 @@ pub(crate) enum Error {
 	#[error("Parse error: {0}")]
 	InvalidQuery(RenderedParserError),
 
+	/// The binary export or backup being imported is invalid
+	#[error("Invalid binary export or backup: {0}")]
+	InvalidExport(String),
+
 	/// There was an error with the SQL query
 	#[error("Cannot use {} in a CONTENT clause", value.to_sql())]
 	InvalidContent {
@@ -170,7 +174,6 @@ pub(crate) enum Error {
 
 	/// The URL is invalid
 	#[error("The URL `{0}` is invalid")]
-	#[cfg_attr(not(any(feature = "http", feature = "jwks")), expect(dead_code))]
 	InvalidUrl(String),
 
 	/// The size of the vector is incorrect
@@ -258,6 +261,12 @@ pub(crate) enum Error {
 		name: String,
 	},
 
+	/// The requested changefeed consumer does not exist
+	#[error("The consumer '{name}' does not exist")]
+	CsNotFound {
+		name: String,
+	},
+
 	/// The requested function does not exist
 	#[error("The function 'fn::{name}' does not exist")]
 	FcNotFound {
@@ -664,6 +673,13 @@ pub(crate) enum Error {
 	#[error("A value can't be analyzed: {0}")]
 	AnalyzerError(String),
 
+	/// A phrase query was run against documents which were indexed without
+	/// the positions of their terms
+	#[error(
+		"The full-text index has no term positions for some documents, and must be rebuilt to support phrase queries"
+	)]
+	FtIndexWithoutPositions,
+
 	/// Represents an error when trying to highlight a value
 	#[error("A value can't be highlighted: {0}")]
 	HighlightError(String),
@@ -802,6 +818,12 @@ pub(crate) enum Error {
 		name: String,
 	},
 
+	/// The requested changefeed consumer already exists
+	#[error("The consumer '{name}' already exists")]
+	CsAlreadyExists {
+		name: String,
+	},
+
 	/// The requested field already exists
 	#[error("The field '{name}' already exists")]
 	FdAlreadyExists {
@@ -1167,6 +1189,18 @@ pub(crate) enum Error {
 	#[error("Operation for bucket `{0}` failed: {1}")]
 	ObjectStoreFailure(String, String),
 
+	#[error("The multipart upload '{0}' does not exist, or has expired")]
+	UploadNotFound(String),
+
+	#[error("The precondition for file `{1}` in bucket `{0}` was not satisfied")]
+	ObjectPreconditionFailed(String, String),
+
+	#[error("Writing file `{1}` would exceed the {2} quota of bucket `{0}`")]
+	BucketQuotaExceeded(String, String, &'static str),
+
+	#[error("The size of file `{1}` must be known upfront, as bucket `{0}` has a quota")]
+	BucketUnknownSize(String, String),
+
 	/// The `COMPUTED` clause cannot be used with other clauses altering or
 	/// working with the value
 	#[error("Cannot use the `{0}` keyword with `COMPUTED`.")]
@@ -1213,6 +1247,20 @@ pub(crate) enum Error {
 		field: String,
 		index: String,
 	},
+
+	#[error("Index expressions must be deterministic. Index: '{index}' - Expression: '{expr}'")]
+	IxExpressionNotDeterministic {
+		expr: String,
+		index: String,
+	},
+
+	#[error(
+		"Index expressions can not follow record links. Index: '{index}' - Expression: '{expr}'"
+	)]
+	IxExpressionRecordLink {
+		expr: String,
+		index: String,
+	},
 }
 
 impl Error {
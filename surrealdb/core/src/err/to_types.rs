@@ -217,6 +217,9 @@ pub fn into_types_error(error: Error) -> TypesError {
 		EvAlreadyExists {
 			..
 		}
+		| CsAlreadyExists {
+			..
+		}
 		| FdAlreadyExists {
 			..
 		}
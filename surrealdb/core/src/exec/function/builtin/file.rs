@@ -4,19 +4,24 @@
 //! Note: File functions require the experimental "files" capability to be enabled.
 
 use std::sync::Arc;
+use std::sync::atomic::Ordering;
 
 use anyhow::{Result, bail, ensure};
 
 use crate::buc::BucketOperation;
-use crate::buc::store::{ListOptions, ObjectKey, ObjectStore};
+use crate::buc::manager::{BucketsManager, UsageDelta};
+use crate::buc::store::{
+	Conditional, GetOptions, ListOptions, ObjectKey, ObjectStore, PreconditionFailure, PutOptions,
+};
 use crate::catalog::providers::BucketProvider;
-use crate::catalog::{BucketDefinition, Permission};
+use crate::catalog::{BucketDefinition, DatabaseId, NamespaceId, Permission};
+use crate::ctx::FrozenContext;
 use crate::dbs::capabilities::ExperimentalTarget;
 use crate::err::Error;
 use crate::exec::function::FunctionRegistry;
 use crate::exec::physical_expr::EvalContext;
-use crate::fnc::args::FromArgs;
-use crate::val::{Bytes, File, Object, Value};
+use crate::fnc::args::{FromArgs, Optional};
+use crate::val::{Bytes, File, Object, Uuid, Value};
 use crate::{define_async_function, define_pure_function, register_functions};
 
 // =========================================================================
@@ -33,6 +38,9 @@ fn accept_payload(value: Value) -> Result<bytes::Bytes> {
 /// Helper struct for bucket operations without needing the full BucketController.
 /// This is used by the streaming executor which doesn't have access to Stk.
 struct StreamingBucketOps<'a> {
+	ctx: &'a FrozenContext,
+	ns: NamespaceId,
+	db: DatabaseId,
 	bucket: Arc<BucketDefinition>,
 	store: Arc<dyn ObjectStore>,
 	opt: &'a crate::dbs::Options,
@@ -66,6 +74,9 @@ impl<'a> StreamingBucketOps<'a> {
 		let store = frozen_ctx.get_bucket_store(ns_id, db_id, bucket_name).await?;
 
 		Ok(Self {
+			ctx: frozen_ctx,
+			ns: ns_id,
+			db: db_id,
 			bucket,
 			store,
 			opt,
@@ -118,16 +129,112 @@ impl<'a> StreamingBucketOps<'a> {
 		Ok(())
 	}
 
+	/// Fetch the buckets manager.
+	fn buckets(&self) -> Result<&'a BucketsManager> {
+		match self.ctx.get_buckets() {
+			Some(buckets) => Ok(buckets),
+			None => bail!(Error::BucketUnavailable(self.bucket.name.clone())),
+		}
+	}
+
+	/// Check that writing `size` bytes to a file would not exceed the bucket quota.
+	async fn check_quota(&self, key: &ObjectKey, size: u64) -> Result<()> {
+		self.buckets()?.check_quota(self.ns, self.db, &self.bucket, &*self.store, key, size).await
+	}
+
+	/// Reserve the usage of writing `size` bytes to a file within the bucket quota.
+	async fn reserve_quota(
+		&self,
+		key: &ObjectKey,
+		size: u64,
+		overwrite: bool,
+	) -> Result<Option<UsageDelta>> {
+		self.buckets()?
+			.reserve_quota(self.ns, self.db, &self.bucket, &*self.store, key, size, overwrite)
+			.await
+	}
+
+	/// Reserve the usage of copying a file within the bucket quota, returning
+	/// the reserved change in usage together with the size of the file.
+	async fn reserve_copy_quota(
+		&self,
+		src: &ObjectKey,
+		dst: &ObjectKey,
+		overwrite: bool,
+	) -> Result<(Option<UsageDelta>, u64)> {
+		if !self.bucket.has_quota() {
+			return Ok((None, 0));
+		}
+		// A missing source file is reported by the copy itself
+		let Some(meta) = self
+			.store
+			.head(src)
+			.await
+			.map_err(|e| Error::ObjectStoreFailure(self.bucket.name.clone(), e))?
+		else {
+			return Ok((None, 0));
+		};
+		Ok((self.reserve_quota(dst, meta.size, overwrite).await?, meta.size))
+	}
+
+	/// Compute the change in usage from deleting a file.
+	async fn release_quota(&self, key: &ObjectKey) -> Result<Option<UsageDelta>> {
+		self.buckets()?.release_quota(&self.bucket, &*self.store, key).await
+	}
+
+	/// Apply a change in usage once a delete has succeeded.
+	async fn apply_usage(&self, delta: Option<UsageDelta>) -> Result<()> {
+		self.buckets()?.apply_usage(self.ns, self.db, &self.bucket, &*self.store, delta).await
+	}
+
+	/// Settle the usage reserved for a write which does not overwrite a file.
+	async fn settle_usage(
+		&self,
+		reserved: Option<UsageDelta>,
+		size: u64,
+		written: bool,
+	) -> Result<()> {
+		self.buckets()?
+			.settle_usage(self.ns, self.db, &self.bucket, &*self.store, reserved, size, written)
+			.await
+	}
+
+	/// Return the result of a write, releasing the reserved usage on failure.
+	async fn written<T>(&self, reserved: Option<UsageDelta>, res: Result<T, String>) -> Result<T> {
+		match res {
+			Ok(v) => Ok(v),
+			Err(e) => {
+				self.buckets()?
+					.release_usage(self.ns, self.db, &self.bucket, &*self.store, reserved)
+					.await;
+				bail!(Error::ObjectStoreFailure(self.bucket.name.clone(), e))
+			}
+		}
+	}
+
 	/// Put a file into the bucket.
 	async fn put(&self, key: &ObjectKey, value: Value) -> Result<()> {
 		let payload = accept_payload(value)?;
 		self.require_writeable()?;
 		self.check_permission(BucketOperation::Put)?;
+		let delta = self.reserve_quota(key, payload.len() as u64, true).await?;
 
-		self.store
-			.put(key, payload)
-			.await
-			.map_err(|e| Error::ObjectStoreFailure(self.bucket.name.clone(), e))?;
+		let res = self.store.put(key, payload).await;
+		self.written(delta, res).await
+	}
+
+	/// Put a file into the bucket with metadata and preconditions.
+	async fn put_opts(&self, key: &ObjectKey, value: Value, opts: PutOptions) -> Result<()> {
+		let payload = accept_payload(value)?;
+		self.require_writeable()?;
+		self.check_permission(BucketOperation::Put)?;
+		let delta = self.reserve_quota(key, payload.len() as u64, true).await?;
+
+		let res = self.store.put_opts(key, payload, opts).await;
+		if let Conditional::Skipped(_) = self.written(delta, res).await? {
+			self.settle_usage(delta, 0, false).await?;
+			bail!(Error::ObjectPreconditionFailed(self.bucket.name.clone(), key.to_string()));
+		}
 
 		Ok(())
 	}
@@ -137,13 +244,12 @@ impl<'a> StreamingBucketOps<'a> {
 		let payload = accept_payload(value)?;
 		self.require_writeable()?;
 		self.check_permission(BucketOperation::Put)?;
+		let size = payload.len() as u64;
+		let delta = self.reserve_quota(key, size, false).await?;
 
-		self.store
-			.put_if_not_exists(key, payload)
-			.await
-			.map_err(|e| Error::ObjectStoreFailure(self.bucket.name.clone(), e))?;
-
-		Ok(())
+		let res = self.store.put_if_not_exists(key, payload).await;
+		let written = self.written(delta, res).await?;
+		self.settle_usage(delta, size, written).await
 	}
 
 	/// Get a file from the bucket.
@@ -163,6 +269,107 @@ impl<'a> StreamingBucketOps<'a> {
 		Ok(Some(Bytes(bytes)))
 	}
 
+	/// Get a file from the bucket, subject to a range and preconditions.
+	async fn get_opts(&self, key: &ObjectKey, opts: GetOptions) -> Result<Option<Bytes>> {
+		self.check_permission(BucketOperation::Get)?;
+
+		let res = self
+			.store
+			.get_opts(key, opts)
+			.await
+			.map_err(|e| Error::ObjectStoreFailure(self.bucket.name.clone(), e))?;
+
+		match res {
+			Conditional::Applied(bytes) => Ok(bytes.map(Bytes)),
+			Conditional::Skipped(PreconditionFailure::NotModified) => Ok(None),
+			Conditional::Skipped(PreconditionFailure::Failed) => {
+				bail!(Error::ObjectPreconditionFailed(self.bucket.name.clone(), key.to_string()))
+			}
+		}
+	}
+
+	/// Start a multipart upload to the bucket.
+	async fn create_upload(&self, key: &ObjectKey) -> Result<uuid::Uuid> {
+		self.require_writeable()?;
+		self.check_permission(BucketOperation::Put)?;
+
+		let upload = self
+			.store
+			.put_multipart(key)
+			.await
+			.map_err(|e| Error::ObjectStoreFailure(self.bucket.name.clone(), e))?;
+
+		let buckets = self.buckets()?;
+
+		Ok(buckets.create_upload(self.ns, self.db, &self.bucket.name, key.clone(), upload).await)
+	}
+
+	/// Append a chunk of data to a multipart upload.
+	async fn put_upload_part(&self, key: &ObjectKey, id: uuid::Uuid, value: Value) -> Result<()> {
+		let payload = accept_payload(value)?;
+		self.require_writeable()?;
+		self.check_permission(BucketOperation::Put)?;
+
+		let buckets = self.buckets()?;
+		let pending = buckets.get_upload(self.ns, self.db, &self.bucket.name, key, id)?;
+
+		let mut upload = pending.upload.lock().await;
+		// Fail early if the completed file would exceed the quota
+		let size = pending.uploaded.load(Ordering::Acquire) + payload.len() as u64;
+		self.check_quota(key, size).await?;
+
+		let len = payload.len() as u64;
+		upload
+			.put_part(payload)
+			.await
+			.map_err(|e| Error::ObjectStoreFailure(self.bucket.name.clone(), e))?;
+		pending.uploaded.fetch_add(len, Ordering::AcqRel);
+
+		Ok(())
+	}
+
+	/// Complete or abort a multipart upload.
+	async fn finish_upload(&self, key: &ObjectKey, id: uuid::Uuid, complete: bool) -> Result<()> {
+		self.require_writeable()?;
+		self.check_permission(BucketOperation::Put)?;
+
+		let buckets = self.buckets()?;
+		let pending = buckets.get_upload(self.ns, self.db, &self.bucket.name, key, id)?;
+		// The upload can not be resumed once it has been finished
+		buckets.remove_upload(id);
+
+		let mut upload = pending.upload.lock().await;
+		if !complete {
+			upload
+				.abort()
+				.await
+				.map_err(|e| Error::ObjectStoreFailure(self.bucket.name.clone(), e))?;
+			return Ok(());
+		}
+
+		let size = pending.uploaded.load(Ordering::Acquire);
+		let delta = match self.reserve_quota(key, size, true).await {
+			Ok(delta) => delta,
+			Err(e) => {
+				// Discard the uploaded data, as the upload can not be resumed
+				if let Err(e) = upload.abort().await {
+					warn!("Failed to abort multipart upload {id}: {e}");
+				}
+				return Err(e);
+			}
+		};
+
+		let res = upload.complete().await;
+		if res.is_err() {
+			// Discard the uploaded data, as the upload can not be resumed
+			if let Err(e) = upload.abort().await {
+				warn!("Failed to abort multipart upload {id}: {e}");
+			}
+		}
+
+		self.written(delta, res).await
+	}
+
 	/// Get file metadata from the bucket.
 	async fn head(&self, key: &ObjectKey) -> Result<Option<Value>> {
 		self.check_permission(BucketOperation::Head)?;
@@ -180,52 +387,54 @@ impl<'a> StreamingBucketOps<'a> {
 	async fn delete(&self, key: &ObjectKey) -> Result<()> {
 		self.require_writeable()?;
 		self.check_permission(BucketOperation::Delete)?;
+		let delta = self.release_quota(key).await?;
 
 		self.store
 			.delete(key)
 			.await
 			.map_err(|e| Error::ObjectStoreFailure(self.bucket.name.clone(), e))?;
 
-		Ok(())
+		self.apply_usage(delta).await
 	}
 
 	/// Copy a file within the bucket.
 	async fn copy(&self, src: &ObjectKey, dst: ObjectKey) -> Result<()> {
 		self.require_writeable()?;
 		self.check_permission(BucketOperation::Copy)?;
+		let (delta, _) = self.reserve_copy_quota(src, &dst, true).await?;
 
-		self.store
-			.copy(src, &dst)
-			.await
-			.map_err(|e| Error::ObjectStoreFailure(self.bucket.name.clone(), e))?;
-
-		Ok(())
+		let res = self.store.copy(src, &dst).await;
+		self.written(delta, res).await
 	}
 
 	/// Copy a file if destination doesn't exist.
 	async fn copy_if_not_exists(&self, src: &ObjectKey, dst: ObjectKey) -> Result<()> {
 		self.require_writeable()?;
 		self.check_permission(BucketOperation::Copy)?;
+		let (delta, size) = self.reserve_copy_quota(src, &dst, false).await?;
 
-		self.store
-			.copy_if_not_exists(src, &dst)
-			.await
-			.map_err(|e| Error::ObjectStoreFailure(self.bucket.name.clone(), e))?;
-
-		Ok(())
+		let res = self.store.copy_if_not_exists(src, &dst).await;
+		let written = self.written(delta, res).await?;
+		self.settle_usage(delta, size, written).await
 	}
 
 	/// Rename a file within the bucket.
 	async fn rename(&self, src: &ObjectKey, dst: ObjectKey) -> Result<()> {
 		self.require_writeable()?;
 		self.check_permission(BucketOperation::Rename)?;
+		// Any file which is overwritten by the rename is released
+		let delta = if *src == dst {
+			None
+		} else {
+			self.release_quota(&dst).await?
+		};
 
 		self.store
 			.rename(src, &dst)
 			.await
 			.map_err(|e| Error::ObjectStoreFailure(self.bucket.name.clone(), e))?;
 
-		Ok(())
+		self.apply_usage(delta).await
 	}
 
 	/// Rename a file if destination doesn't exist.
@@ -300,9 +509,16 @@ fn value_to_file(value: Value) -> Result<DestinationFile> {
 // =========================================================================
 
 async fn file_put_impl(ctx: &EvalContext<'_>, args: Vec<Value>) -> Result<Value> {
-	let (file, value): (File, Value) = FromArgs::from_args("file::put", args)?;
+	let (file, value, Optional(opts)): (File, Value, Optional<Object>) =
+		FromArgs::from_args("file::put", args)?;
 	let ops = StreamingBucketOps::new(ctx, &file.bucket).await?;
-	ops.put(&ObjectKey::new(file.key), value).await?;
+	match opts {
+		Some(opts) => {
+			let opts = PutOptions::try_from(opts)?;
+			ops.put_opts(&ObjectKey::new(file.key), value, opts).await?;
+		}
+		None => ops.put(&ObjectKey::new(file.key), value).await?,
+	}
 	Ok(Value::None)
 }
 
@@ -322,9 +538,15 @@ async fn file_put_if_not_exists_impl(ctx: &EvalContext<'_>, args: Vec<Value>) ->
 // =========================================================================
 
 async fn file_get_impl(ctx: &EvalContext<'_>, args: Vec<Value>) -> Result<Value> {
-	let (file,): (File,) = FromArgs::from_args("file::get", args)?;
+	let (file, Optional(opts)): (File, Optional<Object>) = FromArgs::from_args("file::get", args)?;
 	let ops = StreamingBucketOps::new(ctx, &file.bucket).await?;
-	let res = ops.get(&ObjectKey::new(file.key)).await?;
+	let res = match opts {
+		Some(opts) => {
+			let opts = GetOptions::try_from(opts)?;
+			ops.get_opts(&ObjectKey::new(file.key), opts).await?
+		}
+		None => ops.get(&ObjectKey::new(file.key)).await?,
+	};
 	Ok(res.map(Value::Bytes).unwrap_or_default())
 }
 
@@ -448,8 +670,6 @@ async fn file_exists_impl(ctx: &EvalContext<'_>, args: Vec<Value>) -> Result<Val
 // =========================================================================
 
 async fn file_list_impl(ctx: &EvalContext<'_>, args: Vec<Value>) -> Result<Value> {
-	use crate::fnc::args::Optional;
-
 	let (bucket, Optional(opts)): (String, Optional<Object>) =
 		FromArgs::from_args("file::list", args)?;
 	let ops = StreamingBucketOps::new(ctx, &bucket).await?;
@@ -458,6 +678,50 @@ async fn file_list_impl(ctx: &EvalContext<'_>, args: Vec<Value>) -> Result<Value
 	Ok(items.into())
 }
 
+// =========================================================================
+// file::multipart::create
+// =========================================================================
+
+async fn file_multipart_create_impl(ctx: &EvalContext<'_>, args: Vec<Value>) -> Result<Value> {
+	let (file,): (File,) = FromArgs::from_args("file::multipart::create", args)?;
+	let ops = StreamingBucketOps::new(ctx, &file.bucket).await?;
+	let id = ops.create_upload(&ObjectKey::new(file.key)).await?;
+	Ok(Value::Uuid(Uuid(id)))
+}
+
+// =========================================================================
+// file::multipart::put
+// =========================================================================
+
+async fn file_multipart_put_impl(ctx: &EvalContext<'_>, args: Vec<Value>) -> Result<Value> {
+	let (file, id, value): (File, Uuid, Value) = FromArgs::from_args("file::multipart::put", args)?;
+	let ops = StreamingBucketOps::new(ctx, &file.bucket).await?;
+	ops.put_upload_part(&ObjectKey::new(file.key), id.0, value).await?;
+	Ok(Value::None)
+}
+
+// =========================================================================
+// file::multipart::complete
+// =========================================================================
+
+async fn file_multipart_complete_impl(ctx: &EvalContext<'_>, args: Vec<Value>) -> Result<Value> {
+	let (file, id): (File, Uuid) = FromArgs::from_args("file::multipart::complete", args)?;
+	let ops = StreamingBucketOps::new(ctx, &file.bucket).await?;
+	ops.finish_upload(&ObjectKey::new(file.key), id.0, true).await?;
+	Ok(Value::None)
+}
+
+// =========================================================================
+// file::multipart::abort
+// =========================================================================
+
+async fn file_multipart_abort_impl(ctx: &EvalContext<'_>, args: Vec<Value>) -> Result<Value> {
+	let (file, id): (File, Uuid) = FromArgs::from_args("file::multipart::abort", args)?;
+	let ops = StreamingBucketOps::new(ctx, &file.bucket).await?;
+	ops.finish_upload(&ObjectKey::new(file.key), id.0, false).await?;
+	Ok(Value::None)
+}
+
 // =========================================================================
 // file::bucket (pure function)
 // =========================================================================
@@ -480,9 +744,9 @@ fn file_key_impl((file,): (File,)) -> Result<Value> {
 
 // Note: We use `Any` for file arguments in the macro signature because Kind::File takes
 // parameters (bucket types). The actual type checking happens via FromArgs::from_args.
-define_async_function!(FilePut, "file::put", (file: Any, value: Any) -> Any, file_put_impl);
+define_async_function!(FilePut, "file::put", (file: Any, value: Any, ?opts: Object) -> Any, file_put_impl);
 define_async_function!(FilePutIfNotExists, "file::put_if_not_exists", (file: Any, value: Any) -> Any, file_put_if_not_exists_impl);
-define_async_function!(FileGet, "file::get", (file: Any) -> Any, file_get_impl);
+define_async_function!(FileGet, "file::get", (file: Any, ?opts: Object) -> Any, file_get_impl);
 define_async_function!(FileHead, "file::head", (file: Any) -> Any, file_head_impl);
 define_async_function!(FileDelete, "file::delete", (file: Any) -> Any, file_delete_impl);
 define_async_function!(FileCopy, "file::copy", (src: Any, dst: Any) -> Any, file_copy_impl);
@@ -491,6 +755,10 @@ define_async_function!(FileRename, "file::rename", (file: Any, target: String) -
 define_async_function!(FileRenameIfNotExists, "file::rename_if_not_exists", (file: Any, target: String) -> Any, file_rename_if_not_exists_impl);
 define_async_function!(FileExists, "file::exists", (file: Any) -> Any, file_exists_impl);
 define_async_function!(FileList, "file::list", (bucket: String, ?opts: Object) -> Any, file_list_impl);
+define_async_function!(FileMultipartCreate, "file::multipart::create", (file: Any) -> Any, file_multipart_create_impl);
+define_async_function!(FileMultipartPut, "file::multipart::put", (file: Any, id: Uuid, value: Any) -> Any, file_multipart_put_impl);
+define_async_function!(FileMultipartComplete, "file::multipart::complete", (file: Any, id: Uuid) -> Any, file_multipart_complete_impl);
+define_async_function!(FileMultipartAbort, "file::multipart::abort", (file: Any, id: Uuid) -> Any, file_multipart_abort_impl);
 
 define_pure_function!(FileBucket, "file::bucket", (file: Any) -> Any, file_bucket_impl);
 define_pure_function!(FileKey, "file::key", (file: Any) -> Any, file_key_impl);
@@ -513,6 +781,10 @@ pub fn register(registry: &mut FunctionRegistry) {
 		FileRenameIfNotExists,
 		FileExists,
 		FileList,
+		FileMultipartCreate,
+		FileMultipartPut,
+		FileMultipartComplete,
+		FileMultipartAbort,
 		FileBucket,
 		FileKey,
 	);
@@ -30,6 +30,7 @@ use crate::exec::physical_expr::EvalContext;
 use crate::exec::{BoxFut, ContextLevel, SendSyncRequirement};
 use crate::expr::Kind;
 use crate::expr::idiom::Idiom;
+use crate::expr::operator::Fuzzy;
 use crate::idx::ft::MatchRef;
 use crate::idx::ft::fulltext::{FullTextIndex, QueryTerms, Scorer};
 use crate::val::{Number, RecordId, TableName, Value};
@@ -162,6 +163,8 @@ pub struct MatchContext {
 	pub idiom: Idiom,
 	/// The search query string from the right side of the MATCHES operator.
 	pub query: String,
+	/// The fuzzy matching options of the MATCHES operator.
+	pub fuzzy: Option<Fuzzy>,
 	/// The table name for index lookup.
 	pub table: TableName,
 	/// Lazily initialized full-text index resources.
@@ -170,10 +173,11 @@ pub struct MatchContext {
 
 impl MatchContext {
 	/// Create a new MatchContext from resolved MATCHES clause info.
-	pub fn new(idiom: Idiom, query: String, table: TableName) -> Self {
+	pub fn new(idiom: Idiom, query: String, fuzzy: Option<Fuzzy>, table: TableName) -> Self {
 		Self {
 			idiom,
 			query,
+			fuzzy,
 			table,
 			ft_cache: tokio::sync::OnceCell::new(),
 		}
@@ -245,7 +249,13 @@ impl MatchContext {
 					let mut stack = reblessive::TreeStack::new();
 					stack
 						.enter(|stk| {
-							fti.extract_querying_terms(stk, frozen, opt, self.query.clone())
+							fti.extract_querying_terms(
+								stk,
+								frozen,
+								opt,
+								self.query.clone(),
+								self.fuzzy,
+							)
 						})
 						.finish()
 						.await?
@@ -337,6 +347,8 @@ pub struct MatchInfo {
 	pub idiom: Idiom,
 	/// The search query string from the right side of the MATCHES operator.
 	pub query: String,
+	/// The fuzzy matching options of the MATCHES operator.
+	pub fuzzy: Option<Fuzzy>,
 }
 
 /// Planning-time context mapping match_ref numbers to MATCHES clause info.
@@ -406,9 +418,12 @@ impl MatchesContext {
 		});
 
 		match info {
-			Some(info) => {
-				Ok(Arc::new(MatchContext::new(info.idiom.clone(), info.query.clone(), table)))
-			}
+			Some(info) => Ok(Arc::new(MatchContext::new(
+				info.idiom.clone(),
+				info.query.clone(),
+				info.fuzzy,
+				table,
+			))),
 			None => {
 				// If there are no MATCHES clauses at all, provide a clear error
 				if self.matches.is_empty() {
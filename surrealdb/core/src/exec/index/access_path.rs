@@ -102,6 +102,16 @@ pub enum AccessPath {
 		ef: u32,
 	},
 
+	/// Substring or regex search using a trigram index.
+	///
+	/// Returns the records storing every trigram, which are candidates
+	/// to be filtered by the WHERE condition.
+	TrigramSearch {
+		index_ref: IndexRef,
+		/// The trigrams required by the pattern
+		trigrams: Vec<String>,
+	},
+
 	/// Union of multiple index scans for OR conditions.
 	///
 	/// Each sub-path handles one branch of the OR; results are
@@ -158,6 +168,12 @@ pub enum BTreeAccess {
 		operator: crate::expr::operator::MatchesOperator,
 	},
 
+	/// Trigram index access
+	Trigram {
+		/// The trigrams required by the pattern
+		trigrams: Vec<String>,
+	},
+
 	/// KNN vector search access via HNSW index.
 	Knn {
 		/// The query vector
@@ -3,6 +3,7 @@
 //! The [`IndexAnalyzer`] examines query conditions and ORDER BY clauses to find
 //! indexes that can accelerate the query.
 
+use std::borrow::Cow;
 use std::sync::Arc;
 
 use super::access_path::{AccessPath, BTreeAccess, IndexRef, RangeBound, select_access_path};
@@ -11,9 +12,10 @@ use crate::exec::planner::util::try_literal_to_value;
 use crate::expr::operator::{MatchesOperator, NearestNeighbor, PrefixOperator};
 use crate::expr::order::Ordering;
 use crate::expr::with::With;
-use crate::expr::{BinaryOperator, Cond, Expr, Idiom};
+use crate::expr::{BinaryOperator, Cond, Expr, Function, Idiom, Literal};
 use crate::idx::planner::ScanDirection;
-use crate::val::{Number, Value};
+use crate::idx::trigram::TrigramQuery;
+use crate::val::{Number, Regex, Value};
 
 /// Analyzes query conditions to find matching indexes.
 pub struct IndexAnalyzer<'a> {
@@ -25,7 +27,19 @@ pub struct IndexAnalyzer<'a> {
 
 impl<'a> IndexAnalyzer<'a> {
 	/// Create a new analyzer for the given table and indexes.
-	pub fn new(indexes: Arc<[IndexDefinition]>, with_hints: Option<&'a With>) -> Self {
+	///
+	/// Partial indexes whose condition is not implied by the query condition
+	/// are discarded, as they don't contain every matching record.
+	pub fn new(
+		indexes: Arc<[IndexDefinition]>,
+		cond: Option<&Cond>,
+		with_hints: Option<&'a With>,
+	) -> Self {
+		let indexes = if indexes.iter().all(|ix| ix.is_implied_by(cond)) {
+			indexes
+		} else {
+			indexes.iter().filter(|ix| ix.is_implied_by(cond)).cloned().collect()
+		};
 		Self {
 			indexes,
 			with_hints,
@@ -448,16 +462,21 @@ impl<'a> IndexAnalyzer<'a> {
 		right: &Expr,
 	) -> Option<SimpleCondition> {
 		let (idiom, value, position) = match (left, right) {
-			(Expr::Idiom(idiom), Expr::Literal(lit)) => {
-				if let Some(value) = try_literal_to_value(lit) {
-					(idiom.clone(), value, IdiomPosition::Left)
+			(Expr::Literal(_), Expr::Literal(_)) => return None,
+			(left, Expr::Literal(lit)) => {
+				if let Some(idiom) = operand_idiom(left)
+					&& let Some(value) = try_literal_to_value(lit)
+				{
+					(idiom.into_owned(), value, IdiomPosition::Left)
 				} else {
 					return None;
 				}
 			}
-			(Expr::Literal(lit), Expr::Idiom(idiom)) => {
-				if let Some(value) = try_literal_to_value(lit) {
-					(idiom.clone(), value, IdiomPosition::Right)
+			(Expr::Literal(lit), right) => {
+				if let Some(idiom) = operand_idiom(right)
+					&& let Some(value) = try_literal_to_value(lit)
+				{
+					(idiom.into_owned(), value, IdiomPosition::Right)
 				} else {
 					return None;
 				}
@@ -695,11 +714,33 @@ impl<'a> IndexAnalyzer<'a> {
 						self.try_match_containment(left, op, right, candidates);
 						self.try_match_comparison(left, op, right, candidates);
 					}
+					BinaryOperator::Equal => {
+						self.try_match_regex(left, right, candidates);
+						self.try_match_comparison(left, op, right, candidates);
+					}
 					_ => {
 						self.try_match_comparison(left, op, right, candidates);
 					}
 				}
 			}
+			// String predicates which a trigram index can answer
+			Expr::FunctionCall(f) => {
+				if let Function::Normal(name) = &f.receiver
+					&& matches!(name.as_str(), "string::contains" | "string::matches")
+					&& let [Expr::Idiom(idiom), Expr::Literal(lit)] = f.arguments.as_slice()
+				{
+					let pattern = match (name.as_str(), lit) {
+						("string::contains", Literal::String(s)) => Value::String(s.clone()),
+						("string::matches", Literal::Regex(r)) => Value::Regex(r.clone()),
+						("string::matches", Literal::String(s)) => match s.parse::<Regex>() {
+							Ok(r) => Value::Regex(r),
+							Err(_) => return,
+						},
+						_ => return,
+					};
+					self.try_match_trigram(idiom, &pattern, candidates);
+				}
+			}
 			// Nested expression in parentheses (but NOT negation)
 			Expr::Prefix {
 				op,
@@ -725,15 +766,20 @@ impl<'a> IndexAnalyzer<'a> {
 	) {
 		// Extract idiom and value from the comparison
 		let (idiom, value, position) = match (left, right) {
-			(Expr::Idiom(idiom), Expr::Literal(lit)) => {
-				if let Some(value) = try_literal_to_value(lit) {
+			(Expr::Literal(_), Expr::Literal(_)) => return,
+			(left, Expr::Literal(lit)) => {
+				if let Some(idiom) = operand_idiom(left)
+					&& let Some(value) = try_literal_to_value(lit)
+				{
 					(idiom, value, IdiomPosition::Left)
 				} else {
 					return;
 				}
 			}
-			(Expr::Literal(lit), Expr::Idiom(idiom)) => {
-				if let Some(value) = try_literal_to_value(lit) {
+			(Expr::Literal(lit), right) => {
+				if let Some(idiom) = operand_idiom(right)
+					&& let Some(value) = try_literal_to_value(lit)
+				{
 					(idiom, value, IdiomPosition::Right)
 				} else {
 					return;
@@ -758,7 +804,7 @@ impl<'a> IndexAnalyzer<'a> {
 
 			// Check if the idiom matches the first column of the index
 			if let Some(first_col) = ix_def.cols.first()
-				&& idiom_matches(idiom, first_col)
+				&& idiom_matches(&idiom, first_col)
 				&& let Some(access) =
 					self.match_operator_to_access(op, &value, position, &ix_def.index)
 			{
@@ -990,6 +1036,49 @@ impl<'a> IndexAnalyzer<'a> {
 		}
 	}
 
+	/// Try to match a `field = /regex/` expression to a trigram index.
+	fn try_match_regex(&self, left: &Expr, right: &Expr, candidates: &mut Vec<IndexCandidate>) {
+		if let (Expr::Idiom(idiom), Expr::Literal(Literal::Regex(r)))
+		| (Expr::Literal(Literal::Regex(r)), Expr::Idiom(idiom)) = (left, right)
+		{
+			self.try_match_trigram(idiom, &Value::Regex(r.clone()), candidates);
+		}
+	}
+
+	/// Try to match a substring or regex pattern to a trigram index.
+	///
+	/// Patterns which do not require any literal of at least three
+	/// characters can not use the index.
+	fn try_match_trigram(
+		&self,
+		idiom: &Idiom,
+		pattern: &Value,
+		candidates: &mut Vec<IndexCandidate>,
+	) {
+		let Some(trigrams) = TrigramQuery::new(pattern).map(|q| q.trigrams()) else {
+			return;
+		};
+		if trigrams.is_empty() {
+			return;
+		}
+		for (idx, ix_def) in self.indexes.iter().enumerate() {
+			if ix_def.prepare_remove || !matches!(ix_def.index, Index::Trigram) {
+				continue;
+			}
+			if let Some(first_col) = ix_def.cols.first()
+				&& idiom_matches(idiom, first_col)
+			{
+				candidates.push(IndexCandidate {
+					index_ref: IndexRef::new(self.indexes.clone(), idx),
+					access: BTreeAccess::Trigram {
+						trigrams: trigrams.clone(),
+					},
+					covers_order: false,
+				});
+			}
+		}
+	}
+
 	/// Try to match a KNN expression to an HNSW index.
 	fn try_match_knn(
 		&self,
@@ -1233,6 +1322,7 @@ impl IndexCandidate {
 	/// - Full scan with order: low
 	/// - Order coverage: bonus points
 	/// - FullText and KNN: high (specialized search)
+	/// - Trigram: below equality, as candidates must be filtered
 	pub fn score(&self) -> u32 {
 		let mut score = 0u32;
 
@@ -1281,6 +1371,13 @@ impl IndexCandidate {
 				// when the query uses nearest neighbor operators
 				score += 800;
 			}
+			BTreeAccess::Trigram {
+				..
+			} => {
+				// Trigram candidates still need to be filtered, so an
+				// equality lookup is preferred
+				score += 450;
+			}
 		}
 
 		// Bonus for covering ORDER BY
@@ -1312,6 +1409,12 @@ impl IndexCandidate {
 				k: *k,
 				ef: *ef,
 			},
+			BTreeAccess::Trigram {
+				trigrams,
+			} => AccessPath::TrigramSearch {
+				index_ref: self.index_ref.clone(),
+				trigrams: trigrams.clone(),
+			},
 			_ => AccessPath::BTreeScan {
 				index_ref: self.index_ref.clone(),
 				access: self.access.clone(),
@@ -1362,6 +1465,26 @@ fn idiom_matches(expr_idiom: &Idiom, index_col: &Idiom) -> bool {
 	true
 }
 
+/// Returns the idiom to match against index columns for an operand of a
+/// comparison: either a field path, or an expression which may be the column
+/// of an expression index.
+fn operand_idiom(expr: &Expr) -> Option<Cow<'_, Idiom>> {
+	match expr {
+		Expr::Idiom(idiom) => Some(Cow::Borrowed(idiom)),
+		Expr::FunctionCall(_)
+		| Expr::Binary {
+			..
+		}
+		| Expr::Prefix {
+			..
+		}
+		| Expr::Postfix {
+			..
+		} => Some(Cow::Owned(Idiom::expression(expr.clone()))),
+		_ => None,
+	}
+}
+
 /// Check if an idiom matches an index column for containment operators.
 ///
 /// Unlike `idiom_matches`, this allows `Part::All` in the index column.
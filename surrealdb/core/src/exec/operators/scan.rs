@@ -1,7 +1,7 @@
 //! Scan operators — operators that perform storage I/O and check permissions.
 //!
 //! All operators in this module read data from the underlying key-value store
-//! (table scans, index scans, full-text search, KNN, graph traversals, etc.)
+//! (table scans, index scans, full-text search, trigram search, KNN, graph traversals, etc.)
 //! and handle table/field-level permissions.
 
 pub(crate) mod common;
@@ -17,6 +17,7 @@ mod record_id;
 mod reference;
 pub(crate) mod resolved;
 mod table;
+mod trigram;
 mod union_index;
 
 pub use count::CountScan;
@@ -29,4 +30,5 @@ pub(crate) use pipeline::determine_scan_direction;
 pub use record_id::RecordIdScan;
 pub use reference::{ReferenceScan, ReferenceScanOutput};
 pub use table::TableScan;
+pub use trigram::TrigramScan;
 pub use union_index::UnionIndexScan;
@@ -5,9 +5,10 @@
 
 use std::sync::Arc;
 
-use crate::catalog::{DatabaseId, NamespaceId};
+use crate::catalog::{DatabaseId, IndexId, NamespaceId, Record};
 use crate::exec::{ControlFlowExt, EvalContext, ExecutionContext, PhysicalExpr};
 use crate::expr::ControlFlow;
+use crate::key::index::cv::Cv;
 use crate::kvs::{CachePolicy, Transaction};
 use crate::val::{RecordId, RecordIdKey, Value};
 
@@ -160,8 +161,61 @@ pub(crate) async fn fetch_and_filter_records_batch(
 		.getm_records(ns_id, db_id, rids, version, cache_policy)
 		.await
 		.context("Failed to fetch records")?;
+	filter_records_batch(ctx, records, select_permission, check_perms).await
+}
 
-	let mut values = Vec::with_capacity(rids.len());
+/// Fetch the partial records stored by a covering index for a batch of
+/// [`RecordId`]s, applying permission filtering to each record.
+///
+/// A partial record holds the record id and the indexed and included fields.
+/// Records indexed before their partial record was stored are fetched in
+/// full.
+///
+/// Used by [`super::index_scan::IndexScan`] when the index covers every field
+/// read by the query.
+#[allow(clippy::too_many_arguments)]
+pub(crate) async fn fetch_and_filter_covered_records_batch(
+	ctx: &ExecutionContext,
+	txn: &Transaction,
+	ns_id: NamespaceId,
+	db_id: DatabaseId,
+	ix_id: IndexId,
+	rids: &[RecordId],
+	select_permission: &crate::exec::permission::PhysicalPermission,
+	check_perms: bool,
+) -> Result<Vec<Value>, ControlFlow> {
+	let keys = rids.iter().map(|rid| Cv::new(ns_id, db_id, &rid.table, ix_id, &rid.key)).collect();
+	let covered = txn.getm(keys, None).await.context("Failed to fetch covered records")?;
+	let missing: Vec<RecordId> = rids
+		.iter()
+		.zip(covered.iter())
+		.filter(|(_, c)| c.is_none())
+		.map(|(rid, _)| rid.clone())
+		.collect();
+	let mut fetched = txn
+		.getm_records(ns_id, db_id, &missing, None, CachePolicy::ReadOnly)
+		.await
+		.context("Failed to fetch records")?
+		.into_iter();
+	let records = covered
+		.into_iter()
+		.filter_map(|c| match c {
+			Some(record) => Some(Arc::new(record)),
+			None => fetched.next(),
+		})
+		.collect();
+	filter_records_batch(ctx, records, select_permission, check_perms).await
+}
+
+/// Apply permission filtering to a batch of fetched records, skipping the
+/// records which don't exist.
+async fn filter_records_batch(
+	ctx: &ExecutionContext,
+	records: Vec<Arc<Record>>,
+	select_permission: &crate::exec::permission::PhysicalPermission,
+	check_perms: bool,
+) -> Result<Vec<Value>, ControlFlow> {
+	let mut values = Vec::with_capacity(records.len());
 	for record in records {
 		if record.data.is_none() {
 			continue;
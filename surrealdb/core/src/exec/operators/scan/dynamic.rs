@@ -7,7 +7,7 @@ use tracing::instrument;
 use super::pipeline::{
 	build_field_state, determine_scan_direction, eval_limit_expr, kv_scan_stream,
 };
-use super::{FullTextScan, IndexScan, KnnScan};
+use super::{FullTextScan, IndexScan, KnnScan, TrigramScan};
 use crate::catalog::{DatabaseId, NamespaceId, Permission};
 use crate::err::Error;
 use crate::exec::index::access_path::{AccessPath, select_access_path};
@@ -546,7 +546,7 @@ async fn resolve_table_scan_stream(
 		let indexes =
 			db_ctx.get_table_indexes(&cfg.table_name).await.context("Failed to fetch indexes")?;
 
-		let analyzer = IndexAnalyzer::new(indexes, cfg.with.as_ref());
+		let analyzer = IndexAnalyzer::new(indexes, resolved_cond.as_ref(), cfg.with.as_ref());
 		let candidates = analyzer.analyze(resolved_cond.as_ref(), cfg.order.as_ref());
 		if candidates.is_empty() {
 			// No single-index candidates -- try multi-index union for OR conditions
@@ -601,6 +601,16 @@ async fn resolve_table_scan_stream(
 			Ok((stream, 0))
 		}
 
+		// Substring or regex search via trigram index
+		Some(AccessPath::TrigramSearch {
+			index_ref,
+			trigrams,
+		}) => {
+			let tg_op = TrigramScan::new(index_ref, trigrams, cfg.table_name, cfg.version);
+			let stream = tg_op.execute(ctx)?;
+			Ok((stream, 0))
+		}
+
 		// KNN vector search via HNSW index
 		Some(AccessPath::KnnSearch {
 			index_ref,
@@ -694,6 +704,15 @@ fn create_index_operator(
 			cfg.table_name.clone(),
 			cfg.version.clone(),
 		)),
+		AccessPath::TrigramSearch {
+			index_ref,
+			trigrams,
+		} => Arc::new(TrigramScan::new(
+			index_ref.clone(),
+			trigrams.clone(),
+			cfg.table_name.clone(),
+			cfg.version.clone(),
+		)),
 		AccessPath::KnnSearch {
 			index_ref,
 			vector,
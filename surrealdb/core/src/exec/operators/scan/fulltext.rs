@@ -210,7 +210,9 @@ impl ExecOperator for FullTextScan {
 			let query_terms = {
 				let mut stack = TreeStack::new();
 				stack
-					.enter(|stk| fti.extract_querying_terms(stk, frozen_ctx, opt, query.clone()))
+					.enter(|stk| {
+						fti.extract_querying_terms(stk, frozen_ctx, opt, query.clone(), operator.fuzzy)
+					})
 					.finish()
 					.await
 					.context("Failed to extract query terms")?
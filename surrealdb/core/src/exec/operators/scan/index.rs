@@ -8,9 +8,10 @@ use std::sync::Arc;
 use async_trait::async_trait;
 use surrealdb_types::ToSql;
 
-use super::common::fetch_and_filter_records_batch;
+use super::common::{fetch_and_filter_covered_records_batch, fetch_and_filter_records_batch};
 use super::pipeline::eval_limit_expr;
 use super::resolved::ResolvedTableContext;
+use crate::catalog::{DatabaseId, IndexId, NamespaceId};
 use crate::err::Error;
 use crate::exec::index::access_path::{BTreeAccess, IndexRef};
 use crate::exec::index::iterator::btree::{CompoundEqualIterator, CompoundRangeIterator};
@@ -28,7 +29,8 @@ use crate::exec::{
 use crate::expr::ControlFlow;
 use crate::iam::Action;
 use crate::idx::planner::ScanDirection;
-use crate::kvs::CachePolicy;
+use crate::kvs::{CachePolicy, Transaction};
+use crate::val::{RecordId, Value};
 
 /// Index scan operator for B-tree indexes (Idx and Uniq).
 ///
@@ -64,6 +66,10 @@ pub struct IndexScan {
 	/// continues until either the range is exhausted or the consumer
 	/// drops the stream.
 	pub(crate) batch_ceiling: Option<Arc<dyn PhysicalExpr>>,
+	/// Whether the index covers every field read by the query, in which case
+	/// the partial records stored by the index are read instead of the
+	/// records themselves.
+	pub(crate) covering: bool,
 	/// Per-operator runtime metrics for EXPLAIN ANALYZE.
 	pub(crate) metrics: Arc<OperatorMetrics>,
 }
@@ -88,6 +94,7 @@ impl IndexScan {
 			version,
 			resolved: None,
 			batch_ceiling: None,
+			covering: false,
 			metrics: Arc::new(OperatorMetrics::new()),
 		}
 	}
@@ -109,6 +116,13 @@ impl IndexScan {
 		self.batch_ceiling = ceiling;
 		self
 	}
+
+	/// Read the partial records stored by a covering index instead of the
+	/// records themselves.
+	pub(crate) fn with_covering(mut self, covering: bool) -> Self {
+		self.covering = covering;
+		self
+	}
 }
 
 #[cfg_attr(target_family = "wasm", async_trait(?Send))]
@@ -162,14 +176,17 @@ impl ExecOperator for IndexScan {
 					format!("[{}]", prefix_str)
 				}
 			}
-			// FullText and KNN should use dedicated operators
+			// FullText, trigram and KNN should use dedicated operators
 			BTreeAccess::FullText {
 				..
 			}
+			| BTreeAccess::Trigram {
+				..
+			}
 			| BTreeAccess::Knn {
 				..
 			} => {
-				unreachable!("IndexScan does not support FullText or KNN access")
+				unreachable!("IndexScan does not support FullText, trigram or KNN access")
 			}
 		};
 		let mut attrs = vec![
@@ -183,6 +200,9 @@ impl ExecOperator for IndexScan {
 		if let Some(ref start) = self.start {
 			attrs.push(("offset".to_string(), start.to_sql()));
 		}
+		if self.covering {
+			attrs.push(("covering".to_string(), "true".to_string()));
+		}
 		attrs
 	}
 
@@ -321,6 +341,7 @@ impl ExecOperator for IndexScan {
 		let ceiling_expr = self.batch_ceiling.clone();
 		let version_expr = self.version.clone();
 		let resolved = self.resolved.clone();
+		let covering = self.covering.then(|| self.index_ref.index_id);
 		let ctx = ctx.clone();
 
 		let stream = async_stream::try_stream! {
@@ -434,8 +455,8 @@ impl ExecOperator for IndexScan {
 					let rids = iter.next_batch(&txn).await
 						.context("Failed to iterate index")?;
 
-					let mut values = fetch_and_filter_records_batch(
-						&ctx, &txn, ns_id, db_id, &rids, &select_permission, check_perms, version,
+					let mut values = fetch_index_records_batch(
+						&ctx, &txn, ns_id, db_id, covering, &rids, &select_permission, check_perms, version,
 						CachePolicy::ReadWrite,
 					).await?;
 
@@ -464,8 +485,8 @@ impl ExecOperator for IndexScan {
 							break;
 						}
 
-						let mut values = fetch_and_filter_records_batch(
-							&ctx, &txn, ns_id, db_id, &rids, &select_permission, check_perms, version,
+						let mut values = fetch_index_records_batch(
+							&ctx, &txn, ns_id, db_id, covering, &rids, &select_permission, check_perms, version,
 							CachePolicy::ReadOnly,
 						).await?;
 
@@ -500,8 +521,8 @@ impl ExecOperator for IndexScan {
 							.context("Failed to iterate index")?;
 						if rids.is_empty() { break; }
 
-						let mut values = fetch_and_filter_records_batch(
-							&ctx, &txn, ns_id, db_id, &rids, &select_permission, check_perms, version,
+						let mut values = fetch_index_records_batch(
+							&ctx, &txn, ns_id, db_id, covering, &rids, &select_permission, check_perms, version,
 							CachePolicy::ReadOnly,
 						).await?;
 
@@ -529,8 +550,8 @@ impl ExecOperator for IndexScan {
 							.context("Failed to iterate index")?;
 						if rids.is_empty() { break; }
 
-						let mut values = fetch_and_filter_records_batch(
-							&ctx, &txn, ns_id, db_id, &rids, &select_permission, check_perms, version,
+						let mut values = fetch_index_records_batch(
+							&ctx, &txn, ns_id, db_id, covering, &rids, &select_permission, check_perms, version,
 							CachePolicy::ReadOnly,
 						).await?;
 
@@ -580,8 +601,8 @@ impl ExecOperator for IndexScan {
 						// scanning the next batch of index entries concurrently.
 						// This halves serial latency on TiKV.
 						let (values_result, next_rids_result) = if remaining > 0 {
-							let fetch_fut = fetch_and_filter_records_batch(
-								&ctx, &txn, ns_id, db_id, &rids, &select_permission, check_perms, version,
+							let fetch_fut = fetch_index_records_batch(
+								&ctx, &txn, ns_id, db_id, covering, &rids, &select_permission, check_perms, version,
 								CachePolicy::ReadOnly,
 							);
 							let scan_fut = iter.next_batch(&txn, remaining.min(batch_max));
@@ -589,8 +610,8 @@ impl ExecOperator for IndexScan {
 							(v, Some(n))
 						} else {
 							// No more entries needed; skip the prefetch.
-							let v = fetch_and_filter_records_batch(
-								&ctx, &txn, ns_id, db_id, &rids, &select_permission, check_perms, version,
+							let v = fetch_index_records_batch(
+								&ctx, &txn, ns_id, db_id, covering, &rids, &select_permission, check_perms, version,
 								CachePolicy::ReadOnly,
 							).await;
 							(v, None)
@@ -643,16 +664,16 @@ impl ExecOperator for IndexScan {
 						// Overlap: fetch records for the current batch while
 						// scanning the next batch of index entries concurrently.
 						let (values_result, next_rids_result) = if remaining > 0 {
-							let fetch_fut = fetch_and_filter_records_batch(
-								&ctx, &txn, ns_id, db_id, &rids, &select_permission, check_perms, version,
+							let fetch_fut = fetch_index_records_batch(
+								&ctx, &txn, ns_id, db_id, covering, &rids, &select_permission, check_perms, version,
 								CachePolicy::ReadOnly,
 							);
 							let scan_fut = iter.next_batch(&txn, remaining.min(batch_max));
 							let (v, n) = futures::join!(fetch_fut, scan_fut);
 							(v, Some(n))
 						} else {
-							let v = fetch_and_filter_records_batch(
-								&ctx, &txn, ns_id, db_id, &rids, &select_permission, check_perms, version,
+							let v = fetch_index_records_batch(
+								&ctx, &txn, ns_id, db_id, covering, &rids, &select_permission, check_perms, version,
 								CachePolicy::ReadOnly,
 							).await;
 							(v, None)
@@ -676,10 +697,12 @@ impl ExecOperator for IndexScan {
 					}
 				}
 
-				// FullText and KNN should use dedicated operators
-				(BTreeAccess::FullText { .. }, _) | (BTreeAccess::Knn { .. }, _) => {
+				// FullText, trigram and KNN should use dedicated operators
+				(BTreeAccess::FullText { .. }, _)
+				| (BTreeAccess::Trigram { .. }, _)
+				| (BTreeAccess::Knn { .. }, _) => {
 					Err(ControlFlow::Err(anyhow::anyhow!(
-						"IndexScan does not support FullText or KNN access - use dedicated operators"
+						"IndexScan does not support FullText, trigram or KNN access - use dedicated operators"
 					)))?
 				}
 			}
@@ -688,3 +711,49 @@ impl ExecOperator for IndexScan {
 		Ok(monitor_stream(Box::pin(stream), "IndexScan", &self.metrics))
 	}
 }
+
+/// Fetch the records of a batch of index entries, reading the partial records
+/// stored by the index when it covers the query.
+#[allow(clippy::too_many_arguments)]
+async fn fetch_index_records_batch(
+	ctx: &ExecutionContext,
+	txn: &Transaction,
+	ns_id: NamespaceId,
+	db_id: DatabaseId,
+	covering: Option<IndexId>,
+	rids: &[RecordId],
+	select_permission: &PhysicalPermission,
+	check_perms: bool,
+	version: Option<u64>,
+	cache_policy: CachePolicy,
+) -> Result<Vec<Value>, ControlFlow> {
+	match covering {
+		Some(ix_id) => {
+			fetch_and_filter_covered_records_batch(
+				ctx,
+				txn,
+				ns_id,
+				db_id,
+				ix_id,
+				rids,
+				select_permission,
+				check_perms,
+			)
+			.await
+		}
+		None => {
+			fetch_and_filter_records_batch(
+				ctx,
+				txn,
+				ns_id,
+				db_id,
+				rids,
+				select_permission,
+				check_perms,
+				version,
+				cache_policy,
+			)
+			.await
+		}
+	}
+}
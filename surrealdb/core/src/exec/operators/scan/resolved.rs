@@ -47,6 +47,19 @@ impl ResolvedTableContext {
 		filter_field_state_for_projection(&self.field_state, needed_fields)
 	}
 
+	/// Whether records can be read from the partial records stored by a
+	/// covering index, as neither the table and field permissions nor the
+	/// computed fields read the rest of the record.
+	pub fn allows_partial_records(&self) -> bool {
+		!matches!(self.select_permission, PhysicalPermission::Conditional(_))
+			&& self.field_state.computed_fields.is_empty()
+			&& !self
+				.field_state
+				.field_permissions
+				.values()
+				.any(|p| matches!(p, PhysicalPermission::Conditional(_)))
+	}
+
 	/// Get the SELECT permission, respecting the `check_perms` flag.
 	/// When `check_perms` is false, returns `Allow` regardless of the
 	/// pre-compiled permission.
@@ -0,0 +1,200 @@
+//! Trigram search scan operator.
+//!
+//! This operator retrieves the records storing every trigram required by a
+//! substring or regex pattern, using a trigram index. The records are
+//! candidates, which the WHERE condition filters afterwards.
+
+use std::sync::Arc;
+
+use async_trait::async_trait;
+
+use super::common::{BATCH_SIZE, fetch_and_filter_records_batch};
+use super::resolved::ResolvedTableContext;
+use crate::err::Error;
+use crate::exec::index::access_path::IndexRef;
+use crate::exec::permission::{
+	PhysicalPermission, convert_permission_to_physical, should_check_perms,
+	validate_record_user_access,
+};
+use crate::exec::{
+	AccessMode, ContextLevel, ExecOperator, ExecutionContext, FlowResult, OperatorMetrics,
+	PhysicalExpr, ValueBatch, ValueBatchStream, monitor_stream,
+};
+use crate::expr::{ControlFlow, ControlFlowExt};
+use crate::iam::Action;
+use crate::idx::IndexKeyBase;
+use crate::idx::trigram::TrigramIntersection;
+use crate::kvs::CachePolicy;
+use crate::val::RecordId;
+
+/// Trigram search scan operator.
+///
+/// Intersects the records stored under each trigram of a pattern in a
+/// trigram index, and returns these records.
+#[derive(Debug)]
+pub struct TrigramScan {
+	/// Reference to the index definition
+	pub index_ref: IndexRef,
+	/// The trigrams required by the pattern
+	pub trigrams: Vec<String>,
+	/// Table name for record fetching
+	pub table_name: crate::val::TableName,
+	/// Optional VERSION timestamp for time-travel queries.
+	pub(crate) version: Option<Arc<dyn PhysicalExpr>>,
+	/// Plan-time resolved table context. When present, `execute()` skips
+	/// runtime table def + permission lookup.
+	pub(crate) resolved: Option<ResolvedTableContext>,
+	/// Per-operator runtime metrics for EXPLAIN ANALYZE.
+	pub(crate) metrics: Arc<OperatorMetrics>,
+}
+
+impl TrigramScan {
+	pub(crate) fn new(
+		index_ref: IndexRef,
+		trigrams: Vec<String>,
+		table_name: crate::val::TableName,
+		version: Option<Arc<dyn PhysicalExpr>>,
+	) -> Self {
+		Self {
+			index_ref,
+			trigrams,
+			table_name,
+			version,
+			resolved: None,
+			metrics: Arc::new(OperatorMetrics::new()),
+		}
+	}
+
+	/// Set the plan-time resolved table context.
+	pub(crate) fn with_resolved(mut self, resolved: ResolvedTableContext) -> Self {
+		self.resolved = Some(resolved);
+		self
+	}
+}
+
+#[cfg_attr(target_family = "wasm", async_trait(?Send))]
+#[cfg_attr(not(target_family = "wasm"), async_trait)]
+impl ExecOperator for TrigramScan {
+	fn name(&self) -> &'static str {
+		"TrigramScan"
+	}
+
+	fn attrs(&self) -> Vec<(String, String)> {
+		vec![
+			("index".to_string(), self.index_ref.name.clone()),
+			("trigrams".to_string(), self.trigrams.join(", ")),
+		]
+	}
+
+	fn required_context(&self) -> ContextLevel {
+		ContextLevel::Database
+	}
+
+	fn access_mode(&self) -> AccessMode {
+		AccessMode::ReadOnly
+	}
+
+	fn metrics(&self) -> Option<&OperatorMetrics> {
+		Some(&self.metrics)
+	}
+
+	fn execute(&self, ctx: &ExecutionContext) -> FlowResult<ValueBatchStream> {
+		let db_ctx = ctx.database()?.clone();
+
+		// Validate record user has access to this namespace/database
+		validate_record_user_access(&db_ctx)?;
+
+		// Check if we need to enforce permissions
+		let check_perms = should_check_perms(&db_ctx, Action::View)?;
+
+		// Clone for the async block
+		let index_ref = self.index_ref.clone();
+		let trigrams = self.trigrams.clone();
+		let table_name = self.table_name.clone();
+		let version_expr = self.version.clone();
+		let resolved = self.resolved.clone();
+		let ctx = ctx.clone();
+
+		let stream = async_stream::try_stream! {
+			// Get namespace and database IDs
+			let db_ctx = ctx.database().context("TrigramScan requires database context")?;
+			let ns = Arc::clone(&db_ctx.ns_ctx.ns);
+			let db = Arc::clone(&db_ctx.db);
+			let txn = ctx.txn();
+
+			// Evaluate VERSION expression
+			let version: Option<u64> = match &version_expr {
+				Some(expr) => {
+					let eval_ctx = crate::exec::EvalContext::from_exec_ctx(&ctx);
+					let v = expr.evaluate(eval_ctx).await?;
+					Some(
+						v.cast_to::<crate::val::Datetime>()
+							.map_err(|e| anyhow::anyhow!("{e}"))?
+							.to_version_stamp()?,
+					)
+				}
+				None => None,
+			};
+
+			// Resolve table permissions: plan-time fast path or runtime fallback
+			let select_permission = if let Some(ref res) = resolved {
+				res.select_permission(check_perms)
+			} else if check_perms {
+				let table_def = db_ctx
+					.get_table_def(&table_name)
+					.await
+					.context("Failed to get table")?;
+
+				if let Some(def) = &table_def {
+					convert_permission_to_physical(&def.permissions.select, ctx.ctx()).await
+						.context("Failed to convert permission")?
+				} else {
+					Err(ControlFlow::Err(anyhow::Error::new(Error::TbNotFound {
+						name: table_name.clone(),
+					})))?
+				}
+			} else {
+				PhysicalPermission::Allow
+			};
+
+			// Early exit if denied
+			if matches!(select_permission, PhysicalPermission::Deny) {
+				return;
+			}
+
+			// Intersect the records stored under each trigram
+			let index_def = index_ref.definition();
+			let ikb = IndexKeyBase::new(ns.namespace_id, db.database_id, table_name.clone(), index_def.index_id);
+			let mut postings = TrigramIntersection::new(&ikb, &trigrams)
+				.context("Failed to open trigram index")?;
+
+			loop {
+				let ids = postings.next(txn.as_ref(), BATCH_SIZE as u32).await
+					.context("Failed to scan trigram index")?;
+				if ids.is_empty() {
+					break;
+				}
+				let rids: Vec<RecordId> = ids
+					.into_iter()
+					.map(|id| RecordId::new(table_name.clone(), id))
+					.collect();
+				let values = fetch_and_filter_records_batch(
+					&ctx,
+					&txn,
+					ns.namespace_id,
+					db.database_id,
+					&rids,
+					&select_permission,
+					check_perms,
+					version,
+					CachePolicy::ReadOnly,
+				).await?;
+				if !values.is_empty() {
+					yield ValueBatch { values };
+				}
+			}
+		};
+
+		Ok(monitor_stream(Box::pin(stream), "TrigramScan", &self.metrics))
+	}
+}
@@ -2,8 +2,8 @@
 //!
 //! MATCHES is purely index-driven: it checks whether a record is in the
 //! full-text index's hit set for the given query, using `get_doc_id()` +
-//! `contains_doc()` (a KV lookup + bitmap check). There is no slow
-//! tokenization fallback.
+//! `matches_doc()` (a KV lookup + bitmap check, plus a position check for
+//! phrases). There is no slow tokenization fallback.
 //!
 //! When no full-text index exists for the field, evaluation returns `false`
 //! (matching the old executor's `ExecutorOption::None` path).
@@ -29,7 +29,7 @@ use crate::val::Value;
 ///
 /// Evaluation mirrors the old executor's `fulltext_matches_with_doc_id` path:
 /// 1. Resolve `RecordId → DocId` via `fti.get_doc_id()`
-/// 2. Check `qt.contains_doc(doc_id)` (bitmap check)
+/// 2. Check `fti.matches_doc(doc_id)` (bitmap and phrase check)
 ///
 /// Returns `false` when no full-text index exists for the field.
 pub struct MatchesOp {
@@ -151,7 +151,13 @@ impl MatchesOp {
 					let mut stack = reblessive::TreeStack::new();
 					stack
 						.enter(|stk| {
-							fti.extract_querying_terms(stk, frozen, opt, self.query.clone())
+							fti.extract_querying_terms(
+								stk,
+								frozen,
+								opt,
+								self.query.clone(),
+								self.operator.fuzzy,
+							)
 						})
 						.finish()
 						.await?
@@ -199,7 +205,7 @@ impl PhysicalExpr for MatchesOp {
 		// Resolve RecordId → DocId via the full-text index, then bitmap check.
 		// This mirrors the old executor's `fulltext_matches_with_doc_id` path.
 		let matches = match fti.get_doc_id(&tx, &rid).await? {
-			Some(doc_id) => qt.contains_doc(doc_id),
+			Some(doc_id) => fti.matches_doc(&tx, qt, doc_id, self.operator.operator).await?,
 			// Record not in the index → doesn't match
 			None => false,
 		};
@@ -331,11 +331,15 @@ pub(crate) fn block_required_context(block: &Block) -> ContextLevel {
 /// Determine the minimum [`ContextLevel`] required by an [`InfoStatement`].
 fn info_stmt_required_context(info: &InfoStatement) -> ContextLevel {
 	match info {
-		InfoStatement::Root(_) => ContextLevel::Root,
-		InfoStatement::Ns(_) => ContextLevel::Namespace,
-		InfoStatement::Db(_, _) | InfoStatement::Tb(_, _, _) | InfoStatement::Index(_, _, _) => {
-			ContextLevel::Database
+		InfoStatement::Root(_) | InfoStatement::Stats(_) | InfoStatement::Queries => {
+			ContextLevel::Root
 		}
+		InfoStatement::Ns(_) => ContextLevel::Namespace,
+		InfoStatement::Db(_, _)
+		| InfoStatement::Tb(_, _, _)
+		| InfoStatement::Index(_, _, _)
+		| InfoStatement::Bucket(_, _)
+		| InfoStatement::Consumer(_, _) => ContextLevel::Database,
 		InfoStatement::User(user_expr, base, _) => {
 			let base_ctx = match base {
 				Some(Base::Root) | None => ContextLevel::Root,
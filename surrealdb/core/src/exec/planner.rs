@@ -1019,6 +1019,18 @@ impl<'ctx> Planner<'ctx> {
 				let table = self.physical_expr_as_name(table).await?;
 				Ok(Arc::new(IndexInfoPlan::new(index, table, structured)) as Arc<dyn ExecOperator>)
 			}
+			InfoStatement::Bucket(_, _) => Err(Error::PlannerUnsupported(
+				"INFO FOR BUCKET statements not yet supported in execution plans".to_string(),
+			)),
+			InfoStatement::Consumer(_, _) => Err(Error::PlannerUnsupported(
+				"INFO FOR CONSUMER statements not yet supported in execution plans".to_string(),
+			)),
+			InfoStatement::Stats(_) => Err(Error::PlannerUnsupported(
+				"INFO FOR STATS statements not yet supported in execution plans".to_string(),
+			)),
+			InfoStatement::Queries => Err(Error::PlannerUnsupported(
+				"INFO FOR QUERIES statements not yet supported in execution plans".to_string(),
+			)),
 		}
 	}
 
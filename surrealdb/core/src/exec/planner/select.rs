@@ -22,17 +22,18 @@ use super::util::{
 	all_value_sources, check_forbidden_group_by_params, derive_field_name, extract_bruteforce_knn,
 	extract_count_field_names, extract_matches_context, extract_record_id_point_lookup,
 	extract_version, fold_condition_expressions, get_effective_limit_literal, has_knn_k_operator,
-	has_knn_operator, has_top_level_or, idiom_to_field_name, idiom_to_field_path,
-	index_covers_ordering, is_count_all_eligible, is_indexed_count_eligible,
-	order_is_scan_compatible, resolve_condition_params, strip_fts_condition,
-	strip_index_conditions, strip_knn_from_condition,
+	has_knn_ktree_operator, has_knn_multi_vector_operator, has_knn_operator, has_spatial_operator,
+	has_top_level_or, idiom_to_field_name, idiom_to_field_path, index_covers_ordering,
+	is_count_all_eligible, is_indexed_count_eligible, order_is_scan_compatible,
+	resolve_condition_params, strip_fts_condition, strip_index_conditions,
+	strip_knn_from_condition,
 };
 use crate::catalog::providers::{DatabaseProvider, NamespaceProvider, TableProvider};
 use crate::cnf::MAX_ORDER_LIMIT_PRIORITY_QUEUE_SIZE;
 use crate::err::Error;
 use crate::exec::expression_registry::{ComputePoint, ExpressionRegistry, resolve_order_by_alias};
 use crate::exec::field_path::FieldPath;
-use crate::exec::index::access_path::{AccessPath, BTreeAccess, select_access_path};
+use crate::exec::index::access_path::{AccessPath, BTreeAccess, IndexRef, select_access_path};
 use crate::exec::index::analysis::IndexAnalyzer;
 #[cfg(all(storage, not(target_family = "wasm")))]
 use crate::exec::operators::ExternalSort;
@@ -47,6 +48,7 @@ use crate::exec::operators::{
 use crate::exec::{ExecOperator, OperatorMetrics};
 use crate::expr::field::{Field, Fields};
 use crate::expr::{Cond, Expr, Idiom, Literal};
+use crate::idx::planner::SelectClauses;
 
 /// Configuration for the SELECT pipeline.
 ///
@@ -1339,8 +1341,27 @@ impl<'ctx> Planner<'ctx> {
 			None => None,
 		};
 
+		// Spatial indexes are only supported by the legacy executor
+		if cond.as_ref().is_some_and(|c| has_knn_ktree_operator(&c.0)) {
+			return Err(Error::PlannerUnimplemented(
+				"K-nearest points queries are not supported in the streaming executor".to_string(),
+			));
+		}
+		if cond.as_ref().is_some_and(|c| has_spatial_operator(&c.0))
+			&& self.has_spatial_index(&what).await
+		{
+			return Err(Error::PlannerUnimplemented(
+				"Spatial index queries are not supported in the streaming executor".to_string(),
+			));
+		}
+
 		// KNN handling
 		let has_knn = cond.as_ref().is_some_and(|c| has_knn_operator(&c.0));
+		if cond.as_ref().is_some_and(|c| has_knn_multi_vector_operator(&c.0)) {
+			return Err(Error::PlannerUnimplemented(
+				"Multi-vector KNN is not supported in the streaming executor".to_string(),
+			));
+		}
 		let brute_force_knn = if has_knn {
 			cond.as_ref().and_then(extract_bruteforce_knn)
 		} else {
@@ -1431,6 +1452,15 @@ impl<'ctx> Planner<'ctx> {
 		// The result tracks whether the predicate and limit/start were
 		// consumed by the source operator, so we can avoid duplicating
 		// them in the outer pipeline.
+		// Fields read by the statement, to check whether an index stores them
+		let clauses = SelectClauses {
+			fields: Some(&fields),
+			cond: cond_for_index.as_ref(),
+			order: order.as_ref(),
+			group: group.as_ref(),
+			split: split.as_ref(),
+			fetch: fetch.as_ref(),
+		};
 		let mut planned = pp
 			.plan_sources(
 				what,
@@ -1439,6 +1469,7 @@ impl<'ctx> Planner<'ctx> {
 				order.as_ref(),
 				with.as_ref(),
 				needed_fields,
+				&clauses,
 				scan_predicate,
 				scan_limit,
 				scan_start,
@@ -1556,6 +1587,7 @@ impl<'ctx> Planner<'ctx> {
 		order: Option<&crate::expr::order::Ordering>,
 		with: Option<&crate::expr::with::With>,
 		needed_fields: Option<std::collections::HashSet<String>>,
+		clauses: &SelectClauses<'_>,
 		scan_predicate: Option<Arc<dyn crate::exec::PhysicalExpr>>,
 		scan_limit: Option<Arc<dyn crate::exec::PhysicalExpr>>,
 		scan_start: Option<Arc<dyn crate::exec::PhysicalExpr>>,
@@ -1575,6 +1607,7 @@ impl<'ctx> Planner<'ctx> {
 					order,
 					with,
 					needed_fields.clone(),
+					clauses,
 					scan_predicate.clone(),
 					scan_limit.clone(),
 					scan_start.clone(),
@@ -1616,11 +1649,12 @@ impl<'ctx> Planner<'ctx> {
 		order: Option<&crate::expr::order::Ordering>,
 		with: Option<&crate::expr::with::With>,
 		needed_fields: Option<std::collections::HashSet<String>>,
+		clauses: &SelectClauses<'_>,
 		scan_predicate: Option<Arc<dyn crate::exec::PhysicalExpr>>,
 		scan_limit: Option<Arc<dyn crate::exec::PhysicalExpr>>,
 		scan_start: Option<Arc<dyn crate::exec::PhysicalExpr>>,
 	) -> Result<PlannedSource, Error> {
-		use crate::exec::operators::{FullTextScan, IndexScan, KnnScan};
+		use crate::exec::operators::{FullTextScan, IndexScan, KnnScan, TrigramScan};
 
 		// Optimisation: WHERE id = <RecordId> -> point lookup.
 		// Detects `id = <RecordId literal>` in the top-level AND chain and
@@ -1727,6 +1761,7 @@ impl<'ctx> Planner<'ctx> {
 						} else {
 							None
 						};
+						let covering = Self::is_covering(&index_ref, &version, &table_ctx, clauses);
 						let mut scan = IndexScan::new(
 							index_ref,
 							access,
@@ -1736,7 +1771,8 @@ impl<'ctx> Planner<'ctx> {
 							idx_start,
 							version.clone(),
 						)
-						.with_batch_ceiling(batch_ceiling);
+						.with_batch_ceiling(batch_ceiling)
+						.with_covering(covering);
 						if let Some(ref tc) = table_ctx {
 							scan = scan.with_resolved(tc.clone());
 						}
@@ -1770,6 +1806,23 @@ impl<'ctx> Planner<'ctx> {
 							limit_pushed: false,
 						});
 					}
+					AccessPath::TrigramSearch {
+						index_ref,
+						trigrams,
+					} => {
+						let mut scan =
+							TrigramScan::new(index_ref, trigrams, table, version.clone());
+						if let Some(ref tc) = table_ctx {
+							scan = scan.with_resolved(tc.clone());
+						}
+						// The scan returns candidates, so the whole
+						// condition is still applied
+						return Ok(PlannedSource {
+							operator: Arc::new(scan) as Arc<dyn ExecOperator>,
+							filter_action: FilterAction::UseOriginal,
+							limit_pushed: false,
+						});
+					}
 					AccessPath::KnnSearch {
 						index_ref,
 						vector,
@@ -1880,6 +1933,9 @@ impl<'ctx> Planner<'ctx> {
 									access,
 									direction,
 								} => {
+									let covering = Self::is_covering(
+										&index_ref, &version, &table_ctx, clauses,
+									);
 									let mut scan = IndexScan::new(
 										index_ref,
 										access,
@@ -1888,7 +1944,8 @@ impl<'ctx> Planner<'ctx> {
 										None,
 										None,
 										version.clone(),
-									);
+									)
+									.with_covering(covering);
 									if let Some(ref ceiling) = merge_batch_ceiling {
 										scan = scan.with_batch_ceiling(Some(Arc::clone(ceiling)));
 									}
@@ -1914,6 +1971,21 @@ impl<'ctx> Planner<'ctx> {
 									}
 									Arc::new(scan)
 								}
+								AccessPath::TrigramSearch {
+									index_ref,
+									trigrams,
+								} => {
+									let mut scan = TrigramScan::new(
+										index_ref,
+										trigrams,
+										table.clone(),
+										version.clone(),
+									);
+									if let Some(ref tc) = table_ctx {
+										scan = scan.with_resolved(tc.clone());
+									}
+									Arc::new(scan)
+								}
 								AccessPath::KnnSearch {
 									index_ref,
 									vector,
@@ -2123,6 +2195,23 @@ impl<'ctx> Planner<'ctx> {
 		}
 	}
 
+	/// Whether an index scan can read the partial records stored by a covering
+	/// index instead of the records themselves.
+	///
+	/// Historical records are not stored by the index, and the fields read by
+	/// the statement, the permissions and the computed fields must not read
+	/// any field which the index does not store.
+	fn is_covering(
+		index_ref: &IndexRef,
+		version: &Option<Arc<dyn crate::exec::PhysicalExpr>>,
+		table_ctx: &Option<ResolvedTableContext>,
+		clauses: &SelectClauses<'_>,
+	) -> bool {
+		version.is_none()
+			&& table_ctx.as_ref().is_some_and(|tc| tc.allows_partial_records())
+			&& clauses.are_covered_by(index_ref)
+	}
+
 	/// Try to resolve a `ResolvedTableContext` for the given table.
 	///
 	/// Returns `None` if namespace/database lookup fails or the table doesn't
@@ -2142,6 +2231,29 @@ impl<'ctx> Planner<'ctx> {
 			.ok()?
 	}
 
+	/// Check at plan time whether the single source table has a spatial index.
+	async fn has_spatial_index(&self, what: &[Expr]) -> bool {
+		let (Some(txn), Some(ns_name), Some(db_name)) = (&self.txn, &self.ns, &self.db) else {
+			return false;
+		};
+		let table_name = match what {
+			[Expr::Table(t)] => t,
+			_ => return false,
+		};
+		let Ok(Some(ns_def)) = txn.get_ns_by_name(ns_name).await else {
+			return false;
+		};
+		let Ok(Some(db_def)) = txn.get_db_by_name(ns_name, db_name).await else {
+			return false;
+		};
+		let Ok(indexes) =
+			txn.all_tb_indexes(ns_def.namespace_id, db_def.database_id, table_name).await
+		else {
+			return false;
+		};
+		indexes.iter().any(|ix| matches!(ix.index, crate::catalog::Index::Spatial))
+	}
+
 	/// Check at plan time whether a matching COUNT index exists for the query.
 	///
 	/// Returns `true` when:
@@ -2221,7 +2333,7 @@ impl<'ctx> Planner<'ctx> {
 
 		// Run the index analyzer to find candidate access paths.
 		// We pass None for order since we don't care about ordering for counting.
-		let analyzer = IndexAnalyzer::new(indexes, None);
+		let analyzer = IndexAnalyzer::new(indexes, Some(cond), None);
 		let candidates = analyzer.analyze(Some(cond), None);
 
 		// Look for a candidate that fully covers the WHERE condition
@@ -2286,7 +2398,7 @@ impl<'ctx> Planner<'ctx> {
 			return Ok(Some((AccessPath::TableScan, direction)));
 		}
 
-		let analyzer = IndexAnalyzer::new(indexes, with);
+		let analyzer = IndexAnalyzer::new(indexes, cond, with);
 		let candidates = analyzer.analyze(cond, order);
 
 		if candidates.is_empty() {
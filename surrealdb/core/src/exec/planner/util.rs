@@ -13,7 +13,7 @@ use crate::exec::function::FunctionRegistry;
 use crate::expr::field::{Field, Fields};
 use crate::expr::operator::NearestNeighbor;
 use crate::expr::visit::{MutVisitor, Visit, VisitMut, Visitor};
-use crate::expr::{BinaryOperator, Cond, Expr, Idiom, Literal, Param};
+use crate::expr::{BinaryOperator, Cond, Expr, Function, Idiom, Literal, Param};
 use crate::val::Number;
 
 // ============================================================================
@@ -298,6 +298,8 @@ pub(super) fn has_knn_operator(expr: &Expr) -> bool {
 	let mut checker = KnnOperatorChecker {
 		found_any: false,
 		found_k: false,
+		found_multi_vector: false,
+		found_ktree: false,
 	};
 	let _ = checker.visit_expr(expr);
 	checker.found_any
@@ -311,15 +313,45 @@ pub(super) fn has_knn_k_operator(expr: &Expr) -> bool {
 	let mut checker = KnnOperatorChecker {
 		found_any: false,
 		found_k: false,
+		found_multi_vector: false,
+		found_ktree: false,
 	};
 	let _ = checker.visit_expr(expr);
 	checker.found_k
 }
 
+/// Check if an expression contains an aggregated multi-vector KNN operator
+/// (`NearestNeighbor::MultiVector`), which the streaming executor does not support.
+pub(super) fn has_knn_multi_vector_operator(expr: &Expr) -> bool {
+	let mut checker = KnnOperatorChecker {
+		found_any: false,
+		found_k: false,
+		found_multi_vector: false,
+		found_ktree: false,
+	};
+	let _ = checker.visit_expr(expr);
+	checker.found_multi_vector
+}
+
+/// Check if an expression contains a k-nearest points operator
+/// (`NearestNeighbor::KTree`), which is served by a spatial index.
+pub(super) fn has_knn_ktree_operator(expr: &Expr) -> bool {
+	let mut checker = KnnOperatorChecker {
+		found_any: false,
+		found_k: false,
+		found_multi_vector: false,
+		found_ktree: false,
+	};
+	let _ = checker.visit_expr(expr);
+	checker.found_ktree
+}
+
 /// Visitor that detects the presence of KNN operators in an expression tree.
 struct KnnOperatorChecker {
 	found_any: bool,
 	found_k: bool,
+	found_multi_vector: bool,
+	found_ktree: bool,
 }
 
 impl Visitor for KnnOperatorChecker {
@@ -332,8 +364,11 @@ impl Visitor for KnnOperatorChecker {
 		} = expr
 		{
 			self.found_any = true;
-			if matches!(nn.as_ref(), NearestNeighbor::K(..)) {
-				self.found_k = true;
+			match nn.as_ref() {
+				NearestNeighbor::K(..) => self.found_k = true,
+				NearestNeighbor::MultiVector(..) => self.found_multi_vector = true,
+				NearestNeighbor::KTree(..) => self.found_ktree = true,
+				_ => {}
 			}
 		}
 		expr.visit(self)
@@ -345,6 +380,44 @@ impl Visitor for KnnOperatorChecker {
 	}
 }
 
+/// Check if an expression contains a geometry operator which a spatial index
+/// can serve: `INSIDE`, `OUTSIDE`, `INTERSECTS` or a `geo::distance` call.
+pub(super) fn has_spatial_operator(expr: &Expr) -> bool {
+	let mut checker = SpatialOperatorChecker {
+		found: false,
+	};
+	let _ = checker.visit_expr(expr);
+	checker.found
+}
+
+/// Visitor that detects the presence of spatial operators in an expression tree.
+struct SpatialOperatorChecker {
+	found: bool,
+}
+
+impl Visitor for SpatialOperatorChecker {
+	type Error = std::convert::Infallible;
+
+	fn visit_expr(&mut self, expr: &Expr) -> Result<(), Self::Error> {
+		match expr {
+			Expr::Binary {
+				op: BinaryOperator::Inside | BinaryOperator::Outside | BinaryOperator::Intersects,
+				..
+			} => self.found = true,
+			Expr::FunctionCall(f) if matches!(&f.receiver, Function::Normal(n) if n == "geo::distance") => {
+				self.found = true
+			}
+			_ => {}
+		}
+		expr.visit(self)
+	}
+
+	// Don't descend into subqueries -- only check outer WHERE.
+	fn visit_select(&mut self, _: &crate::expr::SelectStatement) -> Result<(), Self::Error> {
+		Ok(())
+	}
+}
+
 /// Parameters extracted from a brute-force KNN expression.
 pub(super) struct BruteForceKnnParams {
 	/// The idiom path to the vector field.
@@ -1044,6 +1117,7 @@ impl Visitor for MatchesCollector<'_> {
 					crate::exec::function::MatchInfo {
 						idiom: idiom.clone(),
 						query,
+						fuzzy: matches_op.fuzzy,
 					},
 				);
 			}
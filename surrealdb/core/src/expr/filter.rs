@@ -3,7 +3,7 @@ use surrealdb_types::{SqlFormat, ToSql};
 
 use crate::expr::language::Language;
 
-#[revisioned(revision = 1)]
+#[revisioned(revision = 2)]
 #[derive(Clone, Debug, Eq, PartialEq, Hash)]
 pub enum Filter {
 	Ascii,
@@ -13,6 +13,32 @@ pub enum Filter {
 	Snowball(Language),
 	Uppercase,
 	Mapper(String),
+	#[revision(start = 2)]
+	Stopwords(Stopwords),
+	#[revision(start = 2)]
+	Synonyms(Synonyms),
+}
+
+/// The source of the words removed by a `STOPWORDS` filter
+#[revisioned(revision = 1)]
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub enum Stopwords {
+	/// The built-in list for a language
+	Language(Language),
+	/// A custom list of words
+	Words(Vec<String>),
+	/// A file containing one word per line
+	File(String),
+}
+
+/// The source of the rules applied by a `SYNONYMS` filter
+#[revisioned(revision = 1)]
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub enum Synonyms {
+	/// A custom list of rules
+	Rules(Vec<String>),
+	/// A file containing one rule per line
+	File(String),
 }
 
 impl ToSql for Filter {
@@ -14,7 +14,7 @@ use crate::doc::CursorDoc;
 use crate::expr::part::{Next, NextMethod};
 use crate::expr::paths::{ID, IN, OUT};
 use crate::expr::statements::info::InfoStructure;
-use crate::expr::{FlowResult, Part, Value};
+use crate::expr::{Expr, FlowResult, Part, Value};
 use crate::fmt::EscapeKwFreeIdent;
 
 pub mod recursion;
@@ -66,6 +66,22 @@ impl Idiom {
 		Idiom(vec![Part::Field(field_name)])
 	}
 
+	/// Returns an idiom which evaluates the given expression. This is how the
+	/// columns of expression indexes are represented.
+	pub(crate) fn expression(expr: Expr) -> Self {
+		Idiom(vec![Part::Start(expr)])
+	}
+
+	/// Check if this Idiom starts with an expression rather than a field
+	pub(crate) fn is_expression(&self) -> bool {
+		matches!(self.0.first(), Some(Part::Start(_)))
+	}
+
+	/// Check if this Idiom is a plain path of fields, such as `a.b.c`
+	pub(crate) fn is_field_path(&self) -> bool {
+		!self.0.is_empty() && self.0.iter().all(|p| matches!(p, Part::Field(_)))
+	}
+
 	/// Appends a part to the end of this Idiom
 	pub(crate) fn push(mut self, n: Part) -> Idiom {
 		self.0.push(n);
@@ -210,7 +226,9 @@ impl FromStr for Idiom {
 
 		match expr {
 			crate::sql::Expr::Idiom(idiom) => Ok(idiom.into()),
-			_ => Err(revision::Error::Conversion("Expected an idiom".to_string())),
+			// An idiom starting with an expression, such as the column of an
+			// expression index, is formatted as the expression itself
+			expr => Ok(Idiom::expression(expr.into())),
 		}
 	}
 }
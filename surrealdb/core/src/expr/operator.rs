@@ -130,6 +130,7 @@ pub(crate) enum BinaryOperator {
 pub(crate) struct MatchesOperator {
 	pub rf: Option<MatchRef>,
 	pub operator: BooleanOperator,
+	pub fuzzy: Option<Fuzzy>,
 }
 
 impl ToSql for MatchesOperator {
@@ -139,6 +140,22 @@ impl ToSql for MatchesOperator {
 	}
 }
 
+/// Typo-tolerant matching executed by the full-text index
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
+pub struct Fuzzy {
+	/// The maximum edit distance between a query term and an indexed term
+	pub distance: u8,
+	/// The number of leading characters which must match exactly
+	pub prefix: u8,
+}
+
+impl Fuzzy {
+	/// The maximum supported edit distance
+	pub(crate) const MAX_DISTANCE: u8 = 2;
+	/// The edit distance used when none is specified
+	pub(crate) const DEFAULT_DISTANCE: u8 = 2;
+}
+
 /// Boolean operation executed by the full-text index
 
 #[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
@@ -164,6 +181,8 @@ pub(crate) enum NearestNeighbor {
 	KTree(u32),
 	/// `<|k, ef|>`
 	Approximate(u32, u32),
+	/// `<|k, ef, aggregation|>`
+	MultiVector(u32, u32, VectorAggregation),
 }
 
 impl ToSql for NearestNeighbor {
@@ -173,6 +192,19 @@ impl ToSql for NearestNeighbor {
 	}
 }
 
+/// How the distances between the query vectors and the vectors of a record
+/// are aggregated into the distance of the record
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
+pub(crate) enum VectorAggregation {
+	/// The sum, over the query vectors, of the distance to the nearest vector
+	/// of the record (ColBERT late interaction)
+	MaxSim,
+	/// The distance between the nearest query vector and record vector
+	Max,
+	/// The mean distance between the query vectors and the record vectors
+	Mean,
+}
+
 impl ToSql for BinaryOperator {
 	fn fmt_sql(&self, f: &mut String, sql_fmt: SqlFormat) {
 		let binary_operator: crate::sql::BinaryOperator = self.clone().into();
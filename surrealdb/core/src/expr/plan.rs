@@ -1,6 +1,7 @@
 use crate::expr::Expr;
 use crate::expr::statements::{
-	AccessStatement, KillStatement, LiveStatement, OptionStatement, ShowStatement, UseStatement,
+	AccessStatement, AcknowledgeStatement, KillStatement, LiveStatement, OptionStatement,
+	ShowStatement, UseStatement,
 };
 
 #[derive(Clone, Debug)]
@@ -19,6 +20,7 @@ pub(crate) enum TopLevelExpr {
 	Option(OptionStatement),
 	Use(UseStatement),
 	Show(ShowStatement),
+	Acknowledge(AcknowledgeStatement),
 	Expr(Expr),
 }
 
@@ -34,7 +36,8 @@ impl TopLevelExpr {
 			| TopLevelExpr::Live(_)
 			| TopLevelExpr::Option(_)
 			| TopLevelExpr::Use(_)
-			| TopLevelExpr::Access(_) => false,
+			| TopLevelExpr::Access(_)
+			| TopLevelExpr::Acknowledge(_) => false,
 			TopLevelExpr::Expr(expr) => expr.read_only(),
 		}
 	}
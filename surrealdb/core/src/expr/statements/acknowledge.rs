@@ -0,0 +1,54 @@
+use anyhow::Result;
+
+use crate::catalog::ConsumerDefinition;
+use crate::catalog::providers::DatabaseProvider;
+use crate::ctx::FrozenContext;
+use crate::dbs::Options;
+use crate::doc::CursorDoc;
+use crate::expr::{Base, Value};
+use crate::iam::{Action, ResourceKind};
+
+/// An ACKNOWLEDGE statement, which commits the position of a changefeed
+/// consumer up to and including the given versionstamp.
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub(crate) struct AcknowledgeStatement {
+	pub consumer: String,
+	pub versionstamp: u64,
+}
+
+impl AcknowledgeStatement {
+	/// Process this type returning a computed simple Value
+	#[instrument(level = "trace", name = "AcknowledgeStatement::compute", skip_all)]
+	pub(crate) async fn compute(
+		&self,
+		ctx: &FrozenContext,
+		opt: &Options,
+		_doc: Option<&CursorDoc>,
+	) -> Result<Value> {
+		// Allowed to run?
+		opt.is_allowed(Action::Edit, ResourceKind::Table, &Base::Db)?;
+		// Get the transaction
+		let txn = ctx.tx();
+		let (ns, db) = ctx.expect_ns_db_ids(opt).await?;
+		let cs = txn.get_db_consumer(ns, db, &self.consumer).await?;
+		// The position of a consumer never moves backwards
+		if cs.position.is_some_and(|v| v >= self.versionstamp) {
+			return Ok(Value::None);
+		}
+		// Store the new position
+		let key = crate::key::database::cs::new(ns, db, &self.consumer);
+		txn.set(
+			&key,
+			&ConsumerDefinition {
+				position: Some(self.versionstamp),
+				..cs.as_ref().clone()
+			},
+			None,
+		)
+		.await?;
+		// Clear the cache
+		txn.clear_cache();
+		// Ok all good
+		Ok(Value::None)
+	}
+}
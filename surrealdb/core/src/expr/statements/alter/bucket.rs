@@ -68,6 +68,10 @@ impl AlterBucketStatement {
 
 		let key = crate::key::database::bu::new(ns, db, &self.name);
 		txn.set(&key, &bu, None).await?;
+		// The usage is recomputed if the backend has changed
+		if !matches!(self.backend, AlterKind::None) {
+			txn.del(&crate::key::database::bq::new(ns, db, &self.name)).await?;
+		}
 		txn.clear_cache();
 		Ok(Value::None)
 	}
@@ -3,7 +3,7 @@ use reblessive::tree::Stk;
 
 use super::{CursorDoc, DefineKind};
 use crate::catalog::providers::BucketProvider;
-use crate::catalog::{BucketDefinition, Permission};
+use crate::catalog::{BucketDefinition, BucketLifecycleRule, Permission};
 use crate::ctx::FrozenContext;
 use crate::dbs::Options;
 use crate::err::Error;
@@ -19,6 +19,9 @@ pub(crate) struct DefineBucketStatement {
 	pub backend: Option<Expr>,
 	pub permissions: Permission,
 	pub readonly: bool,
+	pub max_size: Option<u64>,
+	pub max_count: Option<u64>,
+	pub lifecycle: Vec<BucketLifecycleRule>,
 	pub comment: Expr,
 }
 
@@ -30,6 +33,9 @@ impl Default for DefineBucketStatement {
 			backend: None,
 			permissions: Permission::default(),
 			readonly: false,
+			max_size: None,
+			max_count: None,
+			lifecycle: Vec::new(),
 			comment: Expr::Literal(Literal::None),
 		}
 	}
@@ -79,6 +85,14 @@ impl DefineBucketStatement {
 			None
 		};
 
+		// Check that a custom S3 endpoint is allowed by the network capabilities
+		#[cfg(all(feature = "bucket-s3", not(target_arch = "wasm32")))]
+		if let Some(backend) = &backend
+			&& let Some(endpoint) = crate::buc::store::s3::S3Store::endpoint(backend)?
+		{
+			ctx.check_allowed_net(&endpoint).await?;
+		}
+
 		// Create and cache a new backend
 		if let Some(buckets) = ctx.get_buckets() {
 			buckets.new_backend(ns, db, &name, self.readonly, backend.as_deref()).await?;
@@ -102,8 +116,13 @@ impl DefineBucketStatement {
 			permissions: self.permissions.clone(),
 			readonly: self.readonly,
 			comment,
+			max_size: self.max_size,
+			max_count: self.max_count,
+			lifecycle: self.lifecycle.clone(),
 		};
 		txn.set(&key, &ap, None).await?;
+		// The usage is recomputed, as the backend may have changed
+		txn.del(&crate::key::database::bq::new(ns, db, &name)).await?;
 		// Clear the cache
 		txn.clear_cache();
 		// Ok all good
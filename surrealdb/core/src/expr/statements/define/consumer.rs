@@ -0,0 +1,154 @@
+use anyhow::{Result, bail};
+use reblessive::tree::Stk;
+use url::Url;
+
+use super::DefineKind;
+use crate::catalog::providers::{BucketProvider, DatabaseProvider, TableProvider};
+use crate::catalog::{self, ConsumerDefinition};
+use crate::ctx::FrozenContext;
+use crate::dbs::Options;
+use crate::doc::CursorDoc;
+use crate::err::Error;
+use crate::expr::parameterize::expr_to_ident;
+use crate::expr::{Base, Expr, FlowResultExt};
+use crate::iam::{Action, ResourceKind};
+use crate::val::{File, TableName, Value};
+
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub(crate) struct DefineConsumerStatement {
+	pub kind: DefineKind,
+	pub name: Expr,
+	pub target_table: Expr,
+	pub comment: Expr,
+	pub sink: Option<ConsumerSink>,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub(crate) enum ConsumerSink {
+	Webhook(Expr),
+	File(Expr),
+}
+
+impl ConsumerSink {
+	/// Computes the sink target, checking that it can be delivered to
+	async fn compute(
+		&self,
+		stk: &mut Stk,
+		ctx: &FrozenContext,
+		opt: &Options,
+		doc: Option<&CursorDoc>,
+	) -> Result<catalog::ConsumerSink> {
+		match self {
+			Self::Webhook(v) => {
+				let uri: String =
+					stk.run(|stk| v.compute(stk, ctx, opt, doc)).await.catch_return()?.cast_to()?;
+				// Check that the endpoint is a valid and allowed URL
+				let url = Url::parse(&uri).map_err(|_| Error::InvalidUrl(uri.clone()))?;
+				if !matches!(url.scheme(), "http" | "https") {
+					bail!(Error::InvalidUrl(uri));
+				}
+				#[cfg(feature = "http")]
+				{
+					ctx.check_allowed_net(&url).await?;
+					Ok(catalog::ConsumerSink::Webhook(uri))
+				}
+				#[cfg(not(feature = "http"))]
+				{
+					let _ = url;
+					bail!(Error::HttpDisabled)
+				}
+			}
+			Self::File(v) => {
+				let file: File =
+					stk.run(|stk| v.compute(stk, ctx, opt, doc)).await.catch_return()?.cast_to()?;
+				// Check that the bucket exists
+				let (ns, db) = ctx.expect_ns_db_ids(opt).await?;
+				ctx.tx().expect_db_bucket(ns, db, &file.bucket).await?;
+				Ok(catalog::ConsumerSink::File(file))
+			}
+		}
+	}
+}
+
+impl DefineConsumerStatement {
+	/// Process this type returning a computed simple Value
+	#[instrument(level = "trace", name = "DefineConsumerStatement::compute", skip_all)]
+	pub(crate) async fn compute(
+		&self,
+		stk: &mut Stk,
+		ctx: &FrozenContext,
+		opt: &Options,
+		doc: Option<&CursorDoc>,
+	) -> Result<Value> {
+		// Allowed to run?
+		opt.is_allowed(Action::Edit, ResourceKind::Table, &Base::Db)?;
+		// Compute the name and target table
+		let name = expr_to_ident(stk, ctx, opt, doc, &self.name, "consumer name").await?;
+		let target_table = TableName::new(
+			expr_to_ident(stk, ctx, opt, doc, &self.target_table, "target table").await?,
+		);
+		// Get the NS and DB
+		let (ns_name, db_name) = opt.ns_db()?;
+		let (ns, db) = ctx.get_ns_db_ids(opt).await?;
+		// Fetch the transaction
+		let txn = ctx.tx();
+		// Check if the definition exists
+		let position = match txn.get_db_consumer(ns, db, &name).await {
+			Ok(cs) => {
+				match self.kind {
+					DefineKind::Default => {
+						if !opt.import {
+							bail!(Error::CsAlreadyExists {
+								name: name.clone(),
+							});
+						}
+					}
+					DefineKind::Overwrite => {}
+					DefineKind::IfNotExists => return Ok(Value::None),
+				}
+				// Keep the acknowledged position when redefining
+				// a consumer which still reads from the same table
+				if cs.target_table == target_table {
+					cs.position
+				} else {
+					None
+				}
+			}
+			Err(_) => None,
+		};
+		// Ensure the table exists
+		let tb = txn.get_or_add_tb(Some(ctx), ns_name, db_name, &target_table).await?;
+		let db_def = txn.expect_db_by_name(ns_name, db_name).await?;
+		// Consumers can only read from a table with a changefeed
+		if tb.changefeed.is_none() && db_def.changefeed.is_none() {
+			bail!(Error::Query {
+				message: format!(
+					"The consumer '{name}' can not be defined, as the table '{target_table}' has no changefeed"
+				),
+			});
+		}
+		let comment = stk
+			.run(|stk| self.comment.compute(stk, ctx, opt, doc))
+			.await
+			.catch_return()?
+			.cast_to()?;
+		let sink = match self.sink {
+			Some(ref sink) => Some(sink.compute(stk, ctx, opt, doc).await?),
+			None => None,
+		};
+		// Process the statement
+		let key = crate::key::database::cs::new(ns, db, &name);
+		let cs = ConsumerDefinition {
+			name: name.clone(),
+			target_table,
+			comment,
+			position,
+			sink,
+		};
+		txn.set(&key, &cs, None).await?;
+		// Clear the cache
+		txn.clear_cache();
+		// Ok all good
+		Ok(Value::None)
+	}
+}
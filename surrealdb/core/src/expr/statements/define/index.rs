@@ -13,7 +13,9 @@ use crate::dbs::Options;
 use crate::doc::CursorDoc;
 use crate::err::Error;
 use crate::expr::parameterize::{expr_to_ident, exprs_to_fields};
-use crate::expr::{Base, Expr, FlowResultExt, Literal, Part};
+use crate::expr::visit::{Visit, Visitor};
+use crate::expr::{Base, Cond, Expr, FlowResultExt, Function, Idiom, Literal, Part};
+use crate::fnc::{is_pure_function, is_pure_method};
 use crate::iam::{Action, ResourceKind};
 use crate::val::{TableName, Value};
 
@@ -24,6 +26,8 @@ pub(crate) struct DefineIndexStatement {
 	pub what: Expr,
 	pub cols: Vec<Expr>,
 	pub index: Index,
+	pub include: Vec<Expr>,
+	pub cond: Option<Cond>,
 	pub comment: Expr,
 	pub concurrently: bool,
 }
@@ -36,6 +40,8 @@ impl Default for DefineIndexStatement {
 			what: Expr::Literal(Literal::None),
 			cols: Vec::new(),
 			index: Index::Idx,
+			include: Vec::new(),
+			cond: None,
 			comment: Expr::Literal(Literal::None),
 			concurrently: false,
 		}
@@ -92,10 +98,20 @@ impl DefineIndexStatement {
 				.await?
 		};
 
-		// Compute columns
-		let cols = exprs_to_fields(stk, ctx, opt, doc, self.cols.as_slice()).await?;
+		// Compute columns. Fields are indexed by their path, while any other
+		// expression is evaluated against each record (expression index).
+		let mut cols = Vec::with_capacity(self.cols.len());
+		for col in self.cols.iter() {
+			if is_field_col(col) {
+				cols.extend(exprs_to_fields(stk, ctx, opt, doc, std::slice::from_ref(col)).await?);
+			} else {
+				cols.push(Idiom::expression(col.clone()));
+			}
+		}
+		// Compute the fields stored alongside the entries of a covering index
+		let include = exprs_to_fields(stk, ctx, opt, doc, &self.include).await?;
 
-		// Validate each indexed field:
+		// Validate each indexed or included field:
 		// 1. Computed fields cannot be indexed (regardless of schemafull/schemaless). This applies
 		//    to both exact field matches and sub-field paths whose parent is a computed field.
 		// 2. If the table is schemafull, ensure that every indexed field is defined. For sub-field
@@ -104,7 +120,17 @@ impl DefineIndexStatement {
 		//    access — this includes `object`, `any`, literal object types (e.g. `{ key: string }`),
 		//    and union types where every non-none variant is object-like. A parent field with no
 		//    explicit type is also accepted, since it is unconstrained.
-		for idiom in cols.iter() {
+		for idiom in cols.iter().chain(include.iter()) {
+			// Expression columns must always yield the same value for the same record
+			if idiom.is_expression() {
+				if idiom.visit(&mut DeterministicChecker).is_err() {
+					bail!(Error::IxExpressionNotDeterministic {
+						expr: idiom.to_sql(),
+						index: name
+					});
+				}
+				continue;
+			}
 			let fd = idiom.to_raw_string();
 			// Check if the exact field path (e.g. `document.visible`) is defined
 			if let Some(f) =
@@ -157,6 +183,8 @@ impl DefineIndexStatement {
 			table_name,
 			cols: cols.clone(),
 			index: self.index.clone(),
+			cond: self.cond.clone(),
+			include,
 			comment,
 			prepare_remove: false,
 		};
@@ -186,6 +214,143 @@ impl DefineIndexStatement {
 		Ok(Value::None)
 	}
 }
+
+/// Checks if a column is a field path, rather than an expression to index
+fn is_field_col(col: &Expr) -> bool {
+	match col {
+		Expr::Idiom(idiom) => !idiom.is_expression(),
+		Expr::FunctionCall(x) => {
+			matches!(&x.receiver, Function::Normal(f) if f == "type::field" || f == "type::fields")
+		}
+		_ => false,
+	}
+}
+
+/// Visitor which rejects expressions whose result may change between two
+/// evaluations on the same record, such as random values, the current time,
+/// parameters, subqueries, user-defined functions or graph traversals.
+struct DeterministicChecker;
+
+impl Visitor for DeterministicChecker {
+	type Error = ();
+
+	fn visit_expr(&mut self, e: &Expr) -> Result<(), Self::Error> {
+		match e {
+			Expr::Literal(_)
+			| Expr::Idiom(_)
+			| Expr::Constant(_)
+			| Expr::Prefix {
+				..
+			}
+			| Expr::Postfix {
+				..
+			}
+			| Expr::Binary {
+				..
+			}
+			| Expr::FunctionCall(_)
+			| Expr::IfElse(_) => e.visit(self),
+			_ => Err(()),
+		}
+	}
+
+	fn visit_idiom(&mut self, i: &Idiom) -> Result<(), Self::Error> {
+		// The value of a computed start can be a record id, which any further
+		// path would fetch, so only methods and array accessors may follow it
+		if let [Part::Start(_), rest @ ..] = i.0.as_slice()
+			&& !rest.iter().all(|p| {
+				matches!(
+					p,
+					Part::Method(..)
+						| Part::Optional | Part::First
+						| Part::Last | Part::All
+						| Part::Flatten
+				)
+			}) {
+			return Err(());
+		}
+		i.visit(self)
+	}
+
+	fn visit_part(&mut self, p: &Part) -> Result<(), Self::Error> {
+		match p {
+			Part::Lookup(_) | Part::Recurse(..) | Part::RepeatRecurse => Err(()),
+			Part::Method(name, _) if !is_pure_method(name) => Err(()),
+			p => p.visit(self),
+		}
+	}
+
+	fn visit_function(&mut self, f: &Function) -> Result<(), Self::Error> {
+		match f {
+			Function::Normal(name) if is_pure_function(name) => Ok(()),
+			_ => Err(()),
+		}
+	}
+}
+
+/// Checks if evaluating an index expression against the given document would
+/// fetch a linked record, whose content can change without the indexed record
+/// being updated.
+pub(crate) fn follows_record_link(col: &Idiom, doc: &Value) -> bool {
+	col.visit(&mut RecordLinkChecker {
+		doc,
+	})
+	.is_err()
+}
+
+/// Visitor which walks the document paths of an index expression, failing on
+/// the first path which would dereference a record link.
+struct RecordLinkChecker<'a> {
+	doc: &'a Value,
+}
+
+impl Visitor for RecordLinkChecker<'_> {
+	type Error = ();
+
+	fn visit_idiom(&mut self, i: &Idiom) -> Result<(), Self::Error> {
+		if matches!(i.0.first(), Some(Part::Field(_))) && follows_link(self.doc, &i.0) {
+			return Err(());
+		}
+		i.visit(self)
+	}
+
+	fn visit_part(&mut self, p: &Part) -> Result<(), Self::Error> {
+		match p {
+			// Conditions are evaluated against the elements of an array, not the document
+			Part::Where(_) => Ok(()),
+			p => p.visit(self),
+		}
+	}
+}
+
+/// Checks if following the path from the given value would fetch a record.
+fn follows_link(v: &Value, path: &[Part]) -> bool {
+	let Some(p) = path.first() else {
+		return false;
+	};
+	match (v, p) {
+		(_, Part::Optional) => follows_link(v, &path[1..]),
+		// Methods are run on the record id itself
+		(_, Part::Method(..)) => false,
+		(Value::RecordId(_), _) => true,
+		(Value::Object(o), Part::Field(f)) => {
+			o.get(f.as_str()).is_some_and(|v| follows_link(v, &path[1..]))
+		}
+		(Value::Object(o), Part::All) => o.values().any(|v| follows_link(v, &path[1..])),
+		(Value::Array(a), Part::All | Part::Flatten) => {
+			a.iter().any(|v| follows_link(v, &path[1..]))
+		}
+		(Value::Array(a), Part::First) => a.first().is_some_and(|v| follows_link(v, &path[1..])),
+		(Value::Array(a), Part::Last) => a.last().is_some_and(|v| follows_link(v, &path[1..])),
+		(Value::Array(a), p) => match p.as_old_index() {
+			Some(i) => a.get(i).is_some_and(|v| follows_link(v, &path[1..])),
+			// Any other part is applied to each element of the array
+			None => a.iter().any(|v| follows_link(v, path)),
+		},
+		_ => false,
+	}
+}
+
 pub(in crate::expr::statements) async fn run_indexing(
 	ctx: &FrozenContext,
 	opt: &Options,
@@ -15,6 +15,7 @@ use crate::err::Error;
 use crate::expr::parameterize::expr_to_ident;
 use crate::expr::{Base, Expr, FlowResultExt};
 use crate::iam::{Action, ResourceKind};
+use crate::kvs::running;
 use crate::sys::INFORMATION;
 use crate::val::{Datetime, Object, TableName, Value};
 
@@ -33,6 +34,14 @@ pub(crate) enum InfoStatement {
 	User(Expr, Option<Base>, bool),
 
 	Index(Expr, Expr, bool),
+
+	Bucket(Expr, bool),
+
+	Consumer(Expr, bool),
+
+	Stats(bool),
+
+	Queries,
 }
 
 impl InfoStatement {
@@ -415,6 +424,78 @@ impl InfoStatement {
 				}
 				Ok(Object::default().into())
 			}
+			InfoStatement::Bucket(bucket, structured) => {
+				// Allowed to run?
+				opt.is_allowed(Action::View, ResourceKind::Bucket, &Base::Db)?;
+				// Compute bucket name
+				let bucket = expr_to_ident(stk, ctx, opt, doc, bucket, "bucket name").await?;
+				// Obtain the bucket
+				let (ns, db) = ctx.expect_ns_db_ids(opt).await?;
+				let bd = ctx.tx().expect_db_bucket(ns, db, &bucket).await?;
+				let Some(buckets) = ctx.get_buckets() else {
+					return Err(Error::BucketUnavailable(bucket).into());
+				};
+				// Compute the current usage of the bucket
+				let store = ctx.get_bucket_store(ns, db, &bucket).await?;
+				let usage = buckets.usage(ns, db, &bd, &*store).await?.structure();
+				// Ok all good
+				Ok(if *structured {
+					let mut out = bd.as_ref().clone().structure();
+					if let Value::Object(out) = &mut out {
+						out.insert("usage".to_string(), usage);
+					}
+					out
+				} else {
+					Value::from(map! {
+						"definition".to_string() => bd.to_sql().into(),
+						"usage".to_string() => usage,
+					})
+				})
+			}
+			InfoStatement::Consumer(consumer, structured) => {
+				// Allowed to run?
+				opt.is_allowed(Action::View, ResourceKind::Table, &Base::Db)?;
+				// Compute consumer name
+				let consumer = expr_to_ident(stk, ctx, opt, doc, consumer, "consumer name").await?;
+				// Obtain the consumer
+				let (ns, db) = ctx.expect_ns_db_ids(opt).await?;
+				let cs = ctx.tx().get_db_consumer(ns, db, &consumer).await?;
+				// Ok all good
+				Ok(if *structured {
+					cs.as_ref().clone().structure()
+				} else {
+					Value::from(map! {
+						"definition".to_string() => cs.to_sql().into(),
+						"position".to_string() => cs.position.map(Value::from).unwrap_or(Value::None),
+					})
+				})
+			}
+			InfoStatement::Stats(reset) => {
+				// Root owners see the statistics of all namespaces, while
+				// namespace owners only see those of their own namespace
+				let ns = if opt.is_allowed(Action::Edit, ResourceKind::Any, &Base::Root).is_ok() {
+					None
+				} else {
+					opt.is_allowed(Action::Edit, ResourceKind::Any, &Base::Ns)?;
+					Some(opt.ns()?)
+				};
+				// Get the statistics, resetting them if requested
+				Ok(match ctx.query_stats() {
+					Some(stats) => stats.snapshot(ns, *reset),
+					None => Value::from(map! {
+						"slow".to_string() => Value::Array(Default::default()),
+						"statements".to_string() => Value::Array(Default::default()),
+					}),
+				})
+			}
+			InfoStatement::Queries => {
+				// Get the statements which the user is allowed to see
+				let (ns, db) = running::scope(opt)?;
+				Ok(match ctx.running_queries() {
+					Some(running) => running.list(ns, db),
+					None => Value::Array(Default::default()),
+				})
+			}
 		}
 	}
 }
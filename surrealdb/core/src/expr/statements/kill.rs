@@ -7,6 +7,7 @@ use crate::dbs::Options;
 use crate::doc::CursorDoc;
 use crate::err::Error;
 use crate::expr::{Expr, FlowResultExt as _};
+use crate::kvs::running;
 use crate::types::{PublicAction, PublicNotification, PublicValue};
 use crate::val::{Uuid, Value};
 
@@ -15,6 +16,8 @@ pub(crate) struct KillStatement {
 	// Uuid of Live Query
 	// or Param resolving to Uuid of Live Query
 	pub id: Expr,
+	// Whether this kills a running query instead of a live query
+	pub query: bool,
 }
 
 impl KillStatement {
@@ -27,24 +30,16 @@ impl KillStatement {
 		opt: &Options,
 		_doc: Option<&CursorDoc>,
 	) -> Result<Value> {
+		// Kill a running query
+		if self.query {
+			return self.kill_query(stk, ctx, opt).await;
+		}
 		// Is realtime enabled?
 		opt.realtime()?;
 		// Valid options?
 		opt.valid_for_db()?;
 		// Resolve live query id
-		let lid = match stk
-			.run(|stk| self.id.compute(stk, ctx, opt, None))
-			.await
-			.catch_return()?
-			.cast_to::<Uuid>()
-		{
-			Err(_) => {
-				bail!(Error::KillStatement {
-					value: self.id.to_sql(),
-				})
-			}
-			Ok(id) => id,
-		};
+		let lid = self.resolve_id(stk, ctx, opt).await?;
 		// Get the Node ID
 		let nid = opt.id();
 		// Get the LIVE ID
@@ -89,4 +84,36 @@ impl KillStatement {
 		// Return the query id
 		Ok(Value::None)
 	}
+
+	/// Cancels a statement which is running on this node.
+	async fn kill_query(&self, stk: &mut Stk, ctx: &FrozenContext, opt: &Options) -> Result<Value> {
+		// Get the statements which the user is allowed to kill
+		let (ns, db) = running::scope(opt)?;
+		// Resolve the running query id
+		let id = self.resolve_id(stk, ctx, opt).await?;
+		// Cancel the statement if it is still running
+		match ctx.running_queries() {
+			Some(running) if running.kill(&id, ns, db) => Ok(Value::None),
+			_ => bail!(Error::KillStatement {
+				value: self.id.to_sql(),
+			}),
+		}
+	}
+
+	/// Computes the id of the query to kill.
+	async fn resolve_id(&self, stk: &mut Stk, ctx: &FrozenContext, opt: &Options) -> Result<Uuid> {
+		match stk
+			.run(|stk| self.id.compute(stk, ctx, opt, None))
+			.await
+			.catch_return()?
+			.cast_to::<Uuid>()
+		{
+			Err(_) => {
+				bail!(Error::KillStatement {
+					value: self.id.to_sql(),
+				})
+			}
+			Ok(id) => Ok(id),
+		}
+	}
 }
@@ -1,4 +1,5 @@
 pub(crate) mod access;
+pub(crate) mod acknowledge;
 pub(crate) mod alter;
 pub(crate) mod create;
 // needs to be public because the RPC layer is accessing the kv store for api
@@ -25,6 +26,7 @@ pub(crate) mod upsert;
 pub(crate) mod r#use;
 
 pub(crate) use self::access::AccessStatement;
+pub(crate) use self::acknowledge::AcknowledgeStatement;
 pub(crate) use self::alter::AlterStatement;
 pub(crate) use self::create::CreateStatement;
 pub(crate) use self::define::{
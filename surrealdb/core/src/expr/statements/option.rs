@@ -6,6 +6,15 @@ pub(crate) struct OptionStatement {
 	pub what: bool,
 }
 
+impl OptionStatement {
+	pub(crate) fn import() -> Self {
+		Self {
+			name: "IMPORT".to_string(),
+			what: true,
+		}
+	}
+}
+
 impl ToSql for OptionStatement {
 	fn fmt_sql(&self, f: &mut String, fmt: SqlFormat) {
 		let stmt: crate::sql::statements::option::OptionStatement = self.clone().into();
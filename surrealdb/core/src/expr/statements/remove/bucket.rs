@@ -56,6 +56,8 @@ impl RemoveBucketStatement {
 		// Delete the definition
 		let key = crate::key::database::bu::new(ns, db, &bu.name);
 		txn.del(&key).await?;
+		// Delete the usage
+		txn.del(&crate::key::database::bq::new(ns, db, &bu.name)).await?;
 		// Clear the cache
 		txn.clear_cache();
 		// Ok all good
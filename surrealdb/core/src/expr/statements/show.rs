@@ -1,5 +1,6 @@
 use anyhow::Result;
 
+use crate::catalog::providers::DatabaseProvider;
 use crate::ctx::FrozenContext;
 use crate::dbs::Options;
 use crate::doc::CursorDoc;
@@ -13,12 +14,21 @@ pub enum ShowSince {
 	Versionstamp(u64),
 }
 
+/// The source of the changes displayed by a SHOW CHANGES statement.
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub enum ShowTarget {
+	Database,
+	Table(TableName),
+	/// Resumes from the acknowledged position of a named consumer
+	Consumer(String),
+}
+
 /// A SHOW CHANGES statement for displaying changes made to a table or database.
 
 #[derive(Clone, Debug, Eq, PartialEq, Hash)]
 pub(crate) struct ShowStatement {
-	pub table: Option<TableName>,
-	pub since: ShowSince,
+	pub target: ShowTarget,
+	pub since: Option<ShowSince>,
 	pub limit: Option<u32>,
 }
 
@@ -37,8 +47,25 @@ impl ShowStatement {
 		let txn = ctx.tx();
 		// Process the show query
 		let (ns, db) = ctx.expect_ns_db_ids(opt).await?;
-		let r = crate::cf::read(&txn, ns, db, self.table.as_ref(), self.since.clone(), self.limit)
-			.await?;
+		let r = match &self.target {
+			ShowTarget::Database => {
+				let since = self.since.clone().unwrap_or(ShowSince::Versionstamp(0));
+				crate::cf::read(&txn, ns, db, None, since, self.limit).await?
+			}
+			ShowTarget::Table(tb) => {
+				let since = self.since.clone().unwrap_or(ShowSince::Versionstamp(0));
+				crate::cf::read(&txn, ns, db, Some(tb), since, self.limit).await?
+			}
+			ShowTarget::Consumer(cs) => {
+				let cs = txn.get_db_consumer(ns, db, cs).await?;
+				// Resume after the last acknowledged change
+				let since = match &self.since {
+					Some(since) => since.clone(),
+					None => ShowSince::Versionstamp(cs.next_versionstamp()),
+				};
+				crate::cf::read(&txn, ns, db, Some(&cs.target_table), since, self.limit).await?
+			}
+		};
 		// Return the changes
 		let a: Vec<Value> = r.iter().cloned().map(|x| x.into_value()).collect();
 		Ok(a.into())
@@ -3,13 +3,17 @@ use std::fmt::Display;
 
 use revision::revisioned;
 
-#[revisioned(revision = 1)]
+#[revisioned(revision = 2)]
 #[derive(Clone, Debug, Eq, PartialEq, Hash)]
 pub enum Tokenizer {
 	Blank,
 	Camel,
 	Class,
 	Punct,
+	#[revision(start = 2)]
+	Word,
+	#[revision(start = 2)]
+	Cjk,
 }
 
 impl Display for Tokenizer {
@@ -19,6 +23,8 @@ impl Display for Tokenizer {
 			Self::Camel => "CAMEL",
 			Self::Class => "CLASS",
 			Self::Punct => "PUNCT",
+			Self::Word => "WORD",
+			Self::Cjk => "CJK",
 		})
 	}
 }
@@ -22,14 +22,15 @@ use crate::expr::statements::define::config::ConfigInner;
 use crate::expr::statements::define::config::api::ApiConfig;
 use crate::expr::statements::define::config::defaults::DefaultConfig;
 use crate::expr::statements::define::{
-	ApiAction, DefineBucketStatement, DefineConfigStatement, DefineDefault, DefineSequenceStatement,
+	ApiAction, ConsumerSink, DefineBucketStatement, DefineConfigStatement, DefineConsumerStatement,
+	DefineDefault, DefineSequenceStatement,
 };
 use crate::expr::statements::rebuild::RebuildStatement;
 use crate::expr::statements::remove::{
-	RemoveApiStatement, RemoveBucketStatement, RemoveSequenceStatement,
+	RemoveApiStatement, RemoveBucketStatement, RemoveConsumerStatement, RemoveSequenceStatement,
 };
 use crate::expr::statements::{
-	AccessStatement, AlterStatement, CreateStatement, DefineAccessStatement,
+	AccessStatement, AcknowledgeStatement, AlterStatement, CreateStatement, DefineAccessStatement,
 	DefineAnalyzerStatement, DefineApiStatement, DefineDatabaseStatement, DefineEventStatement,
 	DefineFieldStatement, DefineFunctionStatement, DefineIndexStatement, DefineModelStatement,
 	DefineModuleStatement, DefineNamespaceStatement, DefineParamStatement, DefineStatement,
@@ -140,6 +141,7 @@ implement_visitor! {
 		TopLevelExpr::Option(s) =>{ this.visit_option(s)?; },
 		TopLevelExpr::Use(s) => {this.visit_use(s)?; },
 		TopLevelExpr::Show(s) => {this.visit_show(s)?; },
+		TopLevelExpr::Acknowledge(s) => {this.visit_acknowledge(s)?; },
 		TopLevelExpr::Expr(e) => {this.visit_expr(e)?; },
 		}
 		Ok(())
@@ -228,6 +230,10 @@ implement_visitor! {
 		Ok(())
 	}
 
+	fn visit_acknowledge(this, s: &AcknowledgeStatement){
+		Ok(())
+	}
+
 	fn visit_expr(this, s: &Expr){
 		match s {
 			Expr::Literal(literal) => {
@@ -658,6 +664,9 @@ implement_visitor! {
 			RemoveStatement::Sequence(r) => {
 				this.visit_remove_sequence(r)?;
 			},
+			RemoveStatement::Consumer(r) => {
+				this.visit_remove_consumer(r)?;
+			},
 			RemoveStatement::Module(r) => {
 				this.visit_remove_module(r)?;
 			},
@@ -750,6 +759,11 @@ implement_visitor! {
 		Ok(())
 	}
 
+	fn visit_remove_consumer(this, r: &RemoveConsumerStatement){
+		this.visit_expr(&r.name)?;
+		Ok(())
+	}
+
 	fn visit_relate(this, o: &RelateStatement){
 		this.visit_expr(&o.through)?;
 		this.visit_expr(&o.from)?;
@@ -811,6 +825,14 @@ implement_visitor! {
 				this.visit_expr(expr)?;
 				this.visit_expr(expr1)?;
 			},
+			InfoStatement::Bucket(expr, _) => {
+				this.visit_expr(expr)?;
+			},
+			InfoStatement::Consumer(expr, _) => {
+				this.visit_expr(expr)?;
+			},
+			InfoStatement::Stats(_) => {},
+			InfoStatement::Queries => {},
 		}
 		Ok(())
 	}
@@ -897,6 +919,9 @@ implement_visitor! {
 			DefineStatement::Sequence(d) => {
 				this.visit_define_sequence(d)?;
 			},
+			DefineStatement::Consumer(d) => {
+				this.visit_define_consumer(d)?;
+			},
 			DefineStatement::Module(d) => {
 				this.visit_define_module(d)?;
 			},
@@ -904,6 +929,17 @@ implement_visitor! {
 		Ok(())
 	}
 
+	fn visit_define_consumer(this, d: &DefineConsumerStatement) {
+		this.visit_expr(&d.name)?;
+		this.visit_expr(&d.target_table)?;
+		this.visit_expr(&d.comment)?;
+		match d.sink {
+			Some(ConsumerSink::Webhook(ref v) | ConsumerSink::File(ref v)) => this.visit_expr(v)?,
+			None => {}
+		}
+		Ok(())
+	}
+
 	fn visit_define_sequence(this, d: &DefineSequenceStatement) {
 		this.visit_expr(&d.name)?;
 		this.visit_expr(&d.batch)?;
@@ -1650,6 +1686,7 @@ implement_visitor_mut! {
 		TopLevelExpr::Option(s) =>{ this.visit_mut_option(s)?; },
 		TopLevelExpr::Use(s) => {this.visit_mut_use(s)?; },
 		TopLevelExpr::Show(s) => {this.visit_mut_show(s)?; },
+		TopLevelExpr::Acknowledge(s) => {this.visit_mut_acknowledge(s)?; },
 		TopLevelExpr::Expr(e) => {this.visit_mut_expr(e)?; },
 		}
 		Ok(())
@@ -1738,6 +1775,10 @@ implement_visitor_mut! {
 		Ok(())
 	}
 
+	fn visit_mut_acknowledge(this, s: &mut AcknowledgeStatement){
+		Ok(())
+	}
+
 	fn visit_mut_expr(this, s: &mut Expr){
 		match s {
 			Expr::Literal(literal) => {
@@ -2168,6 +2209,9 @@ implement_visitor_mut! {
 			RemoveStatement::Sequence(r) => {
 				this.visit_mut_remove_sequence(r)?;
 			},
+			RemoveStatement::Consumer(r) => {
+				this.visit_mut_remove_consumer(r)?;
+			},
 			RemoveStatement::Module(r) => {
 				this.visit_mut_remove_module(r)?;
 			},
@@ -2260,6 +2304,11 @@ implement_visitor_mut! {
 		Ok(())
 	}
 
+	fn visit_mut_remove_consumer(this, r: &mut RemoveConsumerStatement){
+		this.visit_mut_expr(&mut r.name)?;
+		Ok(())
+	}
+
 	fn visit_mut_relate(this, o: &mut RelateStatement){
 		this.visit_mut_expr(&mut o.through)?;
 		this.visit_mut_expr(&mut o.from)?;
@@ -2321,6 +2370,14 @@ implement_visitor_mut! {
 				this.visit_mut_expr(expr)?;
 				this.visit_mut_expr(expr1)?;
 			},
+			InfoStatement::Bucket(expr, _) => {
+				this.visit_mut_expr(expr)?;
+			},
+			InfoStatement::Consumer(expr, _) => {
+				this.visit_mut_expr(expr)?;
+			},
+			InfoStatement::Stats(_) => {},
+			InfoStatement::Queries => {},
 		}
 		Ok(())
 	}
@@ -2407,6 +2464,9 @@ implement_visitor_mut! {
 			DefineStatement::Sequence(d) => {
 				this.visit_mut_define_sequence(d)?;
 			},
+			DefineStatement::Consumer(d) => {
+				this.visit_mut_define_consumer(d)?;
+			},
 			DefineStatement::Module(d) => {
 				this.visit_mut_define_module(d)?;
 			},
@@ -2414,6 +2474,19 @@ implement_visitor_mut! {
 		Ok(())
 	}
 
+	fn visit_mut_define_consumer(this, d: &mut DefineConsumerStatement) {
+		this.visit_mut_expr(&mut d.name)?;
+		this.visit_mut_expr(&mut d.target_table)?;
+		this.visit_mut_expr(&mut d.comment)?;
+		match d.sink {
+			Some(ConsumerSink::Webhook(ref mut v) | ConsumerSink::File(ref mut v)) => {
+				this.visit_mut_expr(v)?
+			}
+			None => {}
+		}
+		Ok(())
+	}
+
 	fn visit_mut_define_sequence(this, d: &mut DefineSequenceStatement) {
 		this.visit_mut_expr(&mut d.name)?;
 		this.visit_mut_expr(&mut d.batch)?;
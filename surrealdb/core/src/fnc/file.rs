@@ -4,18 +4,27 @@ use reblessive::tree::Stk;
 use super::CursorDoc;
 use super::args::Optional;
 use crate::buc::BucketController;
-use crate::buc::store::ObjectKey;
+use crate::buc::store::{GetOptions, ObjectKey, PutOptions};
 use crate::ctx::FrozenContext;
 use crate::dbs::Options;
 use crate::val::{File, Object, Value};
 
 /// Put a file into a bucket.
+///
+/// Optional `{ content_type, metadata, if_match, if_none_match }` options can be
+/// specified to store metadata alongside the file, or to make the write conditional.
 pub async fn put(
 	(stk, ctx, opt, doc): (&mut Stk, &FrozenContext, &Options, Option<&CursorDoc>),
-	(file, value): (File, Value),
+	(file, value, Optional(opts)): (File, Value, Optional<Object>),
 ) -> Result<Value> {
 	let mut controller = BucketController::new(stk, ctx, opt, doc, &file.bucket).await?;
-	controller.put(&ObjectKey::new(file.key), value).await?;
+	match opts {
+		Some(opts) => {
+			let opts = PutOptions::try_from(opts)?;
+			controller.put_opts(&ObjectKey::new(file.key), value, opts).await?;
+		}
+		None => controller.put(&ObjectKey::new(file.key), value).await?,
+	}
 
 	Ok(Value::None)
 }
@@ -32,12 +41,21 @@ pub async fn put_if_not_exists(
 }
 
 /// Get a file from a bucket.
+///
+/// Optional `{ offset, length, if_match, if_none_match }` options can be specified
+/// to only fetch part of the file, or to make the read conditional.
 pub async fn get(
 	(stk, ctx, opt, doc): (&mut Stk, &FrozenContext, &Options, Option<&CursorDoc>),
-	(file,): (File,),
+	(file, Optional(opts)): (File, Optional<Object>),
 ) -> Result<Value> {
 	let mut controller = BucketController::new(stk, ctx, opt, doc, &file.bucket).await?;
-	let res = controller.get(&ObjectKey::new(file.key)).await?;
+	let res = match opts {
+		Some(opts) => {
+			let opts = GetOptions::try_from(opts)?;
+			controller.get_opts(&ObjectKey::new(file.key), opts).await?
+		}
+		None => controller.get(&ObjectKey::new(file.key)).await?,
+	};
 	Ok(res.map(Value::Bytes).unwrap_or_default())
 }
 
@@ -194,6 +212,62 @@ pub async fn list(
 	Ok(res)
 }
 
+pub mod multipart {
+	use anyhow::Result;
+	use reblessive::tree::Stk;
+
+	use super::CursorDoc;
+	use crate::buc::BucketController;
+	use crate::buc::store::ObjectKey;
+	use crate::ctx::FrozenContext;
+	use crate::dbs::Options;
+	use crate::val::{File, Uuid, Value};
+
+	/// Start a multipart upload of a file, returning the id of the upload.
+	pub async fn create(
+		(stk, ctx, opt, doc): (&mut Stk, &FrozenContext, &Options, Option<&CursorDoc>),
+		(file,): (File,),
+	) -> Result<Value> {
+		let mut controller = BucketController::new(stk, ctx, opt, doc, &file.bucket).await?;
+		let id = controller.create_upload(&ObjectKey::new(file.key)).await?;
+
+		Ok(Value::Uuid(Uuid(id)))
+	}
+
+	/// Append a chunk of data to a multipart upload.
+	pub async fn put(
+		(stk, ctx, opt, doc): (&mut Stk, &FrozenContext, &Options, Option<&CursorDoc>),
+		(file, id, value): (File, Uuid, Value),
+	) -> Result<Value> {
+		let mut controller = BucketController::new(stk, ctx, opt, doc, &file.bucket).await?;
+		controller.put_upload_part(&ObjectKey::new(file.key), id.0, value).await?;
+
+		Ok(Value::None)
+	}
+
+	/// Complete a multipart upload, making the file visible in the bucket.
+	pub async fn complete(
+		(stk, ctx, opt, doc): (&mut Stk, &FrozenContext, &Options, Option<&CursorDoc>),
+		(file, id): (File, Uuid),
+	) -> Result<Value> {
+		let mut controller = BucketController::new(stk, ctx, opt, doc, &file.bucket).await?;
+		controller.complete_upload(&ObjectKey::new(file.key), id.0).await?;
+
+		Ok(Value::None)
+	}
+
+	/// Abort a multipart upload, discarding any uploaded data.
+	pub async fn abort(
+		(stk, ctx, opt, doc): (&mut Stk, &FrozenContext, &Options, Option<&CursorDoc>),
+		(file, id): (File, Uuid),
+	) -> Result<Value> {
+		let mut controller = BucketController::new(stk, ctx, opt, doc, &file.bucket).await?;
+		controller.abort_upload(&ObjectKey::new(file.key), id.0).await?;
+
+		Ok(Value::None)
+	}
+}
+
 pub fn bucket((file,): (File,)) -> Result<Value> {
 	Ok(file.bucket.into())
 }
@@ -76,6 +76,10 @@ pub async fn run(
 		|| name.eq("file::rename")
 		|| name.eq("file::rename_if_not_exists")
 		|| name.eq("file::list")
+		|| name.eq("file::multipart::create")
+		|| name.eq("file::multipart::put")
+		|| name.eq("file::multipart::complete")
+		|| name.eq("file::multipart::abort")
 		|| name.eq("record::exists")
 		|| name.eq("record::is_edge")
 		|| name.eq("set::all")
@@ -610,6 +614,10 @@ pub async fn asynchronous(
 		exp(Files) "file::rename_if_not_exists" => file::rename_if_not_exists((stk, ctx, opt, doc)).await,
 		exp(Files) "file::exists" => file::exists((stk, ctx, opt, doc)).await,
 		exp(Files) "file::list" => file::list((stk, ctx, opt, doc)).await,
+		exp(Files) "file::multipart::create" => file::multipart::create((stk, ctx, opt, doc)).await,
+		exp(Files) "file::multipart::put" => file::multipart::put((stk, ctx, opt, doc)).await,
+		exp(Files) "file::multipart::complete" => file::multipart::complete((stk, ctx, opt, doc)).await,
+		exp(Files) "file::multipart::abort" => file::multipart::abort((stk, ctx, opt, doc)).await,
 		//
 		"http::head" => http::head(ctx).await,
 		"http::get" => http::get(ctx).await,
@@ -1730,6 +1738,58 @@ fn idiom_name_to_normal(kind: &str, name: &str) -> String {
 	format!("{kind}::{name}")
 }
 
+/// Checks if a builtin function always returns the same result for the same
+/// arguments, without reading the datastore, the session, the clock or any
+/// other source of entropy.
+pub(crate) fn is_pure_function(name: &str) -> bool {
+	match name {
+		"count" | "not" => true,
+		"array::shuffle"
+		| "record::exists"
+		| "record::is_edge"
+		| "time::now"
+		| "time::timezone"
+		| "type::field"
+		| "type::fields"
+		| "vector::distance::knn" => false,
+		_ => match name.split_once("::") {
+			// Generated hashes are salted with random bytes
+			Some(("crypto", f)) => !f.ends_with("::generate"),
+			Some((
+				"array" | "bytes" | "duration" | "encoding" | "geo" | "math" | "meta" | "object"
+				| "parse" | "record" | "set" | "string" | "time" | "type" | "value" | "vector",
+				_,
+			)) => true,
+			_ => false,
+		},
+	}
+}
+
+/// Checks if a method is pure whatever the type of the value it is called on,
+/// as the receiver is only known once the expression is evaluated.
+pub(crate) fn is_pure_method(name: &str) -> bool {
+	// Methods on files access the bucket which stores them
+	const FILE_METHODS: &[&str] = &[
+		"put",
+		"put_if_not_exists",
+		"get",
+		"head",
+		"delete",
+		"copy",
+		"copy_if_not_exists",
+		"rename",
+		"rename_if_not_exists",
+		"exists",
+	];
+	!FILE_METHODS.contains(&name)
+		&& [
+			"array", "bytes", "datetime", "duration", "geometry", "number", "object", "record",
+			"set", "string",
+		]
+		.into_iter()
+		.all(|kind| is_pure_function(&idiom_name_to_normal(kind, name)))
+}
+
 #[cfg(test)]
 mod tests {
 	use regex::Regex;
@@ -3,6 +3,8 @@ use js::prelude::Async;
 use super::{fut, run};
 use crate::fnc::script::modules::impl_module_def;
 
+mod multipart;
+
 pub struct Package;
 
 impl_module_def!(
@@ -22,5 +24,6 @@ impl_module_def!(
 	"rename" => fut Async,
 	"rename_if_not_exists" => fut Async,
 	"exists" => fut Async,
-	"list" => fut Async
+	"list" => fut Async,
+	"multipart" => (multipart::Package)
 );
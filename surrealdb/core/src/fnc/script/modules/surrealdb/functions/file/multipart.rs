@@ -0,0 +1,15 @@
+use js::prelude::Async;
+
+use super::super::fut;
+use crate::fnc::script::modules::impl_module_def;
+
+pub struct Package;
+
+impl_module_def!(
+	Package,
+	"file::multipart",
+	"create" => fut Async,
+	"put" => fut Async,
+	"complete" => fut Async,
+	"abort" => fut Async
+);
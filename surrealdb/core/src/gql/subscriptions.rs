@@ -356,6 +356,7 @@ async fn start_table_live_query(
 async fn kill_live_query(ds: &Datastore, sess: &Session, live_id: Uuid) -> Result<(), GqlError> {
 	let stmt = KillStatement {
 		id: Expr::Literal(Literal::Uuid(live_id.into())),
+		query: false,
 	};
 	let plan = LogicalPlan {
 		expressions: vec![TopLevelExpr::Kill(stmt)],
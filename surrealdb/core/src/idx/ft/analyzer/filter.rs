@@ -2,10 +2,14 @@ use anyhow::Result;
 use deunicode::deunicode;
 use rust_stemmers::{Algorithm, Stemmer};
 
-use crate::expr::filter::Filter as SqlFilter;
+use crate::expr::filter::{
+	Filter as SqlFilter, Stopwords as SqlStopwords, Synonyms as SqlSynonyms,
+};
 use crate::expr::language::Language;
 use crate::idx::ft::Position;
 use crate::idx::ft::analyzer::mapper::Mapper;
+use crate::idx::ft::analyzer::stopwords::StopWords;
+use crate::idx::ft::analyzer::synonyms::Synonyms;
 use crate::idx::ft::analyzer::tokenizer::Tokens;
 use crate::idx::trees::store::IndexStores;
 
@@ -22,6 +26,8 @@ pub(super) enum Filter {
 	Lowercase,
 	Uppercase,
 	Mapper(Mapper),
+	StopWords(StopWords),
+	Synonyms(Synonyms),
 }
 
 impl Filter {
@@ -56,6 +62,15 @@ impl Filter {
 			}
 			SqlFilter::Uppercase => Filter::Uppercase,
 			SqlFilter::Mapper(path) => Filter::Mapper(ixs.mappers().get(path)?),
+			SqlFilter::Stopwords(s) => Filter::StopWords(match s {
+				SqlStopwords::Language(l) => StopWords::from_language(*l)?,
+				SqlStopwords::Words(w) => StopWords::from_words(w.iter().map(String::as_str)),
+				SqlStopwords::File(path) => ixs.mappers().get_stopwords(path)?,
+			}),
+			SqlFilter::Synonyms(s) => Filter::Synonyms(match s {
+				SqlSynonyms::Rules(r) => Synonyms::from_rules(r.iter().map(String::as_str))?,
+				SqlSynonyms::File(path) => ixs.mappers().get_synonyms(path)?,
+			}),
 		};
 		Ok(f)
 	}
@@ -107,6 +122,8 @@ impl Filter {
 			Filter::Stemmer(s) => Self::stem(s, c),
 			Filter::Uppercase => Self::uppercase(c),
 			Filter::Mapper(m) => m.map(c),
+			Filter::StopWords(s) => s.filter(c),
+			Filter::Synonyms(s) => s.expand(c),
 		}
 	}
 
@@ -901,4 +918,36 @@ mod tests {
 		)
 		.await;
 	}
+
+	#[tokio::test]
+	async fn test_stopwords() {
+		test_analyzer(
+			"ANALYZER test TOKENIZERS blank FILTERS stopwords(english)",
+			"The quick brown fox jumps over the lazy dog",
+			&["quick", "brown", "fox", "jumps", "lazy", "dog"],
+		)
+		.await;
+		test_analyzer(
+			"ANALYZER test TOKENIZERS blank FILTERS lowercase,stopwords(['quick', 'LAZY'])",
+			"The quick brown fox jumps over the lazy dog",
+			&["the", "brown", "fox", "jumps", "over", "the", "dog"],
+		)
+		.await;
+	}
+
+	#[tokio::test]
+	async fn test_synonyms() {
+		test_analyzer(
+			"ANALYZER test TOKENIZERS blank FILTERS lowercase,synonyms(['tv, television', 'laptop => notebook'])",
+			"Laptop and TV",
+			&["notebook", "and", "tv", "television"],
+		)
+		.await;
+		test_analyzer(
+			"ANALYZER test TOKENIZERS blank FILTERS synonyms(['tv, television'])",
+			"television",
+			&["tv", "television"],
+		)
+		.await;
+	}
 }
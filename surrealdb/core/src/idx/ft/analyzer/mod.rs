@@ -1,5 +1,6 @@
 use std::collections::HashMap;
 use std::collections::hash_map::Entry;
+use std::path::Path;
 use std::sync::Arc;
 
 use anyhow::{Result, bail};
@@ -12,15 +13,18 @@ use crate::ctx::FrozenContext;
 use crate::dbs::Options;
 use crate::err::Error;
 use crate::expr::{FlowResultExt as _, Function};
+use crate::iam::file::is_path_allowed;
 use crate::idx::ft::analyzer::filter::FilteringStage;
 use crate::idx::ft::analyzer::tokenizer::{Tokenizer, Tokens};
-use crate::idx::ft::offset::Offset;
+use crate::idx::ft::offset::{Offset, TermPosition};
 use crate::idx::ft::{DocLength, TermFrequency};
 use crate::idx::trees::store::IndexStores;
 use crate::val::Value;
 
 pub(in crate::idx::ft) mod filter;
 pub(in crate::idx) mod mapper;
+pub(in crate::idx) mod stopwords;
+pub(in crate::idx) mod synonyms;
 pub(in crate::idx::ft) mod tokenizer;
 
 #[derive(Clone)]
@@ -130,21 +134,25 @@ impl Analyzer {
 		self.generate_tokens(stk, ctx, opt, FilteringStage::Indexing, input).await?.try_into()
 	}
 
+	#[allow(clippy::type_complexity)]
 	pub(in crate::idx::ft) fn extract_frequencies(
 		inputs: &[Tokens],
-	) -> Result<(DocLength, HashMap<&str, TermFrequency>)> {
+	) -> Result<(DocLength, HashMap<&str, (TermFrequency, Vec<TermPosition>)>)> {
 		let mut dl = 0;
-		let mut tf: HashMap<&str, TermFrequency> = HashMap::new();
-		for tks in inputs {
-			for tk in tks.list() {
+		let mut tf: HashMap<&str, (TermFrequency, Vec<TermPosition>)> = HashMap::new();
+		for (i, tks) in inputs.iter().enumerate() {
+			for (tk, pos) in tks.list().iter().zip(tks.positions()) {
 				dl += 1;
 				let s = tks.get_token_string(tk)?;
+				let p = TermPosition::new(i as u32, pos);
 				match tf.entry(s) {
 					Entry::Vacant(e) => {
-						e.insert(1);
+						e.insert((1, vec![p]));
 					}
 					Entry::Occupied(mut e) => {
-						e.insert(*e.get() + 1);
+						let e = e.get_mut();
+						e.0 += 1;
+						e.1.push(p);
 					}
 				}
 			}
@@ -152,23 +160,50 @@ impl Analyzer {
 		Ok((dl, tf))
 	}
 
+	#[allow(clippy::type_complexity)]
 	pub(in crate::idx::ft) fn extract_offsets(
 		inputs: &[Tokens],
-	) -> anyhow::Result<(DocLength, HashMap<&str, Vec<Offset>>)> {
+	) -> anyhow::Result<(DocLength, HashMap<&str, (Vec<Offset>, Vec<TermPosition>)>)> {
 		let mut dl = 0;
-		let mut tfos: HashMap<&str, Vec<Offset>> = HashMap::new();
+		let mut tfos: HashMap<&str, (Vec<Offset>, Vec<TermPosition>)> = HashMap::new();
 		for (i, tks) in inputs.iter().enumerate() {
-			for tk in tks.list() {
+			for (tk, pos) in tks.list().iter().zip(tks.positions()) {
 				dl += 1;
 				let s = tks.get_token_string(tk)?;
 				let o = tk.new_offset(i as u32);
-				tfos.entry(s).or_default().push(o);
+				let e = tfos.entry(s).or_default();
+				e.0.push(o);
+				e.1.push(TermPosition::new(i as u32, pos));
 			}
 		}
 		Ok((dl, tfos))
 	}
 }
 
+/// Reads the lines of a file referenced by an analyzer filter
+#[cfg(not(target_family = "wasm"))]
+async fn read_lines(path: &Path) -> Result<Vec<String>> {
+	use tokio::io::AsyncBufReadExt;
+	let path = is_path_allowed(path)?;
+	let file = tokio::fs::File::open(path).await?;
+	let mut lines = tokio::io::BufReader::new(file).lines();
+	let mut res = Vec::new();
+	while let Some(line) = lines.next_line().await? {
+		yield_now!();
+		res.push(line);
+	}
+	Ok(res)
+}
+
+/// Reads the lines of a file referenced by an analyzer filter
+#[cfg(target_family = "wasm")]
+async fn read_lines(path: &Path) -> Result<Vec<String>> {
+	use std::io::BufRead;
+	let path = is_path_allowed(path)?;
+	let file = std::fs::File::open(path)?;
+	Ok(std::io::BufReader::new(file).lines().collect::<std::io::Result<_>>()?)
+}
+
 #[cfg(test)]
 mod tests {
 	use std::sync::Arc;
@@ -0,0 +1,96 @@
+use std::collections::HashMap;
+use std::path::Path;
+use std::sync::{Arc, LazyLock};
+
+use ahash::HashSet;
+use anyhow::{Result, bail};
+
+use crate::err::Error;
+use crate::expr::language::Language;
+use crate::idx::ft::analyzer::filter::{FilterResult, Term};
+use crate::idx::ft::analyzer::read_lines;
+
+/// The built-in stopword lists, which are parsed on first use
+static BUILTIN: LazyLock<HashMap<Language, StopWords>> = LazyLock::new(|| {
+	[
+		(Language::Arabic, include_str!("arabic.txt")),
+		(Language::Danish, include_str!("danish.txt")),
+		(Language::Dutch, include_str!("dutch.txt")),
+		(Language::English, include_str!("english.txt")),
+		(Language::Finnish, include_str!("finnish.txt")),
+		(Language::French, include_str!("french.txt")),
+		(Language::German, include_str!("german.txt")),
+		(Language::Greek, include_str!("greek.txt")),
+		(Language::Hungarian, include_str!("hungarian.txt")),
+		(Language::Italian, include_str!("italian.txt")),
+		(Language::Norwegian, include_str!("norwegian.txt")),
+		(Language::Portuguese, include_str!("portuguese.txt")),
+		(Language::Romanian, include_str!("romanian.txt")),
+		(Language::Russian, include_str!("russian.txt")),
+		(Language::Spanish, include_str!("spanish.txt")),
+		(Language::Swedish, include_str!("swedish.txt")),
+		(Language::Turkish, include_str!("turkish.txt")),
+	]
+	.into_iter()
+	.map(|(l, s)| (l, StopWords::from_words(s.lines())))
+	.collect()
+});
+
+/// A set of words which are removed from the tokens.
+/// Words are compared case-insensitively.
+#[derive(Clone, Default)]
+pub(in crate::idx) struct StopWords {
+	words: Arc<HashSet<String>>,
+}
+
+impl StopWords {
+	/// Returns the built-in list for a language
+	pub(in crate::idx) fn from_language(l: Language) -> Result<Self> {
+		match BUILTIN.get(&l) {
+			Some(s) => Ok(s.clone()),
+			None => bail!(Error::AnalyzerError(format!(
+				"There is no built-in stopword list for the language {}",
+				l.as_str()
+			))),
+		}
+	}
+
+	pub(in crate::idx) fn from_words<'a>(words: impl Iterator<Item = &'a str>) -> Self {
+		let words = words
+			.map(str::trim)
+			.filter(|w| !w.is_empty() && !w.starts_with('#'))
+			.map(str::to_lowercase)
+			.collect();
+		Self {
+			words: Arc::new(words),
+		}
+	}
+
+	/// Loads a list containing one word per line.
+	/// Empty lines and lines starting with `#` are ignored.
+	pub(in crate::idx) async fn from_file(path: &Path) -> Result<Self> {
+		let lines = read_lines(path).await?;
+		Ok(Self::from_words(lines.iter().map(String::as_str)))
+	}
+
+	pub(super) fn filter(&self, token: &str) -> FilterResult {
+		// The words are lowercased when the list is created, so a token only
+		// needs to be lowercased when it is not already in lowercase
+		let stop = if token.chars().all(is_lowercase) {
+			self.words.contains(token)
+		} else {
+			self.words.contains(&token.to_lowercase())
+		};
+		if stop {
+			FilterResult::Ignore
+		} else {
+			FilterResult::Term(Term::Unchanged)
+		}
+	}
+}
+
+/// Checks if a character is unchanged when lowercased
+fn is_lowercase(c: char) -> bool {
+	let mut l = c.to_lowercase();
+	l.next() == Some(c) && l.next().is_none()
+}
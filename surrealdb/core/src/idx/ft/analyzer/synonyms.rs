@@ -0,0 +1,90 @@
+use std::collections::HashMap;
+use std::path::Path;
+use std::sync::Arc;
+
+use anyhow::{Result, ensure};
+
+use crate::err::Error;
+use crate::idx::ft::analyzer::filter::{FilterResult, Term};
+use crate::idx::ft::analyzer::read_lines;
+
+/// A set of synonym rules, using the Solr format:
+/// - `tv, television` is an equivalence rule, which expands each of the terms into all of the
+///   terms.
+/// - `laptop, notebook => computer` is a one-way rule, which replaces each of the terms on the left
+///   with the terms on the right.
+///
+/// Terms are compared case-insensitively.
+#[derive(Clone, Default)]
+pub(in crate::idx) struct Synonyms {
+	terms: Arc<HashMap<String, Vec<String>>>,
+}
+
+impl Synonyms {
+	pub(in crate::idx) fn from_rules<'a>(rules: impl Iterator<Item = &'a str>) -> Result<Self> {
+		let mut terms: HashMap<String, Vec<String>> = HashMap::new();
+		for rule in rules {
+			let rule = rule.trim();
+			if rule.is_empty() || rule.starts_with('#') {
+				continue;
+			}
+			let (from, to) = match rule.split_once("=>") {
+				Some((from, to)) => (Self::parse_terms(rule, from)?, Self::parse_terms(rule, to)?),
+				None => {
+					let t = Self::parse_terms(rule, rule)?;
+					(t.clone(), t)
+				}
+			};
+			for f in from {
+				let e = terms.entry(f).or_default();
+				for t in &to {
+					if !e.contains(t) {
+						e.push(t.clone());
+					}
+				}
+			}
+		}
+		Ok(Self {
+			terms: Arc::new(terms),
+		})
+	}
+
+	/// Loads a list containing one rule per line.
+	/// Empty lines and lines starting with `#` are ignored.
+	pub(in crate::idx) async fn from_file(path: &Path) -> Result<Self> {
+		let lines = read_lines(path).await?;
+		Self::from_rules(lines.iter().map(String::as_str))
+	}
+
+	fn parse_terms(rule: &str, terms: &str) -> Result<Vec<String>> {
+		let mut res = Vec::new();
+		for t in terms.split(',') {
+			let t = t.trim();
+			ensure!(
+				!t.is_empty() && !t.contains(char::is_whitespace),
+				Error::AnalyzerError(format!(
+					"Invalid synonym rule '{rule}': each term must be a single non-empty word"
+				))
+			);
+			res.push(t.to_lowercase());
+		}
+		Ok(res)
+	}
+
+	pub(super) fn expand(&self, token: &str) -> FilterResult {
+		let Some(terms) = self.terms.get(&token.to_lowercase()) else {
+			return FilterResult::Term(Term::Unchanged);
+		};
+		let terms = terms
+			.iter()
+			.map(|t| {
+				if t == token {
+					Term::Unchanged
+				} else {
+					Term::NewTerm(t.clone(), 0)
+				}
+			})
+			.collect();
+		FilterResult::Terms(terms)
+	}
+}
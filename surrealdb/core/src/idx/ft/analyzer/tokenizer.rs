@@ -1,4 +1,5 @@
 use anyhow::{Result, bail};
+use unicode_segmentation::UnicodeSegmentation;
 
 use crate::err::Error;
 use crate::expr::tokenizer::Tokenizer as SqlTokenizer;
@@ -65,6 +66,24 @@ impl Tokens {
 		&self.t
 	}
 
+	/// Returns the position of each token, which is the position of the
+	/// original token it was generated from. Tokens generated from the same
+	/// original token, like n-grams or synonyms, share the same position.
+	pub(in crate::idx::ft) fn positions(&self) -> Vec<Position> {
+		let mut res = Vec::with_capacity(self.t.len());
+		let mut last = None;
+		let mut pos = 0;
+		for tk in &self.t {
+			let start = tk.start();
+			if last.is_some_and(|l| l != start) {
+				pos += 1;
+			}
+			last = Some(start);
+			res.push(pos);
+		}
+		res
+	}
+
 	pub(in crate::idx::ft) fn try_contains(&self, s: &str) -> Result<bool> {
 		for t in &self.t {
 			if self.get_token_string(t)?.eq(s) {
@@ -142,6 +161,20 @@ impl Token {
 		}
 	}
 
+	/// The start position of the original token
+	fn start(&self) -> Position {
+		match self {
+			Token::Ref {
+				chars,
+				..
+			} => chars.0,
+			Token::String {
+				chars,
+				..
+			} => chars.0,
+		}
+	}
+
 	fn is_empty(&self) -> bool {
 		match self {
 			Token::Ref {
@@ -194,12 +227,26 @@ impl Token {
 
 pub(super) struct Tokenizer {
 	splitters: Vec<Splitter>,
+	segmenters: Vec<Segmenter>,
 }
 
 impl Tokenizer {
 	pub(in crate::idx::ft) fn new(t: &[SqlTokenizer]) -> Self {
+		let mut splitters = Vec::new();
+		let mut segmenters = Vec::new();
+		// Word boundaries are meaningless within runs of CJK characters, so
+		// those are kept together when they are split into bigrams later
+		let keep_cjk = t.contains(&SqlTokenizer::Cjk);
+		for t in t {
+			match t {
+				SqlTokenizer::Word => segmenters.push(Segmenter::Word(keep_cjk)),
+				SqlTokenizer::Cjk => segmenters.push(Segmenter::Cjk),
+				t => splitters.push(t.into()),
+			}
+		}
 		Self {
-			splitters: t.iter().map(|t| t.into()).collect(),
+			splitters,
+			segmenters,
 		}
 	}
 
@@ -231,6 +278,45 @@ impl Tokenizer {
 
 	pub(super) fn tokenize(t: &[SqlTokenizer], i: String) -> Tokens {
 		let mut w = Tokenizer::new(t);
+		// Without any character based tokenizer, the segmenters receive
+		// the whole input as a single token
+		let mut t = if w.splitters.is_empty() && !w.segmenters.is_empty() {
+			let len = i.chars().count() as Position;
+			if len > 0 {
+				vec![Token::Ref {
+					chars: (0, 0, len),
+					bytes: (0, i.len() as Position),
+					len,
+				}]
+			} else {
+				vec![]
+			}
+		} else {
+			w.split(&i)
+		};
+		// Each segmenter splits the tokens of the previous stage
+		for s in &w.segmenters {
+			let mut res = Vec::with_capacity(t.len());
+			for tk in &t {
+				if let Token::Ref {
+					chars,
+					bytes,
+					..
+				} = tk
+				{
+					s.segment(&i, chars.0, *bytes, &mut res);
+				}
+			}
+			t = res;
+		}
+		Tokens {
+			i,
+			t,
+		}
+	}
+
+	/// Splits the input on the character roles defined by the splitters
+	fn split(&mut self, i: &str) -> Vec<Token> {
 		let mut last_char_pos = 0;
 		let mut last_byte_pos = 0;
 		let mut current_char_pos = 0;
@@ -239,7 +325,7 @@ impl Tokenizer {
 		let mut t = Vec::new();
 		for c in i.chars() {
 			let char_len = c.len_utf8() as Position;
-			let cr = w.character_role(c);
+			let cr = self.character_role(c);
 			// if the new character is not part of the current token,
 			if !matches!(cr, CharacterRole::PartOfCurrentToken)
 				|| matches!(previous_character_role, CharacterRole::IsolatedToken)
@@ -273,11 +359,130 @@ impl Tokenizer {
 				len: current_char_pos - last_char_pos,
 			});
 		}
-		Tokens {
-			i,
-			t,
+		t
+	}
+}
+
+/// Defines the tokenizers which split existing tokens into segments
+enum Segmenter {
+	/// Splits on the word boundaries defined by Unicode Standard Annex #29,
+	/// optionally keeping runs of CJK characters together
+	Word(bool),
+	/// Splits runs of Chinese, Japanese, and Korean characters into
+	/// overlapping bigrams
+	Cjk,
+}
+
+impl Segmenter {
+	/// Segments the part of the input referenced by a token, starting at
+	/// the given character position.
+	fn segment(
+		&self,
+		i: &str,
+		char_pos: Position,
+		bytes: (Position, Position),
+		t: &mut Vec<Token>,
+	) {
+		let s = &i[bytes.0 as usize..bytes.1 as usize];
+		match self {
+			Self::Word(keep_cjk) => Self::words(s, char_pos, bytes.0, *keep_cjk, t),
+			Self::Cjk => Self::bigrams(s, char_pos, bytes.0, t),
+		}
+	}
+
+	fn push(t: &mut Vec<Token>, chars: (Position, Position), bytes: (Position, Position)) {
+		t.push(Token::Ref {
+			chars: (chars.0, chars.0, chars.1),
+			bytes,
+			len: chars.1 - chars.0,
+		});
+	}
+
+	fn words(
+		s: &str,
+		mut char_pos: Position,
+		byte_pos: Position,
+		keep_cjk: bool,
+		t: &mut Vec<Token>,
+	) {
+		// A pending run of CJK characters, as its character and byte ranges
+		let mut run: Option<((Position, Position), (Position, Position))> = None;
+		for (b, w) in s.split_word_bound_indices() {
+			let len = w.chars().count() as Position;
+			let b = byte_pos + b as Position;
+			let chars = (char_pos, char_pos + len);
+			let bytes = (b, b + w.len() as Position);
+			char_pos += len;
+			if keep_cjk && w.chars().all(is_cjk) {
+				run = match run {
+					Some((c, b)) => Some(((c.0, chars.1), (b.0, bytes.1))),
+					None => Some((chars, bytes)),
+				};
+				continue;
+			}
+			if let Some((c, b)) = run.take() {
+				Self::push(t, c, b);
+			}
+			// Whitespace and punctuation are not words
+			if w.chars().any(char::is_alphanumeric) {
+				Self::push(t, chars, bytes);
+			}
+		}
+		if let Some((c, b)) = run {
+			Self::push(t, c, b);
 		}
 	}
+
+	fn bigrams(s: &str, char_pos: Position, byte_pos: Position, t: &mut Vec<Token>) {
+		// The character and byte positions of each character, followed by the end
+		let mut c: Vec<(Position, Position, bool)> = s
+			.char_indices()
+			.enumerate()
+			.map(|(n, (b, c))| (char_pos + n as Position, byte_pos + b as Position, is_cjk(c)))
+			.collect();
+		c.push((char_pos + c.len() as Position, byte_pos + s.len() as Position, false));
+		let mut start = 0;
+		while start < c.len() - 1 {
+			let cjk = c[start].2;
+			let mut end = start + 1;
+			while end < c.len() - 1 && c[end].2 == cjk {
+				end += 1;
+			}
+			if !cjk {
+				// Other characters are kept as a single token
+				let run = &s[(c[start].1 - byte_pos) as usize..(c[end].1 - byte_pos) as usize];
+				if run.chars().any(char::is_alphanumeric) {
+					Self::push(t, (c[start].0, c[end].0), (c[start].1, c[end].1));
+				}
+			} else if end - start == 1 {
+				// A single character is kept as a unigram
+				Self::push(t, (c[start].0, c[end].0), (c[start].1, c[end].1));
+			} else {
+				for n in start..end - 1 {
+					Self::push(t, (c[n].0, c[n + 2].0), (c[n].1, c[n + 2].1));
+				}
+			}
+			start = end;
+		}
+	}
+}
+
+/// Checks if a character belongs to the Han, Hiragana, Katakana, or Hangul
+/// scripts
+fn is_cjk(c: char) -> bool {
+	matches!(c,
+		'\u{1100}'..='\u{11FF}' // Hangul Jamo
+		| '\u{3040}'..='\u{309F}' // Hiragana
+		| '\u{30A0}'..='\u{30FF}' // Katakana
+		| '\u{3130}'..='\u{318F}' // Hangul Compatibility Jamo
+		| '\u{31F0}'..='\u{31FF}' // Katakana Phonetic Extensions
+		| '\u{3400}'..='\u{4DBF}' // CJK Unified Ideographs Extension A
+		| '\u{4E00}'..='\u{9FFF}' // CJK Unified Ideographs
+		| '\u{AC00}'..='\u{D7AF}' // Hangul Syllables
+		| '\u{F900}'..='\u{FAFF}' // CJK Compatibility Ideographs
+		| '\u{FF66}'..='\u{FF9F}' // Halfwidth Katakana
+		| '\u{20000}'..='\u{2FA1F}' // CJK Unified Ideographs Extensions B to F
+	)
 }
 
 struct Splitter {
@@ -349,6 +554,8 @@ impl Splitter {
 			SqlTokenizer::Camel => self.camel_role(cl),
 			SqlTokenizer::Class => self.class_role(cl),
 			SqlTokenizer::Punct => self.punct_role(cl),
+			// Segmenters are not based on character roles
+			SqlTokenizer::Word | SqlTokenizer::Cjk => CharacterRole::PartOfCurrentToken,
 		}
 	}
 
@@ -413,7 +620,8 @@ impl Splitter {
 
 #[cfg(test)]
 mod tests {
-	use crate::idx::ft::analyzer::tests::test_analyzer;
+	use crate::idx::ft::analyzer::tests::{test_analyzer, test_analyzer_tokens};
+	use crate::idx::ft::analyzer::tokenizer::Token;
 
 	#[tokio::test]
 	async fn test_tokenize_blank_class() {
@@ -485,4 +693,57 @@ static LANGUAGE: &str = "Rust";"#,
 		)
 		.await;
 	}
+
+	#[tokio::test]
+	async fn test_tokenize_word() {
+		test_analyzer(
+			"ANALYZER test TOKENIZERS word FILTERS lowercase",
+			"The quick (\"brown\") fox can't jump 32.3 feet, right?",
+			&["the", "quick", "brown", "fox", "can't", "jump", "32.3", "feet", "right"],
+		)
+		.await;
+	}
+
+	#[tokio::test]
+	async fn test_tokenize_cjk() {
+		test_analyzer("ANALYZER test TOKENIZERS cjk", "東京都", &["東京", "京都"]).await;
+		test_analyzer(
+			"ANALYZER test TOKENIZERS blank,cjk",
+			"東京 タワー 한국어 日",
+			&["東京", "タワ", "ワー", "한국", "국어", "日"],
+		)
+		.await;
+		test_analyzer(
+			"ANALYZER test TOKENIZERS word,cjk FILTERS lowercase",
+			"我爱SurrealDB数据库!",
+			&["我爱", "surrealdb", "数据", "据库"],
+		)
+		.await;
+	}
+
+	#[tokio::test]
+	async fn test_tokenize_cjk_tokens() {
+		test_analyzer_tokens(
+			"ANALYZER test TOKENIZERS word,cjk",
+			"é 東京都",
+			&[
+				Token::Ref {
+					chars: (0, 0, 1),
+					bytes: (0, 2),
+					len: 1,
+				},
+				Token::Ref {
+					chars: (2, 2, 4),
+					bytes: (3, 9),
+					len: 2,
+				},
+				Token::Ref {
+					chars: (3, 3, 5),
+					bytes: (6, 12),
+					len: 2,
+				},
+			],
+		)
+		.await;
+	}
 }
@@ -2,7 +2,7 @@ use std::cmp::Ordering;
 use std::collections::{HashMap, HashSet};
 use std::sync::Arc;
 
-use anyhow::Result;
+use anyhow::{Result, ensure};
 use reblessive::tree::Stk;
 use revision::revisioned;
 use roaring::RoaringTreemap;
@@ -24,22 +24,26 @@ use crate::catalog::{FullTextParams, Scoring};
 /// - Compaction of index data
 use crate::ctx::FrozenContext;
 use crate::dbs::Options;
+use crate::err::Error;
 use crate::expr::Idiom;
-use crate::expr::operator::BooleanOperator;
+use crate::expr::operator::{BooleanOperator, Fuzzy};
 use crate::idx::IndexKeyBase;
 use crate::idx::ft::analyzer::Analyzer;
 use crate::idx::ft::analyzer::filter::FilteringStage;
 use crate::idx::ft::analyzer::tokenizer::Tokens;
+use crate::idx::ft::fuzzy::{self, FuzzyTerm};
 use crate::idx::ft::highlighter::{HighlightParams, Highlighter, Offseter};
-use crate::idx::ft::offset::Offset;
-use crate::idx::ft::{DocLength, Score, TermFrequency};
+use crate::idx::ft::offset::{Offset, TermPosition};
+use crate::idx::ft::query::{self, Occur};
+use crate::idx::ft::{DocLength, Position, Score, TermFrequency};
 use crate::idx::planner::iterators::MatchesHitsIterator;
 use crate::idx::seqdocids::{DocId, SeqDocIds};
 use crate::idx::trees::store::IndexStores;
+use crate::key::index::td::TdRoot;
 use crate::key::index::tt::Tt;
 use crate::kvs::{Transaction, impl_kv_value_revisioned};
 use crate::val::{RecordId, Value};
-#[revisioned(revision = 1)]
+#[revisioned(revision = 2)]
 #[derive(Debug, Default, PartialEq)]
 /// Represents a term occurrence within a document
 pub(crate) struct TermDocument {
@@ -47,6 +51,11 @@ pub(crate) struct TermDocument {
 	f: TermFrequency,
 	/// The offsets of the term occurrences in the document
 	o: Vec<Offset>,
+	/// The positions of the term occurrences in the document, used by phrase
+	/// queries. Documents indexed before revision 2 have no positions until
+	/// the index is rebuilt.
+	#[revision(start = 2)]
+	p: Vec<TermPosition>,
 }
 
 impl_kv_value_revisioned!(TermDocument);
@@ -57,6 +66,7 @@ impl TermDocument {
 		Self {
 			f,
 			o,
+			p: Vec::new(),
 		}
 	}
 }
@@ -82,14 +92,64 @@ impl DocLengthAndCount {
 	}
 }
 
+/// A distinct term of a search query
+struct QueryTerm {
+	/// The term string
+	term: String,
+	/// The indexed terms matching this term. Without fuzzy matching, the only
+	/// variant is the term itself.
+	variants: Vec<TermVariant>,
+	/// The documents containing any of the variants
+	docs: Option<RoaringTreemap>,
+	/// Matches the variants of the term, when fuzzy matching is enabled
+	fuzzy: Option<FuzzyTerm>,
+	/// Indicates if the term only appears in prohibited clauses
+	prohibited: bool,
+}
+
+impl QueryTerm {
+	/// Checks if an analyzed token matches the term
+	fn matches(&self, token: &str) -> bool {
+		match &self.fuzzy {
+			Some(fuzzy) => fuzzy.matches(token),
+			None => self.term == token,
+		}
+	}
+}
+
+/// An indexed term matching a query term
+struct TermVariant {
+	/// The term string
+	term: String,
+	/// The length of the term in chars, used for highlighting
+	len: u32,
+	/// The number of documents containing the term
+	doc_count: u64,
+}
+
+/// A clause of a structured query, referencing its terms by index
+enum Clause {
+	/// A single term
+	Term(Occur, usize),
+	/// A phrase, with the position of each term in the query, and the slop
+	Phrase(Occur, Vec<(usize, Position)>, u32),
+}
+
+impl Clause {
+	fn occur(&self) -> Occur {
+		match self {
+			Self::Term(o, _) | Self::Phrase(o, _, _) => *o,
+		}
+	}
+}
+
 /// Represents the terms in a search query and their associated document sets
+#[derive(Clone)]
 pub(crate) struct QueryTerms {
-	/// The tokenized query terms
-	#[allow(dead_code)]
-	tokens: Tokens,
-	/// Document sets for each term (RoaringTreemap of document IDs)
-	#[allow(dead_code)]
-	docs: Vec<Option<RoaringTreemap>>,
+	/// The query terms, with their document sets
+	terms: Arc<[QueryTerm]>,
+	/// The clauses of a structured query, or `None` for a plain query
+	clauses: Option<Arc<[Clause]>>,
 	/// Indicates if any terms in the query are not found in the index
 	#[allow(dead_code)]
 	has_unknown_terms: bool,
@@ -97,23 +157,119 @@ pub(crate) struct QueryTerms {
 
 impl QueryTerms {
 	pub(crate) fn is_empty(&self) -> bool {
-		self.tokens.list().is_empty()
+		self.terms.iter().all(|t| t.prohibited)
+	}
+
+	/// Returns the terms which contribute to scoring and highlighting
+	fn scoring_terms(&self) -> impl Iterator<Item = &QueryTerm> {
+		self.terms.iter().filter(|t| !t.prohibited)
+	}
+
+	fn contains(&self, term: usize, doc_id: DocId) -> bool {
+		self.terms[term].docs.as_ref().is_some_and(|d| d.contains(doc_id))
+	}
+
+	fn has_phrase(&self) -> bool {
+		self.clauses.as_ref().is_some_and(|c| c.iter().any(|c| matches!(c, Clause::Phrase(..))))
+	}
+
+	/// Checks if a document matches the query.
+	///
+	/// A plain query matches any document containing one of its terms, while
+	/// the clauses of a structured query are fully evaluated, reading the term
+	/// positions of the document when the query contains a phrase.
+	async fn matches_doc(
+		&self,
+		ikb: &IndexKeyBase,
+		tx: &Transaction,
+		doc_id: DocId,
+		bo: BooleanOperator,
+	) -> Result<bool> {
+		let Some(clauses) = &self.clauses else {
+			return Ok((0..self.terms.len()).any(|t| self.contains(t, doc_id)));
+		};
+		let mut results = Vec::with_capacity(clauses.len());
+		for c in clauses.iter() {
+			let matched = match c {
+				Clause::Term(_, t) => self.contains(*t, doc_id),
+				Clause::Phrase(_, terms, slop) => {
+					self.phrase_matches_doc(ikb, tx, doc_id, terms, *slop).await?
+				}
+			};
+			results.push((c.occur(), matched));
+		}
+		Ok(query::combine(results.into_iter(), bo))
 	}
 
-	pub(crate) fn contains_doc(&self, doc_id: DocId) -> bool {
-		for d in self.docs.iter().flatten() {
-			if d.contains(doc_id) {
-				return true;
+	async fn phrase_matches_doc(
+		&self,
+		ikb: &IndexKeyBase,
+		tx: &Transaction,
+		doc_id: DocId,
+		terms: &[(usize, Position)],
+		slop: u32,
+	) -> Result<bool> {
+		if !terms.iter().all(|(t, _)| self.contains(*t, doc_id)) {
+			return Ok(false);
+		}
+		let mut positions = Vec::with_capacity(terms.len());
+		for (t, qpos) in terms {
+			let mut p = Vec::new();
+			for v in &self.terms[*t].variants {
+				let key = ikb.new_td(&v.term, doc_id);
+				let td: Option<TermDocument> = tx.get(&key, None).await?;
+				if let Some(td) = td {
+					// Documents indexed before positions were stored have none
+					ensure!(td.f == 0 || !td.p.is_empty(), Error::FtIndexWithoutPositions);
+					p.extend(td.p);
+				}
+			}
+			if p.is_empty() {
+				return Ok(false);
+			}
+			positions.push((*qpos, p));
+		}
+		Ok(query::phrase_matches(&positions, slop))
+	}
+
+	/// Checks if the analyzed tokens of a value match the query
+	fn matches_tokens(&self, tks: &[Tokens], bo: BooleanOperator) -> Result<bool> {
+		let Some(clauses) = &self.clauses else {
+			return match bo {
+				BooleanOperator::And => self.matches_and(tks),
+				BooleanOperator::Or => self.matches_or(tks),
+			};
+		};
+		// Collect the positions of every query term within the value
+		let mut positions: Vec<Vec<TermPosition>> = vec![Vec::new(); self.terms.len()];
+		for (i, tokens) in tks.iter().enumerate() {
+			for (tk, pos) in tokens.list().iter().zip(tokens.positions()) {
+				let s = tokens.get_token_string(tk)?;
+				for (t, qt) in self.terms.iter().enumerate() {
+					if qt.matches(s) {
+						positions[t].push(TermPosition::new(i as u32, pos));
+					}
+				}
 			}
 		}
-		false
+		let results = clauses.iter().map(|c| {
+			let matched = match c {
+				Clause::Term(_, t) => !positions[*t].is_empty(),
+				Clause::Phrase(_, terms, slop) => {
+					let terms: Vec<_> =
+						terms.iter().map(|(t, qpos)| (*qpos, positions[*t].clone())).collect();
+					query::phrase_matches(&terms, *slop)
+				}
+			};
+			(c.occur(), matched)
+		});
+		Ok(query::combine(results, bo))
 	}
 
-	pub(in crate::idx::ft) fn matches_or(&self, tks: &[Tokens]) -> Result<bool> {
-		for t in self.tokens.list() {
-			let t = self.tokens.get_token_string(t)?;
+	fn matches_or(&self, tks: &[Tokens]) -> Result<bool> {
+		for t in self.terms.iter() {
 			for tokens in tks {
-				if tokens.try_contains(t)? {
+				if Self::contains_token(t, tokens)? {
 					return Ok(true);
 				}
 			}
@@ -121,12 +277,11 @@ impl QueryTerms {
 		Ok(false)
 	}
 
-	pub(in crate::idx::ft) fn matches_and(&self, tks: &[Tokens]) -> Result<bool> {
-		for t in self.tokens.list() {
-			let t = self.tokens.get_token_string(t)?;
+	fn matches_and(&self, tks: &[Tokens]) -> Result<bool> {
+		for t in self.terms.iter() {
 			let mut found = false;
 			for tokens in tks {
-				if tokens.try_contains(t)? {
+				if Self::contains_token(t, tokens)? {
 					found = true;
 					break;
 				}
@@ -137,6 +292,18 @@ impl QueryTerms {
 		}
 		Ok(true)
 	}
+
+	fn contains_token(t: &QueryTerm, tokens: &Tokens) -> Result<bool> {
+		if t.fuzzy.is_none() {
+			return tokens.try_contains(&t.term);
+		}
+		for tk in tokens.list() {
+			if t.matches(tokens.get_token_string(tk)?) {
+				return Ok(true);
+			}
+		}
+		Ok(false)
+	}
 }
 
 #[derive(Clone)]
@@ -331,10 +498,11 @@ impl FullTextIndex {
 	) -> Result<DocLength> {
 		let (dl, offsets) = Analyzer::extract_offsets(&tokens)?;
 		let mut td = TermDocument::default();
-		for (t, o) in offsets {
+		for (t, (o, p)) in offsets {
 			let key = self.ikb.new_td(t, id);
 			td.f = o.len() as TermFrequency;
 			td.o = o;
+			td.p = p;
 			tx.set(&key, &td, None).await?;
 			self.set_tt(tx, t, id, nid, true).await?;
 		}
@@ -350,9 +518,10 @@ impl FullTextIndex {
 	) -> Result<DocLength> {
 		let (dl, tf) = Analyzer::extract_frequencies(&tokens)?;
 		let mut td = TermDocument::default();
-		for (t, f) in tf {
+		for (t, (f, p)) in tf {
 			let key = self.ikb.new_td(t, id);
 			td.f = f;
+			td.p = p;
 			tx.set(&key, &td, None).await?;
 			self.set_tt(tx, t, id, nid, true).await?;
 		}
@@ -376,31 +545,101 @@ impl FullTextIndex {
 	/// Tokenizes the query string, then retrieves the document bitmaps for each
 	/// unique term. The compacted bitmap fetches are batched via `tx.getm()` to
 	/// reduce KV round trips (one batch instead of N sequential gets).
+	///
+	/// A query string using phrases, required or prohibited terms is parsed
+	/// into clauses, each clause being analyzed separately.
+	///
+	/// With fuzzy matching, each term is expanded to the indexed terms within
+	/// the maximum edit distance.
 	pub(crate) async fn extract_querying_terms(
 		&self,
 		stk: &mut Stk,
 		ctx: &FrozenContext,
 		opt: &Options,
 		query_string: String,
+		fuzzy: Option<Fuzzy>,
 	) -> Result<QueryTerms> {
-		let tokens = self
-			.analyzer
-			.generate_tokens(stk, ctx, opt, FilteringStage::Querying, query_string)
-			.await?;
-
-		let mut unique_terms: Vec<&str> = Vec::new();
-		let mut unique_tokens = HashSet::new();
-		for token in tokens.list() {
-			if unique_tokens.insert(token) {
-				unique_terms.push(tokens.get_token_string(token)?);
+		let mut terms: Vec<(String, u32, bool)> = Vec::new();
+		let clauses = if let Some(raw_clauses) = query::parse(&query_string) {
+			let mut clauses = Vec::with_capacity(raw_clauses.len());
+			let mut indexes: HashMap<String, usize> = HashMap::new();
+			for raw in raw_clauses {
+				let tokens = self
+					.analyzer
+					.generate_tokens(stk, ctx, opt, FilteringStage::Querying, raw.text.to_string())
+					.await?;
+				let prohibited = raw.occur == Occur::Prohibited;
+				let mut phrase = Vec::new();
+				for (tk, pos) in tokens.list().iter().zip(tokens.positions()) {
+					let s = tokens.get_token_string(tk)?;
+					let idx = match indexes.get(s) {
+						Some(idx) => *idx,
+						None => {
+							indexes.insert(s.to_string(), terms.len());
+							terms.push((s.to_string(), tk.get_char_len(), prohibited));
+							terms.len() - 1
+						}
+					};
+					if !prohibited {
+						terms[idx].2 = false;
+					}
+					match raw.phrase {
+						Some(_) => phrase.push((idx, pos)),
+						None => clauses.push(Clause::Term(raw.occur, idx)),
+					}
+				}
+				if let Some(slop) = raw.phrase {
+					match phrase.len() {
+						0 => {}
+						1 => clauses.push(Clause::Term(raw.occur, phrase[0].0)),
+						_ => clauses.push(Clause::Phrase(raw.occur, phrase, slop)),
+					}
+				}
 			}
-		}
+			Some(clauses.into())
+		} else {
+			let tokens = self
+				.analyzer
+				.generate_tokens(stk, ctx, opt, FilteringStage::Querying, query_string)
+				.await?;
+			let mut unique_tokens = HashSet::new();
+			for token in tokens.list() {
+				if unique_tokens.insert(token) {
+					terms.push((
+						tokens.get_token_string(token)?.to_string(),
+						token.get_char_len(),
+						false,
+					));
+				}
+			}
+			None
+		};
 
 		let tx = ctx.tx();
 
+		// Expand the terms to the indexed terms matching them
+		let mut fuzzy_terms = Vec::with_capacity(terms.len());
+		let mut variants: Vec<(usize, String, u32)> = Vec::with_capacity(terms.len());
+		for (i, (term, len, _)) in terms.iter().enumerate() {
+			match fuzzy {
+				Some(fuzzy) => {
+					let ft = FuzzyTerm::new(term, fuzzy);
+					for v in self.expand_fuzzy_term(&tx, &ft).await? {
+						let len = v.chars().count() as u32;
+						variants.push((i, v, len));
+					}
+					fuzzy_terms.push(Some(ft));
+				}
+				None => {
+					variants.push((i, term.clone(), *len));
+					fuzzy_terms.push(None);
+				}
+			}
+		}
+
 		// Phase 1: Collect deltas for each term (sequential range scans)
-		let mut all_deltas: Vec<HashMap<DocId, i64>> = Vec::with_capacity(unique_terms.len());
-		for term in &unique_terms {
+		let mut all_deltas: Vec<HashMap<DocId, i64>> = Vec::with_capacity(variants.len());
+		for (_, term, _) in &variants {
 			let (beg, end) = self.ikb.new_tt_term_range(term)?;
 			let mut deltas: HashMap<DocId, i64> = HashMap::new();
 			for k in tx.keys(beg..end, u32::MAX, 0, None).await? {
@@ -417,13 +656,15 @@ impl FullTextIndex {
 
 		// Phase 2: Batch-fetch compacted bitmaps for all terms at once
 		let bitmap_keys: Vec<_> =
-			unique_terms.iter().map(|term| self.ikb.new_td_root(term)).collect();
+			variants.iter().map(|(_, term, _)| self.ikb.new_td_root(term)).collect();
 		let bitmaps: Vec<Option<RoaringTreemap>> = tx.getm(bitmap_keys, None).await?;
 
-		// Phase 3: Merge deltas into bitmaps
-		let mut docs = Vec::with_capacity(unique_terms.len());
-		let mut has_unknown_terms = false;
-		for (bitmap, deltas) in bitmaps.into_iter().zip(all_deltas.iter()) {
+		// Phase 3: Merge deltas into bitmaps, and the variants into their term
+		let mut term_variants: Vec<Vec<TermVariant>> = terms.iter().map(|_| Vec::new()).collect();
+		let mut term_docs = vec![RoaringTreemap::new(); terms.len()];
+		for (((i, term, len), bitmap), deltas) in
+			variants.into_iter().zip(bitmaps).zip(all_deltas.iter())
+		{
 			let mut doc_set = bitmap.unwrap_or_default();
 			for (doc_id, delta) in deltas {
 				match 0.cmp(delta) {
@@ -436,23 +677,89 @@ impl FullTextIndex {
 					Ordering::Equal => {}
 				}
 			}
-			if doc_set.is_empty() {
+			term_variants[i].push(TermVariant {
+				term,
+				len,
+				doc_count: doc_set.len(),
+			});
+			term_docs[i] |= doc_set;
+		}
+
+		let mut query_terms = Vec::with_capacity(terms.len());
+		let mut has_unknown_terms = false;
+		for ((((term, _, prohibited), variants), doc_set), fuzzy) in
+			terms.into_iter().zip(term_variants).zip(term_docs).zip(fuzzy_terms)
+		{
+			let docs = if doc_set.is_empty() {
 				if !has_unknown_terms {
 					has_unknown_terms = true;
 				}
-				docs.push(None);
+				None
 			} else {
-				docs.push(Some(doc_set));
-			}
+				Some(doc_set)
+			};
+			query_terms.push(QueryTerm {
+				term,
+				variants,
+				docs,
+				fuzzy,
+				prohibited,
+			});
 		}
 
 		Ok(QueryTerms {
-			tokens,
-			docs,
+			terms: query_terms.into(),
+			clauses,
 			has_unknown_terms,
 		})
 	}
 
+	/// Expands a fuzzy query term to the matching terms of the index.
+	///
+	/// The term dictionary is walked in key order, starting from the prefix of
+	/// the term, and fetched in batches. When a term can't match, the whole
+	/// range of terms sharing its non-matching prefix is skipped. When more
+	/// terms match than can be expanded to, the closest terms are kept, so
+	/// the exact term is always included.
+	async fn expand_fuzzy_term(&self, tx: &Transaction, ft: &FuzzyTerm) -> Result<Vec<String>> {
+		let (mut beg, end) = self.ikb.new_td_prefix_range(ft.prefix())?;
+		let mut res = Vec::new();
+		'scan: while beg < end {
+			let keys = tx.keys(beg.clone()..end.clone(), fuzzy::SCAN_BATCH_SIZE, 0, None).await?;
+			let Some(last) = keys.last() else {
+				break;
+			};
+			// Continue after the batch unless a skip moves past it
+			let mut next = last.clone();
+			next.push(0x00);
+			for key in keys {
+				// Keys before the last skip belong to a skipped range
+				if key < beg {
+					continue;
+				}
+				let term = TdRoot::decode_term(&key)?;
+				beg = match ft.walk(&term) {
+					Ok(distance) => {
+						if let Some(distance) = distance {
+							res.push((distance, term.clone()));
+						}
+						self.ikb.new_td_term_end(&term)?
+					}
+					// No term starting with this prefix can match
+					Err(len) => self.ikb.new_td_prefix_range(&term[..len])?.1,
+				};
+				if beg >= end {
+					break 'scan;
+				}
+			}
+			beg = beg.max(next);
+		}
+		// Keep the closest terms
+		res.sort_unstable();
+		res.truncate(fuzzy::MAX_EXPANSIONS);
+		Ok(res.into_iter().map(|(_, term)| term).collect())
+	}
+
 	pub(in crate::idx) async fn matches_value(
 		&self,
 		stk: &mut Stk,
@@ -464,10 +771,18 @@ impl FullTextIndex {
 	) -> Result<bool> {
 		let mut tks = vec![];
 		self.analyzer.analyze_value(stk, ctx, opt, val, FilteringStage::Indexing, &mut tks).await?;
-		match bo {
-			BooleanOperator::And => qt.matches_and(&tks),
-			BooleanOperator::Or => qt.matches_or(&tks),
-		}
+		qt.matches_tokens(&tks, bo)
+	}
+
+	/// Checks if an indexed document matches the query terms
+	pub(crate) async fn matches_doc(
+		&self,
+		tx: &Transaction,
+		qt: &QueryTerms,
+		doc_id: DocId,
+		bo: BooleanOperator,
+	) -> Result<bool> {
+		qt.matches_doc(&self.ikb, tx, doc_id, bo).await
 	}
 
 	async fn append_term_docs_delta(
@@ -571,41 +886,88 @@ impl FullTextIndex {
 	///
 	/// This method creates an iterator over the documents that match all query
 	/// terms. It returns None if any term has no matching documents.
+	///
+	/// For a structured query, the candidates are computed from the bitmaps of
+	/// the clauses, and documents are verified against the phrases while
+	/// iterating.
 	pub(crate) fn new_hits_iterator(
 		&self,
 		qt: &QueryTerms,
 		bo: BooleanOperator,
 	) -> Option<FullTextHitsIterator> {
 		// Execute the operation depending on the operator
-		let hits = match bo {
-			BooleanOperator::And => Self::intersection_operation(&qt.docs),
-			BooleanOperator::Or => Self::union_operation(&qt.docs),
+		let hits = match &qt.clauses {
+			None => {
+				let docs = qt.terms.iter().map(|t| t.docs.as_ref());
+				match bo {
+					BooleanOperator::And => Self::intersection_operation(docs),
+					BooleanOperator::Or => Self::union_operation(docs),
+				}
+			}
+			Some(clauses) => Self::clauses_operation(qt, clauses, bo),
 		};
 
 		// Create and return an iterator if we have matching documents
 		if let Some(hits) = hits
 			&& !hits.is_empty()
 		{
-			return Some(FullTextHitsIterator::new(self.ikb.clone(), hits));
+			let verify = qt.has_phrase().then(|| (qt.clone(), bo));
+			return Some(FullTextHitsIterator::new(self.ikb.clone(), hits, verify));
 		}
 
 		// No documents match the terms
 		None
 	}
 
-	fn intersection_operation(docs: &[Option<RoaringTreemap>]) -> Option<RoaringTreemap> {
+	/// Computes the candidate documents of a structured query.
+	///
+	/// The candidates of a phrase are the documents containing all its terms,
+	/// the positions being checked later by the hits iterator.
+	fn clauses_operation(
+		qt: &QueryTerms,
+		clauses: &[Clause],
+		bo: BooleanOperator,
+	) -> Option<RoaringTreemap> {
+		let clause_docs = |c: &Clause| match c {
+			Clause::Term(_, t) => qt.terms[*t].docs.clone(),
+			Clause::Phrase(_, terms, _) => {
+				Self::intersection_operation(terms.iter().map(|(t, _)| qt.terms[*t].docs.as_ref()))
+			}
+		};
+		let (required, optional): (Vec<_>, Vec<_>) = clauses
+			.iter()
+			.filter(|c| c.occur() != Occur::Prohibited)
+			.partition(|c| c.occur().is_required(bo));
+		let mut hits = if required.is_empty() {
+			// At least one optional clause must match
+			let docs: Vec<_> = optional.into_iter().map(clause_docs).collect();
+			Self::union_operation(docs.iter().map(Option::as_ref))?
+		} else {
+			// Every required clause must match
+			let docs: Vec<_> = required.into_iter().map(clause_docs).collect();
+			Self::intersection_operation(docs.iter().map(Option::as_ref))?
+		};
+		// Remove the documents containing a prohibited term
+		for c in clauses {
+			if let Clause::Term(Occur::Prohibited, t) = c
+				&& let Some(docs) = &qt.terms[*t].docs
+			{
+				hits -= docs;
+			}
+		}
+		Some(hits)
+	}
+
+	fn intersection_operation<'a>(
+		docs: impl ExactSizeIterator<Item = Option<&'a RoaringTreemap>>,
+	) -> Option<RoaringTreemap> {
 		// Early return for empty input
-		if docs.is_empty() {
+		if docs.len() == 0 {
 			return None;
 		}
 
-		// Collect only the "Some" variants
-		let mut valid_docs: Vec<&RoaringTreemap> = docs.iter().flatten().collect();
-
 		// If any term has no documents, the intersection is empty
-		if docs.len() != valid_docs.len() {
-			return None;
-		}
+		let mut valid_docs: Vec<&RoaringTreemap> = docs.collect::<Option<_>>()?;
 
 		// Sort by cardinality - intersecting with smaller sets first is more efficient
 		valid_docs.sort_by_key(|bitmap| bitmap.len());
@@ -634,9 +996,11 @@ impl FullTextIndex {
 		}
 	}
 
-	fn union_operation(docs: &[Option<RoaringTreemap>]) -> Option<RoaringTreemap> {
+	fn union_operation<'a>(
+		docs: impl Iterator<Item = Option<&'a RoaringTreemap>>,
+	) -> Option<RoaringTreemap> {
 		// Convert docs to an iterator
-		let mut docs = docs.iter().flatten();
+		let mut docs = docs.flatten();
 
 		// Start with the first set
 		if let Some(mut result) = docs.next().cloned() {
@@ -757,11 +1121,9 @@ impl FullTextIndex {
 		let doc_id = self.get_doc_id(tx, thg).await?;
 		if let Some(doc_id) = doc_id {
 			let mut hl = Highlighter::new(hlp, idiom, doc);
-			for tk in qt.tokens.list() {
-				if let Some(td) =
-					self.get_term_document(tx, doc_id, qt.tokens.get_token_string(tk)?).await?
-				{
-					hl.highlight(tk.get_char_len(), td.o);
+			for v in qt.scoring_terms().flat_map(|t| &t.variants) {
+				if let Some(td) = self.get_term_document(tx, doc_id, &v.term).await? {
+					hl.highlight(v.len, td.o);
 				}
 			}
 			return hl.try_into();
@@ -789,11 +1151,10 @@ impl FullTextIndex {
 		let doc_id = self.get_doc_id(tx, thg).await?;
 		if let Some(doc_id) = doc_id {
 			let mut or = Offseter::new(partial);
-			for tk in qt.tokens.list() {
-				let term = qt.tokens.get_token_string(tk)?;
-				let o = self.get_term_document(tx, doc_id, term).await?;
+			for v in qt.scoring_terms().flat_map(|t| &t.variants) {
+				let o = self.get_term_document(tx, doc_id, &v.term).await?;
 				if let Some(o) = o {
-					or.highlight(tk.get_char_len(), o.o);
+					or.highlight(v.len, o.o);
 				}
 			}
 			return Ok(or.into());
@@ -809,6 +1170,9 @@ pub(crate) struct FullTextHitsIterator {
 	ikb: IndexKeyBase,
 	/// Iterator over the document IDs in the search results
 	iter: IntoIter,
+	/// The query the candidate documents are verified against, when the hits
+	/// are not exact (i.e. the query contains phrases)
+	verify: Option<(QueryTerms, BooleanOperator)>,
 }
 
 impl FullTextHitsIterator {
@@ -816,10 +1180,15 @@ impl FullTextHitsIterator {
 	///
 	/// This method initializes an iterator with the index key base and a bitmap
 	/// of matching document IDs.
-	fn new(ikb: IndexKeyBase, hits: RoaringTreemap) -> Self {
+	fn new(
+		ikb: IndexKeyBase,
+		hits: RoaringTreemap,
+		verify: Option<(QueryTerms, BooleanOperator)>,
+	) -> Self {
 		Self {
 			ikb,
 			iter: hits.into_iter(),
+			verify,
 		}
 	}
 }
@@ -840,6 +1209,11 @@ impl MatchesHitsIterator for FullTextHitsIterator {
 	/// it to a Thing. It returns None when there are no more hits.
 	async fn next(&mut self, tx: &Transaction) -> Result<Option<(RecordId, DocId)>> {
 		for doc_id in self.iter.by_ref() {
+			if let Some((qt, bo)) = &self.verify
+				&& !qt.matches_doc(&self.ikb, tx, doc_id, *bo).await?
+			{
+				continue;
+			}
 			if let Some(key) = SeqDocIds::get_id(&self.ikb, tx, doc_id).await? {
 				let rid = RecordId {
 					table: self.ikb.table().clone(),
@@ -886,7 +1260,7 @@ impl Scorer {
 	///
 	/// This method computes the sum of BM25 scores for all matching terms in
 	/// the document. The score represents the relevance of the document to the
-	/// query.
+	/// query. A fuzzy term scores as its best matching variant.
 	pub(crate) async fn score(
 		&self,
 		fti: &FullTextIndex,
@@ -895,18 +1269,21 @@ impl Scorer {
 		doc_id: DocId,
 	) -> Result<Score> {
 		let mut sc = 0.0;
-		let tl = qt.tokens.list();
 		let doc_length = fti.get_doc_length(tx, doc_id).await?.unwrap_or(0) as f64;
-		for (i, d) in qt.docs.iter().enumerate() {
-			if let Some(docs) = d
+		for t in qt.scoring_terms() {
+			if let Some(docs) = &t.docs
 				&& docs.contains(doc_id)
-				&& let Some(token) = tl.get(i)
 			{
-				let term = qt.tokens.get_token_string(token)?;
-				let td = fti.get_term_document(tx, doc_id, term).await?;
-				if let Some(td) = td {
-					sc += self.compute_bm25_score(td.f as f64, docs.len() as f64, doc_length)
+				let mut best: f64 = 0.0;
+				for v in &t.variants {
+					let td = fti.get_term_document(tx, doc_id, &v.term).await?;
+					if let Some(td) = td {
+						let score =
+							self.compute_bm25_score(td.f as f64, v.doc_count as f64, doc_length);
+						best = best.max(score);
+					}
 				}
+				sc += best;
 			}
 		}
 		Ok(sc as f32)
@@ -974,7 +1351,7 @@ mod tests {
 	use crate::dbs::Options;
 	use crate::expr::statements::DefineAnalyzerStatement;
 	use crate::idx::IndexKeyBase;
-	use crate::idx::ft::offset::Offset;
+	use crate::idx::ft::offset::{Offset, TermPosition};
 	use crate::idx::index::IndexOperation;
 	use crate::kvs::LockType::*;
 	use crate::kvs::{Datastore, Transaction, TransactionType};
@@ -1163,6 +1540,13 @@ mod tests {
 							end: 62,
 						},
 					],
+					p: vec![
+						TermPosition::new(2, 6),
+						TermPosition::new(3, 7),
+						TermPosition::new(16, 1),
+						TermPosition::new(18, 2),
+						TermPosition::new(19, 12),
+					],
 				}
 			};
 			for doc_id in &doc_ids {
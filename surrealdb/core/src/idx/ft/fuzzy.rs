@@ -0,0 +1,151 @@
+//! Typo-tolerant matching of full-text query terms.
+//!
+//! A query term is expanded to the indexed terms within a maximum edit
+//! distance. The term dictionary of the index is walked in key order, using a
+//! Levenshtein automaton to skip every range of terms sharing a prefix which
+//! can't lead to a match.
+
+use crate::expr::operator::Fuzzy;
+
+/// The maximum number of indexed terms a query term is expanded to. When
+/// more terms match, the closest terms are kept.
+pub(in crate::idx::ft) const MAX_EXPANSIONS: usize = 50;
+
+/// The number of keys fetched at once while walking the term dictionary
+pub(in crate::idx::ft) const SCAN_BATCH_SIZE: u32 = 100;
+
+/// Matches the strings within a maximum edit distance of a query term, which
+/// start with the same prefix
+pub(in crate::idx::ft) struct FuzzyTerm {
+	/// The prefix which must match exactly
+	prefix: String,
+	automaton: LevenshteinAutomaton,
+}
+
+impl FuzzyTerm {
+	pub(in crate::idx::ft) fn new(term: &str, fuzzy: Fuzzy) -> Self {
+		Self {
+			prefix: term.chars().take(fuzzy.prefix as usize).collect(),
+			automaton: LevenshteinAutomaton::new(term, fuzzy.distance),
+		}
+	}
+
+	pub(in crate::idx::ft) fn prefix(&self) -> &str {
+		&self.prefix
+	}
+
+	/// See [`LevenshteinAutomaton::walk`]
+	pub(in crate::idx::ft) fn walk(&self, s: &str) -> Result<Option<u32>, usize> {
+		self.automaton.walk(s)
+	}
+
+	pub(in crate::idx::ft) fn matches(&self, s: &str) -> bool {
+		s.starts_with(&self.prefix) && matches!(self.walk(s), Ok(Some(_)))
+	}
+}
+
+/// A Levenshtein automaton, recognising the strings within a maximum edit
+/// distance of a query term.
+///
+/// The states are the rows of the Wagner-Fischer matrix, which are computed
+/// incrementally as each character of a candidate string is read.
+struct LevenshteinAutomaton {
+	query: Vec<char>,
+	distance: u32,
+}
+
+type State = Vec<u32>;
+
+impl LevenshteinAutomaton {
+	fn new(query: &str, distance: u8) -> Self {
+		Self {
+			query: query.chars().collect(),
+			distance: distance as u32,
+		}
+	}
+
+	/// The state before any character is read
+	fn start(&self) -> State {
+		(0..=self.query.len() as u32).collect()
+	}
+
+	/// Computes the state following the given character
+	fn step(&self, state: &State, c: char) -> State {
+		// Values above the distance can't match anymore
+		let max = self.distance + 1;
+		let mut next = Vec::with_capacity(state.len());
+		next.push((state[0] + 1).min(max));
+		for (i, q) in self.query.iter().enumerate() {
+			let cost = if *q == c {
+				0
+			} else {
+				1
+			};
+			let v = (next[i] + 1).min(state[i] + cost).min(state[i + 1] + 1);
+			next.push(v.min(max));
+		}
+		next
+	}
+
+	/// Returns the edit distance of the string read so far, if it matches
+	/// the query term
+	fn distance(&self, state: &State) -> Option<u32> {
+		state.last().copied().filter(|d| *d <= self.distance)
+	}
+
+	/// Checks if any string starting with the string read so far can match
+	fn can_match(&self, state: &State) -> bool {
+		state.iter().any(|d| *d <= self.distance)
+	}
+
+	/// Walks the automaton along a candidate string.
+	///
+	/// Returns `Ok` with the edit distance of a matching string, or `Ok(None)`
+	/// if the string does not match, once the whole string has been read.
+	/// Otherwise returns `Err(len)` with the length in bytes of the shortest
+	/// prefix of the string that no matching string starts with.
+	fn walk(&self, s: &str) -> Result<Option<u32>, usize> {
+		let mut state = self.start();
+		for (i, c) in s.char_indices() {
+			state = self.step(&state, c);
+			if !self.can_match(&state) {
+				return Err(i + c.len_utf8());
+			}
+		}
+		Ok(self.distance(&state))
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::{FuzzyTerm, LevenshteinAutomaton};
+	use crate::expr::operator::Fuzzy;
+
+	#[test]
+	fn walk() {
+		let a = LevenshteinAutomaton::new("hello", 1);
+		assert_eq!(a.walk("hello"), Ok(Some(0)));
+		assert_eq!(a.walk("helo"), Ok(Some(1)));
+		assert_eq!(a.walk("hallo"), Ok(Some(1)));
+		assert_eq!(a.walk("helloo"), Ok(Some(1)));
+		assert_eq!(a.walk("hel"), Ok(None));
+		assert_eq!(a.walk("hxxlo"), Err(3));
+		assert_eq!(a.walk("world"), Err(2));
+		let a = LevenshteinAutomaton::new("東京", 1);
+		assert_eq!(a.walk("東都"), Ok(Some(1)));
+		assert_eq!(a.walk("大阪"), Err(6));
+	}
+
+	#[test]
+	fn prefix() {
+		let t = FuzzyTerm::new(
+			"hello",
+			Fuzzy {
+				distance: 1,
+				prefix: 1,
+			},
+		);
+		assert!(t.matches("hallo"));
+		assert!(!t.matches("jello"));
+	}
+}
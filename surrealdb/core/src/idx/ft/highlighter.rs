@@ -100,9 +100,17 @@ impl TryFrom<Highlighter> for Value {
 					Ok(())
 				};
 
+				// Overlapping offsets, as produced by n-grams, are merged
+				let mut merged: Vec<(Position, Position)> = Vec::with_capacity(m.len());
 				for (s, e) in m {
-					append(*s, &hl.prefix)?;
-					append(*e, &hl.suffix)?;
+					match merged.last_mut() {
+						Some(last) if *s < last.1 => last.1 = last.1.max(*e),
+						_ => merged.push((*s, *e)),
+					}
+				}
+				for (s, e) in merged {
+					append(s, &hl.prefix)?;
+					append(e, &hl.suffix)?;
 				}
 
 				let s: String = v.iter().collect();
@@ -24,3 +24,22 @@ impl Offset {
 		}
 	}
 }
+
+/// The position of a term within the indexed content
+#[revisioned(revision = 1)]
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub(crate) struct TermPosition {
+	/// The index of the value the term belongs to
+	pub(super) index: u32,
+	/// The position of the original token within the value
+	pub(super) pos: Position,
+}
+
+impl TermPosition {
+	pub(crate) fn new(index: u32, pos: Position) -> Self {
+		Self {
+			index,
+			pos,
+		}
+	}
+}
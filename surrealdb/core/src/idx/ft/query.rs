@@ -0,0 +1,235 @@
+//! Structured full-text queries.
+//!
+//! Besides plain terms, the query string of a `MATCHES` operator can contain:
+//! - `"new york"`, a phrase, whose terms must appear in order and next to each other.
+//! - `"new york"~3`, a proximity phrase, whose terms must appear in order, with at most 3 other
+//!   terms between them in total.
+//! - `+term` or `+"a phrase"`, a clause which is required, whatever the boolean operator of the
+//!   `MATCHES` operator is.
+//! - `-term` or `-"a phrase"`, a clause which must not match.
+//!
+//! A query which contains none of these constructs is analyzed as a whole,
+//! and matched as a plain list of terms.
+
+use crate::expr::operator::BooleanOperator;
+use crate::idx::ft::Position;
+use crate::idx::ft::offset::TermPosition;
+
+/// Defines how a clause contributes to a match
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub(in crate::idx::ft) enum Occur {
+	/// The clause follows the boolean operator of the query
+	Default,
+	/// The clause must match
+	Required,
+	/// The clause must not match
+	Prohibited,
+}
+
+impl Occur {
+	/// Checks if the clause must match, given the boolean operator
+	pub(in crate::idx::ft) fn is_required(&self, bo: BooleanOperator) -> bool {
+		match self {
+			Self::Default => matches!(bo, BooleanOperator::And),
+			Self::Required => true,
+			Self::Prohibited => false,
+		}
+	}
+}
+
+/// A clause of a query string, before it is analyzed
+#[derive(Debug, Eq, PartialEq)]
+pub(in crate::idx::ft) struct RawClause<'a> {
+	pub(in crate::idx::ft) occur: Occur,
+	pub(in crate::idx::ft) text: &'a str,
+	/// The slop of a phrase, or `None` if the clause is a term
+	pub(in crate::idx::ft) phrase: Option<u32>,
+}
+
+/// Parses a query string into clauses, returning `None` if the query string
+/// does not use any phrase, required, or prohibited clause.
+pub(in crate::idx::ft) fn parse(query: &str) -> Option<Vec<RawClause<'_>>> {
+	let mut res = Vec::new();
+	let mut structured = false;
+	let mut rest = query;
+	loop {
+		rest = rest.trim_start();
+		if rest.is_empty() {
+			break;
+		}
+		// A modifier must be directly followed by a term or a phrase, and a
+		// minus sign followed by a digit is part of a negative number
+		let mut occur = Occur::Default;
+		let mut chars = rest.chars();
+		if let (Some(c @ ('+' | '-')), Some(n)) = (chars.next(), chars.next())
+			&& !n.is_whitespace()
+			&& !(c == '-' && n.is_ascii_digit())
+		{
+			occur = if c == '+' {
+				Occur::Required
+			} else {
+				Occur::Prohibited
+			};
+			rest = &rest[1..];
+		}
+		if let Some(quoted) = rest.strip_prefix('"') {
+			// An unterminated phrase extends to the end of the query
+			let (text, after) = match quoted.find('"') {
+				Some(i) => (&quoted[..i], &quoted[i + 1..]),
+				None => (quoted, ""),
+			};
+			rest = after;
+			let mut slop = 0;
+			if let Some(after) = rest.strip_prefix('~') {
+				let digits = after.bytes().take_while(u8::is_ascii_digit).count();
+				if let Ok(v) = after[..digits].parse::<u32>() {
+					slop = v;
+				}
+				rest = &after[digits..];
+			}
+			res.push(RawClause {
+				occur,
+				text,
+				phrase: Some(slop),
+			});
+			structured = true;
+		} else {
+			let end = rest.find(char::is_whitespace).unwrap_or(rest.len());
+			res.push(RawClause {
+				occur,
+				text: &rest[..end],
+				phrase: None,
+			});
+			structured |= occur != Occur::Default;
+			rest = &rest[end..];
+		}
+	}
+	structured.then_some(res)
+}
+
+/// Combines the result of each clause of a query, given the boolean operator.
+///
+/// When a query contains required clauses, the other clauses only contribute
+/// to the score. Otherwise, at least one of the clauses must match.
+pub(in crate::idx::ft) fn combine(
+	clauses: impl Iterator<Item = (Occur, bool)>,
+	bo: BooleanOperator,
+) -> bool {
+	let mut has_required = false;
+	let mut has_optional = false;
+	let mut any_optional = false;
+	for (occur, matched) in clauses {
+		if occur == Occur::Prohibited {
+			if matched {
+				return false;
+			}
+		} else if occur.is_required(bo) {
+			if !matched {
+				return false;
+			}
+			has_required = true;
+		} else {
+			has_optional = true;
+			any_optional |= matched;
+		}
+	}
+	has_required || (has_optional && any_optional)
+}
+
+/// Checks if the terms of a phrase appear in the same value, in order, with
+/// at most `slop` additional positions between them in total.
+///
+/// Each term of the phrase is given with its position in the query, and its
+/// positions in the document.
+pub(in crate::idx::ft) fn phrase_matches(
+	terms: &[(Position, Vec<TermPosition>)],
+	slop: u32,
+) -> bool {
+	let Some(((qpos, first), rest)) = terms.split_first() else {
+		return false;
+	};
+	first.iter().any(|p| follows(p, *qpos, rest, slop))
+}
+
+fn follows(
+	prev: &TermPosition,
+	prev_qpos: Position,
+	terms: &[(Position, Vec<TermPosition>)],
+	slop: u32,
+) -> bool {
+	let Some(((qpos, positions), rest)) = terms.split_first() else {
+		return true;
+	};
+	let min = prev.pos + qpos.saturating_sub(prev_qpos);
+	positions.iter().any(|p| {
+		p.index == prev.index
+			&& p.pos >= min
+			&& p.pos - min <= slop
+			&& follows(p, *qpos, rest, slop - (p.pos - min))
+	})
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn raw(occur: Occur, text: &str, phrase: Option<u32>) -> RawClause<'_> {
+		RawClause {
+			occur,
+			text,
+			phrase,
+		}
+	}
+
+	#[test]
+	fn parse_plain() {
+		assert_eq!(parse("hello world"), None);
+		assert_eq!(parse("978-3-16 - +"), None);
+		assert_eq!(parse("-5 degrees"), None);
+	}
+
+	#[test]
+	fn parse_structured() {
+		assert_eq!(
+			parse(r#"+new "york city"~3 -"big apple" -boston tea"#),
+			Some(vec![
+				raw(Occur::Required, "new", None),
+				raw(Occur::Default, "york city", Some(3)),
+				raw(Occur::Prohibited, "big apple", Some(0)),
+				raw(Occur::Prohibited, "boston", None),
+				raw(Occur::Default, "tea", None),
+			])
+		);
+		assert_eq!(parse(r#""new york"#), Some(vec![raw(Occur::Default, "new york", Some(0))]));
+	}
+
+	fn positions(p: &[(u32, Position)]) -> Vec<TermPosition> {
+		p.iter().map(|(i, p)| TermPosition::new(*i, *p)).collect()
+	}
+
+	#[test]
+	fn phrase() {
+		// "new york" in "new york city" and "york is new"
+		let terms = vec![(0, positions(&[(0, 0), (1, 2)])), (1, positions(&[(0, 1), (1, 0)]))];
+		assert!(phrase_matches(&terms, 0));
+		// "new york" in "new big york" and "york city new"
+		let terms = vec![(0, positions(&[(0, 0), (1, 2)])), (1, positions(&[(0, 2), (1, 0)]))];
+		assert!(!phrase_matches(&terms, 0));
+		assert!(phrase_matches(&terms, 1));
+		// Terms in different values never match
+		let terms = vec![(0, positions(&[(0, 0)])), (1, positions(&[(1, 1)]))];
+		assert!(!phrase_matches(&terms, 5));
+	}
+
+	#[test]
+	fn combine_clauses() {
+		use BooleanOperator::*;
+		use Occur::*;
+		assert!(combine([(Default, true), (Default, false)].into_iter(), Or));
+		assert!(!combine([(Default, true), (Default, false)].into_iter(), And));
+		assert!(combine([(Required, true), (Default, false)].into_iter(), Or));
+		assert!(!combine([(Required, false), (Default, true)].into_iter(), Or));
+		assert!(!combine([(Default, true), (Prohibited, true)].into_iter(), Or));
+		assert!(!combine([(Prohibited, false)].into_iter(), Or));
+	}
+}
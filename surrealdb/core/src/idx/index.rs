@@ -1,5 +1,5 @@
 //! This module applies index mutations for a single document across different
-//! index types (UNIQUE, regular, search, fulltext, Hnsw). Index keys are
+//! index types (UNIQUE, regular, search, fulltext, Hnsw, IVF, spatial). Index keys are
 //! constructed via key::index and field values are encoded using
 //! key::value::Array.
 //!
@@ -20,20 +20,22 @@ use uuid::Uuid;
 
 use crate::catalog::providers::TableProvider;
 use crate::catalog::{
-	DatabaseId, FullTextParams, HnswParams, Index, IndexDefinition, NamespaceId, TableId,
+	DatabaseId, FullTextParams, HnswParams, Index, IndexDefinition, IvfParams, NamespaceId, Record,
+	TableId,
 };
 use crate::ctx::FrozenContext;
 use crate::dbs::Options;
 use crate::err::Error;
 use crate::expr::{Cond, Part};
-use crate::idx::IndexKeyBase;
 use crate::idx::ft::fulltext::FullTextIndex;
 use crate::idx::planner::iterators::IndexCountThingIterator;
+use crate::idx::trees::ivf;
 use crate::idx::trees::store::IndexStores;
+use crate::idx::{IndexKeyBase, spatial, trigram};
 use crate::key;
 use crate::key::index::iu::IndexCountKey;
 use crate::kvs::Transaction;
-use crate::val::{Array, RecordId, Value};
+use crate::val::{Array, Object, RecordId, Value};
 
 pub(crate) struct IndexOperation<'a> {
 	ctx: &'a FrozenContext,
@@ -94,11 +96,20 @@ impl<'a> IndexOperation<'a> {
 	) -> Result<()> {
 		// Index operation dispatching
 		match &self.ix.index {
-			Index::Uniq => self.index_unique().await,
-			Index::Idx => self.index_non_unique().await,
+			Index::Uniq => {
+				self.index_covered_record().await?;
+				self.index_unique().await
+			}
+			Index::Idx => {
+				self.index_covered_record().await?;
+				self.index_non_unique().await
+			}
 			Index::FullText(p) => self.index_fulltext(stk, p, require_compaction).await,
 			Index::Hnsw(p) => self.index_hnsw(p, require_compaction).await,
 			Index::Count(c) => self.index_count(stk, c.as_ref(), require_compaction).await,
+			Index::Spatial => self.index_spatial().await,
+			Index::Trigram => self.index_trigram().await,
+			Index::Ivf(p) => self.index_ivf(p).await,
 		}
 	}
 
@@ -199,6 +210,22 @@ impl<'a> IndexOperation<'a> {
 		Ok(())
 	}
 
+	/// Maintains the partial record stored by a covering index, which holds the
+	/// record id and the values of the indexed and included fields.
+	async fn index_covered_record(&self) -> Result<()> {
+		if self.ix.include.is_empty() {
+			return Ok(());
+		}
+		let txn = self.ctx.tx();
+		let key = self.ikb.new_cv_key(&self.rid.key);
+		if let Some(n) = &self.n {
+			txn.set(&key, &covered_record(self.ix, self.rid, n), None).await?;
+		} else if self.o.is_some() {
+			txn.del(&key).await?;
+		}
+		Ok(())
+	}
+
 	async fn index_count(
 		&mut self,
 		_stk: &mut Stk,
@@ -357,12 +384,91 @@ impl<'a> IndexOperation<'a> {
 		}
 		Ok(())
 	}
+
+	/// Stores each vector under the list of its nearest centroid.
+	async fn index_ivf(&mut self, p: &IvfParams) -> Result<()> {
+		let txn = self.ctx.tx();
+		ivf::index(&txn, &self.ikb, p, &self.rid.key, self.o.take(), self.n.take()).await
+	}
+
+	/// Stores each geometry under the cells covering its bounding box. Values
+	/// which are not geometries are not indexed.
+	async fn index_spatial(&mut self) -> Result<()> {
+		let txn = self.ctx.tx();
+		let id = &self.rid.key;
+		// Delete the old index data
+		if let Some(o) = self.o.take() {
+			for v in o {
+				if let Value::Geometry(g) = v {
+					for cell in spatial::index_cells(&g) {
+						txn.del(&self.ikb.new_sc_key(&cell, id)).await?;
+					}
+				}
+			}
+		}
+		// Create the new index data
+		if let Some(n) = self.n.take() {
+			for v in n {
+				if let Value::Geometry(g) = v {
+					for cell in spatial::index_cells(&g) {
+						txn.set(&self.ikb.new_sc_key(&cell, id), &g, None).await?;
+					}
+				}
+			}
+		}
+		Ok(())
+	}
+
+	/// Stores the id of the record under the distinct trigrams of its strings.
+	/// Values which are not strings are not indexed.
+	async fn index_trigram(&mut self) -> Result<()> {
+		let txn = self.ctx.tx();
+		let id = &self.rid.key;
+		// Delete the old index data
+		if let Some(o) = self.o.take() {
+			for v in o {
+				if let Value::String(s) = v {
+					for t in trigram::trigrams(&s) {
+						txn.del(&self.ikb.new_tg_key(&t, id)).await?;
+					}
+				}
+			}
+		}
+		// Create the new index data
+		if let Some(n) = self.n.take() {
+			for v in n {
+				if let Value::String(s) = v {
+					for t in trigram::trigrams(&s) {
+						txn.set(&self.ikb.new_tg_key(&t, id), &(), None).await?;
+					}
+				}
+			}
+		}
+		Ok(())
+	}
 }
 
 /// Extract from the given document, the values required by the index and put
 /// then in an array. Eg. IF the index is composed of the columns `name` and
 /// `instrument` Given this doc: { "id": 1, "instrument":"piano", "name":"Tobie"
 /// } It will return: ["Tobie", "piano"]
+/// Builds the partial record stored by a covering index. The values are the
+/// values of the indexed columns followed by the values of the included fields,
+/// as returned by `Document::build_opt_values`. Only plain field paths are
+/// stored, as the values of expression columns have no place in the record.
+fn covered_record(ix: &IndexDefinition, rid: &RecordId, vals: &[Value]) -> Record {
+	let mut data = Object::default();
+	data.insert("id".to_owned(), Value::RecordId(rid.clone()));
+	let mut data = Value::Object(data);
+	for (idiom, v) in ix.cols.iter().chain(ix.include.iter()).zip(vals) {
+		// Missing fields are left out of the record
+		if idiom.is_field_path() && v.is_some() {
+			data.put(idiom, v.clone());
+		}
+	}
+	Record::new(data)
+}
+
 struct Indexable(Vec<(Value, bool)>);
 
 impl Indexable {
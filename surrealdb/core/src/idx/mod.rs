@@ -2,7 +2,9 @@ pub(crate) mod ft;
 pub(crate) mod index;
 pub mod planner;
 pub(super) mod seqdocids;
+pub(crate) mod spatial;
 pub mod trees;
+pub(crate) mod trigram;
 
 use std::borrow::Cow;
 use std::fmt::{Debug, Display};
@@ -17,8 +19,10 @@ use crate::idx::seqdocids::DocId;
 use crate::idx::trees::hnsw::ElementId;
 use crate::idx::trees::hnsw::index::AppendingId64;
 use crate::idx::trees::vector::SerializedVector;
+use crate::key::index::cv::Cv;
 use crate::key::index::dc::Dc;
 use crate::key::index::dl::Dl;
+use crate::key::index::hc::Hc;
 use crate::key::index::hd::{Hd, HdRoot};
 use crate::key::index::he::He;
 use crate::key::index::hh::Hh;
@@ -26,6 +30,7 @@ use crate::key::index::hi::Hi;
 use crate::key::index::hl::Hl;
 use crate::key::index::hn::HnswNode;
 use crate::key::index::hp::{HnswPending, HnswPendingPrefix};
+use crate::key::index::hq::Hq;
 use crate::key::index::hs::Hs;
 use crate::key::index::hv::Hv;
 use crate::key::index::ib::Ib;
@@ -34,8 +39,13 @@ use crate::key::index::ig::IndexAppending;
 use crate::key::index::ii::Ii;
 use crate::key::index::ip::Ip;
 use crate::key::index::is::Is;
+use crate::key::index::sc::Sc;
 use crate::key::index::td::{Td, TdRoot};
+use crate::key::index::tg::Tg;
 use crate::key::index::tt::Tt;
+use crate::key::index::vc::Vc;
+use crate::key::index::vl::Vl;
+use crate::key::index::vr::Vr;
 use crate::key::root::ic::IndexCompactionKey;
 use crate::kvs::Key;
 use crate::kvs::index::{AppendingId, BatchId};
@@ -77,6 +87,10 @@ impl IndexKeyBase {
 		Hd::new(self.0.ns, self.0.db, &self.0.tb, self.0.ix, doc_id)
 	}
 
+	fn new_hc_key(&self, element_id: ElementId) -> Hc<'_> {
+		Hc::new(self.0.ns, self.0.db, &self.0.tb, self.0.ix, element_id)
+	}
+
 	fn new_he_key(&self, element_id: ElementId) -> He<'_> {
 		He::new(self.0.ns, self.0.db, &self.0.tb, self.0.ix, element_id)
 	}
@@ -124,6 +138,10 @@ impl IndexKeyBase {
 		Hs::new(self.0.ns, self.0.db, &self.0.tb, self.0.ix)
 	}
 
+	fn new_hq_key(&self) -> Hq<'_> {
+		Hq::new(self.0.ns, self.0.db, &self.0.tb, self.0.ix)
+	}
+
 	fn new_ii_key(&self, doc_id: DocId) -> Ii<'_> {
 		Ii::new(self.0.ns, self.0.db, &self.0.tb, self.0.ix, doc_id)
 	}
@@ -179,6 +197,14 @@ impl IndexKeyBase {
 		Td::new(self.0.ns, self.0.db, &self.0.tb, self.0.ix, term, doc_id)
 	}
 
+	fn new_td_prefix_range(&self, prefix: &str) -> Result<(Key, Key)> {
+		TdRoot::prefix_range(self.0.ns, self.0.db, &self.0.tb, self.0.ix, prefix)
+	}
+
+	fn new_td_term_end(&self, term: &str) -> Result<Key> {
+		TdRoot::term_end(self.0.ns, self.0.db, &self.0.tb, self.0.ix, term)
+	}
+
 	fn new_tt<'a>(
 		&'a self,
 		term: &'a str,
@@ -218,6 +244,46 @@ impl IndexKeyBase {
 		Dl::new(self.0.ns, self.0.db, &self.0.tb, self.0.ix, doc_id)
 	}
 
+	fn new_sc_key<'a>(&'a self, cell: &'a str, id: &'a RecordIdKey) -> Sc<'a> {
+		Sc::new(self.0.ns, self.0.db, &self.0.tb, self.0.ix, cell, id)
+	}
+
+	fn new_sc_cell_range(&self, cell: &str) -> Result<(Key, Key)> {
+		Sc::cell_range(self.0.ns, self.0.db, &self.0.tb, self.0.ix, cell)
+	}
+
+	fn new_sc_prefix_range(&self, cell: &str) -> Result<(Key, Key)> {
+		Sc::prefix_range(self.0.ns, self.0.db, &self.0.tb, self.0.ix, cell)
+	}
+
+	fn new_tg_key<'a>(&'a self, trigram: &'a str, id: &'a RecordIdKey) -> Tg<'a> {
+		Tg::new(self.0.ns, self.0.db, &self.0.tb, self.0.ix, trigram, id)
+	}
+
+	fn new_tg_range(&self, trigram: &str) -> Result<(Key, Key)> {
+		Tg::trigram_range(self.0.ns, self.0.db, &self.0.tb, self.0.ix, trigram)
+	}
+
+	fn new_cv_key<'a>(&'a self, id: &'a RecordIdKey) -> Cv<'a> {
+		Cv::new(self.0.ns, self.0.db, &self.0.tb, self.0.ix, id)
+	}
+
+	fn new_vc_key(&self) -> Vc<'_> {
+		Vc::new(self.0.ns, self.0.db, &self.0.tb, self.0.ix)
+	}
+
+	fn new_vl_key<'a>(&'a self, list: u16, id: &'a RecordIdKey, pos: u32) -> Vl<'a> {
+		Vl::new(self.0.ns, self.0.db, &self.0.tb, self.0.ix, list, id, pos)
+	}
+
+	fn new_vl_list_range(&self, list: u16) -> Result<(Key, Key)> {
+		Vl::list_range(self.0.ns, self.0.db, &self.0.tb, self.0.ix, list)
+	}
+
+	fn new_vr_key<'a>(&'a self, id: &'a RecordIdKey) -> Vr<'a> {
+		Vr::new(self.0.ns, self.0.db, &self.0.tb, self.0.ix, id)
+	}
+
 	pub(crate) fn ns(&self) -> NamespaceId {
 		self.0.ns
 	}
@@ -0,0 +1,213 @@
+use crate::catalog::{FieldDefinition, IndexDefinition, Permission};
+use crate::dbs::Statement;
+use crate::expr::order::Ordering;
+use crate::expr::visit::{Visit, Visitor};
+use crate::expr::{
+	Cond, Expr, Fetchs, Field, Fields, Function, Groups, Idiom, Literal, Part, Splits,
+};
+use crate::fnc::is_pure_function;
+use crate::idx::planner::{GrantedPermission, StatementContext};
+
+/// Checks whether a SELECT statement can be answered from the partial records
+/// stored by a covering index, without fetching the records themselves.
+///
+/// This is the case when every field read by the statement is the record id,
+/// an indexed column or an included field, and when neither computed fields
+/// nor permissions require the rest of the record.
+pub(super) fn is_covered_by(
+	stm_ctx: &StatementContext<'_>,
+	ix: &IndexDefinition,
+	fields: &[FieldDefinition],
+	gp: GrantedPermission,
+) -> bool {
+	let Statement::Select {
+		stmt,
+		..
+	} = stm_ctx.stm
+	else {
+		return false;
+	};
+	// Table permissions may read any field of the record
+	if matches!(gp, GrantedPermission::Specific) {
+		return false;
+	}
+	// Computed fields and field permissions may read any field of the record
+	if fields.iter().any(|fd| {
+		fd.computed.is_some()
+			|| (stm_ctx.is_perm && matches!(fd.select_permission, Permission::Specific(_)))
+	}) {
+		return false;
+	}
+	// Historical records are not stored in the index
+	if !matches!(stmt.version, Expr::Literal(Literal::None)) {
+		return false;
+	}
+	SelectClauses {
+		fields: stm_ctx.fields,
+		cond: stm_ctx.cond,
+		order: stm_ctx.order,
+		group: stm_ctx.group,
+		split: stm_ctx.stm.split(),
+		fetch: stm_ctx.stm.fetch(),
+	}
+	.are_covered_by(ix)
+}
+
+/// The clauses of a SELECT statement which read the fields of the records.
+pub(crate) struct SelectClauses<'a> {
+	pub(crate) fields: Option<&'a Fields>,
+	pub(crate) cond: Option<&'a Cond>,
+	pub(crate) order: Option<&'a Ordering>,
+	pub(crate) group: Option<&'a Groups>,
+	pub(crate) split: Option<&'a Splits>,
+	pub(crate) fetch: Option<&'a Fetchs>,
+}
+
+impl SelectClauses<'_> {
+	/// Checks whether every field read by these clauses is stored by the
+	/// given covering index.
+	pub(crate) fn are_covered_by(&self, ix: &IndexDefinition) -> bool {
+		if ix.include.is_empty() {
+			return false;
+		}
+		CoveringChecker::new(ix).check_select(self).is_ok()
+	}
+}
+
+/// Visitor which rejects any expression reading a field which is not stored
+/// by a covering index, or reading the whole document.
+struct CoveringChecker {
+	covered: Vec<Idiom>,
+}
+
+impl CoveringChecker {
+	fn new(ix: &IndexDefinition) -> Self {
+		let covered = ix
+			.cols
+			.iter()
+			.chain(ix.include.iter())
+			.filter(|i| i.is_field_path())
+			.cloned()
+			.chain([Idiom::field("id".to_owned())])
+			.collect();
+		Self {
+			covered,
+		}
+	}
+
+	fn check_select(&mut self, clauses: &SelectClauses<'_>) -> Result<(), ()> {
+		match clauses.fields {
+			Some(Fields::Value(s)) => self.visit_expr(&s.expr)?,
+			Some(Fields::Select(fields)) => {
+				for f in fields.iter() {
+					match f {
+						Field::All => return Err(()),
+						Field::Single(s) => self.visit_expr(&s.expr)?,
+					}
+				}
+			}
+			None => return Err(()),
+		}
+		if let Some(c) = clauses.cond {
+			self.visit_expr(&c.0)?;
+		}
+		if let Some(Ordering::Order(o)) = clauses.order {
+			for o in o.0.iter() {
+				self.check_idiom(&o.value)?;
+			}
+		}
+		if let Some(g) = clauses.group {
+			for g in g.0.iter() {
+				self.check_idiom(&g.0)?;
+			}
+		}
+		if let Some(s) = clauses.split {
+			for s in s.0.iter() {
+				self.check_idiom(&s.0)?;
+			}
+		}
+		if let Some(f) = clauses.fetch {
+			for f in f.iter() {
+				self.visit_expr(&f.0)?;
+			}
+		}
+		Ok(())
+	}
+
+	/// Checks an idiom read from the document. It must start with a covered
+	/// field path, the remaining parts being applied to the covered value.
+	fn check_idiom(&mut self, idiom: &Idiom) -> Result<(), ()> {
+		if let Some(Part::Start(e)) = idiom.0.first() {
+			// The idiom is applied to the result of an expression
+			self.visit_expr(e)?;
+			return idiom.0[1..].iter().try_for_each(|p| self.visit_part(p));
+		}
+		let Some(path) = self.covered.iter().find(|c| idiom.0.starts_with(&c.0)) else {
+			return Err(());
+		};
+		let len = path.0.len();
+		idiom.0[len..].iter().try_for_each(|p| self.visit_part(p))
+	}
+}
+
+impl Visitor for CoveringChecker {
+	type Error = ();
+
+	fn visit_expr(&mut self, e: &Expr) -> Result<(), Self::Error> {
+		match e {
+			Expr::Idiom(i) => self.check_idiom(i),
+			Expr::Param(p) => {
+				if matches!(p.as_str(), "this" | "self" | "parent") {
+					Err(())
+				} else {
+					Ok(())
+				}
+			}
+			Expr::Literal(_)
+			| Expr::Table(_)
+			| Expr::Constant(_)
+			| Expr::Prefix {
+				..
+			}
+			| Expr::Postfix {
+				..
+			}
+			| Expr::Binary {
+				..
+			}
+			| Expr::FunctionCall(_)
+			| Expr::IfElse(_) => e.visit(self),
+			// Subqueries, closures and blocks may read the document
+			_ => Err(()),
+		}
+	}
+
+	fn visit_part(&mut self, p: &Part) -> Result<(), Self::Error> {
+		match p {
+			// Graph traversals and recursions read other records
+			Part::Lookup(_) | Part::Recurse(..) | Part::Doc => Err(()),
+			_ => p.visit(self),
+		}
+	}
+
+	fn visit_function(&mut self, f: &Function) -> Result<(), Self::Error> {
+		match f {
+			Function::Normal(name) if reads_arguments_only(name) => Ok(()),
+			_ => Err(()),
+		}
+	}
+}
+
+/// Checks if a builtin function only reads its arguments. Functions such as
+/// `type::field` read the current document, whose fields are not all stored
+/// by the index.
+fn reads_arguments_only(name: &str) -> bool {
+	match name {
+		// Impure functions which do not read the current document
+		"array::shuffle" | "record::exists" | "record::is_edge" | "time::now"
+		| "time::timezone" => true,
+		_ => {
+			matches!(name.split_once("::"), Some(("rand" | "session", _))) || is_pure_function(name)
+		}
+	}
+}
@@ -13,25 +13,28 @@ use crate::dbs::Options;
 use crate::doc::{CursorDoc, NsDbTbCtx};
 use crate::err::Error;
 use crate::expr::operator::{BooleanOperator, MatchesOperator};
-use crate::expr::{Cond, Expr, FlowResultExt as _, Idiom};
+use crate::expr::{BinaryOperator, Cond, Expr, FlowResultExt as _, Idiom};
 use crate::idx::IndexKeyBase;
 use crate::idx::ft::MatchRef;
 use crate::idx::ft::fulltext::{FullTextIndex, QueryTerms, Scorer};
 use crate::idx::ft::highlighter::HighlightParams;
 use crate::idx::planner::iterators::{
-	IndexCountThingIterator, IndexEqualThingIterator, IndexJoinThingIterator,
-	IndexRangeReverseThingIterator, IndexRangeThingIterator, IndexUnionThingIterator,
-	IteratorRecord, IteratorRef, KnnIterator, KnnIteratorResult, MatchesThingIterator,
-	RecordIterator, UniqueEqualThingIterator, UniqueJoinThingIterator,
-	UniqueRangeReverseThingIterator, UniqueRangeThingIterator, UniqueUnionThingIterator,
+	CoveringThingIterator, IndexCountThingIterator, IndexEqualThingIterator,
+	IndexJoinThingIterator, IndexRangeReverseThingIterator, IndexRangeThingIterator,
+	IndexUnionThingIterator, IteratorRecord, IteratorRef, KnnIterator, KnnIteratorResult,
+	MatchesThingIterator, RecordIterator, SpatialThingIterator, TrigramThingIterator,
+	UniqueEqualThingIterator, UniqueJoinThingIterator, UniqueRangeReverseThingIterator,
+	UniqueRangeThingIterator, UniqueUnionThingIterator,
 };
 use crate::idx::planner::knn::{KnnBruteForceResult, KnnPriorityList};
 use crate::idx::planner::plan::IndexOperator::Matches;
 use crate::idx::planner::plan::{IndexOperator, IndexOption, RangeValue};
 use crate::idx::planner::tree::{IdiomPosition, IndexReference};
 use crate::idx::planner::{IterationStage, ScanDirection};
+use crate::idx::spatial::{self, SpatialQuery};
+use crate::idx::trees::ivf;
 use crate::idx::trees::store::hnsw::SharedHnswIndex;
-use crate::val::{Array, Number, Object, RecordId, TableName, Value};
+use crate::val::{Array, Geometry, Number, Object, RecordId, TableName, Value};
 
 pub(super) type KnnBruteForceEntry = (KnnPriorityList, Idiom, Arc<Vec<Number>>, Distance);
 
@@ -74,6 +77,8 @@ enum PerExpressionEntry {
 	FullText(FullTextEntry),
 	Hnsw(HnswEntry),
 	KnnBruteForce(KnnBruteForceEntry),
+	/// The results of a KNN search resolved upfront (spatial or IVF indexes)
+	IndexKnn(VecDeque<KnnIteratorResult>),
 }
 
 /// Entry keyed by MatchRef for MATCHES queries, decoupling expression identity
@@ -88,6 +93,8 @@ pub(super) struct InnerQueryExecutor {
 	mr_entries: HashMap<MatchRef, PerMatchRefEntry>,
 	exp_entries: HashMap<Arc<Expr>, PerExpressionEntry>,
 	it_entries: Vec<IteratorEntry>,
+	/// The iterators answered from the partial records of a covering index
+	covering_entries: HashSet<IteratorRef>,
 	knn_bruteforce_len: usize, // Count of brute-force KNN expressions aggregated for later merging
 }
 
@@ -188,23 +195,20 @@ impl InnerQueryExecutor {
 					}
 				}
 				Index::Hnsw(p) => {
-					if let IndexOperator::Ann(a, k, ef) = io.op() {
+					if matches!(io.op(), IndexOperator::Ann(..) | IndexOperator::MultiAnn(..)) {
 						let he = match ir_map.entry(index_reference.clone()) {
 							Entry::Occupied(e) => {
 								if let PerIndexReferenceIndex::Hnsw(hi) = e.get() {
-									Some(
-										HnswEntry::new(
-											stk,
-											ctx,
-											opt,
-											hi.clone(),
-											a,
-											*k,
-											*ef,
-											knn_condition.clone(),
-										)
-										.await?,
+									HnswEntry::new(
+										stk,
+										ctx,
+										opt,
+										hi.clone(),
+										&index_reference.cols[0],
+										io.op(),
+										knn_condition.clone(),
 									)
+									.await?
 								} else {
 									None
 								}
@@ -237,14 +241,13 @@ impl InnerQueryExecutor {
 									ctx,
 									opt,
 									hi.clone(),
-									a,
-									*k,
-									*ef,
+									&index_reference.cols[0],
+									io.op(),
 									knn_condition.clone(),
 								)
 								.await?;
 								e.insert(PerIndexReferenceIndex::Hnsw(hi));
-								Some(entry)
+								entry
 							}
 						};
 						if let Some(he) = he {
@@ -252,6 +255,51 @@ impl InnerQueryExecutor {
 						}
 					}
 				}
+				Index::Spatial => {
+					if let IndexOperator::SpatialKnn(v, k) = io.op()
+						&& let Value::Geometry(Geometry::Point(p)) = v.as_ref()
+					{
+						let ikb = IndexKeyBase::new(
+							doc_ctx.ns.namespace_id,
+							doc_ctx.db.database_id,
+							index_reference.table_name.clone(),
+							index_reference.index_id,
+						);
+						let res = spatial::knn_search(
+							stk,
+							ctx,
+							opt,
+							&ikb,
+							*p,
+							*k as usize,
+							knn_condition.clone(),
+						)
+						.await?;
+						exp_entries.insert(exp, PerExpressionEntry::IndexKnn(res));
+					}
+				}
+				Index::Ivf(p) => {
+					if let IndexOperator::IvfKnn(v, k) = io.op() {
+						let ikb = IndexKeyBase::new(
+							doc_ctx.ns.namespace_id,
+							doc_ctx.db.database_id,
+							index_reference.table_name.clone(),
+							index_reference.index_id,
+						);
+						let res = ivf::knn_search(
+							stk,
+							ctx,
+							opt,
+							&ikb,
+							p,
+							v,
+							*k as usize,
+							knn_condition.clone(),
+						)
+						.await?;
+						exp_entries.insert(exp, PerExpressionEntry::IndexKnn(res));
+					}
+				}
 				_ => {}
 			}
 		}
@@ -268,6 +316,7 @@ impl InnerQueryExecutor {
 			mr_entries,
 			exp_entries,
 			it_entries: Vec::new(),
+			covering_entries: HashSet::new(),
 			knn_bruteforce_len,
 		})
 	}
@@ -277,6 +326,12 @@ impl InnerQueryExecutor {
 		self.it_entries.push(it_entry);
 		ir as IteratorRef
 	}
+
+	/// Marks an iterator as answered from the partial records of a covering
+	/// index, rather than from the records themselves.
+	pub(super) fn set_covering(&mut self, ir: IteratorRef) {
+		self.covering_entries.insert(ir);
+	}
 }
 
 impl QueryExecutor {
@@ -339,7 +394,15 @@ impl QueryExecutor {
 
 	pub(crate) fn explain(&self, ir: IteratorRef) -> Value {
 		match self.0.it_entries.get(ir) {
-			Some(ie) => ie.explain(),
+			Some(ie) => {
+				let mut e = ie.explain();
+				if self.0.covering_entries.contains(&ir)
+					&& let Value::Object(o) = &mut e
+				{
+					o.insert("covering".to_owned(), Value::Bool(true));
+				}
+				e
+			}
 			None => Value::None,
 		}
 	}
@@ -359,23 +422,36 @@ impl QueryExecutor {
 		db: DatabaseId,
 		ir: IteratorRef,
 	) -> Result<Option<RecordIterator>> {
-		if let Some(it_entry) = self.0.it_entries.get(ir) {
-			match it_entry {
-				IteratorEntry::Single(_, io) => self.new_single_iterator(ns, db, ir, io).await,
-				IteratorEntry::Range(_, index_reference, from, to, sc) => Ok(self
-					.new_range_iterator(
-						ir,
-						ns,
-						db,
-						index_reference,
-						from.clone(),
-						to.clone(),
-						*sc,
-					)?),
+		let Some(it_entry) = self.0.it_entries.get(ir) else {
+			return Ok(None);
+		};
+		let (it, ix) = match it_entry {
+			IteratorEntry::Single(_, io) => {
+				(self.new_single_iterator(ns, db, ir, io).await?, io.index_reference())
 			}
-		} else {
-			Ok(None)
+			IteratorEntry::Range(_, index_reference, from, to, sc) => (
+				self.new_range_iterator(
+					ir,
+					ns,
+					db,
+					index_reference,
+					from.clone(),
+					to.clone(),
+					*sc,
+				)?,
+				index_reference,
+			),
+		};
+		// Covered iterators read the partial records stored by the index
+		if self.0.covering_entries.contains(&ir)
+			&& let Some(it) = it
+		{
+			let ikb = IndexKeyBase::new(ns, db, ix.table_name.clone(), ix.index_id);
+			return Ok(Some(RecordIterator::Covering(Box::new(CoveringThingIterator::new(
+				ikb, it,
+			)))));
 		}
+		Ok(it)
 	}
 
 	async fn new_single_iterator(
@@ -394,6 +470,9 @@ impl QueryExecutor {
 				..
 			} => self.new_fulltext_index_iterator(irf, io.clone()).await,
 			Index::Hnsw(_) => Ok(self.new_hnsw_index_ann_iterator(irf)),
+			Index::Ivf(_) => Ok(self.new_index_knn_iterator(irf)),
+			Index::Spatial => self.new_spatial_index_iterator(ns, db, irf, io),
+			Index::Trigram => self.new_trigram_index_iterator(ns, db, irf, io),
 		}
 	}
 
@@ -658,6 +737,59 @@ impl QueryExecutor {
 		None
 	}
 
+	fn new_index_knn_iterator(&self, ir: IteratorRef) -> Option<RecordIterator> {
+		if let Some(IteratorEntry::Single(Some(exp), ..)) = self.0.it_entries.get(ir)
+			&& let Some(PerExpressionEntry::IndexKnn(res)) = self.0.exp_entries.get(exp)
+		{
+			let it = KnnIterator::new(ir, res.clone());
+			return Some(RecordIterator::Knn(it));
+		}
+		None
+	}
+
+	fn new_spatial_index_iterator(
+		&self,
+		ns: NamespaceId,
+		db: DatabaseId,
+		irf: IteratorRef,
+		io: &IndexOption,
+	) -> Result<Option<RecordIterator>> {
+		let query = match io.op() {
+			IndexOperator::SpatialKnn(..) => return Ok(self.new_index_knn_iterator(irf)),
+			IndexOperator::Spatial(op, v) => match (op, v.as_ref()) {
+				(BinaryOperator::Inside, Value::Geometry(g)) => SpatialQuery::Inside(g.clone()),
+				(BinaryOperator::Intersects, Value::Geometry(g)) => {
+					SpatialQuery::Intersects(g.clone())
+				}
+				(BinaryOperator::Outside, Value::Geometry(g)) => SpatialQuery::Outside(g.clone()),
+				_ => return Ok(None),
+			},
+			IndexOperator::SpatialDistance(v, d) => match v.as_ref() {
+				Value::Geometry(Geometry::Point(p)) => SpatialQuery::Distance(*p, d.to_float()),
+				_ => return Ok(None),
+			},
+			_ => return Ok(None),
+		};
+		let ix = io.index_reference();
+		let ikb = IndexKeyBase::new(ns, db, ix.table_name.clone(), ix.index_id);
+		Ok(Some(RecordIterator::Spatial(SpatialThingIterator::new(irf, &ikb, query)?)))
+	}
+
+	fn new_trigram_index_iterator(
+		&self,
+		ns: NamespaceId,
+		db: DatabaseId,
+		irf: IteratorRef,
+		io: &IndexOption,
+	) -> Result<Option<RecordIterator>> {
+		let IndexOperator::Trigram(trigrams, _) = io.op() else {
+			return Ok(None);
+		};
+		let ix = io.index_reference();
+		let ikb = IndexKeyBase::new(ns, db, ix.table_name.clone(), ix.index_id);
+		Ok(Some(RecordIterator::Trigram(TrigramThingIterator::new(irf, &ikb, trigrams)?)))
+	}
+
 	async fn build_iterators(
 		&self,
 		ns: NamespaceId,
@@ -712,10 +844,8 @@ impl QueryExecutor {
 			return Ok(false);
 		}
 		let tx = ctx.tx();
-		if let Some(doc_id) = fti.get_doc_id(&tx, thg).await?
-			&& fte.0.qt.contains_doc(doc_id)
-		{
-			return Ok(true);
+		if let Some(doc_id) = fti.get_doc_id(&tx, thg).await? {
+			return fti.matches_doc(&tx, &fte.0.qt, doc_id, fte.0.bo).await;
 		}
 		Ok(false)
 	}
@@ -855,7 +985,7 @@ impl FullTextEntry {
 		io: IndexOption,
 	) -> Result<Option<Self>> {
 		if let Matches(qs, mo) = io.op() {
-			let qt = fti.extract_querying_terms(stk, ctx, opt, qs.to_owned()).await?;
+			let qt = fti.extract_querying_terms(stk, ctx, opt, qs.to_owned(), mo.fuzzy).await?;
 			let scorer = fti.new_scorer(ctx).await?;
 			Ok(Some(Self(Arc::new(InnerFullTextEntry {
 				bo: mo.operator,
@@ -875,21 +1005,28 @@ pub(super) struct HnswEntry {
 }
 
 impl HnswEntry {
-	#[expect(clippy::too_many_arguments)]
 	async fn new(
 		stk: &mut Stk,
 		ctx: &FrozenContext,
 		opt: &Options,
 		h: SharedHnswIndex,
-		v: &[Number],
-		n: u32,
-		ef: u32,
+		col: &Idiom,
+		op: &IndexOperator,
 		cond: Option<Arc<Cond>>,
-	) -> Result<Self> {
+	) -> Result<Option<Self>> {
 		let cond_filter = cond.map(|cond| (opt, cond));
-		let res = h.knn_search(ctx, stk, v, n as usize, ef as usize, cond_filter).await?;
-		Ok(Self {
+		let res = match op {
+			IndexOperator::Ann(v, n, ef) => {
+				h.knn_search(ctx, stk, v, *n as usize, *ef as usize, cond_filter).await?
+			}
+			IndexOperator::MultiAnn(v, n, ef, a) => {
+				h.multi_knn_search(ctx, stk, col, v, *a, *n as usize, *ef as usize, cond_filter)
+					.await?
+			}
+			_ => return Ok(None),
+		};
+		Ok(Some(Self {
 			res,
-		})
+		}))
 	}
 }
@@ -1,4 +1,4 @@
-use std::collections::VecDeque;
+use std::collections::{HashSet, VecDeque};
 use std::ops::Range;
 use std::sync::Arc;
 
@@ -16,10 +16,12 @@ use crate::idx::ft::fulltext::FullTextHitsIterator;
 use crate::idx::planner::plan::RangeValue;
 use crate::idx::planner::tree::IndexReference;
 use crate::idx::seqdocids::DocId;
+use crate::idx::spatial::{self, SpatialQuery};
+use crate::idx::trigram::TrigramIntersection;
 use crate::key::index::Index;
 use crate::key::index::iu::IndexCountKey;
 use crate::kvs::{KVKey, Key, Transaction, Val};
-use crate::val::{Array, RecordId, TableName, Value};
+use crate::val::{Array, RecordId, RecordIdKey, TableName, Value};
 
 pub(crate) type IteratorRef = usize;
 
@@ -131,6 +133,9 @@ pub(crate) enum RecordIterator {
 	UniqueJoin(Box<UniqueJoinThingIterator>),
 	FullTextMatches(MatchesThingIterator<FullTextHitsIterator>),
 	Knn(KnnIterator),
+	Spatial(SpatialThingIterator),
+	Trigram(TrigramThingIterator),
+	Covering(Box<CoveringThingIterator>),
 }
 
 impl RecordIterator {
@@ -156,6 +161,9 @@ impl RecordIterator {
 			Self::UniqueUnion(i) => i.next_batch(ctx, txn, size).await,
 			Self::FullTextMatches(i) => i.next_batch(ctx, txn, size).await,
 			Self::Knn(i) => i.next_batch(ctx, size).await,
+			Self::Spatial(i) => i.next_batch(ctx, txn, size).await,
+			Self::Trigram(i) => i.next_batch(ctx, txn, size).await,
+			Self::Covering(i) => Box::pin(i.next_batch(ctx, txn, size)).await,
 			Self::IndexJoin(i) => Box::pin(i.next_batch(ctx, txn, size)).await,
 			Self::UniqueJoin(i) => Box::pin(i.next_batch(ctx, txn, size)).await,
 			Self::IndexCount(_) => {
@@ -185,6 +193,9 @@ impl RecordIterator {
 			Self::UniqueUnion(i) => i.next_count(ctx, txn, size).await,
 			Self::FullTextMatches(i) => i.next_count(ctx, txn, size).await,
 			Self::Knn(i) => i.next_count(ctx, size).await,
+			Self::Spatial(i) => i.next_count(ctx, txn, size).await,
+			Self::Trigram(i) => i.next_count(ctx, txn, size).await,
+			Self::Covering(i) => Box::pin(i.next_count(ctx, txn, size)).await,
 			Self::IndexJoin(i) => Box::pin(i.next_count(ctx, txn, size)).await,
 			Self::UniqueJoin(i) => Box::pin(i.next_count(ctx, txn, size)).await,
 			Self::IndexCount(i) => i.next_count(ctx, txn, size).await,
@@ -1696,6 +1707,133 @@ impl KnnIterator {
 	}
 }
 
+/// Iterates over the records with a geometry matching a spatial query. A
+/// geometry may be stored under several cells, so the records already
+/// returned are remembered.
+pub(crate) struct SpatialThingIterator {
+	irf: IteratorRef,
+	table: TableName,
+	query: SpatialQuery,
+	ranges: VecDeque<(Key, Key)>,
+	seen: HashSet<RecordIdKey>,
+}
+
+impl SpatialThingIterator {
+	pub(super) fn new(irf: IteratorRef, ikb: &IndexKeyBase, query: SpatialQuery) -> Result<Self> {
+		Ok(Self {
+			irf,
+			table: ikb.table().clone(),
+			ranges: query.ranges(ikb)?,
+			query,
+			seen: Default::default(),
+		})
+	}
+
+	/// Returns the ids of the next matching records, scanning up to `limit`
+	/// index entries
+	async fn next_ids(
+		&mut self,
+		ctx: &FrozenContext,
+		tx: &Transaction,
+		limit: u32,
+	) -> Result<Vec<RecordIdKey>> {
+		while let Some(range) = self.ranges.front_mut() {
+			if ctx.is_done(None).await? {
+				break;
+			}
+			let entries = spatial::next_scan(tx, range, limit).await?;
+			if entries.is_empty() {
+				self.ranges.pop_front();
+				continue;
+			}
+			let mut ids = Vec::with_capacity(entries.len());
+			for (id, g) in entries {
+				if !self.seen.contains(&id) && self.query.matches(&g) {
+					self.seen.insert(id.clone());
+					ids.push(id);
+				}
+			}
+			if !ids.is_empty() {
+				return Ok(ids);
+			}
+		}
+		Ok(vec![])
+	}
+
+	async fn next_batch<B: IteratorBatch>(
+		&mut self,
+		ctx: &FrozenContext,
+		tx: &Transaction,
+		limit: u32,
+	) -> Result<B> {
+		let ids = self.next_ids(ctx, tx, limit).await?;
+		let mut records = B::with_capacity(ids.len());
+		for id in ids {
+			let rid = RecordId::new(self.table.clone(), id);
+			records.add(IndexItemRecord::new_key(rid, self.irf.into()));
+		}
+		Ok(records)
+	}
+
+	async fn next_count(
+		&mut self,
+		ctx: &FrozenContext,
+		tx: &Transaction,
+		limit: u32,
+	) -> Result<usize> {
+		Ok(self.next_ids(ctx, tx, limit).await?.len())
+	}
+}
+
+/// Iterates over the records storing every trigram required by a trigram
+/// query. The records are candidates, which are checked against the
+/// condition once fetched.
+pub(crate) struct TrigramThingIterator {
+	irf: IteratorRef,
+	table: TableName,
+	postings: TrigramIntersection,
+}
+
+impl TrigramThingIterator {
+	pub(super) fn new(irf: IteratorRef, ikb: &IndexKeyBase, trigrams: &[String]) -> Result<Self> {
+		Ok(Self {
+			irf,
+			table: ikb.table().clone(),
+			postings: TrigramIntersection::new(ikb, trigrams)?,
+		})
+	}
+
+	async fn next_batch<B: IteratorBatch>(
+		&mut self,
+		ctx: &FrozenContext,
+		tx: &Transaction,
+		limit: u32,
+	) -> Result<B> {
+		if ctx.is_done(None).await? {
+			return Ok(B::empty());
+		}
+		let ids = self.postings.next(tx, limit).await?;
+		let mut records = B::with_capacity(ids.len());
+		for id in ids {
+			let rid = RecordId::new(self.table.clone(), id);
+			records.add(IndexItemRecord::new_key(rid, self.irf.into()));
+		}
+		Ok(records)
+	}
+
+	async fn next_count(
+		&mut self,
+		ctx: &FrozenContext,
+		tx: &Transaction,
+		limit: u32,
+	) -> Result<usize> {
+		if ctx.is_done(None).await? {
+			return Ok(0);
+		}
+		Ok(self.postings.next(tx, limit).await?.len())
+	}
+}
+
 pub(crate) struct IndexCountThingIterator(Option<Range<Key>>);
 
 impl IndexCountThingIterator {
@@ -1775,6 +1913,50 @@ impl IndexCountThingIterator {
 	}
 }
 
+/// Wraps the iterator of a covering index, and reads the partial record stored
+/// by the index for each matching record, so that the records themselves do
+/// not have to be fetched.
+pub(crate) struct CoveringThingIterator {
+	ikb: IndexKeyBase,
+	inner: RecordIterator,
+}
+
+impl CoveringThingIterator {
+	pub(super) fn new(ikb: IndexKeyBase, inner: RecordIterator) -> Self {
+		Self {
+			ikb,
+			inner,
+		}
+	}
+
+	async fn next_batch<B: IteratorBatch>(
+		&mut self,
+		ctx: &FrozenContext,
+		txn: &Transaction,
+		size: u32,
+	) -> Result<B> {
+		let items: Vec<IndexItemRecord> = self.inner.next_batch(ctx, txn, size).await?;
+		let keys = items.iter().map(|i| self.ikb.new_cv_key(&i.record_id().key)).collect();
+		let records = txn.getm(keys, None).await?;
+		let mut batch = B::with_capacity(items.len());
+		for (item, record) in items.into_iter().zip(records) {
+			let (rid, val, ir) = item.consume();
+			// Without a partial record, the record is fetched as usual
+			batch.add(IndexItemRecord::new(rid, ir, val.or_else(|| record.map(Arc::new))));
+		}
+		Ok(batch)
+	}
+
+	async fn next_count(
+		&mut self,
+		ctx: &FrozenContext,
+		txn: &Transaction,
+		size: u32,
+	) -> Result<usize> {
+		self.inner.next_count(ctx, txn, size).await
+	}
+}
+
 #[cfg(test)]
 mod tests {
 	use uuid::Uuid;
@@ -1,4 +1,5 @@
 pub(crate) mod count_exists_rewriter;
+mod covering;
 pub(crate) mod executor;
 pub(crate) mod iterators;
 pub(in crate::idx) mod knn;
@@ -13,7 +14,9 @@ use std::sync::atomic::{self, AtomicU8};
 use anyhow::Result;
 use reblessive::tree::Stk;
 
+pub(crate) use self::covering::SelectClauses;
 use crate::catalog::providers::TableProvider;
+use crate::catalog::{Index, IndexDefinition};
 use crate::ctx::FrozenContext;
 use crate::dbs::{Iterable, Iterator, Options, Statement};
 use crate::doc::NsDbTbCtx;
@@ -314,7 +317,12 @@ impl QueryPlanner {
 					self.requires_distinct = true;
 				}
 				let is_order = io.is_order();
+				let is_covering =
+					Self::is_covering(stm_ctx, &doc_ctx, io.index_reference(), gp, rs);
 				let ir = exe.add_iterator(IteratorEntry::Single(exp, io));
+				if is_covering {
+					exe.set_covering(ir);
+				}
 				self.add(doc_ctx.clone(), t.clone(), Some(ir), exe, it, rs);
 				if is_order {
 					self.ordering_indexes.push(ir);
@@ -336,7 +344,11 @@ impl QueryPlanner {
 				self.add(doc_ctx.clone(), t.clone(), None, exe, it, rs);
 			}
 			Plan::SingleIndexRange(ixn, rq, keys_only, sc, is_order) => {
+				let is_covering = Self::is_covering(stm_ctx, &doc_ctx, &ixn, gp, keys_only);
 				let ir = exe.add_iterator(IteratorEntry::Range(rq.exps, ixn, rq.from, rq.to, sc));
+				if is_covering {
+					exe.set_covering(ir);
+				}
 				if is_order {
 					self.ordering_indexes.push(ir);
 				}
@@ -359,6 +371,20 @@ impl QueryPlanner {
 		Ok(())
 	}
 
+	/// Checks whether an index iterator which would fetch the records can
+	/// rather read the partial records stored by a covering index.
+	fn is_covering(
+		stm_ctx: &StatementContext<'_>,
+		doc_ctx: &NsDbTbCtx,
+		ix: &IndexDefinition,
+		gp: GrantedPermission,
+		rs: RecordStrategy,
+	) -> bool {
+		matches!(rs, RecordStrategy::KeysAndValues)
+			&& matches!(ix.index, Index::Idx | Index::Uniq)
+			&& covering::is_covered_by(stm_ctx, ix, &doc_ctx.fields, gp)
+	}
+
 	fn add(
 		&mut self,
 		doc_ctx: NsDbTbCtx,
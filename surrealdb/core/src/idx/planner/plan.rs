@@ -7,7 +7,7 @@ use anyhow::Result;
 use surrealdb_types::ToSql;
 
 use crate::catalog::Index;
-use crate::expr::operator::{MatchesOperator, NearestNeighbor};
+use crate::expr::operator::{MatchesOperator, NearestNeighbor, VectorAggregation};
 use crate::expr::with::With;
 use crate::expr::{BinaryOperator, Expr, Idiom};
 use crate::idx::planner::tree::{
@@ -413,6 +413,20 @@ pub(super) enum IndexOperator {
 	Range(Vec<Value>, Vec<(BinaryOperator, Arc<Value>)>),
 	Matches(String, MatchesOperator),
 	Ann(Arc<Vec<Number>>, u32, u32),
+	/// The k records nearest to a set of query vectors, aggregating the
+	/// distances to the vectors of each record
+	MultiAnn(Arc<Vec<Vec<Number>>>, u32, u32, VectorAggregation),
+	/// The k records nearest to a vector, searched in an IVF index
+	IvfKnn(Arc<Vec<Number>>, u32),
+	/// INSIDE, INTERSECTS or OUTSIDE a geometry
+	Spatial(BinaryOperator, Arc<Value>),
+	/// The points within a distance, in meters, of a point
+	SpatialDistance(Arc<Value>, Number),
+	/// The k points nearest to a point
+	SpatialKnn(Arc<Value>, u32),
+	/// The strings containing a substring, or matching a regex, scanned from
+	/// the strings containing a trigram
+	Trigram(Vec<String>, Arc<Value>),
 	/// false = ascending, true = descending
 	Order(bool),
 	Count,
@@ -516,6 +530,39 @@ impl IndexOption {
 				e.insert("operator", op);
 				e.insert("value", val);
 			}
+			IndexOperator::MultiAnn(a, k, ef, agg) => {
+				let expr = NearestNeighbor::MultiVector(*k, *ef, *agg).to_sql();
+				let op = Value::from(expr);
+				let val =
+					Value::Array(a.iter().map(|v| Value::Array(Array::from(v.clone()))).collect());
+				e.insert("operator", op);
+				e.insert("value", val);
+			}
+			IndexOperator::IvfKnn(a, k) => {
+				e.insert("operator", Value::from(NearestNeighbor::KTree(*k).to_sql()));
+				e.insert("value", Value::Array(Array::from(a.as_ref().clone())));
+			}
+			IndexOperator::Spatial(op, v) => {
+				e.insert("operator", Value::from(op.to_sql()));
+				e.insert("value", v.as_ref().clone());
+			}
+			IndexOperator::SpatialDistance(v, d) => {
+				e.insert("operator", Value::from("distance"));
+				e.insert("value", v.as_ref().clone());
+				e.insert("distance", Value::Number(*d));
+			}
+			IndexOperator::SpatialKnn(v, k) => {
+				e.insert("operator", Value::from(NearestNeighbor::KTree(*k).to_sql()));
+				e.insert("value", v.as_ref().clone());
+			}
+			IndexOperator::Trigram(t, v) => {
+				e.insert("operator", Value::from("trigram"));
+				e.insert(
+					"trigrams",
+					Value::from(t.iter().cloned().map(Value::from).collect::<Vec<_>>()),
+				);
+				e.insert("value", v.as_ref().clone());
+			}
 			IndexOperator::Order(reverse) => {
 				e.insert(
 					"operator",
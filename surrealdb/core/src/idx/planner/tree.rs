@@ -9,11 +9,13 @@ use surrealdb_types::ToSql;
 
 use crate::catalog::providers::TableProvider;
 use crate::catalog::{self, DatabaseId, Index, IndexDefinition, IndexId, NamespaceId};
+use crate::exec::expression_registry::resolve_order_by_alias;
 use crate::expr::operator::NearestNeighbor;
 use crate::expr::order::{OrderList, Ordering};
 use crate::expr::visit::MutVisitor;
 use crate::expr::{
-	BinaryOperator, Cond, Expr, FlowResultExt as _, Idiom, Kind, Literal, Order, Part, With,
+	BinaryOperator, Cond, Expr, FlowResultExt as _, Function, Idiom, Kind, Literal, Order, Part,
+	With,
 };
 use crate::idx::planner::StatementContext;
 use crate::idx::planner::executor::{
@@ -21,8 +23,9 @@ use crate::idx::planner::executor::{
 };
 use crate::idx::planner::plan::{IndexOperator, IndexOption};
 use crate::idx::planner::rewriter::KnnConditionRewriter;
+use crate::idx::trigram::{RegexLiterals, TrigramQuery};
 use crate::kvs::Transaction;
-use crate::val::{Array, Number, TableName, Value};
+use crate::val::{Array, Geometry, Number, Regex, TableName, Value};
 
 pub(super) struct Tree {
 	pub(super) root: Option<Node>,
@@ -145,9 +148,16 @@ impl<'a> TreeBuilder<'a> {
 	}
 
 	async fn eval_order(&mut self) -> Result<()> {
-		if let Some(o) = self.first_order
-			&& let Node::IndexedField(id, irf) = self.resolve_idiom(&o.value).await?
-		{
+		let Some(o) = self.first_order else {
+			return Ok(());
+		};
+		// The ORDER BY clause may reference the alias of a selected expression
+		let idiom = match self.ctx.fields.and_then(|f| resolve_order_by_alias(&o.value, f)) {
+			Some((Expr::Idiom(i), _)) => i,
+			Some((e, _)) => Idiom::expression(e),
+			None => o.value.clone(),
+		};
+		if let Node::IndexedField(id, irf) = self.resolve_idiom(&idiom).await? {
 			for (index_reference, id_col) in &irf {
 				if *id_col == 0 && index_reference.index.supports_order() {
 					self.index_map.order_limit = Some(IndexOption::new(
@@ -164,7 +174,7 @@ impl<'a> TreeBuilder<'a> {
 	}
 
 	async fn eval_cond(&mut self, stk: &mut Stk, cond: &Cond) -> Result<()> {
-		self.root = Some(self.eval_value(stk, 0, &cond.0).await?);
+		self.root = Some(self.eval_predicate(stk, 0, &cond.0).await?);
 		self.knn_condition = if self.knn_expressions.is_empty() {
 			None
 		} else {
@@ -217,9 +227,30 @@ impl<'a> TreeBuilder<'a> {
 				if let Some(re) = self.resolved_expressions.get(v).cloned() {
 					return Ok(re.into());
 				}
+				// Is the expression the column of an expression index?
+				if !matches!(op, BinaryOperator::And | BinaryOperator::Or)
+					&& let Some(n) = self.resolve_expression(v).await?
+				{
+					self.leaf_nodes_count += 1;
+					return Ok(n);
+				}
 				self.check_boolean_operator(group, op);
-				let left_node = stk.run(|stk| self.eval_value(stk, group, left)).await?;
-				let right_node = stk.run(|stk| self.eval_value(stk, group, right)).await?;
+				// Is it a distance-bounded query on a spatial index?
+				if let Some(n) = self.eval_spatial_distance(stk, group, v, left, op, right).await? {
+					return Ok(n);
+				}
+				let (left_node, right_node) =
+					if matches!(op, BinaryOperator::And | BinaryOperator::Or) {
+						(
+							stk.run(|stk| self.eval_predicate(stk, group, left)).await?,
+							stk.run(|stk| self.eval_predicate(stk, group, right)).await?,
+						)
+					} else {
+						(
+							stk.run(|stk| self.eval_value(stk, group, left)).await?,
+							stk.run(|stk| self.eval_value(stk, group, right)).await?,
+						)
+					};
 				// If both values are computable, then we can delegate the computation to the parent
 				if left_node == Node::Computable && right_node == Node::Computable {
 					return Ok(Node::Computable);
@@ -278,18 +309,36 @@ impl<'a> TreeBuilder<'a> {
 				| Literal::None
 				| Literal::Null
 				| Literal::Decimal(_)
-				| Literal::Float(_),
+				| Literal::Float(_)
+				| Literal::Geometry(_),
 			)
-			| Expr::Param(_)
-			| Expr::FunctionCall(_) => {
+			| Expr::Param(_) => {
 				self.leaf_nodes_count += 1;
 				Ok(Node::Computable)
 			}
+			Expr::FunctionCall(_) => {
+				self.leaf_nodes_count += 1;
+				// Is the function call the column of an expression index?
+				if let Some(n) = self.resolve_expression(v).await? {
+					return Ok(n);
+				}
+				Ok(Node::Computable)
+			}
 			Expr::Literal(Literal::Array(a)) => self.eval_array(stk, a).await,
 			_ => Ok(Node::Unsupported(format!("Unsupported expression: {}", v.to_sql()))),
 		}
 	}
 
+	/// Evaluates a term of the condition. String predicates are only resolved
+	/// here, as an index can only answer them when they are not the operand
+	/// of another expression.
+	async fn eval_predicate(&mut self, stk: &mut Stk, group: GroupRef, v: &Expr) -> Result<Node> {
+		if let Some(n) = self.eval_string_predicate(stk, group, v).await? {
+			return Ok(n);
+		}
+		self.eval_value(stk, group, v).await
+	}
+
 	async fn compute(&self, stk: &mut Stk, v: &Expr, n: Node) -> Result<Node> {
 		Ok(if n == Node::Computable {
 			match stk.run(|stk| v.compute(stk, self.ctx.ctx, self.ctx.opt, None)).await {
@@ -359,6 +408,21 @@ impl<'a> TreeBuilder<'a> {
 		Ok(n)
 	}
 
+	/// Matches an expression against the columns of the expression indexes
+	async fn resolve_expression(&mut self, e: &Expr) -> Result<Option<Node>> {
+		let tx = self.ctx.ctx.tx();
+		let schema = self.lazy_load_schema_resolver(&tx, self.table).await?;
+		if !schema.indexes.iter().any(|ix| ix.cols.iter().any(|c| c.is_expression())) {
+			return Ok(None);
+		}
+		let i = Idiom::expression(e.clone());
+		let irs = self.resolve_indexes(self.table, &i, &schema);
+		if irs.is_empty() {
+			return Ok(None);
+		}
+		Ok(Some(Node::IndexedField(Arc::new(i), irs)))
+	}
+
 	fn resolve_indexes(&mut self, t: &str, i: &Idiom, schema: &SchemaCache) -> LocalIndexRefs {
 		// Did we already resolve this idiom?
 		if let Some(m) = self.idioms_indexes.get(t)
@@ -371,6 +435,10 @@ impl<'a> TreeBuilder<'a> {
 			if ix.prepare_remove {
 				continue;
 			}
+			// A partial index can only be used if the query condition implies its condition
+			if ix.cond.is_some() && (t != self.table.as_str() || !ix.is_implied_by(self.ctx.cond)) {
+				continue;
+			}
 			if let Some(idiom_index) = ix.cols.iter().position(|p| p.eq(i)) {
 				let ixr = schema.new_reference(idx);
 				// Check if the WITH clause allows the index to be used
@@ -525,6 +593,8 @@ impl<'a> TreeBuilder<'a> {
 					..
 				} if *col == 0 => Self::eval_matches_operator(op, n),
 				Index::Hnsw(_) if *col == 0 => self.eval_hnsw_knn(e, op, n)?,
+				Index::Ivf(_) if *col == 0 => self.eval_ivf_knn(e, op, n)?,
+				Index::Spatial if *col == 0 => self.eval_spatial_operator(e, op, n, p),
 				_ => None,
 			};
 			if res.is_none()
@@ -566,17 +636,292 @@ impl<'a> TreeBuilder<'a> {
 		let BinaryOperator::NearestNeighbor(nn) = op else {
 			return Ok(None);
 		};
-		let NearestNeighbor::Approximate(k, ef) = &**nn else {
+		let Node::Computed(v) = n else {
 			return Ok(None);
 		};
+		match &**nn {
+			NearestNeighbor::Approximate(k, ef) => {
+				let vec: Arc<Vec<Number>> = Arc::new(v.as_ref().clone().coerce_to()?);
+				self.knn_expressions.insert(exp.clone());
+				Ok(Some(IndexOperator::Ann(vec, *k, *ef)))
+			}
+			NearestNeighbor::MultiVector(k, ef, agg) => {
+				let vecs = Arc::new(Self::query_vectors(v)?);
+				self.knn_expressions.insert(exp.clone());
+				Ok(Some(IndexOperator::MultiAnn(vecs, *k, *ef, *agg)))
+			}
+			_ => Ok(None),
+		}
+	}
 
-		if let Node::Computed(v) = n {
-			let vec: Arc<Vec<Number>> = Arc::new(v.as_ref().clone().coerce_to()?);
-			self.knn_expressions.insert(exp.clone());
-			return Ok(Some(IndexOperator::Ann(vec, *k, *ef)));
+	fn eval_ivf_knn(
+		&mut self,
+		exp: &Arc<Expr>,
+		op: &BinaryOperator,
+		n: &Node,
+	) -> Result<Option<IndexOperator>> {
+		let BinaryOperator::NearestNeighbor(nn) = op else {
+			return Ok(None);
+		};
+		let Node::Computed(v) = n else {
+			return Ok(None);
+		};
+		let NearestNeighbor::KTree(k) = &**nn else {
+			return Ok(None);
+		};
+		let vec: Arc<Vec<Number>> = Arc::new(v.as_ref().clone().coerce_to()?);
+		self.knn_expressions.insert(exp.clone());
+		Ok(Some(IndexOperator::IvfKnn(vec, *k)))
+	}
+
+	/// The query of an aggregated KNN search is either a single vector, or an
+	/// array of vectors.
+	fn query_vectors(v: &Value) -> Result<Vec<Vec<Number>>> {
+		if let Value::Array(a) = v
+			&& !a.is_empty()
+			&& a.iter().all(|v| matches!(v, Value::Array(_)))
+		{
+			let mut vecs = Vec::with_capacity(a.len());
+			for v in a.iter() {
+				vecs.push(v.clone().coerce_to()?);
+			}
+			return Ok(vecs);
 		}
+		Ok(vec![v.clone().coerce_to()?])
+	}
 
-		Ok(None)
+	fn eval_spatial_operator(
+		&mut self,
+		exp: &Arc<Expr>,
+		op: &BinaryOperator,
+		n: &Node,
+		p: IdiomPosition,
+	) -> Option<IndexOperator> {
+		let v = n.is_computed()?;
+		match (op, v.as_ref(), p) {
+			(
+				BinaryOperator::Inside | BinaryOperator::Outside,
+				Value::Geometry(_),
+				IdiomPosition::Left,
+			)
+			| (BinaryOperator::Intersects, Value::Geometry(_), _) => {
+				Some(IndexOperator::Spatial(op.clone(), v))
+			}
+			(BinaryOperator::NearestNeighbor(nn), Value::Geometry(Geometry::Point(_)), _) => {
+				let NearestNeighbor::KTree(k) = &**nn else {
+					return None;
+				};
+				self.knn_expressions.insert(exp.clone());
+				Some(IndexOperator::SpatialKnn(v, *k))
+			}
+			_ => None,
+		}
+	}
+
+	/// Resolves a distance-bounded query, `geo::distance(field, point) < distance`,
+	/// using a spatial index on the field.
+	async fn eval_spatial_distance(
+		&mut self,
+		stk: &mut Stk,
+		group: GroupRef,
+		v: &Expr,
+		left: &Expr,
+		op: &BinaryOperator,
+		right: &Expr,
+	) -> Result<Option<Node>> {
+		let (f, d) = match (left, op, right) {
+			(
+				Expr::FunctionCall(f),
+				BinaryOperator::LessThan | BinaryOperator::LessThanEqual,
+				d,
+			)
+			| (
+				d,
+				BinaryOperator::MoreThan | BinaryOperator::MoreThanEqual,
+				Expr::FunctionCall(f),
+			) => (f, d),
+			_ => return Ok(None),
+		};
+		if !matches!(&f.receiver, Function::Normal(n) if n == "geo::distance") {
+			return Ok(None);
+		}
+		let (id, point) = match f.arguments.as_slice() {
+			[Expr::Idiom(id), point] | [point, Expr::Idiom(id)] => (id, point),
+			_ => return Ok(None),
+		};
+		if let Some(Part::Start(_)) = id.0.first() {
+			return Ok(None);
+		}
+		let node = match self.resolved_idioms.get(id).cloned() {
+			Some(node) => node,
+			None => self.resolve_idiom(id).await?,
+		};
+		let Node::IndexedField(id, irs) = &node else {
+			return Ok(None);
+		};
+		let Some((ixr, _)) =
+			irs.iter().find(|(ixr, col)| *col == 0 && matches!(ixr.index, Index::Spatial))
+		else {
+			return Ok(None);
+		};
+		let Node::Computed(point) = self.compute(stk, point, Node::Computable).await? else {
+			return Ok(None);
+		};
+		let Node::Computed(d) = self.compute(stk, d, Node::Computable).await? else {
+			return Ok(None);
+		};
+		let (Value::Geometry(Geometry::Point(_)), Value::Number(n)) = (point.as_ref(), d.as_ref())
+		else {
+			return Ok(None);
+		};
+		// The field and the bounds are the leaves of the expression
+		self.leaf_nodes_count += 2;
+		let exp = Arc::new(v.clone());
+		let io = IndexOption::new(
+			ixr.clone(),
+			Some(id.clone()),
+			IdiomPosition::Left,
+			IndexOperator::SpatialDistance(point.clone(), *n),
+		);
+		self.index_map.options.push((exp.clone(), io.clone()));
+		let re = ResolvedExpression {
+			group,
+			exp: exp.clone(),
+			io: Some(io),
+			left: Arc::new(node.clone()),
+			right: Arc::new(Node::Computed(d)),
+		};
+		self.resolved_expressions.insert(exp, re.clone());
+		Ok(Some(re.into()))
+	}
+
+	/// Resolves a string predicate on an indexed field: a prefix match, with
+	/// `string::starts_with` or an anchored regex, using a range scan over a
+	/// standard or unique index, or a substring or regex match, with
+	/// `string::contains`, `string::matches` or `field = /regex/`, using a
+	/// trigram index.
+	async fn eval_string_predicate(
+		&mut self,
+		stk: &mut Stk,
+		group: GroupRef,
+		v: &Expr,
+	) -> Result<Option<Node>> {
+		let (id, pattern, predicate) = match v {
+			Expr::FunctionCall(f) => {
+				let Function::Normal(name) = &f.receiver else {
+					return Ok(None);
+				};
+				let predicate = match name.as_str() {
+					"string::starts_with" => StringPredicate::StartsWith,
+					"string::contains" => StringPredicate::Contains,
+					"string::matches" => StringPredicate::Matches,
+					_ => return Ok(None),
+				};
+				let [Expr::Idiom(id), pattern] = f.arguments.as_slice() else {
+					return Ok(None);
+				};
+				(id, pattern, predicate)
+			}
+			Expr::Binary {
+				left,
+				op: BinaryOperator::Equal,
+				right,
+			} => match (left.as_ref(), right.as_ref()) {
+				(Expr::Idiom(id), pattern @ Expr::Literal(Literal::Regex(_)))
+				| (pattern @ Expr::Literal(Literal::Regex(_)), Expr::Idiom(id)) => {
+					(id, pattern, StringPredicate::Matches)
+				}
+				_ => return Ok(None),
+			},
+			_ => return Ok(None),
+		};
+		if let Some(Part::Start(_)) = id.0.first() {
+			return Ok(None);
+		}
+		// Did we already compute the same expression?
+		if let Some(re) = self.resolved_expressions.get(v).cloned() {
+			return Ok(Some(re.into()));
+		}
+		let node = match self.resolved_idioms.get(id).cloned() {
+			Some(node) => node,
+			None => self.resolve_idiom(id).await?,
+		};
+		let Node::IndexedField(id, irs) = &node else {
+			return Ok(None);
+		};
+		let Node::Computed(pattern) = self.compute(stk, pattern, Node::Computable).await? else {
+			return Ok(None);
+		};
+		let pattern = match (predicate, pattern.as_ref()) {
+			(StringPredicate::StartsWith | StringPredicate::Contains, Value::String(_))
+			| (StringPredicate::Matches, Value::Regex(_)) => pattern,
+			(StringPredicate::Matches, Value::String(s)) => match s.parse::<Regex>() {
+				Ok(r) => Arc::new(Value::Regex(r)),
+				Err(_) => return Ok(None),
+			},
+			_ => return Ok(None),
+		};
+		let Some((ixr, iop)) = irs.iter().filter(|(_, col)| *col == 0).find_map(|(ixr, _)| {
+			Self::eval_string_operator(ixr, predicate, &pattern).map(|iop| (ixr, iop))
+		}) else {
+			return Ok(None);
+		};
+		// The function call, or the comparison, is the leaf of the expression
+		self.leaf_nodes_count += 1;
+		let exp = Arc::new(v.clone());
+		let io = IndexOption::new(ixr.clone(), Some(id.clone()), IdiomPosition::Left, iop);
+		self.index_map.options.push((exp.clone(), io.clone()));
+		let re = ResolvedExpression {
+			group,
+			exp: exp.clone(),
+			io: Some(io),
+			left: Arc::new(node.clone()),
+			right: Arc::new(Node::Computed(pattern)),
+		};
+		self.resolved_expressions.insert(exp, re.clone());
+		Ok(Some(re.into()))
+	}
+
+	/// Returns the operator answering a string predicate with the given index
+	fn eval_string_operator(
+		ixr: &IndexReference,
+		predicate: StringPredicate,
+		pattern: &Arc<Value>,
+	) -> Option<IndexOperator> {
+		match (&ixr.index, predicate, pattern.as_ref()) {
+			(Index::Idx | Index::Uniq, StringPredicate::StartsWith, Value::String(p)) => {
+				Self::prefix_range(p)
+			}
+			(Index::Idx | Index::Uniq, StringPredicate::Matches, Value::Regex(r)) => {
+				Self::prefix_range(&RegexLiterals::new(r.inner().as_str()).prefix?)
+			}
+			(Index::Trigram, StringPredicate::Contains | StringPredicate::Matches, _) => {
+				let trigrams = TrigramQuery::new(pattern)?.trigrams();
+				(!trigrams.is_empty()).then(|| IndexOperator::Trigram(trigrams, pattern.clone()))
+			}
+			_ => None,
+		}
+	}
+
+	/// Returns the range of the strings starting with a prefix. The upper
+	/// bound is the prefix with its last character incremented, and is
+	/// omitted when no such string exists.
+	fn prefix_range(prefix: &str) -> Option<IndexOperator> {
+		if prefix.is_empty() {
+			return None;
+		}
+		let mut ranges =
+			vec![(BinaryOperator::MoreThanEqual, Arc::new(Value::from(prefix.to_owned())))];
+		let mut end: Vec<char> = prefix.chars().collect();
+		while let Some(c) = end.pop() {
+			if let Some(n) = (c as u32 + 1..=char::MAX as u32).find_map(char::from_u32) {
+				end.push(n);
+				let end: String = end.into_iter().collect();
+				ranges.push((BinaryOperator::LessThan, Arc::new(Value::from(end))));
+				break;
+			}
+		}
+		Some(IndexOperator::Range(vec![], ranges))
 	}
 
 	fn eval_bruteforce_knn(&mut self, id: &Idiom, val: &Node, exp: &Arc<Expr>) -> Result<()> {
@@ -876,6 +1221,17 @@ impl IdiomPosition {
 	}
 }
 
+/// A string predicate which may be answered by an index
+#[derive(Clone, Copy)]
+enum StringPredicate {
+	/// `string::starts_with(field, prefix)`
+	StartsWith,
+	/// `string::contains(field, substring)`
+	Contains,
+	/// `string::matches(field, regex)` or `field = /regex/`
+	Matches,
+}
+
 #[derive(Clone)]
 struct ResolvedExpression {
 	group: GroupRef,
@@ -0,0 +1,164 @@
+//! Geohash cells covering bounding boxes.
+//!
+//! A cell of precision `p` is identified by a geohash of `p` characters, each
+//! character encoding 5 bits which alternately split the longitude and the
+//! latitude ranges in two. The cell of precision 0 is the empty geohash, which
+//! covers the whole globe. The geohash of a cell is a prefix of the geohash of
+//! every cell it contains.
+
+use geo::{Point, Rect, coord};
+
+/// The highest precision of a cell, about 1.2m by 0.6m
+pub(super) const MAX_PRECISION: usize = 10;
+
+/// The mean radius of the Earth, in meters, as used by `geo::distance`
+pub(super) const EARTH_RADIUS: f64 = 6_371_008.8;
+
+const BASE32: &[u8; 32] = b"0123456789bcdefghjkmnpqrstuvwxyz";
+
+/// Returns the number of bits splitting the longitude and the latitude ranges
+fn bits(precision: usize) -> (u32, u32) {
+	let bits = (precision * 5) as u32;
+	(bits.div_ceil(2), bits / 2)
+}
+
+/// Returns the position, along an axis divided in `2^bits` slices, of the
+/// slice containing a coordinate.
+///
+/// The positions computed for different numbers of bits are consistent: the
+/// position of the slice containing a coordinate, shifted right by `n` bits,
+/// is its position in the axis divided in `2^(bits-n)` slices.
+fn position(v: f64, min: f64, max: f64, bits: u32) -> u64 {
+	let n = 1u64 << bits;
+	let p = ((v - min) / (max - min) * n as f64).floor();
+	if p > 0.0 {
+		(p as u64).min(n - 1)
+	} else {
+		0
+	}
+}
+
+/// Encodes the geohash of the cell at the given positions
+fn encode(x: u64, y: u64, precision: usize) -> String {
+	let (mut xb, mut yb) = bits(precision);
+	let mut hash = String::with_capacity(precision);
+	let mut c = 0;
+	for b in 0..precision * 5 {
+		let bit = if b % 2 == 0 {
+			xb -= 1;
+			(x >> xb) & 1
+		} else {
+			yb -= 1;
+			(y >> yb) & 1
+		};
+		c = (c << 1) | bit as usize;
+		if b % 5 == 4 {
+			hash.push(BASE32[c] as char);
+			c = 0;
+		}
+	}
+	hash
+}
+
+/// Returns the geohashes of the cells covering a bounding box, using the
+/// highest precision for which at most `max_cells` cells are required.
+pub(super) fn covering(rect: &Rect<f64>, max_cells: u64) -> Vec<String> {
+	for precision in (1..=MAX_PRECISION).rev() {
+		let (xb, yb) = bits(precision);
+		let x0 = position(rect.min().x, -180.0, 180.0, xb);
+		let x1 = position(rect.max().x, -180.0, 180.0, xb);
+		let y0 = position(rect.min().y, -90.0, 90.0, yb);
+		let y1 = position(rect.max().y, -90.0, 90.0, yb);
+		if (x1 - x0 + 1) * (y1 - y0 + 1) <= max_cells {
+			let mut cells = Vec::with_capacity(max_cells as usize);
+			for x in x0..=x1 {
+				for y in y0..=y1 {
+					cells.push(encode(x, y, precision));
+				}
+			}
+			return cells;
+		}
+	}
+	vec![String::new()]
+}
+
+/// Returns a bounding box containing every point within a distance, in meters,
+/// of a point, or `None` if the bounding box would cover every longitude.
+///
+/// See <http://janmatuschek.de/LatitudeLongitudeBoundingCoordinates>
+pub(super) fn distance_rect(p: Point<f64>, distance: f64) -> Option<Rect<f64>> {
+	// Allow for rounding errors
+	let angle = distance.max(0.0) / EARTH_RADIUS * (1.0 + 1e-9);
+	let lat = p.y().to_radians();
+	let min_lat = lat - angle;
+	let max_lat = lat + angle;
+	if min_lat <= -std::f64::consts::FRAC_PI_2 || max_lat >= std::f64::consts::FRAC_PI_2 {
+		// A pole is within the distance
+		return None;
+	}
+	let delta_lon = (angle.sin() / lat.cos()).asin().to_degrees();
+	let min_lon = p.x() - delta_lon;
+	let max_lon = p.x() + delta_lon;
+	if min_lon < -180.0 || max_lon > 180.0 {
+		// The antimeridian is within the distance
+		return None;
+	}
+	Some(Rect::new(
+		coord! {
+			x: min_lon,
+			y: min_lat.to_degrees(),
+		},
+		coord! {
+			x: max_lon,
+			y: max_lat.to_degrees(),
+		},
+	))
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn rect(min: (f64, f64), max: (f64, f64)) -> Rect<f64> {
+		Rect::new(
+			coord! {
+				x: min.0,
+				y: min.1,
+			},
+			coord! {
+				x: max.0,
+				y: max.1,
+			},
+		)
+	}
+
+	#[test]
+	fn point_cell() {
+		// The geohash of a point
+		let cells = covering(&rect((-5.6, 42.6), (-5.6, 42.6)), 4);
+		assert_eq!(cells, vec!["ezs42e44yx".to_string()]);
+	}
+
+	#[test]
+	fn covering_cells() {
+		let cells = covering(&rect((-0.5, -0.5), (0.5, 0.5)), 4);
+		assert_eq!(cells.len(), 4);
+		// Every cell has the same precision
+		assert!(cells.iter().all(|c| c.len() == cells[0].len()));
+		// A cell is a prefix of the cell of any point it contains
+		let point = covering(&rect((0.2, -0.3), (0.2, -0.3)), 1);
+		assert!(cells.iter().any(|c| point[0].starts_with(c.as_str())));
+		// The whole globe is covered by the root cell
+		let cells = covering(&rect((-180.0, -90.0), (180.0, 90.0)), 16);
+		assert_eq!(cells, vec![String::new()]);
+	}
+
+	#[test]
+	fn distance() {
+		let r = distance_rect(Point::new(0.0, 0.0), 111_195.0).unwrap();
+		assert!((r.max().y - 1.0).abs() < 1e-3);
+		assert!((r.min().x + 1.0).abs() < 1e-3);
+		assert!(distance_rect(Point::new(0.0, 89.0), 200_000.0).is_none());
+		assert!(distance_rect(Point::new(179.9, 0.0), 20_000.0).is_none());
+	}
+}
@@ -0,0 +1,241 @@
+//! Spatial indexes over geometries.
+//!
+//! Each geometry is stored under the geohash cells covering its bounding box
+//! (see [`crate::key::index::sc`]), using the highest precision for which at
+//! most [`MAX_INDEX_CELLS`] cells are required. A point is then stored in a
+//! single small cell, while a large polygon is stored in a few large cells.
+//!
+//! A query scans the cells which may hold a geometry whose bounding box
+//! intersects the area of the query, and checks each stored geometry against
+//! the query. Only the records with an indexed geometry can be returned.
+
+pub(crate) mod cell;
+
+use std::collections::{BTreeSet, HashMap, HashSet, VecDeque};
+use std::sync::Arc;
+
+use anyhow::Result;
+use geo::{BoundingRect, Distance, Haversine, Point, Rect};
+use reblessive::tree::Stk;
+
+use crate::catalog::Record;
+use crate::catalog::providers::TableProvider;
+use crate::cnf::NORMAL_FETCH_SIZE;
+use crate::ctx::FrozenContext;
+use crate::dbs::Options;
+use crate::doc::CursorDoc;
+use crate::expr::{Cond, FlowResultExt as _};
+use crate::idx::IndexKeyBase;
+use crate::idx::planner::iterators::KnnIteratorResult;
+use crate::key::index::sc::Sc;
+use crate::kvs::{KVValue, Key, Transaction};
+use crate::val::{Geometry, RecordId, RecordIdKey};
+
+/// The maximum number of cells a geometry is stored under
+const MAX_INDEX_CELLS: u64 = 4;
+
+/// The maximum number of cells covering the area of a query
+const MAX_QUERY_CELLS: u64 = 16;
+
+/// The radius, in meters, of the first area searched for the nearest points
+const INITIAL_KNN_RADIUS: f64 = 1_000.0;
+
+/// Returns the bounding box of a geometry, or `None` if it is empty
+fn bounding_rect(g: &Geometry) -> Option<Rect<f64>> {
+	geo::Geometry::from(g.clone()).bounding_rect()
+}
+
+/// Returns the cells a geometry is stored under
+pub(crate) fn index_cells(g: &Geometry) -> Vec<String> {
+	match bounding_rect(g) {
+		Some(rect) => cell::covering(&rect, MAX_INDEX_CELLS),
+		// An empty geometry is stored in the root cell
+		None => vec![String::new()],
+	}
+}
+
+/// A query answered by a spatial index
+#[derive(Debug, Clone)]
+pub(crate) enum SpatialQuery {
+	/// The geometries inside a geometry
+	Inside(Geometry),
+	/// The geometries intersecting a geometry
+	Intersects(Geometry),
+	/// The geometries not intersecting a geometry
+	Outside(Geometry),
+	/// The points within a distance, in meters, of a point
+	Distance(Point<f64>, f64),
+}
+
+impl SpatialQuery {
+	/// Returns the bounding box of the area containing every match, or `None`
+	/// if the whole index must be scanned
+	fn bounding_rect(&self) -> Option<Rect<f64>> {
+		match self {
+			Self::Inside(g) | Self::Intersects(g) => bounding_rect(g),
+			Self::Outside(_) => None,
+			Self::Distance(p, d) => cell::distance_rect(*p, *d),
+		}
+	}
+
+	/// Checks if an indexed geometry matches the query
+	pub(crate) fn matches(&self, g: &Geometry) -> bool {
+		match self {
+			Self::Inside(a) => a.contains(g),
+			Self::Intersects(a) => g.intersects(a),
+			Self::Outside(a) => !g.intersects(a),
+			Self::Distance(p, d) => match g {
+				Geometry::Point(q) => Haversine.distance(*p, *q) <= *d,
+				_ => false,
+			},
+		}
+	}
+
+	/// Returns the key ranges containing every geometry whose bounding box may
+	/// intersect the area of the query.
+	///
+	/// A geometry whose bounding box intersects the area is either stored in a
+	/// cell contained by one of the cells covering the area, or in a cell
+	/// containing one of them.
+	pub(crate) fn ranges(&self, ikb: &IndexKeyBase) -> Result<VecDeque<(Key, Key)>> {
+		let cells = match self.bounding_rect() {
+			Some(rect) => cell::covering(&rect, MAX_QUERY_CELLS),
+			None => vec![String::new()],
+		};
+		let parents: BTreeSet<&str> =
+			cells.iter().flat_map(|c| (0..c.len()).map(move |l| &c[..l])).collect();
+		let mut ranges = VecDeque::with_capacity(parents.len() + cells.len());
+		for p in parents {
+			ranges.push_back(ikb.new_sc_cell_range(p)?);
+		}
+		for c in &cells {
+			ranges.push_back(ikb.new_sc_prefix_range(c)?);
+		}
+		Ok(ranges)
+	}
+}
+
+/// Scans the next entries of a key range of a spatial index, returning the id
+/// of the record and the geometry of each entry. The beginning of the range is
+/// moved past the returned entries.
+pub(crate) async fn next_scan(
+	tx: &Transaction,
+	range: &mut (Key, Key),
+	limit: u32,
+) -> Result<Vec<(RecordIdKey, Geometry)>> {
+	let res = tx.scan(range.0.clone()..range.1.clone(), limit, 0, None).await?;
+	if let Some((last, _)) = res.last() {
+		range.0.clone_from(last);
+		range.0.push(0x00);
+	}
+	let mut entries = Vec::with_capacity(res.len());
+	for (k, v) in res {
+		let id = Sc::decode_key(&k)?.id.into_owned();
+		entries.push((id, Geometry::kv_decode_value(v)?));
+	}
+	Ok(entries)
+}
+
+/// Searches the `k` points nearest to a point, ordered by their haversine
+/// distance in meters.
+///
+/// When a condition is given, only the records satisfying it are returned.
+/// The searched area starts with a small radius which grows until enough
+/// points are found within it.
+pub(crate) async fn knn_search(
+	stk: &mut Stk,
+	ctx: &FrozenContext,
+	opt: &Options,
+	ikb: &IndexKeyBase,
+	point: Point<f64>,
+	k: usize,
+	cond: Option<Arc<Cond>>,
+) -> Result<VecDeque<KnnIteratorResult>> {
+	let tx = ctx.tx();
+	let mut filter = cond.map(|cond| (cond, HashMap::new()));
+	let mut radius = INITIAL_KNN_RADIUS;
+	loop {
+		let query = SpatialQuery::Distance(point, radius);
+		let whole = query.bounding_rect().is_none();
+		// Collect the points within the radius
+		let mut seen = HashSet::new();
+		let mut points = Vec::new();
+		for mut range in query.ranges(ikb)? {
+			loop {
+				let entries = next_scan(&tx, &mut range, *NORMAL_FETCH_SIZE).await?;
+				if entries.is_empty() {
+					break;
+				}
+				for (id, g) in entries {
+					if let Geometry::Point(p) = g
+						&& seen.insert(id.clone())
+					{
+						let d = Haversine.distance(point, p);
+						if whole || d <= radius {
+							points.push((d, id));
+						}
+					}
+				}
+			}
+		}
+		points.sort_by(|a, b| a.0.total_cmp(&b.0));
+		// Keep the nearest records satisfying the condition
+		let mut res = VecDeque::with_capacity(k);
+		for (d, id) in points {
+			if res.len() == k {
+				break;
+			}
+			if ctx.is_done(None).await? {
+				return Ok(res);
+			}
+			let rid = Arc::new(RecordId::new(ikb.table().clone(), id));
+			let record = match &mut filter {
+				Some((cond, cache)) => {
+					let record = match cache.get(&rid.key) {
+						Some(record) => Option::clone(record),
+						None => {
+							let record = is_record_truthy(stk, ctx, opt, ikb, cond, &rid).await?;
+							cache.insert(rid.key.clone(), record.clone());
+							record
+						}
+					};
+					match record {
+						Some(record) => Some(record),
+						None => continue,
+					}
+				}
+				None => None,
+			};
+			res.push_back((rid, d, record));
+		}
+		if res.len() == k || whole {
+			return Ok(res);
+		}
+		radius *= 4.0;
+	}
+}
+
+/// Fetches a record and evaluates the condition against it, returning the
+/// record if the condition is truthy
+pub(crate) async fn is_record_truthy(
+	stk: &mut Stk,
+	ctx: &FrozenContext,
+	opt: &Options,
+	ikb: &IndexKeyBase,
+	cond: &Cond,
+	rid: &Arc<RecordId>,
+) -> Result<Option<Arc<Record>>> {
+	let val = ctx.tx().get_record(ikb.ns(), ikb.db(), &rid.table, &rid.key, None).await?;
+	if val.data.is_nullish() {
+		return Ok(None);
+	}
+	let doc = CursorDoc {
+		rid: Some(rid.clone()),
+		ir: None,
+		doc: val.into(),
+		fields_computed: false,
+	};
+	let truthy =
+		stk.run(|stk| cond.0.compute(stk, ctx, opt, Some(&doc))).await.catch_return()?.is_truthy();
+	Ok(truthy.then(|| doc.doc.into_read_only()))
+}
@@ -1,16 +1,37 @@
+use std::sync::Arc;
+
 use anyhow::Result;
+use quick_cache::Weighter;
+use quick_cache::sync::Cache;
 
 use crate::catalog::{Distance, IndexId, TableId};
+use crate::cnf;
 use crate::idx::IndexKeyBase;
 use crate::idx::trees::hnsw::ElementId;
 use crate::idx::trees::hnsw::cache::VectorCache;
+use crate::idx::trees::hnsw::quantizer::{
+	PQ_RETRAINING_LIMIT, PQ_TRAINING_SIZE, QuantizedQuery, QuantizedVector, Quantizer,
+};
 use crate::idx::trees::vector::{SerializedVector, SharedVector, Vector};
 use crate::kvs::Transaction;
 
+/// Weighter for the quantized codes, based on their memory usage.
+#[derive(Clone)]
+struct CodeWeighter;
+
+impl Weighter<ElementId, Arc<QuantizedVector>> for CodeWeighter {
+	fn weight(&self, key: &ElementId, val: &Arc<QuantizedVector>) -> u64 {
+		(val.mem_size() + std::mem::size_of_val(key)) as u64
+	}
+}
+
 /// Manages storage and retrieval of element vectors in the HNSW graph.
 ///
 /// Vectors are stored in the key-value store and cached in-memory via
 /// [`VectorCache`] for fast distance computations during graph traversal.
+/// When the index is quantized, searches traverse the graph on compressed
+/// codes, which are stored alongside the vectors and cached in a bounded
+/// cache, and the full-precision vectors are not cached.
 pub(super) struct HnswElements {
 	/// The table this index belongs to.
 	table_id: TableId,
@@ -24,6 +45,10 @@ pub(super) struct HnswElements {
 	next_element_id: ElementId,
 	/// Distance metric for similarity computations.
 	dist: Distance,
+	/// Optional quantizer compressing the vectors traversed by searches.
+	quantizer: Option<Quantizer>,
+	/// The cached compressed codes of the elements.
+	codes: Cache<ElementId, Arc<QuantizedVector>, CodeWeighter>,
 }
 
 impl HnswElements {
@@ -32,6 +57,7 @@ impl HnswElements {
 		table_id: TableId,
 		ikb: IndexKeyBase,
 		dist: Distance,
+		quantizer: Option<Quantizer>,
 		vector_cache: VectorCache,
 	) -> Self {
 		Self {
@@ -41,6 +67,8 @@ impl HnswElements {
 			vector_cache,
 			next_element_id: 0,
 			dist,
+			quantizer,
+			codes: Cache::with_weighter(1024, *cnf::HNSW_CODE_CACHE_SIZE, CodeWeighter),
 		}
 	}
 
@@ -61,19 +89,32 @@ impl HnswElements {
 	}
 
 	#[cfg(test)]
-	pub(super) async fn len(&self) -> usize {
+	pub(super) async fn len(&self, tx: &Transaction) -> usize {
+		if self.quantizer.is_some() {
+			let mut len = 0;
+			for e_id in 0..self.next_element_id {
+				if tx.exists(&self.ikb.new_he_key(e_id), None).await.unwrap() {
+					len += 1;
+				}
+			}
+			return len;
+		}
 		self.vector_cache.len(self.ikb.ns(), self.ikb.db(), self.table_id, self.index_id).await
 			as usize
 	}
 
 	#[cfg(test)]
-	pub(super) async fn contains(&self, e_id: ElementId) -> bool {
+	pub(super) async fn contains(&self, tx: &Transaction, e_id: ElementId) -> bool {
+		if self.quantizer.is_some() {
+			return tx.exists(&self.ikb.new_he_key(e_id), None).await.unwrap();
+		}
 		self.vector_cache
 			.contains(self.ikb.ns(), self.ikb.db(), self.table_id, self.index_id, e_id)
 			.await
 	}
 
-	/// Stores a vector in the key-value store and caches it. Returns the shared vector.
+	/// Stores a vector in the key-value store and caches it, or caches its code
+	/// when the index is quantized. Returns the shared vector.
 	pub(super) async fn insert(
 		&mut self,
 		tx: &Transaction,
@@ -83,6 +124,13 @@ impl HnswElements {
 	) -> Result<SharedVector> {
 		let key = self.ikb.new_he_key(id);
 		tx.set(&key, ser_vec, None).await?;
+		if let Some(qt) = &self.quantizer {
+			if let Some(code) = qt.encode(&vec) {
+				tx.set(&self.ikb.new_hc_key(id), &code, None).await?;
+				self.codes.insert(id, Arc::new(code));
+			}
+			return Ok(vec.into());
+		}
 		let pt: SharedVector = vec.into();
 		self.vector_cache
 			.insert(self.ikb.ns(), self.ikb.db(), self.table_id, self.index_id, id, pt.clone())
@@ -91,6 +139,7 @@ impl HnswElements {
 	}
 
 	/// Retrieves a vector by element ID, checking the cache first then the key-value store.
+	/// The vectors of a quantized index are not cached.
 	pub(super) async fn get_vector(
 		&self,
 		tx: &Transaction,
@@ -109,6 +158,9 @@ impl HnswElements {
 			Some(vec) => {
 				let vec = Vector::from(vec);
 				let vec: SharedVector = vec.into();
+				if self.quantizer.is_some() {
+					return Ok(Some(vec));
+				}
 				self.vector_cache
 					.insert(
 						self.ikb.ns(),
@@ -139,8 +191,128 @@ impl HnswElements {
 		Ok(self.get_vector(tx, e_id).await?.map(|r| self.dist.calculate(&r, q)))
 	}
 
+	/// Prepares a query for the traversal on compressed codes. Returns `None`
+	/// if the index is not quantized, or if its quantizer is not trained yet.
+	pub(super) fn encode_query(&self, q: &SharedVector) -> Option<QuantizedQuery> {
+		self.quantizer.as_ref().and_then(|qt| qt.encode_query(q))
+	}
+
+	/// Retrieves the compressed code of an element, checking the cache first
+	/// then the key-value store. An element without a stored code is encoded
+	/// from its full-precision vector, without filling the vector cache.
+	async fn get_code(
+		&self,
+		tx: &Transaction,
+		qt: &Quantizer,
+		e_id: &ElementId,
+	) -> Result<Option<Arc<QuantizedVector>>> {
+		if let Some(c) = self.codes.get(e_id) {
+			return Ok(Some(c));
+		}
+		let code = match tx.get(&self.ikb.new_hc_key(*e_id), None).await? {
+			Some(code) => code,
+			None => {
+				let Some(vec) = tx.get(&self.ikb.new_he_key(*e_id), None).await? else {
+					return Ok(None);
+				};
+				let Some(code) = qt.encode(&Vector::from(vec)) else {
+					return Ok(None);
+				};
+				code
+			}
+		};
+		let code = Arc::new(code);
+		self.codes.insert(*e_id, code.clone());
+		Ok(Some(code))
+	}
+
+	/// Computes the distance used to traverse the graph during a search: the
+	/// estimated distance to the compressed code when a prepared query is
+	/// given, otherwise the exact distance.
+	pub(super) async fn get_search_distance(
+		&self,
+		tx: &Transaction,
+		q: &SharedVector,
+		qq: Option<&QuantizedQuery>,
+		e_id: &ElementId,
+	) -> Result<Option<f64>> {
+		if let (Some(qq), Some(qt)) = (qq, &self.quantizer) {
+			return Ok(self.get_code(tx, qt, e_id).await?.map(|c| qt.distance(&self.dist, qq, &c)));
+		}
+		self.get_distance(tx, q, e_id).await
+	}
+
+	/// Loads the codebook of a product quantizer, trained by this or another
+	/// node, and drops the cached codes which were computed with the previous
+	/// codebook.
+	pub(super) async fn load_codebook(&mut self, tx: &Transaction) -> Result<()> {
+		if let Some(qt) = &mut self.quantizer
+			&& qt.is_product()
+			&& let Some(cb) = tx.get(&self.ikb.new_hq_key(), None).await?
+		{
+			qt.set_codebook(cb);
+			self.codes.clear();
+		}
+		Ok(())
+	}
+
+	/// Trains the codebook of a product quantizer once enough elements have
+	/// been inserted, and retrains it each time the number of elements has
+	/// doubled since, up to [`PQ_RETRAINING_LIMIT`]. The training samples are
+	/// spread over the elements, and the codes of every element are recomputed
+	/// with the new codebook.
+	///
+	/// Returns the number of elements the codebook was trained on, if it was.
+	pub(super) async fn train_if_required(
+		&mut self,
+		tx: &Transaction,
+		trained_on: ElementId,
+	) -> Result<Option<ElementId>> {
+		let Some(qt) = &self.quantizer else {
+			return Ok(None);
+		};
+		let count = self.next_element_id;
+		let required = if trained_on == 0 {
+			count as usize >= PQ_TRAINING_SIZE
+		} else {
+			count >= trained_on * 2 && count as usize <= PQ_RETRAINING_LIMIT
+		};
+		if !qt.is_product() || !required {
+			return Ok(None);
+		}
+		let step = (count as usize / PQ_TRAINING_SIZE).max(1);
+		let mut samples = Vec::with_capacity(PQ_TRAINING_SIZE);
+		for e_id in (0..count).step_by(step) {
+			if let Some(vec) = tx.get(&self.ikb.new_he_key(e_id), None).await? {
+				samples.push(Vector::from(vec));
+			}
+		}
+		let Some(cb) = qt.train(&samples) else {
+			return Ok(None);
+		};
+		tx.set(&self.ikb.new_hq_key(), &cb, None).await?;
+		let Some(qt) = &mut self.quantizer else {
+			return Ok(None);
+		};
+		qt.set_codebook(cb);
+		// Recompute the codes with the new codebook
+		self.codes.clear();
+		for e_id in 0..count {
+			if let Some(vec) = tx.get(&self.ikb.new_he_key(e_id), None).await?
+				&& let Some(code) = qt.encode(&Vector::from(vec))
+			{
+				tx.set(&self.ikb.new_hc_key(e_id), &code, None).await?;
+			}
+		}
+		Ok(Some(count))
+	}
+
 	/// Removes an element's vector from both the cache and the key-value store.
 	pub(super) async fn remove(&mut self, tx: &Transaction, e_id: ElementId) -> Result<()> {
+		if self.quantizer.is_some() {
+			self.codes.remove(&e_id);
+			tx.del(&self.ikb.new_hc_key(e_id)).await?;
+		}
 		self.vector_cache
 			.remove(self.ikb.ns(), self.ikb.db(), self.table_id, self.index_id, e_id)
 			.await;
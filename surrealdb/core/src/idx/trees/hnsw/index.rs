@@ -9,11 +9,13 @@ use reblessive::tree::Stk;
 use roaring::RoaringTreemap;
 use tokio::sync::RwLock;
 
-use crate::catalog::{Distance, HnswParams, TableId, VectorType};
+use crate::catalog::providers::TableProvider;
+use crate::catalog::{Distance, HnswParams, Record, TableId, VectorType};
 use crate::ctx::{Context, FrozenContext};
 use crate::dbs::Options;
 use crate::err::Error;
-use crate::expr::Cond;
+use crate::expr::operator::VectorAggregation;
+use crate::expr::{Cond, Idiom};
 use crate::idx::IndexKeyBase;
 use crate::idx::planner::ScanDirection;
 use crate::idx::planner::iterators::KnnIteratorResult;
@@ -51,6 +53,8 @@ pub(crate) struct HnswIndex {
 	ikb: IndexKeyBase,
 	/// The type of vector stored in this index.
 	vector_type: VectorType,
+	/// Whether each record holds an array of vectors.
+	multi_vector: bool,
 	/// The HNSW graph, protected by a read-write lock for concurrent access.
 	hnsw: RwLock<HnswFlavor>,
 	/// Vector-to-document mappings.
@@ -106,6 +110,7 @@ impl HnswIndex {
 		Ok(Self {
 			dim: p.dimension as usize,
 			vector_type: p.vector_type,
+			multi_vector: p.multi_vector,
 			distance: p.distance.clone(),
 			hnsw: RwLock::new(HnswFlavor::new(tb, ikb.clone(), p, vector_cache)?),
 			vec_docs: VecDocs::new(ikb.clone(), p.use_hashed_vector),
@@ -115,19 +120,31 @@ impl HnswIndex {
 	}
 
 	/// Converts content values into serialized vectors, validating dimensionality.
+	///
+	/// On a multi-vector index, each value is an array of vectors.
 	fn content_to_vectors(&self, content: Vec<Value>) -> Result<Vec<SerializedVector>> {
 		let mut vectors = Vec::with_capacity(content.len());
 		// Index the values
 		for value in content.into_iter().filter(|v| !v.is_nullish()) {
-			// Extract the vector
-			let vector = SerializedVector::try_from_value(self.vector_type, self.dim, value)?;
-			Vector::check_expected_dimension(vector.dimension(), self.dim)?;
-			// Insert the vector
-			vectors.push(vector);
+			match value {
+				Value::Array(a) if self.multi_vector => {
+					for value in a.0.into_iter().filter(|v| !v.is_nullish()) {
+						vectors.push(self.value_to_vector(value)?);
+					}
+				}
+				value => vectors.push(self.value_to_vector(value)?),
+			}
 		}
 		Ok(vectors)
 	}
 
+	/// Extracts a vector from a value, validating its dimensionality.
+	fn value_to_vector(&self, value: Value) -> Result<SerializedVector> {
+		let vector = SerializedVector::try_from_value(self.vector_type, self.dim, value)?;
+		Vector::check_expected_dimension(vector.dimension(), self.dim)?;
+		Ok(vector)
+	}
+
 	/// Enqueues a vector update for later application to the HNSW graph.
 	///
 	/// Converts old/new document values into serialized vectors, resolves the
@@ -334,6 +351,98 @@ impl HnswIndex {
 		Ok(res)
 	}
 
+	/// Performs a k-nearest neighbor search where the distance of a record
+	/// aggregates the distances between the query vectors and the vectors of
+	/// the record.
+	///
+	/// The candidates are the `ef` nearest records of each query vector. Each
+	/// candidate is then scored against all the vectors held by its indexed
+	/// field `col`, and the k nearest candidates are returned.
+	#[expect(clippy::too_many_arguments)]
+	pub(crate) async fn multi_knn_search(
+		&self,
+		ctx: &FrozenContext,
+		stk: &mut Stk,
+		col: &Idiom,
+		pts: &[Vec<Number>],
+		aggregation: VectorAggregation,
+		k: usize,
+		ef: usize,
+		cond_filter: Option<(&Options, Arc<Cond>)>,
+	) -> Result<VecDeque<KnnIteratorResult>> {
+		let mut queries = Vec::with_capacity(pts.len());
+		for pt in pts {
+			let vector = Vector::try_from_vector(self.vector_type, pt)?;
+			vector.check_dimension(self.dim)?;
+			queries.push(vector);
+		}
+		// Collect the candidates of each query vector
+		let mut candidates: HashMap<Arc<RecordId>, Option<Arc<Record>>> = HashMap::default();
+		for pt in pts {
+			let res = self.knn_search(ctx, stk, pt, k.max(ef), ef, cond_filter.clone()).await?;
+			for (rid, _, record) in res {
+				let entry = candidates.entry(rid).or_insert(None);
+				if entry.is_none() {
+					*entry = record;
+				}
+			}
+		}
+		// Score each candidate against all of its vectors
+		let tx = ctx.tx();
+		let mut scored = Vec::with_capacity(candidates.len());
+		for (rid, record) in candidates {
+			let record = match record {
+				Some(record) => record,
+				None => {
+					tx.get_record(self.ikb.0.ns, self.ikb.0.db, &rid.table, &rid.key, None).await?
+				}
+			};
+			let vectors = self.content_to_vectors(vec![record.data.pick(&col.0)])?;
+			if let Some(dist) = self.aggregate_distance(&queries, vectors, aggregation) {
+				scored.push((dist, rid, record));
+			}
+		}
+		scored.sort_by(|a, b| a.0.total_cmp(&b.0));
+		Ok(scored
+			.into_iter()
+			.take(k)
+			.map(|(dist, rid, record)| (rid, dist, Some(record)))
+			.collect())
+	}
+
+	/// Aggregates the distances between the query vectors and the vectors of
+	/// a record. Returns `None` when either side holds no vector.
+	fn aggregate_distance(
+		&self,
+		queries: &[Vector],
+		vectors: Vec<SerializedVector>,
+		aggregation: VectorAggregation,
+	) -> Option<f64> {
+		if queries.is_empty() || vectors.is_empty() {
+			return None;
+		}
+		let vectors: Vec<Vector> = vectors.into_iter().map(Vector::from).collect();
+		let mut sum_nearest = 0.0;
+		let mut min_nearest = f64::INFINITY;
+		let mut sum = 0.0;
+		for q in queries {
+			let mut nearest = f64::INFINITY;
+			for v in &vectors {
+				let d = self.distance.calculate(q, v);
+				nearest = nearest.min(d);
+				sum += d;
+			}
+			sum_nearest += nearest;
+			min_nearest = min_nearest.min(nearest);
+		}
+		let dist = match aggregation {
+			VectorAggregation::MaxSim => sum_nearest,
+			VectorAggregation::Max => min_nearest,
+			VectorAggregation::Mean => sum / (queries.len() * vectors.len()) as f64,
+		};
+		Some(dist)
+	}
+
 	/// Searches for nearest neighbors in the committed HNSW graph.
 	///
 	/// Acquires a read lock on the graph and performs KNN search, optionally
@@ -475,7 +584,7 @@ impl HnswIndex {
 	}
 
 	#[cfg(test)]
-	pub(super) async fn check_hnsw_properties(&self, expected_count: usize) {
-		self.hnsw.read().await.check_hnsw_properties(expected_count).await
+	pub(super) async fn check_hnsw_properties(&self, tx: &Transaction, expected_count: usize) {
+		self.hnsw.read().await.check_hnsw_properties(tx, expected_count).await
 	}
 }
@@ -15,6 +15,7 @@ use crate::idx::trees::graph::UndirectedGraph;
 use crate::idx::trees::hnsw::filter::HnswTruthyDocumentFilter;
 use crate::idx::trees::hnsw::heuristic::Heuristic;
 use crate::idx::trees::hnsw::index::HnswContext;
+use crate::idx::trees::hnsw::quantizer::QuantizedQuery;
 use crate::idx::trees::hnsw::{ElementId, HnswElements, HnswSearch};
 use crate::idx::trees::knn::{DoublePriorityQueue, Ids64};
 use crate::idx::trees::vector::SharedVector;
@@ -78,6 +79,7 @@ where
 		ctx: &HnswContext<'_>,
 		elements: &HnswElements,
 		pt: &SharedVector,
+		qq: Option<&QuantizedQuery>,
 		ep_dist: f64,
 		ep_id: ElementId,
 		ef: usize,
@@ -86,7 +88,7 @@ where
 		let visited = HashSet::from_iter([ep_id]);
 		let candidates = DoublePriorityQueue::from(ep_dist, ep_id);
 		let w = candidates.clone();
-		self.search(ctx, elements, pt, candidates, visited, w, ef, pending_docs).await
+		self.search(ctx, elements, pt, qq, candidates, visited, w, ef, pending_docs).await
 	}
 
 	pub(super) async fn search_single_with_ignore(
@@ -103,7 +105,7 @@ where
 			candidates.push(dist, ignore_id);
 		}
 		let w = DoublePriorityQueue::default();
-		let q = self.search(ctx, elements, pt, candidates, visited, w, ef, None).await?;
+		let q = self.search(ctx, elements, pt, None, candidates, visited, w, ef, None).await?;
 		Ok(q.peek_first().map(|(_, e_id)| e_id))
 	}
 
@@ -114,6 +116,7 @@ where
 		stk: &mut Stk,
 		elements: &HnswElements,
 		search: &HnswSearch,
+		qq: Option<&QuantizedQuery>,
 		ep_dist: f64,
 		ep_id: ElementId,
 		filter: &mut HnswTruthyDocumentFilter<'_>,
@@ -139,6 +142,7 @@ where
 			stk,
 			elements,
 			search,
+			qq,
 			candidates,
 			visited,
 			w,
@@ -158,7 +162,7 @@ where
 	) -> Result<DoublePriorityQueue> {
 		let w = candidates.clone();
 		let visited = w.to_set();
-		self.search(ctx, elements, pt, candidates, visited, w, ef, None).await
+		self.search(ctx, elements, pt, None, candidates, visited, w, ef, None).await
 	}
 
 	pub(super) async fn search_multi_with_ignore(
@@ -177,7 +181,7 @@ where
 		}
 		let visited = HashSet::from_iter(ignore_ids);
 		let w = DoublePriorityQueue::default();
-		self.search(ctx, elements, pt, candidates, visited, w, efc, None).await
+		self.search(ctx, elements, pt, None, candidates, visited, w, efc, None).await
 	}
 
 	#[expect(clippy::too_many_arguments)]
@@ -186,8 +190,9 @@ where
 		ctx: &HnswContext<'_>,
 		elements: &HnswElements,
 		q: &SharedVector,
+		qq: Option<&QuantizedQuery>, // the query prepared for compressed codes
 		mut candidates: DoublePriorityQueue, // set of candidates
-		mut visited: HashSet<ElementId>,     // set of visited elements
+		mut visited: HashSet<ElementId>, // set of visited elements
 		mut w: DoublePriorityQueue,
 		ef: usize,
 		pending_docs: Option<&RoaringTreemap>,
@@ -203,18 +208,20 @@ where
 					if !visited.insert(e_id) {
 						continue;
 					}
-					if let Some(e_pt) = elements.get_vector(&ctx.tx, &e_id).await? {
-						let e_dist = elements.distance(&e_pt, q);
-						if e_dist < fq_dist || w.len() < ef {
-							if !Self::are_all_docs_in_pending(ctx, &e_pt, pending_docs).await? {
-								candidates.push(e_dist, e_id);
-							}
-							w.push(e_dist, e_id);
-							if w.len() > ef {
-								w.pop_last();
-							}
-							fq_dist = w.peek_last_dist().unwrap_or(f64::MAX);
+					if let Some(e_dist) =
+						elements.get_search_distance(&ctx.tx, q, qq, &e_id).await?
+						&& (e_dist < fq_dist || w.len() < ef)
+					{
+						if !Self::are_all_docs_in_pending(ctx, elements, &e_id, pending_docs)
+							.await?
+						{
+							candidates.push(e_dist, e_id);
+						}
+						w.push(e_dist, e_id);
+						if w.len() > ef {
+							w.pop_last();
 						}
+						fq_dist = w.peek_last_dist().unwrap_or(f64::MAX);
 					}
 				}
 			}
@@ -229,6 +236,7 @@ where
 		stk: &mut Stk,
 		elements: &HnswElements,
 		search: &HnswSearch,
+		qq: Option<&QuantizedQuery>,
 		mut candidates: DoublePriorityQueue,
 		mut visited: HashSet<ElementId>,
 		mut w: DoublePriorityQueue,
@@ -247,11 +255,13 @@ where
 					if !visited.insert(e_id) {
 						continue;
 					}
-					if let Some(e_pt) = elements.get_vector(&ctx.tx, &e_id).await? {
-						let e_dist = elements.distance(&e_pt, &search.pt);
-						if e_dist < f_dist || w.len() < search.ef {
-							candidates.push(e_dist, e_id);
-							if Self::add_if_truthy(
+					if let Some(e_dist) =
+						elements.get_search_distance(&ctx.tx, &search.pt, qq, &e_id).await?
+						&& (e_dist < f_dist || w.len() < search.ef)
+					{
+						candidates.push(e_dist, e_id);
+						if let Some(e_pt) = elements.get_vector(&ctx.tx, &e_id).await?
+							&& Self::add_if_truthy(
 								ctx,
 								stk,
 								search.ef,
@@ -263,9 +273,8 @@ where
 								pending_docs,
 							)
 							.await?
-							{
-								f_dist = w.peek_last_dist().expect("w is non-empty"); // w can't be empty
-							}
+						{
+							f_dist = w.peek_last_dist().expect("w is non-empty"); // w can't be empty
 						}
 					}
 				}
@@ -319,7 +328,8 @@ where
 
 	async fn are_all_docs_in_pending(
 		search_ctx: &HnswContext<'_>,
-		e_pt: &SharedVector,
+		elements: &HnswElements,
+		e_id: &ElementId,
 		pending_docs: Option<&RoaringTreemap>,
 	) -> Result<bool> {
 		let Some(pending_docs) = pending_docs else {
@@ -328,7 +338,10 @@ where
 		if pending_docs.is_empty() {
 			return Ok(false);
 		}
-		if let Some(docs) = search_ctx.vec_docs.get_docs(&search_ctx.tx, e_pt).await? {
+		let Some(e_pt) = elements.get_vector(&search_ctx.tx, e_id).await? else {
+			return Ok(true);
+		};
+		if let Some(docs) = search_ctx.vec_docs.get_docs(&search_ctx.tx, &e_pt).await? {
 			for doc_id in docs.iter() {
 				if !pending_docs.contains(doc_id) {
 					return Ok(false);
@@ -568,8 +581,12 @@ impl<S> HnswLayer<S>
 where
 	S: DynamicSet,
 {
-	pub(in crate::idx::trees::hnsw) async fn check_props(&self, elements: &HnswElements) {
-		let elements_len = elements.len().await;
+	pub(in crate::idx::trees::hnsw) async fn check_props(
+		&self,
+		elements: &HnswElements,
+		tx: &Transaction,
+	) {
+		let elements_len = elements.len(tx).await;
 		assert!(self.graph.len() <= elements_len, "{} - {}", self.graph.len(), elements_len);
 		for (e_id, f_ids) in self.graph.nodes() {
 			assert!(
@@ -580,7 +597,7 @@ where
 			);
 			assert!(!f_ids.contains(e_id), "!f_ids.contains(e_id) - el: {e_id} - f_ids: {f_ids:?}");
 			assert!(
-				elements.contains(*e_id).await,
+				elements.contains(tx, *e_id).await,
 				"h.elements.contains_key(e_id) - el: {e_id} - f_ids: {f_ids:?}"
 			);
 		}
@@ -6,6 +6,7 @@ mod flavor;
 mod heuristic;
 pub mod index;
 mod layer;
+pub(crate) mod quantizer;
 
 use std::sync::Arc;
 
@@ -28,6 +29,7 @@ use crate::idx::trees::hnsw::filter::HnswTruthyDocumentFilter;
 use crate::idx::trees::hnsw::heuristic::Heuristic;
 use crate::idx::trees::hnsw::index::HnswContext;
 use crate::idx::trees::hnsw::layer::{HnswLayer, LayerState};
+use crate::idx::trees::hnsw::quantizer::{QuantizedQuery, Quantizer};
 use crate::idx::trees::knn::DoublePriorityQueue;
 use crate::idx::trees::vector::{SerializedVector, SharedVector, Vector};
 use crate::kvs::{KVValue, Transaction, impl_kv_value_revisioned};
@@ -58,7 +60,7 @@ impl HnswSearch {
 /// Tracks the current entry point, element ID counter, and per-layer state.
 /// This state is loaded at startup and saved after each mutation to ensure
 /// consistency across concurrent transactions.
-#[revisioned(revision = 1)]
+#[revisioned(revision = 2)]
 #[derive(Default, Serialize, Deserialize)]
 pub(crate) struct HnswState {
 	/// The entry point element for graph traversal, or `None` if the graph is empty.
@@ -69,6 +71,10 @@ pub(crate) struct HnswState {
 	layer0: LayerState,
 	/// State of the upper layers (layers 1..N with progressively fewer elements).
 	layers: Vec<LayerState>,
+	/// The number of elements the product quantization codebook was trained
+	/// on, or 0 if it is not trained.
+	#[revision(start = 2)]
+	codebook: ElementId,
 }
 
 impl KVValue for HnswState {
@@ -172,7 +178,13 @@ where
 			ml: p.ml.to_float(),
 			layer0: HnswLayer::new(ikb.clone(), 0, m0),
 			layers: Vec::default(),
-			elements: HnswElements::new(table_id, ikb.clone(), p.distance.clone(), vector_cache),
+			elements: HnswElements::new(
+				table_id,
+				ikb.clone(),
+				p.distance.clone(),
+				p.quantization.as_ref().map(Quantizer::from),
+				vector_cache,
+			),
 			rng: SmallRng::from_rng(thread_rng())?,
 			heuristic: p.into(),
 			ikb,
@@ -212,6 +224,10 @@ where
 		while self.layers.len() > st.layers.len() {
 			self.layers.pop();
 		}
+		// Load the codebook if it was (re)trained by another node
+		if st.codebook != self.state.codebook {
+			self.elements.load_codebook(&tx).await?;
+		}
 		// Set the enter_point
 		self.elements.set_next_element_id(st.next_element_id);
 		self.state = st;
@@ -307,7 +323,7 @@ where
 			if q_level < top_up_layers {
 				for layer in self.layers[q_level..top_up_layers].iter_mut().rev() {
 					if let Some(ep_dist_id) = layer
-						.search_single(ctx, &self.elements, q_pt, ep_dist, ep_id, 1, None)
+						.search_single(ctx, &self.elements, q_pt, None, ep_dist, ep_id, 1, None)
 						.await?
 						.peek_first()
 					{
@@ -389,6 +405,9 @@ where
 	async fn insert(&mut self, ctx: &HnswContext<'_>, q_pt: Vector) -> Result<ElementId> {
 		let q_level = self.get_random_level();
 		let res = self.insert_level(ctx, q_pt, q_level).await?;
+		if let Some(n) = self.elements.train_if_required(&ctx.tx, self.state.codebook).await? {
+			self.state.codebook = n;
+		}
 		self.save_state(&ctx.tx).await?;
 		Ok(res)
 	}
@@ -462,20 +481,24 @@ where
 		search: &HnswSearch,
 		pending_docs: Option<&RoaringTreemap>,
 	) -> Result<Vec<(f64, ElementId)>> {
-		if let Some((ep_dist, ep_id)) = self.search_ep(ctx, &search.pt, pending_docs).await? {
+		let qq = self.elements.encode_query(&search.pt);
+		if let Some((ep_dist, ep_id)) =
+			self.search_ep(ctx, &search.pt, qq.as_ref(), pending_docs).await?
+		{
 			let w = self
 				.layer0
 				.search_single(
 					ctx,
 					&self.elements,
 					&search.pt,
+					qq.as_ref(),
 					ep_dist,
 					ep_id,
 					search.ef,
 					pending_docs,
 				)
 				.await?;
-			Ok(w.to_vec_limit(search.k))
+			self.top_k(ctx, search, qq.is_some(), w).await
 		} else {
 			Ok(vec![])
 		}
@@ -493,9 +516,34 @@ where
 		filter: &mut HnswTruthyDocumentFilter<'_>,
 		pending_docs: Option<&RoaringTreemap>,
 	) -> Result<Vec<(f64, ElementId)>> {
-		if let Some((ep_dist, ep_id)) = self.search_ep(ctx, &search.pt, pending_docs).await?
+		let qq = self.elements.encode_query(&search.pt);
+		if let Some((ep_dist, ep_id)) =
+			self.search_ep(ctx, &search.pt, qq.as_ref(), pending_docs).await?
 			&& self.elements.get_vector(&ctx.tx, &ep_id).await?.is_some()
 		{
+			// A quantized search first traverses the graph on the codes alone,
+			// and only checks the condition on the final candidates
+			if let Some(qq) = &qq {
+				let w = self
+					.layer0
+					.search_single(
+						ctx,
+						&self.elements,
+						&search.pt,
+						Some(qq),
+						ep_dist,
+						ep_id,
+						search.ef,
+						pending_docs,
+					)
+					.await?;
+				let res = self.top_k_with_filter(ctx, stk, search, filter, pending_docs, w).await?;
+				if res.len() >= search.k {
+					return Ok(res);
+				}
+			}
+			// Otherwise, or when too few of the candidates match the condition,
+			// the condition is checked while traversing the graph
 			let w = self
 				.layer0
 				.search_single_with_filter(
@@ -503,32 +551,93 @@ where
 					stk,
 					&self.elements,
 					search,
+					qq.as_ref(),
 					ep_dist,
 					ep_id,
 					filter,
 					pending_docs,
 				)
 				.await?;
-			return Ok(w.to_vec_limit(search.k));
+			return self.top_k(ctx, search, qq.is_some(), w).await;
 		}
 		Ok(vec![])
 	}
 
+	/// Returns the `k` nearest candidates found by a search.
+	///
+	/// When the graph has been traversed on compressed codes, the candidates
+	/// are re-ranked using their exact distance, computed from the stored
+	/// full-precision vectors.
+	async fn top_k(
+		&self,
+		ctx: &HnswContext<'_>,
+		search: &HnswSearch,
+		quantized: bool,
+		w: DoublePriorityQueue,
+	) -> Result<Vec<(f64, ElementId)>> {
+		if !quantized {
+			return Ok(w.to_vec_limit(search.k));
+		}
+		let mut exact = DoublePriorityQueue::default();
+		for (_, e_id) in w.to_vec() {
+			if let Some(dist) = self.elements.get_distance(&ctx.tx, &search.pt, &e_id).await? {
+				exact.push(dist, e_id);
+			}
+		}
+		Ok(exact.to_vec_limit(search.k))
+	}
+
+	/// Returns the `k` nearest candidates found by a search on compressed
+	/// codes which match the condition, using their exact distance.
+	async fn top_k_with_filter(
+		&self,
+		ctx: &HnswContext<'_>,
+		stk: &mut Stk,
+		search: &HnswSearch,
+		filter: &mut HnswTruthyDocumentFilter<'_>,
+		pending_docs: Option<&RoaringTreemap>,
+		w: DoublePriorityQueue,
+	) -> Result<Vec<(f64, ElementId)>> {
+		let mut exact = DoublePriorityQueue::default();
+		for (_, e_id) in w.to_vec() {
+			if let Some(e_pt) = self.elements.get_vector(&ctx.tx, &e_id).await? {
+				let e_dist = self.elements.distance(&e_pt, &search.pt);
+				HnswLayer::<L0>::add_if_truthy(
+					ctx,
+					stk,
+					search.k,
+					&mut exact,
+					&e_pt,
+					e_dist,
+					e_id,
+					filter,
+					pending_docs,
+				)
+				.await?;
+			}
+		}
+		Ok(exact.to_vec_limit(search.k))
+	}
+
 	/// Finds the best entry point for a search by traversing the upper layers.
 	///
 	/// Starting from the graph's entry point, greedily descends through the upper
-	/// layers to find the closest element to the query vector `pt`.
+	/// layers to find the closest element to the query vector `pt`, using the
+	/// compressed codes when a prepared query `qq` is given.
 	async fn search_ep(
 		&self,
 		ctx: &HnswContext<'_>,
 		pt: &SharedVector,
+		qq: Option<&QuantizedQuery>,
 		pending_doc: Option<&RoaringTreemap>,
 	) -> Result<Option<(f64, ElementId)>> {
 		if let Some(mut ep_id) = self.state.enter_point {
-			if let Some(mut ep_dist) = self.elements.get_distance(&ctx.tx, pt, &ep_id).await? {
+			if let Some(mut ep_dist) =
+				self.elements.get_search_distance(&ctx.tx, pt, qq, &ep_id).await?
+			{
 				for layer in self.layers.iter().rev() {
 					if let Some(ep_dist_id) = layer
-						.search_single(ctx, &self.elements, pt, ep_dist, ep_id, 1, pending_doc)
+						.search_single(ctx, &self.elements, pt, qq, ep_dist, ep_id, 1, pending_doc)
 						.await?
 						.peek_first()
 					{
@@ -552,20 +661,20 @@ where
 		self.elements.get_vector(tx, e_id).await
 	}
 	#[cfg(test)]
-	async fn check_hnsw_properties(&self, expected_count: usize) {
-		check_hnsw_props(self, expected_count).await;
+	async fn check_hnsw_properties(&self, tx: &Transaction, expected_count: usize) {
+		check_hnsw_props(self, tx, expected_count).await;
 	}
 }
 
 #[cfg(test)]
-async fn check_hnsw_props<L0, L>(h: &Hnsw<L0, L>, expected_count: usize)
+async fn check_hnsw_props<L0, L>(h: &Hnsw<L0, L>, tx: &Transaction, expected_count: usize)
 where
 	L0: DynamicSet,
 	L: DynamicSet,
 {
-	assert_eq!(h.elements.len().await, expected_count);
+	assert_eq!(h.elements.len(tx).await, expected_count);
 	for layer in h.layers.iter() {
-		layer.check_props(&h.elements).await;
+		layer.check_props(&h.elements, tx).await;
 	}
 }
 
@@ -583,8 +692,8 @@ mod tests {
 
 	use crate::catalog::providers::CatalogProvider;
 	use crate::catalog::{
-		DatabaseId, Distance, HnswParams, IndexId, NamespaceId, TableDefinition, TableId,
-		VectorType,
+		DatabaseId, Distance, HnswParams, IndexId, NamespaceId, Quantization, TableDefinition,
+		TableId, VectorType,
 	};
 	use crate::ctx::{Context, FrozenContext};
 	use crate::idx::IndexKeyBase;
@@ -592,10 +701,11 @@ mod tests {
 	use crate::idx::trees::hnsw::docs::VecDocs;
 	use crate::idx::trees::hnsw::flavor::HnswFlavor;
 	use crate::idx::trees::hnsw::index::{HnswContext, HnswIndex};
-	use crate::idx::trees::hnsw::{ElementId, HnswSearch, VectorId};
+	use crate::idx::trees::hnsw::quantizer::{PQ_TRAINING_SIZE, Quantizer};
+	use crate::idx::trees::hnsw::{ElementId, HnswElements, HnswSearch, VectorId};
 	use crate::idx::trees::knn::tests::{TestCollection, new_vectors_from_file};
 	use crate::idx::trees::knn::{Ids64, KnnResult, KnnResultBuilder};
-	use crate::idx::trees::vector::{SharedVector, Vector};
+	use crate::idx::trees::vector::{SerializedVector, SharedVector, Vector};
 	use crate::kvs::LockType::Optimistic;
 	use crate::kvs::{Datastore, TransactionType};
 	use crate::val::{RecordIdKey, Value};
@@ -610,7 +720,7 @@ mod tests {
 			let obj: SharedVector = obj.clone();
 			let e_id = h.insert(ctx, obj.clone_vector()).await.unwrap();
 			map.insert(e_id, obj);
-			h.check_hnsw_properties(map.len()).await;
+			h.check_hnsw_properties(&ctx.tx, map.len()).await;
 		}
 		map
 	}
@@ -670,7 +780,7 @@ mod tests {
 		for e_id in element_ids {
 			assert!(h.remove(ctx, e_id).await.unwrap());
 			map.remove(&e_id);
-			h.check_hnsw_properties(map.len()).await;
+			h.check_hnsw_properties(&ctx.tx, map.len()).await;
 		}
 	}
 
@@ -734,6 +844,8 @@ mod tests {
 			extend_candidates,
 			keep_pruned_connections,
 			use_hashed_vector,
+			quantization: None,
+			multi_vector: false,
 		}
 	}
 
@@ -790,6 +902,73 @@ mod tests {
 		Ok(())
 	}
 
+	#[test(tokio::test(flavor = "multi_thread"))]
+	async fn tests_hnsw_quantized() -> Result<()> {
+		let mut futures = Vec::new();
+		for dist in [Distance::Cosine, Distance::Euclidean] {
+			for quantization in
+				[Quantization::Scalar, Quantization::Binary, Quantization::Product(4)]
+			{
+				let mut p =
+					new_params(20, VectorType::F32, dist.clone(), 24, 500, false, false, false);
+				p.quantization = Some(quantization);
+				let f = tokio::spawn(async move {
+					test_hnsw(30, p).await;
+				});
+				futures.push(f);
+			}
+		}
+		for f in futures {
+			f.await.expect("Task error");
+		}
+		Ok(())
+	}
+
+	#[test(tokio::test)]
+	async fn tests_hnsw_product_quantizer_training() -> Result<()> {
+		let ds = Datastore::new("memory").await?;
+		let ikb = IndexKeyBase::new(NamespaceId(1), DatabaseId(2), "tb".into(), IndexId(4));
+		let new_elements = || {
+			HnswElements::new(
+				TableId(3),
+				ikb.clone(),
+				Distance::Euclidean,
+				Some(Quantizer::from(&Quantization::Product(2))),
+				ds.index_store().vector_cache().clone(),
+			)
+		};
+		let mut elements = new_elements();
+		let tx = ds.transaction(TransactionType::Write, Optimistic).await?;
+		let mut trainings = Vec::new();
+		let count = (PQ_TRAINING_SIZE * 2) as ElementId;
+		for e_id in 0..count {
+			let vec = Vector::F32(Array1::from_vec(vec![(e_id % 10) as f32, 1.0, 2.0, 3.0]));
+			let ser_vec = SerializedVector::from(&vec);
+			elements.insert(&tx, e_id, vec, &ser_vec).await?;
+			elements.set_next_element_id(e_id + 1);
+			let trained_on = trainings.last().copied().unwrap_or(0);
+			if let Some(n) = elements.train_if_required(&tx, trained_on).await? {
+				trainings.push(n);
+			}
+		}
+		// The codebook is trained, then retrained once the elements have doubled
+		assert_eq!(trainings, [PQ_TRAINING_SIZE as ElementId, count]);
+		// Every element has a stored code
+		for e_id in 0..count {
+			assert!(tx.exists(&ikb.new_hc_key(e_id), None).await?);
+		}
+		// Another instance loads the codebook and traverses on the stored codes
+		let mut elements = new_elements();
+		elements.load_codebook(&tx).await?;
+		let pt: SharedVector = Vector::F32(Array1::from_vec(vec![3.0, 1.0, 2.0, 3.0])).into();
+		let qq = elements.encode_query(&pt).expect("The quantizer is trained");
+		let near = elements.get_search_distance(&tx, &pt, Some(&qq), &3).await?;
+		let far = elements.get_search_distance(&tx, &pt, Some(&qq), &9).await?;
+		assert!(near.zip(far).is_some_and(|(near, far)| near < far), "{near:?} {far:?}");
+		tx.cancel().await?;
+		Ok(())
+	}
+
 	async fn insert_collection_hnsw_index(
 		ctx: &FrozenContext,
 		h: &mut HnswIndex,
@@ -808,7 +987,7 @@ mod tests {
 				}
 			}
 			h.index_pendings(ctx).await?;
-			h.check_hnsw_properties(map.len()).await;
+			h.check_hnsw_properties(&ctx.tx(), map.len()).await;
 		}
 		Ok(map)
 	}
@@ -875,7 +1054,7 @@ mod tests {
 			}
 			h.index_pendings(ctx).await?;
 			// Check properties
-			h.check_hnsw_properties(map.len()).await;
+			h.check_hnsw_properties(&ctx.tx(), map.len()).await;
 		}
 		Ok(())
 	}
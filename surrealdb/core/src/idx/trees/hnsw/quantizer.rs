@@ -0,0 +1,385 @@
+//! Compressed representations of the vectors traversed by an HNSW index.
+//!
+//! A quantizer encodes every element of the graph into a compact code which is
+//! stored next to the element, and computes an approximate distance between a
+//! query and a code. The full-precision vectors stay in the key-value store, and
+//! are used to re-rank the candidates found while traversing the graph.
+
+use std::ops::Range;
+use std::sync::Arc;
+
+use ndarray::Array1;
+use revision::revisioned;
+
+use crate::catalog::{Distance, Quantization};
+use crate::idx::trees::vector::Vector;
+use crate::kvs::impl_kv_value_revisioned;
+
+/// The number of vectors required before training a product quantizer, and
+/// the number of vectors sampled to train it
+pub(super) const PQ_TRAINING_SIZE: usize = 1024;
+
+/// The number of elements beyond which a product quantizer is not retrained.
+/// Until then, the codebook is retrained each time the number of elements has
+/// doubled, and the codes of every element are recomputed.
+pub(super) const PQ_RETRAINING_LIMIT: usize = 65536;
+
+/// The number of centroids of each sub-space of a product quantizer
+const PQ_CENTROIDS: usize = 256;
+
+/// The number of k-means iterations used to train a product quantizer
+const PQ_ITERATIONS: usize = 10;
+
+/// Encodes vectors into compact codes, and estimates distances between a
+/// query and the codes.
+pub(super) enum Quantizer {
+	/// Each component is mapped to 8 bits over the range of the vector
+	Scalar,
+	/// Each component is reduced to its sign bit
+	Binary,
+	/// Each sub-vector is replaced by the index of its nearest centroid. The
+	/// codebook is trained once enough vectors have been indexed.
+	Product {
+		subvectors: usize,
+		codebook: Option<Arc<ProductCodebook>>,
+	},
+}
+
+/// The compact code of an element
+#[revisioned(revision = 1)]
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum QuantizedVector {
+	Scalar {
+		min: f32,
+		scale: f32,
+		codes: Vec<u8>,
+	},
+	Binary(Vec<u64>),
+	Product(Vec<u8>),
+}
+
+impl_kv_value_revisioned!(QuantizedVector);
+
+impl QuantizedVector {
+	/// Returns the approximate memory used by the code
+	pub(super) fn mem_size(&self) -> usize {
+		let s = match self {
+			Self::Scalar {
+				codes,
+				..
+			} => codes.len(),
+			Self::Binary(bits) => bits.len() * std::mem::size_of::<u64>(),
+			Self::Product(codes) => codes.len(),
+		};
+		std::mem::size_of::<Self>() + s
+	}
+}
+
+/// A query prepared for the comparison with the codes
+pub(super) enum QuantizedQuery {
+	/// The query, compared to the decoded codes
+	Decoded(Vector),
+	/// The sign bits of the query
+	Binary(Vec<u64>),
+}
+
+/// The centroids of each sub-space of a product quantizer
+#[revisioned(revision = 1)]
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct ProductCodebook {
+	centroids: Vec<Vec<Vec<f64>>>,
+}
+
+impl_kv_value_revisioned!(ProductCodebook);
+
+/// Returns the components of a vector as 64-bit floats
+fn to_f64(v: &Vector) -> Vec<f64> {
+	match v {
+		Vector::F64(a) => a.to_vec(),
+		Vector::F32(a) => a.iter().map(|&x| x as f64).collect(),
+		Vector::I64(a) => a.iter().map(|&x| x as f64).collect(),
+		Vector::I32(a) => a.iter().map(|&x| x as f64).collect(),
+		Vector::I16(a) => a.iter().map(|&x| x as f64).collect(),
+	}
+}
+
+/// Returns the sign bits of a vector
+fn to_bits(v: &[f64]) -> Vec<u64> {
+	let mut bits = vec![0u64; v.len().div_ceil(64)];
+	for (i, x) in v.iter().enumerate() {
+		if *x > 0.0 {
+			bits[i / 64] |= 1 << (i % 64);
+		}
+	}
+	bits
+}
+
+/// Returns the range of the components of the `i`-th sub-vector
+fn subspace(dim: usize, subvectors: usize, i: usize) -> Range<usize> {
+	i * dim / subvectors..(i + 1) * dim / subvectors
+}
+
+/// Returns the squared euclidean distance between two slices
+fn squared_l2(a: &[f64], b: &[f64]) -> f64 {
+	a.iter().zip(b).map(|(x, y)| (x - y).powi(2)).sum()
+}
+
+/// Returns the index of the centroid nearest to a point
+fn nearest(centroids: &[Vec<f64>], p: &[f64]) -> usize {
+	let mut best = (f64::MAX, 0);
+	for (i, c) in centroids.iter().enumerate() {
+		let d = squared_l2(c, p);
+		if d < best.0 {
+			best = (d, i);
+		}
+	}
+	best.1
+}
+
+impl From<&Quantization> for Quantizer {
+	fn from(q: &Quantization) -> Self {
+		match q {
+			Quantization::Scalar => Self::Scalar,
+			Quantization::Binary => Self::Binary,
+			Quantization::Product(m) => Self::Product {
+				subvectors: (*m as usize).max(1),
+				codebook: None,
+			},
+		}
+	}
+}
+
+impl Quantizer {
+	/// Returns true if the quantizer is a product quantizer without codebook
+	pub(super) fn requires_training(&self) -> bool {
+		matches!(
+			self,
+			Self::Product {
+				codebook: None,
+				..
+			}
+		)
+	}
+
+	/// Returns true if the quantizer is a product quantizer
+	pub(super) fn is_product(&self) -> bool {
+		matches!(self, Self::Product { .. })
+	}
+
+	/// Sets the codebook of a product quantizer
+	pub(super) fn set_codebook(&mut self, cb: ProductCodebook) {
+		if let Self::Product {
+			codebook,
+			..
+		} = self
+		{
+			*codebook = Some(Arc::new(cb));
+		}
+	}
+
+	/// Trains the codebook of a product quantizer over a sample of vectors
+	pub(super) fn train(&self, samples: &[Vector]) -> Option<ProductCodebook> {
+		match self {
+			Self::Product {
+				subvectors,
+				..
+			} if !samples.is_empty() => {
+				let samples: Vec<Vec<f64>> = samples.iter().map(to_f64).collect();
+				Some(ProductCodebook::train(*subvectors, &samples))
+			}
+			_ => None,
+		}
+	}
+
+	/// Encodes a vector, or returns `None` if the quantizer is not trained yet
+	pub(super) fn encode(&self, v: &Vector) -> Option<QuantizedVector> {
+		let v = to_f64(v);
+		match self {
+			Self::Scalar => {
+				let (min, max) =
+					v.iter().fold((f64::MAX, f64::MIN), |(min, max), &x| (min.min(x), max.max(x)));
+				let scale = if max > min {
+					(max - min) / 255.0
+				} else {
+					1.0
+				};
+				let codes = v.iter().map(|x| ((x - min) / scale).round() as u8).collect();
+				Some(QuantizedVector::Scalar {
+					min: min as f32,
+					scale: scale as f32,
+					codes,
+				})
+			}
+			Self::Binary => Some(QuantizedVector::Binary(to_bits(&v))),
+			Self::Product {
+				codebook,
+				..
+			} => codebook.as_ref().map(|cb| QuantizedVector::Product(cb.encode(&v))),
+		}
+	}
+
+	/// Prepares a query, or returns `None` if the quantizer is not trained yet
+	pub(super) fn encode_query(&self, q: &Vector) -> Option<QuantizedQuery> {
+		if self.requires_training() {
+			return None;
+		}
+		let q = to_f64(q);
+		Some(match self {
+			Self::Binary => QuantizedQuery::Binary(to_bits(&q)),
+			_ => QuantizedQuery::Decoded(Vector::F64(Array1::from_vec(q))),
+		})
+	}
+
+	/// Estimates the distance between a query and a code. With binary
+	/// quantization, the estimate is the number of differing sign bits.
+	pub(super) fn distance(&self, dist: &Distance, q: &QuantizedQuery, c: &QuantizedVector) -> f64 {
+		match (q, c) {
+			(QuantizedQuery::Binary(q), QuantizedVector::Binary(c)) => {
+				q.iter().zip(c).map(|(a, b)| (a ^ b).count_ones()).sum::<u32>() as f64
+			}
+			(
+				QuantizedQuery::Decoded(q),
+				QuantizedVector::Scalar {
+					min,
+					scale,
+					codes,
+				},
+			) => {
+				let v = codes.iter().map(|&c| (*min + c as f32 * *scale) as f64).collect();
+				dist.calculate(q, &Vector::F64(Array1::from_vec(v)))
+			}
+			(QuantizedQuery::Decoded(q), QuantizedVector::Product(codes)) => match self {
+				Self::Product {
+					codebook: Some(cb),
+					..
+				} => dist.calculate(q, &Vector::F64(Array1::from_vec(cb.decode(codes)))),
+				_ => f64::INFINITY,
+			},
+			_ => f64::INFINITY,
+		}
+	}
+}
+
+impl ProductCodebook {
+	/// Trains the centroids of each sub-space with k-means
+	fn train(subvectors: usize, samples: &[Vec<f64>]) -> Self {
+		let dim = samples[0].len();
+		let subvectors = subvectors.min(dim).max(1);
+		let k = PQ_CENTROIDS.min(samples.len());
+		let mut centroids = Vec::with_capacity(subvectors);
+		for i in 0..subvectors {
+			let r = subspace(dim, subvectors, i);
+			let points: Vec<&[f64]> = samples.iter().map(|s| &s[r.clone()]).collect();
+			// Initialise with evenly spread samples
+			let mut cs: Vec<Vec<f64>> =
+				(0..k).map(|j| points[j * points.len() / k].to_vec()).collect();
+			for _ in 0..PQ_ITERATIONS {
+				let mut sums = vec![vec![0.0; r.len()]; k];
+				let mut counts = vec![0usize; k];
+				for p in &points {
+					let j = nearest(&cs, p);
+					counts[j] += 1;
+					sums[j].iter_mut().zip(p.iter()).for_each(|(s, x)| *s += x);
+				}
+				for ((c, sum), count) in cs.iter_mut().zip(sums).zip(counts) {
+					// An empty cluster keeps its centroid
+					if count > 0 {
+						*c = sum.into_iter().map(|s| s / count as f64).collect();
+					}
+				}
+			}
+			centroids.push(cs);
+		}
+		Self {
+			centroids,
+		}
+	}
+
+	/// Returns the dimension of the vectors
+	fn dimension(&self) -> usize {
+		self.centroids.iter().map(|cs| cs.first().map(Vec::len).unwrap_or(0)).sum()
+	}
+
+	/// Returns the index of the nearest centroid of each sub-vector
+	fn encode(&self, v: &[f64]) -> Vec<u8> {
+		let dim = self.dimension();
+		let m = self.centroids.len();
+		self.centroids
+			.iter()
+			.enumerate()
+			.map(|(i, cs)| nearest(cs, &v[subspace(dim, m, i)]) as u8)
+			.collect()
+	}
+
+	/// Returns the vector made of the centroids of a code
+	fn decode(&self, codes: &[u8]) -> Vec<f64> {
+		let mut v = Vec::with_capacity(self.dimension());
+		for (cs, &c) in self.centroids.iter().zip(codes) {
+			v.extend_from_slice(&cs[c as usize]);
+		}
+		v
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use ndarray::Array1;
+
+	use crate::catalog::{Distance, Quantization};
+	use crate::idx::trees::hnsw::quantizer::{QuantizedVector, Quantizer};
+	use crate::idx::trees::vector::Vector;
+
+	fn vector(v: &[f32]) -> Vector {
+		Vector::F32(Array1::from_vec(v.to_vec()))
+	}
+
+	#[test]
+	fn scalar_quantization() {
+		let q = Quantizer::from(&Quantization::Scalar);
+		let v = vector(&[-1.0, 0.0, 0.5, 1.0]);
+		let c = q.encode(&v).unwrap();
+		assert!(
+			matches!(&c, QuantizedVector::Scalar { codes, .. } if codes == &[0, 128, 191, 255])
+		);
+		let query = q.encode_query(&v).unwrap();
+		assert!(q.distance(&Distance::Euclidean, &query, &c) < 0.01);
+		let far = q.encode(&vector(&[1.0, 1.0, -1.0, -1.0])).unwrap();
+		assert!(q.distance(&Distance::Euclidean, &query, &far) > 2.0);
+	}
+
+	#[test]
+	fn binary_quantization() {
+		let q = Quantizer::from(&Quantization::Binary);
+		let query = q.encode_query(&vector(&[1.0, -1.0, 1.0, -1.0])).unwrap();
+		let c1 = q.encode(&vector(&[0.5, -0.2, 0.1, -3.0])).unwrap();
+		let c2 = q.encode(&vector(&[-0.5, 0.2, 0.1, -3.0])).unwrap();
+		assert_eq!(q.distance(&Distance::Cosine, &query, &c1), 0.0);
+		assert_eq!(q.distance(&Distance::Cosine, &query, &c2), 2.0);
+	}
+
+	#[test]
+	fn product_quantization() {
+		let mut q = Quantizer::from(&Quantization::Product(2));
+		let v = vector(&[1.0, 2.0, 3.0, 4.0]);
+		// Not trained yet
+		assert!(q.requires_training());
+		assert!(q.encode(&v).is_none());
+		assert!(q.encode_query(&v).is_none());
+		// Train over two clusters
+		let samples: Vec<Vector> = (0..100)
+			.map(|i| {
+				let x = (i % 2) as f32 * 10.0;
+				vector(&[x, x + 1.0, x + 2.0, x + 3.0])
+			})
+			.collect();
+		let cb = q.train(&samples).unwrap();
+		q.set_codebook(cb);
+		assert!(!q.requires_training());
+		let c = q.encode(&vector(&[0.0, 1.0, 2.0, 3.0])).unwrap();
+		assert!(matches!(&c, QuantizedVector::Product(codes) if codes.len() == 2));
+		let query = q.encode_query(&vector(&[0.0, 1.0, 2.0, 3.0])).unwrap();
+		assert_eq!(q.distance(&Distance::Euclidean, &query, &c), 0.0);
+		let far = q.encode(&vector(&[10.0, 11.0, 12.0, 13.0])).unwrap();
+		assert_eq!(q.distance(&Distance::Euclidean, &query, &far), 20.0);
+	}
+}
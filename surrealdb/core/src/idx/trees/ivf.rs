@@ -0,0 +1,206 @@
+//! Inverted-file (IVF) vector indexes.
+//!
+//! The vectors are partitioned into lists, each list being represented by a
+//! centroid. A vector is stored in the key-value store under the list of its
+//! nearest centroid (see [`crate::key::index::vl`]), so that, unlike an HNSW
+//! index, no part of the index has to be held in memory.
+//!
+//! A search computes the distance between the query vector and every
+//! centroid, then only scans the vectors of the `probes` nearest lists. The
+//! result is therefore approximate: a higher number of probes trades speed
+//! for recall.
+//!
+//! The centroids are seeded with the first distinct vectors being indexed,
+//! until the configured number of lists is reached, and are not trained
+//! afterwards. Rebuilding the index seeds the centroids again from the current
+//! records.
+//!
+//! The list and the position of each vector stored for a record are recorded
+//! with the record (see [`crate::key::index::vr`]), so that updating or
+//! removing a record deletes its vectors from the lists they were stored in,
+//! without depending on the current centroids. A record with several vectors is
+//! ranked by its nearest vector.
+//!
+//! The vectors are stored in full: the centroids are not refined with k-means
+//! and the lists do not store quantized residuals.
+
+use std::collections::{HashSet, VecDeque};
+use std::sync::Arc;
+
+use anyhow::Result;
+use reblessive::tree::Stk;
+use revision::revisioned;
+
+use crate::catalog::{IvfParams, Record};
+use crate::cnf::NORMAL_FETCH_SIZE;
+use crate::ctx::FrozenContext;
+use crate::dbs::Options;
+use crate::expr::Cond;
+use crate::idx::IndexKeyBase;
+use crate::idx::planner::iterators::KnnIteratorResult;
+use crate::idx::spatial::is_record_truthy;
+use crate::idx::trees::vector::{SerializedVector, Vector};
+use crate::key::index::vl::Vl;
+use crate::kvs::{KVValue, Transaction, impl_kv_value_revisioned};
+use crate::val::{Number, RecordId, RecordIdKey, Value};
+
+/// The centroids of the lists of an IVF index
+#[revisioned(revision = 1)]
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct IvfCentroids(Vec<SerializedVector>);
+
+impl_kv_value_revisioned!(IvfCentroids);
+
+/// The list and the position of each vector of a record stored by an IVF index
+#[revisioned(revision = 1)]
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct IvfRecordLists(Vec<(u16, u32)>);
+
+impl_kv_value_revisioned!(IvfRecordLists);
+
+impl IvfCentroids {
+	/// Returns the centroids ordered by their distance to a vector, nearest first
+	fn nearest(&self, p: &IvfParams, v: &Vector) -> Vec<(f64, u16)> {
+		let mut lists: Vec<(f64, u16)> = self
+			.0
+			.iter()
+			.enumerate()
+			.map(|(i, c)| (p.distance.calculate(&Vector::from(c.clone()), v), i as u16))
+			.collect();
+		lists.sort_by(|a, b| a.0.total_cmp(&b.0));
+		lists
+	}
+
+	/// Returns the list of the centroid nearest to a vector
+	fn nearest_list(&self, p: &IvfParams, v: &Vector) -> Option<u16> {
+		self.nearest(p, v).first().map(|(_, l)| *l)
+	}
+}
+
+/// Extracts the vector of an indexed value, validating its dimensionality
+fn value_to_vector(p: &IvfParams, value: Value) -> Result<SerializedVector> {
+	let dim = p.dimension as usize;
+	let vector = SerializedVector::try_from_value(p.vector_type, dim, value)?;
+	Vector::check_expected_dimension(vector.dimension(), dim)?;
+	Ok(vector)
+}
+
+/// Stores the vectors of a record under the list of their nearest centroid,
+/// replacing the vectors previously stored for this record.
+pub(crate) async fn index(
+	tx: &Transaction,
+	ikb: &IndexKeyBase,
+	p: &IvfParams,
+	id: &RecordIdKey,
+	old_values: Option<Vec<Value>>,
+	new_values: Option<Vec<Value>>,
+) -> Result<()> {
+	// Delete the old index data, from the lists the vectors were stored in
+	if old_values.is_some() {
+		let key = ikb.new_vr_key(id);
+		if let Some(lists) = tx.get(&key, None).await? {
+			for (list, pos) in lists.0 {
+				tx.del(&ikb.new_vl_key(list, id, pos)).await?;
+			}
+			tx.del(&key).await?;
+		}
+	}
+	// Create the new index data
+	if let Some(n) = new_values {
+		let key = ikb.new_vc_key();
+		let mut centroids = match tx.get(&key, None).await? {
+			Some(centroids) => centroids,
+			None => IvfCentroids(Vec::new()),
+		};
+		let mut lists = Vec::new();
+		for (pos, value) in n.into_iter().enumerate() {
+			if value.is_nullish() {
+				continue;
+			}
+			let serialized = value_to_vector(p, value)?;
+			// Seed a new centroid until every list has one
+			if centroids.0.len() < p.lists as usize && !centroids.0.contains(&serialized) {
+				centroids.0.push(serialized.clone());
+				tx.set(&key, &centroids, None).await?;
+			}
+			let vector = Vector::from(serialized.clone());
+			if let Some(list) = centroids.nearest_list(p, &vector) {
+				let pos = pos as u32;
+				tx.set(&ikb.new_vl_key(list, id, pos), &serialized, None).await?;
+				lists.push((list, pos));
+			}
+		}
+		if !lists.is_empty() {
+			tx.set(&ikb.new_vr_key(id), &IvfRecordLists(lists), None).await?;
+		}
+	}
+	Ok(())
+}
+
+/// Searches the `k` records nearest to a vector, scanning the lists of the
+/// `probes` nearest centroids.
+///
+/// When a condition is given, only the records satisfying it are returned.
+#[expect(clippy::too_many_arguments)]
+pub(crate) async fn knn_search(
+	stk: &mut Stk,
+	ctx: &FrozenContext,
+	opt: &Options,
+	ikb: &IndexKeyBase,
+	p: &IvfParams,
+	pt: &[Number],
+	k: usize,
+	cond: Option<Arc<Cond>>,
+) -> Result<VecDeque<KnnIteratorResult>> {
+	let query = Vector::try_from_vector(p.vector_type, pt)?;
+	query.check_dimension(p.dimension as usize)?;
+	let tx = ctx.tx();
+	let Some(centroids) = tx.get(&ikb.new_vc_key(), None).await? else {
+		return Ok(VecDeque::new());
+	};
+	// Compute the distance to the vectors of the nearest lists
+	let mut candidates = Vec::new();
+	for (_, list) in centroids.nearest(p, &query).into_iter().take(p.probes as usize) {
+		let mut range = ikb.new_vl_list_range(list)?;
+		loop {
+			if ctx.is_done(None).await? {
+				break;
+			}
+			let res =
+				tx.scan(range.0.clone()..range.1.clone(), *NORMAL_FETCH_SIZE, 0, None).await?;
+			let Some((last, _)) = res.last() else {
+				break;
+			};
+			range.0.clone_from(last);
+			range.0.push(0x00);
+			for (key, val) in res {
+				let id = Vl::decode_key(&key)?.id.into_owned();
+				let vector = Vector::from(SerializedVector::kv_decode_value(val)?);
+				candidates.push((p.distance.calculate(&query, &vector), id));
+			}
+		}
+	}
+	candidates.sort_by(|a, b| a.0.total_cmp(&b.0));
+	// Keep the nearest records satisfying the condition, a record with
+	// several vectors being ranked by its nearest vector
+	let mut res = VecDeque::with_capacity(k);
+	let mut seen = HashSet::new();
+	for (d, id) in candidates {
+		if res.len() == k || ctx.is_done(None).await? {
+			break;
+		}
+		if !seen.insert(id.clone()) {
+			continue;
+		}
+		let rid = Arc::new(RecordId::new(ikb.table().clone(), id));
+		let record: Option<Arc<Record>> = match &cond {
+			Some(cond) => match is_record_truthy(stk, ctx, opt, ikb, cond, &rid).await? {
+				Some(record) => Some(record),
+				None => continue,
+			},
+			None => None,
+		};
+		res.push_back((rid, d, record));
+	}
+	Ok(res)
+}
@@ -1,6 +1,7 @@
 pub mod dynamicset;
 mod graph;
 pub mod hnsw;
+pub(crate) mod ivf;
 pub(in crate::idx) mod knn;
 pub mod store;
 pub mod vector;
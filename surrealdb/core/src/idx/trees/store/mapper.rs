@@ -7,54 +7,98 @@ use dashmap::DashMap;
 use crate::catalog;
 use crate::err::Error;
 use crate::expr::Filter;
+use crate::expr::filter::{Stopwords, Synonyms};
 use crate::iam::file::is_path_allowed;
 use crate::idx::ft::analyzer::mapper::Mapper;
+use crate::idx::ft::analyzer::stopwords::StopWords;
+use crate::idx::ft::analyzer::synonyms::Synonyms as SynonymRules;
 
+/// The in-memory content of the files referenced by analyzer filters
 #[derive(Default)]
-pub(crate) struct Mappers(DashMap<String, Mapper>);
+pub(crate) struct Mappers {
+	mappers: DashMap<String, Mapper>,
+	stopwords: DashMap<String, StopWords>,
+	synonyms: DashMap<String, SynonymRules>,
+}
 
 impl Mappers {
-	/// If any mapper is defined, it will be loaded in memory.
+	/// If any mapper, stopword, or synonym file is defined, it will be loaded
+	/// in memory. Built-in and inline lists are validated.
 	pub(crate) async fn load(&self, az: &catalog::AnalyzerDefinition) -> Result<()> {
 		if let Some(filters) = &az.filters {
 			for f in filters {
-				if let Filter::Mapper(path) = f {
-					self.insert(path).await?;
+				match f {
+					Filter::Mapper(path) => self.insert(path).await?,
+					Filter::Stopwords(Stopwords::File(path)) => self.insert_stopwords(path).await?,
+					Filter::Stopwords(Stopwords::Language(l)) => {
+						StopWords::from_language(*l)?;
+					}
+					Filter::Synonyms(Synonyms::File(path)) => self.insert_synonyms(path).await?,
+					Filter::Synonyms(Synonyms::Rules(r)) => {
+						SynonymRules::from_rules(r.iter().map(String::as_str))?;
+					}
+					_ => {}
 				}
 			}
 		}
 		Ok(())
 	}
 
-	/// Ensure that if a mapper is defined, that it is also loaded in memory.
-	/// This method does not reload a mapper if it is already in memory.
+	/// Ensure that if a mapper, stopword, or synonym file is defined, that it
+	/// is also loaded in memory. This method does not reload a file if it is
+	/// already in memory.
 	pub(crate) async fn check(&self, az: &catalog::AnalyzerDefinition) -> Result<()> {
 		if let Some(filters) = &az.filters {
 			for f in filters {
-				if let Filter::Mapper(path) = f
-					&& !self.0.contains_key(path)
-				{
-					self.insert(path).await?;
+				match f {
+					Filter::Mapper(path) if !self.mappers.contains_key(path) => {
+						self.insert(path).await?
+					}
+					Filter::Stopwords(Stopwords::File(path))
+						if !self.stopwords.contains_key(path) =>
+					{
+						self.insert_stopwords(path).await?
+					}
+					Filter::Synonyms(Synonyms::File(path)) if !self.synonyms.contains_key(path) => {
+						self.insert_synonyms(path).await?
+					}
+					_ => {}
 				}
 			}
 		}
 		Ok(())
 	}
 
-	async fn insert(&self, path: &str) -> Result<()> {
+	fn check_path<'a>(path: &'a str, kind: &str) -> Result<&'a Path> {
 		let p = Path::new(path);
 		// Check the path is allowed
 		is_path_allowed(p)?;
 		if !p.exists() || !p.is_file() {
-			bail!(Error::Internal(format!("Invalid mapper path: {p:?}")));
+			bail!(Error::Internal(format!("Invalid {kind} path: {p:?}")));
 		}
-		let mapper = Mapper::new(p).await?;
-		self.0.insert(path.to_string(), mapper);
+		Ok(p)
+	}
+
+	async fn insert(&self, path: &str) -> Result<()> {
+		let mapper = Mapper::new(Self::check_path(path, "mapper")?).await?;
+		self.mappers.insert(path.to_string(), mapper);
+		Ok(())
+	}
+
+	async fn insert_stopwords(&self, path: &str) -> Result<()> {
+		let stopwords = StopWords::from_file(Self::check_path(path, "stopwords")?).await?;
+		self.stopwords.insert(path.to_string(), stopwords);
+		Ok(())
+	}
+
+	async fn insert_synonyms(&self, path: &str) -> Result<()> {
+		let synonyms = SynonymRules::from_file(Self::check_path(path, "synonyms")?).await?;
+		self.synonyms.insert(path.to_string(), synonyms);
 		Ok(())
 	}
 
 	pub(in crate::idx) fn get(&self, path: &str) -> Result<Mapper> {
-		match self.0.get(path) {
+		match self.mappers.get(path) {
 			None => {
 				Err(anyhow::Error::new(Error::Internal(format!("Mapper not found for {path}"))))
 			}
@@ -62,22 +106,58 @@ impl Mappers {
 		}
 	}
 
+	pub(in crate::idx) fn get_stopwords(&self, path: &str) -> Result<StopWords> {
+		match self.stopwords.get(path) {
+			None => {
+				Err(anyhow::Error::new(Error::Internal(format!("Stopwords not found for {path}"))))
+			}
+			Some(e) => Ok(e.value().clone()),
+		}
+	}
+
+	pub(in crate::idx) fn get_synonyms(&self, path: &str) -> Result<SynonymRules> {
+		match self.synonyms.get(path) {
+			None => {
+				Err(anyhow::Error::new(Error::Internal(format!("Synonyms not found for {path}"))))
+			}
+			Some(e) => Ok(e.value().clone()),
+		}
+	}
+
 	pub(crate) fn cleanup(&self, azs: &[catalog::AnalyzerDefinition]) {
-		// Collect every existing mapper
-		let mut keys: HashSet<String> = self.0.iter().map(|e| e.key().clone()).collect();
+		// Collect every loaded file
+		let mut mappers: HashSet<String> = self.mappers.iter().map(|e| e.key().clone()).collect();
+		let mut stopwords: HashSet<String> =
+			self.stopwords.iter().map(|e| e.key().clone()).collect();
+		let mut synonyms: HashSet<String> = self.synonyms.iter().map(|e| e.key().clone()).collect();
 		// Remove keys that still exist in the definitions
 		for az in azs {
 			if let Some(filters) = &az.filters {
 				for f in filters {
-					if let Filter::Mapper(path) = f {
-						keys.remove(path);
+					match f {
+						Filter::Mapper(path) => {
+							mappers.remove(path);
+						}
+						Filter::Stopwords(Stopwords::File(path)) => {
+							stopwords.remove(path);
+						}
+						Filter::Synonyms(Synonyms::File(path)) => {
+							synonyms.remove(path);
+						}
+						_ => {}
 					}
 				}
 			}
 		}
 		// Any left key can be removed
-		for key in keys {
-			self.0.remove(&key);
+		for key in mappers {
+			self.mappers.remove(&key);
+		}
+		for key in stopwords {
+			self.stopwords.remove(&key);
+		}
+		for key in synonyms {
+			self.synonyms.remove(&key);
 		}
 	}
 }
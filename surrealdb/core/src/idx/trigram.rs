@@ -0,0 +1,312 @@
+//! Trigram indexes over strings.
+//!
+//! The id of each record is stored under every distinct sequence of three
+//! consecutive characters of its strings (see [`crate::key::index::tg`]). Any
+//! string matching a substring or a regex query contains every trigram of the
+//! literals required by this query, so a query intersects the records stored
+//! under each of these trigrams. The resulting records are candidates, which
+//! are checked against the condition of the query once fetched.
+
+use std::cmp::Ordering;
+use std::collections::{BTreeSet, VecDeque};
+
+use anyhow::Result;
+
+use crate::idx::IndexKeyBase;
+use crate::key::index::tg::Tg;
+use crate::kvs::{Key, Transaction};
+use crate::val::{RecordIdKey, Regex, Value};
+
+/// The number of characters of a trigram
+const TRIGRAM_LEN: usize = 3;
+
+/// Returns the distinct trigrams of a string
+pub(crate) fn trigrams(s: &str) -> BTreeSet<String> {
+	let chars: Vec<char> = s.chars().collect();
+	chars.windows(TRIGRAM_LEN).map(|w| w.iter().collect()).collect()
+}
+
+/// A query answered by a trigram index
+#[derive(Debug, Clone)]
+pub(crate) enum TrigramQuery {
+	/// The strings containing a substring
+	Contains(String),
+	/// The strings matching a regex
+	Regex(Regex),
+}
+
+impl TrigramQuery {
+	/// Creates a query from a substring or a regex
+	pub(crate) fn new(v: &Value) -> Option<Self> {
+		match v {
+			Value::String(s) => Some(Self::Contains(s.clone())),
+			Value::Regex(r) => Some(Self::Regex(r.clone())),
+			_ => None,
+		}
+	}
+
+	/// Returns the distinct trigrams of the literals required by the query.
+	/// The result is empty if the query does not require any literal of at
+	/// least three characters, in which case the index can not be used.
+	pub(crate) fn trigrams(&self) -> Vec<String> {
+		let literals = match self {
+			Self::Contains(c) => vec![c.clone()],
+			Self::Regex(r) => RegexLiterals::new(r.inner().as_str()).literals,
+		};
+		literals.iter().flat_map(|l| trigrams(l)).collect::<BTreeSet<_>>().into_iter().collect()
+	}
+}
+
+/// The literals which every string matching a regex contains.
+///
+/// The pattern is analysed conservatively: the analysis stops at the first
+/// group, character class or escaped class (`\d`, `\w`, ...), and gives up on
+/// any alternation, so that every returned literal is actually required.
+#[derive(Debug, Default, PartialEq)]
+pub(crate) struct RegexLiterals {
+	/// The literal every match starts with, when the regex is anchored
+	pub(crate) prefix: Option<String>,
+	/// The literals every match contains
+	pub(crate) literals: Vec<String>,
+}
+
+impl RegexLiterals {
+	pub(crate) fn new(pattern: &str) -> Self {
+		let mut res = Self::default();
+		if pattern.contains('|') {
+			return res;
+		}
+		let mut chars = pattern.chars().peekable();
+		// Is the regex anchored to the start of the string?
+		let mut in_prefix = chars.next_if_eq(&'^').is_some();
+		let mut run = String::new();
+		while let Some(c) = chars.next() {
+			match c {
+				'(' | '[' => break,
+				'\\' => match chars.next() {
+					Some(e) if !e.is_alphanumeric() => {
+						run.push(e);
+						continue;
+					}
+					_ => break,
+				},
+				'*' | '?' => {
+					// The previous character is optional
+					run.pop();
+					chars.next_if_eq(&'?');
+				}
+				'+' => {
+					chars.next_if_eq(&'?');
+				}
+				'{' => {
+					let mut min = String::new();
+					for c in chars.by_ref() {
+						if c == '}' {
+							break;
+						}
+						min.push(c);
+					}
+					let min = min.split(',').next().and_then(|m| m.trim().parse::<u32>().ok());
+					if min.is_none_or(|m| m == 0) {
+						// The previous character may be absent
+						run.pop();
+					}
+					chars.next_if_eq(&'?');
+				}
+				'.' | '^' | '$' => {}
+				c => {
+					run.push(c);
+					continue;
+				}
+			}
+			res.push(std::mem::take(&mut run), in_prefix);
+			in_prefix = false;
+		}
+		res.push(run, in_prefix);
+		res
+	}
+
+	fn push(&mut self, run: String, in_prefix: bool) {
+		if run.is_empty() {
+			return;
+		}
+		if in_prefix {
+			self.prefix = Some(run.clone());
+		}
+		self.literals.push(run);
+	}
+}
+
+/// The number of keys read by each scan of a posting list
+const SCAN_BATCH_SIZE: u32 = 1000;
+
+/// The records stored under one trigram, in the order of their keys
+struct PostingList {
+	/// The range of the keys which have not been read yet
+	range: (Key, Key),
+	/// The length of the key prefix shared by every record of the trigram
+	prefix: usize,
+	/// The keys read, but not consumed yet
+	keys: VecDeque<Key>,
+}
+
+impl PostingList {
+	fn new(ikb: &IndexKeyBase, trigram: &str) -> Result<Self> {
+		let range = ikb.new_tg_range(trigram)?;
+		Ok(Self {
+			prefix: range.0.len(),
+			range,
+			keys: VecDeque::new(),
+		})
+	}
+
+	/// Returns the encoded id of the next record, or `None` once the list
+	/// is exhausted
+	async fn head(&mut self, tx: &Transaction) -> Result<Option<&[u8]>> {
+		if self.keys.is_empty() && self.range.0 < self.range.1 {
+			let keys = tx
+				.keys(self.range.0.clone()..self.range.1.clone(), SCAN_BATCH_SIZE, 0, None)
+				.await?;
+			match keys.last() {
+				Some(last) => {
+					self.range.0.clone_from(last);
+					self.range.0.push(0x00);
+				}
+				None => self.range.0.clone_from(&self.range.1),
+			}
+			self.keys = keys.into();
+		}
+		Ok(self.keys.front().map(|k| &k[self.prefix..]))
+	}
+
+	/// Skips the records whose encoded id is lower than the given one
+	fn seek(&mut self, id: &[u8]) {
+		while self.keys.front().is_some_and(|k| &k[self.prefix..] < id) {
+			self.keys.pop_front();
+		}
+		if self.keys.is_empty() {
+			// Resume the scan from the first key which may hold the id
+			let mut beg = self.range.0[..self.prefix].to_vec();
+			beg.extend_from_slice(id);
+			if beg > self.range.0 {
+				self.range.0 = beg;
+			}
+		}
+	}
+}
+
+/// Iterates over the records stored under every trigram of a query, by
+/// intersecting the posting lists of these trigrams.
+pub(crate) struct TrigramIntersection {
+	lists: Vec<PostingList>,
+}
+
+impl TrigramIntersection {
+	pub(crate) fn new(ikb: &IndexKeyBase, trigrams: &[String]) -> Result<Self> {
+		let lists = trigrams.iter().map(|t| PostingList::new(ikb, t)).collect::<Result<_>>()?;
+		Ok(Self {
+			lists,
+		})
+	}
+
+	/// Returns the ids of up to `limit` next records. An empty result means
+	/// that the intersection is exhausted.
+	pub(crate) async fn next(&mut self, tx: &Transaction, limit: u32) -> Result<Vec<RecordIdKey>> {
+		let mut ids = Vec::new();
+		while ids.len() < limit as usize {
+			match self.next_key(tx).await? {
+				Some(key) => ids.push(Tg::decode_key(&key)?.id.into_owned()),
+				None => break,
+			}
+		}
+		Ok(ids)
+	}
+
+	/// Returns the key of the next record present in every list
+	async fn next_key(&mut self, tx: &Transaction) -> Result<Option<Key>> {
+		if self.lists.is_empty() {
+			return Ok(None);
+		}
+		loop {
+			// The greatest head is the smallest id which may be in every list
+			let mut max: Option<Vec<u8>> = None;
+			let mut aligned = true;
+			for list in self.lists.iter_mut() {
+				let Some(head) = list.head(tx).await? else {
+					return Ok(None);
+				};
+				match max.as_deref().map(|m| head.cmp(m)) {
+					None => max = Some(head.to_vec()),
+					Some(Ordering::Equal) => {}
+					Some(Ordering::Less) => aligned = false,
+					Some(Ordering::Greater) => {
+						aligned = false;
+						max = Some(head.to_vec());
+					}
+				}
+			}
+			let Some(max) = max else {
+				return Ok(None);
+			};
+			if aligned {
+				let mut key = None;
+				for list in self.lists.iter_mut() {
+					key = list.keys.pop_front();
+				}
+				return Ok(key);
+			}
+			for list in self.lists.iter_mut() {
+				list.seek(&max);
+			}
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_trigrams() {
+		assert_eq!(trigrams("ab"), BTreeSet::new());
+		assert_eq!(
+			trigrams("banana").into_iter().collect::<Vec<_>>(),
+			vec!["ana".to_string(), "ban".to_string(), "nan".to_string()]
+		);
+		assert_eq!(trigrams("héllo").len(), 3);
+	}
+
+	fn literals(prefix: Option<&str>, literals: &[&str]) -> RegexLiterals {
+		RegexLiterals {
+			prefix: prefix.map(String::from),
+			literals: literals.iter().copied().map(String::from).collect(),
+		}
+	}
+
+	#[test]
+	fn test_regex_literals() {
+		assert_eq!(RegexLiterals::new("^abc"), literals(Some("abc"), &["abc"]));
+		assert_eq!(RegexLiterals::new("^abc.*def$"), literals(Some("abc"), &["abc", "def"]));
+		assert_eq!(RegexLiterals::new("abc"), literals(None, &["abc"]));
+		assert_eq!(RegexLiterals::new("^abcd?e"), literals(Some("abc"), &["abc", "e"]));
+		assert_eq!(RegexLiterals::new("^ab+c"), literals(Some("ab"), &["ab", "c"]));
+		assert_eq!(RegexLiterals::new("^ab{0,2}c"), literals(Some("a"), &["a", "c"]));
+		assert_eq!(RegexLiterals::new("^ab{2}c"), literals(Some("ab"), &["ab", "c"]));
+		assert_eq!(RegexLiterals::new(r"^a\.b\dcde"), literals(Some("a.b"), &["a.b"]));
+		assert_eq!(RegexLiterals::new("^abc(def)"), literals(Some("abc"), &["abc"]));
+		assert_eq!(RegexLiterals::new("[a-z]abc"), literals(None, &[]));
+		assert_eq!(RegexLiterals::new("^abc|def"), literals(None, &[]));
+		assert_eq!(RegexLiterals::new("(?i)abc"), literals(None, &[]));
+		assert_eq!(RegexLiterals::new("^a*bc"), literals(None, &["bc"]));
+	}
+
+	#[test]
+	fn test_query_trigrams() {
+		let contains = TrigramQuery::Contains("hello".to_string());
+		assert_eq!(contains.trigrams(), vec!["ell", "hel", "llo"]);
+		assert!(TrigramQuery::Contains("he".to_string()).trigrams().is_empty());
+		let regex = TrigramQuery::Regex("abc.*world".parse().unwrap());
+		assert_eq!(regex.trigrams(), vec!["abc", "orl", "rld", "wor"]);
+		assert!(TrigramQuery::Regex("a.b".parse().unwrap()).trigrams().is_empty());
+	}
+}
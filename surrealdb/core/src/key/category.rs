@@ -11,6 +11,8 @@ pub(crate) trait Categorise {
 pub enum Category {
 	/// crate::key::storage::version         /sv
 	Version,
+	/// crate::key::encryption               !e
+	Encryption,
 	/// crate::key::root::all                /
 	Root,
 	/// crate::key::root::access::ac         /!ac{ac}
@@ -33,6 +35,8 @@ pub enum Category {
 	TaskLease,
 	/// crate::key::root::cg                 /!cg{ty}
 	RootConfig,
+	/// crate::key::root::rs                 /!rs
+	RestoreState,
 	/// crate::key::root::ic                 /!ic{ns}{db}{tb}{ix}{nid}{uuid}
 	IndexCompaction,
 	/// crate::key::root::eq                 /!eq{ns}{db}{tb}{ev}{ts}{nid}
@@ -91,12 +95,18 @@ pub enum Category {
 	DatabaseAnalyzer,
 	/// crate::key::database::bu             /*{ns}*{db}!bu{bu}
 	DatabaseBucket,
+	/// crate::key::database::bq             /*{ns}*{db}!bq{bu}
+	DatabaseBucketUsage,
+	/// crate::key::database::cs             /*{ns}*{db}!cs{cs}
+	DatabaseConsumer,
 	/// crate::key::database::fc             /*{ns}*{db}!fn{fc}
 	DatabaseFunction,
 	/// crate::key::database::ml             /*{ns}*{db}!ml{ml}{vn}
 	DatabaseModel,
 	/// crate::key::database::pa             /*{ns}*{db}!pa{pa}
 	DatabaseParameter,
+	/// crate::key::database::sk             /*{ns}*{db}!sk{cs}
+	DatabaseSinkState,
 	/// crate::key::database::tb             /*{ns}*{db}!tb{tb}
 	DatabaseTable,
 	/// crate::key::database::ts             /*{ns}*{db}!ts{ts}
@@ -175,6 +185,18 @@ pub enum Category {
 	IndexFullTextDocIdsSequenceState,
 	/// crate::key::index::iu                /*{ns}*{db}*{tb}+{ix}*iu{uuid}{uuid}{count}
 	IndexCountState,
+	/// crate::key::index::sc                /*{ns}*{db}*{tb}+{ix}!sc{cell}{id}
+	IndexSpatialCell,
+	/// crate::key::index::tg                /*{ns}*{db}*{tb}+{ix}!tg{trigram}{id}
+	IndexTrigram,
+	/// crate::key::index::cv                /*{ns}*{db}*{tb}+{ix}!cv{id}
+	IndexCoveredRecord,
+	/// crate::key::index::vc                /*{ns}*{db}*{tb}+{ix}!vc
+	IndexIvfCentroids,
+	/// crate::key::index::vl                /*{ns}*{db}*{tb}+{ix}!vl{list}{id}{pos}
+	IndexIvfList,
+	/// crate::key::index::vr                /*{ns}*{db}*{tb}+{ix}!vr{id}
+	IndexIvfRecordLists,
 	/// crate::key::index                    /*{ns}*{db}*{tb}+{ix}*{fd}{id}
 	Index,
 	///
@@ -210,6 +232,7 @@ impl Display for Category {
 	fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
 		let name = match self {
 			Self::Version => "StorageVersion",
+			Self::Encryption => "Encryption",
 			Self::Root => "Root",
 			Self::Access => "Access",
 			Self::AccessRoot => "AccessRoot",
@@ -236,9 +259,12 @@ impl Display for Category {
 			Self::DatabaseApi => "DatabaseApi",
 			Self::DatabaseAnalyzer => "DatabaseAnalyzer",
 			Self::DatabaseBucket => "DatabaseBucket",
+			Self::DatabaseBucketUsage => "DatabaseBucketUsage",
+			Self::DatabaseConsumer => "DatabaseConsumer",
 			Self::DatabaseFunction => "DatabaseFunction",
 			Self::DatabaseModel => "DatabaseModel",
 			Self::DatabaseParameter => "DatabaseParameter",
+			Self::DatabaseSinkState => "DatabaseSinkState",
 			Self::DatabaseTable => "DatabaseTable",
 			Self::DatabaseTableIdentifierBatch => "DatabaseTableIdentifierBatch",
 			Self::DatabaseTableIdentifierState => "DatabaseTableIdentifierState",
@@ -280,12 +306,19 @@ impl Display for Category {
 			Self::SequenceBatch => "SequenceBatch",
 			Self::TaskLease => "TaskLease",
 			Self::RootConfig => "RootConfig",
+			Self::RestoreState => "RestoreState",
 			Self::IndexInvertedDocIds => "IndexInvertedDocIds",
 			Self::IndexFullTextDocIdsSequenceState => "IndexFullTextDocIdsSequenceState",
 			Self::IndexFullTextDocCountAndLength => "IndexFullTextDocCountAndLength",
 			Self::IndexTermDocuments => "IndexTermDocuments",
 			Self::IndexCompaction => "IndexCompaction",
 			Self::IndexCountState => "IndexCountState",
+			Self::IndexSpatialCell => "IndexSpatialCell",
+			Self::IndexTrigram => "IndexTrigram",
+			Self::IndexCoveredRecord => "IndexCoveredRecord",
+			Self::IndexIvfCentroids => "IndexIvfCentroids",
+			Self::IndexIvfList => "IndexIvfList",
+			Self::IndexIvfRecordLists => "IndexIvfRecordLists",
 			Self::EventQueue => "EventQueue",
 			Self::TableIndexIdentifierBatch => "TableIndexIdentifierBatch",
 			Self::TableIndexIdentifierState => "TableIndexIdentifierState",
@@ -0,0 +1,63 @@
+//! Stores the usage of a DEFINE BUCKET with a quota
+use std::borrow::Cow;
+
+use storekey::{BorrowDecode, Encode};
+
+use crate::buc::manager::BucketUsage;
+use crate::catalog::{DatabaseId, NamespaceId};
+use crate::key::category::{Categorise, Category};
+use crate::kvs::impl_kv_key_storekey;
+
+#[derive(Clone, Debug, Eq, PartialEq, PartialOrd, Encode, BorrowDecode)]
+pub(crate) struct BucketUsageKey<'a> {
+	__: u8,
+	_a: u8,
+	pub ns: NamespaceId,
+	_b: u8,
+	pub db: DatabaseId,
+	_c: u8,
+	_d: u8,
+	_e: u8,
+	pub bu: Cow<'a, str>,
+}
+
+impl_kv_key_storekey!(BucketUsageKey<'_> => BucketUsage);
+
+pub fn new(ns: NamespaceId, db: DatabaseId, bu: &str) -> BucketUsageKey<'_> {
+	BucketUsageKey::new(ns, db, bu)
+}
+
+impl Categorise for BucketUsageKey<'_> {
+	fn categorise(&self) -> Category {
+		Category::DatabaseBucketUsage
+	}
+}
+
+impl<'a> BucketUsageKey<'a> {
+	pub fn new(ns: NamespaceId, db: DatabaseId, bu: &'a str) -> Self {
+		Self {
+			__: b'/', // /
+			_a: b'*', // *
+			ns,
+			_b: b'*', // *
+			db,
+			_c: b'!', // !
+			_d: b'b', // b
+			_e: b'q', // q
+			bu: Cow::Borrowed(bu),
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::kvs::KVKey;
+
+	#[test]
+	fn key() {
+		let val = BucketUsageKey::new(NamespaceId(1), DatabaseId(2), "test");
+		let enc = BucketUsageKey::encode_key(&val).unwrap();
+		assert_eq!(enc, b"/*\x00\x00\x00\x01*\x00\x00\x00\x02!bqtest\0");
+	}
+}
@@ -3,12 +3,15 @@ pub mod access;
 pub mod all;
 pub mod ap;
 pub mod az;
+pub mod bq;
 pub mod bu;
 pub mod cg;
+pub mod cs;
 pub mod fc;
 pub mod md;
 pub mod ml;
 pub mod pa;
+pub mod sk;
 pub mod sq;
 pub mod tb;
 pub mod th;
@@ -0,0 +1,63 @@
+//! Stores the delivery state of a changefeed consumer sink
+use std::borrow::Cow;
+
+use storekey::{BorrowDecode, Encode};
+
+use crate::catalog::{DatabaseId, NamespaceId};
+use crate::cf::sink::SinkState;
+use crate::key::category::{Categorise, Category};
+use crate::kvs::impl_kv_key_storekey;
+
+#[derive(Clone, Debug, Eq, PartialEq, PartialOrd, Encode, BorrowDecode)]
+pub(crate) struct SinkStateKey<'a> {
+	__: u8,
+	_a: u8,
+	pub ns: NamespaceId,
+	_b: u8,
+	pub db: DatabaseId,
+	_c: u8,
+	_d: u8,
+	_e: u8,
+	pub cs: Cow<'a, str>,
+}
+
+impl_kv_key_storekey!(SinkStateKey<'_> => SinkState);
+
+pub fn new(ns: NamespaceId, db: DatabaseId, cs: &str) -> SinkStateKey<'_> {
+	SinkStateKey::new(ns, db, cs)
+}
+
+impl Categorise for SinkStateKey<'_> {
+	fn categorise(&self) -> Category {
+		Category::DatabaseSinkState
+	}
+}
+
+impl<'a> SinkStateKey<'a> {
+	pub fn new(ns: NamespaceId, db: DatabaseId, cs: &'a str) -> Self {
+		Self {
+			__: b'/', // /
+			_a: b'*', // *
+			ns,
+			_b: b'*', // *
+			db,
+			_c: b'!', // !
+			_d: b's', // s
+			_e: b'k', // k
+			cs: Cow::Borrowed(cs),
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::kvs::KVKey;
+
+	#[test]
+	fn key() {
+		let val = SinkStateKey::new(NamespaceId(1), DatabaseId(2), "test");
+		let enc = SinkStateKey::encode_key(&val).unwrap();
+		assert_eq!(enc, b"/*\x00\x00\x00\x01*\x00\x00\x00\x02!sktest\0");
+	}
+}
@@ -0,0 +1,55 @@
+//! Stores the encryption header of an encrypted datastore
+use storekey::{BorrowDecode, Encode};
+
+use crate::key::category::{Categorise, Category};
+use crate::kvs::encryption::EncryptionHeader;
+use crate::kvs::impl_kv_key_storekey;
+
+/// The encoded key, which is never encrypted itself
+pub(crate) const KEY: &[u8] = b"!e";
+
+#[derive(Clone, Debug, Eq, PartialEq, PartialOrd, Encode, BorrowDecode)]
+pub(crate) struct Encryption {
+	__: u8,
+	_a: u8,
+}
+
+impl_kv_key_storekey!(Encryption => EncryptionHeader);
+
+pub fn new() -> Encryption {
+	Encryption::new()
+}
+
+impl Categorise for Encryption {
+	fn categorise(&self) -> Category {
+		Category::Encryption
+	}
+}
+
+impl Encryption {
+	pub fn new() -> Self {
+		Self {
+			__: b'!',
+			_a: b'e',
+		}
+	}
+}
+
+impl Default for Encryption {
+	fn default() -> Self {
+		Self::new()
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::kvs::KVKey;
+
+	#[test]
+	fn key() {
+		let val = Encryption::new();
+		let enc = Encryption::encode_key(&val).unwrap();
+		assert_eq!(enc, KEY);
+	}
+}
@@ -13,8 +13,10 @@
 //! using zero-terminated components where appropriate to ensure parsers stop at
 //! the correct boundaries when decoding.
 pub mod all;
+pub mod cv;
 pub mod dc;
 pub mod dl;
+pub mod hc;
 pub mod hd;
 pub mod he;
 pub mod hh;
@@ -22,6 +24,7 @@ pub mod hi;
 pub mod hl;
 pub mod hn;
 pub mod hp;
+pub mod hq;
 pub mod hs;
 pub mod hv;
 pub mod ib;
@@ -31,8 +34,13 @@ pub mod ii;
 pub mod ip;
 pub mod is;
 pub mod iu;
+pub mod sc;
 pub mod td;
+pub mod tg;
 pub mod tt;
+pub mod vc;
+pub mod vl;
+pub mod vr;
 
 use std::borrow::Cow;
 
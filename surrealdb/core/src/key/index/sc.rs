@@ -0,0 +1,187 @@
+//! Stores the geometries of a spatial index, by cell
+//!
+//! A geometry is stored under each of the geohash cells covering its bounding
+//! box. As a cell is a prefix of all the cells it contains, the geometries
+//! stored within an area are found by scanning the keys of every cell starting
+//! with the cells covering this area.
+//!
+//! The key structure includes:
+//! - Namespace, database, table, and index identifiers
+//! - The geohash of the cell
+//! - The id of the record
+
+use std::borrow::Cow;
+
+use anyhow::Result;
+use storekey::{BorrowDecode, Encode};
+
+use crate::catalog::{DatabaseId, IndexId, NamespaceId};
+use crate::key::category::{Categorise, Category};
+use crate::kvs::{KVKey, impl_kv_key_storekey};
+use crate::val::{Geometry, RecordIdKey, TableName};
+
+#[derive(Clone, Debug, Eq, PartialEq, PartialOrd, Encode, BorrowDecode)]
+#[storekey(format = "()")]
+struct ScCell<'a> {
+	__: u8,
+	_a: u8,
+	pub ns: NamespaceId,
+	_b: u8,
+	pub db: DatabaseId,
+	_c: u8,
+	pub tb: Cow<'a, TableName>,
+	_d: u8,
+	pub ix: IndexId,
+	_e: u8,
+	_f: u8,
+	_g: u8,
+	pub cell: Cow<'a, str>,
+}
+
+impl_kv_key_storekey!(ScCell<'_> => ());
+
+impl<'a> ScCell<'a> {
+	fn new(ns: NamespaceId, db: DatabaseId, tb: &'a TableName, ix: IndexId, cell: &'a str) -> Self {
+		Self {
+			__: b'/',
+			_a: b'*',
+			ns,
+			_b: b'*',
+			db,
+			_c: b'*',
+			tb: Cow::Borrowed(tb),
+			_d: b'+',
+			ix,
+			_e: b'!',
+			_f: b's',
+			_g: b'c',
+			cell: Cow::Borrowed(cell),
+		}
+	}
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, PartialOrd, Encode, BorrowDecode)]
+#[storekey(format = "()")]
+pub(crate) struct Sc<'a> {
+	__: u8,
+	_a: u8,
+	pub ns: NamespaceId,
+	_b: u8,
+	pub db: DatabaseId,
+	_c: u8,
+	pub tb: Cow<'a, TableName>,
+	_d: u8,
+	pub ix: IndexId,
+	_e: u8,
+	_f: u8,
+	_g: u8,
+	pub cell: Cow<'a, str>,
+	pub id: Cow<'a, RecordIdKey>,
+}
+
+impl_kv_key_storekey!(Sc<'_> => Geometry);
+
+impl Categorise for Sc<'_> {
+	fn categorise(&self) -> Category {
+		Category::IndexSpatialCell
+	}
+}
+
+impl<'a> Sc<'a> {
+	pub(crate) fn new(
+		ns: NamespaceId,
+		db: DatabaseId,
+		tb: &'a TableName,
+		ix: IndexId,
+		cell: &'a str,
+		id: &'a RecordIdKey,
+	) -> Self {
+		Self {
+			__: b'/',
+			_a: b'*',
+			ns,
+			_b: b'*',
+			db,
+			_c: b'*',
+			tb: Cow::Borrowed(tb),
+			_d: b'+',
+			ix,
+			_e: b'!',
+			_f: b's',
+			_g: b'c',
+			cell: Cow::Borrowed(cell),
+			id: Cow::Borrowed(id),
+		}
+	}
+
+	/// Creates a key range covering the geometries stored in the given cell
+	pub(crate) fn cell_range(
+		ns: NamespaceId,
+		db: DatabaseId,
+		tb: &'a TableName,
+		ix: IndexId,
+		cell: &'a str,
+	) -> Result<(Vec<u8>, Vec<u8>)> {
+		let beg = ScCell::new(ns, db, tb, ix, cell).encode_key()?;
+		let mut end = beg.clone();
+		end.push(0xff);
+		Ok((beg, end))
+	}
+
+	/// Creates a key range covering the geometries stored in the given cell,
+	/// and in every cell it contains
+	pub(crate) fn prefix_range(
+		ns: NamespaceId,
+		db: DatabaseId,
+		tb: &'a TableName,
+		ix: IndexId,
+		cell: &'a str,
+	) -> Result<(Vec<u8>, Vec<u8>)> {
+		let mut beg = ScCell::new(ns, db, tb, ix, cell).encode_key()?;
+		// Remove the terminator of the cell
+		beg.pop();
+		let mut end = beg.clone();
+		end.push(0xff);
+		Ok((beg, end))
+	}
+
+	pub(crate) fn decode_key(k: &[u8]) -> Result<Sc<'_>> {
+		Ok(storekey::decode_borrow(k)?)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn key() {
+		let tb = TableName::from("testtb");
+		let id = RecordIdKey::String("testid".into());
+		let val = Sc::new(NamespaceId(1), DatabaseId(2), &tb, IndexId(3), "u09t", &id);
+		let enc = Sc::encode_key(&val).unwrap();
+		let dec = Sc::decode_key(&enc).unwrap();
+		assert_eq!(val, dec);
+		assert!(
+			enc.starts_with(b"/*\x00\x00\x00\x01*\x00\x00\x00\x02*testtb\0+\0\0\0\x03!scu09t\0")
+		);
+	}
+
+	#[test]
+	fn ranges() {
+		let tb = TableName::from("testtb");
+		let id = RecordIdKey::String("testid".into());
+		let key = Sc::new(NamespaceId(1), DatabaseId(2), &tb, IndexId(3), "u09t", &id)
+			.encode_key()
+			.unwrap();
+		let contains = |(beg, end): (Vec<u8>, Vec<u8>)| beg <= key && key < end;
+		let range = |cell| Sc::cell_range(NamespaceId(1), DatabaseId(2), &tb, IndexId(3), cell);
+		assert!(contains(range("u09t").unwrap()));
+		assert!(!contains(range("u09").unwrap()));
+		let range = |cell| Sc::prefix_range(NamespaceId(1), DatabaseId(2), &tb, IndexId(3), cell);
+		assert!(contains(range("u09t").unwrap()));
+		assert!(contains(range("u0").unwrap()));
+		assert!(contains(range("").unwrap()));
+		assert!(!contains(range("u09v").unwrap()));
+	}
+}
@@ -16,6 +16,7 @@
 
 use std::borrow::Cow;
 
+use anyhow::Result;
 use roaring::RoaringTreemap;
 use storekey::{BorrowDecode, Encode};
 
@@ -23,7 +24,7 @@ use crate::catalog::{DatabaseId, IndexId, NamespaceId};
 use crate::idx::ft::fulltext::TermDocument;
 use crate::idx::seqdocids::DocId;
 use crate::key::category::{Categorise, Category};
-use crate::kvs::impl_kv_key_storekey;
+use crate::kvs::{KVKey, impl_kv_key_storekey};
 use crate::val::TableName;
 
 #[derive(Clone, Debug, Eq, PartialEq, PartialOrd, Encode, BorrowDecode)]
@@ -76,6 +77,49 @@ impl<'a> TdRoot<'a> {
 			term: Cow::Borrowed(term),
 		}
 	}
+
+	/// Creates a key range covering the keys of every term starting with the
+	/// given prefix
+	///
+	/// The range includes both the root keys and the term-document keys, and is
+	/// used to walk the term dictionary of the index.
+	pub(crate) fn prefix_range(
+		ns: NamespaceId,
+		db: DatabaseId,
+		tb: &'a TableName,
+		ix: IndexId,
+		prefix: &'a str,
+	) -> Result<(Vec<u8>, Vec<u8>)> {
+		let mut beg = Self::new(ns, db, tb, ix, prefix).encode_key()?;
+		// Remove the terminator of the prefix
+		beg.pop();
+		let mut end = beg.clone();
+		end.push(0xff);
+		Ok((beg, end))
+	}
+
+	/// Creates the first key following every key of the given term
+	pub(crate) fn term_end(
+		ns: NamespaceId,
+		db: DatabaseId,
+		tb: &'a TableName,
+		ix: IndexId,
+		term: &'a str,
+	) -> Result<Vec<u8>> {
+		let mut key = Self::new(ns, db, tb, ix, term).encode_key()?;
+		// Skip the document ids of the term-document keys
+		key.extend([0xff; 9]);
+		Ok(key)
+	}
+
+	/// Decodes the term of either a root key or a term-document key
+	pub(crate) fn decode_term(k: &[u8]) -> Result<String> {
+		if let Ok(td) = storekey::decode_borrow::<Td>(k) {
+			return Ok(td.term.into_owned());
+		}
+		let root: TdRoot = storekey::decode_borrow(k)?;
+		Ok(root.term.into_owned())
+	}
 }
 
 #[derive(Clone, Debug, Eq, PartialEq, PartialOrd, Encode, BorrowDecode)]
@@ -159,6 +203,26 @@ mod tests {
 		assert_eq!(enc, b"/*\x00\x00\x00\x01*\x00\x00\x00\x02*testtb\0+\0\0\0\x03!tdterm\0");
 	}
 
+	#[test]
+	fn prefix_range() {
+		let tb = TableName::from("testtb");
+		let (beg, end) =
+			TdRoot::prefix_range(NamespaceId(1), DatabaseId(2), &tb, IndexId(3), "te").unwrap();
+		assert_eq!(beg, b"/*\x00\x00\x00\x01*\x00\x00\x00\x02*testtb\0+\0\0\0\x03!tdte");
+		assert_eq!(end, b"/*\x00\x00\x00\x01*\x00\x00\x00\x02*testtb\0+\0\0\0\x03!tdte\xff");
+	}
+
+	#[test]
+	fn decode_term() {
+		let tb = TableName::from("testtb");
+		let root = TdRoot::new(NamespaceId(1), DatabaseId(2), &tb, IndexId(3), "term");
+		let enc = TdRoot::encode_key(&root).unwrap();
+		assert_eq!(TdRoot::decode_term(&enc).unwrap(), "term");
+		let val = Td::new(NamespaceId(1), DatabaseId(2), &tb, IndexId(3), "term", 129);
+		let enc = Td::encode_key(&val).unwrap();
+		assert_eq!(TdRoot::decode_term(&enc).unwrap(), "term");
+	}
+
 	#[test]
 	fn key() {
 		let tb = TableName::from("testtb");
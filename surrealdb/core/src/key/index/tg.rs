@@ -0,0 +1,172 @@
+//! Stores the records of a trigram index, by trigram
+//!
+//! The id of a record is stored under each distinct sequence of three
+//! consecutive characters of its indexed strings. The records containing a
+//! given trigram are found, in the order of their ids, by scanning the keys of
+//! this trigram.
+//!
+//! The key structure includes:
+//! - Namespace, database, table, and index identifiers
+//! - The trigram
+//! - The id of the record
+
+use std::borrow::Cow;
+
+use anyhow::Result;
+use storekey::{BorrowDecode, Encode};
+
+use crate::catalog::{DatabaseId, IndexId, NamespaceId};
+use crate::key::category::{Categorise, Category};
+use crate::kvs::{KVKey, impl_kv_key_storekey};
+use crate::val::{RecordIdKey, TableName};
+
+#[derive(Clone, Debug, Eq, PartialEq, PartialOrd, Encode, BorrowDecode)]
+#[storekey(format = "()")]
+struct TgTrigram<'a> {
+	__: u8,
+	_a: u8,
+	pub ns: NamespaceId,
+	_b: u8,
+	pub db: DatabaseId,
+	_c: u8,
+	pub tb: Cow<'a, TableName>,
+	_d: u8,
+	pub ix: IndexId,
+	_e: u8,
+	_f: u8,
+	_g: u8,
+	pub trigram: Cow<'a, str>,
+}
+
+impl_kv_key_storekey!(TgTrigram<'_> => ());
+
+impl<'a> TgTrigram<'a> {
+	fn new(
+		ns: NamespaceId,
+		db: DatabaseId,
+		tb: &'a TableName,
+		ix: IndexId,
+		trigram: &'a str,
+	) -> Self {
+		Self {
+			__: b'/',
+			_a: b'*',
+			ns,
+			_b: b'*',
+			db,
+			_c: b'*',
+			tb: Cow::Borrowed(tb),
+			_d: b'+',
+			ix,
+			_e: b'!',
+			_f: b't',
+			_g: b'g',
+			trigram: Cow::Borrowed(trigram),
+		}
+	}
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, PartialOrd, Encode, BorrowDecode)]
+#[storekey(format = "()")]
+pub(crate) struct Tg<'a> {
+	__: u8,
+	_a: u8,
+	pub ns: NamespaceId,
+	_b: u8,
+	pub db: DatabaseId,
+	_c: u8,
+	pub tb: Cow<'a, TableName>,
+	_d: u8,
+	pub ix: IndexId,
+	_e: u8,
+	_f: u8,
+	_g: u8,
+	pub trigram: Cow<'a, str>,
+	pub id: Cow<'a, RecordIdKey>,
+}
+
+impl_kv_key_storekey!(Tg<'_> => ());
+
+impl Categorise for Tg<'_> {
+	fn categorise(&self) -> Category {
+		Category::IndexTrigram
+	}
+}
+
+impl<'a> Tg<'a> {
+	pub(crate) fn new(
+		ns: NamespaceId,
+		db: DatabaseId,
+		tb: &'a TableName,
+		ix: IndexId,
+		trigram: &'a str,
+		id: &'a RecordIdKey,
+	) -> Self {
+		Self {
+			__: b'/',
+			_a: b'*',
+			ns,
+			_b: b'*',
+			db,
+			_c: b'*',
+			tb: Cow::Borrowed(tb),
+			_d: b'+',
+			ix,
+			_e: b'!',
+			_f: b't',
+			_g: b'g',
+			trigram: Cow::Borrowed(trigram),
+			id: Cow::Borrowed(id),
+		}
+	}
+
+	/// Creates a key range covering the records stored under the given trigram
+	pub(crate) fn trigram_range(
+		ns: NamespaceId,
+		db: DatabaseId,
+		tb: &'a TableName,
+		ix: IndexId,
+		trigram: &'a str,
+	) -> Result<(Vec<u8>, Vec<u8>)> {
+		let beg = TgTrigram::new(ns, db, tb, ix, trigram).encode_key()?;
+		let mut end = beg.clone();
+		end.push(0xff);
+		Ok((beg, end))
+	}
+
+	pub(crate) fn decode_key(k: &[u8]) -> Result<Tg<'_>> {
+		Ok(storekey::decode_borrow(k)?)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn key() {
+		let tb = TableName::from("testtb");
+		let id = RecordIdKey::String("testid".into());
+		let val = Tg::new(NamespaceId(1), DatabaseId(2), &tb, IndexId(3), "abc", &id);
+		let enc = Tg::encode_key(&val).unwrap();
+		let dec = Tg::decode_key(&enc).unwrap();
+		assert_eq!(val, dec);
+		assert!(
+			enc.starts_with(b"/*\x00\x00\x00\x01*\x00\x00\x00\x02*testtb\0+\0\0\0\x03!tgabc\0")
+		);
+	}
+
+	#[test]
+	fn range() {
+		let tb = TableName::from("testtb");
+		let id = RecordIdKey::String("testid".into());
+		let key = Tg::new(NamespaceId(1), DatabaseId(2), &tb, IndexId(3), "abc", &id)
+			.encode_key()
+			.unwrap();
+		let contains = |(beg, end): (Vec<u8>, Vec<u8>)| beg <= key && key < end;
+		let range = |t| Tg::trigram_range(NamespaceId(1), DatabaseId(2), &tb, IndexId(3), t);
+		assert!(contains(range("abc").unwrap()));
+		assert!(!contains(range("ab").unwrap()));
+		assert!(!contains(range("abd").unwrap()));
+	}
+}
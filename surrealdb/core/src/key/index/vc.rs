@@ -0,0 +1,79 @@
+//! Stores the centroids of an IVF index
+//!
+//! Each centroid represents one of the lists the vectors of the index are
+//! partitioned into.
+//!
+//! The key structure includes:
+//! - Namespace, database, table, and index identifiers
+use std::borrow::Cow;
+
+use storekey::{BorrowDecode, Encode};
+
+use crate::catalog::{DatabaseId, IndexId, NamespaceId};
+use crate::idx::trees::ivf::IvfCentroids;
+use crate::key::category::{Categorise, Category};
+use crate::kvs::impl_kv_key_storekey;
+use crate::val::TableName;
+
+#[derive(Debug, Clone, PartialEq, Encode, BorrowDecode)]
+#[storekey(format = "()")]
+pub(crate) struct Vc<'a> {
+	__: u8,
+	_a: u8,
+	pub ns: NamespaceId,
+	_b: u8,
+	pub db: DatabaseId,
+	_c: u8,
+	pub tb: Cow<'a, TableName>,
+	_d: u8,
+	pub ix: IndexId,
+	_e: u8,
+	_f: u8,
+	_g: u8,
+}
+
+impl_kv_key_storekey!(Vc<'_> => IvfCentroids);
+
+impl Categorise for Vc<'_> {
+	fn categorise(&self) -> Category {
+		Category::IndexIvfCentroids
+	}
+}
+
+impl<'a> Vc<'a> {
+	pub(crate) fn new(ns: NamespaceId, db: DatabaseId, tb: &'a TableName, ix: IndexId) -> Self {
+		Self {
+			__: b'/',
+			_a: b'*',
+			ns,
+			_b: b'*',
+			db,
+			_c: b'*',
+			tb: Cow::Borrowed(tb),
+			_d: b'+',
+			ix,
+			_e: b'!',
+			_f: b'v',
+			_g: b'c',
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::kvs::KVKey;
+
+	#[test]
+	fn key() {
+		let tb = TableName::from("testtb");
+		let val = Vc::new(NamespaceId(1), DatabaseId(2), &tb, IndexId(3));
+		let enc = Vc::encode_key(&val).unwrap();
+		assert_eq!(
+			enc,
+			b"/*\x00\x00\x00\x01*\x00\x00\x00\x02*testtb\0+\0\0\0\x03!vc",
+			"{}",
+			String::from_utf8_lossy(&enc)
+		);
+	}
+}
@@ -0,0 +1,170 @@
+//! Stores the vectors of an IVF index, by list
+//!
+//! A vector is stored under the list of its nearest centroid, so that a search
+//! only scans the keys of the lists nearest to the query vector.
+//!
+//! The key structure includes:
+//! - Namespace, database, table, and index identifiers
+//! - The number of the list
+//! - The id of the record
+//! - The position of the vector among the indexed values of the record
+
+use std::borrow::Cow;
+
+use anyhow::Result;
+use storekey::{BorrowDecode, Encode};
+
+use crate::catalog::{DatabaseId, IndexId, NamespaceId};
+use crate::idx::trees::vector::SerializedVector;
+use crate::key::category::{Categorise, Category};
+use crate::kvs::{KVKey, impl_kv_key_storekey};
+use crate::val::{RecordIdKey, TableName};
+
+#[derive(Clone, Debug, Eq, PartialEq, PartialOrd, Encode, BorrowDecode)]
+#[storekey(format = "()")]
+struct VlList<'a> {
+	__: u8,
+	_a: u8,
+	pub ns: NamespaceId,
+	_b: u8,
+	pub db: DatabaseId,
+	_c: u8,
+	pub tb: Cow<'a, TableName>,
+	_d: u8,
+	pub ix: IndexId,
+	_e: u8,
+	_f: u8,
+	_g: u8,
+	pub list: u16,
+}
+
+impl_kv_key_storekey!(VlList<'_> => ());
+
+impl<'a> VlList<'a> {
+	fn new(ns: NamespaceId, db: DatabaseId, tb: &'a TableName, ix: IndexId, list: u16) -> Self {
+		Self {
+			__: b'/',
+			_a: b'*',
+			ns,
+			_b: b'*',
+			db,
+			_c: b'*',
+			tb: Cow::Borrowed(tb),
+			_d: b'+',
+			ix,
+			_e: b'!',
+			_f: b'v',
+			_g: b'l',
+			list,
+		}
+	}
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, PartialOrd, Encode, BorrowDecode)]
+#[storekey(format = "()")]
+pub(crate) struct Vl<'a> {
+	__: u8,
+	_a: u8,
+	pub ns: NamespaceId,
+	_b: u8,
+	pub db: DatabaseId,
+	_c: u8,
+	pub tb: Cow<'a, TableName>,
+	_d: u8,
+	pub ix: IndexId,
+	_e: u8,
+	_f: u8,
+	_g: u8,
+	pub list: u16,
+	pub id: Cow<'a, RecordIdKey>,
+	pub pos: u32,
+}
+
+impl_kv_key_storekey!(Vl<'_> => SerializedVector);
+
+impl Categorise for Vl<'_> {
+	fn categorise(&self) -> Category {
+		Category::IndexIvfList
+	}
+}
+
+impl<'a> Vl<'a> {
+	pub(crate) fn new(
+		ns: NamespaceId,
+		db: DatabaseId,
+		tb: &'a TableName,
+		ix: IndexId,
+		list: u16,
+		id: &'a RecordIdKey,
+		pos: u32,
+	) -> Self {
+		Self {
+			__: b'/',
+			_a: b'*',
+			ns,
+			_b: b'*',
+			db,
+			_c: b'*',
+			tb: Cow::Borrowed(tb),
+			_d: b'+',
+			ix,
+			_e: b'!',
+			_f: b'v',
+			_g: b'l',
+			list,
+			id: Cow::Borrowed(id),
+			pos,
+		}
+	}
+
+	/// Creates a key range covering the vectors stored in the given list
+	pub(crate) fn list_range(
+		ns: NamespaceId,
+		db: DatabaseId,
+		tb: &'a TableName,
+		ix: IndexId,
+		list: u16,
+	) -> Result<(Vec<u8>, Vec<u8>)> {
+		let beg = VlList::new(ns, db, tb, ix, list).encode_key()?;
+		let mut end = beg.clone();
+		end.push(0xff);
+		Ok((beg, end))
+	}
+
+	pub(crate) fn decode_key(k: &[u8]) -> Result<Vl<'_>> {
+		Ok(storekey::decode_borrow(k)?)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn key() {
+		let tb = TableName::from("testtb");
+		let id = RecordIdKey::String("testid".into());
+		let val = Vl::new(NamespaceId(1), DatabaseId(2), &tb, IndexId(3), 7, &id, 1);
+		let enc = Vl::encode_key(&val).unwrap();
+		let dec = Vl::decode_key(&enc).unwrap();
+		assert_eq!(val, dec);
+		assert!(
+			enc.starts_with(b"/*\x00\x00\x00\x01*\x00\x00\x00\x02*testtb\0+\0\0\0\x03!vl\0\x07")
+		);
+	}
+
+	#[test]
+	fn list_range() {
+		let tb = TableName::from("testtb");
+		let id = RecordIdKey::String("testid".into());
+		let (beg, end) = Vl::list_range(NamespaceId(1), DatabaseId(2), &tb, IndexId(3), 7).unwrap();
+		let key = Vl::new(NamespaceId(1), DatabaseId(2), &tb, IndexId(3), 7, &id, 0)
+			.encode_key()
+			.unwrap();
+		assert!(beg <= key && key < end);
+		let other = Vl::new(NamespaceId(1), DatabaseId(2), &tb, IndexId(3), 8, &id, 0)
+			.encode_key()
+			.unwrap();
+		assert!(other >= end);
+	}
+}
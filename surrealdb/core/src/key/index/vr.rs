@@ -0,0 +1,86 @@
+//! Stores the lists of the vectors of a record indexed by an IVF index
+//!
+//! The list a vector is stored in depends on the centroids at the time it was
+//! indexed. Recording it allows the vectors of a record to be removed without
+//! computing their nearest centroid again.
+//!
+//! The key structure includes:
+//! - Namespace, database, table, and index identifiers
+//! - The id of the record
+
+use std::borrow::Cow;
+
+use storekey::{BorrowDecode, Encode};
+
+use crate::catalog::{DatabaseId, IndexId, NamespaceId};
+use crate::idx::trees::ivf::IvfRecordLists;
+use crate::key::category::{Categorise, Category};
+use crate::kvs::impl_kv_key_storekey;
+use crate::val::{RecordIdKey, TableName};
+
+#[derive(Clone, Debug, Eq, PartialEq, PartialOrd, Encode, BorrowDecode)]
+#[storekey(format = "()")]
+pub(crate) struct Vr<'a> {
+	__: u8,
+	_a: u8,
+	pub ns: NamespaceId,
+	_b: u8,
+	pub db: DatabaseId,
+	_c: u8,
+	pub tb: Cow<'a, TableName>,
+	_d: u8,
+	pub ix: IndexId,
+	_e: u8,
+	_f: u8,
+	_g: u8,
+	pub id: Cow<'a, RecordIdKey>,
+}
+
+impl_kv_key_storekey!(Vr<'_> => IvfRecordLists);
+
+impl Categorise for Vr<'_> {
+	fn categorise(&self) -> Category {
+		Category::IndexIvfRecordLists
+	}
+}
+
+impl<'a> Vr<'a> {
+	pub(crate) fn new(
+		ns: NamespaceId,
+		db: DatabaseId,
+		tb: &'a TableName,
+		ix: IndexId,
+		id: &'a RecordIdKey,
+	) -> Self {
+		Self {
+			__: b'/',
+			_a: b'*',
+			ns,
+			_b: b'*',
+			db,
+			_c: b'*',
+			tb: Cow::Borrowed(tb),
+			_d: b'+',
+			ix,
+			_e: b'!',
+			_f: b'v',
+			_g: b'r',
+			id: Cow::Borrowed(id),
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::kvs::KVKey;
+
+	#[test]
+	fn key() {
+		let tb = TableName::from("testtb");
+		let id = RecordIdKey::String("testid".into());
+		let val = Vr::new(NamespaceId(1), DatabaseId(2), &tb, IndexId(3), &id);
+		let enc = Vr::encode_key(&val).unwrap();
+		assert!(enc.starts_with(b"/*\x00\x00\x00\x01*\x00\x00\x00\x02*testtb\0+\0\0\0\x03!vr"));
+	}
+}
@@ -18,6 +18,7 @@
 //! - {ns_name}: &str
 //! - {db_name}: &str
 //!
+//! crate::key::encryption               !e -> EncryptionHeader
 //! crate::key::version                  !v -> Version
 //!
 //! crate::key::root::all                /
@@ -27,6 +28,7 @@
 //! crate::key::root::ns                 /!ns{ns} -> NamespaceDefinition
 //! crate::key::root::us                 /!us{us}
 //! crate::key::root::tl                 /!tl{tl}
+//! crate::key::root::rs                 /!rs
 //! crate::key::root::cg                 /!cg{ty}
 //!
 //! crate::key::node::all                /${nd}
@@ -49,10 +51,12 @@
 //! crate::key::database::ac             /*{ns}*{db}!ac{ac_name}
 //! crate::key::database::az             /*{ns}*{db}!az{az_name}
 //! crate::key::database::bu             /*{ns}*{db}!bu{bu_name}
+//! crate::key::database::cs             /*{ns}*{db}!cs{cs_name} -> ConsumerDefinition
 //! crate::key::database::fc             /*{ns}*{db}!fn{fc_name}
 //! crate::key::database::md             /*{ns}*{db}!md{md_name} -> ModuleDefinition
 //! crate::key::database::ml             /*{ns}*{db}!ml{ml_name}{vn}
 //! crate::key::database::pa             /*{ns}*{db}!pa{pa_name}
+//! crate::key::database::sk             /*{ns}*{db}!sk{cs_name} -> SinkState
 //! crate::key::database::sq             /*{ns}*{db}!sq{sq_name}
 //! crate::key::database::tb             /*{ns}*{db}!tb{tb_name} -> TableDefinition
 //! crate::key::database::ti             /+{ns}*{db}!ti
@@ -85,7 +89,12 @@
 //! crate::key::index::bt                /*{ns}*{db}*{tb_name}+{ix}!bt{id}
 //! crate::key::index::bu                /*{ns}*{db}*{tb_name}+{ix}!bu{id}
 //! crate::key::index::dl                /*{ns}*{db}*{tb_name}+{ix}!dl{id}
+//! crate::key::index::sc                /*{ns}*{db}*{tb_name}+{ix}!sc{cell}{id}
 //! crate::key::index::tf                /*{ns}*{db}*{tb_name}+{ix}!tf{term}{id}
+//! crate::key::index::tg                /*{ns}*{db}*{tb_name}+{ix}!tg{trigram}{id}
+//! crate::key::index::cv                /*{ns}*{db}*{tb_name}+{ix}!cv{id}
+//! crate::key::index::vc                /*{ns}*{db}*{tb_name}+{ix}!vc
+//! crate::key::index::vl                /*{ns}*{db}*{tb_name}+{ix}!vl{list}{id}
 //! crate::key::index                    /*{ns}*{db}*{tb_name}+{ix}*{fd}{id}
 //!
 //! crate::key::change::vs_key_prefix    /*{ns}*{db}#
@@ -108,6 +117,7 @@ pub(crate) mod category;
 pub(crate) mod change;
 pub(crate) mod database;
 pub(crate) mod debug;
+pub(crate) mod encryption;
 pub(crate) mod graph;
 pub(crate) mod index;
 pub(crate) mod namespace;
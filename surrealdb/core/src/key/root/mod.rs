@@ -8,5 +8,6 @@ pub mod nh;
 pub mod ni;
 pub mod ns;
 pub mod root_config;
+pub mod rs;
 pub mod tl;
 pub mod us;
@@ -0,0 +1,46 @@
+//! Stores the state of the last backup restored into the datastore
+use storekey::{BorrowDecode, Encode};
+
+use crate::key::category::{Categorise, Category};
+use crate::kvs::backup::RestoreState;
+use crate::kvs::impl_kv_key_storekey;
+
+#[derive(Clone, Debug, Eq, PartialEq, PartialOrd, Encode, BorrowDecode)]
+pub(crate) struct Rs {
+	__: u8,
+	_a: u8,
+	_b: u8,
+	_c: u8,
+}
+
+impl_kv_key_storekey!(Rs => RestoreState);
+
+impl Categorise for Rs {
+	fn categorise(&self) -> Category {
+		Category::RestoreState
+	}
+}
+
+impl Rs {
+	pub(crate) fn new() -> Self {
+		Self {
+			__: b'/',
+			_a: b'!',
+			_b: b'r',
+			_c: b's',
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::kvs::KVKey;
+
+	#[test]
+	fn key() {
+		let val = Rs::new();
+		let enc = Rs::encode_key(&val).unwrap();
+		assert_eq!(enc, b"/!rs");
+	}
+}
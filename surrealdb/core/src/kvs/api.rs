@@ -488,6 +488,12 @@ pub trait Transactable: requirements::TransactionRequirements {
 		}
 	}
 
+	/// Get the version of the snapshot which this transaction reads from, if
+	/// the storage engine exposes it
+	async fn snapshot_version(&self) -> Result<Option<u64>> {
+		Ok(None)
+	}
+
 	async fn compact(&self, _range: Option<Range<Key>>) -> anyhow::Result<()> {
 		bail!(Error::CompactionNotSupported)
 	}
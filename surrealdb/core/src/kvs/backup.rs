@@ -0,0 +1,392 @@
+//! Backups of the entire key space.
+//!
+//! A backup starts with the [`MAGIC`] header, followed by length-prefixed
+//! revisioned frames, as used by the binary export format. The first frame is
+//! always the [`Manifest`], followed by batches of key-value entries, and a
+//! final frame which marks the backup as complete.
+//!
+//! A full backup contains every key in a single consistent snapshot of the
+//! datastore. An incremental backup contains only the keys which have been
+//! created, updated, or deleted since the snapshot version of a previous
+//! backup, and requires a storage engine which supports versioned queries.
+//!
+//! Keys which belong to the running datastore rather than to its data, such
+//! as node registrations and task leases, are not included in backups.
+//!
+//! A restore is applied in batches, so it is not atomic. The progress of a
+//! restore is recorded in the datastore, so that a failed restore can be
+//! retried, and so that an incremental backup is only ever applied over the
+//! backup which it is based on.
+
+use std::cmp::Ordering;
+use std::collections::VecDeque;
+use std::ops::Range;
+use std::pin::pin;
+
+use anyhow::{Result, bail, ensure};
+use async_channel::Sender;
+use bytes::{Buf, Bytes, BytesMut};
+use futures::{Stream, StreamExt};
+use revision::revisioned;
+
+use super::export::binary::{FrameReader, encode_frame};
+use super::{Key, Transaction, Val, impl_kv_value_revisioned};
+use crate::catalog::providers::NamespaceProvider;
+use crate::cnf::EXPORT_BATCH_SIZE;
+use crate::err::Error;
+
+/// The header which identifies a backup
+pub(crate) const MAGIC: &[u8] = b"SURREALDB-BACKUP";
+
+/// The current version of the backup format
+const FORMAT_VERSION: u16 = 1;
+
+/// Describes the contents of a backup
+#[revisioned(revision = 1)]
+#[derive(Clone, Debug)]
+pub struct Manifest {
+	/// The version of the backup format
+	pub format: u16,
+	/// The version of SurrealDB which created the backup
+	pub version: String,
+	/// The snapshot version at which the backup was taken
+	pub timestamp: u64,
+	/// The snapshot version of the backup which this backup is based on
+	pub since: Option<u64>,
+}
+
+impl Manifest {
+	/// Check if this is an incremental backup
+	pub fn is_incremental(&self) -> bool {
+		self.since.is_some()
+	}
+}
+
+/// The progress of the last backup restored into a datastore
+#[revisioned(revision = 1)]
+#[derive(Clone, Debug)]
+pub(crate) struct RestoreState {
+	/// The snapshot version of the restored backup
+	timestamp: u64,
+	/// The snapshot version of the backup which it is based on
+	since: Option<u64>,
+	/// Whether the restore completed successfully
+	complete: bool,
+}
+
+impl_kv_value_revisioned!(RestoreState);
+
+/// A key which was set or deleted
+#[revisioned(revision = 1)]
+#[derive(Clone, Debug)]
+struct Entry {
+	key: Key,
+	/// The value of the key, or `None` if the key was deleted
+	val: Option<Val>,
+}
+
+#[revisioned(revision = 1)]
+#[derive(Clone, Debug)]
+enum Frame {
+	/// The manifest, which is always the first frame
+	Manifest(Manifest),
+	/// A batch of entries
+	Entries(Vec<Entry>),
+	/// The total number of entries, which is always the last frame
+	Complete(u64),
+}
+
+/// The range which covers the entire key space
+fn keyspace() -> Range<Key> {
+	vec![0x00]..vec![0xff]
+}
+
+/// The range which covers all keys below the root key
+fn rootspace() -> Range<Key> {
+	vec![b'/']..vec![b'/' + 1]
+}
+
+/// Checks if a key belongs to the running datastore rather than to its data
+fn is_local(key: &[u8]) -> bool {
+	key == crate::key::encryption::KEY
+		|| key.starts_with(b"/!nd")
+		|| key.starts_with(b"/!tl")
+		|| key.starts_with(b"/!rs")
+		|| key.starts_with(b"/$")
+}
+
+/// A batched scan over the key space at a specific version
+struct Cursor<'a> {
+	tx: &'a Transaction,
+	next: Option<Range<Key>>,
+	version: Option<u64>,
+	batch: VecDeque<(Key, Val)>,
+}
+
+impl<'a> Cursor<'a> {
+	fn new(tx: &'a Transaction, version: Option<u64>) -> Self {
+		Self {
+			tx,
+			next: Some(keyspace()),
+			version,
+			batch: VecDeque::new(),
+		}
+	}
+
+	/// Fetches the next batch of entries if the current batch is exhausted
+	async fn fill(&mut self) -> Result<()> {
+		while self.batch.is_empty()
+			&& let Some(rng) = self.next.take()
+		{
+			let batch = self.tx.batch_keys_vals(rng, *EXPORT_BATCH_SIZE, self.version).await?;
+			self.next = batch.next;
+			self.batch = batch.result.into_iter().filter(|(k, _)| !is_local(k)).collect();
+		}
+		Ok(())
+	}
+}
+
+impl Transaction {
+	/// Writes a backup of the entire key space, as seen by this transaction.
+	///
+	/// The `timestamp` is the snapshot version of this transaction, which can
+	/// be used as the base of a later incremental backup. If `since` is
+	/// specified, only the changes after that snapshot version are written.
+	pub async fn backup(
+		&self,
+		timestamp: u64,
+		since: Option<u64>,
+		chn: Sender<Vec<u8>>,
+	) -> Result<()> {
+		// Output the header and manifest
+		chn.send(MAGIC.to_vec()).await?;
+		let manifest = Manifest {
+			format: FORMAT_VERSION,
+			version: crate::env::VERSION.to_owned(),
+			timestamp,
+			since,
+		};
+		chn.send(encode_frame(&Frame::Manifest(manifest))?).await?;
+		// Scan the current and previous snapshots in key order
+		let mut cur = Cursor::new(self, None);
+		let mut old = since.map(|v| Cursor::new(self, Some(v)));
+		let mut entries = Vec::new();
+		let mut total = 0;
+		loop {
+			cur.fill().await?;
+			if let Some(old) = old.as_mut() {
+				old.fill().await?;
+			}
+			// Compare the next keys from each snapshot
+			let step = match (cur.batch.front(), old.as_ref().and_then(|o| o.batch.front())) {
+				(None, None) => break,
+				(Some(_), None) => Ordering::Less,
+				(None, Some(_)) => Ordering::Greater,
+				(Some((k, _)), Some((o, _))) => k.cmp(o),
+			};
+			let entry = match step {
+				// The key exists in the current snapshot only
+				Ordering::Less => cur.batch.pop_front().map(|(key, val)| Entry {
+					key,
+					val: Some(val),
+				}),
+				// The key was deleted since the previous snapshot
+				Ordering::Greater => {
+					old.as_mut().and_then(|o| o.batch.pop_front()).map(|(key, _)| Entry {
+						key,
+						val: None,
+					})
+				}
+				// The key exists in both snapshots, so check if it changed
+				Ordering::Equal => {
+					let prev = old.as_mut().and_then(|o| o.batch.pop_front());
+					match (cur.batch.pop_front(), prev) {
+						(Some((key, val)), Some((_, prev))) if val != prev => Some(Entry {
+							key,
+							val: Some(val),
+						}),
+						_ => None,
+					}
+				}
+			};
+			entries.extend(entry);
+			if entries.len() >= *EXPORT_BATCH_SIZE as usize {
+				total += entries.len() as u64;
+				chn.send(encode_frame(&Frame::Entries(std::mem::take(&mut entries)))?).await?;
+			}
+		}
+		if !entries.is_empty() {
+			total += entries.len() as u64;
+			chn.send(encode_frame(&Frame::Entries(entries))?).await?;
+		}
+		// Mark the backup as complete
+		chn.send(encode_frame(&Frame::Complete(total))?).await?;
+		Ok(())
+	}
+}
+
+/// Reads a backup from a byte stream, applying each batch of entries using a
+/// new transaction created by the `txn` callback.
+pub(crate) async fn restore<S, F, Fut>(stream: S, txn: F) -> Result<Manifest>
+where
+	S: Stream<Item = Result<Bytes>>,
+	F: Fn() -> Fut,
+	Fut: Future<Output = Result<Transaction>>,
+{
+	let mut stream = pin!(stream);
+	// Check the header
+	let mut buffer = BytesMut::new();
+	while buffer.len() < MAGIC.len() {
+		match stream.next().await {
+			Some(bytes) => buffer.extend_from_slice(&bytes?),
+			None => break,
+		}
+	}
+	ensure!(
+		buffer.starts_with(MAGIC),
+		Error::InvalidExport("the backup header is missing".to_owned())
+	);
+	buffer.advance(MAGIC.len());
+	let mut reader = FrameReader::new(buffer, stream);
+	// The backup must start with a supported manifest
+	let manifest = match reader.next::<Frame>().await? {
+		Some(Frame::Manifest(manifest)) => manifest,
+		_ => bail!(Error::InvalidExport("the manifest is missing".to_owned())),
+	};
+	ensure!(
+		manifest.format <= FORMAT_VERSION,
+		Error::InvalidExport(format!("format version {} is not supported", manifest.format))
+	);
+	// Check that the backup can be restored, and record that it is in progress
+	let tx = txn().await?;
+	let retry = match check(&tx, &manifest).await {
+		Ok(retry) => retry,
+		Err(e) => {
+			tx.cancel().await?;
+			return Err(e);
+		}
+	};
+	let mut state = RestoreState {
+		timestamp: manifest.timestamp,
+		since: manifest.since,
+		complete: false,
+	};
+	tx.set(&crate::key::root::rs::Rs::new(), &state, None).await?;
+	tx.commit().await?;
+	// Remove the data of a previously failed full restore
+	if retry && !manifest.is_incremental() {
+		clear(&txn).await?;
+	}
+	// Apply each batch of entries in its own transaction
+	let mut total = 0;
+	loop {
+		let entries = match reader.next::<Frame>().await? {
+			Some(Frame::Entries(entries)) => entries,
+			Some(Frame::Complete(count)) if count == total => break,
+			Some(Frame::Complete(_)) => {
+				bail!(Error::InvalidExport("the number of entries does not match".to_owned()))
+			}
+			Some(Frame::Manifest(_)) => {
+				bail!(Error::InvalidExport("unexpected manifest".to_owned()))
+			}
+			None => bail!(Error::InvalidExport("the backup is incomplete".to_owned())),
+		};
+		total += entries.len() as u64;
+		let tx = txn().await?;
+		for entry in entries {
+			let res = match entry.val {
+				Some(val) => tx.set(&entry.key, &val, None).await,
+				None => tx.del(&entry.key).await,
+			};
+			if let Err(e) = res {
+				tx.cancel().await?;
+				return Err(e);
+			}
+		}
+		tx.commit().await?;
+	}
+	// Nothing may follow the final frame
+	ensure!(
+		reader.next::<Frame>().await?.is_none(),
+		Error::InvalidExport("unexpected data after the end of the backup".to_owned())
+	);
+	// Mark the restore as complete
+	state.complete = true;
+	let tx = txn().await?;
+	tx.set(&crate::key::root::rs::Rs::new(), &state, None).await?;
+	tx.commit().await?;
+	Ok(manifest)
+}
+
+/// Checks whether a backup can be restored into the datastore, returning
+/// whether the restore retries a restore which did not complete.
+async fn check(tx: &Transaction, manifest: &Manifest) -> Result<bool> {
+	let state = tx.get(&crate::key::root::rs::Rs::new(), None).await?;
+	match (manifest.since, state) {
+		// A failed restore can be retried with any full backup
+		(None, Some(state)) if !state.complete => Ok(true),
+		// Otherwise a full backup requires a datastore without namespaces
+		(None, _) => {
+			ensure!(
+				tx.all_ns().await?.is_empty(),
+				Error::InvalidExport(
+					"a full backup can only be restored into an empty datastore".to_owned()
+				)
+			);
+			Ok(false)
+		}
+		// An incremental backup must be based on the last restored backup
+		(Some(since), Some(state)) if state.complete => {
+			ensure!(
+				state.timestamp == since,
+				Error::InvalidExport(format!(
+					"the backup is based on snapshot version {since}, but the datastore was restored to snapshot version {}",
+					state.timestamp
+				))
+			);
+			Ok(false)
+		}
+		// A failed incremental restore can only be retried with the same backup
+		(Some(since), Some(state)) => {
+			ensure!(
+				state.since == Some(since) && state.timestamp == manifest.timestamp,
+				Error::InvalidExport(
+					"a previous restore did not complete, and must be retried first".to_owned()
+				)
+			);
+			Ok(true)
+		}
+		(Some(_), None) => bail!(Error::InvalidExport(
+			"an incremental backup can only be restored over the backup which it is based on"
+				.to_owned()
+		)),
+	}
+}
+
+/// Removes all data from the datastore, apart from its local keys
+async fn clear<F, Fut>(txn: &F) -> Result<()>
+where
+	F: Fn() -> Fut,
+	Fut: Future<Output = Result<Transaction>>,
+{
+	let mut next = Some(rootspace());
+	while let Some(rng) = next {
+		let tx = txn().await?;
+		let batch = match tx.batch_keys(rng, *EXPORT_BATCH_SIZE, None).await {
+			Ok(batch) => batch,
+			Err(e) => {
+				tx.cancel().await?;
+				return Err(e);
+			}
+		};
+		for key in batch.result.iter().filter(|k| !is_local(k)) {
+			if let Err(e) = tx.del(key).await {
+				tx.cancel().await?;
+				return Err(e);
+			}
+		}
+		tx.commit().await?;
+		next = batch.next;
+	}
+	Ok(())
+}
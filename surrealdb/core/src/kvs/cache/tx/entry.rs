@@ -56,6 +56,8 @@ pub(crate) enum Entry {
 	Pas(Arc<[catalog::ParamDefinition]>),
 	/// A slice of DefineSequenceStatement specified on a namespace.
 	Sqs(Arc<[catalog::SequenceDefinition]>),
+	/// A slice of DefineConsumerStatement specified on a database.
+	Css(Arc<[catalog::ConsumerDefinition]>),
 	/// A slice of DefineEventStatement specified on a table.
 	Evs(Arc<[catalog::EventDefinition]>),
 	/// A slice of DefineFieldStatement specified on a table.
@@ -210,6 +212,14 @@ impl Entry {
 			_ => fail!("Unable to convert type into Entry::Sqs"),
 		}
 	}
+	/// Converts this cache entry into a slice of [`catalog::ConsumerDefinition`].
+	/// This panics if called on a cache entry that is not an [`Entry::Css`].
+	pub(crate) fn try_into_css(self) -> Result<Arc<[catalog::ConsumerDefinition]>> {
+		match self {
+			Entry::Css(v) => Ok(v),
+			_ => fail!("Unable to convert type into Entry::Css"),
+		}
+	}
 
 	/// Converts this cache entry into a slice of [`catalog::FunctionDefinition`].
 	/// This panics if called on a cache entry that is not an [`Entry::Fcs`].
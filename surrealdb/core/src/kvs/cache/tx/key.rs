@@ -50,6 +50,8 @@ pub(crate) enum Key {
 	Tbs(NamespaceId, DatabaseId),
 	/// A cache key for sequences (on a database)
 	Sqs(NamespaceId, DatabaseId),
+	/// A cache key for changefeed consumers (on a database)
+	Css(NamespaceId, DatabaseId),
 	/// A cache key for events (on a table)
 	Evs(NamespaceId, DatabaseId, String),
 	/// A cache key for fieds (on a table)
@@ -104,6 +106,8 @@ pub(crate) enum Key {
 	Pa(NamespaceId, DatabaseId, String),
 	/// A cache key for a sequence (on a database)
 	Sq(NamespaceId, DatabaseId, String),
+	/// A cache key for a changefeed consumer (on a database)
+	Cs(NamespaceId, DatabaseId, String),
 	/// A cache key for a table
 	TbByName(String, String, String),
 	/// A cache key for a table by id.
@@ -144,6 +148,7 @@ impl<'a> From<Lookup<'a>> for Key {
 			Lookup::Cgs(a, b) => Key::Cgs(a, b),
 			Lookup::Pas(a, b) => Key::Pas(a, b),
 			Lookup::Sqs(a, b) => Key::Sqs(a, b),
+			Lookup::Css(a, b) => Key::Css(a, b),
 			Lookup::Tbs(a, b) => Key::Tbs(a, b),
 			Lookup::Evs(a, b, c) => Key::Evs(a, b, c.to_string()),
 			Lookup::Fds(a, b, c) => Key::Fds(a, b, c.to_string()),
@@ -173,6 +178,7 @@ impl<'a> From<Lookup<'a>> for Key {
 			Lookup::Cg(a, b, c) => Key::Cg(a, b, c.to_string()),
 			Lookup::Pa(a, b, c) => Key::Pa(a, b, c.to_string()),
 			Lookup::Sq(a, b,c) => Key::Sq(a, b, c.to_string()),
+			Lookup::Cs(a, b, c) => Key::Cs(a, b, c.to_string()),
 			Lookup::Tb(a, b, c) => Key::Tb(a, b, c.to_string()),
 			Lookup::TbByName(a, b, c) => Key::TbByName(a.to_string(), b.to_string(), c.to_string()),
 			Lookup::Ev(a, b, c, d) => Key::Ev(a, b, c.to_string(), d.to_string()),
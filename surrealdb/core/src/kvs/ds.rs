@@ -17,7 +17,7 @@ use anyhow::bail;
 use anyhow::{Context as _, Result, ensure};
 use async_channel::{Receiver, Sender};
 use bytes::{Bytes, BytesMut};
-use futures::{Future, Stream};
+use futures::{Future, Stream, StreamExt as _};
 use rand::{Rng, thread_rng};
 use reblessive::TreeStack;
 use surrealdb_types::{AuthError, Error as TypesError, SurrealValue, object};
@@ -32,6 +32,7 @@ use tracing::{debug, instrument, trace, warn};
 use uuid::Uuid;
 
 use super::api::Transactable;
+use super::encryption::{Cipher, EncryptedTransaction, EncryptionHeader};
 use super::tr::Transactor;
 use super::tx::Transaction;
 use super::version::MajorVersion;
@@ -40,15 +41,20 @@ use crate::api::err::ApiError;
 use crate::api::invocation::process_api_request;
 use crate::api::request::ApiRequest;
 use crate::api::response::ApiResponse;
-use crate::buc::BucketStoreProvider;
 use crate::buc::manager::BucketsManager;
+use crate::buc::store::{ByteStream, ObjectKey, ObjectMeta, ObjectRange};
+use crate::buc::{BucketController, BucketStoreProvider};
 use crate::catalog::providers::{
-	ApiProvider, CatalogProvider, DatabaseProvider, NamespaceProvider, NodeProvider, TableProvider,
-	UserProvider,
+	ApiProvider, BucketProvider, CatalogProvider, DatabaseProvider, NamespaceProvider,
+	NodeProvider, TableProvider, UserProvider,
 };
-use crate::catalog::{ApiDefinition, Index, NodeLiveQuery, SubscriptionDefinition};
-use crate::cnf::NORMAL_FETCH_SIZE;
+use crate::catalog::{
+	ApiDefinition, ConsumerDefinition, ConsumerSink, DatabaseDefinition, Index, NodeLiveQuery,
+	SubscriptionDefinition,
+};
+use crate::cf::sink::{SinkState, Source, encode_jsonl, redact_url};
 use crate::cnf::dynamic::DynamicConfiguration;
+use crate::cnf::{CHANGEFEED_SINK_BATCH_SIZE, NORMAL_FETCH_SIZE};
 use crate::ctx::Context;
 #[cfg(feature = "jwks")]
 use crate::dbs::capabilities::NetTarget;
@@ -60,6 +66,7 @@ use crate::dbs::{Capabilities, Executor, Options, QueryResult, QueryResultBuilde
 use crate::doc::AsyncEventRecord;
 use crate::err::Error;
 use crate::expr::model::get_model_path;
+use crate::expr::statements::show::ShowSince;
 use crate::expr::statements::{DefineModelStatement, DefineStatement, DefineUserStatement};
 use crate::expr::{Base, Expr, FlowResultExt as _, Literal, LogicalPlan, TopLevelExpr};
 #[cfg(feature = "jwks")]
@@ -77,16 +84,18 @@ use crate::kvs::ds::requirements::{
 	TransactionBuilderFactoryRequirements, TransactionBuilderRequirements,
 };
 use crate::kvs::index::IndexBuilder;
+use crate::kvs::running::RunningQueries;
 use crate::kvs::sequences::Sequences;
 use crate::kvs::slowlog::SlowLog;
+use crate::kvs::stats::QueryStats;
 use crate::kvs::tasklease::{LeaseHandler, TaskLeaseType};
 use crate::kvs::{KVValue, LockType, TransactionType};
 use crate::sql::Ast;
 #[cfg(feature = "surrealism")]
 use crate::surrealism::cache::SurrealismCache;
 use crate::syn::parser::{ParserSettings, StatementStream};
-use crate::types::{PublicNotification, PublicValue, PublicVariables};
-use crate::val::convert_value_to_public_value;
+use crate::types::{PublicFile, PublicNotification, PublicValue, PublicVariables};
+use crate::val::{Datetime, convert_value_to_public_value};
 use crate::{CommunityComposer, syn};
 
 const TARGET: &str = "surrealdb::core::kvs::ds";
@@ -110,6 +119,12 @@ pub struct Datastore {
 	dynamic_configuration: DynamicConfiguration,
 	/// The slow log configuration determining when a query should be logged
 	slow_log: Option<SlowLog>,
+	/// The aggregated statement statistics and recent slow queries
+	query_stats: QueryStats,
+	/// The statements which are currently running
+	running_queries: RunningQueries,
+	/// Whether an encrypted datastore can be backed up, as backups are not encrypted
+	unencrypted_backups: bool,
 	/// The maximum duration timeout for running multiple statements in a
 	/// transaction.
 	transaction_timeout: Option<Duration>,
@@ -164,6 +179,8 @@ pub(crate) struct TransactionFactory {
 	builder: Arc<Box<dyn TransactionBuilder>>,
 	// Async event processing trigger
 	async_event_trigger: Arc<Notify>,
+	// The cipher used to encrypt the stored values, if encryption is enabled
+	cipher: Option<Arc<Cipher>>,
 }
 
 impl TransactionFactory {
@@ -174,9 +191,16 @@ impl TransactionFactory {
 		Self {
 			builder: Arc::new(builder),
 			async_event_trigger,
+			cipher: None,
 		}
 	}
 
+	/// Encrypts the values of the transactions created by this factory.
+	pub(super) fn with_cipher(mut self, cipher: Cipher) -> Self {
+		self.cipher = Some(Arc::new(cipher));
+		self
+	}
+
 	#[allow(
 		unreachable_code,
 		unreachable_patterns,
@@ -201,6 +225,11 @@ impl TransactionFactory {
 		};
 		// Create a new transaction on the datastore
 		let (inner, local) = self.builder.new_transaction(write, lock).await?;
+		// Encrypt the values if encryption is enabled
+		let inner: Box<dyn Transactable> = match &self.cipher {
+			Some(cipher) => Box::new(EncryptedTransaction::new(inner, cipher.clone())),
+			None => inner,
+		};
 		Ok(Transaction::new(
 			local,
 			sequences,
@@ -220,6 +249,16 @@ impl TransactionFactory {
 	fn collect_u64_metric(&self, metric: &str) -> Option<u64> {
 		self.builder.collect_u64_metric(metric)
 	}
+
+	/// Whether the values stored by the datastore flavor can be encrypted.
+	fn supports_encryption(&self) -> bool {
+		self.builder.supports_encryption()
+	}
+
+	/// Whether the values of the transactions created by this factory are encrypted.
+	fn is_encrypted(&self) -> bool {
+		self.cipher.is_some()
+	}
 }
 
 #[cfg_attr(target_family = "wasm", async_trait::async_trait(?Send))]
@@ -262,6 +301,11 @@ pub trait TransactionBuilder: TransactionBuilderRequirements {
 	///
 	/// - `metric`: The name of the metric to collect.
 	fn collect_u64_metric(&self, metric: &str) -> Option<u64>;
+
+	/// Whether the values stored by this backend can be encrypted at rest.
+	fn supports_encryption(&self) -> bool {
+		false
+	}
 }
 
 #[cfg_attr(target_family = "wasm", async_trait::async_trait(?Send))]
@@ -539,6 +583,18 @@ impl TransactionBuilder for DatastoreFlavor {
 		}
 	}
 
+	/// Encryption at rest is supported by the on-disk storage engines.
+	fn supports_encryption(&self) -> bool {
+		match self {
+			#[cfg(feature = "kv-rocksdb")]
+			Self::RocksDB(_) => true,
+			#[cfg(feature = "kv-surrealkv")]
+			Self::SurrealKV(_) => true,
+			#[allow(unreachable_patterns)]
+			_ => false,
+		}
+	}
+
 	async fn shutdown(&self) -> Result<()> {
 		match self {
 			#[cfg(feature = "kv-mem")]
@@ -657,12 +713,16 @@ impl Datastore {
 		let async_event_trigger = Arc::new(Notify::new());
 		let tf = TransactionFactory::new(async_event_trigger.clone(), builder);
 		let id = Uuid::new_v4();
+		let sequences = Sequences::new(tf.clone(), id);
 		Ok(Self {
 			id,
 			transaction_factory: tf.clone(),
 			auth_enabled: false,
 			dynamic_configuration: DynamicConfiguration::default(),
 			slow_log: None,
+			query_stats: QueryStats::default(),
+			running_queries: RunningQueries::default(),
+			unencrypted_backups: false,
 			transaction_timeout: None,
 			notification_channel: None,
 			capabilities: Arc::new(Capabilities::default()),
@@ -673,8 +733,8 @@ impl Datastore {
 			#[cfg(storage)]
 			temporary_directory: None,
 			cache: Arc::new(DatastoreCache::new()),
-			buckets,
-			sequences: Sequences::new(tf, id),
+			buckets: buckets.with_transactions(tf, sequences.clone()),
+			sequences,
 			#[cfg(feature = "surrealism")]
 			surrealism_cache: Arc::new(SurrealismCache::new()),
 			async_event_trigger,
@@ -704,6 +764,9 @@ impl Datastore {
 			auth_enabled: self.auth_enabled,
 			dynamic_configuration: DynamicConfiguration::default(),
 			slow_log: self.slow_log,
+			query_stats: self.query_stats,
+			running_queries: self.running_queries,
+			unencrypted_backups: self.unencrypted_backups,
 			transaction_timeout: self.transaction_timeout,
 			capabilities: self.capabilities,
 			notification_channel: self.notification_channel,
@@ -783,6 +846,99 @@ impl Datastore {
 		self
 	}
 
+	/// Set the key used to encrypt the values stored in this Datastore.
+	///
+	/// Encryption can only be enabled on a new datastore, and an encrypted
+	/// datastore can only be opened with the key it was encrypted with. This
+	/// must be called before the datastore is used.
+	pub async fn with_encryption_key(mut self, key: Option<&str>) -> Result<Self> {
+		// Fetch the encryption header, if the datastore is encrypted
+		let txn = self.transaction(Read, Optimistic).await?.enclose();
+		let hdr = crate::key::encryption::new();
+		let header = catch!(txn, txn.get(&hdr, None).await);
+		txn.cancel().await?;
+		let cipher = match (key, header) {
+			// The datastore is not encrypted
+			(None, None) => return Ok(self),
+			// The datastore is encrypted, but no key was specified
+			(None, Some(_)) => bail!(Error::Kvs(crate::kvs::Error::EncryptionKeyMissing)),
+			// Check the key against the encryption header
+			(Some(key), Some(header)) => header.unwrap(key).map_err(Error::Kvs)?,
+			// Enable encryption on a new datastore
+			(Some(key), None) => self.enable_encryption(key).await?,
+		};
+		// Use the cipher for every transaction
+		self.transaction_factory = self.transaction_factory.with_cipher(cipher);
+		self.index_builder = IndexBuilder::new(self.transaction_factory.clone());
+		self.sequences = Sequences::new(self.transaction_factory.clone(), self.id);
+		self.buckets = self
+			.buckets
+			.with_transactions(self.transaction_factory.clone(), self.sequences.clone());
+		Ok(self)
+	}
+
+	/// Allow backups of an encrypted datastore.
+	///
+	/// Backups contain the decrypted values, so they are refused for an
+	/// encrypted datastore unless they are explicitly allowed.
+	pub fn with_unencrypted_backups(mut self, allowed: bool) -> Self {
+		self.unencrypted_backups = allowed;
+		self
+	}
+
+	/// Stores the encryption header of a new datastore, returning the cipher
+	/// of its data key.
+	async fn enable_encryption(&self, key: &str) -> Result<Cipher> {
+		ensure!(
+			self.transaction_factory.supports_encryption(),
+			Error::Kvs(crate::kvs::Error::EncryptionUnsupported(self.to_string()))
+		);
+		// Start a new writeable transaction
+		let txn = self.transaction(Write, Optimistic).await?.enclose();
+		let keys = catch!(txn, txn.keys(vec![0x00]..vec![0xff], 1, 0, None).await);
+		if !keys.is_empty() {
+			txn.cancel().await?;
+			bail!(Error::Kvs(crate::kvs::Error::EncryptionExistingData));
+		}
+		let (header, cipher) = match EncryptionHeader::new(key) {
+			Ok(v) => v,
+			Err(e) => {
+				txn.cancel().await?;
+				bail!(Error::Kvs(e));
+			}
+		};
+		let hdr = crate::key::encryption::new();
+		catch!(txn, txn.set(&hdr, &header, None).await);
+		catch!(txn, txn.commit().await);
+		Ok(cipher)
+	}
+
+	/// Change the key used to encrypt the values stored in this Datastore.
+	///
+	/// Only the data key, stored in the encryption header, is wrapped again
+	/// with the new key, so the stored values do not need to be rewritten.
+	pub async fn rotate_encryption_key(&self, key: &str, new_key: &str) -> Result<()> {
+		// Start a new writeable transaction
+		let txn = self.transaction(Write, Optimistic).await?.enclose();
+		// Fetch the encryption header
+		let hdr = crate::key::encryption::new();
+		let Some(header) = catch!(txn, txn.get(&hdr, None).await) else {
+			catch!(txn, txn.cancel().await);
+			bail!(Error::Kvs(crate::kvs::Error::EncryptionNotEnabled));
+		};
+		// Wrap the data key with the new key
+		let header = match header.rewrap(key, new_key) {
+			Ok(v) => v,
+			Err(e) => {
+				catch!(txn, txn.cancel().await);
+				bail!(Error::Kvs(e));
+			}
+		};
+		catch!(txn, txn.set(&hdr, &header, None).await);
+		catch!(txn, txn.commit().await);
+		Ok(())
+	}
+
 	#[cfg(storage)]
 	/// Set a temporary directory for ordering of large result sets
 	pub fn with_temporary_directory(mut self, path: Option<PathBuf>) -> Self {
@@ -1444,6 +1600,202 @@ impl Datastore {
 		Ok(())
 	}
 
+	/// Delivers changefeed entries to the sinks of any consumers which have
+	/// one, using a distributed lease so that only one node delivers the
+	/// changes at a time.
+	///
+	/// Each consumer is processed independently. A batch of changes is read
+	/// from the acknowledged position of the consumer, and the position is
+	/// only advanced once the batch has been delivered. When a delivery fails,
+	/// the attempt is recorded in the datastore, and the consumer is skipped
+	/// until its backoff period has passed.
+	///
+	/// # Arguments
+	/// * `interval` - The interval between delivery runs, used to calculate the lease duration
+	#[instrument(level = "trace", target = "surrealdb::core::kvs::ds", skip(self))]
+	pub async fn changefeed_sink_process(&self, interval: Duration) -> Result<()> {
+		// Output function invocation details to logs
+		trace!(target: TARGET, "Attempting changefeed sink process");
+		// Create a new lease handler
+		let lh = LeaseHandler::new(
+			self.sequences.clone(),
+			self.id,
+			self.transaction_factory.clone(),
+			TaskLeaseType::ChangeFeedSink,
+			interval * 2,
+		)?;
+		// If we don't get the lease, another node is handling this task
+		if !lh.has_lease().await? {
+			return Ok(());
+		}
+		// Output function invocation details to logs
+		trace!(target: TARGET, "Running changefeed sink process");
+		// Fetch all consumers which have a sink
+		let consumers = {
+			let txn = self.transaction(Read, Optimistic).await?;
+			let res = async {
+				let mut out = Vec::new();
+				for ns in txn.all_ns().await?.iter() {
+					for db in txn.all_db(ns.namespace_id).await?.iter() {
+						let (ns_id, db_id) = (db.namespace_id, db.database_id);
+						for cs in txn.all_db_consumers(ns_id, db_id).await?.iter() {
+							if cs.sink.is_none() {
+								continue;
+							}
+							let key = crate::key::database::sk::new(ns_id, db_id, &cs.name);
+							let state = txn.get(&key, None).await?;
+							out.push((ns.name.clone(), db.clone(), cs.clone(), state));
+						}
+					}
+				}
+				Ok::<_, anyhow::Error>(out)
+			}
+			.await;
+			let _ = txn.cancel().await;
+			res?
+		};
+		// Deliver the changes for each consumer
+		let now = Datetime::now();
+		for (ns, db, cs, state) in consumers {
+			// Stop if another node has taken over the lease
+			if !lh.try_maintain_lease().await? {
+				return Ok(());
+			}
+			// Wait until a failed delivery is due to be retried
+			if state.as_ref().is_some_and(|s| s.is_waiting(&now)) {
+				continue;
+			}
+			let Some(sink) = &cs.sink else {
+				continue;
+			};
+			if let Err(e) = self.changefeed_sink_deliver(&ns, &db, &cs, sink).await {
+				// Ensure that no credentials end up in the logs
+				let error = match sink {
+					ConsumerSink::Webhook(uri) => redact_url(&e.to_string(), uri),
+					ConsumerSink::File(_) => e.to_string(),
+				};
+				warn!(target: TARGET, "Failed to deliver changes for consumer {}: {error}", cs.name);
+				// Record the failure, so that the delivery is retried later
+				let key = crate::key::database::sk::new(db.namespace_id, db.database_id, &cs.name);
+				let state = SinkState::failed(state, error);
+				let txn = self.transaction(Write, Optimistic).await?;
+				run!(txn, txn.set(&key, &state, None).await)?;
+			}
+		}
+		// Everything ok
+		Ok(())
+	}
+
+	/// Delivers the next batch of changes for a consumer to its sink, and
+	/// advances the position of the consumer once the batch is delivered.
+	async fn changefeed_sink_deliver(
+		&self,
+		ns: &str,
+		db: &DatabaseDefinition,
+		cs: &ConsumerDefinition,
+		sink: &ConsumerSink,
+	) -> Result<()> {
+		let (ns_id, db_id) = (db.namespace_id, db.database_id);
+		// Read the next batch of changes, and the bucket of a file sink
+		let txn = self.transaction(Read, Optimistic).await?;
+		let res = async {
+			let tb = txn.get_tb(ns_id, db_id, &cs.target_table).await?;
+			let store_diff = tb
+				.as_ref()
+				.and_then(|tb| tb.changefeed.as_ref())
+				.or(db.changefeed.as_ref())
+				.is_some_and(|cf| cf.store_diff);
+			let since = ShowSince::Versionstamp(cs.next_versionstamp());
+			let limit = Some(*CHANGEFEED_SINK_BATCH_SIZE);
+			let changes =
+				crate::cf::read(&txn, ns_id, db_id, Some(&cs.target_table), since, limit).await?;
+			let bucket = match sink {
+				ConsumerSink::File(file) => {
+					let bu = txn.expect_db_bucket(ns_id, db_id, &file.bucket).await?;
+					let store = self.buckets.get_bucket_store(&txn, ns_id, db_id, &bu.name).await?;
+					Some((bu, store))
+				}
+				ConsumerSink::Webhook(_) => None,
+			};
+			Ok::<_, anyhow::Error>((store_diff, changes, bucket))
+		}
+		.await;
+		let _ = txn.cancel().await;
+		let (store_diff, changes, bucket) = res?;
+		// Check if there is anything to deliver
+		let (Some(first), Some(last)) = (changes.first(), changes.last()) else {
+			return Ok(());
+		};
+		let (first, last) = (u64::try_from(first.0)?, u64::try_from(last.0)?);
+		// Convert the changes into envelopes
+		let source = Source {
+			ns,
+			db: &db.name,
+			tb: &cs.target_table,
+			store_diff,
+		};
+		let mut envelopes = Vec::new();
+		for change in changes {
+			envelopes.extend(source.envelopes(change)?);
+		}
+		// Deliver the envelopes to the sink
+		match (sink, bucket) {
+			(ConsumerSink::Webhook(uri), _) => {
+				#[cfg(feature = "http")]
+				{
+					let ctx = self.setup_ctx()?.freeze();
+					let body = crate::val::Value::Array(envelopes.into());
+					let opts = crate::val::Object::default();
+					crate::fnc::util::http::post(&ctx, uri.clone(), body, opts).await?;
+				}
+				#[cfg(not(feature = "http"))]
+				{
+					let _ = (uri, envelopes);
+					bail!(Error::HttpDisabled);
+				}
+			}
+			(ConsumerSink::File(file), Some((bu, store))) => {
+				ensure!(!bu.readonly, Error::ReadonlyBucket(bu.name.clone()));
+				// A retried batch starts at the same versionstamp, so it
+				// replaces the segment from the failed attempt
+				let key = format!("{}/{first:020}.jsonl", file.key.trim_end_matches('/'));
+				let key = ObjectKey::new(key);
+				let payload = Bytes::from(encode_jsonl(envelopes)?);
+				let delta = self
+					.buckets
+					.reserve_quota(ns_id, db_id, &bu, &*store, &key, payload.len() as u64, true)
+					.await?;
+				if let Err(e) = store.put(&key, payload).await {
+					self.buckets.release_usage(ns_id, db_id, &bu, &*store, delta).await;
+					bail!(Error::ObjectStoreFailure(bu.name.clone(), e));
+				}
+			}
+			(ConsumerSink::File(file), None) => {
+				bail!(Error::BuNotFound {
+					name: file.bucket.clone(),
+				})
+			}
+		}
+		// Advance the position of the consumer
+		let txn = self.transaction(Write, Optimistic).await?;
+		run!(
+			txn,
+			async {
+				let key = crate::key::database::cs::new(ns_id, db_id, &cs.name);
+				// The consumer may have been changed while the batch was delivered
+				if let Some(mut def) = txn.get(&key, None).await?
+					&& def.target_table == cs.target_table
+					&& def.position.is_none_or(|v| v < last)
+				{
+					def.position = Some(last);
+					txn.set(&key, &def, None).await?;
+				}
+				txn.del(&crate::key::database::sk::new(ns_id, db_id, &cs.name)).await
+			}
+			.await
+		)
+	}
+
 	// --------------------------------------------------
 	// Indexing functions
 	// --------------------------------------------------
@@ -1691,6 +2043,87 @@ impl Datastore {
 		}
 	}
 
+	// --------------------------------------------------
+	// Bucket functions
+	// --------------------------------------------------
+
+	/// Deletes any files which have expired according to the lifecycle rules
+	/// defined on each bucket, using a distributed lease so that only one node
+	/// processes the buckets at a time. Any expired multipart uploads which
+	/// are held on this node are aborted beforehand.
+	///
+	/// The bucket definitions and connections are fetched in a short-lived
+	/// read transaction, so that no transaction is held open while files are
+	/// listed and deleted. Failures for an individual bucket are logged but do
+	/// not prevent other buckets from being processed.
+	///
+	/// # Arguments
+	/// * `interval` - The interval between lifecycle runs, used to calculate the lease duration
+	#[instrument(level = "trace", target = "surrealdb::core::kvs::ds", skip(self))]
+	pub async fn bucket_lifecycle_process(&self, interval: Duration) -> Result<()> {
+		// Output function invocation details to logs
+		trace!(target: TARGET, "Attempting bucket lifecycle process");
+		// Multipart uploads are held in memory on each node, so expired
+		// uploads are aborted on every node, whoever holds the lease
+		self.buckets.expire_uploads().await;
+		// Create a new lease handler
+		let lh = LeaseHandler::new(
+			self.sequences.clone(),
+			self.id,
+			self.transaction_factory.clone(),
+			TaskLeaseType::BucketLifecycle,
+			interval * 2,
+		)?;
+		// If we don't get the lease, another node is handling this task
+		if !lh.has_lease().await? {
+			return Ok(());
+		}
+		// Output function invocation details to logs
+		trace!(target: TARGET, "Running bucket lifecycle process");
+		// Fetch all buckets which have lifecycle rules
+		let buckets = {
+			let txn = self.transaction(Read, Optimistic).await?;
+			let res = async {
+				let mut out = Vec::new();
+				for ns in txn.all_ns().await?.iter() {
+					for db in txn.all_db(ns.namespace_id).await?.iter() {
+						let (ns, db) = (db.namespace_id, db.database_id);
+						for bu in txn.all_db_buckets(ns, db).await?.iter() {
+							if bu.lifecycle.is_empty() {
+								continue;
+							}
+							let store =
+								self.buckets.get_bucket_store(&txn, ns, db, &bu.name).await?;
+							out.push((ns, db, bu.clone(), store));
+						}
+					}
+				}
+				Ok::<_, anyhow::Error>(out)
+			}
+			.await;
+			let _ = txn.cancel().await;
+			res?
+		};
+		// Expire the files in each bucket
+		for (ns, db, bu, store) in buckets {
+			// Stop if another node has taken over the lease
+			if !lh.try_maintain_lease().await? {
+				return Ok(());
+			}
+			match self.buckets.expire_files(ns, db, &bu, &*store).await {
+				Ok(0) => {}
+				Ok(count) => {
+					debug!(target: TARGET, "Expired {count} files from bucket {}", bu.name);
+				}
+				Err(e) => {
+					warn!(target: TARGET, "Failed to expire files from bucket {}: {e}", bu.name);
+				}
+			}
+		}
+		// Everything ok
+		Ok(())
+	}
+
 	// --------------------------------------------------
 	// Other functions
 	// --------------------------------------------------
@@ -1895,7 +2328,22 @@ impl Datastore {
 		let mut parse_size = 4096;
 		let mut bytes_stream = pin!(query);
 		let mut complete = false;
-		let mut filling = true;
+
+		// Read enough bytes to detect a binary export
+		while buffer.len() < export::binary::MAGIC.len() {
+			match bytes_stream.next().await {
+				Some(bytes) => buffer.extend_from_slice(&bytes?),
+				None => {
+					complete = true;
+					break;
+				}
+			}
+		}
+		if buffer.starts_with(export::binary::MAGIC) {
+			return export::binary::import(self, Arc::new(ctx), opt, buffer, bytes_stream).await;
+		}
+
+		let mut filling = !complete;
 
 		let stream = futures::stream::poll_fn(move |cx| {
 			loop {
@@ -2162,6 +2610,119 @@ impl Datastore {
 		})
 	}
 
+	/// Performs a backup of the entire datastore, or of the changes since the
+	/// snapshot version of a previous backup.
+	///
+	/// Returns the snapshot version of the backup, along with the backup job.
+	#[instrument(level = "debug", target = "surrealdb::core::kvs::ds", skip_all)]
+	pub async fn backup(
+		&self,
+		since: Option<u64>,
+		chn: Sender<Vec<u8>>,
+	) -> Result<(u64, impl Future<Output = Result<()>> + use<>)> {
+		// Backups are not encrypted, so they must be allowed explicitly
+		ensure!(
+			self.unencrypted_backups || !self.transaction_factory.is_encrypted(),
+			Error::Kvs(crate::kvs::Error::EncryptionUnencryptedBackup)
+		);
+		// Take the current time before opening the transaction
+		let now = Datetime::now().to_version_stamp()?;
+		// Create a new readonly transaction
+		let txn = self.transaction(Read, Optimistic).await?;
+		// Use the version of the transaction snapshot where it is available,
+		// otherwise the time before the snapshot was taken, so that a later
+		// incremental backup can not miss any changes.
+		let timestamp = match txn.snapshot_version().await {
+			Ok(version) => version.unwrap_or(now),
+			Err(e) => {
+				txn.cancel().await?;
+				return Err(e);
+			}
+		};
+		if since.is_some_and(|since| since >= timestamp) {
+			txn.cancel().await?;
+			bail!(Error::InvalidExport("the base snapshot version is in the future".to_owned()));
+		}
+		// Return an async backup job
+		Ok((timestamp, async move {
+			// Process the backup
+			let res = txn.backup(timestamp, since, chn).await;
+			txn.cancel().await?;
+			res
+		}))
+	}
+
+	/// Performs a backup of the entire datastore, or of the changes since the
+	/// snapshot version of a previous backup, into a file in a bucket of the
+	/// database selected by the session.
+	///
+	/// Returns the snapshot version of the backup. The file is only written
+	/// if the backup completes. As the size of a backup is not known upfront,
+	/// buckets with a quota are not supported.
+	#[instrument(level = "debug", target = "surrealdb::core::kvs::ds", skip_all)]
+	pub async fn backup_to_bucket(
+		&self,
+		session: &Session,
+		bucket: &str,
+		key: &str,
+		since: Option<u64>,
+	) -> Result<u64> {
+		// Backups cover the entire datastore
+		self.check(session, Action::View, ResourceKind::Any.on_root())?;
+		// Check that the file can be written
+		let tx = Arc::new(self.transaction(Read, Optimistic).await?);
+		let opt = self.setup_options(session);
+		let mut ctx = self.setup_ctx()?;
+		ctx.set_transaction(Arc::clone(&tx));
+		ctx.attach_session(session)?;
+		let ctx = ctx.freeze();
+		let key = ObjectKey::new(key);
+		let res = TreeStack::new()
+			.enter(|stk| async move {
+				let mut controller = BucketController::new(stk, &ctx, &opt, None, bucket).await?;
+				controller.put_stream(&key, None).await
+			})
+			.finish()
+			.await;
+		tx.cancel().await?;
+		let write = res?;
+		// Stream the backup into the file, failing the write if the backup fails
+		let (snd, rcv) = async_channel::bounded(1);
+		let (version, task) = self.backup(since, snd).await?;
+		let (err, failed) = async_channel::bounded(1);
+		let task = async move {
+			let res = task.await;
+			if let Err(e) = &res {
+				err.send(e.to_string()).await.ok();
+			}
+			res
+		};
+		let data: ByteStream = Box::pin(rcv.map(|v| Ok(Bytes::from(v))).chain(failed.map(Err)));
+		let (res, written) = futures::join!(task, write.write(&self.buckets, data));
+		res?;
+		written?;
+		Ok(version)
+	}
+
+	/// Restores a full or incremental backup into this datastore.
+	///
+	/// The restore is applied in batches, so the datastore should not be used
+	/// until it completes. A failed restore can be retried, and an incremental
+	/// backup can only be restored over the backup which it is based on.
+	#[instrument(level = "debug", target = "surrealdb::core::kvs::ds", skip_all)]
+	pub async fn restore<S>(&self, stream: S) -> Result<super::backup::Manifest>
+	where
+		S: Stream<Item = Result<Bytes>>,
+	{
+		// Apply the backup
+		let manifest =
+			super::backup::restore(stream, || self.transaction(Write, Optimistic)).await?;
+		// Flush any cached definitions
+		self.cache.clear();
+		self.buckets.clear();
+		Ok(manifest)
+	}
+
 	/// Checks the required permissions level for this session
 	#[instrument(level = "trace", target = "surrealdb::core::kvs::ds", skip(self, sess))]
 	pub fn check(&self, sess: &Session, action: Action, resource: Resource) -> Result<()> {
@@ -2189,6 +2750,8 @@ impl Datastore {
 		let mut ctx = Context::from_ds(
 			self.dynamic_configuration.get_query_timeout(),
 			self.slow_log.clone(),
+			self.query_stats.clone(),
+			self.running_queries.clone(),
 			self.capabilities.clone(),
 			self.index_stores.clone(),
 			self.index_builder.clone(),
@@ -2361,6 +2924,72 @@ impl Datastore {
 		res
 	}
 
+	/// Opens a file in a bucket as a stream.
+	///
+	/// This is used to send files which are returned by API handlers without
+	/// loading them into memory. As API handlers run without permissions, the
+	/// file is read with the same privileges as the handler which returned it,
+	/// so the permissions of the bucket are not checked. If a range is
+	/// specified, only the bytes within the range are streamed. Returns `None`
+	/// if the file does not exist.
+	pub async fn get_file_stream(
+		&self,
+		session: &Session,
+		file: &PublicFile,
+		range: Option<ObjectRange>,
+	) -> Result<Option<(ObjectMeta, ByteStream)>> {
+		let tx = Arc::new(self.transaction(Read, Optimistic).await?);
+		let opt = self.setup_options(session).new_with_perms(false);
+		let mut ctx = self.setup_ctx()?;
+		ctx.set_transaction(Arc::clone(&tx));
+		ctx.attach_session(session)?;
+		let ctx = ctx.freeze();
+		let key = ObjectKey::new(file.key());
+		let res = TreeStack::new()
+			.enter(|stk| async move {
+				let mut controller =
+					BucketController::new(stk, &ctx, &opt, None, file.bucket()).await?;
+				controller.get_stream(&key, range).await
+			})
+			.finish()
+			.await;
+		tx.cancel().await?;
+		res
+	}
+
+	/// Writes a stream of `size` bytes to a file in a bucket.
+	///
+	/// This is used to receive request bodies which API handlers direct into
+	/// a file, without loading them into memory. As with [`Self::get_file_stream`],
+	/// the file is written with the privileges of the API handler. The quota
+	/// of the bucket is checked before any data is read from the stream, and
+	/// no transaction is held open while the data is being written.
+	pub async fn put_file_stream(
+		&self,
+		session: &Session,
+		file: &PublicFile,
+		size: Option<u64>,
+		data: ByteStream,
+	) -> Result<()> {
+		let tx = Arc::new(self.transaction(Read, Optimistic).await?);
+		let opt = self.setup_options(session).new_with_perms(false);
+		let mut ctx = self.setup_ctx()?;
+		ctx.set_transaction(Arc::clone(&tx));
+		ctx.attach_session(session)?;
+		let ctx = ctx.freeze();
+		let key = ObjectKey::new(file.key());
+		let res = TreeStack::new()
+			.enter(|stk| async move {
+				let mut controller =
+					BucketController::new(stk, &ctx, &opt, None, file.bucket()).await?;
+				controller.put_stream(&key, size).await
+			})
+			.finish()
+			.await;
+		tx.cancel().await?;
+		res?.write(&self.buckets, data).await
+	}
+
 	pub async fn put_ml_model(
 		&self,
 		session: &Session,
@@ -0,0 +1,439 @@
+//! Encryption at rest of the values stored in the datastore.
+//!
+//! Every value written through a transaction is encrypted with
+//! XChaCha20-Poly1305, using a random nonce and the storage key as associated
+//! data, so that a value can not be moved to another key without being
+//! detected. Keys are stored unencrypted, so that range scans keep working.
+//!
+//! Values are encrypted with a random data key, generated when encryption is
+//! enabled on a new datastore. The data key is stored in the
+//! [`EncryptionHeader`], wrapped with a key derived (Argon2id) from the
+//! configured encryption key and a random per-datastore salt. Changing the
+//! encryption key therefore only needs to wrap the data key again.
+
+use std::ops::Range;
+use std::sync::Arc;
+
+use argon2::Argon2;
+use chacha20poly1305::aead::{Aead, KeyInit, Payload};
+use chacha20poly1305::{Key as CipherKey, XChaCha20Poly1305, XNonce};
+use revision::revisioned;
+
+use super::api::{ScanLimit, Transactable};
+use super::batch::Batch;
+use super::err::{Error, Result};
+use super::timestamp::{BoxTimeStamp, BoxTimeStampImpl};
+use super::{Key, Val, impl_kv_value_revisioned};
+
+/// The length of a nonce
+const NONCE_LEN: usize = 24;
+
+/// The length of the data key, and of the derived key wrapping it
+const KEY_LEN: usize = 32;
+
+/// The length of the salt used to derive the key wrapping the data key
+const SALT_LEN: usize = 16;
+
+/// Describes how the values of an encrypted datastore are encrypted. The
+/// header itself is stored unencrypted, under [`crate::key::encryption`].
+#[revisioned(revision = 1)]
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) struct EncryptionHeader {
+	/// The salt used to derive the key wrapping the data key
+	salt: Vec<u8>,
+	/// The nonce used to wrap the data key
+	nonce: Vec<u8>,
+	/// The wrapped data key
+	key: Vec<u8>,
+}
+
+impl_kv_value_revisioned!(EncryptionHeader);
+
+impl EncryptionHeader {
+	/// Generates a new random data key, wrapped with the encryption key
+	pub(crate) fn new(key: &str) -> Result<(Self, Cipher)> {
+		let data: [u8; KEY_LEN] = rand::random();
+		let header = Self::wrap(key, &data)?;
+		Ok((header, Cipher::new(&data)))
+	}
+
+	/// Unwraps the data key with the encryption key. This fails with
+	/// [`Error::EncryptionKeyIncorrect`] if the encryption key is not the one
+	/// the data key was wrapped with.
+	pub(crate) fn unwrap(&self, key: &str) -> Result<Cipher> {
+		let data = self.data_key(key)?;
+		Ok(Cipher::new(&data))
+	}
+
+	/// Wraps the data key again, with a new encryption key
+	pub(crate) fn rewrap(&self, key: &str, new_key: &str) -> Result<Self> {
+		let data = self.data_key(key)?;
+		Self::wrap(new_key, &data)
+	}
+
+	/// Unwraps the data key with the encryption key
+	fn data_key(&self, key: &str) -> Result<Vec<u8>> {
+		if self.nonce.len() != NONCE_LEN {
+			return Err(Error::Decryption);
+		}
+		let kek = Cipher::derive(key, &self.salt)?;
+		let data = kek
+			.0
+			.decrypt(XNonce::from_slice(&self.nonce), self.key.as_slice())
+			.map_err(|_| Error::EncryptionKeyIncorrect)?;
+		if data.len() != KEY_LEN {
+			return Err(Error::Decryption);
+		}
+		Ok(data)
+	}
+
+	/// Wraps a data key with a key derived from the encryption key and a new
+	/// random salt
+	fn wrap(key: &str, data: &[u8]) -> Result<Self> {
+		let salt: [u8; SALT_LEN] = rand::random();
+		let nonce: [u8; NONCE_LEN] = rand::random();
+		let kek = Cipher::derive(key, &salt)?;
+		let key = kek
+			.0
+			.encrypt(XNonce::from_slice(&nonce), data)
+			.map_err(|_| Error::Internal("Unable to wrap the data key".to_owned()))?;
+		Ok(Self {
+			salt: salt.to_vec(),
+			nonce: nonce.to_vec(),
+			key,
+		})
+	}
+}
+
+/// Encrypts and decrypts the values of an encrypted datastore
+pub(crate) struct Cipher(XChaCha20Poly1305);
+
+impl Cipher {
+	fn new(key: &[u8]) -> Self {
+		Self(XChaCha20Poly1305::new(CipherKey::from_slice(key)))
+	}
+
+	/// Derives a key from the configured encryption key
+	fn derive(key: &str, salt: &[u8]) -> Result<Self> {
+		let mut out = [0u8; KEY_LEN];
+		Argon2::default()
+			.hash_password_into(key.as_bytes(), salt, &mut out)
+			.map_err(|_| Error::Internal("Unable to derive the encryption key".to_owned()))?;
+		Ok(Self::new(&out))
+	}
+
+	/// Encrypts the value stored under a key. The output is the random nonce
+	/// followed by the ciphertext.
+	pub(crate) fn encrypt(&self, key: &[u8], val: Val) -> Result<Val> {
+		// The encryption header is never encrypted
+		if key == crate::key::encryption::KEY {
+			return Ok(val);
+		}
+		let nonce: [u8; NONCE_LEN] = rand::random();
+		let payload = Payload {
+			msg: &val,
+			aad: key,
+		};
+		let ciphertext = self
+			.0
+			.encrypt(XNonce::from_slice(&nonce), payload)
+			.map_err(|_| Error::Internal("Unable to encrypt a value".to_owned()))?;
+		let mut out = Vec::with_capacity(NONCE_LEN + ciphertext.len());
+		out.extend_from_slice(&nonce);
+		out.extend_from_slice(&ciphertext);
+		Ok(out)
+	}
+
+	/// Decrypts the value stored under a key
+	pub(crate) fn decrypt(&self, key: &[u8], val: Val) -> Result<Val> {
+		// The encryption header is never encrypted
+		if key == crate::key::encryption::KEY {
+			return Ok(val);
+		}
+		if val.len() < NONCE_LEN {
+			return Err(Error::Decryption);
+		}
+		let (nonce, ciphertext) = val.split_at(NONCE_LEN);
+		let payload = Payload {
+			msg: ciphertext,
+			aad: key,
+		};
+		self.0.decrypt(XNonce::from_slice(nonce), payload).map_err(|_| Error::Decryption)
+	}
+}
+
+/// A transaction which encrypts the values written to, and decrypts the
+/// values read from, an underlying transaction.
+pub(super) struct EncryptedTransaction {
+	/// The underlying transaction
+	inner: Box<dyn Transactable>,
+	/// The cipher of the datastore
+	cipher: Arc<Cipher>,
+}
+
+impl EncryptedTransaction {
+	pub(super) fn new(inner: Box<dyn Transactable>, cipher: Arc<Cipher>) -> Self {
+		Self {
+			inner,
+			cipher,
+		}
+	}
+
+	/// Decrypts an optional value
+	fn decrypt_opt(&self, key: &[u8], val: Option<Val>) -> Result<Option<Val>> {
+		val.map(|v| self.cipher.decrypt(key, v)).transpose()
+	}
+
+	/// Decrypts the values of scanned key-value pairs
+	fn decrypt_all(&self, res: Vec<(Key, Val)>) -> Result<Vec<(Key, Val)>> {
+		res.into_iter()
+			.map(|(k, v)| {
+				let v = self.cipher.decrypt(&k, v)?;
+				Ok((k, v))
+			})
+			.collect()
+	}
+
+	/// Checks the current value of a key against a condition, returning the
+	/// stored ciphertext, which the underlying transaction then checks again.
+	///
+	/// A ciphertext can not be compared with the condition directly, as the
+	/// same value is never encrypted twice to the same ciphertext.
+	async fn check(&self, key: &Key, chk: Option<Val>) -> Result<Option<Val>> {
+		let raw = self.inner.get(key.clone(), None).await?;
+		let val = self.decrypt_opt(key, raw.clone())?;
+		match (val, chk) {
+			(Some(v), Some(w)) if v == w => Ok(raw),
+			(None, None) => Ok(None),
+			_ => Err(Error::TransactionConditionNotMet),
+		}
+	}
+}
+
+#[cfg_attr(target_family = "wasm", async_trait::async_trait(?Send))]
+#[cfg_attr(not(target_family = "wasm"), async_trait::async_trait)]
+impl Transactable for EncryptedTransaction {
+	fn kind(&self) -> &'static str {
+		self.inner.kind()
+	}
+
+	fn closed(&self) -> bool {
+		self.inner.closed()
+	}
+
+	fn writeable(&self) -> bool {
+		self.inner.writeable()
+	}
+
+	async fn cancel(&self) -> Result<()> {
+		self.inner.cancel().await
+	}
+
+	async fn commit(&self) -> Result<()> {
+		self.inner.commit().await
+	}
+
+	async fn exists(&self, key: Key, version: Option<u64>) -> Result<bool> {
+		self.inner.exists(key, version).await
+	}
+
+	async fn get(&self, key: Key, version: Option<u64>) -> Result<Option<Val>> {
+		let val = self.inner.get(key.clone(), version).await?;
+		self.decrypt_opt(&key, val)
+	}
+
+	async fn getm(&self, keys: Vec<Key>, version: Option<u64>) -> Result<Vec<Option<Val>>> {
+		let vals = self.inner.getm(keys.clone(), version).await?;
+		keys.iter().zip(vals).map(|(k, v)| self.decrypt_opt(k, v)).collect()
+	}
+
+	async fn getp(&self, key: Key) -> Result<Vec<(Key, Val)>> {
+		let res = self.inner.getp(key).await?;
+		self.decrypt_all(res)
+	}
+
+	async fn getr(&self, rng: Range<Key>, version: Option<u64>) -> Result<Vec<(Key, Val)>> {
+		let res = self.inner.getr(rng, version).await?;
+		self.decrypt_all(res)
+	}
+
+	async fn set(&self, key: Key, val: Val, version: Option<u64>) -> Result<()> {
+		let val = self.cipher.encrypt(&key, val)?;
+		self.inner.set(key, val, version).await
+	}
+
+	async fn replace(&self, key: Key, val: Val) -> Result<()> {
+		let val = self.cipher.encrypt(&key, val)?;
+		self.inner.replace(key, val).await
+	}
+
+	async fn put(&self, key: Key, val: Val, version: Option<u64>) -> Result<()> {
+		let val = self.cipher.encrypt(&key, val)?;
+		self.inner.put(key, val, version).await
+	}
+
+	async fn putc(&self, key: Key, val: Val, chk: Option<Val>) -> Result<()> {
+		let chk = self.check(&key, chk).await?;
+		let val = self.cipher.encrypt(&key, val)?;
+		self.inner.putc(key, val, chk).await
+	}
+
+	async fn del(&self, key: Key) -> Result<()> {
+		self.inner.del(key).await
+	}
+
+	async fn delc(&self, key: Key, chk: Option<Val>) -> Result<()> {
+		let chk = self.check(&key, chk).await?;
+		self.inner.delc(key, chk).await
+	}
+
+	async fn clr(&self, key: Key) -> Result<()> {
+		self.inner.clr(key).await
+	}
+
+	async fn clrc(&self, key: Key, chk: Option<Val>) -> Result<()> {
+		let chk = self.check(&key, chk).await?;
+		self.inner.clrc(key, chk).await
+	}
+
+	async fn delp(&self, key: Key) -> Result<()> {
+		self.inner.delp(key).await
+	}
+
+	async fn delr(&self, rng: Range<Key>) -> Result<()> {
+		self.inner.delr(rng).await
+	}
+
+	async fn clrp(&self, key: Key) -> Result<()> {
+		self.inner.clrp(key).await
+	}
+
+	async fn clrr(&self, rng: Range<Key>) -> Result<()> {
+		self.inner.clrr(rng).await
+	}
+
+	async fn keys(
+		&self,
+		rng: Range<Key>,
+		limit: ScanLimit,
+		skip: u32,
+		version: Option<u64>,
+	) -> Result<Vec<Key>> {
+		self.inner.keys(rng, limit, skip, version).await
+	}
+
+	async fn keysr(
+		&self,
+		rng: Range<Key>,
+		limit: ScanLimit,
+		skip: u32,
+		version: Option<u64>,
+	) -> Result<Vec<Key>> {
+		self.inner.keysr(rng, limit, skip, version).await
+	}
+
+	async fn scan(
+		&self,
+		rng: Range<Key>,
+		limit: ScanLimit,
+		skip: u32,
+		version: Option<u64>,
+	) -> Result<Vec<(Key, Val)>> {
+		let res = self.inner.scan(rng, limit, skip, version).await?;
+		self.decrypt_all(res)
+	}
+
+	async fn scanr(
+		&self,
+		rng: Range<Key>,
+		limit: ScanLimit,
+		skip: u32,
+		version: Option<u64>,
+	) -> Result<Vec<(Key, Val)>> {
+		let res = self.inner.scanr(rng, limit, skip, version).await?;
+		self.decrypt_all(res)
+	}
+
+	async fn count(&self, rng: Range<Key>, version: Option<u64>) -> Result<usize> {
+		self.inner.count(rng, version).await
+	}
+
+	async fn batch_keys(
+		&self,
+		rng: Range<Key>,
+		batch: u32,
+		version: Option<u64>,
+	) -> Result<Batch<Key>> {
+		self.inner.batch_keys(rng, batch, version).await
+	}
+
+	async fn batch_keys_vals(
+		&self,
+		rng: Range<Key>,
+		batch: u32,
+		version: Option<u64>,
+	) -> Result<Batch<(Key, Val)>> {
+		let res = self.inner.batch_keys_vals(rng, batch, version).await?;
+		Ok(Batch::new(res.next, self.decrypt_all(res.result)?))
+	}
+
+	async fn new_save_point(&self) -> Result<()> {
+		self.inner.new_save_point().await
+	}
+
+	async fn release_last_save_point(&self) -> Result<()> {
+		self.inner.release_last_save_point().await
+	}
+
+	async fn rollback_to_save_point(&self) -> Result<()> {
+		self.inner.rollback_to_save_point().await
+	}
+
+	async fn timestamp(&self) -> Result<BoxTimeStamp> {
+		self.inner.timestamp().await
+	}
+
+	fn timestamp_impl(&self) -> BoxTimeStampImpl {
+		self.inner.timestamp_impl()
+	}
+
+	async fn snapshot_version(&self) -> Result<Option<u64>> {
+		self.inner.snapshot_version().await
+	}
+
+	async fn compact(&self, range: Option<Range<Key>>) -> anyhow::Result<()> {
+		self.inner.compact(range).await
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn encrypt_decrypt() {
+		let (_, cipher) = EncryptionHeader::new("0123456789abcdef").unwrap();
+		let enc = cipher.encrypt(b"key", b"value".to_vec()).unwrap();
+		assert_ne!(enc, b"value");
+		assert_eq!(cipher.decrypt(b"key", enc.clone()).unwrap(), b"value");
+		// The value is bound to its key
+		assert!(matches!(cipher.decrypt(b"other", enc), Err(Error::Decryption)));
+	}
+
+	#[test]
+	fn incorrect_key() {
+		let (header, _) = EncryptionHeader::new("0123456789abcdef").unwrap();
+		assert!(header.unwrap("0123456789abcdef").is_ok());
+		assert!(matches!(header.unwrap("fedcba9876543210"), Err(Error::EncryptionKeyIncorrect)));
+	}
+
+	#[test]
+	fn rewrap() {
+		let (header, cipher) = EncryptionHeader::new("0123456789abcdef").unwrap();
+		let enc = cipher.encrypt(b"key", b"value".to_vec()).unwrap();
+		let header = header.rewrap("0123456789abcdef", "fedcba9876543210").unwrap();
+		assert!(matches!(header.unwrap("0123456789abcdef"), Err(Error::EncryptionKeyIncorrect)));
+		let cipher = header.unwrap("fedcba9876543210").unwrap();
+		assert_eq!(cipher.decrypt(b"key", enc).unwrap(), b"value");
+	}
+}
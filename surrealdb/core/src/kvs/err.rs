@@ -70,6 +70,36 @@ pub enum Error {
 
 	#[error("The storage layer does not support compaction requests.")]
 	CompactionNotSupported,
+
+	/// The datastore is encrypted, but no encryption key was specified
+	#[error("The datastore is encrypted, but no encryption key was specified")]
+	EncryptionKeyMissing,
+
+	/// The encryption key is not the key the datastore was encrypted with
+	#[error("The encryption key is incorrect for this datastore")]
+	EncryptionKeyIncorrect,
+
+	/// The datastore is not encrypted
+	#[error("The datastore is not encrypted")]
+	EncryptionNotEnabled,
+
+	/// Encryption can not be enabled on a datastore which already contains data
+	#[error("Encryption can only be enabled on a new datastore")]
+	EncryptionExistingData,
+
+	/// The storage engine does not support encryption at rest
+	#[error("Encryption at rest is not supported by the {0} storage engine")]
+	EncryptionUnsupported(String),
+
+	/// A stored value could not be decrypted
+	#[error("Unable to decrypt a value stored in the datastore")]
+	Decryption,
+
+	/// Backups of an encrypted datastore are not encrypted
+	#[error(
+		"Backups of an encrypted datastore are not encrypted, and must be allowed with --allow-unencrypted-backups"
+	)]
+	EncryptionUnencryptedBackup,
 }
 
 impl Error {
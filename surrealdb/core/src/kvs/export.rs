@@ -19,6 +19,8 @@ use crate::key::record;
 use crate::kvs::KVValue;
 use crate::sql::statements::OptionStatement;
 
+pub(crate) mod binary;
+
 #[derive(Clone, Debug, SurrealValue)]
 #[surreal(crate = "surrealdb_types")]
 #[surreal(default)]
@@ -36,6 +38,7 @@ pub struct Config {
 	pub versions: bool,
 	pub records: bool,
 	pub sequences: bool,
+	pub format: ExportFormat,
 }
 
 impl Default for Config {
@@ -54,10 +57,25 @@ impl Default for Config {
 			versions: false,
 			records: true,
 			sequences: true,
+			format: ExportFormat::default(),
 		}
 	}
 }
 
+/// The format in which an export is written
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, SurrealValue)]
+#[surreal(crate = "surrealdb_types")]
+#[surreal(untagged)]
+pub enum ExportFormat {
+	/// A SurrealQL script, which is parsed statement by statement on import
+	#[default]
+	#[surreal(value = "sql")]
+	Sql,
+	/// Length-prefixed binary frames, which are imported concurrently
+	#[surreal(value = "binary")]
+	Binary,
+}
+
 /// Named-field wrapper so that the untagged `SurrealValue` serialization
 /// can differentiate `Exclude` from `Some` (include).
 #[derive(Clone, Debug, SurrealValue)]
@@ -127,6 +145,22 @@ impl TableConfig {
 	}
 }
 
+/// Returns the tables which are included in the export config
+fn included_tables<'a>(
+	cfg: &'a Config,
+	tables: &'a [TableDefinition],
+) -> impl Iterator<Item = &'a TableDefinition> {
+	// Warn if any specified table names don't match existing tables
+	if let Some(names) = cfg.tables.names() {
+		for name in names {
+			if !tables.iter().any(|t| t.name.as_str() == name.as_str()) {
+				warn!("Table '{name}' does not exist in the database");
+			}
+		}
+	}
+	tables.iter().filter(|t| cfg.tables.includes(&t.name))
+}
+
 struct InlineCommentWriter<'a, F>(&'a mut F);
 impl<F: fmt::Write> fmt::Write for InlineCommentWriter<'_, F> {
 	fn write_str(&mut self, s: &str) -> fmt::Result {
@@ -159,7 +193,7 @@ impl<F: fmt::Display> fmt::Display for InlineCommentDisplay<F> {
 }
 
 impl Transaction {
-	/// Writes the full database contents as binary SQL.
+	/// Writes the full database contents in the configured format.
 	pub async fn export(
 		&self,
 		ns: &str,
@@ -173,6 +207,10 @@ impl Transaction {
 			})
 		})?;
 
+		// Check if this is a binary export
+		if cfg.format == ExportFormat::Binary {
+			return self.export_binary(&cfg, &chn, db.namespace_id, db.database_id).await;
+		}
 		// Output USERS, ACCESSES, PARAMS, FUNCTIONS, ANALYZERS
 		self.export_metadata(&cfg, &chn, db.namespace_id, db.database_id).await?;
 		// Output TABLES
@@ -309,21 +347,8 @@ impl Transaction {
 		}
 		// Fetch all of the tables for this NS / DB
 		let tables = self.all_tb(ns, db, None).await?;
-		// Warn if any specified table names don't match existing tables
-		if let Some(names) = cfg.tables.names() {
-			let existing: Vec<&str> = tables.iter().map(|t| t.name.as_str()).collect();
-			for name in names {
-				if !existing.contains(&name.as_str()) {
-					warn!("Table '{name}' does not exist in the database");
-				}
-			}
-		}
-		// Loop over all of the tables in order
-		for table in tables.iter() {
-			// Check if this table is included in the export config
-			if !cfg.tables.includes(&table.name) {
-				continue;
-			}
+		// Loop over all of the included tables in order
+		for table in included_tables(cfg, &tables) {
 			// Export the table definition structure first
 			self.export_table_structure(ns, db, table, chn).await?;
 			// Then export the table data if its desired
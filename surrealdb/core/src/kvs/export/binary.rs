@@ -0,0 +1,396 @@
+//! The binary export format.
+//!
+//! A binary export starts with the [`MAGIC`] header, followed by a sequence of
+//! frames. Each frame is a big-endian `u32` length, followed by a revisioned
+//! [`Frame`]. The first frame is always the [`Manifest`].
+//!
+//! All definitions are written before any record data, and all index
+//! definitions are written after all record data. This allows the record
+//! batches to be imported concurrently, without parsing any SurrealQL, and
+//! ensures that each index is only built once over the imported data. As
+//! with a SurrealQL import, everything is imported using `OPTION IMPORT`.
+//!
+//! An import is not atomic. The definitions and each record batch are
+//! committed in separate transactions, so if an import fails part way
+//! through, anything imported before the failure remains in the database.
+//! A failed import should be retried into an empty database.
+
+use std::pin::{Pin, pin};
+
+use anyhow::{Result, bail, ensure};
+use async_channel::Sender;
+use bytes::{Buf, Bytes, BytesMut};
+use futures::future::{Either, select};
+use futures::stream::FuturesUnordered;
+use futures::{Stream, StreamExt};
+use revision::{DeserializeRevisioned, SerializeRevisioned, revisioned};
+use surrealdb_types::ToSql;
+
+use super::Config;
+use crate::catalog::providers::TableProvider;
+use crate::catalog::{DatabaseId, NamespaceId, Record};
+use crate::cnf::{EXPORT_BATCH_SIZE, IMPORT_CONCURRENCY, IMPORT_MAX_FRAME_SIZE};
+use crate::ctx::FrozenContext;
+use crate::dbs::{Executor, Options, QueryResult};
+use crate::err::Error;
+use crate::expr::paths::{IN, OUT};
+use crate::expr::statements::{InsertStatement, OptionStatement};
+use crate::expr::{Data, Expr, Literal, LogicalPlan, Output, TopLevelExpr};
+use crate::key::record;
+use crate::kvs::{Datastore, KVValue, Transaction};
+use crate::syn;
+use crate::val::{RecordId, RecordIdKey, TableName, Value};
+
+/// The header which identifies a binary export
+pub(crate) const MAGIC: &[u8] = b"SURREALDB-EXPORT";
+
+/// The current version of the binary export format
+const FORMAT_VERSION: u16 = 1;
+
+/// Describes the contents of a binary export
+#[revisioned(revision = 1)]
+#[derive(Clone, Debug)]
+pub(crate) struct Manifest {
+	/// The version of the binary export format
+	pub(crate) format: u16,
+	/// The version of SurrealDB which created the export
+	pub(crate) version: String,
+	/// The tables which are included in the export
+	pub(crate) tables: Vec<TableName>,
+	/// Whether record data is included in the export
+	pub(crate) records: bool,
+}
+
+/// A single record within a [`RecordBatch`]
+#[revisioned(revision = 1)]
+#[derive(Clone, Debug)]
+pub(crate) struct RecordEntry {
+	pub(crate) id: RecordIdKey,
+	pub(crate) record: Record,
+}
+
+/// A batch of records from a single table
+#[revisioned(revision = 1)]
+#[derive(Clone, Debug)]
+pub(crate) struct RecordBatch {
+	pub(crate) table: TableName,
+	pub(crate) records: Vec<RecordEntry>,
+}
+
+#[revisioned(revision = 1)]
+#[derive(Clone, Debug)]
+pub(crate) enum Frame {
+	/// The manifest, which is always the first frame
+	Manifest(Manifest),
+	/// SurrealQL definitions, applied before any record data
+	Definitions(String),
+	/// A batch of records
+	Records(RecordBatch),
+	/// SurrealQL index definitions, applied after all record data
+	Indexes(String),
+}
+
+/// Encodes a revisioned frame, prefixed with its length
+pub(crate) fn encode_frame<T: SerializeRevisioned>(frame: &T) -> Result<Vec<u8>> {
+	let bytes = revision::to_vec(frame)?;
+	let Ok(len) = u32::try_from(bytes.len()) else {
+		bail!(Error::InvalidExport(format!("frame of {} bytes is too large", bytes.len())));
+	};
+	let mut out = Vec::with_capacity(bytes.len() + 4);
+	out.extend_from_slice(&len.to_be_bytes());
+	out.extend_from_slice(&bytes);
+	Ok(out)
+}
+
+impl Transaction {
+	/// Writes the database contents in the binary export format.
+	pub(super) async fn export_binary(
+		&self,
+		cfg: &Config,
+		chn: &Sender<Vec<u8>>,
+		ns: NamespaceId,
+		db: DatabaseId,
+	) -> Result<()> {
+		// Fetch the tables included in the export
+		let tables = if cfg.tables.is_any() {
+			self.all_tb(ns, db, None).await?
+		} else {
+			Default::default()
+		};
+		let tables = super::included_tables(cfg, &tables).collect::<Vec<_>>();
+		// Output the header and manifest
+		chn.send(MAGIC.to_vec()).await?;
+		let manifest = Manifest {
+			format: FORMAT_VERSION,
+			version: crate::env::VERSION.to_owned(),
+			tables: tables.iter().map(|t| t.name.clone()).collect(),
+			records: cfg.records,
+		};
+		chn.send(encode_frame(&Frame::Manifest(manifest))?).await?;
+		// Output USERS, ACCESSES, PARAMS, FUNCTIONS, ANALYZERS, ...
+		let (snd, rcv) = async_channel::unbounded();
+		self.export_metadata(cfg, &snd, ns, db).await?;
+		snd.close();
+		let mut sql = Vec::new();
+		while let Ok(v) = rcv.try_recv() {
+			sql.extend(v);
+		}
+		chn.send(encode_frame(&Frame::Definitions(String::from_utf8(sql)?))?).await?;
+		// Output the structure of each table, without indexes
+		for table in tables.iter() {
+			let mut sql = format!("{};\n{};\n", OptionStatement::import().to_sql(), table.to_sql());
+			for field in self.all_tb_fields(ns, db, &table.name, None).await?.iter() {
+				sql.push_str(&format!("{};\n", field.to_sql()));
+			}
+			for event in self.all_tb_events(ns, db, &table.name).await?.iter() {
+				sql.push_str(&format!("{};\n", event.to_sql()));
+			}
+			chn.send(encode_frame(&Frame::Definitions(sql))?).await?;
+		}
+		// Output the records of each table
+		if cfg.records {
+			for table in tables.iter() {
+				self.export_binary_records(ns, db, &table.name, chn).await?;
+			}
+		}
+		// Output the indexes of each table
+		for table in tables.iter() {
+			let indexes = self.all_tb_indexes(ns, db, &table.name).await?;
+			if indexes.is_empty() {
+				continue;
+			}
+			let mut sql = format!("{};\n", OptionStatement::import().to_sql());
+			for index in indexes.iter() {
+				sql.push_str(&format!("{};\n", index.to_sql()));
+			}
+			chn.send(encode_frame(&Frame::Indexes(sql))?).await?;
+		}
+		Ok(())
+	}
+
+	async fn export_binary_records(
+		&self,
+		ns: NamespaceId,
+		db: DatabaseId,
+		tb: &TableName,
+		chn: &Sender<Vec<u8>>,
+	) -> Result<()> {
+		let beg = record::prefix(ns, db, tb)?;
+		let end = record::suffix(ns, db, tb)?;
+		let mut next = Some(beg..end);
+		while let Some(rng) = next {
+			let batch = self.batch_keys_vals(rng, *EXPORT_BATCH_SIZE, None).await?;
+			next = batch.next;
+			// If there are no values, return early.
+			if batch.result.is_empty() {
+				break;
+			}
+			let mut records = Vec::with_capacity(batch.result.len());
+			for (k, v) in batch.result {
+				records.push(RecordEntry {
+					id: record::RecordKey::decode_key(&k)?.id,
+					record: Record::kv_decode_value(v)?,
+				});
+			}
+			let batch = RecordBatch {
+				table: tb.clone(),
+				records,
+			};
+			chn.send(encode_frame(&Frame::Records(batch))?).await?;
+		}
+		Ok(())
+	}
+}
+
+/// Reads length-prefixed frames from a byte stream
+pub(crate) struct FrameReader<'a, S> {
+	buffer: BytesMut,
+	stream: Pin<&'a mut S>,
+}
+
+impl<'a, S> FrameReader<'a, S>
+where
+	S: Stream<Item = Result<Bytes>>,
+{
+	/// Creates a reader, with any bytes which have already been read
+	pub(crate) fn new(buffer: BytesMut, stream: Pin<&'a mut S>) -> Self {
+		Self {
+			buffer,
+			stream,
+		}
+	}
+
+	/// Reads the next frame, returning `None` once the stream is exhausted.
+	///
+	/// Frames which are larger than the configured maximum frame size are
+	/// rejected before they are buffered. This function is cancel safe, as a
+	/// partially read frame is kept in the buffer until the next call.
+	pub(crate) async fn next<T: DeserializeRevisioned>(&mut self) -> Result<Option<T>> {
+		loop {
+			if self.buffer.len() >= 4 {
+				let mut len = [0u8; 4];
+				len.copy_from_slice(&self.buffer[..4]);
+				let len = u32::from_be_bytes(len) as usize;
+				ensure!(
+					len <= *IMPORT_MAX_FRAME_SIZE,
+					Error::InvalidExport(format!(
+						"frame of {len} bytes exceeds the maximum frame size of {} bytes",
+						*IMPORT_MAX_FRAME_SIZE
+					))
+				);
+				if self.buffer.len() >= len + 4 {
+					self.buffer.advance(4);
+					let bytes = self.buffer.split_to(len);
+					return Ok(Some(revision::from_slice(&bytes)?));
+				}
+			}
+			match self.stream.next().await {
+				Some(bytes) => self.buffer.extend_from_slice(&bytes?),
+				None if self.buffer.is_empty() => return Ok(None),
+				None => bail!(Error::InvalidExport("unexpected end of file".to_owned())),
+			}
+		}
+	}
+}
+
+/// Imports a binary export into the database.
+///
+/// The `buffer` contains any bytes which have already been read from the
+/// stream, starting with the [`MAGIC`] header. Record batches are imported
+/// concurrently, with each batch being processed in its own transaction.
+///
+/// The import is not atomic. If it fails, any definitions and record batches
+/// which were committed before the failure remain in the database.
+pub(crate) async fn import<S>(
+	ds: &Datastore,
+	ctx: FrozenContext,
+	opt: Options,
+	mut buffer: BytesMut,
+	stream: Pin<&mut S>,
+) -> Result<Vec<QueryResult>>
+where
+	S: Stream<Item = Result<Bytes>>,
+{
+	// Skip the header
+	buffer.advance(MAGIC.len());
+	let mut reader = FrameReader::new(buffer, stream);
+	// The export must start with a supported manifest
+	let manifest = match reader.next::<Frame>().await? {
+		Some(Frame::Manifest(manifest)) => manifest,
+		_ => bail!(Error::InvalidExport("the manifest is missing".to_owned())),
+	};
+	ensure!(
+		manifest.format <= FORMAT_VERSION,
+		Error::InvalidExport(format!("format version {} is not supported", manifest.format))
+	);
+	debug!(
+		"Importing {} tables from a SurrealDB {} export",
+		manifest.tables.len(),
+		manifest.version
+	);
+	// Process all of the frames
+	let mut results = Vec::new();
+	let mut pending = FuturesUnordered::new();
+	loop {
+		// Keep the pending batches progressing while reading the next frame
+		let frame = loop {
+			if pending.is_empty() {
+				break reader.next::<Frame>().await?;
+			}
+			match select(pin!(reader.next::<Frame>()), pending.next()).await {
+				Either::Left((frame, _)) => break frame?,
+				Either::Right((Some(res), _)) => results.extend(res?),
+				Either::Right((None, _)) => {}
+			}
+		};
+		let Some(frame) = frame else {
+			break;
+		};
+		match frame {
+			Frame::Manifest(_) => {
+				bail!(Error::InvalidExport("unexpected manifest".to_owned()));
+			}
+			Frame::Definitions(sql) | Frame::Indexes(sql) => {
+				// Wait for any pending records before applying definitions
+				while let Some(res) = pending.next().await {
+					results.extend(res?);
+				}
+				results.extend(import_sql(ds, &ctx, &opt, &sql).await?);
+			}
+			Frame::Records(batch) => {
+				// Limit the number of concurrently imported batches
+				while pending.len() >= *IMPORT_CONCURRENCY {
+					if let Some(res) = pending.next().await {
+						results.extend(res?);
+					}
+				}
+				pending.push(import_batch(ds, ctx.clone(), opt.clone(), batch));
+			}
+		}
+	}
+	// Wait for any remaining records
+	while let Some(res) = pending.next().await {
+		results.extend(res?);
+	}
+	Ok(results)
+}
+
+/// Executes the SurrealQL definitions from a frame
+async fn import_sql(
+	ds: &Datastore,
+	ctx: &FrozenContext,
+	opt: &Options,
+	sql: &str,
+) -> Result<Vec<QueryResult>> {
+	let plan: LogicalPlan = syn::parse_with_capabilities(sql, &ctx.get_capabilities())?.into();
+	let stream = futures::stream::iter(plan.expressions.into_iter().map(Ok));
+	Executor::execute_expr_stream(ds, ctx.clone(), opt.clone(), true, stream).await
+}
+
+/// Inserts a batch of records, without converting them to SurrealQL
+async fn import_batch(
+	ds: &Datastore,
+	ctx: FrozenContext,
+	opt: Options,
+	batch: RecordBatch,
+) -> Result<Vec<QueryResult>> {
+	let mut normal = Vec::new();
+	let mut relate = Vec::new();
+	for RecordEntry {
+		id,
+		mut record,
+	} in batch.records
+	{
+		// Inject the id field into the document
+		record.data.def(RecordId {
+			table: batch.table.clone(),
+			key: id,
+		});
+		// Graph edges are inserted as relations
+		if record.is_edge()
+			&& let Value::RecordId(_) = record.data.pick(&*IN)
+			&& let Value::RecordId(_) = record.data.pick(&*OUT)
+		{
+			relate.push(record.data);
+		} else {
+			normal.push(record.data);
+		}
+	}
+	let mut exprs = vec![Ok(TopLevelExpr::Option(OptionStatement::import()))];
+	for (values, relation) in [(normal, false), (relate, true)] {
+		if values.is_empty() {
+			continue;
+		}
+		let stmt = InsertStatement {
+			into: None,
+			data: Data::SingleExpression(Value::from(values).into_literal()),
+			ignore: false,
+			update: None,
+			output: Some(Output::None),
+			timeout: Expr::Literal(Literal::None),
+			relation,
+		};
+		exprs.push(Ok(TopLevelExpr::Expr(Expr::Insert(Box::new(stmt)))));
+	}
+	Executor::execute_expr_stream(ds, ctx, opt, true, futures::stream::iter(exprs)).await
+}
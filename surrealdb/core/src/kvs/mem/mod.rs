@@ -536,6 +536,11 @@ impl Transactable for Transaction {
 	async fn release_last_save_point(&self) -> Result<()> {
 		Ok(())
 	}
+
+	/// Get the version of the snapshot which this transaction reads from.
+	async fn snapshot_version(&self) -> Result<Option<u64>> {
+		Ok(Some(self.inner.read().await.version()))
+	}
 }
 
 // Consume and iterate over only keys
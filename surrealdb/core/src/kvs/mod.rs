@@ -14,6 +14,7 @@
 //!   database
 //! - `mem`: in-memory database
 
+pub mod backup;
 pub mod config;
 pub mod export;
 
@@ -41,9 +42,12 @@ mod tikv;
 mod tests;
 
 pub(crate) mod cache;
+pub(crate) mod encryption;
 pub(crate) mod index;
+pub(crate) mod running;
 pub(crate) mod sequences;
 pub(crate) mod slowlog;
+pub(crate) mod stats;
 pub(crate) mod tasklease;
 pub(crate) mod version;
 
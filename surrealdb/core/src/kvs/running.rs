@@ -0,0 +1,189 @@
+//! The registry of statements which are currently running.
+//!
+//! Every top-level statement executed on the datastore is registered for as
+//! long as it runs, together with the session, user, namespace and database
+//! which it runs under. A registered statement can be cancelled from any other
+//! session with `KILL QUERY`, which triggers the same cancellation as a client
+//! disconnecting, stopping the statement and the rest of its query.
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use anyhow::Result;
+use parking_lot::Mutex;
+use tokio_util::sync::CancellationToken;
+use web_time::Instant;
+
+use crate::ctx::Canceller;
+use crate::dbs::Options;
+use crate::expr::Base;
+use crate::iam::{Action, Auth, ResourceKind};
+use crate::val::{Array, Datetime, Object, Uuid, Value};
+
+#[derive(Clone, Default)]
+/// The statements which are currently running on a datastore.
+pub(crate) struct RunningQueries(Arc<Mutex<HashMap<Uuid, RunningQuery>>>);
+
+/// A statement which is currently running.
+pub(crate) struct RunningQuery {
+	/// The id of the session which is running the statement
+	pub session: Option<Uuid>,
+	/// The authentication of the user which is running the statement
+	pub auth: Arc<Auth>,
+	/// The namespace which the statement runs in
+	pub namespace: Option<String>,
+	/// The database which the statement runs in
+	pub database: Option<String>,
+	/// The normalized SQL text of the statement
+	pub statement: String,
+	/// Cancels the context of the query which the statement is part of
+	pub canceller: Canceller,
+	/// Cancels the streaming execution of the query
+	pub cancellation: CancellationToken,
+	/// The time at which the statement started running
+	pub started: Datetime,
+	/// The instant at which the statement started running
+	pub start: Instant,
+}
+
+/// Removes a statement from the registry once it has finished running.
+pub(crate) struct RunningQueryGuard {
+	queries: RunningQueries,
+	id: Uuid,
+}
+
+impl Drop for RunningQueryGuard {
+	fn drop(&mut self) {
+		self.queries.0.lock().remove(&self.id);
+	}
+}
+
+impl RunningQuery {
+	/// Checks whether the statement runs within the given namespace and
+	/// database, where `None` matches any namespace or database.
+	fn visible(&self, ns: Option<&str>, db: Option<&str>) -> bool {
+		(ns.is_none() || self.namespace.as_deref() == ns)
+			&& (db.is_none() || self.database.as_deref() == db)
+	}
+
+	fn value(&self, id: &Uuid) -> Value {
+		let user = if self.auth.is_anon() {
+			Value::None
+		} else {
+			Value::from(self.auth.id().to_owned())
+		};
+		Value::Object(Object(map! {
+			"database".to_string() => self.database.clone().map(Value::from).unwrap_or(Value::None),
+			"duration".to_string() => Value::from(self.start.elapsed()),
+			"id".to_string() => Value::Uuid(*id),
+			"level".to_string() => Value::from(self.auth.level().to_string()),
+			"namespace".to_string() => self.namespace.clone().map(Value::from).unwrap_or(Value::None),
+			"session".to_string() => self.session.map(Value::Uuid).unwrap_or(Value::None),
+			"started".to_string() => Value::from(self.started.clone()),
+			"statement".to_string() => Value::from(self.statement.clone()),
+			"user".to_string() => user,
+		}))
+	}
+}
+
+impl RunningQueries {
+	/// Registers a running statement, which is removed from the registry
+	/// when the returned guard is dropped.
+	pub(crate) fn register(&self, query: RunningQuery) -> RunningQueryGuard {
+		let id = Uuid::new_v7();
+		self.0.lock().insert(id, query);
+		RunningQueryGuard {
+			queries: self.clone(),
+			id,
+		}
+	}
+
+	/// Returns the statements running within the given namespace and
+	/// database, longest running first.
+	pub(crate) fn list(&self, ns: Option<&str>, db: Option<&str>) -> Value {
+		let queries = self.0.lock();
+		let mut out = queries.iter().filter(|(_, q)| q.visible(ns, db)).collect::<Vec<_>>();
+		out.sort_by(|a, b| a.1.start.cmp(&b.1.start));
+		Value::Array(Array(out.into_iter().map(|(id, q)| q.value(id)).collect()))
+	}
+
+	/// Cancels a statement running within the given namespace and database,
+	/// returning whether a matching statement was found.
+	pub(crate) fn kill(&self, id: &Uuid, ns: Option<&str>, db: Option<&str>) -> bool {
+		match self.0.lock().get(id) {
+			Some(query) if query.visible(ns, db) => {
+				query.canceller.cancel();
+				query.cancellation.cancel();
+				true
+			}
+			_ => false,
+		}
+	}
+}
+
+/// Returns the namespace and database whose running statements the current
+/// user may view and kill. Root owners may see every statement, while
+/// namespace and database owners only see those within their own level.
+pub(crate) fn scope(opt: &Options) -> Result<(Option<&str>, Option<&str>)> {
+	if opt.is_allowed(Action::Edit, ResourceKind::Any, &Base::Root).is_ok() {
+		Ok((None, None))
+	} else if opt.is_allowed(Action::Edit, ResourceKind::Any, &Base::Ns).is_ok() {
+		Ok((Some(opt.ns()?), None))
+	} else {
+		opt.is_allowed(Action::Edit, ResourceKind::Any, &Base::Db)?;
+		let (ns, db) = opt.ns_db()?;
+		Ok((Some(ns), Some(db)))
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use std::sync::atomic::{AtomicBool, Ordering};
+
+	use super::*;
+
+	fn query(ns: &str, db: &str, cancelled: Arc<AtomicBool>) -> RunningQuery {
+		RunningQuery {
+			session: None,
+			auth: Arc::new(Auth::default()),
+			namespace: Some(ns.to_owned()),
+			database: Some(db.to_owned()),
+			statement: "SLEEP 1s".to_owned(),
+			canceller: Canceller::new(cancelled),
+			cancellation: CancellationToken::new(),
+			started: Datetime::now(),
+			start: Instant::now(),
+		}
+	}
+
+	#[test]
+	fn registers_and_kills_within_scope() {
+		let queries = RunningQueries::default();
+		let cancelled = Arc::new(AtomicBool::new(false));
+		let guard = queries.register(query("a", "a", cancelled.clone()));
+		let _other = queries.register(query("b", "b", Arc::new(AtomicBool::new(false))));
+
+		let Value::Array(all) = queries.list(None, None) else {
+			panic!("expected an array");
+		};
+		assert_eq!(all.len(), 2);
+		let Value::Array(scoped) = queries.list(Some("a"), Some("b")) else {
+			panic!("expected an array");
+		};
+		assert!(scoped.is_empty());
+
+		// A statement outside of the given scope can not be killed
+		assert!(!queries.kill(&guard.id, Some("b"), None));
+		assert!(!cancelled.load(Ordering::Relaxed));
+		assert!(queries.kill(&guard.id, Some("a"), Some("a")));
+		assert!(cancelled.load(Ordering::Relaxed));
+
+		// The statement is removed once it has finished
+		let id = guard.id;
+		drop(guard);
+		assert!(!queries.kill(&id, None, None));
+		let Value::Array(all) = queries.list(None, None) else {
+			panic!("expected an array");
+		};
+		assert_eq!(all.len(), 1);
+	}
+}
@@ -14,6 +14,9 @@
 //! - For readability, both the SQL statement and parameter values are rendered to SQL and
 //!   whitespace is collapsed so the entire log fits on one line.
 //!
+//! Slow queries are also kept in a bounded ring on the datastore, which can be
+//! queried with `INFO FOR STATS`.
+//!
 //! Note: Values considered "nullish" are not logged.
 use std::fmt::Write;
 use std::sync::Arc;
@@ -133,9 +136,12 @@ impl SlowLog {
 	///   values from the `Context`.
 	/// - Renders the SQL and parameter values, collapsing whitespace so the output is a single line
 	///   suitable for log processing.
+	/// - Keeps the query in the datastore's ring of recent slow queries, so it can be retrieved
+	///   with `INFO FOR STATS`.
 	pub(crate) fn check_log<S: SlowLogVisit + ToSql>(
 		&self,
 		ctx: &FrozenContext,
+		ns: Option<&str>,
 		start: &Instant,
 		stm: &S,
 	) {
@@ -149,6 +155,10 @@ impl SlowLog {
 		// Ensure the query is logged on a single line by collapsing whitespace
 		let stm = stm.to_sql().split_whitespace().collect::<Vec<_>>().join(" ");
 		warn!("Slow query detected - time: {elapsed:#?} - query: {stm} - params: [ {params} ]");
+		// Keep the query in the ring of recent slow queries
+		if let Some(stats) = ctx.query_stats() {
+			stats.record_slow(ns, stm, params, elapsed);
+		}
 	}
 
 	fn extract_params<S: SlowLogVisit + ToSql>(&self, ctx: &FrozenContext, stm: &S) -> String {
@@ -0,0 +1,265 @@
+//! Statement statistics and recent slow queries.
+//!
+//! The datastore aggregates statistics for every top-level statement which is
+//! executed, keyed by the namespace it ran in and its normalized SQL text. A
+//! statement is normalized by replacing scalar literals and literal record id
+//! keys with a `_` placeholder, so that statements which only differ in their
+//! values are aggregated together. Both the number of tracked statements and
+//! the number of retained slow queries are bounded, so memory use does not
+//! grow with the number of distinct queries.
+use std::collections::{HashMap, VecDeque};
+use std::sync::Arc;
+use std::time::Duration;
+
+use parking_lot::Mutex;
+use surrealdb_types::ToSql;
+
+use crate::cnf::{SLOW_QUERY_LOG_CAPACITY, STATEMENT_STATS_CAPACITY};
+use crate::expr::visit::{MutVisitor, VisitMut};
+use crate::expr::{Expr, Literal, Param, RecordIdKeyLit, TopLevelExpr};
+use crate::val::{Array, Datetime, Object, Value};
+
+#[derive(Clone, Default)]
+/// Statement statistics and the ring of recent slow queries for a datastore.
+pub(crate) struct QueryStats(Arc<Inner>);
+
+#[derive(Default)]
+struct Inner {
+	statements: Mutex<HashMap<(Option<String>, String), StatementStats>>,
+	slow: Mutex<VecDeque<SlowQuery>>,
+}
+
+#[derive(Default)]
+struct StatementStats {
+	calls: u64,
+	errors: u64,
+	rows: u64,
+	total: Duration,
+	max: Duration,
+}
+
+struct SlowQuery {
+	namespace: Option<String>,
+	statement: String,
+	params: String,
+	duration: Duration,
+	time: Datetime,
+}
+
+impl QueryStats {
+	/// Records a single execution of a normalized statement.
+	pub(crate) fn record(
+		&self,
+		ns: Option<&str>,
+		statement: String,
+		duration: Duration,
+		rows: u64,
+		error: bool,
+	) {
+		let mut statements = self.0.statements.lock();
+		let key = (ns.map(str::to_owned), statement);
+		// Stop tracking new statements once the capacity is reached
+		if !statements.contains_key(&key) && statements.len() >= *STATEMENT_STATS_CAPACITY {
+			return;
+		}
+		let entry = statements.entry(key).or_default();
+		entry.calls += 1;
+		entry.rows += rows;
+		entry.total += duration;
+		entry.max = entry.max.max(duration);
+		if error {
+			entry.errors += 1;
+		}
+	}
+
+	/// Records a slow query, evicting the oldest one if the ring is full.
+	pub(crate) fn record_slow(
+		&self,
+		ns: Option<&str>,
+		statement: String,
+		params: String,
+		duration: Duration,
+	) {
+		let mut slow = self.0.slow.lock();
+		if slow.len() >= *SLOW_QUERY_LOG_CAPACITY {
+			slow.pop_front();
+		}
+		slow.push_back(SlowQuery {
+			namespace: ns.map(str::to_owned),
+			statement,
+			params,
+			duration,
+			time: Datetime::now(),
+		});
+	}
+
+	/// Returns the statistics for the given namespace, or for all namespaces
+	/// when `ns` is `None`, optionally clearing the returned entries.
+	pub(crate) fn snapshot(&self, ns: Option<&str>, reset: bool) -> Value {
+		let visible = |v: &Option<String>| ns.is_none() || v.as_deref() == ns;
+		// Collect the statement statistics, slowest in total first
+		let mut statements = {
+			let mut statements = self.0.statements.lock();
+			let out = statements
+				.iter()
+				.filter(|((v, _), _)| visible(v))
+				.map(|((v, stm), s)| (s.total, statement_value(v, stm, s)))
+				.collect::<Vec<_>>();
+			if reset {
+				statements.retain(|(v, _), _| !visible(v));
+			}
+			out
+		};
+		statements.sort_by(|a, b| b.0.cmp(&a.0));
+		// Collect the slow queries, most recent first
+		let slow = {
+			let mut slow = self.0.slow.lock();
+			let out = slow.iter().rev().filter(|s| visible(&s.namespace)).map(slow_value).collect();
+			if reset {
+				slow.retain(|s| !visible(&s.namespace));
+			}
+			out
+		};
+		Value::from(map! {
+			"slow".to_string() => Value::Array(Array(slow)),
+			"statements".to_string() => Value::Array(statements.into_iter().map(|(_, v)| v).collect()),
+		})
+	}
+}
+
+fn namespace_value(ns: &Option<String>) -> Value {
+	ns.clone().map(Value::from).unwrap_or(Value::None)
+}
+
+fn statement_value(ns: &Option<String>, statement: &str, s: &StatementStats) -> Value {
+	Value::Object(Object(map! {
+		"calls".to_string() => Value::from(s.calls),
+		"errors".to_string() => Value::from(s.errors),
+		"max".to_string() => Value::from(s.max),
+		"mean".to_string() => Value::from(s.total.div_f64(s.calls as f64)),
+		"namespace".to_string() => namespace_value(ns),
+		"rows".to_string() => Value::from(s.rows),
+		"statement".to_string() => Value::from(statement.to_owned()),
+		"total".to_string() => Value::from(s.total),
+	}))
+}
+
+fn slow_value(s: &SlowQuery) -> Value {
+	Value::Object(Object(map! {
+		"duration".to_string() => Value::from(s.duration),
+		"namespace".to_string() => namespace_value(&s.namespace),
+		"params".to_string() => Value::from(s.params.clone()),
+		"statement".to_string() => Value::from(s.statement.clone()),
+		"time".to_string() => Value::from(s.time.clone()),
+	}))
+}
+
+/// Replaces scalar literals and literal record id keys with a placeholder.
+struct Normalizer;
+
+impl MutVisitor for Normalizer {
+	type Error = std::convert::Infallible;
+
+	fn visit_mut_expr(&mut self, expr: &mut Expr) -> Result<(), Self::Error> {
+		if let Expr::Literal(
+			Literal::Bool(_)
+			| Literal::Float(_)
+			| Literal::Integer(_)
+			| Literal::Decimal(_)
+			| Literal::String(_)
+			| Literal::Bytes(_)
+			| Literal::Regex(_)
+			| Literal::Datetime(_)
+			| Literal::Duration(_)
+			| Literal::Uuid(_)
+			| Literal::File(_)
+			| Literal::Geometry(_),
+		) = expr
+		{
+			*expr = Expr::Param(Param::new("_".to_owned()));
+			return Ok(());
+		}
+		expr.visit_mut(self)
+	}
+
+	fn visit_mut_record_id_key(&mut self, key: &mut RecordIdKeyLit) -> Result<(), Self::Error> {
+		if let RecordIdKeyLit::Number(_) | RecordIdKeyLit::String(_) | RecordIdKeyLit::Uuid(_) = key
+		{
+			*key = RecordIdKeyLit::String("_".to_owned());
+			return Ok(());
+		}
+		key.visit_mut(self)
+	}
+}
+
+/// Returns the normalized SQL text of a statement, on a single line.
+pub(crate) fn normalize(stm: &TopLevelExpr) -> String {
+	let mut stm = stm.clone();
+	let _ = Normalizer.visit_mut_top_level_expr(&mut stm);
+	stm.to_sql().split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+#[cfg(test)]
+mod tests {
+	use std::time::Duration;
+
+	use super::*;
+
+	fn normalized(sql: &str) -> String {
+		let ast = crate::syn::parse(sql).unwrap();
+		let stm: TopLevelExpr = ast.expressions.into_iter().next().unwrap().into();
+		normalize(&stm)
+	}
+
+	#[test]
+	fn normalizes_literals() {
+		assert_eq!(
+			normalized("SELECT * FROM person WHERE age > 10 AND name = 'Tobie'"),
+			normalized("SELECT * FROM person WHERE age > 25 AND name = 'Jaime'"),
+		);
+		assert_ne!(
+			normalized("SELECT * FROM person WHERE age > 10"),
+			normalized("SELECT * FROM animal WHERE age > 10"),
+		);
+		assert_eq!(normalized("SELECT * FROM person:1"), normalized("SELECT * FROM person:tobie"));
+	}
+
+	#[test]
+	fn aggregates_and_resets() {
+		let stats = QueryStats::default();
+		stats.record(Some("a"), "SELECT 1".to_owned(), Duration::from_millis(10), 2, false);
+		stats.record(Some("a"), "SELECT 1".to_owned(), Duration::from_millis(30), 2, true);
+		stats.record(Some("b"), "SELECT 1".to_owned(), Duration::from_millis(5), 1, false);
+		stats.record_slow(Some("b"), "SELECT 1".to_owned(), String::new(), Duration::from_secs(1));
+
+		let Value::Object(res) = stats.snapshot(Some("a"), true) else {
+			panic!("expected an object");
+		};
+		let Some(Value::Array(statements)) = res.get("statements") else {
+			panic!("expected an array");
+		};
+		assert_eq!(statements.len(), 1);
+		let Value::Object(stm) = &statements[0] else {
+			panic!("expected an object");
+		};
+		assert_eq!(stm.get("calls"), Some(&Value::from(2u64)));
+		assert_eq!(stm.get("errors"), Some(&Value::from(1u64)));
+		assert_eq!(stm.get("rows"), Some(&Value::from(4u64)));
+		assert_eq!(stm.get("mean"), Some(&Value::from(Duration::from_millis(20))));
+		assert_eq!(stm.get("max"), Some(&Value::from(Duration::from_millis(30))));
+		assert_eq!(res.get("slow"), Some(&Value::Array(Array::new())));
+
+		// Only the statistics of namespace `a` were reset
+		let Value::Object(res) = stats.snapshot(None, false) else {
+			panic!("expected an object");
+		};
+		let Some(Value::Array(statements)) = res.get("statements") else {
+			panic!("expected an array");
+		};
+		assert_eq!(statements.len(), 1);
+		let Some(Value::Array(slow)) = res.get("slow") else {
+			panic!("expected an array");
+		};
+		assert_eq!(slow.len(), 1);
+	}
+}
@@ -27,6 +27,10 @@ pub(crate) enum TaskLeaseType {
 	IndexCompaction,
 	/// Event processing
 	EventProcessing,
+	/// Expiring files from buckets with lifecycle rules
+	BucketLifecycle,
+	/// Delivering changefeed entries to consumer sinks
+	ChangeFeedSink,
 }
 
 /// Represents a distributed task lease stored in the datastore.
@@ -451,7 +455,7 @@ mod tests {
 
 	#[cfg(feature = "kv-mem")]
 	use chrono::Utc;
-	#[cfg(feature = "kv-rocksdb")]
+	#[cfg(any(feature = "kv-rocksdb", feature = "kv-surrealkv"))]
 	use temp_dir::TempDir;
 	use tokio::sync::Notify;
 	#[cfg(feature = "kv-mem")]
@@ -0,0 +1,125 @@
+//! Tests for backing up and restoring the key space
+
+use bytes::Bytes;
+use futures::stream;
+
+use crate::dbs::{Capabilities, Session};
+use crate::kvs::Datastore;
+use crate::kvs::LockType::Optimistic;
+use crate::kvs::TransactionType::{Read, Write};
+
+/// Creates a full backup of a datastore
+async fn backup(ds: &Datastore) -> Vec<u8> {
+	backup_since(ds, None).await.1
+}
+
+/// Creates a backup of the changes since a snapshot version
+async fn backup_since(ds: &Datastore, since: Option<u64>) -> (u64, Vec<u8>) {
+	let (snd, rcv) = async_channel::unbounded();
+	let (version, task) = ds.backup(since, snd).await.unwrap();
+	task.await.unwrap();
+	let mut out = Vec::new();
+	while let Ok(bytes) = rcv.try_recv() {
+		out.extend(bytes);
+	}
+	(version, out)
+}
+
+/// Creates a datastore with the given statements applied
+async fn source(sql: &str) -> Datastore {
+	let ds = Datastore::new("memory").await.unwrap().with_capabilities(Capabilities::all());
+	let sql =
+		format!("DEFINE NAMESPACE test; USE NS test; DEFINE DATABASE test; USE DB test; {sql}");
+	for res in ds.execute(&sql, &Session::owner(), None).await.unwrap() {
+		res.result.unwrap();
+	}
+	ds
+}
+
+/// Lists the names of the people in a datastore
+async fn names(ds: &Datastore, ses: &Session) -> Vec<String> {
+	let mut res =
+		ds.execute("SELECT VALUE name FROM person ORDER BY name", ses, None).await.unwrap();
+	let val = res.remove(0).result.unwrap();
+	val.as_array().unwrap().iter().map(|v| v.clone().into_string().unwrap()).collect()
+}
+
+fn stream(bytes: Vec<u8>) -> impl futures::Stream<Item = anyhow::Result<Bytes>> {
+	stream::iter(bytes.chunks(7).map(|c| Ok(Bytes::copy_from_slice(c))).collect::<Vec<_>>())
+}
+
+#[tokio::test]
+async fn backup_restore_roundtrip() {
+	let ses = Session::owner().with_ns("test").with_db("test");
+	let src =
+		source("CREATE person:one SET name = 'One'; CREATE person:two SET name = 'Two'").await;
+	let bytes = backup(&src).await;
+	// Restore into an empty datastore
+	let dst = Datastore::new("memory").await.unwrap().with_capabilities(Capabilities::all());
+	let manifest = dst.restore(stream(bytes.clone())).await.unwrap();
+	assert!(!manifest.is_incremental());
+	let mut res = dst.execute("SELECT VALUE name FROM person", &ses, None).await.unwrap();
+	let val = res.remove(0).result.unwrap();
+	assert_eq!(val.as_array().unwrap().len(), 2, "Both records should be restored");
+	// A full backup can not be restored over existing data
+	assert!(dst.restore(stream(bytes.clone())).await.is_err());
+	// A truncated backup is rejected
+	let empty = Datastore::new("memory").await.unwrap();
+	assert!(empty.restore(stream(bytes[..bytes.len() - 4].to_vec())).await.is_err());
+}
+
+#[tokio::test]
+async fn backup_restore_incremental() {
+	let ses = Session::owner().with_ns("test").with_db("test");
+	let src =
+		source("CREATE person:one SET name = 'One'; CREATE person:two SET name = 'Two'").await;
+	let (base, full) = backup_since(&src, None).await;
+	let sql = "UPDATE person:one SET name = 'Uno'; DELETE person:two; CREATE person:three SET name = 'Three'";
+	for res in src.execute(sql, &ses, None).await.unwrap() {
+		res.result.unwrap();
+	}
+	let (_, incremental) = backup_since(&src, Some(base)).await;
+	// An incremental backup requires the backup it is based on
+	let dst = Datastore::new("memory").await.unwrap().with_capabilities(Capabilities::all());
+	assert!(dst.restore(stream(incremental.clone())).await.is_err());
+	dst.restore(stream(full)).await.unwrap();
+	assert_eq!(names(&dst, &ses).await, ["One", "Two"]);
+	// The incremental backup applies the changes since the base backup
+	let manifest = dst.restore(stream(incremental.clone())).await.unwrap();
+	assert_eq!(manifest.since, Some(base));
+	assert_eq!(names(&dst, &ses).await, ["Three", "Uno"]);
+	// It can not be applied again over itself
+	assert!(dst.restore(stream(incremental)).await.is_err());
+}
+
+#[tokio::test]
+async fn backup_restore_retry() {
+	let ses = Session::owner().with_ns("test").with_db("test");
+	let src = source("CREATE person:one SET name = 'One'").await;
+	let bytes = backup(&src).await;
+	// A truncated backup leaves a partially restored datastore
+	let dst = Datastore::new("memory").await.unwrap().with_capabilities(Capabilities::all());
+	assert!(dst.restore(stream(bytes[..bytes.len() - 4].to_vec())).await.is_err());
+	// The failed restore can be retried
+	dst.restore(stream(bytes)).await.unwrap();
+	assert_eq!(names(&dst, &ses).await, ["One"]);
+}
+
+#[tokio::test]
+async fn backup_skips_local_keys() {
+	let src = Datastore::new("memory").await.unwrap();
+	let tx = src.transaction(Write, Optimistic).await.unwrap();
+	for key in [b"/!ndtest".to_vec(), b"/!tltest".to_vec(), b"/$test".to_vec(), b"/test".to_vec()] {
+		tx.set(&key, &b"test".to_vec(), None).await.unwrap();
+	}
+	tx.commit().await.unwrap();
+	let bytes = backup(&src).await;
+	let dst = Datastore::new("memory").await.unwrap();
+	dst.restore(stream(bytes)).await.unwrap();
+	let tx = dst.transaction(Read, Optimistic).await.unwrap();
+	assert!(tx.exists(&b"/test".to_vec(), None).await.unwrap());
+	for key in [b"/!ndtest".to_vec(), b"/!tltest".to_vec(), b"/$test".to_vec()] {
+		assert!(!tx.exists(&key, None).await.unwrap(), "{key:?} should not be restored");
+	}
+	tx.cancel().await.unwrap();
+}
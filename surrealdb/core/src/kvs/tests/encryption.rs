@@ -0,0 +1,137 @@
+//! Tests for encryption at rest
+
+use std::path::Path;
+
+use temp_dir::TempDir;
+use tokio_util::sync::CancellationToken;
+
+use crate::CommunityComposer;
+use crate::dbs::Session;
+use crate::err::Error;
+use crate::kvs::{Datastore, Error as KvsError};
+
+const KEY: &str = "0123456789abcdef";
+
+const NEW_KEY: &str = "fedcba9876543210";
+
+/// A value which is only found on disk if it is stored unencrypted
+const SECRET: &str = "an-unmistakable-plaintext-secret";
+
+/// Opens a datastore with an encryption key
+async fn open(path: &str, key: Option<&str>) -> anyhow::Result<Datastore> {
+	Datastore::new_with_factory(CommunityComposer(), path, CancellationToken::new())
+		.await?
+		.with_encryption_key(key)
+		.await
+}
+
+fn kvs_error(err: anyhow::Error) -> KvsError {
+	match err.downcast::<Error>() {
+		Ok(Error::Kvs(e)) => e,
+		e => panic!("unexpected error: {e:?}"),
+	}
+}
+
+/// Checks if any file below a directory contains the given bytes
+fn files_contain(dir: &Path, needle: &[u8]) -> bool {
+	std::fs::read_dir(dir).unwrap().any(|entry| {
+		let path = entry.unwrap().path();
+		if path.is_dir() {
+			files_contain(&path, needle)
+		} else {
+			let data = std::fs::read(&path).unwrap();
+			data.windows(needle.len()).any(|w| w == needle)
+		}
+	})
+}
+
+/// Creates a backup of a datastore, returning the error if it is refused
+async fn backup(ds: &Datastore) -> anyhow::Result<Vec<u8>> {
+	let (snd, rcv) = async_channel::unbounded();
+	let (_, task) = ds.backup(None, snd).await?;
+	task.await?;
+	let mut out = Vec::new();
+	while let Ok(bytes) = rcv.try_recv() {
+		out.extend(bytes);
+	}
+	Ok(out)
+}
+
+async fn encrypted_datastore(engine: &str) {
+	let dir = TempDir::new().unwrap();
+	let path = format!("{engine}:{}", dir.path().to_string_lossy());
+	let ses = Session::owner().with_ns("test").with_db("test");
+	// Write to a new encrypted datastore
+	let ds = open(&path, Some(KEY)).await.unwrap();
+	ds.execute(&format!("CREATE person:one SET name = '{SECRET}'"), &ses, None).await.unwrap();
+	ds.shutdown().await.unwrap();
+	// The values are not stored in plaintext
+	assert!(!files_contain(dir.path(), SECRET.as_bytes()), "The record should be encrypted");
+	// The datastore can not be opened without the key
+	let err = open(&path, None).await.err().unwrap();
+	assert!(matches!(kvs_error(err), KvsError::EncryptionKeyMissing));
+	// The datastore can not be opened with another key
+	let err = open(&path, Some(NEW_KEY)).await.err().unwrap();
+	assert!(matches!(kvs_error(err), KvsError::EncryptionKeyIncorrect));
+	// Change the encryption key
+	let ds = Datastore::new_with_factory(CommunityComposer(), &path, CancellationToken::new())
+		.await
+		.unwrap();
+	let err = ds.rotate_encryption_key(NEW_KEY, KEY).await.err().unwrap();
+	assert!(matches!(kvs_error(err), KvsError::EncryptionKeyIncorrect));
+	ds.rotate_encryption_key(KEY, NEW_KEY).await.unwrap();
+	ds.shutdown().await.unwrap();
+	// The data is read with the new key
+	let err = open(&path, Some(KEY)).await.err().unwrap();
+	assert!(matches!(kvs_error(err), KvsError::EncryptionKeyIncorrect));
+	let ds = open(&path, Some(NEW_KEY)).await.unwrap();
+	let mut res = ds.execute("SELECT VALUE name FROM person", &ses, None).await.unwrap();
+	let val = res.remove(0).result.unwrap();
+	assert_eq!(val.as_array().unwrap().len(), 1, "The record should be decrypted");
+	// Backups contain the decrypted values, so they must be allowed explicitly
+	let err = backup(&ds).await.err().unwrap();
+	assert!(matches!(kvs_error(err), KvsError::EncryptionUnencryptedBackup));
+	let ds = ds.with_unencrypted_backups(true);
+	assert!(!backup(&ds).await.unwrap().is_empty());
+	ds.shutdown().await.unwrap();
+}
+
+async fn unencrypted_datastore(engine: &str) {
+	let dir = TempDir::new().unwrap();
+	let path = format!("{engine}:{}", dir.path().to_string_lossy());
+	let ses = Session::owner().with_ns("test").with_db("test");
+	let ds = open(&path, None).await.unwrap();
+	ds.execute(&format!("CREATE person:one SET name = '{SECRET}'"), &ses, None).await.unwrap();
+	// Backups of an unencrypted datastore are always allowed
+	assert!(!backup(&ds).await.unwrap().is_empty());
+	ds.shutdown().await.unwrap();
+	// The values are stored in plaintext
+	assert!(files_contain(dir.path(), SECRET.as_bytes()), "The record should not be encrypted");
+	// Encryption can not be enabled on an existing datastore
+	let err = open(&path, Some(KEY)).await.err().unwrap();
+	assert!(matches!(kvs_error(err), KvsError::EncryptionExistingData));
+}
+
+#[cfg(feature = "kv-surrealkv")]
+#[tokio::test]
+async fn surrealkv_encrypted_datastore() {
+	encrypted_datastore("surrealkv").await;
+}
+
+#[cfg(feature = "kv-surrealkv")]
+#[tokio::test]
+async fn surrealkv_unencrypted_datastore() {
+	unencrypted_datastore("surrealkv").await;
+}
+
+#[cfg(feature = "kv-rocksdb")]
+#[tokio::test]
+async fn rocksdb_encrypted_datastore() {
+	encrypted_datastore("rocksdb").await;
+}
+
+#[cfg(feature = "kv-rocksdb")]
+#[tokio::test]
+async fn rocksdb_unencrypted_datastore() {
+	unencrypted_datastore("rocksdb").await;
+}
@@ -20,6 +20,10 @@ macro_rules! include_tests {
 	};
 }
 
+#[cfg(feature = "kv-mem")]
+mod backup;
+#[cfg(any(feature = "kv-rocksdb", feature = "kv-surrealkv"))]
+mod encryption;
 #[cfg(feature = "kv-rocksdb")]
 mod metrics;
 
@@ -574,4 +574,9 @@ impl Transactor {
 	pub fn timestamp_impl(&self) -> BoxTimeStampImpl {
 		self.inner.timestamp_impl()
 	}
+
+	/// Get the version of the snapshot which this transaction reads from
+	pub async fn snapshot_version(&self) -> Result<Option<u64>> {
+		self.inner.snapshot_version().await
+	}
 }
@@ -755,6 +755,11 @@ impl Transaction {
 		self.tr.timestamp_impl()
 	}
 
+	/// Get the version of the snapshot which this transaction reads from
+	pub async fn snapshot_version(&self) -> Result<Option<u64>> {
+		Ok(self.tr.snapshot_version().await.map_err(Error::from)?)
+	}
+
 	// --------------------------------------------------
 	// Changefeed functions
 	// --------------------------------------------------
@@ -1222,6 +1227,28 @@ impl DatabaseProvider for Transaction {
 		}
 	}
 
+	/// Retrieve all changefeed consumer definitions for a specific database.
+	#[instrument(level = "trace", target = "surrealdb::core::kvs::tx", skip(self))]
+	async fn all_db_consumers(
+		&self,
+		ns: NamespaceId,
+		db: DatabaseId,
+	) -> Result<Arc<[catalog::ConsumerDefinition]>> {
+		let qey = cache::tx::Lookup::Css(ns, db);
+		match self.cache.get(&qey) {
+			Some(val) => val.try_into_css(),
+			None => {
+				let beg = crate::key::database::cs::prefix(ns, db)?;
+				let end = crate::key::database::cs::suffix(ns, db)?;
+				let val = self.getr(beg..end, None).await?;
+				let val = util::deserialize_cache(val.iter().map(|x| x.1.as_slice()))?;
+				let entry = cache::tx::Entry::Css(val.clone());
+				self.cache.insert(qey, entry);
+				Ok(val)
+			}
+		}
+	}
+
 	/// Retrieve all function definitions for a specific database.
 	#[instrument(level = "trace", target = "surrealdb::core::kvs::tx", skip(self))]
 	async fn all_db_functions(
@@ -1405,6 +1432,30 @@ impl DatabaseProvider for Transaction {
 		}
 	}
 
+	/// Retrieve a specific changefeed consumer definition from a database.
+	#[instrument(level = "trace", target = "surrealdb::core::kvs::tx", skip(self))]
+	async fn get_db_consumer(
+		&self,
+		ns: NamespaceId,
+		db: DatabaseId,
+		cs: &str,
+	) -> Result<Arc<catalog::ConsumerDefinition>> {
+		let qey = cache::tx::Lookup::Cs(ns, db, cs);
+		match self.cache.get(&qey) {
+			Some(val) => val.try_into_type(),
+			None => {
+				let key = crate::key::database::cs::new(ns, db, cs);
+				let val = self.get(&key, None).await?.ok_or_else(|| Error::CsNotFound {
+					name: cs.to_owned(),
+				})?;
+				let val = Arc::new(val);
+				let entry = cache::tx::Entry::Any(val.clone());
+				self.cache.insert(qey, entry);
+				Ok(val)
+			}
+		}
+	}
+
 	/// Retrieve a specific function definition from a database.
 	#[instrument(level = "trace", target = "surrealdb::core::kvs::tx", skip(self))]
 	async fn get_db_function(
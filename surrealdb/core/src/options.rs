@@ -27,6 +27,16 @@ pub struct EngineOptions {
 	///
 	/// Default: 5 seconds
 	pub event_processing_interval: Duration,
+	/// Interval for deleting files which have expired according to the
+	/// lifecycle rules of each bucket.
+	///
+	/// Default: 60 seconds
+	pub bucket_lifecycle_interval: Duration,
+	/// Interval for delivering changefeed entries to the sinks of
+	/// changefeed consumers.
+	///
+	/// Default: 5 seconds
+	pub changefeed_sink_interval: Duration,
 }
 
 impl Default for EngineOptions {
@@ -38,6 +48,8 @@ impl Default for EngineOptions {
 			changefeed_gc_interval: Duration::from_secs(30),
 			index_compaction_interval: Duration::from_secs(5),
 			event_processing_interval: Duration::from_secs(5),
+			bucket_lifecycle_interval: Duration::from_secs(60),
+			changefeed_sink_interval: Duration::from_secs(5),
 		}
 	}
 }
@@ -69,4 +81,14 @@ impl EngineOptions {
 		self.event_processing_interval = interval;
 		self
 	}
+
+	pub fn with_bucket_lifecycle_interval(mut self, interval: Duration) -> Self {
+		self.bucket_lifecycle_interval = interval;
+		self
+	}
+
+	pub fn with_changefeed_sink_interval(mut self, interval: Duration) -> Self {
+		self.changefeed_sink_interval = interval;
+		self
+	}
 }
@@ -694,6 +694,7 @@ pub trait RpcProtocol {
 		let ast = Ast {
 			expressions: vec![TopLevelExpr::Kill(KillStatement {
 				id: Expr::from_public_value(id),
+				query: false,
 			})],
 		};
 		// Specify the query parameters
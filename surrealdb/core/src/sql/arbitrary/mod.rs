@@ -13,6 +13,7 @@ pub(crate) use utils::*;
 
 use crate::sql::changefeed::ChangeFeed;
 use crate::sql::statements::SleepStatement;
+use crate::sql::statements::define::BucketLifecycleRule;
 use crate::val::Bytes;
 
 impl<'a> Arbitrary<'a> for ChangeFeed {
@@ -24,6 +25,15 @@ impl<'a> Arbitrary<'a> for ChangeFeed {
 	}
 }
 
+impl<'a> Arbitrary<'a> for BucketLifecycleRule {
+	fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+		Ok(Self {
+			prefix: u.arbitrary()?,
+			expiry: u.arbitrary()?,
+		})
+	}
+}
+
 impl<'a> Arbitrary<'a> for SleepStatement {
 	fn arbitrary(_u: &mut Unstructured<'a>) -> Result<Self> {
 		Ok(Self {
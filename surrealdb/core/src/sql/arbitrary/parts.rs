@@ -8,6 +8,7 @@ use crate::sql::access_type::{JwtAccess, JwtAccessIssue, JwtAccessVerify};
 use crate::sql::arbitrary::idiom::plain_idiom;
 use crate::sql::arbitrary::{arb_vec1, arb_vec2, atleast_one, basic_idiom};
 use crate::sql::field::Selector;
+use crate::sql::operator::Fuzzy;
 use crate::sql::order::{OrderList, Ordering};
 use crate::sql::statements::access::Subject;
 use crate::sql::statements::define::config::api::Middleware;
@@ -52,6 +53,15 @@ impl<'a> Arbitrary<'a> for Function {
 	}
 }
 
+impl<'a> Arbitrary<'a> for Fuzzy {
+	fn arbitrary(u: &mut arbitrary::Unstructured<'a>) -> arbitrary::Result<Self> {
+		Ok(Self {
+			distance: u.int_in_range(0..=crate::expr::operator::Fuzzy::MAX_DISTANCE)?,
+			prefix: u.arbitrary()?,
+		})
+	}
+}
+
 impl<'a> Arbitrary<'a> for Closure {
 	fn arbitrary(u: &mut arbitrary::Unstructured<'a>) -> arbitrary::Result<Self> {
 		let args = u.arbitrary()?;
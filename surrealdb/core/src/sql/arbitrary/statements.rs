@@ -15,8 +15,8 @@ use crate::sql::statements::define::{
 };
 use crate::sql::{
 	AccessType, Ast, Base, BinaryOperator, Data, DefineFieldStatement, DefineIndexStatement, Expr,
-	Index, InsertStatement, KillStatement, Kind, Literal, Permission, Permissions, SelectStatement,
-	TopLevelExpr, View,
+	Idiom, Index, InsertStatement, KillStatement, Kind, Literal, Part, Permission, Permissions,
+	SelectStatement, TopLevelExpr, View,
 };
 
 impl<'a> Arbitrary<'a> for KillStatement {
@@ -29,6 +29,7 @@ impl<'a> Arbitrary<'a> for KillStatement {
 
 		Ok(KillStatement {
 			id,
+			query: u.arbitrary()?,
 		})
 	}
 }
@@ -126,16 +127,41 @@ impl<'a> arbitrary::Arbitrary<'a> for DefineIndexStatement {
 				}
 				cols
 			}
-			Index::Hnsw(_) | Index::FullText(_) => vec![u.arbitrary()?],
+			Index::Hnsw(_)
+			| Index::FullText(_)
+			| Index::Spatial
+			| Index::Trigram
+			| Index::Ivf(_) => {
+				vec![u.arbitrary()?]
+			}
 			Index::Count(_) => Vec::new(),
 		};
 
+		let include = match index {
+			Index::Uniq | Index::Idx => {
+				let len = u.arbitrary_len::<String>()?;
+				let mut include = Vec::with_capacity(len);
+				for _ in 0..len {
+					include.push(Expr::Idiom(Idiom(vec![Part::Field(u.arbitrary()?)])));
+				}
+				include
+			}
+			_ => Vec::new(),
+		};
+
+		let cond = match index {
+			Index::Uniq | Index::Idx => u.arbitrary()?,
+			_ => None,
+		};
+
 		Ok(DefineIndexStatement {
 			kind,
 			name,
 			what,
 			cols,
 			index,
+			include,
+			cond,
 			comment,
 			concurrently,
 		})
@@ -5,7 +5,8 @@ use surrealdb_types::{SqlFormat, ToSql, write_sql};
 use crate::expr;
 use crate::fmt::Fmt;
 use crate::sql::statements::{
-	AccessStatement, KillStatement, LiveStatement, OptionStatement, ShowStatement, UseStatement,
+	AccessStatement, AcknowledgeStatement, KillStatement, LiveStatement, OptionStatement,
+	ShowStatement, UseStatement,
 };
 use crate::sql::{Expr, Param};
 
@@ -109,6 +110,7 @@ pub(crate) enum TopLevelExpr {
 	Option(OptionStatement),
 	Use(UseStatement),
 	Show(ShowStatement),
+	Acknowledge(AcknowledgeStatement),
 	Expr(Expr),
 }
 
@@ -136,6 +138,9 @@ impl From<TopLevelExpr> for crate::expr::TopLevelExpr {
 			TopLevelExpr::Show(show_statement) => {
 				crate::expr::TopLevelExpr::Show(show_statement.into())
 			}
+			TopLevelExpr::Acknowledge(acknowledge_statement) => {
+				crate::expr::TopLevelExpr::Acknowledge(acknowledge_statement.into())
+			}
 			TopLevelExpr::Expr(expr) => crate::expr::TopLevelExpr::Expr(expr.into()),
 		}
 	}
@@ -165,6 +170,9 @@ impl From<crate::expr::TopLevelExpr> for TopLevelExpr {
 			crate::expr::TopLevelExpr::Show(show_statement) => {
 				TopLevelExpr::Show(show_statement.into())
 			}
+			crate::expr::TopLevelExpr::Acknowledge(acknowledge_statement) => {
+				TopLevelExpr::Acknowledge(acknowledge_statement.into())
+			}
 			crate::expr::TopLevelExpr::Expr(expr) => TopLevelExpr::Expr(expr.into()),
 		}
 	}
@@ -192,6 +200,7 @@ impl ToSql for TopLevelExpr {
 			TopLevelExpr::Option(s) => s.fmt_sql(f, fmt),
 			TopLevelExpr::Use(s) => s.fmt_sql(f, fmt),
 			TopLevelExpr::Show(s) => s.fmt_sql(f, fmt),
+			TopLevelExpr::Acknowledge(s) => s.fmt_sql(f, fmt),
 			TopLevelExpr::Expr(e) => e.fmt_sql(f, fmt),
 		}
 	}
@@ -1,6 +1,6 @@
 use surrealdb_types::{SqlFormat, ToSql, write_sql};
 
-use crate::fmt::QuoteStr;
+use crate::fmt::{Fmt, QuoteStr};
 use crate::sql::language::Language;
 
 #[derive(Clone, Debug, PartialEq, Eq)]
@@ -13,6 +13,34 @@ pub enum Filter {
 	Snowball(Language),
 	Uppercase,
 	Mapper(String),
+	Stopwords(Stopwords),
+	Synonyms(Synonyms),
+}
+
+/// The source of the words removed by a `STOPWORDS` filter
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub enum Stopwords {
+	/// The built-in list for a language
+	Language(Language),
+	/// A custom list of words
+	Words(Vec<String>),
+	/// A file containing one word per line
+	File(String),
+}
+
+/// The source of the rules applied by a `SYNONYMS` filter
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub enum Synonyms {
+	/// A custom list of rules
+	Rules(Vec<String>),
+	/// A file containing one rule per line
+	File(String),
+}
+
+fn fmt_list(list: &[String], f: &mut String, fmt: SqlFormat) {
+	write_sql!(f, fmt, "[{}]", Fmt::comma_separated(list.iter().map(|x| QuoteStr(x))));
 }
 
 impl ToSql for Filter {
@@ -25,6 +53,61 @@ impl ToSql for Filter {
 			Self::Snowball(lang) => write_sql!(f, fmt, "SNOWBALL({lang})"),
 			Self::Uppercase => f.push_str("UPPERCASE"),
 			Self::Mapper(path) => write_sql!(f, fmt, "MAPPER({})", QuoteStr(path)),
+			Self::Stopwords(v) => {
+				f.push_str("STOPWORDS(");
+				match v {
+					Stopwords::Language(lang) => write_sql!(f, fmt, "{lang}"),
+					Stopwords::Words(words) => fmt_list(words, f, fmt),
+					Stopwords::File(path) => write_sql!(f, fmt, "{}", QuoteStr(path)),
+				}
+				f.push(')');
+			}
+			Self::Synonyms(v) => {
+				f.push_str("SYNONYMS(");
+				match v {
+					Synonyms::Rules(rules) => fmt_list(rules, f, fmt),
+					Synonyms::File(path) => write_sql!(f, fmt, "{}", QuoteStr(path)),
+				}
+				f.push(')');
+			}
+		}
+	}
+}
+
+impl From<Stopwords> for crate::expr::filter::Stopwords {
+	fn from(v: Stopwords) -> Self {
+		match v {
+			Stopwords::Language(lang) => Self::Language(lang.into()),
+			Stopwords::Words(words) => Self::Words(words),
+			Stopwords::File(path) => Self::File(path),
+		}
+	}
+}
+
+impl From<crate::expr::filter::Stopwords> for Stopwords {
+	fn from(v: crate::expr::filter::Stopwords) -> Self {
+		match v {
+			crate::expr::filter::Stopwords::Language(lang) => Self::Language(lang.into()),
+			crate::expr::filter::Stopwords::Words(words) => Self::Words(words),
+			crate::expr::filter::Stopwords::File(path) => Self::File(path),
+		}
+	}
+}
+
+impl From<Synonyms> for crate::expr::filter::Synonyms {
+	fn from(v: Synonyms) -> Self {
+		match v {
+			Synonyms::Rules(rules) => Self::Rules(rules),
+			Synonyms::File(path) => Self::File(path),
+		}
+	}
+}
+
+impl From<crate::expr::filter::Synonyms> for Synonyms {
+	fn from(v: crate::expr::filter::Synonyms) -> Self {
+		match v {
+			crate::expr::filter::Synonyms::Rules(rules) => Self::Rules(rules),
+			crate::expr::filter::Synonyms::File(path) => Self::File(path),
 		}
 	}
 }
@@ -39,6 +122,8 @@ impl From<Filter> for crate::expr::Filter {
 			Filter::Snowball(lang) => Self::Snowball(lang.into()),
 			Filter::Uppercase => Self::Uppercase,
 			Filter::Mapper(path) => Self::Mapper(path),
+			Filter::Stopwords(v) => Self::Stopwords(v.into()),
+			Filter::Synonyms(v) => Self::Synonyms(v.into()),
 		}
 	}
 }
@@ -53,6 +138,8 @@ impl From<crate::expr::Filter> for Filter {
 			crate::expr::Filter::Snowball(lang) => Self::Snowball(lang.into()),
 			crate::expr::Filter::Uppercase => Self::Uppercase,
 			crate::expr::Filter::Mapper(path) => Self::Mapper(path),
+			crate::expr::Filter::Stopwords(v) => Self::Stopwords(v.into()),
+			crate::expr::Filter::Synonyms(v) => Self::Synonyms(v.into()),
 		}
 	}
 }
@@ -18,6 +18,12 @@ pub(crate) enum Index {
 	FullText(FullTextParams),
 	/// Count index
 	Count(Option<Cond>),
+	/// Spatial index over geometries
+	Spatial,
+	/// Trigram index over strings
+	Trigram,
+	/// Inverted-file index for distance based metrics
+	Ivf(IvfParams),
 }
 
 impl From<Index> for crate::catalog::Index {
@@ -28,6 +34,9 @@ impl From<Index> for crate::catalog::Index {
 			Index::Hnsw(p) => Self::Hnsw(p.into()),
 			Index::FullText(p) => Self::FullText(p.into()),
 			Index::Count(c) => Self::Count(c.map(Into::into)),
+			Index::Spatial => Self::Spatial,
+			Index::Trigram => Self::Trigram,
+			Index::Ivf(p) => Self::Ivf(p.into()),
 		}
 	}
 }
@@ -40,6 +49,9 @@ impl From<crate::catalog::Index> for Index {
 			crate::catalog::Index::Hnsw(p) => Self::Hnsw(p.into()),
 			crate::catalog::Index::FullText(p) => Self::FullText(p.into()),
 			crate::catalog::Index::Count(c) => Self::Count(c.map(Into::into)),
+			crate::catalog::Index::Spatial => Self::Spatial,
+			crate::catalog::Index::Trigram => Self::Trigram,
+			crate::catalog::Index::Ivf(p) => Self::Ivf(p.into()),
 		}
 	}
 }
@@ -84,6 +96,8 @@ pub(crate) struct HnswParams {
 	pub keep_pruned_connections: bool,
 	pub ml: PublicNumber,
 	pub use_hashed_vector: bool,
+	pub quantization: Option<Quantization>,
+	pub multi_vector: bool,
 }
 
 impl From<HnswParams> for crate::catalog::HnswParams {
@@ -99,6 +113,8 @@ impl From<HnswParams> for crate::catalog::HnswParams {
 			extend_candidates: v.extend_candidates,
 			keep_pruned_connections: v.keep_pruned_connections,
 			use_hashed_vector: v.use_hashed_vector,
+			quantization: v.quantization.map(Into::into),
+			multi_vector: v.multi_vector,
 		}
 	}
 }
@@ -116,6 +132,8 @@ impl From<crate::catalog::HnswParams> for HnswParams {
 			extend_candidates: v.extend_candidates,
 			keep_pruned_connections: v.keep_pruned_connections,
 			use_hashed_vector: v.use_hashed_vector,
+			quantization: v.quantization.map(Into::into),
+			multi_vector: v.multi_vector,
 		}
 	}
 }
@@ -202,11 +220,85 @@ impl ToSql for VectorType {
 	}
 }
 
+#[derive(Clone, Debug, Eq, PartialEq, PartialOrd, Hash)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub(crate) enum Quantization {
+	Scalar,
+	Binary,
+	Product(u16),
+}
+
+impl ToSql for Quantization {
+	fn fmt_sql(&self, f: &mut String, fmt: SqlFormat) {
+		match self {
+			Self::Scalar => f.push_str("SCALAR"),
+			Self::Binary => f.push_str("BINARY"),
+			Self::Product(m) => write_sql!(f, fmt, "PRODUCT {}", m),
+		}
+	}
+}
+
+impl From<Quantization> for crate::catalog::Quantization {
+	fn from(v: Quantization) -> Self {
+		match v {
+			Quantization::Scalar => Self::Scalar,
+			Quantization::Binary => Self::Binary,
+			Quantization::Product(m) => Self::Product(m),
+		}
+	}
+}
+
+impl From<crate::catalog::Quantization> for Quantization {
+	fn from(v: crate::catalog::Quantization) -> Self {
+		match v {
+			crate::catalog::Quantization::Scalar => Self::Scalar,
+			crate::catalog::Quantization::Binary => Self::Binary,
+			crate::catalog::Quantization::Product(m) => Self::Product(m),
+		}
+	}
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, PartialOrd, Hash)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub(crate) struct IvfParams {
+	pub dimension: u16,
+	pub distance: Distance,
+	pub vector_type: VectorType,
+	pub lists: u16,
+	pub probes: u16,
+}
+
+impl From<IvfParams> for crate::catalog::IvfParams {
+	fn from(v: IvfParams) -> Self {
+		crate::catalog::IvfParams {
+			dimension: v.dimension,
+			distance: v.distance.into(),
+			vector_type: v.vector_type.into(),
+			lists: v.lists,
+			probes: v.probes,
+		}
+	}
+}
+
+impl From<crate::catalog::IvfParams> for IvfParams {
+	fn from(v: crate::catalog::IvfParams) -> Self {
+		Self {
+			dimension: v.dimension,
+			distance: v.distance.into(),
+			vector_type: v.vector_type.into(),
+			lists: v.lists,
+			probes: v.probes,
+		}
+	}
+}
+
 impl ToSql for Index {
 	fn fmt_sql(&self, f: &mut String, fmt: SqlFormat) {
 		match self {
 			Self::Idx => {}
 			Self::Uniq => f.push_str("UNIQUE"),
+			Self::Spatial => f.push_str("SPATIAL"),
+			Self::Trigram => f.push_str("TRIGRAM"),
 			Self::Count(c) => {
 				f.push_str("COUNT");
 				if let Some(v) = c {
@@ -241,6 +333,24 @@ impl ToSql for Index {
 				if p.use_hashed_vector {
 					f.push_str(" HASHED_VECTOR")
 				}
+				if let Some(q) = &p.quantization {
+					write_sql!(f, fmt, " QUANTIZATION {}", q)
+				}
+				if p.multi_vector {
+					f.push_str(" MULTI_VECTOR")
+				}
+			}
+			Self::Ivf(p) => {
+				write_sql!(
+					f,
+					fmt,
+					"IVF DIMENSION {} DIST {} TYPE {} LISTS {} PROBES {}",
+					p.dimension,
+					p.distance,
+					p.vector_type,
+					p.lists,
+					p.probes
+				);
 			}
 		}
 	}
@@ -211,31 +211,34 @@ pub(crate) enum BinaryOperator {
 pub struct MatchesOperator {
 	pub rf: Option<u8>,
 	pub operator: Option<BooleanOperator>,
+	pub fuzzy: Option<Fuzzy>,
 }
 
 impl ToSql for MatchesOperator {
 	fn fmt_sql(&self, f: &mut String, fmt: SqlFormat) {
+		f.push('@');
+		let mut separate = false;
 		if let Some(r) = self.rf {
-			if let Some(ref o) = self.operator {
-				// Don't show AND operator since it's the default
-				if !matches!(o, BooleanOperator::And) {
-					write_sql!(f, fmt, "@{r},{o}@");
-				} else {
-					write_sql!(f, fmt, "@{r}@");
-				}
-			} else {
-				write_sql!(f, fmt, "@{r}@");
+			write_sql!(f, fmt, "{r}");
+			separate = true;
+		}
+		// Don't show AND operator since it's the default
+		if let Some(ref o) = self.operator
+			&& !matches!(o, BooleanOperator::And)
+		{
+			if separate {
+				f.push(',');
 			}
-		} else if let Some(ref o) = self.operator {
-			// Don't show AND operator since it's the default
-			if !matches!(o, BooleanOperator::And) {
-				write_sql!(f, fmt, "@{o}@");
-			} else {
-				f.push_str("@@");
+			write_sql!(f, fmt, "{o}");
+			separate = true;
+		}
+		if let Some(ref fuzzy) = self.fuzzy {
+			if separate {
+				f.push(',');
 			}
-		} else {
-			f.push_str("@@");
+			write_sql!(f, fmt, "{fuzzy}");
 		}
+		f.push('@');
 	}
 }
 
@@ -247,6 +250,7 @@ impl From<MatchesOperator> for crate::expr::operator::MatchesOperator {
 				.operator
 				.map(From::from)
 				.unwrap_or(crate::expr::operator::BooleanOperator::And),
+			fuzzy: value.fuzzy.map(From::from),
 		}
 	}
 }
@@ -256,6 +260,44 @@ impl From<crate::expr::operator::MatchesOperator> for MatchesOperator {
 		MatchesOperator {
 			rf: value.rf,
 			operator: Some(value.operator.into()),
+			fuzzy: value.fuzzy.map(From::from),
+		}
+	}
+}
+
+/// Typo-tolerant matching of the terms of a full-text query
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Fuzzy {
+	/// The maximum edit distance between a query term and an indexed term
+	pub distance: u8,
+	/// The number of leading characters which must match exactly
+	pub prefix: u8,
+}
+
+impl ToSql for Fuzzy {
+	fn fmt_sql(&self, f: &mut String, fmt: SqlFormat) {
+		if self.prefix == 0 {
+			write_sql!(f, fmt, "FUZZY({})", self.distance);
+		} else {
+			write_sql!(f, fmt, "FUZZY({},{})", self.distance, self.prefix);
+		}
+	}
+}
+
+impl From<Fuzzy> for crate::expr::operator::Fuzzy {
+	fn from(value: Fuzzy) -> Self {
+		crate::expr::operator::Fuzzy {
+			distance: value.distance,
+			prefix: value.prefix,
+		}
+	}
+}
+
+impl From<crate::expr::operator::Fuzzy> for Fuzzy {
+	fn from(value: crate::expr::operator::Fuzzy) -> Self {
+		Fuzzy {
+			distance: value.distance,
+			prefix: value.prefix,
 		}
 	}
 }
@@ -396,6 +438,8 @@ pub(crate) enum NearestNeighbor {
 	KTree(u32),
 	/// `<|k, ef|>`
 	Approximate(u32, u32),
+	/// `<|k, ef, aggregation|>`
+	MultiVector(u32, u32, VectorAggregation),
 }
 
 impl ToSql for NearestNeighbor {
@@ -404,6 +448,7 @@ impl ToSql for NearestNeighbor {
 			Self::K(k, d) => write_sql!(f, fmt, "<|{k},{d}|>"),
 			Self::KTree(k) => write_sql!(f, fmt, "<|{k}|>"),
 			Self::Approximate(k, ef) => write_sql!(f, fmt, "<|{k},{ef}|>"),
+			Self::MultiVector(k, ef, a) => write_sql!(f, fmt, "<|{k},{ef},{a}|>"),
 		}
 	}
 }
@@ -416,6 +461,9 @@ impl From<NearestNeighbor> for crate::expr::operator::NearestNeighbor {
 			NearestNeighbor::Approximate(k, ef) => {
 				crate::expr::operator::NearestNeighbor::Approximate(k, ef)
 			}
+			NearestNeighbor::MultiVector(k, ef, a) => {
+				crate::expr::operator::NearestNeighbor::MultiVector(k, ef, a.into())
+			}
 		}
 	}
 }
@@ -428,6 +476,49 @@ impl From<crate::expr::operator::NearestNeighbor> for NearestNeighbor {
 			crate::expr::operator::NearestNeighbor::Approximate(k, ef) => {
 				NearestNeighbor::Approximate(k, ef)
 			}
+			crate::expr::operator::NearestNeighbor::MultiVector(k, ef, a) => {
+				NearestNeighbor::MultiVector(k, ef, a.into())
+			}
+		}
+	}
+}
+
+/// How the distances between the query vectors and the vectors of a record
+/// are aggregated into the distance of the record
+#[derive(Clone, Debug, Eq, PartialEq, PartialOrd, Hash)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub(crate) enum VectorAggregation {
+	MaxSim,
+	Max,
+	Mean,
+}
+
+impl ToSql for VectorAggregation {
+	fn fmt_sql(&self, f: &mut String, _fmt: SqlFormat) {
+		match self {
+			Self::MaxSim => f.push_str("MAXSIM"),
+			Self::Max => f.push_str("MAX"),
+			Self::Mean => f.push_str("MEAN"),
+		}
+	}
+}
+
+impl From<VectorAggregation> for crate::expr::operator::VectorAggregation {
+	fn from(value: VectorAggregation) -> Self {
+		match value {
+			VectorAggregation::MaxSim => crate::expr::operator::VectorAggregation::MaxSim,
+			VectorAggregation::Max => crate::expr::operator::VectorAggregation::Max,
+			VectorAggregation::Mean => crate::expr::operator::VectorAggregation::Mean,
+		}
+	}
+}
+
+impl From<crate::expr::operator::VectorAggregation> for VectorAggregation {
+	fn from(value: crate::expr::operator::VectorAggregation) -> Self {
+		match value {
+			crate::expr::operator::VectorAggregation::MaxSim => VectorAggregation::MaxSim,
+			crate::expr::operator::VectorAggregation::Max => VectorAggregation::Max,
+			crate::expr::operator::VectorAggregation::Mean => VectorAggregation::Mean,
 		}
 	}
 }
@@ -481,6 +572,9 @@ impl ToSql for BinaryOperator {
 				NearestNeighbor::Approximate(k, ef) => {
 					write_sql!(f, fmt, "<|{k},{ef}|>");
 				}
+				NearestNeighbor::MultiVector(k, ef, a) => {
+					write_sql!(f, fmt, "<|{k},{ef},{a}|>");
+				}
 			},
 		}
 	}
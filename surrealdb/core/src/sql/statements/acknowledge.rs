@@ -0,0 +1,42 @@
+use surrealdb_types::{SqlFormat, ToSql, write_sql};
+
+use crate::fmt::EscapeKwFreeIdent;
+
+/// An ACKNOWLEDGE statement, which commits the position of a changefeed
+/// consumer up to and including the given versionstamp.
+#[derive(Clone, Debug, Eq, PartialEq, PartialOrd, Hash)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub struct AcknowledgeStatement {
+	pub consumer: String,
+	pub versionstamp: u64,
+}
+
+impl ToSql for AcknowledgeStatement {
+	fn fmt_sql(&self, f: &mut String, fmt: SqlFormat) {
+		write_sql!(
+			f,
+			fmt,
+			"ACKNOWLEDGE CONSUMER {} AT {}",
+			EscapeKwFreeIdent(&self.consumer),
+			self.versionstamp
+		);
+	}
+}
+
+impl From<AcknowledgeStatement> for crate::expr::statements::AcknowledgeStatement {
+	fn from(v: AcknowledgeStatement) -> Self {
+		Self {
+			consumer: v.consumer,
+			versionstamp: v.versionstamp,
+		}
+	}
+}
+
+impl From<crate::expr::statements::AcknowledgeStatement> for AcknowledgeStatement {
+	fn from(v: crate::expr::statements::AcknowledgeStatement) -> Self {
+		Self {
+			consumer: v.consumer,
+			versionstamp: v.versionstamp,
+		}
+	}
+}
@@ -1,8 +1,9 @@
 use surrealdb_types::{SqlFormat, ToSql, write_sql};
 
 use super::DefineKind;
-use crate::fmt::CoverStmts;
+use crate::fmt::{CoverStmts, Fmt, QuoteStr};
 use crate::sql::{Expr, Literal, Permission};
+use crate::types::PublicDuration;
 
 #[derive(Clone, Debug, Eq, PartialEq)]
 #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
@@ -12,6 +13,9 @@ pub(crate) struct DefineBucketStatement {
 	pub backend: Option<Expr>,
 	pub permissions: Permission,
 	pub readonly: bool,
+	pub max_size: Option<u64>,
+	pub max_count: Option<u64>,
+	pub lifecycle: Vec<BucketLifecycleRule>,
 	pub comment: Expr,
 }
 
@@ -23,6 +27,9 @@ impl Default for DefineBucketStatement {
 			backend: None,
 			permissions: Permission::default(),
 			readonly: false,
+			max_size: None,
+			max_count: None,
+			lifecycle: Vec::new(),
 			comment: Expr::Literal(Literal::None),
 		}
 	}
@@ -46,6 +53,18 @@ impl ToSql for DefineBucketStatement {
 			write_sql!(f, sql_fmt, " BACKEND {}", CoverStmts(backend));
 		}
 
+		if let Some(max_size) = self.max_size {
+			write_sql!(f, sql_fmt, " MAXSIZE {max_size}");
+		}
+
+		if let Some(max_count) = self.max_count {
+			write_sql!(f, sql_fmt, " MAXCOUNT {max_count}");
+		}
+
+		if !self.lifecycle.is_empty() {
+			write_sql!(f, sql_fmt, " LIFECYCLE {}", Fmt::comma_separated(self.lifecycle.iter()));
+		}
+
 		write_sql!(f, sql_fmt, " PERMISSIONS {}", self.permissions);
 
 		if !matches!(self.comment, Expr::Literal(Literal::None)) {
@@ -62,6 +81,9 @@ impl From<DefineBucketStatement> for crate::expr::statements::define::DefineBuck
 			backend: v.backend.map(Into::into),
 			permissions: v.permissions.into(),
 			readonly: v.readonly,
+			max_size: v.max_size,
+			max_count: v.max_count,
+			lifecycle: v.lifecycle.into_iter().map(Into::into).collect(),
 			comment: v.comment.into(),
 		}
 	}
@@ -75,7 +97,41 @@ impl From<crate::expr::statements::define::DefineBucketStatement> for DefineBuck
 			backend: v.backend.map(Into::into),
 			permissions: v.permissions.into(),
 			readonly: v.readonly,
+			max_size: v.max_size,
+			max_count: v.max_count,
+			lifecycle: v.lifecycle.into_iter().map(Into::into).collect(),
 			comment: v.comment.into(),
 		}
 	}
 }
+
+/// A lifecycle rule, as written in a `DEFINE BUCKET` statement.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub(crate) struct BucketLifecycleRule {
+	pub prefix: String,
+	pub expiry: PublicDuration,
+}
+
+impl ToSql for BucketLifecycleRule {
+	fn fmt_sql(&self, f: &mut String, sql_fmt: SqlFormat) {
+		write_sql!(f, sql_fmt, "{} AFTER {}", QuoteStr(&self.prefix), self.expiry);
+	}
+}
+
+impl From<BucketLifecycleRule> for crate::catalog::BucketLifecycleRule {
+	fn from(v: BucketLifecycleRule) -> Self {
+		crate::catalog::BucketLifecycleRule {
+			prefix: v.prefix,
+			expiry: v.expiry.into(),
+		}
+	}
+}
+
+impl From<crate::catalog::BucketLifecycleRule> for BucketLifecycleRule {
+	fn from(v: crate::catalog::BucketLifecycleRule) -> Self {
+		BucketLifecycleRule {
+			prefix: v.prefix,
+			expiry: v.expiry.into(),
+		}
+	}
+}
@@ -0,0 +1,91 @@
+use surrealdb_types::{SqlFormat, ToSql, write_sql};
+
+use super::DefineKind;
+use crate::fmt::CoverStmts;
+use crate::sql::{Expr, Literal};
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub(crate) struct DefineConsumerStatement {
+	pub kind: DefineKind,
+	pub name: Expr,
+	pub target_table: Expr,
+	pub comment: Expr,
+	pub sink: Option<ConsumerSink>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub(crate) enum ConsumerSink {
+	Webhook(Expr),
+	File(Expr),
+}
+
+impl ToSql for ConsumerSink {
+	fn fmt_sql(&self, f: &mut String, fmt: SqlFormat) {
+		match self {
+			Self::Webhook(v) => write_sql!(f, fmt, "SINK WEBHOOK {}", CoverStmts(v)),
+			Self::File(v) => write_sql!(f, fmt, "SINK FILE {}", CoverStmts(v)),
+		}
+	}
+}
+
+impl From<ConsumerSink> for crate::expr::statements::define::ConsumerSink {
+	fn from(v: ConsumerSink) -> Self {
+		match v {
+			ConsumerSink::Webhook(v) => Self::Webhook(v.into()),
+			ConsumerSink::File(v) => Self::File(v.into()),
+		}
+	}
+}
+
+impl From<crate::expr::statements::define::ConsumerSink> for ConsumerSink {
+	fn from(v: crate::expr::statements::define::ConsumerSink) -> Self {
+		match v {
+			crate::expr::statements::define::ConsumerSink::Webhook(v) => Self::Webhook(v.into()),
+			crate::expr::statements::define::ConsumerSink::File(v) => Self::File(v.into()),
+		}
+	}
+}
+
+impl ToSql for DefineConsumerStatement {
+	fn fmt_sql(&self, f: &mut String, fmt: SqlFormat) {
+		f.push_str("DEFINE CONSUMER");
+		match self.kind {
+			DefineKind::Default => {}
+			DefineKind::Overwrite => f.push_str(" OVERWRITE"),
+			DefineKind::IfNotExists => f.push_str(" IF NOT EXISTS"),
+		}
+		write_sql!(f, fmt, " {} ON {}", CoverStmts(&self.name), CoverStmts(&self.target_table));
+		if let Some(ref v) = self.sink {
+			write_sql!(f, fmt, " {}", v);
+		}
+		if !matches!(self.comment, Expr::Literal(Literal::None)) {
+			write_sql!(f, fmt, " COMMENT {}", CoverStmts(&self.comment));
+		}
+	}
+}
+
+impl From<DefineConsumerStatement> for crate::expr::statements::define::DefineConsumerStatement {
+	fn from(v: DefineConsumerStatement) -> Self {
+		Self {
+			kind: v.kind.into(),
+			name: v.name.into(),
+			target_table: v.target_table.into(),
+			comment: v.comment.into(),
+			sink: v.sink.map(Into::into),
+		}
+	}
+}
+
+impl From<crate::expr::statements::define::DefineConsumerStatement> for DefineConsumerStatement {
+	fn from(v: crate::expr::statements::define::DefineConsumerStatement) -> Self {
+		Self {
+			kind: v.kind.into(),
+			name: v.name.into(),
+			target_table: v.target_table.into(),
+			comment: v.comment.into(),
+			sink: v.sink.map(Into::into),
+		}
+	}
+}
@@ -2,7 +2,7 @@ use surrealdb_types::{SqlFormat, ToSql, write_sql};
 
 use super::DefineKind;
 use crate::fmt::{CoverStmts, Fmt};
-use crate::sql::{Expr, Index, Literal};
+use crate::sql::{Cond, Expr, Index, Literal};
 
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub(crate) struct DefineIndexStatement {
@@ -11,6 +11,8 @@ pub(crate) struct DefineIndexStatement {
 	pub what: Expr,
 	pub cols: Vec<Expr>,
 	pub index: Index,
+	pub include: Vec<Expr>,
+	pub cond: Option<Cond>,
 	pub comment: Expr,
 	pub concurrently: bool,
 }
@@ -35,6 +37,17 @@ impl ToSql for DefineIndexStatement {
 		if Index::Idx != self.index {
 			write_sql!(f, sql_fmt, " {}", self.index);
 		}
+		if !self.include.is_empty() {
+			write_sql!(
+				f,
+				sql_fmt,
+				" INCLUDE {}",
+				Fmt::comma_separated(self.include.iter().map(CoverStmts))
+			);
+		}
+		if let Some(ref v) = self.cond {
+			write_sql!(f, sql_fmt, " {}", v);
+		}
 		if !matches!(self.comment, Expr::Literal(Literal::None)) {
 			write_sql!(f, sql_fmt, " COMMENT {}", CoverStmts(&self.comment));
 		}
@@ -52,6 +65,8 @@ impl From<DefineIndexStatement> for crate::expr::statements::DefineIndexStatemen
 			what: v.what.into(),
 			cols: v.cols.into_iter().map(From::from).collect(),
 			index: v.index.into(),
+			include: v.include.into_iter().map(From::from).collect(),
+			cond: v.cond.map(Into::into),
 			comment: v.comment.into(),
 			concurrently: v.concurrently,
 		}
@@ -66,6 +81,8 @@ impl From<crate::expr::statements::DefineIndexStatement> for DefineIndexStatemen
 			what: v.what.into(),
 			cols: v.cols.into_iter().map(From::from).collect(),
 			index: v.index.into(),
+			include: v.include.into_iter().map(From::from).collect(),
+			cond: v.cond.map(Into::into),
 			comment: v.comment.into(),
 			concurrently: v.concurrently,
 		}
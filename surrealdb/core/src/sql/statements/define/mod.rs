@@ -3,6 +3,7 @@ mod analyzer;
 mod api;
 mod bucket;
 pub mod config;
+mod consumer;
 mod database;
 mod event;
 mod field;
@@ -19,8 +20,9 @@ pub mod user;
 pub(crate) use access::DefineAccessStatement;
 pub(crate) use analyzer::DefineAnalyzerStatement;
 pub(crate) use api::{ApiAction, DefineApiStatement};
-pub(crate) use bucket::DefineBucketStatement;
+pub(crate) use bucket::{BucketLifecycleRule, DefineBucketStatement};
 pub(crate) use config::DefineConfigStatement;
+pub(crate) use consumer::{ConsumerSink, DefineConsumerStatement};
 pub(crate) use database::DefineDatabaseStatement;
 pub(crate) use event::DefineEventStatement;
 pub(crate) use field::{DefineDefault, DefineFieldStatement};
@@ -84,6 +86,7 @@ pub(crate) enum DefineStatement {
 	Api(DefineApiStatement),
 	Bucket(DefineBucketStatement),
 	Sequence(DefineSequenceStatement),
+	Consumer(DefineConsumerStatement),
 	#[cfg_attr(feature = "arbitrary", arbitrary(skip))]
 	Module(DefineModuleStatement),
 }
@@ -107,6 +110,7 @@ impl ToSql for DefineStatement {
 			Self::Api(v) => v.fmt_sql(f, fmt),
 			Self::Bucket(v) => v.fmt_sql(f, fmt),
 			Self::Sequence(v) => v.fmt_sql(f, fmt),
+			Self::Consumer(v) => v.fmt_sql(f, fmt),
 			Self::Module(v) => v.fmt_sql(f, fmt),
 		}
 	}
@@ -131,6 +135,7 @@ impl From<DefineStatement> for crate::expr::statements::DefineStatement {
 			DefineStatement::Api(v) => Self::Api(v.into()),
 			DefineStatement::Bucket(v) => Self::Bucket(v.into()),
 			DefineStatement::Sequence(v) => Self::Sequence(v.into()),
+			DefineStatement::Consumer(v) => Self::Consumer(v.into()),
 			DefineStatement::Module(v) => Self::Module(v.into()),
 		}
 	}
@@ -155,6 +160,7 @@ impl From<crate::expr::statements::DefineStatement> for DefineStatement {
 			crate::expr::statements::DefineStatement::Api(v) => Self::Api(v.into()),
 			crate::expr::statements::DefineStatement::Bucket(v) => Self::Bucket(v.into()),
 			crate::expr::statements::DefineStatement::Sequence(v) => Self::Sequence(v.into()),
+			crate::expr::statements::DefineStatement::Consumer(v) => Self::Consumer(v.into()),
 			crate::expr::statements::DefineStatement::Module(v) => Self::Module(v.into()),
 		}
 	}
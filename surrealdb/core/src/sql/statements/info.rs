@@ -14,6 +14,10 @@ pub enum InfoStatement {
 	Tb(Expr, bool, Option<Expr>),
 	User(Expr, Option<Base>, bool),
 	Index(Expr, Expr, bool),
+	Bucket(Expr, bool),
+	Consumer(Expr, bool),
+	Stats(bool),
+	Queries,
 }
 
 impl ToSql for InfoStatement {
@@ -77,6 +81,19 @@ impl ToSql for InfoStatement {
 					CoverStmts(t)
 				)
 			}
+			Self::Bucket(b, false) => write_sql!(f, sql_fmt, "INFO FOR BUCKET {}", CoverStmts(b)),
+			Self::Bucket(b, true) => {
+				write_sql!(f, sql_fmt, "INFO FOR BUCKET {} STRUCTURE", CoverStmts(b))
+			}
+			Self::Consumer(c, false) => {
+				write_sql!(f, sql_fmt, "INFO FOR CONSUMER {}", CoverStmts(c))
+			}
+			Self::Consumer(c, true) => {
+				write_sql!(f, sql_fmt, "INFO FOR CONSUMER {} STRUCTURE", CoverStmts(c))
+			}
+			Self::Stats(false) => f.push_str("INFO FOR STATS"),
+			Self::Stats(true) => f.push_str("INFO FOR STATS RESET"),
+			Self::Queries => f.push_str("INFO FOR QUERIES"),
 		}
 	}
 }
@@ -90,6 +107,10 @@ impl From<InfoStatement> for crate::expr::statements::InfoStatement {
 			InfoStatement::Tb(t, v, ver) => Self::Tb(t.into(), v, ver.map(From::from)),
 			InfoStatement::User(u, b, v) => Self::User(u.into(), b.map(Into::into), v),
 			InfoStatement::Index(i, t, v) => Self::Index(i.into(), t.into(), v),
+			InfoStatement::Bucket(b, v) => Self::Bucket(b.into(), v),
+			InfoStatement::Consumer(c, v) => Self::Consumer(c.into(), v),
+			InfoStatement::Stats(v) => Self::Stats(v),
+			InfoStatement::Queries => Self::Queries,
 		}
 	}
 }
@@ -109,6 +130,10 @@ impl From<crate::expr::statements::InfoStatement> for InfoStatement {
 			crate::expr::statements::InfoStatement::Index(i, t, v) => {
 				Self::Index(i.into(), t.into(), v)
 			}
+			crate::expr::statements::InfoStatement::Bucket(b, v) => Self::Bucket(b.into(), v),
+			crate::expr::statements::InfoStatement::Consumer(c, v) => Self::Consumer(c.into(), v),
+			crate::expr::statements::InfoStatement::Stats(v) => Self::Stats(v),
+			crate::expr::statements::InfoStatement::Queries => Self::Queries,
 		}
 	}
 }
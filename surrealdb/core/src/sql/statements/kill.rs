@@ -8,11 +8,17 @@ pub struct KillStatement {
 	// Uuid of Live Query
 	// or Param resolving to Uuid of Live Query
 	pub id: Expr,
+	// Whether this kills a running query instead of a live query
+	pub query: bool,
 }
 
 impl ToSql for KillStatement {
 	fn fmt_sql(&self, f: &mut String, fmt: SqlFormat) {
-		write_sql!(f, fmt, "KILL {}", CoverStmts(&self.id));
+		if self.query {
+			write_sql!(f, fmt, "KILL QUERY {}", CoverStmts(&self.id));
+		} else {
+			write_sql!(f, fmt, "KILL {}", CoverStmts(&self.id));
+		}
 	}
 }
 
@@ -20,6 +26,7 @@ impl From<KillStatement> for crate::expr::statements::KillStatement {
 	fn from(v: KillStatement) -> Self {
 		Self {
 			id: v.id.into(),
+			query: v.query,
 		}
 	}
 }
@@ -28,6 +35,7 @@ impl From<crate::expr::statements::KillStatement> for KillStatement {
 	fn from(v: crate::expr::statements::KillStatement) -> Self {
 		Self {
 			id: v.id.into(),
+			query: v.query,
 		}
 	}
 }
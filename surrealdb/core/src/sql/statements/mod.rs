@@ -1,4 +1,5 @@
 pub(crate) mod access;
+pub(crate) mod acknowledge;
 pub(crate) mod alter;
 pub(crate) mod create;
 pub(crate) mod define;
@@ -23,6 +24,7 @@ pub(crate) mod upsert;
 pub(crate) mod r#use;
 
 pub(crate) use self::access::AccessStatement;
+pub(crate) use self::acknowledge::AcknowledgeStatement;
 pub(crate) use self::alter::{AlterStatement, AlterTableStatement};
 pub(crate) use self::create::CreateStatement;
 pub(crate) use self::define::{
@@ -31,25 +31,61 @@ impl From<crate::expr::statements::show::ShowSince> for ShowSince {
 	}
 }
 
+/// The source of the changes displayed by a SHOW CHANGES statement.
+#[derive(Clone, Debug, Eq, PartialEq, PartialOrd, Hash)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub enum ShowTarget {
+	Database,
+	Table(String),
+	Consumer(String),
+}
+
+impl From<ShowTarget> for crate::expr::statements::show::ShowTarget {
+	fn from(v: ShowTarget) -> Self {
+		match v {
+			ShowTarget::Database => Self::Database,
+			ShowTarget::Table(v) => Self::Table(TableName::new(v)),
+			ShowTarget::Consumer(v) => Self::Consumer(v),
+		}
+	}
+}
+
+impl From<crate::expr::statements::show::ShowTarget> for ShowTarget {
+	fn from(v: crate::expr::statements::show::ShowTarget) -> Self {
+		match v {
+			crate::expr::statements::show::ShowTarget::Database => ShowTarget::Database,
+			crate::expr::statements::show::ShowTarget::Table(v) => {
+				ShowTarget::Table(v.into_string())
+			}
+			crate::expr::statements::show::ShowTarget::Consumer(v) => ShowTarget::Consumer(v),
+		}
+	}
+}
+
 /// A SHOW CHANGES statement for displaying changes made to a table or database.
 #[derive(Clone, Debug, Eq, PartialEq, PartialOrd, Hash)]
 #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct ShowStatement {
-	pub table: Option<String>,
-	pub since: ShowSince,
+	pub target: ShowTarget,
+	/// The position to read from, which is only optional for a consumer
+	pub since: Option<ShowSince>,
 	pub limit: Option<u32>,
 }
 
 impl ToSql for ShowStatement {
 	fn fmt_sql(&self, f: &mut String, fmt: SqlFormat) {
 		write_sql!(f, fmt, "SHOW CHANGES FOR");
-		match self.table {
-			Some(ref v) => write_sql!(f, fmt, " TABLE {}", EscapeKwFreeIdent(v)),
-			None => write_sql!(f, fmt, " DATABASE"),
+		match self.target {
+			ShowTarget::Database => write_sql!(f, fmt, " DATABASE"),
+			ShowTarget::Table(ref v) => write_sql!(f, fmt, " TABLE {}", EscapeKwFreeIdent(v)),
+			ShowTarget::Consumer(ref v) => {
+				write_sql!(f, fmt, " CONSUMER {}", EscapeKwFreeIdent(v))
+			}
 		}
 		match self.since {
-			ShowSince::Timestamp(ref v) => write_sql!(f, fmt, " SINCE {}", v),
-			ShowSince::Versionstamp(ref v) => write_sql!(f, fmt, " SINCE {}", v),
+			Some(ShowSince::Timestamp(ref v)) => write_sql!(f, fmt, " SINCE {}", v),
+			Some(ShowSince::Versionstamp(ref v)) => write_sql!(f, fmt, " SINCE {}", v),
+			None => {}
 		}
 		if let Some(ref v) = self.limit {
 			write_sql!(f, fmt, " LIMIT {}", v)
@@ -60,8 +96,8 @@ impl ToSql for ShowStatement {
 impl From<ShowStatement> for crate::expr::statements::ShowStatement {
 	fn from(v: ShowStatement) -> Self {
 		crate::expr::statements::ShowStatement {
-			table: v.table.map(TableName::new),
-			since: v.since.into(),
+			target: v.target.into(),
+			since: v.since.map(Into::into),
 			limit: v.limit,
 		}
 	}
@@ -70,8 +106,8 @@ impl From<ShowStatement> for crate::expr::statements::ShowStatement {
 impl From<crate::expr::statements::ShowStatement> for ShowStatement {
 	fn from(v: crate::expr::statements::ShowStatement) -> Self {
 		ShowStatement {
-			table: v.table.map(TableName::into_string),
-			since: v.since.into(),
+			target: v.target.into(),
+			since: v.since.map(Into::into),
 			limit: v.limit,
 		}
 	}
@@ -5,15 +5,16 @@ use surrealdb_types::ToSql;
 use crate::sql::literal::ObjectEntry;
 use crate::sql::statements::access::{AccessStatementGrant, Subject};
 use crate::sql::statements::alter::AlterKind;
+use crate::sql::statements::define::{ConsumerSink, DefineConsumerStatement, DefineKind};
 use crate::sql::statements::live::LiveFields;
 use crate::sql::statements::rebuild::RebuildIndexStatement;
-use crate::sql::statements::show::ShowSince;
+use crate::sql::statements::show::{ShowSince, ShowTarget};
 use crate::sql::statements::{
-	AccessStatement, AlterStatement, AlterTableStatement, CreateStatement, DefineStatement,
-	DefineTableStatement, DeleteStatement, ForeachStatement, IfelseStatement, InfoStatement,
-	InsertStatement, OptionStatement, OutputStatement, RebuildStatement, RelateStatement,
-	RemoveStatement, RemoveTableStatement, SelectStatement, SetStatement, ShowStatement,
-	SleepStatement, UpdateStatement, UpsertStatement, UseStatement,
+	AccessStatement, AcknowledgeStatement, AlterStatement, AlterTableStatement, CreateStatement,
+	DefineStatement, DefineTableStatement, DeleteStatement, ForeachStatement, IfelseStatement,
+	InfoStatement, InsertStatement, OptionStatement, OutputStatement, RebuildStatement,
+	RelateStatement, RemoveStatement, RemoveTableStatement, SelectStatement, SetStatement,
+	ShowStatement, SleepStatement, UpdateStatement, UpsertStatement, UseStatement,
 };
 use crate::sql::{
 	BinaryOperator, Block, Closure, Constant, Data, Expr, Fields, Function, FunctionCall, Idiom,
@@ -150,6 +151,11 @@ use crate::val::{Bytes, Duration, File, Geometry, Number, Object, RecordId, Set,
 	"DEFINE TABLE NONE TYPE ANY SCHEMALESS PERMISSIONS NONE",
 	"DEFINE TABLE NONE TYPE ANY SCHEMALESS\n\tPERMISSIONS NONE"
 )]
+#[case::expr_define_consumer_sink(
+	Expr::Define(Box::new(DefineStatement::Consumer(DefineConsumerStatement { kind: DefineKind::Default, name: Expr::Idiom(Idiom::field("audit".to_string())), target_table: Expr::Table("person".to_string()), comment: Expr::Literal(Literal::None), sink: Some(ConsumerSink::Webhook(Expr::Literal(Literal::String("https://example.com/cdc".to_string())))) }))),
+	"DEFINE CONSUMER audit ON person SINK WEBHOOK 'https://example.com/cdc'",
+	"DEFINE CONSUMER audit ON person SINK WEBHOOK 'https://example.com/cdc'"
+)]
 // Expression: Remove
 #[case::expr_remove(
 	Expr::Remove(Box::new(RemoveStatement::Table(RemoveTableStatement::default()))),
@@ -322,12 +328,15 @@ use crate::val::{Bytes, Duration, File, Geometry, Number, Object, RecordId, Set,
         base: None,
         subject: Subject::Record(RecordIdLit { table: "user".to_string(), key: RecordIdKeyLit::Number(123) }),
     }))), "ACCESS user GRANT FOR RECORD user:123", "ACCESS user GRANT FOR RECORD user:123")]
-#[case::top_level_kill(TopLevelExpr::Kill(KillStatement { id: Expr::Param(Param::new("id".to_string())) }), "KILL $id", "KILL $id")]
+#[case::top_level_kill(TopLevelExpr::Kill(KillStatement { id: Expr::Param(Param::new("id".to_string())), query: false }), "KILL $id", "KILL $id")]
+#[case::top_level_kill_query(TopLevelExpr::Kill(KillStatement { id: Expr::Param(Param::new("id".to_string())), query: true }), "KILL QUERY $id", "KILL QUERY $id")]
 #[case::top_level_live(TopLevelExpr::Live(Box::new(LiveStatement { fields: LiveFields::Select(Fields::all()), what: Expr::Table("user".to_string()), cond: None, fetch: None })), "LIVE SELECT * FROM user", "LIVE SELECT * FROM user")]
 #[case::top_level_live_diff(TopLevelExpr::Live(Box::new(LiveStatement { fields: LiveFields::Diff, what: Expr::Table("user".to_string()), cond: None, fetch: None })), "LIVE SELECT DIFF FROM user", "LIVE SELECT DIFF FROM user")]
 #[case::top_level_option(TopLevelExpr::Option(OptionStatement { name: "IMPORT".to_string(), what: true }), "OPTION IMPORT", "OPTION IMPORT")]
 #[case::top_level_use(TopLevelExpr::Use(UseStatement::NsDb(Expr::Idiom(Idiom::field("ns".to_string())), Expr::Idiom(Idiom::field("db".to_string())))), "USE NS ns DB db", "USE NS ns DB db")]
-#[case::top_level_show(TopLevelExpr::Show(ShowStatement { table: Some("user".to_string()), since: ShowSince::Versionstamp(123), limit: Some(10) }), "SHOW CHANGES FOR TABLE user SINCE 123 LIMIT 10", "SHOW CHANGES FOR TABLE user SINCE 123 LIMIT 10")]
+#[case::top_level_show(TopLevelExpr::Show(ShowStatement { target: ShowTarget::Table("user".to_string()), since: Some(ShowSince::Versionstamp(123)), limit: Some(10) }), "SHOW CHANGES FOR TABLE user SINCE 123 LIMIT 10", "SHOW CHANGES FOR TABLE user SINCE 123 LIMIT 10")]
+#[case::top_level_show_consumer(TopLevelExpr::Show(ShowStatement { target: ShowTarget::Consumer("sync".to_string()), since: None, limit: None }), "SHOW CHANGES FOR CONSUMER sync", "SHOW CHANGES FOR CONSUMER sync")]
+#[case::top_level_acknowledge(TopLevelExpr::Acknowledge(AcknowledgeStatement { consumer: "sync".to_string(), versionstamp: 42 }), "ACKNOWLEDGE CONSUMER sync AT 42", "ACKNOWLEDGE CONSUMER sync AT 42")]
 #[case::top_level_expr(TopLevelExpr::Expr(Expr::Literal(Literal::Integer(1))), "1", "1")]
 fn test_to_sql(#[case] v: impl ToSql, #[case] expected: &str, #[case] expected_pretty: &str) {
 	assert_eq!(v.to_sql(), expected);
@@ -10,6 +10,8 @@ pub enum Tokenizer {
 	Camel,
 	Class,
 	Punct,
+	Word,
+	Cjk,
 }
 
 impl Display for Tokenizer {
@@ -19,6 +21,8 @@ impl Display for Tokenizer {
 			Self::Camel => "CAMEL",
 			Self::Class => "CLASS",
 			Self::Punct => "PUNCT",
+			Self::Word => "WORD",
+			Self::Cjk => "CJK",
 		})
 	}
 }
@@ -34,6 +38,8 @@ impl ToSql for Tokenizer {
 				Self::Camel => "CAMEL",
 				Self::Class => "CLASS",
 				Self::Punct => "PUNCT",
+				Self::Word => "WORD",
+				Self::Cjk => "CJK",
 			}
 		)
 	}
@@ -46,6 +52,8 @@ impl From<Tokenizer> for crate::expr::Tokenizer {
 			Tokenizer::Camel => Self::Camel,
 			Tokenizer::Class => Self::Class,
 			Tokenizer::Punct => Self::Punct,
+			Tokenizer::Word => Self::Word,
+			Tokenizer::Cjk => Self::Cjk,
 		}
 	}
 }
@@ -57,6 +65,8 @@ impl From<crate::expr::Tokenizer> for Tokenizer {
 			crate::expr::Tokenizer::Camel => Self::Camel,
 			crate::expr::Tokenizer::Class => Self::Class,
 			crate::expr::Tokenizer::Punct => Self::Punct,
+			crate::expr::Tokenizer::Word => Self::Word,
+			crate::expr::Tokenizer::Cjk => Self::Cjk,
 		}
 	}
 }
@@ -62,6 +62,7 @@ pub fn could_be_reserved(s: &str) -> bool {
 pub(crate) static KEYWORDS: phf::Map<UniCase<&'static str>, TokenKind> = phf_map! {
 	// Keywords
 	UniCase::ascii("ACCESS") => TokenKind::Keyword(Keyword::Access),
+	UniCase::ascii("ACKNOWLEDGE") => TokenKind::Keyword(Keyword::Acknowledge),
 	UniCase::ascii("AFTER") => TokenKind::Keyword(Keyword::After),
 	UniCase::ascii("ALGORITHM") => TokenKind::Keyword(Keyword::Algorithm),
 	UniCase::ascii("ALL") => TokenKind::Keyword(Keyword::All),
@@ -87,6 +88,7 @@ pub(crate) static KEYWORDS: phf::Map<UniCase<&'static str>, TokenKind> = phf_map
 	UniCase::ascii("BEARER") => TokenKind::Keyword(Keyword::Bearer),
 	UniCase::ascii("BEFORE") => TokenKind::Keyword(Keyword::Before),
 	UniCase::ascii("BEGIN") => TokenKind::Keyword(Keyword::Begin),
+	UniCase::ascii("BINARY") => TokenKind::Keyword(Keyword::Binary),
 	UniCase::ascii("BLANK") => TokenKind::Keyword(Keyword::Blank),
 	UniCase::ascii("BM25") => TokenKind::Keyword(Keyword::Bm25),
 	UniCase::ascii("BREAK") => TokenKind::Keyword(Keyword::Break),
@@ -98,6 +100,7 @@ pub(crate) static KEYWORDS: phf::Map<UniCase<&'static str>, TokenKind> = phf_map
 	UniCase::ascii("CASCADE") => TokenKind::Keyword(Keyword::Cascade),
 	UniCase::ascii("CHANGEFEED") => TokenKind::Keyword(Keyword::ChangeFeed),
 	UniCase::ascii("CHANGES") => TokenKind::Keyword(Keyword::Changes),
+	UniCase::ascii("CJK") => TokenKind::Keyword(Keyword::Cjk),
 	UniCase::ascii("CLASS") => TokenKind::Keyword(Keyword::Class),
 	UniCase::ascii("COLLATE") => TokenKind::Keyword(Keyword::Collate),
 	UniCase::ascii("COLUMNS") => TokenKind::Keyword(Keyword::Fields),
@@ -107,6 +110,7 @@ pub(crate) static KEYWORDS: phf::Map<UniCase<&'static str>, TokenKind> = phf_map
 	UniCase::ascii("COMPUTED") => TokenKind::Keyword(Keyword::Computed),
 	UniCase::ascii("CONCURRENTLY") => TokenKind::Keyword(Keyword::Concurrently),
 	UniCase::ascii("CONFIG") => TokenKind::Keyword(Keyword::Config),
+	UniCase::ascii("CONSUMER") => TokenKind::Keyword(Keyword::Consumer),
 	UniCase::ascii("CONTAINS") => TokenKind::Keyword(Keyword::Contains),
 	UniCase::ascii("CONTAINSALL") => TokenKind::Keyword(Keyword::ContainsAll),
 	UniCase::ascii("CONTAINSANY") => TokenKind::Keyword(Keyword::ContainsAny),
@@ -155,6 +159,7 @@ pub(crate) static KEYWORDS: phf::Map<UniCase<&'static str>, TokenKind> = phf_map
 	UniCase::ascii("FULLTEXT") => TokenKind::Keyword(Keyword::Fulltext),
 	UniCase::ascii("FUNCTION") => TokenKind::Keyword(Keyword::Function),
 	UniCase::ascii("FUNCTIONS") => TokenKind::Keyword(Keyword::Functions),
+	UniCase::ascii("FUZZY") => TokenKind::Keyword(Keyword::Fuzzy),
 	UniCase::ascii("GRANT") => TokenKind::Keyword(Keyword::Grant),
 	UniCase::ascii("GRAPHQL") => TokenKind::Keyword(Keyword::Graphql),
 	UniCase::ascii("GROUP") => TokenKind::Keyword(Keyword::Group),
@@ -174,6 +179,7 @@ pub(crate) static KEYWORDS: phf::Map<UniCase<&'static str>, TokenKind> = phf_map
 	UniCase::ascii("INTO") => TokenKind::Keyword(Keyword::Into),
 	UniCase::ascii("IS") => TokenKind::Keyword(Keyword::Is),
 	UniCase::ascii("ISSUER") => TokenKind::Keyword(Keyword::Issuer),
+	UniCase::ascii("IVF") => TokenKind::Keyword(Keyword::Ivf),
 	UniCase::ascii("JWKS") => TokenKind::Keyword(Keyword::Jwks),
 	UniCase::ascii("JWT") => TokenKind::Keyword(Keyword::Jwt),
 	UniCase::ascii("KEEP_PRUNED_CONNECTIONS") => TokenKind::Keyword(Keyword::KeepPrunedConnections),
@@ -181,19 +187,27 @@ pub(crate) static KEYWORDS: phf::Map<UniCase<&'static str>, TokenKind> = phf_map
 	UniCase::ascii("KILL") => TokenKind::Keyword(Keyword::Kill),
 	UniCase::ascii("KV") => TokenKind::Keyword(Keyword::Root),
 	UniCase::ascii("LET") => TokenKind::Keyword(Keyword::Let),
+	UniCase::ascii("LIFECYCLE") => TokenKind::Keyword(Keyword::Lifecycle),
 	UniCase::ascii("LIMIT") => TokenKind::Keyword(Keyword::Limit),
+	UniCase::ascii("LISTS") => TokenKind::Keyword(Keyword::Lists),
 	UniCase::ascii("LIVE") => TokenKind::Keyword(Keyword::Live),
 	UniCase::ascii("LM") => TokenKind::Keyword(Keyword::Lm),
 	UniCase::ascii("LOWERCASE") => TokenKind::Keyword(Keyword::Lowercase),
 	UniCase::ascii("M") => TokenKind::Keyword(Keyword::M),
 	UniCase::ascii("M0") => TokenKind::Keyword(Keyword::M0),
 	UniCase::ascii("MAPPER") => TokenKind::Keyword(Keyword::Mapper),
+	UniCase::ascii("MAX") => TokenKind::Keyword(Keyword::Max),
+	UniCase::ascii("MAXCOUNT") => TokenKind::Keyword(Keyword::MaxCount),
 	UniCase::ascii("MAXDEPTH") => TokenKind::Keyword(Keyword::MaxDepth),
+	UniCase::ascii("MAXSIM") => TokenKind::Keyword(Keyword::MaxSim),
+	UniCase::ascii("MAXSIZE") => TokenKind::Keyword(Keyword::MaxSize),
+	UniCase::ascii("MEAN") => TokenKind::Keyword(Keyword::Mean),
 	UniCase::ascii("MERGE") => TokenKind::Keyword(Keyword::Merge),
 	UniCase::ascii("MIDDLEWARE") => TokenKind::Keyword(Keyword::Middleware),
 	UniCase::ascii("ML") => TokenKind::Keyword(Keyword::ML),
 	UniCase::ascii("MODEL") => TokenKind::Keyword(Keyword::Model),
 	UniCase::ascii("MODULE") => TokenKind::Keyword(Keyword::Module),
+	UniCase::ascii("MULTI_VECTOR") => TokenKind::Keyword(Keyword::MultiVector),
 	UniCase::ascii("NAMESPACE") => TokenKind::Keyword(Keyword::Namespace),
 	UniCase::ascii("NGRAM") => TokenKind::Keyword(Keyword::Ngram),
 	UniCase::ascii("NO") => TokenKind::Keyword(Keyword::No),
@@ -225,8 +239,13 @@ pub(crate) static KEYWORDS: phf::Map<UniCase<&'static str>, TokenKind> = phf_map
 	UniCase::ascii("POSTINGS_CACHE") => TokenKind::Keyword(Keyword::PostingsCache),
 	UniCase::ascii("POSTINGS_ORDER") => TokenKind::Keyword(Keyword::PostingsOrder),
 	UniCase::ascii("PREPARE") => TokenKind::Keyword(Keyword::Prepare),
+	UniCase::ascii("PROBES") => TokenKind::Keyword(Keyword::Probes),
+	UniCase::ascii("PRODUCT") => TokenKind::Keyword(Keyword::Product),
 	UniCase::ascii("PUNCT") => TokenKind::Keyword(Keyword::Punct),
 	UniCase::ascii("PURGE") => TokenKind::Keyword(Keyword::Purge),
+	UniCase::ascii("QUANTIZATION") => TokenKind::Keyword(Keyword::Quantization),
+	UniCase::ascii("QUERIES") => TokenKind::Keyword(Keyword::Queries),
+	UniCase::ascii("QUERY") => TokenKind::Keyword(Keyword::Query),
 	UniCase::ascii("RANGE") => TokenKind::Keyword(Keyword::Range),
 	UniCase::ascii("READONLY") => TokenKind::Keyword(Keyword::Readonly),
 	UniCase::ascii("REBUILD") => TokenKind::Keyword(Keyword::Rebuild),
@@ -237,6 +256,7 @@ pub(crate) static KEYWORDS: phf::Map<UniCase<&'static str>, TokenKind> = phf_map
 	UniCase::ascii("RELATION") => TokenKind::Keyword(Keyword::Relation),
 	UniCase::ascii("REMOVE") => TokenKind::Keyword(Keyword::Remove),
 	UniCase::ascii("REPLACE") => TokenKind::Keyword(Keyword::Replace),
+	UniCase::ascii("RESET") => TokenKind::Keyword(Keyword::Reset),
 	UniCase::ascii("RETRY") => TokenKind::Keyword(Keyword::Retry),
 	UniCase::ascii("RETURN") => TokenKind::Keyword(Keyword::Return),
 	UniCase::ascii("REVOKE") => TokenKind::Keyword(Keyword::Revoke),
@@ -244,6 +264,7 @@ pub(crate) static KEYWORDS: phf::Map<UniCase<&'static str>, TokenKind> = phf_map
 	UniCase::ascii("ROLES") => TokenKind::Keyword(Keyword::Roles),
 	UniCase::ascii("ROOT") => TokenKind::Keyword(Keyword::Root),
 	UniCase::ascii("SC") => TokenKind::Keyword(Keyword::Scope),
+	UniCase::ascii("SCALAR") => TokenKind::Keyword(Keyword::Scalar),
 	UniCase::ascii("SCHEMAFUL") => TokenKind::Keyword(Keyword::Schemafull),
 	UniCase::ascii("SCHEMAFULL") => TokenKind::Keyword(Keyword::Schemafull),
 	UniCase::ascii("SCHEMALESS") => TokenKind::Keyword(Keyword::Schemaless),
@@ -255,12 +276,17 @@ pub(crate) static KEYWORDS: phf::Map<UniCase<&'static str>, TokenKind> = phf_map
 	UniCase::ascii("SIGNIN") => TokenKind::Keyword(Keyword::Signin),
 	UniCase::ascii("SIGNUP") => TokenKind::Keyword(Keyword::Signup),
 	UniCase::ascii("SINCE") => TokenKind::Keyword(Keyword::Since),
+	UniCase::ascii("SINK") => TokenKind::Keyword(Keyword::Sink),
 	UniCase::ascii("SLEEP") => TokenKind::Keyword(Keyword::Sleep),
 	UniCase::ascii("SNOWBALL") => TokenKind::Keyword(Keyword::Snowball),
+	UniCase::ascii("SPATIAL") => TokenKind::Keyword(Keyword::Spatial),
 	UniCase::ascii("SPLIT") => TokenKind::Keyword(Keyword::Split),
 	UniCase::ascii("START") => TokenKind::Keyword(Keyword::Start),
+	UniCase::ascii("STATS") => TokenKind::Keyword(Keyword::Stats),
+	UniCase::ascii("STOPWORDS") => TokenKind::Keyword(Keyword::Stopwords),
 	UniCase::ascii("STRICT") => TokenKind::Keyword(Keyword::Strict),
 	UniCase::ascii("STRUCTURE") => TokenKind::Keyword(Keyword::Structure),
+	UniCase::ascii("SYNONYMS") => TokenKind::Keyword(Keyword::Synonyms),
 	UniCase::ascii("SYSTEM") => TokenKind::Keyword(Keyword::System),
 	UniCase::ascii("TABLE") => TokenKind::Keyword(Keyword::Table),
 	UniCase::ascii("TABLES") => TokenKind::Keyword(Keyword::Tables),
@@ -275,6 +301,7 @@ pub(crate) static KEYWORDS: phf::Map<UniCase<&'static str>, TokenKind> = phf_map
 	UniCase::ascii("TOKEN") => TokenKind::Keyword(Keyword::Token),
 	UniCase::ascii("TOKENIZERS") => TokenKind::Keyword(Keyword::Tokenizers),
 	UniCase::ascii("TRANSACTION") => TokenKind::Keyword(Keyword::Transaction),
+	UniCase::ascii("TRIGRAM") => TokenKind::Keyword(Keyword::Trigram),
 	UniCase::ascii("true") => TokenKind::Keyword(Keyword::True),
 	UniCase::ascii("TYPE") => TokenKind::Keyword(Keyword::Type),
 	UniCase::ascii("UNIQUE") => TokenKind::Keyword(Keyword::Unique),
@@ -289,9 +316,11 @@ pub(crate) static KEYWORDS: phf::Map<UniCase<&'static str>, TokenKind> = phf_map
 	UniCase::ascii("VALUES") => TokenKind::Keyword(Keyword::Values),
 	UniCase::ascii("VERSION") => TokenKind::Keyword(Keyword::Version),
 	UniCase::ascii("VS") => TokenKind::Keyword(Keyword::Vs),
+	UniCase::ascii("WEBHOOK") => TokenKind::Keyword(Keyword::Webhook),
 	UniCase::ascii("WHEN") => TokenKind::Keyword(Keyword::When),
 	UniCase::ascii("WHERE") => TokenKind::Keyword(Keyword::Where),
 	UniCase::ascii("WITH") => TokenKind::Keyword(Keyword::With),
+	UniCase::ascii("WORD") => TokenKind::Keyword(Keyword::Word),
 
 	// Types
 	UniCase::ascii("ANY") => TokenKind::Keyword(Keyword::Any),
@@ -156,6 +156,10 @@ pub(crate) static PATHS: phf::Map<
 		UniCase::ascii("file::rename_if_not_exists") => (PathKind::Function, None),
 		UniCase::ascii("file::exists") => (PathKind::Function, None),
 		UniCase::ascii("file::list") => (PathKind::Function, None),
+		UniCase::ascii("file::multipart::create") => (PathKind::Function, None),
+		UniCase::ascii("file::multipart::put") => (PathKind::Function, None),
+		UniCase::ascii("file::multipart::complete") => (PathKind::Function, None),
+		UniCase::ascii("file::multipart::abort") => (PathKind::Function, None),
 		//
 		UniCase::ascii("geo::area") => (PathKind::Function, None),
 		UniCase::ascii("geo::bearing") => (PathKind::Function, None),
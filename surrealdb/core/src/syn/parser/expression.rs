@@ -5,7 +5,9 @@ use surrealdb_types::ToSql;
 
 use super::enter_query_recursion;
 use super::mac::unexpected;
-use crate::sql::operator::{BindingPower, BooleanOperator, MatchesOperator, NearestNeighbor};
+use crate::sql::operator::{
+	BindingPower, BooleanOperator, Fuzzy, MatchesOperator, NearestNeighbor, VectorAggregation,
+};
 use crate::sql::{BinaryOperator, Expr, Literal, Part, PostfixOperator, PrefixOperator};
 use crate::syn::error::bail;
 use crate::syn::lexer::compound::Numeric;
@@ -317,7 +319,12 @@ impl Parser<'_> {
 				}
 				TokenKind::Digits => {
 					let ef = self.next_token_value()?;
-					NearestNeighbor::Approximate(amount, ef)
+					if self.eat(t!(",")) {
+						let aggregation = self.parse_vector_aggregation()?;
+						NearestNeighbor::MultiVector(amount, ef, aggregation)
+					} else {
+						NearestNeighbor::Approximate(amount, ef)
+					}
 				}
 				_ => {
 					bail!("Unexpected token {} expected a distance of an integer", token.kind,
@@ -338,6 +345,16 @@ impl Parser<'_> {
 		Ok(res)
 	}
 
+	fn parse_vector_aggregation(&mut self) -> ParseResult<VectorAggregation> {
+		let next = self.next();
+		match next.kind {
+			t!("MAXSIM") => Ok(VectorAggregation::MaxSim),
+			t!("MAX") => Ok(VectorAggregation::Max),
+			t!("MEAN") => Ok(VectorAggregation::Mean),
+			_ => unexpected!(self, next, "a vector aggregation (MAXSIM, MAX or MEAN)"),
+		}
+	}
+
 	/// Returns if an operator has a defined associativity.
 	/// For example: `a - b - c == (a - b) - c` so `-` is left associative.
 	/// However `a == b == c` is not defined to be either `(a == b) == c` nor `a == (b == c)`.
@@ -536,52 +553,58 @@ impl Parser<'_> {
 	}
 
 	fn parse_matches(&mut self) -> ParseResult<MatchesOperator> {
-		let peek = self.peek();
-		match peek.kind {
-			TokenKind::Digits => {
-				let number = self.next_token_value()?;
-				let op = if self.eat(t!(",")) {
-					let peek = self.next();
-					let op = match peek.kind {
-						t!("AND") => BooleanOperator::And,
-						t!("OR") => BooleanOperator::Or,
-						_ => unexpected!(self, peek, "either `AND` or `OR`"),
-					};
-					Some(op)
-				} else {
-					None
-				};
+		let mut res = MatchesOperator {
+			rf: None,
+			operator: None,
+			fuzzy: None,
+		};
+		if self.eat(t!("@")) {
+			return Ok(res);
+		}
+		if let TokenKind::Digits = self.peek().kind {
+			res.rf = Some(self.next_token_value()?);
+			if !self.eat(t!(",")) {
 				expected!(self, t!("@"));
-				Ok(MatchesOperator {
-					operator: op,
-					rf: Some(number),
-				})
+				return Ok(res);
 			}
-			t!("AND") => {
-				self.pop_peek();
-				expected!(self, t!("@"));
-				Ok(MatchesOperator {
-					operator: Some(BooleanOperator::And),
-					rf: None,
-				})
+		}
+		loop {
+			let peek = self.next();
+			match peek.kind {
+				t!("AND") if res.operator.is_none() => res.operator = Some(BooleanOperator::And),
+				t!("OR") if res.operator.is_none() => res.operator = Some(BooleanOperator::Or),
+				t!("FUZZY") if res.fuzzy.is_none() => res.fuzzy = Some(self.parse_fuzzy()?),
+				_ => unexpected!(self, peek, "a match reference, `AND`, `OR`, `FUZZY` or `@`"),
+			}
+			if !self.eat(t!(",")) {
+				break;
 			}
-			t!("OR") => {
-				self.pop_peek();
-				expected!(self, t!("@"));
-				Ok(MatchesOperator {
-					operator: Some(BooleanOperator::Or),
-					rf: None,
-				})
+		}
+		expected!(self, t!("@"));
+		Ok(res)
+	}
+
+	/// Parses the optional arguments of a `FUZZY` matches option:
+	/// `FUZZY`, `FUZZY(distance)` or `FUZZY(distance, prefix)`.
+	fn parse_fuzzy(&mut self) -> ParseResult<Fuzzy> {
+		let mut res = Fuzzy {
+			distance: crate::expr::operator::Fuzzy::DEFAULT_DISTANCE,
+			prefix: 0,
+		};
+		if self.peek().kind == t!("(") {
+			let open = self.pop_peek().span;
+			let token = self.peek();
+			res.distance = self.next_token_value()?;
+			if res.distance > crate::expr::operator::Fuzzy::MAX_DISTANCE {
+				bail!("Unsupported fuzzy edit distance {}", res.distance,
+					@token.span => "The maximum edit distance is {}", crate::expr::operator::Fuzzy::MAX_DISTANCE)
 			}
-			t!("@") => {
-				self.pop_peek();
-				Ok(MatchesOperator {
-					operator: None,
-					rf: None,
-				})
+			if self.eat(t!(",")) {
+				res.prefix = self.next_token_value()?;
 			}
-			_ => unexpected!(self, peek, "a match reference, operator or `@`"),
+			self.expect_closing_delimiter(t!(")"), open)?;
 		}
+		Ok(res)
 	}
 
 	async fn parse_postfix(
@@ -658,6 +658,8 @@ impl Parser<'_> {
 							t!("CAMEL") => Tokenizer::Camel,
 							t!("CLASS") => Tokenizer::Class,
 							t!("PUNCT") => Tokenizer::Punct,
+							t!("WORD") => Tokenizer::Word,
+							t!("CJK") => Tokenizer::Cjk,
 							_ => unexpected!(self, next, "a tokenizer"),
 						};
 						tokenizers.push(tokenizer);
@@ -704,6 +706,8 @@ impl Parser<'_> {
 								self.expect_closing_delimiter(t!(")"), open_span)?;
 								filters.push(Filter::Mapper(path));
 							}
+							t!("STOPWORDS") => filters.push(self.parse_stopwords_filter()?),
+							t!("SYNONYMS") => filters.push(self.parse_synonyms_filter()?),
 							_ => unexpected!(self, next, "a filter"),
 						}
 						if !self.eat(t!(",")) {
@@ -4,8 +4,8 @@ use crate::catalog::{ApiMethod, EventDefinition, EventKind};
 use crate::sql::access::AccessDuration;
 use crate::sql::access_type::JwtAccessVerify;
 use crate::sql::base::Base;
-use crate::sql::filter::Filter;
-use crate::sql::index::{Distance, HnswParams, VectorType};
+use crate::sql::filter::{Filter, Stopwords, Synonyms};
+use crate::sql::index::{Distance, HnswParams, IvfParams, VectorType};
 use crate::sql::kind::KindLiteral;
 use crate::sql::statements::define::config::api::{ApiConfig, Middleware};
 use crate::sql::statements::define::config::defaults::DefaultConfig;
@@ -13,16 +13,17 @@ use crate::sql::statements::define::config::graphql::{GraphQLConfig, TableConfig
 use crate::sql::statements::define::config::{ConfigInner, graphql};
 use crate::sql::statements::define::user::PassType;
 use crate::sql::statements::define::{
-	ApiAction, DefineAccessStatement, DefineAnalyzerStatement, DefineApiStatement,
-	DefineBucketStatement, DefineConfigStatement, DefineDatabaseStatement, DefineDefault,
-	DefineEventStatement, DefineFieldStatement, DefineFunctionStatement, DefineIndexStatement,
-	DefineKind, DefineNamespaceStatement, DefineParamStatement, DefineSequenceStatement,
-	DefineStatement, DefineTableStatement, DefineUserStatement,
+	ApiAction, BucketLifecycleRule, ConsumerSink, DefineAccessStatement, DefineAnalyzerStatement,
+	DefineApiStatement, DefineBucketStatement, DefineConfigStatement, DefineConsumerStatement,
+	DefineDatabaseStatement, DefineDefault, DefineEventStatement, DefineFieldStatement,
+	DefineFunctionStatement, DefineIndexStatement, DefineKind, DefineNamespaceStatement,
+	DefineParamStatement, DefineSequenceStatement, DefineStatement, DefineTableStatement,
+	DefineUserStatement,
 };
 use crate::sql::tokenizer::Tokenizer;
 use crate::sql::{
-	AccessType, DefineModuleStatement, Expr, Index, Kind, Literal, Param, Permission, Permissions,
-	Scoring, TableType, access_type, table_type,
+	AccessType, DefineModuleStatement, Expr, Index, Kind, Literal, Param, Part, Permission,
+	Permissions, Scoring, TableType, access_type, table_type,
 };
 #[cfg(feature = "surrealism")]
 use crate::sql::{ModuleExecutable, SiloExecutable, SurrealismExecutable};
@@ -64,6 +65,7 @@ impl Parser<'_> {
 			t!("CONFIG") => self.parse_define_config(stk).await.map(DefineStatement::Config),
 			t!("BUCKET") => self.parse_define_bucket(stk, next).await.map(DefineStatement::Bucket),
 			t!("SEQUENCE") => self.parse_define_sequence(stk).await.map(DefineStatement::Sequence),
+			t!("CONSUMER") => self.parse_define_consumer(stk).await.map(DefineStatement::Consumer),
 			t!("MODULE") => self.parse_define_module(stk).await.map(DefineStatement::Module),
 			_ => unexpected!(self, next, "a define statement keyword"),
 		}
@@ -1033,11 +1035,15 @@ impl Parser<'_> {
 			kind,
 			cols: Vec::new(),
 			index: Index::Idx,
+			include: Vec::new(),
+			cond: None,
 			comment: Expr::Literal(Literal::None),
 			concurrently: false,
 		};
 
 		let mut field_span = None;
+		let mut cond_span = None;
+		let mut include_span = None;
 
 		loop {
 			match self.peek_kind() {
@@ -1050,6 +1056,22 @@ impl Parser<'_> {
 					}
 					field_span = Some(self.last_span);
 				}
+				t!("INCLUDE") => {
+					let start = self.pop_peek().span;
+					loop {
+						let field = stk.run(|ctx| self.parse_expr_field(ctx)).await?;
+						let is_field = matches!(&field, Expr::Idiom(idiom)
+							if idiom.0.iter().all(|p| matches!(p, Part::Field(_))));
+						if !is_field {
+							bail!("Expected a field path to include", @self.last_span);
+						}
+						res.include.push(field);
+						if !self.eat(t!(",")) {
+							break;
+						}
+					}
+					include_span = Some(start.covers(self.last_span()));
+				}
 				t!("UNIQUE") => {
 					self.pop_peek();
 					res.index = Index::Uniq;
@@ -1059,6 +1081,19 @@ impl Parser<'_> {
 					let cond = self.try_parse_condition(stk).await?;
 					res.index = Index::Count(cond);
 				}
+				t!("SPATIAL") => {
+					self.pop_peek();
+					res.index = Index::Spatial;
+				}
+				t!("TRIGRAM") => {
+					self.pop_peek();
+					res.index = Index::Trigram;
+				}
+				t!("WHERE") => {
+					let start = self.peek().span;
+					res.cond = self.try_parse_condition(stk).await?;
+					cond_span = Some(start.covers(self.last_span()));
+				}
 				t!("FULLTEXT") => {
 					self.pop_peek();
 					let mut analyzer: Option<String> = None;
@@ -1112,6 +1147,8 @@ impl Parser<'_> {
 					let mut extend_candidates = false;
 					let mut keep_pruned_connections = false;
 					let mut use_hashed_vector = false;
+					let mut quantization = None;
+					let mut multi_vector = false;
 					loop {
 						match self.peek_kind() {
 							t!("DISTANCE") => {
@@ -1152,6 +1189,14 @@ impl Parser<'_> {
 								self.pop_peek();
 								use_hashed_vector = true;
 							}
+							t!("QUANTIZATION") => {
+								self.pop_peek();
+								quantization = Some(self.parse_quantization()?);
+							}
+							t!("MULTI_VECTOR") => {
+								self.pop_peek();
+								multi_vector = true;
+							}
 							_ => {
 								break;
 							}
@@ -1180,6 +1225,53 @@ impl Parser<'_> {
 						extend_candidates,
 						keep_pruned_connections,
 						use_hashed_vector,
+						quantization,
+						multi_vector,
+					});
+				}
+				t!("IVF") => {
+					self.pop_peek();
+					expected!(self, t!("DIMENSION"));
+					let dimension = self.next_token_value()?;
+					let mut distance = Distance::Euclidean;
+					let mut vector_type = VectorType::F32;
+					let mut lists = 100u16;
+					let mut probes = 10u16;
+					loop {
+						match self.peek_kind() {
+							t!("DISTANCE") => {
+								self.pop_peek();
+								distance = self.parse_distance()?;
+							}
+							t!("TYPE") => {
+								self.pop_peek();
+								vector_type = self.parse_vector_type()?;
+							}
+							t!("LISTS") => {
+								self.pop_peek();
+								lists = self.next_token_value()?;
+								if lists == 0 {
+									bail!("Invalid value for IVF parameter `LISTS`", @self.last_span() => "`LISTS` must be greater than 0")
+								}
+							}
+							t!("PROBES") => {
+								self.pop_peek();
+								probes = self.next_token_value()?;
+								if probes == 0 {
+									bail!("Invalid value for IVF parameter `PROBES`", @self.last_span() => "`PROBES` must be greater than 0")
+								}
+							}
+							_ => {
+								break;
+							}
+						}
+					}
+					res.index = Index::Ivf(IvfParams {
+						dimension,
+						distance,
+						vector_type,
+						lists,
+						probes,
 					});
 				}
 				t!("CONCURRENTLY") => {
@@ -1199,7 +1291,14 @@ impl Parser<'_> {
 					bail!("Cannot create a count index with fields", @field_span);
 				}
 			}
-			(field_span, Index::FullText(_) | Index::Hnsw(_)) => {
+			(
+				field_span,
+				Index::FullText(_)
+				| Index::Hnsw(_)
+				| Index::Ivf(_)
+				| Index::Spatial
+				| Index::Trigram,
+			) => {
 				if res.cols.len() != 1 {
 					if let Some(field_span) = field_span {
 						bail!("Expected one column, found {}", res.cols.len(), @field_span);
@@ -1215,6 +1314,16 @@ impl Parser<'_> {
 			}
 			(_, _) => {}
 		}
+		if let Some(cond_span) = cond_span
+			&& !matches!(res.index, Index::Uniq | Index::Idx)
+		{
+			bail!("Only standard and unique indexes can have a WHERE condition", @cond_span);
+		}
+		if let Some(include_span) = include_span
+			&& !matches!(res.index, Index::Uniq | Index::Idx)
+		{
+			bail!("Only standard and unique indexes can include fields", @include_span);
+		}
 		Ok(res)
 	}
 
@@ -1287,6 +1396,8 @@ impl Parser<'_> {
 								self.expect_closing_delimiter(t!(")"), open_span)?;
 								filters.push(Filter::Mapper(path))
 							}
+							t!("STOPWORDS") => filters.push(self.parse_stopwords_filter()?),
+							t!("SYNONYMS") => filters.push(self.parse_synonyms_filter()?),
 							_ => unexpected!(self, next, "a filter"),
 						}
 						if !self.eat(t!(",")) {
@@ -1306,6 +1417,8 @@ impl Parser<'_> {
 							t!("CAMEL") => Tokenizer::Camel,
 							t!("CLASS") => Tokenizer::Class,
 							t!("PUNCT") => Tokenizer::Punct,
+							t!("WORD") => Tokenizer::Word,
+							t!("CJK") => Tokenizer::Cjk,
 							_ => unexpected!(self, next, "a tokenizer"),
 						};
 						tokenizers.push(tokenizer);
@@ -1338,6 +1451,46 @@ impl Parser<'_> {
 		Ok(res)
 	}
 
+	/// Parses the arguments of a `STOPWORDS` filter, after the keyword
+	pub(super) fn parse_stopwords_filter(&mut self) -> ParseResult<Filter> {
+		let open_span = expected!(self, t!("(")).span;
+		let stopwords = match self.peek_kind() {
+			t!("[") => Stopwords::Words(self.parse_string_list()?),
+			t!("\"") | t!("'") => Stopwords::File(self.parse_string_lit()?),
+			_ => Stopwords::Language(self.next_token_value()?),
+		};
+		self.expect_closing_delimiter(t!(")"), open_span)?;
+		Ok(Filter::Stopwords(stopwords))
+	}
+
+	/// Parses the arguments of a `SYNONYMS` filter, after the keyword
+	pub(super) fn parse_synonyms_filter(&mut self) -> ParseResult<Filter> {
+		let open_span = expected!(self, t!("(")).span;
+		let synonyms = match self.peek_kind() {
+			t!("[") => Synonyms::Rules(self.parse_string_list()?),
+			_ => Synonyms::File(self.parse_string_lit()?),
+		};
+		self.expect_closing_delimiter(t!(")"), open_span)?;
+		Ok(Filter::Synonyms(synonyms))
+	}
+
+	/// Parses an array of string literals
+	fn parse_string_list(&mut self) -> ParseResult<Vec<String>> {
+		let open_span = expected!(self, t!("[")).span;
+		let mut res = Vec::new();
+		loop {
+			if self.eat(t!("]")) {
+				break;
+			}
+			res.push(self.parse_string_lit()?);
+			if !self.eat(t!(",")) {
+				self.expect_closing_delimiter(t!("]"), open_span)?;
+				break;
+			}
+		}
+		Ok(res)
+	}
+
 	pub(crate) async fn parse_define_bucket(
 		&mut self,
 		stk: &mut Stk,
@@ -1379,6 +1532,30 @@ impl Parser<'_> {
 					self.pop_peek();
 					res.readonly = true;
 				}
+				t!("MAXSIZE") => {
+					self.pop_peek();
+					res.max_size = Some(self.next_token_value::<u64>()?);
+				}
+				t!("MAXCOUNT") => {
+					self.pop_peek();
+					res.max_count = Some(self.next_token_value::<u64>()?);
+				}
+				t!("LIFECYCLE") => {
+					self.pop_peek();
+					res.lifecycle.clear();
+					loop {
+						let prefix = self.parse_string_lit()?;
+						expected!(self, t!("AFTER"));
+						let expiry = self.next_token_value::<PublicDuration>()?;
+						res.lifecycle.push(BucketLifecycleRule {
+							prefix,
+							expiry,
+						});
+						if !self.eat(t!(",")) {
+							break;
+						}
+					}
+				}
 				t!("COMMENT") => {
 					self.pop_peek();
 					res.comment = stk.run(|ctx| self.parse_expr_field(ctx)).await?;
@@ -1426,6 +1603,55 @@ impl Parser<'_> {
 		})
 	}
 
+	pub(crate) async fn parse_define_consumer(
+		&mut self,
+		stk: &mut Stk,
+	) -> ParseResult<DefineConsumerStatement> {
+		let kind = if self.eat(t!("IF")) {
+			expected!(self, t!("NOT"));
+			expected!(self, t!("EXISTS"));
+			DefineKind::IfNotExists
+		} else if self.eat(t!("OVERWRITE")) {
+			DefineKind::Overwrite
+		} else {
+			DefineKind::Default
+		};
+		let name = stk.run(|ctx| self.parse_expr_field(ctx)).await?;
+		expected!(self, t!("ON"));
+		self.eat(t!("TABLE"));
+		let target_table = stk.run(|ctx| self.parse_expr_table(ctx)).await?;
+		let mut res = DefineConsumerStatement {
+			kind,
+			name,
+			target_table,
+			comment: Expr::Literal(Literal::None),
+			sink: None,
+		};
+		loop {
+			match self.peek_kind() {
+				t!("COMMENT") => {
+					self.pop_peek();
+					res.comment = stk.run(|ctx| self.parse_expr_field(ctx)).await?;
+				}
+				t!("SINK") => {
+					self.pop_peek();
+					let next = self.next();
+					res.sink = Some(match next.kind {
+						t!("WEBHOOK") => {
+							ConsumerSink::Webhook(stk.run(|ctx| self.parse_expr_field(ctx)).await?)
+						}
+						t!("FILE") => {
+							ConsumerSink::File(stk.run(|ctx| self.parse_expr_field(ctx)).await?)
+						}
+						_ => unexpected!(self, next, "`WEBHOOK` or `FILE`"),
+					});
+				}
+				_ => break,
+			}
+		}
+		Ok(res)
+	}
+
 	pub(crate) async fn parse_define_config(
 		&mut self,
 		stk: &mut Stk,
@@ -9,10 +9,11 @@ use crate::sql::statements::access::{
 };
 use crate::sql::statements::live::LiveFields;
 use crate::sql::statements::rebuild::RebuildIndexStatement;
-use crate::sql::statements::show::ShowSince;
+use crate::sql::statements::show::{ShowSince, ShowTarget};
 use crate::sql::statements::{
-	ForeachStatement, InfoStatement, KillStatement, LiveStatement, OptionStatement,
-	OutputStatement, RebuildStatement, SetStatement, ShowStatement, SleepStatement, UseStatement,
+	AcknowledgeStatement, ForeachStatement, InfoStatement, KillStatement, LiveStatement,
+	OptionStatement, OutputStatement, RebuildStatement, SetStatement, ShowStatement,
+	SleepStatement, UseStatement,
 };
 use crate::sql::{AssignOperator, ExplainFormat, Expr, Literal, Param, TopLevelExpr};
 use crate::syn::lexer::compound;
@@ -111,8 +112,16 @@ impl Parser<'_> {
 			}
 			t!("SHOW") => {
 				self.pop_peek();
+				// `SHOW QUERIES` is an alias of `INFO FOR QUERIES`
+				if self.eat(t!("QUERIES")) {
+					return Ok(TopLevelExpr::Expr(Expr::Info(Box::new(InfoStatement::Queries))));
+				}
 				self.parse_show_stmt().map(TopLevelExpr::Show)
 			}
+			t!("ACKNOWLEDGE") => {
+				self.pop_peek();
+				self.parse_acknowledge_stmt().map(TopLevelExpr::Acknowledge)
+			}
 			_ => {
 				let covered = self.peek_kind() == t!("(");
 				let expr = self.parse_expr_start(stk).await?;
@@ -459,6 +468,24 @@ impl Parser<'_> {
 				let structure = self.eat(t!("STRUCTURE"));
 				InfoStatement::Index(index, table, structure)
 			}
+			t!("BUCKET") => {
+				if !self.settings.files_enabled {
+					unexpected!(self, next, "the experimental files feature to be enabled");
+				}
+				let bucket = stk.run(|stk| self.parse_expr_field(stk)).await?;
+				let structure = self.eat(t!("STRUCTURE"));
+				InfoStatement::Bucket(bucket, structure)
+			}
+			t!("CONSUMER") => {
+				let consumer = stk.run(|stk| self.parse_expr_field(stk)).await?;
+				let structure = self.eat(t!("STRUCTURE"));
+				InfoStatement::Consumer(consumer, structure)
+			}
+			t!("STATS") => {
+				let reset = self.eat(t!("RESET"));
+				InfoStatement::Stats(reset)
+			}
+			t!("QUERIES") => InfoStatement::Queries,
 			_ => unexpected!(self, next, "an info target"),
 		};
 
@@ -470,6 +497,7 @@ impl Parser<'_> {
 	/// # Parser State
 	/// Expects `KILL` to already be consumed.
 	pub(super) fn parse_kill_stmt(&mut self) -> ParseResult<KillStatement> {
+		let query = self.eat(t!("QUERY"));
 		let peek = self.peek();
 		let id = match peek.kind {
 			t!("u\"") | t!("u'") => {
@@ -480,6 +508,7 @@ impl Parser<'_> {
 		};
 		Ok(KillStatement {
 			id,
+			query,
 		})
 	}
 
@@ -609,37 +638,60 @@ impl Parser<'_> {
 		expected!(self, t!("FOR"));
 
 		let next = self.next();
-		let table = match next.kind {
-			t!("TABLE") => {
-				let table = self.parse_ident()?;
-				Some(table)
-			}
-			t!("DATABASE") => None,
-			_ => unexpected!(self, next, "`TABLE` or `DATABASE`"),
+		let target = match next.kind {
+			t!("TABLE") => ShowTarget::Table(self.parse_ident()?),
+			t!("DATABASE") => ShowTarget::Database,
+			t!("CONSUMER") => ShowTarget::Consumer(self.parse_ident()?),
+			_ => unexpected!(self, next, "`TABLE`, `DATABASE` or `CONSUMER`"),
 		};
 
-		expected!(self, t!("SINCE"));
-
-		let next = self.peek();
-		let since = match next.kind {
-			TokenKind::Digits => {
-				self.pop_peek();
-				let int = self.lex_compound(next, compound::integer)?.value;
-				ShowSince::Versionstamp(int)
-			}
-			t!("d\"") | t!("d'") => ShowSince::Timestamp(self.next_token_value()?),
-			_ => unexpected!(self, next, "a version stamp or a date-time"),
+		// A consumer resumes from its acknowledged position by default
+		let since = if matches!(target, ShowTarget::Consumer(_)) && self.peek_kind() != t!("SINCE")
+		{
+			None
+		} else {
+			expected!(self, t!("SINCE"));
+			let next = self.peek();
+			let since = match next.kind {
+				TokenKind::Digits => {
+					self.pop_peek();
+					let int = self.lex_compound(next, compound::integer)?.value;
+					ShowSince::Versionstamp(int)
+				}
+				t!("d\"") | t!("d'") => ShowSince::Timestamp(self.next_token_value()?),
+				_ => unexpected!(self, next, "a version stamp or a date-time"),
+			};
+			Some(since)
 		};
 
 		let limit = self.eat(t!("LIMIT")).then(|| self.next_token_value()).transpose()?;
 
 		Ok(ShowStatement {
-			table,
+			target,
 			since,
 			limit,
 		})
 	}
 
+	/// Parsers an ACKNOWLEDGE statement
+	///
+	/// # Parser State
+	/// Expects `ACKNOWLEDGE` to already be consumed.
+	pub(super) fn parse_acknowledge_stmt(&mut self) -> ParseResult<AcknowledgeStatement> {
+		expected!(self, t!("CONSUMER"));
+		let consumer = self.parse_ident()?;
+		expected!(self, t!("AT"));
+		let next = self.next();
+		let versionstamp = match next.kind {
+			TokenKind::Digits => self.lex_compound(next, compound::integer)?.value,
+			_ => unexpected!(self, next, "a version stamp"),
+		};
+		Ok(AcknowledgeStatement {
+			consumer,
+			versionstamp,
+		})
+	}
+
 	/// Parsers a SLEEP statement
 	///
 	/// # Parser State
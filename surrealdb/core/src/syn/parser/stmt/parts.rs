@@ -4,7 +4,7 @@ use reblessive::Stk;
 use surrealdb_types::ToSql;
 
 use crate::sql::changefeed::ChangeFeed;
-use crate::sql::index::{Distance, VectorType};
+use crate::sql::index::{Distance, Quantization, VectorType};
 use crate::sql::reference::{Reference, ReferenceDeleteStrategy};
 use crate::sql::{
 	Base, Cond, Data, Explain, Expr, Fetch, Fetchs, Field, Fields, Group, Groups, Idiom, Literal,
@@ -562,6 +562,22 @@ impl Parser<'_> {
 		}
 	}
 
+	pub(crate) fn parse_quantization(&mut self) -> ParseResult<Quantization> {
+		let next = self.next();
+		match next.kind {
+			t!("SCALAR") => Ok(Quantization::Scalar),
+			t!("BINARY") => Ok(Quantization::Binary),
+			t!("PRODUCT") => {
+				let m: u16 = self.next_token_value()?;
+				if m == 0 {
+					bail!("Invalid value for product quantization", @self.last_span() => "The number of sub-vectors must be greater than 0")
+				}
+				Ok(Quantization::Product(m))
+			}
+			_ => unexpected!(self, next, "a quantization"),
+		}
+	}
+
 	pub fn parse_custom_function_name(&mut self) -> ParseResult<String> {
 		expected!(self, t!("fn"));
 		expected!(self, t!("::"));
@@ -2,7 +2,7 @@ use reblessive::Stk;
 
 use crate::sql::statements::remove::{
 	RemoveAnalyzerStatement, RemoveApiStatement, RemoveBucketStatement, RemoveConfigKind,
-	RemoveConfigStatement, RemoveModuleStatement, RemoveSequenceStatement,
+	RemoveConfigStatement, RemoveConsumerStatement, RemoveModuleStatement, RemoveSequenceStatement,
 };
 use crate::sql::statements::{
 	RemoveAccessStatement, RemoveDatabaseStatement, RemoveEventStatement, RemoveFieldStatement,
@@ -272,6 +272,19 @@ impl Parser<'_> {
 					if_exists,
 				})
 			}
+			t!("CONSUMER") => {
+				let if_exists = if self.eat(t!("IF")) {
+					expected!(self, t!("EXISTS"));
+					true
+				} else {
+					false
+				};
+				let name = stk.run(|stk| self.parse_expr_field(stk)).await?;
+				RemoveStatement::Consumer(RemoveConsumerStatement {
+					name,
+					if_exists,
+				})
+			}
 			t!("USER") => {
 				let if_exists = if self.eat(t!("IF")) {
 					expected!(self, t!("EXISTS"));
@@ -1,5 +1,6 @@
 use chrono::offset::TimeZone;
 use chrono::{NaiveDate, Offset, Utc};
+use surrealdb_types::ToSql;
 
 use crate::catalog::EventKind;
 use crate::sql::access::AccessDuration;
@@ -10,8 +11,10 @@ use crate::sql::access_type::{
 use crate::sql::changefeed::ChangeFeed;
 use crate::sql::data::Assignment;
 use crate::sql::field::Selector;
-use crate::sql::filter::Filter;
-use crate::sql::index::{Distance, FullTextParams, HnswParams, VectorType};
+use crate::sql::filter::{Filter, Stopwords, Synonyms};
+use crate::sql::index::{
+	Distance, FullTextParams, HnswParams, IvfParams, Quantization, VectorType,
+};
 use crate::sql::language::Language;
 use crate::sql::literal::ObjectEntry;
 use crate::sql::lookup::{LookupKind, LookupSubject};
@@ -21,24 +24,24 @@ use crate::sql::statements::access::{
 };
 use crate::sql::statements::define::user::PassType;
 use crate::sql::statements::define::{
-	DefineAccessStatement, DefineAnalyzerStatement, DefineDatabaseStatement, DefineDefault,
-	DefineEventStatement, DefineFieldStatement, DefineFunctionStatement, DefineIndexStatement,
-	DefineKind, DefineNamespaceStatement, DefineParamStatement, DefineStatement,
-	DefineTableStatement,
+	ConsumerSink, DefineAccessStatement, DefineAnalyzerStatement, DefineConsumerStatement,
+	DefineDatabaseStatement, DefineDefault, DefineEventStatement, DefineFieldStatement,
+	DefineFunctionStatement, DefineIndexStatement, DefineKind, DefineNamespaceStatement,
+	DefineParamStatement, DefineStatement, DefineTableStatement,
 };
 use crate::sql::statements::live::LiveFields;
 use crate::sql::statements::remove::{
-	RemoveAnalyzerStatement, RemoveConfigKind, RemoveConfigStatement,
+	RemoveAnalyzerStatement, RemoveConfigKind, RemoveConfigStatement, RemoveConsumerStatement,
 };
-use crate::sql::statements::show::{ShowSince, ShowStatement};
+use crate::sql::statements::show::{ShowSince, ShowStatement, ShowTarget};
 use crate::sql::statements::sleep::SleepStatement;
 use crate::sql::statements::{
-	AccessStatement, CreateStatement, DeleteStatement, ForeachStatement, IfelseStatement,
-	InfoStatement, InsertStatement, KillStatement, OptionStatement, OutputStatement,
-	RelateStatement, RemoveAccessStatement, RemoveDatabaseStatement, RemoveEventStatement,
-	RemoveFieldStatement, RemoveFunctionStatement, RemoveIndexStatement, RemoveNamespaceStatement,
-	RemoveParamStatement, RemoveStatement, RemoveTableStatement, RemoveUserStatement,
-	SelectStatement, UpdateStatement, UpsertStatement, UseStatement,
+	AccessStatement, AcknowledgeStatement, CreateStatement, DeleteStatement, ForeachStatement,
+	IfelseStatement, InfoStatement, InsertStatement, KillStatement, OptionStatement,
+	OutputStatement, RelateStatement, RemoveAccessStatement, RemoveDatabaseStatement,
+	RemoveEventStatement, RemoveFieldStatement, RemoveFunctionStatement, RemoveIndexStatement,
+	RemoveNamespaceStatement, RemoveParamStatement, RemoveStatement, RemoveTableStatement,
+	RemoveUserStatement, SelectStatement, UpdateStatement, UpsertStatement, UseStatement,
 };
 use crate::sql::tokenizer::Tokenizer;
 use crate::sql::{
@@ -49,7 +52,7 @@ use crate::sql::{
 };
 use crate::syn;
 use crate::syn::parser::ParserSettings;
-use crate::types::{PublicDatetime, PublicDuration, PublicUuid};
+use crate::types::{PublicDatetime, PublicDuration, PublicFile, PublicUuid};
 use crate::val::range::TypedRange;
 
 fn ident_field(name: &str) -> Expr {
@@ -1632,6 +1635,65 @@ fn parse_define_event() {
 	)
 }
 
+#[test]
+fn parse_define_consumer() {
+	let res = syn::parse_with(
+		r#"DEFINE CONSUMER IF NOT EXISTS sync ON TABLE person COMMENT "search""#.as_bytes(),
+		async |parser, stk| parser.parse_expr_inherit(stk).await,
+	)
+	.unwrap();
+
+	assert_eq!(
+		res,
+		Expr::Define(Box::new(DefineStatement::Consumer(DefineConsumerStatement {
+			kind: DefineKind::IfNotExists,
+			name: Expr::Idiom(Idiom::field("sync".to_string())),
+			target_table: Expr::Table("person".to_string()),
+			comment: Expr::Literal(Literal::String("search".to_string())),
+			sink: None,
+		})))
+	);
+
+	let res = syn::parse_with(
+		r#"DEFINE CONSUMER audit ON person SINK WEBHOOK "https://example.com/cdc""#.as_bytes(),
+		async |parser, stk| parser.parse_expr_inherit(stk).await,
+	)
+	.unwrap();
+
+	assert_eq!(
+		res,
+		Expr::Define(Box::new(DefineStatement::Consumer(DefineConsumerStatement {
+			kind: DefineKind::Default,
+			name: Expr::Idiom(Idiom::field("audit".to_string())),
+			target_table: Expr::Table("person".to_string()),
+			comment: Expr::Literal(Literal::None),
+			sink: Some(ConsumerSink::Webhook(Expr::Literal(Literal::String(
+				"https://example.com/cdc".to_string()
+			)))),
+		})))
+	);
+
+	let res = syn::parse_with(
+		r#"DEFINE CONSUMER audit ON person SINK FILE f"cdc:/person" COMMENT "archive""#.as_bytes(),
+		async |parser, stk| parser.parse_expr_inherit(stk).await,
+	)
+	.unwrap();
+
+	assert_eq!(
+		res,
+		Expr::Define(Box::new(DefineStatement::Consumer(DefineConsumerStatement {
+			kind: DefineKind::Default,
+			name: Expr::Idiom(Idiom::field("audit".to_string())),
+			target_table: Expr::Table("person".to_string()),
+			comment: Expr::Literal(Literal::String("archive".to_string())),
+			sink: Some(ConsumerSink::File(Expr::Literal(Literal::File(PublicFile::new(
+				"cdc".to_string(),
+				"/person".to_string()
+			))))),
+		})))
+	);
+}
+
 #[test]
 fn parse_define_field() {
 	// General
@@ -1705,6 +1767,8 @@ fn parse_define_index() {
 					b: 0.2
 				},
 			}),
+			include: vec![],
+			cond: None,
 			comment: Expr::Literal(Literal::None),
 			concurrently: false
 		})))
@@ -1724,11 +1788,44 @@ fn parse_define_index() {
 			what: Expr::Table("table".to_string()),
 			cols: vec![Expr::Idiom(Idiom(vec![Part::Field("a".to_string())]))],
 			index: Index::Uniq,
+			include: vec![],
+			cond: None,
 			comment: Expr::Literal(Literal::None),
 			concurrently: false
 		})))
 	);
 
+	let res = syn::parse_with(
+		r#"DEFINE INDEX index ON TABLE table FIELDS a UNIQUE WHERE b = 1"#.as_bytes(),
+		async |parser, stk| parser.parse_expr_inherit(stk).await,
+	)
+	.unwrap();
+
+	assert_eq!(
+		res,
+		Expr::Define(Box::new(DefineStatement::Index(DefineIndexStatement {
+			kind: DefineKind::Default,
+			name: Expr::Idiom(Idiom::field("index".to_string())),
+			what: Expr::Table("table".to_string()),
+			cols: vec![Expr::Idiom(Idiom(vec![Part::Field("a".to_string())]))],
+			index: Index::Uniq,
+			include: vec![],
+			cond: Some(Cond(Expr::Binary {
+				left: Box::new(ident_field("b")),
+				op: BinaryOperator::Equal,
+				right: Box::new(Expr::Literal(Literal::Integer(1)))
+			})),
+			comment: Expr::Literal(Literal::None),
+			concurrently: false
+		})))
+	);
+
+	syn::parse_with(
+		r#"DEFINE INDEX index ON TABLE table FIELDS a SPATIAL WHERE b = 1"#.as_bytes(),
+		async |parser, stk| parser.parse_expr_inherit(stk).await,
+	)
+	.unwrap_err();
+
 	let res =
 		syn::parse_with( r#"DEFINE INDEX index ON TABLE table FIELDS a HNSW DIMENSION 128 EFC 250 TYPE F32 DISTANCE MANHATTAN M 6 M0 12 LM 0.5 EXTEND_CANDIDATES KEEP_PRUNED_CONNECTIONS HASHED_VECTOR"#.as_bytes(),async |parser,stk| parser.parse_expr_inherit(stk).await).unwrap();
 	assert_eq!(
@@ -1749,11 +1846,153 @@ fn parse_define_index() {
 				keep_pruned_connections: true,
 				ml: 0.5.into(),
 				use_hashed_vector: true,
+				quantization: None,
+				multi_vector: false,
+			}),
+			include: vec![],
+			cond: None,
+			comment: Expr::Literal(Literal::None),
+			concurrently: false
+		})))
+	);
+
+	let res =
+		syn::parse_with( r#"DEFINE INDEX index ON TABLE table FIELDS a HNSW DIMENSION 128 DISTANCE COSINE QUANTIZATION PRODUCT 16 MULTI_VECTOR"#.as_bytes(),async |parser,stk| parser.parse_expr_inherit(stk).await).unwrap();
+	assert_eq!(
+		res,
+		Expr::Define(Box::new(DefineStatement::Index(DefineIndexStatement {
+			kind: DefineKind::Default,
+			name: Expr::Idiom(Idiom::field("index".to_string())),
+			what: Expr::Table("table".to_string()),
+			cols: vec![Expr::Idiom(Idiom(vec![Part::Field("a".to_string())]))],
+			index: Index::Hnsw(HnswParams {
+				dimension: 128,
+				distance: Distance::Cosine,
+				vector_type: VectorType::F32,
+				m: 12,
+				m0: 24,
+				ef_construction: 150,
+				extend_candidates: false,
+				keep_pruned_connections: false,
+				ml: (1.0 / 12f64.ln()).into(),
+				use_hashed_vector: false,
+				quantization: Some(Quantization::Product(16)),
+				multi_vector: true,
+			}),
+			include: vec![],
+			cond: None,
+			comment: Expr::Literal(Literal::None),
+			concurrently: false
+		})))
+	);
+
+	let res = syn::parse_with(
+		r#"DEFINE INDEX index ON TABLE table FIELDS a SPATIAL"#.as_bytes(),
+		async |parser, stk| parser.parse_expr_inherit(stk).await,
+	)
+	.unwrap();
+
+	assert_eq!(
+		res,
+		Expr::Define(Box::new(DefineStatement::Index(DefineIndexStatement {
+			kind: DefineKind::Default,
+			name: Expr::Idiom(Idiom::field("index".to_string())),
+			what: Expr::Table("table".to_string()),
+			cols: vec![Expr::Idiom(Idiom(vec![Part::Field("a".to_string())]))],
+			index: Index::Spatial,
+			include: vec![],
+			cond: None,
+			comment: Expr::Literal(Literal::None),
+			concurrently: false
+		})))
+	);
+
+	let res = syn::parse_with(
+		r#"DEFINE INDEX index ON TABLE table FIELDS a TRIGRAM"#.as_bytes(),
+		async |parser, stk| parser.parse_expr_inherit(stk).await,
+	)
+	.unwrap();
+
+	assert_eq!(
+		res,
+		Expr::Define(Box::new(DefineStatement::Index(DefineIndexStatement {
+			kind: DefineKind::Default,
+			name: Expr::Idiom(Idiom::field("index".to_string())),
+			what: Expr::Table("table".to_string()),
+			cols: vec![Expr::Idiom(Idiom(vec![Part::Field("a".to_string())]))],
+			index: Index::Trigram,
+			include: vec![],
+			cond: None,
+			comment: Expr::Literal(Literal::None),
+			concurrently: false
+		})))
+	);
+
+	let res = syn::parse_with(
+		r#"DEFINE INDEX index ON TABLE table FIELDS a IVF DIMENSION 4 DIST EUCLIDEAN LISTS 2 PROBES 1"#.as_bytes(),
+		async |parser, stk| parser.parse_expr_inherit(stk).await,
+	)
+	.unwrap();
+
+	assert_eq!(
+		res,
+		Expr::Define(Box::new(DefineStatement::Index(DefineIndexStatement {
+			kind: DefineKind::Default,
+			name: Expr::Idiom(Idiom::field("index".to_string())),
+			what: Expr::Table("table".to_string()),
+			cols: vec![Expr::Idiom(Idiom(vec![Part::Field("a".to_string())]))],
+			index: Index::Ivf(IvfParams {
+				dimension: 4,
+				distance: Distance::Euclidean,
+				vector_type: VectorType::F32,
+				lists: 2,
+				probes: 1,
 			}),
+			include: vec![],
+			cond: None,
+			comment: Expr::Literal(Literal::None),
+			concurrently: false
+		})))
+	);
+
+	let res = syn::parse_with(
+		r#"DEFINE INDEX index ON TABLE table FIELDS a UNIQUE INCLUDE b, c.d"#.as_bytes(),
+		async |parser, stk| parser.parse_expr_inherit(stk).await,
+	)
+	.unwrap();
+
+	assert_eq!(
+		res,
+		Expr::Define(Box::new(DefineStatement::Index(DefineIndexStatement {
+			kind: DefineKind::Default,
+			name: Expr::Idiom(Idiom::field("index".to_string())),
+			what: Expr::Table("table".to_string()),
+			cols: vec![Expr::Idiom(Idiom(vec![Part::Field("a".to_string())]))],
+			index: Index::Uniq,
+			include: vec![
+				Expr::Idiom(Idiom(vec![Part::Field("b".to_string())])),
+				Expr::Idiom(Idiom(vec![
+					Part::Field("c".to_string()),
+					Part::Field("d".to_string())
+				])),
+			],
+			cond: None,
 			comment: Expr::Literal(Literal::None),
 			concurrently: false
 		})))
 	);
+
+	syn::parse_with(
+		r#"DEFINE INDEX index ON TABLE table FIELDS a TRIGRAM INCLUDE b"#.as_bytes(),
+		async |parser, stk| parser.parse_expr_inherit(stk).await,
+	)
+	.unwrap_err();
+
+	syn::parse_with(
+		r#"DEFINE INDEX index ON TABLE table FIELDS a INCLUDE b + 1"#.as_bytes(),
+		async |parser, stk| parser.parse_expr_inherit(stk).await,
+	)
+	.unwrap_err();
 }
 
 #[test]
@@ -1784,6 +2023,36 @@ fn parse_define_analyzer() {
 	)
 }
 
+#[test]
+fn parse_define_analyzer_stopwords_synonyms() {
+	let res = syn::parse_with(r#"DEFINE ANALYZER ana TOKENIZERS BLANK FILTERS LOWERCASE, STOPWORDS(ENGLISH), STOPWORDS(["a", 'the',]), STOPWORDS("/stop.txt"), SYNONYMS(["tv, television", "laptop => notebook"]), SYNONYMS('/syn.txt')"#.as_bytes(),async |parser,stk| parser. parse_expr_inherit(stk).await).unwrap();
+	assert_eq!(
+		res,
+		Expr::Define(Box::new(DefineStatement::Analyzer(DefineAnalyzerStatement {
+			kind: DefineKind::Default,
+			name: Expr::Idiom(Idiom::field("ana".to_string())),
+			tokenizers: Some(vec![Tokenizer::Blank]),
+			filters: Some(vec![
+				Filter::Lowercase,
+				Filter::Stopwords(Stopwords::Language(Language::English)),
+				Filter::Stopwords(Stopwords::Words(vec!["a".to_owned(), "the".to_owned()])),
+				Filter::Stopwords(Stopwords::File("/stop.txt".to_owned())),
+				Filter::Synonyms(Synonyms::Rules(vec![
+					"tv, television".to_owned(),
+					"laptop => notebook".to_owned()
+				])),
+				Filter::Synonyms(Synonyms::File("/syn.txt".to_owned())),
+			]),
+			comment: Expr::Literal(Literal::None),
+			function: None,
+		}))),
+	);
+	assert_eq!(
+		res.to_sql(),
+		"DEFINE ANALYZER ana TOKENIZERS BLANK FILTERS LOWERCASE, STOPWORDS(ENGLISH), STOPWORDS(['a', 'the']), STOPWORDS('/stop.txt'), SYNONYMS(['tv, television', 'laptop => notebook']), SYNONYMS('/syn.txt')"
+	);
+}
+
 #[test]
 fn parse_delete() {
 	let res = syn::parse_with("DELETE FROM ONLY |foo:32..64| WITH INDEX index,index_2 Where 2 RETURN AFTER TIMEOUT 1s EXPLAIN FULL".as_bytes(),async |parser,stk| parser. parse_expr_inherit(stk).await).unwrap();
@@ -1969,6 +2238,24 @@ fn parse_info() {
 			false
 		)))
 	);
+
+	let res = syn::parse_with("INFO FOR STATS".as_bytes(), async |parser, stk| {
+		parser.parse_expr_inherit(stk).await
+	})
+	.unwrap();
+	assert_eq!(res, Expr::Info(Box::new(InfoStatement::Stats(false))));
+
+	let res = syn::parse_with("INFO FOR STATS RESET".as_bytes(), async |parser, stk| {
+		parser.parse_expr_inherit(stk).await
+	})
+	.unwrap();
+	assert_eq!(res, Expr::Info(Box::new(InfoStatement::Stats(true))));
+
+	let res = syn::parse_with("INFO FOR QUERIES".as_bytes(), async |parser, stk| {
+		parser.parse_expr_inherit(stk).await
+	})
+	.unwrap();
+	assert_eq!(res, Expr::Info(Box::new(InfoStatement::Queries)));
 }
 
 #[test]
@@ -1982,8 +2269,8 @@ fn parse_show() {
 	assert_eq!(
 		res,
 		TopLevelExpr::Show(ShowStatement {
-			table: Some("foo".to_owned()),
-			since: ShowSince::Versionstamp(1),
+			target: ShowTarget::Table("foo".to_owned()),
+			since: Some(ShowSince::Versionstamp(1)),
 			limit: Some(10)
 		})
 	);
@@ -2011,11 +2298,69 @@ fn parse_show() {
 	assert_eq!(
 		res,
 		TopLevelExpr::Show(ShowStatement {
-			table: None,
-			since: ShowSince::Timestamp(PublicDatetime::from(expected_datetime)),
+			target: ShowTarget::Database,
+			since: Some(ShowSince::Timestamp(PublicDatetime::from(expected_datetime))),
 			limit: None
 		})
+	);
+
+	let res = syn::parse_with(r#"SHOW QUERIES"#.as_bytes(), async |parser, stk| {
+		parser.parse_top_level_expr(stk).await
+	})
+	.unwrap();
+	assert_eq!(res, TopLevelExpr::Expr(Expr::Info(Box::new(InfoStatement::Queries))));
+}
+
+#[test]
+fn parse_show_consumer() {
+	let res = syn::parse_with(
+		r#"SHOW CHANGES FOR CONSUMER sync LIMIT 10"#.as_bytes(),
+		async |parser, stk| parser.parse_top_level_expr(stk).await,
+	)
+	.unwrap();
+	assert_eq!(
+		res,
+		TopLevelExpr::Show(ShowStatement {
+			target: ShowTarget::Consumer("sync".to_owned()),
+			since: None,
+			limit: Some(10)
+		})
+	);
+
+	let res = syn::parse_with(
+		r#"SHOW CHANGES FOR CONSUMER sync SINCE 5"#.as_bytes(),
+		async |parser, stk| parser.parse_top_level_expr(stk).await,
 	)
+	.unwrap();
+	assert_eq!(
+		res,
+		TopLevelExpr::Show(ShowStatement {
+			target: ShowTarget::Consumer("sync".to_owned()),
+			since: Some(ShowSince::Versionstamp(5)),
+			limit: None
+		})
+	);
+
+	syn::parse_with(r#"SHOW CHANGES FOR TABLE foo LIMIT 10"#.as_bytes(), async |parser, stk| {
+		parser.parse_top_level_expr(stk).await
+	})
+	.unwrap_err();
+}
+
+#[test]
+fn parse_acknowledge() {
+	let res =
+		syn::parse_with(r#"ACKNOWLEDGE CONSUMER sync AT 42"#.as_bytes(), async |parser, stk| {
+			parser.parse_top_level_expr(stk).await
+		})
+		.unwrap();
+	assert_eq!(
+		res,
+		TopLevelExpr::Acknowledge(AcknowledgeStatement {
+			consumer: "sync".to_owned(),
+			versionstamp: 42,
+		})
+	);
 }
 
 #[test]
@@ -2222,7 +2567,8 @@ fn parse_kill() {
 	assert_eq!(
 		res,
 		TopLevelExpr::Kill(KillStatement {
-			id: Expr::Param(Param::new("param".to_owned()))
+			id: Expr::Param(Param::new("param".to_owned())),
+			query: false,
 		})
 	);
 
@@ -2239,7 +2585,26 @@ fn parse_kill() {
 		TopLevelExpr::Kill(KillStatement {
 			id: Expr::Literal(Literal::Uuid(PublicUuid::from(uuid::uuid!(
 				"e72bee20-f49b-11ec-b939-0242ac120002"
-			))))
+			)))),
+			query: false,
+		})
+	);
+
+	let res = syn::parse_with(
+		r#"KILL QUERY u"e72bee20-f49b-11ec-b939-0242ac120002" "#.as_bytes(),
+		async |parser, stk| parser.parse_query(stk).await,
+	)
+	.unwrap()
+	.expressions
+	.pop()
+	.unwrap();
+	assert_eq!(
+		res,
+		TopLevelExpr::Kill(KillStatement {
+			id: Expr::Literal(Literal::Uuid(PublicUuid::from(uuid::uuid!(
+				"e72bee20-f49b-11ec-b939-0242ac120002"
+			)))),
+			query: true,
 		})
 	);
 }
@@ -2500,6 +2865,19 @@ fn parse_remove() {
 		})))
 	);
 
+	let res =
+		syn::parse_with(r#"REMOVE CONSUMER IF EXISTS foo"#.as_bytes(), async |parser, stk| {
+			parser.parse_expr_inherit(stk).await
+		})
+		.unwrap();
+	assert_eq!(
+		res,
+		Expr::Remove(Box::new(RemoveStatement::Consumer(RemoveConsumerStatement {
+			name: Expr::Idiom(Idiom(vec![Part::Field("foo".to_string())])),
+			if_exists: true,
+		})))
+	);
+
 	let res = syn::parse_with(r#"REMOVE user foo on database"#.as_bytes(), async |parser, stk| {
 		parser.parse_expr_inherit(stk).await
 	})
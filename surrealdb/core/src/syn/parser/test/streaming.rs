@@ -22,7 +22,7 @@ use crate::sql::statements::define::{
 	DefineKind, DefineNamespaceStatement, DefineParamStatement, DefineStatement,
 	DefineTableStatement,
 };
-use crate::sql::statements::show::{ShowSince, ShowStatement};
+use crate::sql::statements::show::{ShowSince, ShowStatement, ShowTarget};
 use crate::sql::statements::sleep::SleepStatement;
 use crate::sql::statements::{
 	CreateStatement, DeleteStatement, ForeachStatement, IfelseStatement, InfoStatement,
@@ -341,6 +341,8 @@ fn statements() -> Vec<TopLevelExpr> {
 					b: 0.2,
 				},
 			}),
+			include: vec![],
+			cond: None,
 			comment: Expr::Literal(Literal::None),
 			concurrently: false,
 		})))),
@@ -350,6 +352,8 @@ fn statements() -> Vec<TopLevelExpr> {
 			what: Expr::Table("table".to_string()),
 			cols: vec![Expr::Idiom(Idiom(vec![Part::Field("a".to_string())]))],
 			index: Index::Uniq,
+			include: vec![],
+			cond: None,
 			comment: Expr::Literal(Literal::None),
 			concurrently: false,
 		})))),
@@ -538,13 +542,13 @@ fn statements() -> Vec<TopLevelExpr> {
 			kind: None,
 		}))),
 		TopLevelExpr::Show(ShowStatement {
-			table: Some("foo".to_owned()),
-			since: ShowSince::Versionstamp(1),
+			target: ShowTarget::Table("foo".to_owned()),
+			since: Some(ShowSince::Versionstamp(1)),
 			limit: Some(10),
 		}),
 		TopLevelExpr::Show(ShowStatement {
-			table: None,
-			since: ShowSince::Timestamp(PublicDatetime::from(expected_datetime)),
+			target: ShowTarget::Database,
+			since: Some(ShowSince::Timestamp(PublicDatetime::from(expected_datetime))),
 			limit: None,
 		}),
 		TopLevelExpr::Expr(Expr::Sleep(Box::new(SleepStatement {
@@ -588,6 +592,7 @@ fn statements() -> Vec<TopLevelExpr> {
 			id: Expr::Literal(Literal::Uuid(PublicUuid::from(uuid::uuid!(
 				"e72bee20-f49b-11ec-b939-0242ac120002"
 			)))),
+			query: false,
 		}),
 		TopLevelExpr::Expr(Expr::Return(Box::new(OutputStatement {
 			what: ident_field("RETRUN"),
@@ -62,6 +62,33 @@ fn parse_range_operator() {
 		.unwrap();
 }
 
+#[test]
+fn parse_matches_fuzzy() {
+	for (src, expected) in [
+		("t @FUZZY@ 'x'", "t @FUZZY(2)@ 'x'"),
+		("t @1,OR,FUZZY(1,2)@ 'x'", "t @1,OR,FUZZY(1,2)@ 'x'"),
+		("t @AND,FUZZY(0)@ 'x'", "t @FUZZY(0)@ 'x'"),
+		("t @FUZZY(1),OR@ 'x'", "t @OR,FUZZY(1)@ 'x'"),
+	] {
+		let v =
+			syn::parse_with(src.as_bytes(), async |parser, stk| parser.parse_expr_field(stk).await)
+				.unwrap();
+		let Expr::Binary {
+			op: BinaryOperator::Matches(op),
+			..
+		} = &v
+		else {
+			panic!("not a matches operator: {src}");
+		};
+		assert!(op.fuzzy.is_some());
+		assert_eq!(v.to_sql(), expected);
+	}
+	for src in ["t @FUZZY(3)@ 'x'", "t @FUZZY,FUZZY@ 'x'", "t @1 FUZZY@ 'x'"] {
+		syn::parse_with(src.as_bytes(), async |parser, stk| parser.parse_expr_field(stk).await)
+			.unwrap_err();
+	}
+}
+
 #[test]
 fn parse_large_depth_object() {
 	let mut text = String::new();
@@ -7,6 +7,7 @@ impl Parser<'_> {
 		matches!(
 			kind,
 			t!("ACCESS")
+				| t!("ACKNOWLEDGE")
 				| t!("ALTER")
 				| t!("BEGIN")
 				| t!("BREAK")
@@ -120,7 +121,8 @@ impl Parser<'_> {
 	pub(super) fn starts_disallowed_subquery_statement(kind: TokenKind) -> bool {
 		matches!(
 			kind,
-			t!("BEGIN")
+			t!("ACKNOWLEDGE")
+				| t!("BEGIN")
 				| t!("BREAK")
 				| t!("CANCEL")
 				| t!("COMMIT")
@@ -1,7 +1,7 @@
 macro_rules! keyword {
 	($($name:ident => $value:tt),* $(,)?) => {
 
-		#[repr(u8)]
+		#[repr(u16)]
 		#[derive(Clone, Copy, Eq, PartialEq, Hash, Debug)]
 		pub enum Keyword{
 			$($name,)*
@@ -25,6 +25,7 @@ macro_rules! keyword {
 
 keyword! {
 	Access => "ACCESS",
+	Acknowledge => "ACKNOWLEDGE",
 	After => "AFTER",
 	Algorithm => "ALGORITHM",
 	All => "ALL",
@@ -45,6 +46,7 @@ keyword! {
 	Bearer => "BEARER",
 	Before => "BEFORE",
 	Begin => "BEGIN",
+	Binary => "BINARY",
 	Blank => "BLANK",
 	Bucket => "BUCKET",
 	Reject => "REJECT",
@@ -56,6 +58,7 @@ keyword! {
 	Cascade => "CASCADE",
 	ChangeFeed => "CHANGEFEED",
 	Changes => "CHANGES",
+	Cjk => "CJK",
 	Capacity => "CAPACITY",
 	Class => "CLASS",
 	Comment => "COMMENT",
@@ -63,6 +66,7 @@ keyword! {
 	Compact => "COMPACT",
 	Concurrently => "CONCURRENTLY",
 	Config => "CONFIG",
+	Consumer => "CONSUMER",
 	Content => "CONTENT",
 	Continue => "CONTINUE",
 	Computed => "COMPUTED",
@@ -102,6 +106,7 @@ keyword! {
 	Fulltext => "FULLTEXT",
 	Function => "FUNCTION",
 	Functions => "FUNCTIONS",
+	Fuzzy => "FUZZY",
 	Grant => "GRANT",
 	Graphql => "GRAPHQL",
 	Group => "GROUP",
@@ -117,6 +122,7 @@ keyword! {
 	If => "IF",
 	Is => "IS",
 	Issuer => "ISSUER",
+	Ivf => "IVF",
 	Jwt => "JWT",
 	Jwks => "JWKS",
 	HashedVector => "HASHED_VECTOR",
@@ -125,17 +131,25 @@ keyword! {
 	Kill => "KILL",
 	Let => "LET",
 	Limit => "LIMIT",
+	Lists => "LISTS",
 	Live => "LIVE",
 	Lowercase => "LOWERCASE",
+	Lifecycle => "LIFECYCLE",
 	Lm => "LM",
 	M => "M",
 	M0 => "M0",
 	Mapper => "MAPPER",
+	Max => "MAX",
+	MaxCount => "MAXCOUNT",
 	MaxDepth => "MAXDEPTH",
+	MaxSim => "MAXSIM",
+	MaxSize => "MAXSIZE",
+	Mean => "MEAN",
 	Middleware => "MIDDLEWARE",
 	Merge => "MERGE",
 	Model => "MODEL",
 	Module => "MODULE",
+	MultiVector => "MULTI_VECTOR",
 	Namespace => "NAMESPACE",
 	Ngram => "NGRAM",
 	No => "NO",
@@ -159,8 +173,13 @@ keyword! {
 	PostingsCache => "POSTINGS_CACHE",
 	PostingsOrder => "POSTINGS_ORDER",
 	Prepare => "PREPARE",
+	Probes => "PROBES",
+	Product => "PRODUCT",
 	Punct => "PUNCT",
 	Purge => "PURGE",
+	Quantization => "QUANTIZATION",
+	Queries => "QUERIES",
+	Query => "QUERY",
 	Range => "RANGE",
 	Readonly => "READONLY",
 	Rebuild => "REBUILD",
@@ -171,12 +190,14 @@ keyword! {
 	Relation => "RELATION",
 	Remove => "REMOVE",
 	Replace => "REPLACE",
+	Reset => "RESET",
 	Retry => "RETRY",
 	Return => "RETURN",
 	Revoke => "REVOKE",
 	Revoked => "REVOKED",
 	Roles => "ROLES",
 	Root => "ROOT",
+	Scalar => "SCALAR",
 	Schemafull => "SCHEMAFULL",
 	Schemaless => "SCHEMALESS",
 	Scope => "SCOPE",
@@ -188,12 +209,17 @@ keyword! {
 	Signin => "SIGNIN",
 	Signup => "SIGNUP",
 	Since => "SINCE",
+	Sink => "SINK",
 	Sleep => "SLEEP",
 	Snowball => "SNOWBALL",
+	Spatial => "SPATIAL",
 	Split => "SPLIT",
 	Start => "START",
+	Stats => "STATS",
+	Stopwords => "STOPWORDS",
 	Strict => "STRICT",
 	Structure => "STRUCTURE",
+	Synonyms => "SYNONYMS",
 	System => "SYSTEM",
 	Table => "TABLE",
 	Tables => "TABLES",
@@ -207,6 +233,7 @@ keyword! {
 	Token => "TOKEN",
 	To => "TO",
 	Transaction => "TRANSACTION",
+	Trigram => "TRIGRAM",
 	True => "true",
 	Type => "TYPE",
 	Unique => "UNIQUE",
@@ -221,9 +248,11 @@ keyword! {
 	Values => "VALUES",
 	Version => "VERSION",
 	Vs => "VS",
+	Webhook => "WEBHOOK",
 	When => "WHEN",
 	Where => "WHERE",
 	With => "WITH",
+	Word => "WORD",
 	AllInside => "ALLINSIDE",
 	AndKw => "ANDKW",
 	AnyInside => "ANYINSIDE",
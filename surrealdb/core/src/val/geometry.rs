@@ -14,6 +14,7 @@ use surrealdb_types::{SqlFormat, ToSql, write_sql};
 
 use super::Object;
 use crate::fmt::Fmt;
+use crate::kvs::impl_kv_value_revisioned;
 use crate::val::{Array, Value};
 
 #[revisioned(revision = 1)]
@@ -30,6 +31,8 @@ pub(crate) enum Geometry {
 	// Add new variants here
 }
 
+impl_kv_value_revisioned!(Geometry);
+
 impl Geometry {
 	/// Check if this is not a Collection
 	pub fn is_geometry(&self) -> bool {
@@ -0,0 +1,154 @@
+use std::pin::Pin;
+
+use anyhow::{Result, bail, ensure};
+use bytes::Bytes;
+use clap::Args;
+use futures::{Stream, StreamExt, TryStreamExt};
+use reqwest::{Body, Client, RequestBuilder, Url};
+use surrealdb::headers::{BACKUP_VERSION, DB, NS};
+use tokio::fs::File;
+use tokio::io::{self, AsyncWrite, AsyncWriteExt};
+use tokio_util::io::ReaderStream;
+
+use crate::cli::abstraction::auth::CredentialsLevel;
+use crate::cli::abstraction::{AuthArguments, LevelSelectionArguments};
+
+type ByteStream = Pin<Box<dyn Stream<Item = Result<Bytes>> + Send>>;
+
+#[derive(Args, Debug)]
+pub struct BackupCommandArguments {
+	#[arg(help = "Database endpoint to back up, or path to the backup file to restore")]
+	#[arg(long_help = "Database endpoint to back up, or path to the backup file to restore.
+Use dash - to read the backup from stdin.")]
+	#[arg(index = 1)]
+	from: String,
+	#[arg(help = "Path to the backup file to write, or database endpoint to restore into")]
+	#[arg(long_help = "Path to the backup file to write, or database endpoint to restore into.
+Use dash - to write the backup into stdout. When a bucket is specified, this is the key
+of the file in the bucket which the server writes the backup into.")]
+	#[arg(index = 2)]
+	#[arg(default_value = "-")]
+	into: String,
+	#[arg(help = "Only back up the changes since the snapshot version of a previous backup")]
+	#[arg(long)]
+	since: Option<u64>,
+	#[arg(help = "Have the server write the backup into a file in this bucket")]
+	#[arg(long, requires_all = ["namespace", "database"])]
+	bucket: Option<String>,
+	#[command(flatten)]
+	level: LevelSelectionArguments,
+	#[command(flatten)]
+	auth: AuthArguments,
+}
+
+/// Checks if the argument refers to a remote database endpoint
+fn is_endpoint(v: &str) -> bool {
+	v.starts_with("http://") || v.starts_with("https://")
+}
+
+pub async fn init(
+	BackupCommandArguments {
+		from,
+		into,
+		since,
+		bucket,
+		level: LevelSelectionArguments {
+			namespace,
+			database,
+		},
+		auth: AuthArguments {
+			username,
+			password,
+			token,
+			auth_level,
+		},
+	}: BackupCommandArguments,
+) -> Result<()> {
+	// Backups cover the entire datastore, so require root access
+	ensure!(
+		matches!(auth_level, CredentialsLevel::Root),
+		"Backups can only be created or restored by root users"
+	);
+	ensure!(
+		is_endpoint(&from) || is_endpoint(&into),
+		"Either the source or the destination of a backup must be a database endpoint"
+	);
+	ensure!(
+		since.is_none() || is_endpoint(&from),
+		"An incremental backup can only be created from a database endpoint"
+	);
+	ensure!(
+		bucket.is_none() || (is_endpoint(&from) && !is_endpoint(&into) && into != "-"),
+		"A backup can only be written into a bucket from a database endpoint, with the key of the file as the destination"
+	);
+	// Attach any credentials to the requests
+	let client = Client::new();
+	let authenticate = |req: RequestBuilder| match (&username, &password, &token) {
+		(Some(user), pass, _) => req.basic_auth(user, pass.as_ref()),
+		(None, _, Some(token)) => req.bearer_auth(token),
+		_ => req,
+	};
+	// Have the server write the backup into a bucket
+	if let Some(bucket) = bucket {
+		let mut url = Url::parse(&format!("{}/sync", from.trim_end_matches('/')))?;
+		url.query_pairs_mut().append_pair("bucket", &bucket).append_pair("key", &into);
+		if let Some(since) = since {
+			url.query_pairs_mut().append_pair("since", &since.to_string());
+		}
+		let mut req = authenticate(client.get(url));
+		if let (Some(ns), Some(db)) = (&namespace, &database) {
+			req = req.header(&NS, ns).header(&DB, db);
+		}
+		let res = req.send().await?.error_for_status()?;
+		if let Some(version) = res.headers().get(&BACKUP_VERSION) {
+			info!("Created a backup at snapshot version {}", version.to_str()?);
+		}
+		info!("The backup was written to '{into}' in bucket '{bucket}'");
+		return Ok(());
+	}
+	// Open the source of the backup
+	let stream: ByteStream = if is_endpoint(&from) {
+		let url = match since {
+			Some(since) => format!("{}/sync?since={since}", from.trim_end_matches('/')),
+			None => format!("{}/sync", from.trim_end_matches('/')),
+		};
+		let res = authenticate(client.get(url)).send().await?.error_for_status()?;
+		if let Some(version) = res.headers().get(&BACKUP_VERSION) {
+			info!("Creating a backup at snapshot version {}", version.to_str()?);
+		}
+		Box::pin(res.bytes_stream().map_err(anyhow::Error::new))
+	} else if from == "-" {
+		Box::pin(ReaderStream::new(io::stdin()).map_err(anyhow::Error::new))
+	} else {
+		Box::pin(ReaderStream::new(File::open(&from).await?).map_err(anyhow::Error::new))
+	};
+	// Write the backup to the destination
+	if is_endpoint(&into) {
+		let url = format!("{}/sync", into.trim_end_matches('/'));
+		authenticate(client.post(url))
+			.body(Body::wrap_stream(stream))
+			.send()
+			.await?
+			.error_for_status()?;
+		info!("The backup was restored successfully");
+	} else if into == "-" {
+		write(stream, io::stdout()).await?;
+	} else {
+		write(stream, File::create(&into).await?).await?;
+		info!("The backup was written to '{into}'");
+	}
+	// All ok
+	Ok(())
+}
+
+/// Writes the backup stream into a file or stdout
+async fn write<W: AsyncWrite + Unpin>(mut stream: ByteStream, mut output: W) -> Result<()> {
+	while let Some(bytes) = stream.next().await {
+		match bytes {
+			Ok(bytes) => output.write_all(&bytes).await?,
+			Err(e) => bail!("The backup could not be completed: {e}"),
+		}
+	}
+	output.flush().await?;
+	Ok(())
+}
@@ -44,13 +44,19 @@ impl ConfigCheckRequirements for CommunityComposer {}
 
 #[derive(Clone, Debug)]
 pub struct Config {
-	pub bind: SocketAddr,
+	/// The TCP address to listen for connections on
+	pub bind: Option<SocketAddr>,
+	/// The path of the Unix domain socket to listen for connections on
+	pub unix_socket: Option<PathBuf>,
+	/// The file permissions of the Unix domain socket
+	pub unix_socket_mode: u32,
 	pub path: String,
 	pub client_ip: ClientIp,
 	pub user: Option<String>,
 	pub pass: Option<String>,
 	pub crt: Option<PathBuf>,
 	pub key: Option<PathBuf>,
+	pub encryption_key: Option<String>,
 	pub engine: EngineOptions,
 	pub no_identification_headers: bool,
 	pub allow_origin: Vec<String>,
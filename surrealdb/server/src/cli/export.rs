@@ -4,7 +4,7 @@ use futures_util::StreamExt;
 use surrealdb::Connection;
 use surrealdb::engine::any::{self, connect};
 use surrealdb::method::{Export, ExportConfig};
-use surrealdb_core::kvs::export::TableConfig;
+use surrealdb_core::kvs::export::{ExportFormat, TableConfig};
 use tokio::io::{self, AsyncWriteExt};
 
 use crate::cli::abstraction::auth::{CredentialsBuilder, CredentialsLevel};
@@ -64,11 +64,14 @@ struct ExportConfigArguments {
 	/// Whether configs should be exported
 	#[arg(long, num_args = 0..=1, default_missing_value = "true")]
 	configs: Option<bool>,
+	/// The format of the export, either sql or binary
+	#[arg(long, value_parser = super::validator::export_format)]
+	format: Option<ExportFormat>,
 }
 
 #[derive(Args, Debug)]
 pub struct ExportCommandArguments {
-	#[arg(help = "Path to the SurrealQL file to export. Use dash - to write into stdout.")]
+	#[arg(help = "Path to the export file. Use dash - to write into stdout.")]
 	#[arg(default_value = "-")]
 	#[arg(index = 1)]
 	file: String,
@@ -153,7 +156,7 @@ pub async fn init(
 	} else {
 		apply_config(config, client.export(file)).await?;
 	}
-	info!("The SurrealQL file was exported successfully");
+	info!("The export file was written successfully");
 	// Everything OK
 	Ok(())
 }
@@ -227,5 +230,9 @@ fn apply_config<C: Connection, R>(
 		export = export.configs(value);
 	}
 
+	if let Some(value) = config.format {
+		export = export.format(value);
+	}
+
 	export
 }
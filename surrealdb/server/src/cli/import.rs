@@ -18,7 +18,7 @@ pub struct DatabaseConnectionArguments {
 
 #[derive(Args, Debug)]
 pub struct ImportCommandArguments {
-	#[arg(help = "Path to the SurrealQL file to import")]
+	#[arg(help = "Path to the SurrealQL or binary export file to import")]
 	#[arg(index = 1)]
 	file: String,
 	#[command(flatten)]
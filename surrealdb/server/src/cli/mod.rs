@@ -1,6 +1,7 @@
 #![allow(deprecated)]
 
 pub(crate) mod abstraction;
+mod backup;
 mod config;
 mod export;
 mod fix;
@@ -9,6 +10,7 @@ mod isready;
 mod ml;
 #[cfg(feature = "surrealism")]
 mod module;
+mod rekey;
 #[cfg(feature = "cli")]
 mod sql;
 mod start;
@@ -26,6 +28,7 @@ use std::process::ExitCode;
 use std::time::Duration;
 
 use anyhow::Result;
+use backup::BackupCommandArguments;
 use clap::{Parser, Subcommand, ValueEnum};
 pub use config::{Config, ConfigCheck, ConfigCheckRequirements};
 use export::ExportCommandArguments;
@@ -35,6 +38,7 @@ use isready::IsReadyCommandArguments;
 use ml::MlCommand;
 #[cfg(feature = "surrealism")]
 use module::ModuleCommand;
+use rekey::RekeyCommandArguments;
 use semver::Version;
 #[cfg(feature = "cli")]
 use sql::SqlCommandArguments;
@@ -161,10 +165,8 @@ struct Cli {
 enum Commands {
 	#[command(about = "Start the database server")]
 	Start(StartCommandArguments),
-	/* Not implemented yet
 	#[command(about = "Backup data to or from an existing database")]
 	Backup(BackupCommandArguments),
-	*/
 	#[command(about = "Import a SurrealQL script into an existing database")]
 	Import(ImportCommandArguments),
 	#[command(about = "Export an existing database as a SurrealQL script")]
@@ -190,6 +192,8 @@ enum Commands {
 	Validate(ValidateCommandArguments),
 	#[command(about = "Fix database storage issues")]
 	Fix(FixCommandArguments),
+	#[command(about = "Change the encryption key of an encrypted database")]
+	Rekey(RekeyCommandArguments),
 	#[command(about = "Run commands in version 2 of the database for backwards compatibility")]
 	V2(V2Commands),
 }
@@ -269,6 +273,11 @@ pub async fn init<
 	}
 	// Check if we are running the server
 	let server = matches!(args.command, Commands::Start(_));
+	// Check if we are exposing the Prometheus metrics endpoint
+	let prometheus = match &args.command {
+		Commands::Start(args) => args.metrics_endpoint(),
+		_ => false,
+	};
 	// Initialize opentelemetry and logging
 	let telemetry = crate::telemetry::builder()
 		.with_log_level("info")
@@ -283,12 +292,14 @@ pub async fn init<
 		.with_file_path(Some(args.log_file_path.clone()))
 		.with_file_name(Some(args.log_file_name.clone()))
 		.with_file_format(args.log_file_format)
-		.with_file_rotation(Some(args.log_file_rotation.as_str().to_string()));
+		.with_file_rotation(Some(args.log_file_rotation.as_str().to_string()))
+		.with_prometheus(prometheus);
 	// Extract the telemetry log guards
 	let guards = telemetry.init().expect("Unable to configure logs");
 	// After version warning we can run the respective command
 	let output = match args.command {
 		Commands::Start(args) => start::init::<C>(composer, args).await,
+		Commands::Backup(args) => backup::init(args).await,
 		Commands::Import(args) => import::init(args).await,
 		Commands::Export(args) => export::init(args).await,
 		Commands::Version(args) => version::init(args).await,
@@ -301,6 +312,7 @@ pub async fn init<
 		Commands::IsReady(args) => isready::init(args).await,
 		Commands::Validate(args) => validate::init(args).await,
 		Commands::Fix(args) => fix::init::<C>(args).await,
+		Commands::Rekey(args) => rekey::init::<C>(composer, args).await,
 		Commands::V2(args) => v2::init(args).await,
 	};
 	// Save the flamegraph and profile
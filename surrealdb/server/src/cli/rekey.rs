@@ -0,0 +1,56 @@
+use anyhow::Result;
+use clap::Args;
+use surrealdb::engine::any;
+use surrealdb_core::buc::BucketStoreProvider;
+use surrealdb_core::kvs::{Datastore, TransactionBuilderFactory};
+use tokio_util::sync::CancellationToken;
+
+#[derive(Args, Debug)]
+pub struct RekeyCommandArguments {
+	#[arg(help = "Database path used for storing data")]
+	#[arg(env = "SURREAL_PATH", index = 1)]
+	path: String,
+	#[arg(help = "The current encryption key of the database")]
+	#[arg(env = "SURREAL_KEY", short = 'k', long = "key")]
+	#[arg(value_parser = super::validator::key_valid)]
+	key: String,
+	#[arg(help = "The new encryption key of the database")]
+	#[arg(env = "SURREAL_NEW_KEY", long = "new-key")]
+	#[arg(value_parser = super::validator::key_valid)]
+	new_key: String,
+}
+
+/// Change the encryption key of an encrypted datastore.
+///
+/// This runs offline, against the storage path directly, so the database
+/// server must not be running while the key is changed.
+pub async fn init<C: TransactionBuilderFactory + BucketStoreProvider>(
+	composer: C,
+	RekeyCommandArguments {
+		path,
+		key,
+		new_key,
+	}: RekeyCommandArguments,
+) -> Result<()> {
+	// Check the path is valid
+	C::path_valid(&path)?;
+	// Clean the path
+	let endpoint = any::__into_endpoint(path)?;
+	let path = if endpoint.path.is_empty() {
+		endpoint.url.to_string()
+	} else {
+		endpoint.path
+	};
+	// Open the datastore
+	let datastore =
+		Datastore::new_with_factory::<C>(composer, &path, CancellationToken::new()).await?;
+	// Wrap the data key with the new encryption key
+	let res = datastore.rotate_encryption_key(&key, &new_key).await;
+	// Shutdown the datastore
+	datastore.shutdown().await?;
+	res?;
+	// Log output ok
+	println!("The encryption key was changed");
+	// All ok
+	Ok(())
+}
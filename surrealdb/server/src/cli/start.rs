@@ -1,5 +1,6 @@
 use std::net::SocketAddr;
 use std::path::PathBuf;
+use std::str::FromStr;
 use std::sync::Arc;
 use std::time::Duration;
 
@@ -31,10 +32,9 @@ pub struct StartCommandArguments {
 	#[arg(env = "SURREAL_NO_BANNER", long)]
 	#[arg(default_value_t = false)]
 	no_banner: bool,
-	#[arg(help = "Encryption key to use for on-disk encryption")]
+	#[arg(help = "Encryption key to use for on-disk encryption (RocksDB and SurrealKV only)")]
 	#[arg(env = "SURREAL_KEY", short = 'k', long = "key")]
 	#[arg(value_parser = super::validator::key_valid)]
-	#[arg(hide = true)] // Not currently in use
 	key: Option<String>,
 	//
 	// Tasks
@@ -72,6 +72,20 @@ pub struct StartCommandArguments {
 	#[arg(env = "SURREAL_ASYNC_EVENT_PROCESSING_INTERVAL", long = "async-event-interval", value_parser = super::validator::duration)]
 	#[arg(default_value = "5s")]
 	event_processing_interval: Duration,
+	#[arg(
+		help = "The interval at which to delete files which have expired according to bucket lifecycle rules",
+		help_heading = "Database"
+	)]
+	#[arg(env = "SURREAL_BUCKET_LIFECYCLE_INTERVAL", long = "bucket-lifecycle-interval", value_parser = super::validator::duration)]
+	#[arg(default_value = "60s")]
+	bucket_lifecycle_interval: Duration,
+	#[arg(
+		help = "The interval at which to deliver changefeed entries to the sinks of changefeed consumers",
+		help_heading = "Database"
+	)]
+	#[arg(env = "SURREAL_CHANGEFEED_SINK_INTERVAL", long = "changefeed-sink-interval", value_parser = super::validator::duration)]
+	#[arg(default_value = "5s")]
+	changefeed_sink_interval: Duration,
 	//
 	// Authentication
 	#[arg(
@@ -112,10 +126,16 @@ pub struct StartCommandArguments {
 	#[arg(env = "SURREAL_CLIENT_IP", long)]
 	#[arg(default_value = "socket", value_enum)]
 	client_ip: ClientIp,
-	#[arg(help = "The hostname or IP address to listen for connections on")]
+	#[arg(
+		help = "The hostname or IP address to listen for connections on, or a Unix domain socket path prefixed with 'unix:'"
+	)]
 	#[arg(env = "SURREAL_BIND", short = 'b', long = "bind")]
 	#[arg(default_value = "127.0.0.1:8000")]
-	listen_addresses: Vec<SocketAddr>,
+	listen_addresses: Vec<ListenAddress>,
+	#[arg(help = "The file permissions, in octal, of the Unix domain socket")]
+	#[arg(env = "SURREAL_UNIX_SOCKET_MODE", long = "unix-socket-mode")]
+	#[arg(default_value = "600", value_parser = super::validator::unix_socket_mode)]
+	unix_socket_mode: u32,
 	#[arg(help = "Whether to suppress the server name and version headers")]
 	#[arg(env = "SURREAL_NO_IDENTIFICATION_HEADERS", long)]
 	#[arg(default_value_t = false)]
@@ -124,6 +144,12 @@ pub struct StartCommandArguments {
 	#[arg(env = "SURREAL_ALLOW_ORIGIN", long = "allow-origin")]
 	#[arg(value_delimiter = ',', value_parser = super::validator::cors_origin)]
 	allow_origin: Vec<String>,
+	#[arg(
+		help = "Whether to expose server metrics in the Prometheus format on the /metrics route"
+	)]
+	#[arg(env = "SURREAL_METRICS_ENDPOINT", long = "metrics-endpoint")]
+	#[arg(default_value_t = false)]
+	metrics_endpoint: bool,
 	//
 	// Database options
 	#[command(flatten)]
@@ -131,6 +157,36 @@ pub struct StartCommandArguments {
 	dbs: StartCommandDbsOptions,
 }
 
+impl StartCommandArguments {
+	/// Whether the Prometheus metrics endpoint should be enabled
+	pub(crate) fn metrics_endpoint(&self) -> bool {
+		self.metrics_endpoint
+	}
+}
+
+/// An address which the HTTP server listens for connections on
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(crate) enum ListenAddress {
+	/// A TCP socket address
+	Tcp(SocketAddr),
+	/// The path of a Unix domain socket
+	Unix(PathBuf),
+}
+
+impl FromStr for ListenAddress {
+	type Err = String;
+
+	fn from_str(v: &str) -> Result<Self, Self::Err> {
+		match v.strip_prefix("unix:") {
+			Some("") => Err(String::from("Provide a path for the Unix domain socket")),
+			Some(path) => Ok(Self::Unix(PathBuf::from(path))),
+			None => {
+				v.parse().map(Self::Tcp).map_err(|e| format!("Invalid listen address '{v}': {e}"))
+			}
+		}
+	}
+}
+
 #[derive(Args, Debug)]
 #[group(requires_all = ["kvs_ca", "kvs_crt", "kvs_key"], multiple = true)]
 struct StartCommandRemoteTlsOptions {
@@ -178,6 +234,7 @@ pub async fn init<
 		password: pass,
 		client_ip,
 		listen_addresses,
+		unix_socket_mode,
 		dbs,
 		web,
 		node_membership_refresh_interval,
@@ -186,7 +243,10 @@ pub async fn init<
 		changefeed_gc_interval,
 		index_compaction_interval,
 		event_processing_interval,
+		bucket_lifecycle_interval,
+		changefeed_sink_interval,
 		no_banner,
+		key: encryption_key,
 		no_identification_headers,
 		allow_origin,
 		..
@@ -220,13 +280,28 @@ pub async fn init<
 		.with_node_membership_cleanup_interval(node_membership_cleanup_interval)
 		.with_changefeed_gc_interval(changefeed_gc_interval)
 		.with_index_compaction_interval(index_compaction_interval)
-		.with_event_processing_interval(event_processing_interval);
+		.with_event_processing_interval(event_processing_interval)
+		.with_bucket_lifecycle_interval(bucket_lifecycle_interval)
+		.with_changefeed_sink_interval(changefeed_sink_interval);
 	// Configure the config
-	let Some(bind) = listen_addresses.first().copied() else {
+	let bind = listen_addresses.iter().find_map(|v| match v {
+		ListenAddress::Tcp(addr) => Some(*addr),
+		ListenAddress::Unix(_) => None,
+	});
+	let unix_socket = listen_addresses.into_iter().find_map(|v| match v {
+		ListenAddress::Tcp(_) => None,
+		ListenAddress::Unix(path) => Some(path),
+	});
+	if bind.is_none() && unix_socket.is_none() {
 		return Err(anyhow::anyhow!("No listen address provided"));
-	};
+	}
+	if cfg!(not(unix)) && unix_socket.is_some() {
+		return Err(anyhow::anyhow!("Unix domain sockets are not supported on this platform"));
+	}
 	let config = Config {
 		bind,
+		unix_socket,
+		unix_socket_mode,
 		client_ip,
 		path,
 		user,
@@ -236,6 +311,7 @@ pub async fn init<
 		engine,
 		crt,
 		key,
+		encryption_key,
 	};
 	composer.check_config(&config).await?;
 	// Setup the command-line options
@@ -6,7 +6,7 @@ use surrealdb_core::dbs::capabilities::{
 	ArbitraryQueryTarget, ExperimentalTarget, FuncTarget, MethodTarget, NetTarget, RouteTarget,
 	Targets,
 };
-use surrealdb_core::kvs::export::{ExcludedTables, TableConfig};
+use surrealdb_core::kvs::export::{ExcludedTables, ExportFormat, TableConfig};
 use surrealdb_types::Duration;
 
 pub(crate) mod parser;
@@ -186,6 +186,21 @@ pub(crate) fn export_tables_exclude(value: &str) -> Result<TableConfig, String>
 	}))
 }
 
+pub(crate) fn export_format(value: &str) -> Result<ExportFormat, String> {
+	match value {
+		"sql" | "surql" => Ok(ExportFormat::Sql),
+		"binary" => Ok(ExportFormat::Binary),
+		_ => Err(String::from("Provide a valid export format: sql or binary")),
+	}
+}
+
+pub(crate) fn unix_socket_mode(value: &str) -> Result<u32, String> {
+	match u32::from_str_radix(value, 8) {
+		Ok(mode) if mode <= 0o777 => Ok(mode),
+		_ => Err(String::from("Provide valid octal file permissions, for example 600 or 660")),
+	}
+}
+
 #[cfg(test)]
 mod tests {
 	use super::*;
@@ -296,4 +311,14 @@ mod tests {
 			)
 		);
 	}
+
+	#[test]
+	fn test_unix_socket_mode() {
+		assert_eq!(unix_socket_mode("600").unwrap(), 0o600);
+		assert_eq!(unix_socket_mode("0660").unwrap(), 0o660);
+		assert!(unix_socket_mode("777").is_ok());
+		assert!(unix_socket_mode("1777").is_err());
+		assert!(unix_socket_mode("800").is_err());
+		assert!(unix_socket_mode("rw").is_err());
+	}
 }
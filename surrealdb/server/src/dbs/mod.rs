@@ -77,6 +77,10 @@ pub struct StartCommandDbsOptions {
 	#[arg(env = "SURREAL_NO_DEFAULTS", long = "no-defaults", conflicts_with_all = ["default_namespace", "default_database"])]
 	#[arg(default_value_t = false)]
 	no_defaults: bool,
+	#[arg(help = "Whether to allow backups of an encrypted datastore, which are not encrypted")]
+	#[arg(env = "SURREAL_ALLOW_UNENCRYPTED_BACKUPS", long = "allow-unencrypted-backups")]
+	#[arg(default_value_t = false)]
+	allow_unencrypted_backups: bool,
 }
 
 #[derive(Args, Debug)]
@@ -735,6 +739,7 @@ pub async fn init<C: TransactionBuilderFactory + BucketStoreProvider>(
 		default_namespace,
 		default_database,
 		no_defaults,
+		allow_unencrypted_backups,
 	}: StartCommandDbsOptions,
 ) -> Result<Datastore> {
 	// Warn about the strict mode flag being unused.
@@ -779,6 +784,9 @@ pub async fn init<C: TransactionBuilderFactory + BucketStoreProvider>(
 	// Parse and setup the desired kv datastore
 	let dbs = Datastore::new_with_factory::<C>(composer, &opt.path, canceller)
 		.await?
+		.with_encryption_key(opt.encryption_key.as_deref())
+		.await?
+		.with_unencrypted_backups(allow_unencrypted_backups)
 		.with_notifications()
 		.with_query_timeout(query_timeout)
 		.with_transaction_timeout(transaction_timeout)
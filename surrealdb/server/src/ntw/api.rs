@@ -1,16 +1,22 @@
-use axum::body::Bytes;
+use axum::body::{Body, Bytes};
 use axum::extract::{DefaultBodyLimit, Path, Query};
-use axum::http::{HeaderMap, Method};
-use axum::response::IntoResponse;
+use axum::http::header::{
+	ACCEPT_RANGES, CONTENT_LENGTH, CONTENT_RANGE, CONTENT_TYPE, ETAG, EXPECT, RANGE,
+};
+use axum::http::{HeaderMap, HeaderValue, Method, StatusCode};
+use axum::response::{IntoResponse, Response};
 use axum::routing::any;
 use axum::{Extension, Router};
+use bytes::BytesMut;
+use futures::{StreamExt, TryStreamExt};
 use surrealdb_core::api::err::ApiError;
 use surrealdb_core::api::request::ApiRequest;
+use surrealdb_core::buc::store::{ByteStream, ObjectRange};
 use surrealdb_core::catalog::ApiMethod;
 use surrealdb_core::dbs::Session;
 use surrealdb_core::dbs::capabilities::RouteTarget;
-use surrealdb_types::Value;
-use tower_http::limit::RequestBodyLimitLayer;
+use surrealdb_core::kvs::Datastore;
+use surrealdb_types::{File, Value};
 use uuid::Uuid;
 
 use super::AppState;
@@ -26,7 +32,6 @@ where
 	Router::new()
 		.route("/api/{ns}/{db}/{*path}", any(handler))
 		.route_layer(DefaultBodyLimit::disable())
-		.layer(RequestBodyLimitLayer::new(*HTTP_MAX_API_BODY_SIZE))
 }
 
 async fn handler(
@@ -38,8 +43,8 @@ async fn handler(
 		inner: query,
 	}): Query<Params>,
 	method: Method,
-	body: Bytes,
-) -> Result<impl IntoResponse, ApiHandlerError> {
+	body: Body,
+) -> Result<Response, ApiHandlerError> {
 	// Generate request ID at the start so it can be passed back for ALL errors and included in
 	// warns
 	let request_id = Uuid::new_v4().to_string();
@@ -88,15 +93,25 @@ async fn handler(
 		}
 	};
 
-	// TODO we need to get a max body size back somehow. Introduction of blob like value? This
-	// stream somehow needs to be postponed... also if such a value would be introduced then this
-	// whole enum can be eliminated. body would always just simply be a value maybe bytes could
-	// have two variants, consumed and unconsumed. To the user its simply bytes, but whenever an api
-	// request is processed, the body would be unconsumed bytes, and whenever we get a file, that
-	// too could be unconsumed bytes. When the user actually does something with them, they get
-	// consumed, but to the user its always simply just bytes. we could expose handlebars to
-	// describe the "internal state" of the value...
-	let body = Value::Bytes(body.into());
+	// A request which expects a `100 Continue` response is an upload. The
+	// handler is invoked before the body is read, and the body is only read
+	// if the handler directs it into a file. Any other body is buffered, up
+	// to the maximum API body size.
+	let upload = matches!(method, ApiMethod::Post | ApiMethod::Put | ApiMethod::Patch)
+		&& headers.get(EXPECT).is_some_and(|v| v.as_bytes().eq_ignore_ascii_case(b"100-continue"));
+	let size = headers
+		.get(CONTENT_LENGTH)
+		.and_then(|v| v.to_str().ok())
+		.and_then(|v| v.parse::<u64>().ok());
+	let range = headers.get(RANGE).and_then(parse_range);
+	let (body, upload) = if upload {
+		(Value::None, Some(body))
+	} else {
+		let body = read_body(body, size)
+			.await
+			.map_err(|e| ApiHandlerError(e.into(), request_id.clone()))?;
+		(Value::Bytes(body.into()), None)
+	};
 
 	let req = ApiRequest {
 		method,
@@ -122,14 +137,28 @@ async fn handler(
 		status = %res.status,
 		"API handler completed"
 	);
-	let res_body = match res.body {
-		Value::None => Vec::new(),
-		Value::Bytes(x) => x.into_inner().to_vec(),
-		Value::String(s) => s.into_bytes(),
+	let res_body = match (res.body, upload) {
+		// A file returned by an upload handler receives the request body
+		(Value::File(file), Some(body)) if res.status.is_success() => {
+			let data: ByteStream = Box::pin(body.into_data_stream().map_err(|e| e.to_string()));
+			ds.put_file_stream(&session, &file, size, data)
+				.await
+				.map_err(|e| ApiHandlerError(ResponseError(e), request_id.clone()))?;
+			Body::empty()
+		}
+		// Any other file is streamed as the response body
+		(Value::File(file), _) => {
+			return send_file(ds, &session, &file, res.status, res.headers, range)
+				.await
+				.map_err(|e| ApiHandlerError(e, request_id));
+		}
+		(Value::None, _) => Body::empty(),
+		(Value::Bytes(x), _) => Body::from(x.into_inner()),
+		(Value::String(s), _) => Body::from(s),
 		_ => {
 			return Err(ApiHandlerError(
 				ApiError::InvalidApiResponse(
-					"HTTP API response body must be None, bytes, or string; other values are not supported".into(),
+					"HTTP API response body must be None, bytes, string, or a file; other values are not supported".into(),
 				)
 				.into(),
 				request_id,
@@ -137,5 +166,86 @@ async fn handler(
 		}
 	};
 
-	Ok((res.status, res.headers, res_body))
+	Ok((res.status, res.headers, res_body).into_response())
+}
+
+/// Reads a request body into memory, up to the maximum API body size.
+async fn read_body(body: Body, size: Option<u64>) -> Result<Bytes, NetError> {
+	let limit = *HTTP_MAX_API_BODY_SIZE;
+	if size.is_some_and(|size| size > limit as u64) {
+		return Err(NetError::PayloadTooLarge(limit));
+	}
+	let mut stream = body.into_data_stream();
+	let mut data = BytesMut::new();
+	while let Some(chunk) = stream.next().await {
+		let chunk = chunk.map_err(|_| NetError::Request)?;
+		if data.len() + chunk.len() > limit {
+			return Err(NetError::PayloadTooLarge(limit));
+		}
+		data.extend_from_slice(&chunk);
+	}
+	Ok(data.freeze())
+}
+
+/// Parses a `Range` header with a single `bytes=start-end` or `bytes=start-`
+/// range. Any other range, including suffix ranges and multiple ranges, is
+/// ignored, in which case the whole file is sent.
+fn parse_range(value: &HeaderValue) -> Option<ObjectRange> {
+	let (start, end) = value.to_str().ok()?.trim().strip_prefix("bytes=")?.split_once('-')?;
+	let offset = start.trim().parse::<u64>().ok()?;
+	let length = match end.trim() {
+		"" => None,
+		end => Some(end.parse::<u64>().ok()?.checked_sub(offset)?.checked_add(1)?),
+	};
+	Some(ObjectRange {
+		offset,
+		length,
+	})
+}
+
+/// Streams a file returned by an API handler as the response body.
+///
+/// When a range was requested, only the bytes within the range are sent,
+/// with a `206 Partial Content` status in place of a successful status.
+async fn send_file(
+	ds: &Datastore,
+	session: &Session,
+	file: &File,
+	status: StatusCode,
+	mut headers: HeaderMap,
+	range: Option<ObjectRange>,
+) -> Result<Response, ResponseError> {
+	let range = range.filter(|_| status == StatusCode::OK);
+	let Some((meta, stream)) =
+		ds.get_file_stream(session, file, range).await.map_err(ResponseError)?
+	else {
+		return Err(ApiError::NotFound.into());
+	};
+	headers.insert(ACCEPT_RANGES, HeaderValue::from_static("bytes"));
+	if let Some(content_type) = meta.content_type.as_deref().and_then(|v| v.parse().ok()) {
+		headers.entry(CONTENT_TYPE).or_insert(content_type);
+	}
+	if let Some(etag) = meta.etag.as_deref() {
+		let etag = format!("\"{}\"", etag.trim_matches('"'));
+		if let Ok(etag) = HeaderValue::try_from(etag) {
+			headers.entry(ETAG).or_insert(etag);
+		}
+	}
+	let (status, length) = match range {
+		None => (status, meta.size),
+		// A range which starts beyond the end of the file can not be satisfied
+		Some(range) if range.offset >= meta.size => {
+			let value = format!("bytes */{}", meta.size);
+			headers.insert(CONTENT_RANGE, HeaderValue::try_from(value)?);
+			return Ok((StatusCode::RANGE_NOT_SATISFIABLE, headers).into_response());
+		}
+		Some(range) => {
+			let range = range.clamp(meta.size);
+			let value = format!("bytes {}-{}/{}", range.start, range.end - 1, meta.size);
+			headers.insert(CONTENT_RANGE, HeaderValue::try_from(value)?);
+			(StatusCode::PARTIAL_CONTENT, range.end - range.start)
+		}
+	};
+	headers.insert(CONTENT_LENGTH, HeaderValue::from(length));
+	Ok((status, headers, Body::from_stream(stream)).into_response())
 }
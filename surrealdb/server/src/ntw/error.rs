@@ -16,6 +16,9 @@ pub enum Error {
 	#[error("The request body contains invalid data")]
 	Request,
 
+	#[error("The request body exceeds the maximum size of {0} bytes")]
+	PayloadTooLarge(usize),
+
 	#[error("There was a problem with authentication")]
 	InvalidAuth,
 
@@ -73,6 +76,14 @@ impl IntoResponse for Error {
 					information: Some(self.to_string()),
 				}.into_response()
 			}
+			Error::PayloadTooLarge(_) => {
+				ErrorMessage {
+					code: StatusCode::PAYLOAD_TOO_LARGE,
+					details: Some("Payload too large".to_string()),
+					description: Some("The request body is larger than this endpoint accepts.".to_string()),
+					information: Some(self.to_string()),
+				}.into_response()
+			}
 			Error::InvalidStorage =>
 				ErrorMessage {
 					code: StatusCode::INTERNAL_SERVER_ERROR,
@@ -0,0 +1,45 @@
+use axum::response::IntoResponse;
+use axum::routing::get;
+use axum::{Extension, Router};
+use http::header::CONTENT_TYPE;
+use surrealdb_core::dbs::Session;
+use surrealdb_core::dbs::capabilities::RouteTarget;
+use surrealdb_core::iam::Action::View;
+use surrealdb_core::iam::ResourceKind::Any;
+
+use super::AppState;
+use super::error::ResponseError;
+use crate::ntw::error::Error as NetError;
+use crate::telemetry::metrics::prometheus;
+
+pub fn router<S>() -> Router<S>
+where
+	S: Clone + Send + Sync + 'static,
+{
+	Router::new().route("/metrics", get(handler))
+}
+
+async fn handler(
+	Extension(state): Extension<AppState>,
+	Extension(session): Extension<Session>,
+) -> Result<impl IntoResponse, ResponseError> {
+	// The route only exists when enabled at startup
+	if !prometheus::enabled() {
+		return Err(NetError::NotFound("/metrics".to_string()).into());
+	}
+	// Get the datastore reference
+	let db = &state.datastore;
+	// Check if capabilities allow querying the requested HTTP route
+	if !db.allows_http_route(&RouteTarget::Metrics) {
+		warn!(
+			"Capabilities denied HTTP route request attempt, target: '{}'",
+			&RouteTarget::Metrics
+		);
+		return Err(NetError::ForbiddenRoute(RouteTarget::Metrics.to_string()).into());
+	}
+	// Check the permissions level
+	db.check(&session, View, Any.on_root()).map_err(ResponseError)?;
+	// Collect the metrics in the Prometheus text format
+	let body = prometheus::render().map_err(ResponseError)?;
+	Ok(([(CONTENT_TYPE, "text/plain; version=0.0.4; charset=utf-8")], body))
+}
@@ -10,6 +10,7 @@ pub mod health;
 pub mod import;
 mod input;
 pub mod key;
+pub mod metrics;
 pub mod ml;
 pub(crate) mod output;
 mod params;
@@ -20,6 +21,8 @@ pub mod signup;
 pub mod sql;
 pub mod sync;
 mod tracer;
+#[cfg(unix)]
+mod unix;
 pub mod version;
 
 use std::io;
@@ -137,6 +140,7 @@ impl RouterFactory for CommunityComposer {
 			.merge(signin::router())
 			.merge(signup::router())
 			.merge(key::router())
+			.merge(metrics::router())
 			.merge(ml::router())
 			.merge(api::router());
 
@@ -497,24 +501,79 @@ pub async fn init<F: RouterFactory>(
 	// Get a new server handler
 	let handle = Handle::new();
 
+	// Get a token to stop accepting connections on the Unix domain socket
+	let unix_shutdown = CancellationToken::new();
+
 	// Setup the graceful shutdown handler
-	let shutdown_handler =
-		graceful_shutdown(surreal.rpc_state().clone(), surreal.canceller().clone(), handle.clone());
+	let shutdown_handler = graceful_shutdown(
+		surreal.rpc_state().clone(),
+		surreal.canceller().clone(),
+		handle.clone(),
+		unix_shutdown.clone(),
+	);
 
 	// Spawn the notification delivery task
 	surreal.spawn_notifications();
 
+	// Clone the cancellation token
+	#[cfg_attr(not(unix), expect(unused_variables))]
+	let canceller = surreal.canceller().clone();
+
 	// Extract the router for serving
 	let axum_app = surreal.into_router();
 
-	// If a certificate and key are specified, then setup TLS
-	let res = if let (Some(cert), Some(key)) = (&opt.crt, &opt.key) {
+	// Serve the router over TCP
+	let tcp = async {
+		let Some(bind) = opt.bind else {
+			return Ok(());
+		};
+		let res = serve_tcp(opt, bind, axum_app.clone(), handle).await;
+		// Catch the error and try to provide some guidance
+		if let Err(e) = &res
+			&& bind.port() < 1024
+			&& let io::ErrorKind::PermissionDenied = e.kind()
+		{
+			error!(target: LOG, "Binding to ports below 1024 requires privileged access or special permissions.");
+		}
+		res
+	};
+
+	// Serve the router over a Unix domain socket
+	#[cfg(unix)]
+	let unix = async {
+		let Some(path) = &opt.unix_socket else {
+			return Ok(());
+		};
+		unix::serve(path, opt.unix_socket_mode, axum_app.clone(), unix_shutdown, canceller).await
+	};
+	#[cfg(not(unix))]
+	let unix = async { io::Result::Ok(()) };
+
+	// Run both servers until they have stopped
+	tokio::try_join!(tcp, unix)?;
+	// Wait for the shutdown to finish
+	let _ = shutdown_handler.await;
+	// Log the server shutdown to the CLI
+	info!(target: LOG, "Web server stopped. Bye!");
+
+	Ok(())
+}
+
+/// Serve the router over TCP on the given address, using TLS if a
+/// certificate and key are specified.
+async fn serve_tcp(
+	opt: &Config,
+	bind: SocketAddr,
+	axum_app: Router,
+	handle: Handle<SocketAddr>,
+) -> io::Result<()> {
+	if let (Some(cert), Some(key)) = (&opt.crt, &opt.key) {
 		// Configure certificate and private key used by https
 		let tls = RustlsConfig::from_pem_file(cert, key).await?;
 		// Setup the Axum server with TLS
-		let server = axum_server::bind_rustls(opt.bind, tls);
+		let server = axum_server::bind_rustls(bind, tls);
 		// Log the server startup to the CLI
-		info!(target: LOG, "Started web server on {}", &opt.bind);
+		info!(target: LOG, "Started web server on {}", &bind);
 		// Start the server and listen for connections
 		server
 			.handle(handle)
@@ -522,28 +581,13 @@ pub async fn init<F: RouterFactory>(
 			.await
 	} else {
 		// Setup the Axum server
-		let server = axum_server::bind(opt.bind);
+		let server = axum_server::bind(bind);
 		// Log the server startup to the CLI
-		info!(target: LOG, "Started web server on {}", &opt.bind);
+		info!(target: LOG, "Started web server on {}", &bind);
 		// Start the server and listen for connections
 		server
 			.handle(handle)
 			.serve(axum_app.into_make_service_with_connect_info::<SocketAddr>())
 			.await
-	};
-	// Catch the error and try to provide some guidance
-	if let Err(e) = res {
-		if opt.bind.port() < 1024
-			&& let io::ErrorKind::PermissionDenied = e.kind()
-		{
-			error!(target: LOG, "Binding to ports below 1024 requires privileged access or special permissions.");
-		}
-		return Err(e.into());
 	}
-	// Wait for the shutdown to finish
-	let _ = shutdown_handler.await;
-	// Log the server shutdown to the CLI
-	info!(target: LOG, "Web server stopped. Bye!");
-
-	Ok(())
 }
@@ -10,7 +10,7 @@ use crate::rpc::{self, RpcState};
 use crate::telemetry;
 
 /// Start a graceful shutdown:
-/// * Signal the Axum Handle when a shutdown signal is received.
+/// * Signal the Axum Handle and the Unix domain socket server when a shutdown signal is received.
 /// * Stop all WebSocket connections.
 /// * Flush all telemetry data.
 ///
@@ -19,6 +19,7 @@ pub fn graceful_shutdown(
 	state: Arc<RpcState>,
 	canceller: CancellationToken,
 	http_handle: Handle<SocketAddr>,
+	unix_shutdown: CancellationToken,
 ) -> JoinHandle<()> {
 	// Spawn a new background asynchronous task
 	tokio::spawn(async move {
@@ -42,6 +43,7 @@ pub fn graceful_shutdown(
 			tokio::spawn(async move {
 				// Stop accepting new HTTP connections
 				http_handle.graceful_shutdown(None);
+				unix_shutdown.cancel();
 				// Wait for all connections to close
 				while http_handle.connection_count() > 0 {
 					tokio::time::sleep(tokio::time::Duration::from_millis(100)).await;
@@ -1,42 +1,125 @@
-use axum::response::IntoResponse;
+use anyhow::Result;
+use axum::body::Body;
+use axum::extract::{DefaultBodyLimit, Request};
+use axum::response::{IntoResponse, Response};
 use axum::routing::get;
 use axum::{Extension, Router};
+use axum_extra::extract::Query;
+use bytes::Bytes;
+use futures::TryStreamExt;
+use http::StatusCode;
+use serde::Deserialize;
+use surrealdb::headers::BACKUP_VERSION;
+use surrealdb_core::dbs::Session;
 use surrealdb_core::dbs::capabilities::RouteTarget;
+use surrealdb_core::iam::Action::{Edit, View};
+use surrealdb_core::iam::ResourceKind::Any;
+use tower_http::limit::RequestBodyLimitLayer;
 
 use super::AppState;
+use super::error::ResponseError;
+use super::output::Output;
+use crate::cnf::HTTP_MAX_IMPORT_BODY_SIZE;
 use crate::ntw::error::Error as NetError;
 
+#[derive(Default, Deserialize, Debug, Clone)]
+struct BackupOptions {
+	/// The snapshot version of a previous backup
+	pub since: Option<u64>,
+	/// The bucket to write the backup into, instead of the response
+	pub bucket: Option<String>,
+	/// The key of the file to write the backup into
+	pub key: Option<String>,
+}
+
 pub fn router<S>() -> Router<S>
 where
 	S: Clone + Send + Sync + 'static,
 {
-	Router::new().route("/sync", get(save).post(load))
+	Router::new()
+		.route("/sync", get(save).post(load))
+		.route_layer(DefaultBodyLimit::disable())
+		.layer(RequestBodyLimitLayer::new(*HTTP_MAX_IMPORT_BODY_SIZE))
 }
 
 async fn load(
 	Extension(state): Extension<AppState>,
-) -> Result<impl IntoResponse, impl IntoResponse> {
+	Extension(session): Extension<Session>,
+	request: Request,
+) -> Result<impl IntoResponse, ResponseError> {
 	// Get the datastore reference
 	let db = &state.datastore;
 	// Check if capabilities allow querying the requested HTTP route
 	if !db.allows_http_route(&RouteTarget::Sync) {
 		warn!("Capabilities denied HTTP route request attempt, target: '{}'", &RouteTarget::Sync);
-		return Err(NetError::ForbiddenRoute(RouteTarget::Sync.to_string()));
+		return Err(NetError::ForbiddenRoute(RouteTarget::Sync.to_string()).into());
 	}
-
-	Ok("Load")
+	// Check the permissions level
+	db.check(&session, Edit, Any.on_root()).map_err(ResponseError)?;
+	// Restore the backup from the request body
+	let body_stream = request.into_body().into_data_stream().map_err(anyhow::Error::new);
+	let manifest = db.restore(body_stream).await.map_err(ResponseError)?;
+	info!("Restored a backup taken at snapshot version {}", manifest.timestamp);
+	Ok(Output::None)
 }
 
 async fn save(
 	Extension(state): Extension<AppState>,
-) -> Result<impl IntoResponse, impl IntoResponse> {
+	Extension(session): Extension<Session>,
+	Query(query): Query<BackupOptions>,
+) -> Result<impl IntoResponse, ResponseError> {
 	// Get the datastore reference
 	let db = &state.datastore;
 	// Check if capabilities allow querying the requested HTTP route
 	if !db.allows_http_route(&RouteTarget::Sync) {
 		warn!("Capabilities denied HTTP route request attempt, target: '{}'", &RouteTarget::Sync);
-		return Err(NetError::ForbiddenRoute(RouteTarget::Sync.to_string()));
+		return Err(NetError::ForbiddenRoute(RouteTarget::Sync.to_string()).into());
 	}
-
-	Ok("Save")
+	// Check the permissions level
+	db.check(&session, View, Any.on_root()).map_err(ResponseError)?;
+	// Write the backup into a bucket if one is specified
+	if let Some(bucket) = query.bucket {
+		let Some(key) = query.key else {
+			return Err(ResponseError(anyhow::anyhow!(
+				"A key is required to write a backup into a bucket"
+			)));
+		};
+		let version = db
+			.backup_to_bucket(&session, &bucket, &key, query.since)
+			.await
+			.map_err(ResponseError)?;
+		return Ok(Response::builder()
+			.status(StatusCode::OK)
+			.header(&BACKUP_VERSION, version.to_string())
+			.body(Body::empty())?);
+	}
+	// Create a chunked response
+	let (chn, body_stream) = surrealdb::channel::bounded::<Result<Bytes>>(1);
+	let body = Body::from_stream(body_stream);
+	// Create a new bounded channel
+	let (snd, rcv) = surrealdb::channel::bounded(1);
+	// Start the backup task
+	let (version, task) = db.backup(query.since, snd).await.map_err(ResponseError)?;
+	// Spawn a new database backup job, aborting the response if it fails
+	let err = chn.clone();
+	tokio::spawn(async move {
+		if let Err(e) = task.await {
+			tracing::warn!("Error creating backup: {e}");
+			err.send(Err(e)).await.ok();
+		}
+	});
+	// Process all chunk values, stopping the backup if the client goes away
+	tokio::spawn(async move {
+		while let Ok(v) = rcv.recv().await {
+			if let Err(err) = chn.send(Ok(Bytes::from(v))).await {
+				tracing::warn!("Error sending bytes: {:?}", err);
+				break;
+			}
+		}
+	});
+	// Return the chunked body
+	Ok(Response::builder()
+		.status(StatusCode::OK)
+		.header(&BACKUP_VERSION, version.to_string())
+		.body(body)?)
 }
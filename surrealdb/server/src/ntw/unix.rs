@@ -0,0 +1,80 @@
+//! Serving the HTTP router over a Unix domain socket.
+//!
+//! The same router which is served over TCP, including the `/rpc` WebSocket
+//! endpoint, is served over the socket. Connections over the socket have no
+//! IP address of their own, so they are reported to the router as coming from
+//! the loopback address. The socket is created with the configured file
+//! permissions before it is exposed at its final path, so that it can never be
+//! connected to with broader permissions.
+use std::io;
+use std::net::{IpAddr, Ipv4Addr, SocketAddr};
+use std::os::unix::fs::{FileTypeExt, PermissionsExt};
+use std::path::{Path, PathBuf};
+
+use axum::extract::connect_info::ConnectInfo;
+use axum::{Extension, Router};
+use tokio::fs;
+use tokio::net::UnixListener;
+use tokio_util::sync::CancellationToken;
+
+/// The peer address reported for connections over a Unix domain socket
+const UNIX_PEER: SocketAddr = SocketAddr::new(IpAddr::V4(Ipv4Addr::LOCALHOST), 0);
+
+/// Serves the router on a Unix domain socket at the given path.
+///
+/// New connections stop being accepted once `shutdown` is cancelled, and the
+/// server stops immediately once `canceller` is cancelled. The socket file is
+/// removed when the server stops.
+pub(super) async fn serve(
+	path: &Path,
+	mode: u32,
+	app: Router,
+	shutdown: CancellationToken,
+	canceller: CancellationToken,
+) -> io::Result<()> {
+	// Remove any socket left behind by a previous server
+	remove_stale(path).await?;
+	// Bind to a temporary path, and restrict its permissions
+	let tmp = temporary_path(path);
+	remove_stale(&tmp).await?;
+	let listener = UnixListener::bind(&tmp)?;
+	fs::set_permissions(&tmp, std::fs::Permissions::from_mode(mode)).await?;
+	// Expose the socket at its final path
+	fs::rename(&tmp, path).await?;
+	// Log the server startup to the CLI
+	info!(target: super::LOG, "Started web server on unix:{}", path.display());
+	// Start the server and listen for connections
+	let app = app.layer(Extension(ConnectInfo(UNIX_PEER)));
+	let server = axum::serve(listener, app.into_make_service())
+		.with_graceful_shutdown(shutdown.cancelled_owned());
+	let res = tokio::select! {
+		res = server => res,
+		_ = canceller.cancelled() => Ok(()),
+	};
+	// Remove the socket once the server has stopped
+	if let Err(e) = fs::remove_file(path).await {
+		warn!(target: super::LOG, "Failed to remove the socket at {}: {e}", path.display());
+	}
+	res
+}
+
+/// Removes the file at the given path if it is a socket, and fails if it is
+/// any other kind of file.
+async fn remove_stale(path: &Path) -> io::Result<()> {
+	match fs::symlink_metadata(path).await {
+		Ok(meta) if meta.file_type().is_socket() => fs::remove_file(path).await,
+		Ok(_) => Err(io::Error::new(
+			io::ErrorKind::AlreadyExists,
+			format!("{} already exists and is not a socket", path.display()),
+		)),
+		Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(()),
+		Err(e) => Err(e),
+	}
+}
+
+/// The path which the socket is bound to before its permissions are set.
+fn temporary_path(path: &Path) -> PathBuf {
+	let mut tmp = path.as_os_str().to_owned();
+	tmp.push(format!(".{}.tmp", std::process::id()));
+	PathBuf::from(tmp)
+}
@@ -1,5 +1,6 @@
 pub mod ds;
 pub mod http;
+pub mod prometheus;
 pub mod ws;
 
 use opentelemetry_sdk::metrics::{
@@ -36,58 +37,69 @@ const HISTOGRAM_BUCKETS_BYTES: &[f64] = &[
 ];
 
 // Returns a metrics configuration based on the SURREAL_TELEMETRY_PROVIDER
-// environment variable
-pub fn init() -> anyhow::Result<Option<SdkMeterProvider>> {
-	match TELEMETRY_PROVIDER.trim() {
-		// The OTLP telemetry provider has been specified
-		s if s.eq_ignore_ascii_case("otlp") && !*TELEMETRY_DISABLE_METRICS => {
-			// Create a new metrics exporter using OTLP with tonic transport
-			let exporter = opentelemetry_otlp::MetricExporter::builder()
-				.with_tonic()
-				.with_temporality(opentelemetry_sdk::metrics::Temporality::Cumulative)
-				.build()?;
-			let reader = PeriodicReader::builder(exporter)
-				.with_interval(std::time::Duration::from_secs(60))
-				.build();
-			// Create view for histogram durations with custom buckets
-			let duration_view = |instrument: &Instrument| -> Option<Stream> {
-				if instrument.name().ends_with(".duration") {
-					Stream::builder()
-						.with_aggregation(Aggregation::ExplicitBucketHistogram {
-							boundaries: HISTOGRAM_BUCKETS_MS.to_vec(),
-							record_min_max: true,
-						})
-						.build()
-						.ok()
-				} else {
-					None
-				}
-			};
-			// Create view for histogram sizes with custom buckets
-			let size_view = |instrument: &Instrument| -> Option<Stream> {
-				if instrument.name().ends_with(".size") {
-					Stream::builder()
-						.with_aggregation(Aggregation::ExplicitBucketHistogram {
-							boundaries: HISTOGRAM_BUCKETS_BYTES.to_vec(),
-							record_min_max: true,
-						})
-						.build()
-						.ok()
-				} else {
-					None
-				}
-			};
-			// Create the new metrics provider
-			Ok(Some(
-				SdkMeterProvider::builder()
-					.with_reader(reader)
-					.with_resource(OTEL_DEFAULT_RESOURCE.clone())
-					.with_view(duration_view)
-					.with_view(size_view)
-					.build(),
-			))
-		}
-		// No matching telemetry provider was found
-		_ => Ok(None),
+// environment variable, and whether the Prometheus scrape endpoint is enabled
+pub fn init(prometheus: bool) -> anyhow::Result<Option<SdkMeterProvider>> {
+	// Create a new metrics provider builder
+	let mut builder = SdkMeterProvider::builder();
+	let mut enabled = false;
+	// The OTLP telemetry provider has been specified
+	if TELEMETRY_PROVIDER.trim().eq_ignore_ascii_case("otlp") && !*TELEMETRY_DISABLE_METRICS {
+		// Create a new metrics exporter using OTLP with tonic transport
+		let exporter = opentelemetry_otlp::MetricExporter::builder()
+			.with_tonic()
+			.with_temporality(opentelemetry_sdk::metrics::Temporality::Cumulative)
+			.build()?;
+		let reader = PeriodicReader::builder(exporter)
+			.with_interval(std::time::Duration::from_secs(60))
+			.build();
+		builder = builder.with_reader(reader);
+		enabled = true;
+	}
+	// The Prometheus scrape endpoint has been enabled
+	if prometheus {
+		builder = builder.with_reader(prometheus::reader());
+		enabled = true;
+	}
+	// No metrics reader was configured
+	if !enabled {
+		return Ok(None);
+	}
+	// Create the new metrics provider
+	Ok(Some(
+		builder
+			.with_resource(OTEL_DEFAULT_RESOURCE.clone())
+			.with_view(duration_view)
+			.with_view(size_view)
+			.build(),
+	))
+}
+
+// Create view for histogram durations with custom buckets
+fn duration_view(instrument: &Instrument) -> Option<Stream> {
+	if instrument.name().ends_with(".duration") {
+		Stream::builder()
+			.with_aggregation(Aggregation::ExplicitBucketHistogram {
+				boundaries: HISTOGRAM_BUCKETS_MS.to_vec(),
+				record_min_max: true,
+			})
+			.build()
+			.ok()
+	} else {
+		None
+	}
+}
+
+// Create view for histogram sizes with custom buckets
+fn size_view(instrument: &Instrument) -> Option<Stream> {
+	if instrument.name().ends_with(".size") {
+		Stream::builder()
+			.with_aggregation(Aggregation::ExplicitBucketHistogram {
+				boundaries: HISTOGRAM_BUCKETS_BYTES.to_vec(),
+				record_min_max: true,
+			})
+			.build()
+			.ok()
+	} else {
+		None
 	}
 }
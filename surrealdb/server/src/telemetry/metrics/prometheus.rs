@@ -0,0 +1,209 @@
+use std::fmt::{Display, Write};
+use std::sync::{Arc, OnceLock, Weak};
+
+use anyhow::Result;
+use opentelemetry::KeyValue;
+use opentelemetry_sdk::error::OTelSdkError;
+use opentelemetry_sdk::metrics::data::{AggregatedMetrics, MetricData, ResourceMetrics};
+use opentelemetry_sdk::metrics::reader::MetricReader;
+use opentelemetry_sdk::metrics::{InstrumentKind, ManualReader, Pipeline, Temporality};
+
+/// The reader which is registered with the meter provider when the
+/// Prometheus scrape endpoint is enabled
+static READER: OnceLock<PrometheusReader> = OnceLock::new();
+
+/// A shareable pull-based reader, which is collected on each scrape
+#[derive(Clone, Debug)]
+pub(super) struct PrometheusReader {
+	inner: Arc<ManualReader>,
+}
+
+impl MetricReader for PrometheusReader {
+	fn register_pipeline(&self, pipeline: Weak<Pipeline>) {
+		self.inner.register_pipeline(pipeline);
+	}
+
+	fn collect(&self, rm: &mut ResourceMetrics) -> Result<(), OTelSdkError> {
+		self.inner.collect(rm)
+	}
+
+	fn force_flush(&self) -> Result<(), OTelSdkError> {
+		self.inner.force_flush()
+	}
+
+	fn shutdown(&self) -> Result<(), OTelSdkError> {
+		self.inner.shutdown()
+	}
+
+	fn shutdown_with_timeout(&self, timeout: std::time::Duration) -> Result<(), OTelSdkError> {
+		self.inner.shutdown_with_timeout(timeout)
+	}
+
+	fn temporality(&self, kind: InstrumentKind) -> Temporality {
+		self.inner.temporality(kind)
+	}
+}
+
+/// Returns the global Prometheus reader, creating it if necessary
+pub(super) fn reader() -> PrometheusReader {
+	READER
+		.get_or_init(|| PrometheusReader {
+			inner: Arc::new(ManualReader::builder().build()),
+		})
+		.clone()
+}
+
+/// Whether the Prometheus scrape endpoint has been enabled
+pub fn enabled() -> bool {
+	READER.get().is_some()
+}
+
+/// Collects all registered instruments in the Prometheus text format
+pub fn render() -> Result<String> {
+	let Some(reader) = READER.get() else {
+		return Err(anyhow::anyhow!("The Prometheus metrics reader is not enabled"));
+	};
+	let mut metrics = ResourceMetrics::default();
+	reader.collect(&mut metrics)?;
+	Ok(encode(&metrics))
+}
+
+/// Encodes the collected metrics in the Prometheus text exposition format
+fn encode(metrics: &ResourceMetrics) -> String {
+	let mut out = String::new();
+	for metric in metrics.scope_metrics().flat_map(|scope| scope.metrics()) {
+		let name = sanitize(metric.name());
+		let help = metric.description();
+		match metric.data() {
+			AggregatedMetrics::F64(data) => encode_data(&mut out, &name, help, data),
+			AggregatedMetrics::U64(data) => encode_data(&mut out, &name, help, data),
+			AggregatedMetrics::I64(data) => encode_data(&mut out, &name, help, data),
+		}
+	}
+	out
+}
+
+fn encode_data<T: Copy + Display>(out: &mut String, name: &str, help: &str, data: &MetricData<T>) {
+	match data {
+		MetricData::Gauge(gauge) => {
+			header(out, name, help, "gauge");
+			for point in gauge.data_points() {
+				let labels = labels(point.attributes(), None);
+				let _ = writeln!(out, "{name}{labels} {}", point.value());
+			}
+		}
+		MetricData::Sum(sum) => {
+			// Monotonic sums are exposed as counters, which carry a _total suffix
+			let (name, kind) = if sum.is_monotonic() {
+				(format!("{name}_total"), "counter")
+			} else {
+				(name.to_string(), "gauge")
+			};
+			header(out, &name, help, kind);
+			for point in sum.data_points() {
+				let labels = labels(point.attributes(), None);
+				let _ = writeln!(out, "{name}{labels} {}", point.value());
+			}
+		}
+		MetricData::Histogram(histogram) => {
+			header(out, name, help, "histogram");
+			for point in histogram.data_points() {
+				// Prometheus buckets are cumulative
+				let mut cumulative = 0;
+				for (bound, count) in point.bounds().zip(point.bucket_counts()) {
+					cumulative += count;
+					let bucket = labels(point.attributes(), Some(&bound.to_string()));
+					let _ = writeln!(out, "{name}_bucket{bucket} {cumulative}");
+				}
+				let bucket = labels(point.attributes(), Some("+Inf"));
+				let _ = writeln!(out, "{name}_bucket{bucket} {}", point.count());
+				let labels = labels(point.attributes(), None);
+				let _ = writeln!(out, "{name}_sum{labels} {}", point.sum());
+				let _ = writeln!(out, "{name}_count{labels} {}", point.count());
+			}
+		}
+		// Exponential histograms are not configured by any of our views
+		MetricData::ExponentialHistogram(_) => {}
+	}
+}
+
+fn header(out: &mut String, name: &str, help: &str, kind: &str) {
+	if !help.is_empty() {
+		let help = help.replace('\\', "\\\\").replace('\n', "\\n");
+		let _ = writeln!(out, "# HELP {name} {help}");
+	}
+	let _ = writeln!(out, "# TYPE {name} {kind}");
+}
+
+fn labels<'a>(attributes: impl Iterator<Item = &'a KeyValue>, le: Option<&str>) -> String {
+	let mut pairs = attributes
+		.map(|kv| format!("{}=\"{}\"", sanitize(kv.key.as_str()), escape(&kv.value.as_str())))
+		.collect::<Vec<_>>();
+	if let Some(le) = le {
+		pairs.push(format!("le=\"{le}\""));
+	}
+	if pairs.is_empty() {
+		String::new()
+	} else {
+		format!("{{{}}}", pairs.join(","))
+	}
+}
+
+/// Converts an OpenTelemetry name into a valid Prometheus name
+fn sanitize(name: &str) -> String {
+	name.chars()
+		.map(|c| {
+			if c.is_ascii_alphanumeric() || c == '_' {
+				c
+			} else {
+				'_'
+			}
+		})
+		.collect()
+}
+
+fn escape(value: &str) -> String {
+	value.replace('\\', "\\\\").replace('"', "\\\"").replace('\n', "\\n")
+}
+
+#[cfg(test)]
+mod tests {
+	use opentelemetry::KeyValue;
+	use opentelemetry::metrics::MeterProvider;
+	use opentelemetry_sdk::metrics::SdkMeterProvider;
+	use opentelemetry_sdk::metrics::data::ResourceMetrics;
+	use opentelemetry_sdk::metrics::reader::MetricReader;
+
+	use super::{PrometheusReader, encode};
+
+	#[test]
+	fn encodes_prometheus_text_format() {
+		let reader = PrometheusReader {
+			inner: std::sync::Arc::new(opentelemetry_sdk::metrics::ManualReader::builder().build()),
+		};
+		let provider = SdkMeterProvider::builder().with_reader(reader.clone()).build();
+		let meter = provider.meter("test");
+		let counter = meter.u64_counter("rpc.server.calls").with_description("Calls").build();
+		counter.add(2, &[KeyValue::new("rpc.method", "query")]);
+		let histogram =
+			meter.f64_histogram("http.server.duration").with_boundaries(vec![10.0, 100.0]).build();
+		histogram.record(5.0, &[]);
+		histogram.record(50.0, &[]);
+
+		let mut metrics = ResourceMetrics::default();
+		reader.collect(&mut metrics).unwrap();
+		let text = encode(&metrics);
+
+		assert!(text.contains("# HELP rpc_server_calls_total Calls\n"));
+		assert!(text.contains("# TYPE rpc_server_calls_total counter\n"));
+		assert!(text.contains("rpc_server_calls_total{rpc_method=\"query\"} 2\n"));
+		assert!(text.contains("# TYPE http_server_duration histogram\n"));
+		assert!(text.contains("http_server_duration_bucket{le=\"10\"} 1\n"));
+		assert!(text.contains("http_server_duration_bucket{le=\"100\"} 2\n"));
+		assert!(text.contains("http_server_duration_bucket{le=\"+Inf\"} 2\n"));
+		assert!(text.contains("http_server_duration_sum 55\n"));
+		assert!(text.contains("http_server_duration_count 2\n"));
+
+		provider.shutdown().unwrap();
+	}
+}
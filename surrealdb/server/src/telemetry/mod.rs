@@ -42,6 +42,8 @@ pub struct Builder {
 	file_path: Option<String>,
 	file_name: Option<String>,
 	file_rotation: Option<String>,
+	// Metrics options
+	prometheus: bool,
 }
 
 pub fn builder() -> Builder {
@@ -69,6 +71,8 @@ impl Default for Builder {
 			file_path: Some("logs".to_string()),
 			file_name: Some("surrealdb.log".to_string()),
 			file_rotation: Some("daily".to_string()),
+			// Metrics options
+			prometheus: false,
 		}
 	}
 }
@@ -167,10 +171,16 @@ impl Builder {
 		self
 	}
 
+	/// Enable or disable the Prometheus metrics reader
+	pub fn with_prometheus(mut self, enabled: bool) -> Self {
+		self.prometheus = enabled;
+		self
+	}
+
 	/// Build a tracing dispatcher with the logs and tracer subscriber
 	pub fn build(&self) -> Result<(Box<dyn Subscriber + Send + Sync + 'static>, Vec<WorkerGuard>)> {
 		// Setup the metrics layer
-		if let Some(provider) = metrics::init()? {
+		if let Some(provider) = metrics::init(self.prometheus)? {
 			global::set_meter_provider(provider);
 		}
 		// Create a non-blocking stdout log destination
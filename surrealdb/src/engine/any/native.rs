@@ -133,11 +133,8 @@ impl conn::Sealed for Any {
 						features.insert(ExtraFeatures::Backup);
 						let base_url = address.url;
 
-						#[cfg(any(feature = "native-tls", feature = "rustls"))]
-						let client = http::native::create_client(&base_url, address.config.tls_config.as_ref())
-							.await?;
-						#[cfg(not(any(feature = "native-tls", feature = "rustls")))]
-						let client = http::native::create_client(&base_url).await?;
+						let client =
+							http::native::create_client(&base_url, &address.config).await?;
 
 						tokio::spawn(http::native::run_router(
 							client,
@@ -13,7 +13,7 @@ use crate::engine::{SessionError, session_error_to_error};
 use crate::method::BoxFuture;
 #[cfg(any(feature = "native-tls", feature = "rustls"))]
 use crate::opt::Tls;
-use crate::opt::{Endpoint, WaitFor};
+use crate::opt::{Config, Endpoint, WaitFor};
 use crate::{Error, ExtraFeatures, Result, SessionClone, SessionId, Surreal, conn};
 
 /// Creates an HTTP client with address pinning for the given URL.
@@ -21,21 +21,28 @@ use crate::{Error, ExtraFeatures, Result, SessionClone, SessionId, Surreal, conn
 /// This function resolves the hostname to IP addresses and tries each one
 /// until a successful health check is performed. The resulting client is
 /// configured with `reqwest::ClientBuilder::resolve()` to pin all requests
-/// to the working IP address, ensuring session consistency.
+/// to the working IP address, ensuring session consistency. If a Unix domain
+/// socket is configured, all requests are sent over the socket instead.
 ///
 /// # Arguments
 ///
 /// * `base_url` - The base URL of the SurrealDB server
-/// * `tls_config` - Optional TLS configuration for HTTPS connections
+/// * `config` - The connection configuration, including any TLS configuration
 ///
 /// # Returns
 ///
 /// A configured `reqwest::Client` pinned to a specific server IP address.
-pub(crate) async fn create_client(
-	base_url: &Url,
-	#[cfg(any(feature = "native-tls", feature = "rustls"))] tls_config: Option<&Tls>,
-) -> Result<reqwest::Client> {
-	let headers = super::default_headers();
+pub(crate) async fn create_client(base_url: &Url, config: &Config) -> Result<reqwest::Client> {
+	// Connect over the Unix domain socket, if one is configured
+	#[cfg(unix)]
+	if let Some(path) = &config.unix_socket {
+		let client = client_builder(config)
+			.unix_socket(path.clone())
+			.build()
+			.map_err(|error| Error::internal(error.to_string()))?;
+		health_check(&client, base_url).await?;
+		return Ok(client);
+	}
 
 	// Extract hostname and port for DNS resolution
 	let hostname = base_url.domain().unwrap_or("localhost");
@@ -50,20 +57,7 @@ pub(crate) async fn create_client(
 	let mut last_error = None;
 
 	for addr in addrs {
-		#[cfg_attr(not(any(feature = "native-tls", feature = "rustls")), expect(unused_mut))]
-		let mut builder = ClientBuilder::new().default_headers(headers.clone()).resolve(hostname, addr);
-
-		#[cfg(any(feature = "native-tls", feature = "rustls"))]
-		if let Some(tls) = tls_config {
-			builder = match tls {
-				#[cfg(feature = "native-tls")]
-				Tls::Native(config) => builder.use_preconfigured_tls(config.clone()),
-				#[cfg(feature = "rustls")]
-				Tls::Rust(config) => builder.use_preconfigured_tls(config.clone()),
-			};
-		}
-
-		let client = match builder.build() {
+		let client = match client_builder(config).resolve(hostname, addr).build() {
 			Ok(client) => client,
 			Err(error) => {
 				last_error = Some(Error::internal(error.to_string()));
@@ -72,11 +66,7 @@ pub(crate) async fn create_client(
 		};
 
 		// Try health check with this address
-		let req = client
-			.get(base_url.join("health").map_err(crate::std_error_to_types_error)?)
-			.header(reqwest::header::USER_AGENT, &*SURREALDB_USER_AGENT);
-
-		match super::health(req).await {
+		match health_check(&client, base_url).await {
 			Ok(()) => return Ok(client),
 			Err(e) => {
 				last_error = Some(e);
@@ -88,6 +78,33 @@ pub(crate) async fn create_client(
 	Err(last_error.unwrap_or_else(|| Error::internal("No addresses available".to_string())))
 }
 
+/// Creates a client builder with the default headers and any TLS configuration.
+#[cfg_attr(not(any(feature = "native-tls", feature = "rustls")), expect(unused_variables))]
+fn client_builder(config: &Config) -> ClientBuilder {
+	#[cfg_attr(not(any(feature = "native-tls", feature = "rustls")), expect(unused_mut))]
+	let mut builder = ClientBuilder::new().default_headers(super::default_headers());
+
+	#[cfg(any(feature = "native-tls", feature = "rustls"))]
+	if let Some(tls) = &config.tls_config {
+		builder = match tls {
+			#[cfg(feature = "native-tls")]
+			Tls::Native(config) => builder.use_preconfigured_tls(config.clone()),
+			#[cfg(feature = "rustls")]
+			Tls::Rust(config) => builder.use_preconfigured_tls(config.clone()),
+		};
+	}
+
+	builder
+}
+
+/// Checks that the server is reachable and healthy using the given client.
+async fn health_check(client: &reqwest::Client, base_url: &Url) -> Result<()> {
+	let req = client
+		.get(base_url.join("health").map_err(crate::std_error_to_types_error)?)
+		.header(reqwest::header::USER_AGENT, &*SURREALDB_USER_AGENT);
+	super::health(req).await
+}
+
 impl crate::Connection for Client {}
 impl conn::Sealed for Client {
 	#[allow(private_interfaces)]
@@ -100,10 +117,7 @@ impl conn::Sealed for Client {
 			let config = address.config.clone();
 			let base_url = address.url;
 
-			#[cfg(any(feature = "native-tls", feature = "rustls"))]
-			let client = create_client(&base_url, address.config.tls_config.as_ref()).await?;
-			#[cfg(not(any(feature = "native-tls", feature = "rustls")))]
-			let client = create_client(&base_url).await?;
+			let client = create_client(&base_url, &address.config).await?;
 
 			let (route_tx, route_rx) = match capacity {
 				0 => async_channel::unbounded(),
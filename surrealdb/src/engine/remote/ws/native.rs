@@ -1,10 +1,16 @@
 use std::collections::HashSet;
+use std::io;
+use std::pin::Pin;
 use std::sync::Arc;
+use std::task::{Context, Poll};
 
 use async_channel::Receiver;
 use futures::stream::{SplitSink, SplitStream};
 use futures::{SinkExt, StreamExt};
+use tokio::io::{AsyncRead, AsyncWrite, ReadBuf};
 use tokio::net::TcpStream;
+#[cfg(unix)]
+use tokio::net::UnixStream;
 use tokio::sync::{RwLock, watch};
 use tokio::time;
 use tokio::time::MissedTickBehavior;
@@ -33,14 +39,67 @@ use crate::{Error, ExtraFeatures, SessionClone, SessionId, Surreal};
 
 pub(crate) const NAGLE_ALG: bool = false;
 
-type MessageSink = SplitSink<WebSocketStream<MaybeTlsStream<TcpStream>>, Message>;
-type MessageStream = SplitStream<WebSocketStream<MaybeTlsStream<TcpStream>>>;
+type MessageSink = SplitSink<WebSocketStream<MaybeTlsStream<Socket>>, Message>;
+type MessageStream = SplitStream<WebSocketStream<MaybeTlsStream<Socket>>>;
 type Sessions = HashMap<Uuid, Result<Arc<SessionState>, SessionError>>;
 
 // ============================================================================
 // Platform Implementation
 // ============================================================================
 
+/// The transport which a WebSocket connection runs over
+pub(crate) enum Socket {
+	/// A TCP connection to the host and port of the endpoint
+	Tcp(TcpStream),
+	/// A connection to a Unix domain socket
+	#[cfg(unix)]
+	Unix(UnixStream),
+}
+
+impl AsyncRead for Socket {
+	fn poll_read(
+		self: Pin<&mut Self>,
+		cx: &mut Context<'_>,
+		buf: &mut ReadBuf<'_>,
+	) -> Poll<io::Result<()>> {
+		match self.get_mut() {
+			Socket::Tcp(stream) => Pin::new(stream).poll_read(cx, buf),
+			#[cfg(unix)]
+			Socket::Unix(stream) => Pin::new(stream).poll_read(cx, buf),
+		}
+	}
+}
+
+impl AsyncWrite for Socket {
+	fn poll_write(
+		self: Pin<&mut Self>,
+		cx: &mut Context<'_>,
+		buf: &[u8],
+	) -> Poll<io::Result<usize>> {
+		match self.get_mut() {
+			Socket::Tcp(stream) => Pin::new(stream).poll_write(cx, buf),
+			#[cfg(unix)]
+			Socket::Unix(stream) => Pin::new(stream).poll_write(cx, buf),
+		}
+	}
+
+	fn poll_flush(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+		match self.get_mut() {
+			Socket::Tcp(stream) => Pin::new(stream).poll_flush(cx),
+			#[cfg(unix)]
+			Socket::Unix(stream) => Pin::new(stream).poll_flush(cx),
+		}
+	}
+
+	fn poll_shutdown(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+		match self.get_mut() {
+			Socket::Tcp(stream) => Pin::new(stream).poll_shutdown(cx),
+			#[cfg(unix)]
+			Socket::Unix(stream) => Pin::new(stream).poll_shutdown(cx),
+		}
+	}
+}
+
 impl WsMessage for Message {
 	fn binary(payload: Vec<u8>) -> Self {
 		Message::Binary(payload.into())
@@ -86,31 +145,58 @@ pub(crate) async fn connect(
 	config: Option<WebSocketConfig>,
 	#[cfg_attr(not(any(feature = "native-tls", feature = "rustls")), expect(unused_variables))]
 	maybe_connector: Option<Connector>,
-) -> crate::Result<WebSocketStream<MaybeTlsStream<TcpStream>>> {
+) -> crate::Result<WebSocketStream<MaybeTlsStream<Socket>>> {
 	let mut request = (&endpoint.url)
 		.into_client_request()
 		.map_err(|err| Error::internal(format!("Invalid URL: {}", err)))?;
 
 	request.headers_mut().insert(SEC_WEBSOCKET_PROTOCOL, HeaderValue::from_static("flatbuffers"));
 
+	let socket = open_socket(endpoint).await?;
+
 	#[cfg(any(feature = "native-tls", feature = "rustls"))]
-	let (socket, _) = tokio_tungstenite::connect_async_tls_with_config(
-		request,
-		config,
-		NAGLE_ALG,
-		maybe_connector,
-	)
-	.await
-	.map_err(|err| Error::internal(format!("WebSocket error: {}", err)))?;
+	let (socket, _) =
+		tokio_tungstenite::client_async_tls_with_config(request, socket, config, maybe_connector)
+			.await
+			.map_err(|err| Error::internal(format!("WebSocket error: {}", err)))?;
 
 	#[cfg(not(any(feature = "native-tls", feature = "rustls")))]
-	let (socket, _) = tokio_tungstenite::connect_async_with_config(request, config, NAGLE_ALG)
-		.await
-		.map_err(|err| Error::internal(format!("WebSocket error: {}", err)))?;
+	let (socket, _) =
+		tokio_tungstenite::client_async_with_config(request, MaybeTlsStream::Plain(socket), config)
+			.await
+			.map_err(|err| Error::internal(format!("WebSocket error: {}", err)))?;
 
 	Ok(socket)
 }
 
+/// Opens the transport for a connection, using the Unix domain socket from
+/// the configuration when one is set, and the host and port of the URL
+/// otherwise.
+async fn open_socket(endpoint: &Endpoint) -> crate::Result<Socket> {
+	#[cfg(unix)]
+	if let Some(path) = &endpoint.config.unix_socket {
+		let stream = UnixStream::connect(path)
+			.await
+			.map_err(|err| Error::internal(format!("WebSocket error: {}", err)))?;
+		return Ok(Socket::Unix(stream));
+	}
+	let host = endpoint
+		.url
+		.host_str()
+		.ok_or_else(|| Error::internal(format!("Invalid URL: {}", endpoint.url)))?;
+	let port = endpoint
+		.url
+		.port_or_known_default()
+		.ok_or_else(|| Error::internal(format!("Invalid URL: {}", endpoint.url)))?;
+	let stream = TcpStream::connect((host, port))
+		.await
+		.map_err(|err| Error::internal(format!("WebSocket error: {}", err)))?;
+	stream
+		.set_nodelay(NAGLE_ALG)
+		.map_err(|err| Error::internal(format!("WebSocket error: {}", err)))?;
+	Ok(Socket::Tcp(stream))
+}
+
 impl crate::Connection for super::Client {}
 impl conn::Sealed for super::Client {
 	#[allow(private_interfaces)]
@@ -231,7 +317,7 @@ pub(crate) async fn run_router(
 	endpoint: Endpoint,
 	maybe_connector: Option<Connector>,
 	config: WebSocketConfig,
-	socket: WebSocketStream<MaybeTlsStream<TcpStream>>,
+	socket: WebSocketStream<MaybeTlsStream<Socket>>,
 	route_rx: Receiver<Route>,
 	session_rx: Receiver<SessionId>,
 ) {
@@ -8,7 +8,7 @@ use std::task::{Context, Poll};
 use async_channel::Receiver;
 use futures::{Stream, StreamExt};
 use semver::Version;
-use surrealdb_core::kvs::export::{Config as DbExportConfig, TableConfig};
+use surrealdb_core::kvs::export::{Config as DbExportConfig, ExportFormat, TableConfig};
 
 use crate::conn::{Command, MlExportConfig};
 use crate::method::{BoxFuture, ExportConfig as Config, Model, OnceLockExt};
@@ -173,6 +173,27 @@ where
 		}
 		self
 	}
+
+	/// The format in which the database should be exported
+	///
+	/// Binary exports are much faster to import than SurrealQL exports, as no
+	/// parsing is required and records are imported concurrently. Each batch
+	/// of records is committed separately, so a binary import which fails part
+	/// way through leaves the records imported so far in the database, and
+	/// should be retried into an empty database:
+	/// ```
+	/// # let db = surrealdb::Surreal::<surrealdb::engine::any::Any>::init();
+	/// # let target = ();
+	/// use surrealdb::opt::ExportFormat;
+	///
+	/// db.export(target).with_config().format(ExportFormat::Binary);
+	/// ```
+	pub fn format(mut self, format: ExportFormat) -> Self {
+		if let Some(cfg) = self.db_config.as_mut() {
+			cfg.format = format;
+		}
+		self
+	}
 }
 
 impl<C, R, T> Export<'_, C, R, T>
@@ -1,4 +1,4 @@
-#[cfg(storage)]
+#[cfg(any(storage, all(unix, any(feature = "protocol-http", feature = "protocol-ws"))))]
 use std::path::PathBuf;
 use std::time::Duration;
 
@@ -17,6 +17,8 @@ pub struct Config {
 	pub(crate) transaction_timeout: Option<Duration>,
 	#[cfg(any(feature = "native-tls", feature = "rustls"))]
 	pub(crate) tls_config: Option<super::Tls>,
+	#[cfg(all(unix, any(feature = "protocol-http", feature = "protocol-ws")))]
+	pub(crate) unix_socket: Option<PathBuf>,
 	// Only used by the local engines
 	// `Level::No` in this context means no authentication information was configured
 	pub(crate) auth: Level,
@@ -94,6 +96,18 @@ impl Config {
 		self
 	}
 
+	/// Connect to the server over a Unix domain socket instead of over TCP
+	///
+	/// The scheme and host of the endpoint are still used for the requests
+	/// sent over the socket, so this works with both the HTTP and WebSocket
+	/// remote engines, for example with `("ws://localhost", config)`.
+	#[cfg(all(unix, any(feature = "protocol-http", feature = "protocol-ws")))]
+	#[cfg_attr(docsrs, doc(cfg(unix)))]
+	pub fn unix_socket(mut self, path: impl Into<PathBuf>) -> Self {
+		self.unix_socket = Some(path.into());
+		self
+	}
+
 	/// Set the capabilities for the database
 	pub fn capabilities(mut self, capabilities: Capabilities) -> Self {
 		self.capabilities = capabilities.into();
@@ -2,6 +2,8 @@
 
 use std::path::{Path, PathBuf};
 
+pub use surrealdb_core::kvs::export::ExportFormat;
+
 /// Destination for database export operations.
 ///
 /// Used to specify where exported data should be written.
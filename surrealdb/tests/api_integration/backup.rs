@@ -11,8 +11,9 @@
 // Supported by the storage engines and the HTTP protocol
 
 use futures::StreamExt as _;
-use surrealdb::opt::Config;
+use surrealdb::opt::{Config, ExportFormat};
 use surrealdb::types::Value;
+use surrealdb_core::kvs::export::{ExcludedTables, TableConfig};
 use tokio::fs::remove_file;
 use ulid::Ulid;
 
@@ -133,6 +134,97 @@ pub async fn export_with_config(new_db: impl CreateDb) {
 	}
 }
 
+pub async fn export_import_binary(new_db: impl CreateDb) {
+	let config = Config::new();
+	let (permit, db) = new_db.create_db(config).await;
+	let db_name = Ulid::new().to_string();
+	db.use_ns(Ulid::new().to_string()).use_db(&db_name).await.unwrap();
+
+	// Insert records, relations and an index
+	db.query(
+		"
+		DEFINE INDEX name ON user FIELDS name UNIQUE;
+		FOR $i IN 0..10 { CREATE type::record('user', $i) SET name = 'User ' + <string> $i };
+		FOR $i IN 0..5 { CREATE type::record('group', $i) SET name = 'Group ' + <string> $i };
+		RELATE user:1->member->group:1;
+		",
+	)
+	.await
+	.unwrap()
+	.check()
+	.unwrap();
+
+	// Drop the permit to release the database lock
+	drop(permit);
+
+	// Define the export file name
+	let file = format!("{db_name}.bin");
+
+	// Export, remove tables, and import
+	let res = async {
+		db.export(&file)
+			.with_config()
+			.format(ExportFormat::Binary)
+			.tables(TableConfig::Exclude(ExcludedTables {
+				exclude: vec!["group".to_string()],
+			}))
+			.await?;
+		db.query("REMOVE TABLE user; REMOVE TABLE group; REMOVE TABLE member").await?;
+		db.import(&file).await?;
+		Ok::<(), surrealdb::Error>(())
+	}
+	.await;
+
+	// Remove the export file
+	remove_file(&file).await.unwrap();
+
+	// Check the result of the export/import operations
+	res.unwrap();
+
+	// Verify that all user records exist post-import, using the index
+	for i in 0..10 {
+		let mut response = db
+			.query(format!("SELECT name FROM user WITH INDEX name WHERE name = 'User {i}'"))
+			.await
+			.unwrap();
+		let Some(name): Option<String> = response.take("name").unwrap() else {
+			panic!("query returned no record");
+		};
+		assert_eq!(name, format!("User {i}"));
+	}
+
+	// Verify that the unique index was rebuilt
+	db.query("CREATE user SET name = 'User 1'").await.unwrap().check().unwrap_err();
+
+	// Verify that the relation was imported with its graph edges
+	let mut response = db.query("RETURN count(user:1->member->group)").await.unwrap();
+	let count: Option<i64> = response.take(0).unwrap();
+	assert_eq!(count, Some(1));
+
+	// Verify that the excluded table was not imported
+	let mut response = db.query("SELECT id FROM group").await.unwrap();
+	let tmp: Option<Value> = response.take(0).unwrap();
+	assert_eq!(tmp, None);
+}
+
+pub async fn import_binary_oversized_frame(new_db: impl CreateDb) {
+	let config = Config::new();
+	let (_, db) = new_db.create_db(config).await;
+	let db_name = Ulid::new().to_string();
+	db.use_ns(Ulid::new().to_string()).use_db(&db_name).await.unwrap();
+
+	// A binary export whose first frame claims to be 4 GiB long
+	let dir = temp_dir::TempDir::new().unwrap();
+	let file_path = dir.path().join("export.bin");
+	let mut data = b"SURREALDB-EXPORT".to_vec();
+	data.extend_from_slice(&u32::MAX.to_be_bytes());
+	data.extend_from_slice(&[0; 16]);
+	std::fs::write(&file_path, &data).unwrap();
+
+	// The frame is rejected without waiting for the rest of it
+	db.import(file_path).await.unwrap_err();
+}
+
 #[cfg(feature = "ml")]
 pub async fn ml_export_import(new_db: impl CreateDb) {
 	let config = Config::new();
@@ -210,6 +302,12 @@ define_include_tests!(backup => {
 	#[tokio::test]
 	export_with_config,
 
+	#[tokio::test]
+	export_import_binary,
+
+	#[tokio::test]
+	import_binary_oversized_frame,
+
 	#[test_log::test(tokio::test)]
 	#[cfg(feature = "ml")]
 	ml_export_import,
@@ -492,6 +492,46 @@ mod http_integration {
 		Ok(())
 	}
 
+	#[test(tokio::test)]
+	async fn metrics_endpoint() -> Result<(), Box<dyn std::error::Error>> {
+		// The endpoint is not found unless enabled
+		{
+			let (addr, _server) = common::start_server_with_defaults().await.unwrap();
+			let url = &format!("http://{addr}/metrics");
+
+			let res = Client::default().get(url).send().await?;
+			assert_eq!(res.status(), 404, "body: {}", res.text().await?);
+		}
+
+		// Turn on the metrics endpoint
+		{
+			let mut start_server_arguments = StartServerArguments::default();
+			start_server_arguments.args.push_str(" --metrics-endpoint");
+			let (addr, _server) = common::start_server(start_server_arguments).await.unwrap();
+			let url = &format!("http://{addr}/metrics");
+
+			// Generate some HTTP metrics
+			Client::default().get(format!("http://{addr}/health")).send().await?;
+
+			// When no auth is provided, the endpoint returns a 403
+			let res = Client::default().get(url).send().await?;
+			assert_eq!(res.status(), 403, "body: {}", res.text().await?);
+
+			// When root auth is provided, the endpoint returns the metrics
+			let res = Client::default().get(url).basic_auth(USER, Some(PASS)).send().await?;
+			assert_eq!(res.status(), 200);
+			assert!(
+				res.headers()[header::CONTENT_TYPE]
+					.to_str()?
+					.starts_with("text/plain; version=0.0.4")
+			);
+			let body = res.text().await?;
+			assert!(body.contains("# TYPE "), "body: {body}");
+		}
+
+		Ok(())
+	}
+
 	#[test(tokio::test)]
 	async fn import_endpoint() -> Result<(), Box<dyn std::error::Error>> {
 		let (addr, _server) = common::start_server_with_defaults().await.unwrap();
@@ -2357,6 +2397,121 @@ mod http_integration {
 		}
 	}
 
+	#[test(tokio::test)]
+	async fn api_file_stream() -> Result<(), Box<dyn std::error::Error>> {
+		let (addr, _server) = common::start_server(StartServerArguments {
+			args: "--allow-experimental files".to_string(),
+			..Default::default()
+		})
+		.await
+		.unwrap();
+
+		// Prepare HTTP client
+		let mut headers = reqwest::header::HeaderMap::new();
+		let ns = Ulid::new().to_string();
+		let db = Ulid::new().to_string();
+		headers.insert("surreal-ns", ns.parse()?);
+		headers.insert("surreal-db", db.parse()?);
+		let client = reqwest::Client::builder()
+			.connect_timeout(Duration::from_millis(10))
+			.default_headers(headers)
+			.build()?;
+
+		// Create namespace and database
+		ensure_namespace_and_database(&client, &addr, &ns, &db).await?;
+
+		// Define a bucket, and an API which reads and writes its files
+		{
+			let res = client
+				.post(format!("http://{addr}/sql"))
+				.basic_auth(USER, Some(PASS))
+				.body(
+					r#"
+					DEFINE BUCKET test BACKEND "memory";
+					DEFINE API "/files/:name" FOR get, put THEN {
+						{ status: 200, body: type::file("test", $request.params.name) }
+					};
+					"#,
+				)
+				.send()
+				.await?;
+			assert_eq!(res.status(), 200, "body: {}", res.text().await?);
+		}
+
+		let url = &format!("http://{addr}/api/{ns}/{db}/files/a.txt");
+
+		// An upload streams the request body into the returned file
+		{
+			let res = client
+				.put(url)
+				.basic_auth(USER, Some(PASS))
+				.header(header::EXPECT, "100-continue")
+				.body("0123456789")
+				.send()
+				.await?;
+			assert_eq!(res.status(), 200, "body: {}", res.text().await?);
+		}
+
+		// The whole file is sent without a range
+		{
+			let res = client.get(url).basic_auth(USER, Some(PASS)).send().await?;
+			assert_eq!(res.status(), 200);
+			assert_eq!(res.headers()[header::ACCEPT_RANGES], "bytes");
+			assert_eq!(res.headers()[header::CONTENT_LENGTH], "10");
+			assert_eq!(res.text().await?, "0123456789");
+		}
+
+		// A range only sends the bytes within the range
+		{
+			let res = client
+				.get(url)
+				.basic_auth(USER, Some(PASS))
+				.header(header::RANGE, "bytes=2-5")
+				.send()
+				.await?;
+			assert_eq!(res.status(), 206);
+			assert_eq!(res.headers()[header::CONTENT_RANGE], "bytes 2-5/10");
+			assert_eq!(res.text().await?, "2345");
+		}
+
+		// An open-ended range sends the rest of the file
+		{
+			let res = client
+				.get(url)
+				.basic_auth(USER, Some(PASS))
+				.header(header::RANGE, "bytes=7-")
+				.send()
+				.await?;
+			assert_eq!(res.status(), 206);
+			assert_eq!(res.headers()[header::CONTENT_RANGE], "bytes 7-9/10");
+			assert_eq!(res.text().await?, "789");
+		}
+
+		// A range beyond the end of the file can not be satisfied
+		{
+			let res = client
+				.get(url)
+				.basic_auth(USER, Some(PASS))
+				.header(header::RANGE, "bytes=20-")
+				.send()
+				.await?;
+			assert_eq!(res.status(), 416);
+			assert_eq!(res.headers()[header::CONTENT_RANGE], "bytes */10");
+		}
+
+		// A file which does not exist is not found
+		{
+			let res = client
+				.get(format!("http://{addr}/api/{ns}/{db}/files/b.txt"))
+				.basic_auth(USER, Some(PASS))
+				.send()
+				.await?;
+			assert_eq!(res.status(), 404, "body: {}", res.text().await?);
+		}
+
+		Ok(())
+	}
+
 	#[test(tokio::test)]
 	async fn arbitrary_query_capabilities() {
 		// Allow system
@@ -2472,4 +2627,49 @@ mod http_integration {
 			assert!(res.contains("The HTTP route 'sql' is forbidden"), "body: {}", res);
 		}
 	}
+
+	#[cfg(unix)]
+	#[test(tokio::test)]
+	async fn unix_socket() -> Result<(), Box<dyn std::error::Error>> {
+		use std::os::unix::fs::PermissionsExt;
+
+		let socket = std::env::temp_dir().join(format!("surreal-{}.sock", Ulid::new()));
+		let (_addr, _server) = common::start_server(StartServerArguments {
+			args: format!("--bind unix:{} --unix-socket-mode 660", socket.display()),
+			..Default::default()
+		})
+		.await
+		.unwrap();
+
+		// The socket is created with the configured permissions
+		let mode = std::fs::metadata(&socket)?.permissions().mode();
+		assert_eq!(mode & 0o777, 0o660);
+
+		// Prepare HTTP client
+		let mut headers = reqwest::header::HeaderMap::new();
+		headers.insert(header::ACCEPT, "application/json".parse()?);
+		let client = reqwest::Client::builder()
+			.unix_socket(socket.clone())
+			.default_headers(headers)
+			.build()?;
+
+		// The router is served over the socket
+		let res = client.get("http://localhost/health").send().await?;
+		assert_eq!(res.status(), 200);
+		let res = client
+			.post("http://localhost/sql")
+			.basic_auth(USER, Some(PASS))
+			.body("RETURN 1")
+			.send()
+			.await?;
+		assert_eq!(res.status(), 200);
+		let body: serde_json::Value = serde_json::from_str(&res.text().await?)?;
+		assert_eq!(body[0]["result"], json!(1), "body: {body}");
+
+		// Authentication is still required over the socket
+		let res = client.post("http://localhost/sql").body("INFO FOR ROOT").send().await?;
+		assert_eq!(res.status(), 403, "body: {}", res.text().await?);
+
+		Ok(())
+	}
 }